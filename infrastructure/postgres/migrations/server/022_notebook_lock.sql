@@ -0,0 +1,7 @@
+-- Migration 022: Notebook lock (read-only mode)
+-- Owners can freeze a notebook for review; locked notebooks reject new
+-- entries and revisions but remain readable.
+
+ALTER TABLE notebooks ADD COLUMN IF NOT EXISTS is_locked BOOLEAN NOT NULL DEFAULT FALSE;
+
+COMMENT ON COLUMN notebooks.is_locked IS 'When true, writes (new entries and revisions) are rejected';
@@ -0,0 +1,8 @@
+-- Migration 023: Entry content compression at rest
+-- Large, compressible entry content is stored zstd-compressed. The codec is
+-- recorded per row so raw and compressed entries can coexist; readers
+-- decompress transparently.
+
+ALTER TABLE entries ADD COLUMN IF NOT EXISTS compression TEXT;
+
+COMMENT ON COLUMN entries.compression IS 'Codec applied to content (NULL = stored raw, ''zstd'' = zstd-compressed)';
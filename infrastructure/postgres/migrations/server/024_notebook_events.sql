@@ -0,0 +1,19 @@
+-- Migration 024: Durable notebook change log
+-- Every mutation of a notebook (entry writes and revisions, renames, locks,
+-- access grants and revocations) appends one row here in the same
+-- transaction as the change itself. `seq` is gapless per notebook, so
+-- subscribers can resume from the last event they saw without missing or
+-- duplicating changes.
+
+CREATE TABLE IF NOT EXISTS notebook_events (
+    notebook_id UUID NOT NULL REFERENCES notebooks(id) ON DELETE CASCADE,
+    seq BIGINT NOT NULL,
+    event_type TEXT NOT NULL,
+    payload JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (notebook_id, seq)
+);
+
+COMMENT ON TABLE notebook_events IS 'Append-only per-notebook change log feeding observe and event streams';
+COMMENT ON COLUMN notebook_events.seq IS 'Gapless per-notebook event sequence, starting at 1';
+COMMENT ON COLUMN notebook_events.event_type IS 'Event kind: entry, notebook_renamed, notebook_locked, access_granted, access_revoked';
@@ -0,0 +1,13 @@
+-- Migration 025: Entry content encryption for private notebooks
+-- An encrypted notebook has its own random data key, stored wrapped by the
+-- server master key. Entry content in such notebooks is compressed (if
+-- worthwhile) and then sealed with the data key; the cipher is recorded per
+-- row and readers decrypt transparently.
+
+ALTER TABLE notebooks ADD COLUMN IF NOT EXISTS encrypted BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE notebooks ADD COLUMN IF NOT EXISTS data_key BYTEA;
+ALTER TABLE entries ADD COLUMN IF NOT EXISTS encryption TEXT;
+
+COMMENT ON COLUMN notebooks.encrypted IS 'Whether entry content in this notebook is encrypted at rest';
+COMMENT ON COLUMN notebooks.data_key IS 'Notebook data key wrapped by the server master key (NULL for unencrypted notebooks)';
+COMMENT ON COLUMN entries.encryption IS 'Cipher applied to content (NULL = plaintext, ''aes-256-gcm'' = sealed with the notebook data key)';
@@ -0,0 +1,18 @@
+-- Migration 026: Per-notebook entropy alert webhooks
+-- When a write pushes a notebook's recent entropy above its threshold, the
+-- server POSTs an alert to the webhook URL. `triggered` records that the
+-- notebook is above the threshold and the alert was claimed, so one crossing
+-- fires one alert; it resets once entropy drops back to the threshold.
+
+CREATE TABLE IF NOT EXISTS notebook_alerts (
+    notebook_id UUID PRIMARY KEY REFERENCES notebooks(id) ON DELETE CASCADE,
+    entropy_threshold DOUBLE PRECISION NOT NULL CHECK (entropy_threshold >= 0),
+    webhook_url TEXT NOT NULL,
+    triggered BOOLEAN NOT NULL DEFAULT FALSE,
+    created TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_fired TIMESTAMPTZ
+);
+
+COMMENT ON TABLE notebook_alerts IS 'Entropy threshold webhooks, at most one per notebook';
+COMMENT ON COLUMN notebook_alerts.entropy_threshold IS 'Recent entropy (sum of catalog shift over the last 10 entries) above which an alert fires';
+COMMENT ON COLUMN notebook_alerts.triggered IS 'Whether entropy is above the threshold and the alert for this crossing was claimed';
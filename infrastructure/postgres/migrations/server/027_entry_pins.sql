@@ -0,0 +1,7 @@
+-- Migration 027: Pinned entries
+-- Authors pin important entries so catalog summaries always list them,
+-- however tight the token budget.
+
+ALTER TABLE entries ADD COLUMN IF NOT EXISTS pinned BOOLEAN NOT NULL DEFAULT FALSE;
+
+COMMENT ON COLUMN entries.pinned IS 'When true, the entry is always listed among its cluster''s representatives in catalogs';
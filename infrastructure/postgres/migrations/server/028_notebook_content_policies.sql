@@ -0,0 +1,15 @@
+-- Migration 028: Per-notebook content-type policies
+-- Restricts which content types may be written to a notebook, on top of the
+-- server-wide policy. Patterns are globs such as 'text/*'. An empty allow
+-- list admits every type not denied.
+
+CREATE TABLE IF NOT EXISTS notebook_content_policies (
+    notebook_id UUID PRIMARY KEY REFERENCES notebooks(id) ON DELETE CASCADE,
+    allowed_content_types TEXT[] NOT NULL DEFAULT '{}',
+    denied_content_types TEXT[] NOT NULL DEFAULT '{}',
+    updated TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+COMMENT ON TABLE notebook_content_policies IS 'Content-type allow and deny globs, at most one policy per notebook';
+COMMENT ON COLUMN notebook_content_policies.allowed_content_types IS 'Globs of admitted content types; empty admits all';
+COMMENT ON COLUMN notebook_content_policies.denied_content_types IS 'Globs of rejected content types; checked before the allow list';
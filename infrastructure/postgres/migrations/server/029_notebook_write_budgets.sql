@@ -0,0 +1,15 @@
+-- Migration 029: Per-notebook write budgets
+-- Caps how much entropy one author may add to a notebook within a window.
+-- An author over budget must wait between writes; see the server's write
+-- throttle. Overrides the server-wide budget.
+
+CREATE TABLE IF NOT EXISTS notebook_write_budgets (
+    notebook_id UUID PRIMARY KEY REFERENCES notebooks(id) ON DELETE CASCADE,
+    entropy_budget DOUBLE PRECISION NOT NULL CHECK (entropy_budget > 0),
+    window_secs INTEGER NOT NULL CHECK (window_secs > 0),
+    updated TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+COMMENT ON TABLE notebook_write_budgets IS 'Entropy budget per author and window, at most one per notebook';
+COMMENT ON COLUMN notebook_write_budgets.entropy_budget IS 'Summed entry entropy an author may add per window before being slowed';
+COMMENT ON COLUMN notebook_write_budgets.window_secs IS 'Length of the sliding window, in seconds';
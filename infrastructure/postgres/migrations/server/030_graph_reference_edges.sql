@@ -0,0 +1,19 @@
+-- Migration 030: Batched reference edges
+-- Depends on: 003_graph.sql (add_reference_edge)
+-- Adds all reference edges of an entry in one call, so inserting an entry
+-- with many references takes one round trip instead of one per reference.
+
+CREATE OR REPLACE FUNCTION add_reference_edges(
+    p_from_entry_id UUID,
+    p_to_entry_ids UUID[]
+) RETURNS void AS $fn$
+DECLARE
+    to_entry_id UUID;
+BEGIN
+    FOREACH to_entry_id IN ARRAY p_to_entry_ids LOOP
+        PERFORM add_reference_edge(p_from_entry_id, to_entry_id);
+    END LOOP;
+END;
+$fn$ LANGUAGE plpgsql;
+
+COMMENT ON FUNCTION add_reference_edges(UUID, UUID[]) IS 'Adds a references edge from one entry to each of the given entries, in order';
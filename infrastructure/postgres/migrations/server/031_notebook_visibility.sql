@@ -0,0 +1,7 @@
+-- Migration 031: Notebook visibility
+-- Owners can make a notebook public; public notebooks can be read and
+-- browsed without authentication. Writes always require authentication.
+
+ALTER TABLE notebooks ADD COLUMN IF NOT EXISTS is_public BOOLEAN NOT NULL DEFAULT FALSE;
+
+COMMENT ON COLUMN notebooks.is_public IS 'When true, reads are allowed without authentication';
@@ -0,0 +1,19 @@
+-- Migration 032: Unique notebook names per owner
+-- Clients resolve notebooks by name (e.g. the CLI's --notebook <name>), which
+-- is ambiguous when one owner has two notebooks of the same name.
+
+-- Existing duplicates keep the oldest notebook's name; later ones get a
+-- suffix with the start of their id so the index below can be built.
+UPDATE notebooks n
+SET name = n.name || ' (' || left(n.id::text, 8) || ')'
+FROM (
+    SELECT id,
+           row_number() OVER (PARTITION BY owner_id, name ORDER BY created, id) AS position
+    FROM notebooks
+) ranked
+WHERE n.id = ranked.id
+  AND ranked.position > 1;
+
+CREATE UNIQUE INDEX IF NOT EXISTS notebooks_owner_name_key ON notebooks (owner_id, name);
+
+COMMENT ON INDEX notebooks_owner_name_key IS 'Notebook names are unique per owner';
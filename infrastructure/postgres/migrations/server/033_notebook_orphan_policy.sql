@@ -0,0 +1,8 @@
+-- Migration 033: Notebook orphan policy
+-- How writes of orphan entries (entries that join no cluster and reference
+-- nothing) are handled: stored as before, stored with a warning, or rejected.
+
+ALTER TABLE notebooks ADD COLUMN IF NOT EXISTS orphan_policy TEXT NOT NULL DEFAULT 'allow'
+    CHECK (orphan_policy IN ('allow', 'warn', 'reject'));
+
+COMMENT ON COLUMN notebooks.orphan_policy IS 'allow, warn or reject writes of orphan entries';
@@ -0,0 +1,8 @@
+-- Migration 034: Entry cost computed flag
+-- Whether an entry's stored integration cost was actually computed, or is a
+-- zero fallback written because computation failed or timed out. Entries
+-- written before this migration are assumed to carry computed costs.
+
+ALTER TABLE entries ADD COLUMN IF NOT EXISTS cost_computed BOOLEAN NOT NULL DEFAULT TRUE;
+
+COMMENT ON COLUMN entries.cost_computed IS 'false while the stored integration cost is a zero fallback';
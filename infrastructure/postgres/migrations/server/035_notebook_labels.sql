@@ -0,0 +1,20 @@
+-- Migration 035: Notebook labels
+-- Authors tag the notebooks they can access (e.g. "project-alpha",
+-- "archived") to organize their notebook list. Labels belong to the author
+-- who applied them: others don't see them and they grant no access.
+
+CREATE TABLE IF NOT EXISTS notebook_labels (
+    notebook_id UUID NOT NULL REFERENCES notebooks(id) ON DELETE CASCADE,
+    author_id BYTEA NOT NULL REFERENCES authors(id) ON DELETE CASCADE,
+    label TEXT NOT NULL,
+    created TIMESTAMPTZ NOT NULL DEFAULT now(),
+
+    CONSTRAINT label_author_id_length CHECK (octet_length(author_id) = 32),
+
+    PRIMARY KEY (notebook_id, author_id, label)
+);
+
+CREATE INDEX IF NOT EXISTS idx_notebook_labels_author ON notebook_labels(author_id, label);
+
+COMMENT ON TABLE notebook_labels IS 'Per-author labels organizing notebooks; not an access control list';
+COMMENT ON COLUMN notebook_labels.author_id IS 'Author who applied the label and alone sees it';
@@ -2,18 +2,21 @@
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use clap::Args;
+use clap::{Args, ValueEnum};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{HumanReadable, make_request, output, truncate};
+use super::{
+    ApiClient, HumanReadable, OutputFormat, make_request, output, resolve_notebook, truncate,
+};
+use crate::table::Table;
 
 /// Arguments for the browse command.
 #[derive(Args)]
 pub struct BrowseArgs {
-    /// Notebook ID to browse
-    pub notebook_id: Uuid,
+    /// Notebook ID or name to browse
+    pub notebook: String,
 
     /// Search query to filter results
     #[arg(short, long)]
@@ -22,12 +25,47 @@ pub struct BrowseArgs {
     /// Maximum tokens for the response (default: 4000)
     #[arg(long)]
     pub max_tokens: Option<usize>,
+
+    /// Maximum number of clusters to return
+    #[arg(long)]
+    pub cluster_limit: Option<usize>,
+
+    /// Number of clusters to skip
+    #[arg(long)]
+    pub cluster_offset: Option<usize>,
+
+    /// Cluster ordering
+    #[arg(long, value_enum)]
+    pub sort: Option<ClusterSort>,
+}
+
+/// Cluster ordering for the catalog.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ClusterSort {
+    /// Highest cumulative cost first
+    Cost,
+    /// Most entries first
+    Size,
+    /// Most recently modified first
+    Recency,
+}
+
+impl ClusterSort {
+    fn as_str(self) -> &'static str {
+        match self {
+            ClusterSort::Cost => "cost",
+            ClusterSort::Size => "size",
+            ClusterSort::Recency => "recency",
+        }
+    }
 }
 
 /// Response from browsing a notebook.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct BrowseResponse {
     pub catalog: Vec<ClusterSummary>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_clusters: Option<usize>,
     pub notebook_entropy: f64,
     pub total_entries: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -48,6 +86,31 @@ pub struct ClusterSummary {
     pub entry_ids: Vec<Uuid>,
 }
 
+impl BrowseResponse {
+    /// Render the catalog clusters as an aligned table.
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(["TOPIC", "ENTRIES", "COST", "SEQ", "SUMMARY"]);
+
+        for cluster in &self.catalog {
+            // Summaries may span lines; keep each row on one line.
+            let summary = cluster
+                .summary
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            table.add_row([
+                cluster.topic.clone(),
+                cluster.entry_count.to_string(),
+                format!("{:.2}", cluster.cumulative_cost),
+                cluster.latest_sequence.to_string(),
+                truncate(&summary, 60),
+            ]);
+        }
+
+        table
+    }
+}
+
 impl HumanReadable for BrowseResponse {
     fn print_human(&self) {
         println!("{}", "Notebook Catalog".green().bold());
@@ -55,6 +118,14 @@ impl HumanReadable for BrowseResponse {
         println!();
 
         println!("  {} {}", "Total Entries:".cyan(), self.total_entries);
+        if let Some(total) = self.total_clusters {
+            println!(
+                "  {} {} (showing {})",
+                "Total Clusters:".cyan(),
+                total,
+                self.catalog.len()
+            );
+        }
         println!(
             "  {} {:.1}",
             "Notebook Entropy:".cyan(),
@@ -66,33 +137,10 @@ impl HumanReadable for BrowseResponse {
 
         println!();
         println!("{}", "Topic Clusters:".yellow());
-        println!("{}", "-".repeat(70));
         println!();
 
-        for cluster in &self.catalog {
-            // Topic header
-            println!(
-                "{} {} {}",
-                ">>".blue().bold(),
-                cluster.topic.bold(),
-                format!("({} entries)", cluster.entry_count).dimmed()
-            );
-
-            // Summary (truncated for display)
-            let summary = truncate(&cluster.summary, 200);
-            for line in summary.lines().take(3) {
-                println!("   {}", line);
-            }
-
-            // Metadata
-            println!(
-                "   {} {:.2}  {} {}",
-                "Cost:".dimmed(),
-                cluster.cumulative_cost,
-                "Seq:".dimmed(),
-                cluster.latest_sequence
-            );
-
+        if !self.catalog.is_empty() {
+            print!("{}", self.to_table());
             println!();
         }
 
@@ -103,8 +151,15 @@ impl HumanReadable for BrowseResponse {
 }
 
 /// Execute the browse command.
-pub async fn execute(client: &reqwest::Client, base_url: &str, human: bool, args: BrowseArgs) -> Result<()> {
-    let mut url = format!("{}/notebooks/{}/browse", base_url, args.notebook_id);
+pub async fn execute(
+    client: &ApiClient,
+    base_url: &str,
+    format: OutputFormat,
+    args: BrowseArgs,
+) -> Result<()> {
+    let notebook_id = resolve_notebook(client, base_url, &args.notebook).await?;
+
+    let mut url = format!("{}/notebooks/{}/browse", base_url, notebook_id);
 
     // Build query string
     let mut params = Vec::new();
@@ -114,13 +169,22 @@ pub async fn execute(client: &reqwest::Client, base_url: &str, human: bool, args
     if let Some(max_tokens) = args.max_tokens {
         params.push(format!("max_tokens={}", max_tokens));
     }
+    if let Some(limit) = args.cluster_limit {
+        params.push(format!("cluster_limit={}", limit));
+    }
+    if let Some(offset) = args.cluster_offset {
+        params.push(format!("cluster_offset={}", offset));
+    }
+    if let Some(sort) = args.sort {
+        params.push(format!("sort={}", sort.as_str()));
+    }
     if !params.is_empty() {
         url = format!("{}?{}", url, params.join("&"));
     }
 
     let response: BrowseResponse = make_request(client, client.get(&url)).await?;
 
-    output(&response, human)
+    output(&response, format)
 }
 
 /// URL encoding helper.
@@ -143,3 +207,53 @@ mod urlencoding {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_response() -> BrowseResponse {
+        BrowseResponse {
+            catalog: vec![ClusterSummary {
+                topic: "rust, async".to_string(),
+                summary: "Tokio tasks: a primer.\nSecond line".to_string(),
+                entry_count: 4,
+                cumulative_cost: 1.25,
+                latest_sequence: 42,
+                entry_ids: vec![Uuid::nil()],
+            }],
+            total_clusters: Some(1),
+            notebook_entropy: 2.5,
+            total_entries: 4,
+            query_matches: Some(1),
+            generated: None,
+        }
+    }
+
+    #[test]
+    fn test_browse_yaml_round_trip() {
+        let response = sample_response();
+
+        let yaml = serde_yaml::to_string(&response).unwrap();
+        let parsed: BrowseResponse = serde_yaml::from_str(&yaml).unwrap();
+
+        assert_eq!(parsed.total_entries, 4);
+        assert_eq!(parsed.notebook_entropy, 2.5);
+        assert_eq!(parsed.query_matches, Some(1));
+        assert_eq!(parsed.total_clusters, Some(1));
+        assert_eq!(parsed.catalog.len(), 1);
+        assert_eq!(parsed.catalog[0].topic, "rust, async");
+        assert_eq!(parsed.catalog[0].summary, response.catalog[0].summary);
+        assert_eq!(parsed.catalog[0].latest_sequence, 42);
+        assert_eq!(parsed.catalog[0].entry_ids, vec![Uuid::nil()]);
+    }
+
+    #[test]
+    fn test_browse_table_keeps_rows_on_one_line() {
+        let rendered = sample_response().to_table().to_string();
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[2].ends_with("Tokio tasks: a primer. Second line"));
+    }
+}
@@ -7,19 +7,24 @@ use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{HumanReadable, format_timestamp, make_request, output};
+use super::{ApiClient, HumanReadable, OutputFormat, format_timestamp, make_request, output};
 
 /// Arguments for the create command.
 #[derive(Args)]
 pub struct CreateArgs {
     /// Name for the new notebook
     pub name: String,
+
+    /// Encrypt entry content at rest (the server needs a master key)
+    #[arg(long)]
+    pub encrypted: bool,
 }
 
 /// Request body for creating a notebook.
 #[derive(Serialize)]
 struct CreateNotebookRequest {
     name: String,
+    encrypted: bool,
 }
 
 /// Response from creating a notebook.
@@ -29,6 +34,8 @@ pub struct CreateNotebookResponse {
     pub name: String,
     pub owner: String,
     pub created: DateTime<Utc>,
+    #[serde(default)]
+    pub encrypted: bool,
 }
 
 impl HumanReadable for CreateNotebookResponse {
@@ -43,17 +50,28 @@ impl HumanReadable for CreateNotebookResponse {
             "Created:".cyan(),
             format_timestamp(&self.created)
         );
+        if self.encrypted {
+            println!("  {} yes", "Encrypted:".cyan());
+        }
     }
 }
 
 /// Execute the create command.
-pub async fn execute(client: &reqwest::Client, base_url: &str, human: bool, args: CreateArgs) -> Result<()> {
+pub async fn execute(
+    client: &ApiClient,
+    base_url: &str,
+    format: OutputFormat,
+    args: CreateArgs,
+) -> Result<()> {
     let url = format!("{}/notebooks", base_url);
 
-    let request_body = CreateNotebookRequest { name: args.name };
+    let request_body = CreateNotebookRequest {
+        name: args.name,
+        encrypted: args.encrypted,
+    };
 
     let response: CreateNotebookResponse =
         make_request(client, client.post(&url).json(&request_body)).await?;
 
-    output(&response, human)
+    output(&response, format)
 }
@@ -6,13 +6,13 @@ use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{HumanReadable, make_request, output};
+use super::{ApiClient, HumanReadable, OutputFormat, make_request, output, resolve_notebook};
 
 /// Arguments for the delete command.
 #[derive(Args)]
 pub struct DeleteArgs {
-    /// Notebook ID to delete
-    pub notebook_id: Uuid,
+    /// Notebook ID or name to delete
+    pub notebook: String,
 
     /// Skip confirmation prompt (for non-interactive use)
     #[arg(long, short = 'y')]
@@ -35,13 +35,25 @@ impl HumanReadable for DeleteNotebookResponse {
 }
 
 /// Execute the delete command.
-pub async fn execute(client: &reqwest::Client, base_url: &str, human: bool, args: DeleteArgs) -> Result<()> {
+pub async fn execute(
+    client: &ApiClient,
+    base_url: &str,
+    format: OutputFormat,
+    args: DeleteArgs,
+) -> Result<()> {
+    let notebook_id = resolve_notebook(client, base_url, &args.notebook).await?;
+
     // Confirmation prompt for interactive use
-    if human && !args.yes {
+    if format == OutputFormat::Table && !args.yes {
+        let target = if Uuid::parse_str(&args.notebook).is_ok() {
+            notebook_id.to_string()
+        } else {
+            format!("{:?} ({})", args.notebook, notebook_id)
+        };
         eprint!(
             "{} Are you sure you want to delete notebook {}? [y/N] ",
             "Warning:".yellow().bold(),
-            args.notebook_id
+            target
         );
 
         use std::io::Write;
@@ -56,9 +68,9 @@ pub async fn execute(client: &reqwest::Client, base_url: &str, human: bool, args
         }
     }
 
-    let url = format!("{}/notebooks/{}", base_url, args.notebook_id);
+    let url = format!("{}/notebooks/{}", base_url, notebook_id);
 
     let response: DeleteNotebookResponse = make_request(client, client.delete(&url)).await?;
 
-    output(&response, human)
+    output(&response, format)
 }
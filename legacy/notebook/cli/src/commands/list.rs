@@ -7,7 +7,8 @@ use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{HumanReadable, format_timestamp, make_request, output};
+use super::{ApiClient, HumanReadable, OutputFormat, make_request, output, truncate};
+use crate::table::Table;
 
 /// Arguments for the list command.
 #[derive(Args)]
@@ -61,72 +62,58 @@ pub struct BootstrapParticipant {
     pub write: bool,
 }
 
-impl HumanReadable for ListNotebooksResponse {
-    fn print_human(&self) {
-        println!("{}", "Accessible Notebooks".green().bold());
-        println!("{}", "=".repeat(80));
-        println!();
-
-        if self.notebooks.is_empty() {
-            println!("  {}", "(No notebooks accessible)".dimmed());
-            return;
-        }
+impl ListNotebooksResponse {
+    /// Render the notebooks as an aligned table.
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new([
+            "", "NAME", "ID", "OWNER", "PERMS", "ENTRIES", "ENTROPY", "LAST SEQ",
+        ]);
 
         for notebook in &self.notebooks {
-            // Ownership indicator
-            let owner_indicator = if notebook.is_owner {
-                "*".yellow()
-            } else {
-                " ".normal()
-            };
-
             // Permissions - handle both formats
             let perms = if let Some(ref p) = notebook.permissions {
                 format!(
-                    "[{}{}]",
+                    "{}{}",
                     if p.read { "R" } else { "-" },
                     if p.write { "W" } else { "-" }
                 )
             } else if !notebook.participants.is_empty() {
                 // Bootstrap format - show participant count
-                format!("[{} participants]", notebook.participants.len())
+                format!("{} participants", notebook.participants.len())
             } else {
                 String::new()
             };
 
-            println!(
-                "  {} {} {}",
-                owner_indicator,
-                notebook.name.bold(),
-                perms.dimmed()
-            );
-            println!("    {} {}", "ID:".cyan(), notebook.id);
-            println!("    {} {}", "Owner:".cyan(), notebook.owner);
-
-            // Show stats if available (Rust server format)
-            if notebook.total_entries > 0 || notebook.total_entropy > 0.0 {
-                println!(
-                    "    {} {} entries, {:.1} entropy, {} participants",
-                    "Stats:".cyan(),
-                    notebook.total_entries,
-                    notebook.total_entropy,
-                    notebook.participant_count
-                );
-                println!(
-                    "    {} sequence {}",
-                    "Last Activity:".cyan(),
-                    notebook.last_activity_sequence
-                );
-            }
+            table.add_row([
+                if notebook.is_owner { "*" } else { "" }.to_string(),
+                notebook.name.clone(),
+                notebook.id.to_string(),
+                truncate(&notebook.owner, 16),
+                perms,
+                notebook.total_entries.to_string(),
+                format!("{:.1}", notebook.total_entropy),
+                notebook.last_activity_sequence.to_string(),
+            ]);
+        }
 
-            // Show created timestamp if available (bootstrap format)
-            if let Some(created) = &notebook.created {
-                println!("    {} {}", "Created:".cyan(), format_timestamp(created));
-            }
+        table
+    }
+}
 
-            println!();
+impl HumanReadable for ListNotebooksResponse {
+    fn print_human(&self) {
+        println!("{}", "Accessible Notebooks".green().bold());
+        println!("{}", "=".repeat(80));
+        println!();
+
+        if self.notebooks.is_empty() {
+            println!("  {}", "(No notebooks accessible)".dimmed());
+            return;
         }
 
+        print!("{}", self.to_table());
+
+        println!();
         println!("  {} {}", "Total:".cyan(), self.notebooks.len());
         println!();
         println!("  {}", "* = You are the owner".dimmed());
@@ -134,10 +121,69 @@ impl HumanReadable for ListNotebooksResponse {
 }
 
 /// Execute the list command.
-pub async fn execute(client: &reqwest::Client, base_url: &str, human: bool, _args: ListArgs) -> Result<()> {
+pub async fn execute(
+    client: &ApiClient,
+    base_url: &str,
+    format: OutputFormat,
+    _args: ListArgs,
+) -> Result<()> {
     let url = format!("{}/notebooks", base_url);
 
     let response: ListNotebooksResponse = make_request(client, client.get(&url)).await?;
 
-    output(&response, human)
+    output(&response, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notebook(name: &str, is_owner: bool, total_entries: i64) -> NotebookSummary {
+        NotebookSummary {
+            id: Uuid::nil(),
+            name: name.to_string(),
+            owner: "ab".repeat(32),
+            is_owner,
+            permissions: Some(NotebookPermissions {
+                read: true,
+                write: is_owner,
+            }),
+            total_entries,
+            total_entropy: 1.5,
+            last_activity_sequence: 7,
+            participant_count: 1,
+            participants: Vec::new(),
+            created: None,
+        }
+    }
+
+    #[test]
+    fn test_list_table_aligns_columns() {
+        let response = ListNotebooksResponse {
+            notebooks: vec![
+                notebook("research", true, 3),
+                notebook("a much longer name", false, 1200),
+            ],
+        };
+
+        let rendered = response.to_table().to_string();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 4);
+
+        // Every column starts at the same offset on every line.
+        for column in ["ID", "OWNER", "PERMS", "ENTRIES", "ENTROPY", "LAST SEQ"] {
+            let offset = lines[0].find(column).unwrap();
+            for line in &lines[2..] {
+                assert_ne!(
+                    line.as_bytes()[offset],
+                    b' ',
+                    "column {} misaligned",
+                    column
+                );
+                assert_eq!(line.as_bytes()[offset - 1], b' ');
+            }
+        }
+        assert!(lines[2].starts_with("*  research "));
+        assert!(lines[3].starts_with("   a much longer name  "));
+    }
 }
@@ -16,9 +16,37 @@ pub mod revise;
 pub mod share;
 pub mod write;
 
+use std::ops::Deref;
+use std::time::Duration;
+
 use anyhow::Result;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use reqwest::{Method, StatusCode};
 use serde::Serialize;
+use uuid::Uuid;
+
+use list::{ListNotebooksResponse, NotebookSummary};
+
+/// Default per-request timeout in seconds.
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Default number of retries for retryable requests.
+pub const DEFAULT_RETRIES: u32 = 0;
+
+/// Header marking a write as safe to replay.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Server API version this CLI was built against.
+pub const SUPPORTED_API_VERSION: u32 = 1;
+
+/// Marker appended to text cut short for display.
+pub const DEFAULT_ELLIPSIS: &str = "...";
+
+/// Delay before the first retry; doubled on each subsequent attempt.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+
+/// Upper bound on the delay between two attempts.
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(8);
 
 /// Common error type for HTTP requests.
 #[derive(Debug, thiserror::Error)]
@@ -27,12 +55,88 @@ pub enum CliError {
     Http(#[from] reqwest::Error),
 
     #[error("Server error ({status}): {message}")]
-    Server { status: u16, message: String },
+    Server {
+        status: u16,
+        /// Stable machine-readable code, e.g. `NOTEBOOK_NOT_FOUND`, when the
+        /// server sent one.
+        code: Option<String>,
+        message: String,
+    },
+
+    #[error("No accessible notebook named {0:?}")]
+    NotebookNotFound(String),
+
+    #[error("Notebook name {name:?} is ambiguous; pass one of these IDs instead: {}", join_ids(.ids))]
+    AmbiguousNotebook { name: String, ids: Vec<Uuid> },
+}
+
+fn join_ids(ids: &[Uuid]) -> String {
+    ids.iter()
+        .map(Uuid::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Retry behaviour for requests sent through [`make_request`].
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Number of additional attempts after the first one.
+    pub retries: u32,
+    /// Delay before the first retry.
+    pub base_delay: Duration,
+    /// Upper bound on the delay between attempts.
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Create a policy with the default backoff schedule.
+    pub fn new(retries: u32) -> Self {
+        Self {
+            retries,
+            base_delay: RETRY_BASE_DELAY,
+            max_delay: RETRY_MAX_DELAY,
+        }
+    }
+
+    /// Exponential backoff delay before retry number `attempt` (0-based).
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+/// HTTP client used by all commands.
+///
+/// Wraps a `reqwest::Client` together with the retry policy so that
+/// commands can keep building requests with `client.get(..)` etc.
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    http: reqwest::Client,
+    retry: RetryPolicy,
+}
+
+impl ApiClient {
+    /// Returns the retry policy applied by [`make_request`].
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry
+    }
+}
+
+impl Deref for ApiClient {
+    type Target = reqwest::Client;
+
+    fn deref(&self) -> &Self::Target {
+        &self.http
+    }
 }
 
 /// Build an HTTP client, optionally configured with a Bearer token.
-pub fn build_client(token: Option<&str>) -> Result<reqwest::Client> {
-    let mut builder = reqwest::Client::builder();
+pub fn build_client(
+    token: Option<&str>,
+    timeout: Duration,
+    retry: RetryPolicy,
+) -> Result<ApiClient> {
+    let mut builder = reqwest::Client::builder().timeout(timeout);
 
     if let Some(token) = token {
         let mut headers = HeaderMap::new();
@@ -42,15 +146,30 @@ pub fn build_client(token: Option<&str>) -> Result<reqwest::Client> {
         builder = builder.default_headers(headers);
     }
 
-    Ok(builder.build()?)
+    Ok(ApiClient {
+        http: builder.build()?,
+        retry,
+    })
 }
 
-/// Print output in JSON or human-readable format.
-pub fn output<T: Serialize + HumanReadable>(value: &T, human: bool) -> Result<()> {
-    if human {
-        value.print_human();
-    } else {
-        println!("{}", serde_json::to_string_pretty(value)?);
+/// Output format selected with `--format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Pretty-printed JSON (default, for agents)
+    #[default]
+    Json,
+    /// YAML
+    Yaml,
+    /// Human-readable text with aligned tables
+    Table,
+}
+
+/// Print output in the selected format.
+pub fn output<T: Serialize + HumanReadable>(value: &T, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(value)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(value)?),
+        OutputFormat::Table => value.print_human(),
     }
     Ok(())
 }
@@ -60,12 +179,63 @@ pub trait HumanReadable {
     fn print_human(&self);
 }
 
+/// Whether a request may be replayed without side effects.
+///
+/// Safe methods are always retryable; writes only when they carry an
+/// idempotency key.
+fn is_retryable_request(request: &reqwest::Request) -> bool {
+    matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    ) || request.headers().contains_key(IDEMPOTENCY_KEY_HEADER)
+}
+
+/// Whether a transport error is worth another attempt.
+fn is_transient_error(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout()
+}
+
+/// Whether a response status is worth another attempt.
+fn is_transient_status(status: StatusCode) -> bool {
+    status.is_server_error()
+}
+
 /// Make an HTTP request and handle common error cases.
+///
+/// Retryable requests (see [`is_retryable_request`]) are retried with
+/// exponential backoff on connection errors, timeouts and 5xx responses,
+/// up to the client's configured number of retries.
 pub async fn make_request<T: serde::de::DeserializeOwned>(
-    _client: &reqwest::Client,
+    client: &ApiClient,
     request: reqwest::RequestBuilder,
 ) -> Result<T, CliError> {
-    let response = request.send().await?;
+    let request = request.build()?;
+    let policy = client.retry_policy();
+    let max_retries = if is_retryable_request(&request) {
+        policy.retries
+    } else {
+        0
+    };
+
+    let mut attempt = 0;
+    let response = loop {
+        // The last attempt (or a request whose body cannot be cloned) sends
+        // the original request and surfaces whatever comes back.
+        let Some(current) = request.try_clone().filter(|_| attempt < max_retries) else {
+            break client.execute(request).await?;
+        };
+
+        match client.execute(current).await {
+            Ok(response) if is_transient_status(response.status()) => {}
+            Ok(response) => break response,
+            Err(e) if is_transient_error(&e) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        tokio::time::sleep(policy.delay_for(attempt)).await;
+        attempt += 1;
+    };
+
     let status = response.status();
 
     if status.is_success() {
@@ -73,24 +243,120 @@ pub async fn make_request<T: serde::de::DeserializeOwned>(
         Ok(body)
     } else {
         let body = response.text().await.unwrap_or_default();
+        let (code, message) = parse_error_body(&body);
+        Err(CliError::Server {
+            status: status.as_u16(),
+            code,
+            message,
+        })
+    }
+}
+
+/// Extract the error code and message from an error response body.
+///
+/// Understands the structured `{"error": {"error_code", "message"}}` shape
+/// as well as a plain `{"error": "..."}`; anything else is returned verbatim.
+fn parse_error_body(body: &str) -> (Option<String>, String) {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(body) else {
+        return (None, body.to_string());
+    };
 
-        // Try to parse as JSON error
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&body) {
-            let message = json
-                .get("error")
+    match json.get("error") {
+        Some(serde_json::Value::String(message)) => (None, message.clone()),
+        Some(error @ serde_json::Value::Object(_)) => {
+            let code = error
+                .get("error_code")
                 .and_then(|v| v.as_str())
-                .unwrap_or(&body)
+                .map(String::from);
+            let message = error
+                .get("message")
+                .and_then(|v| v.as_str())
+                .unwrap_or(body)
                 .to_string();
-            Err(CliError::Server {
-                status: status.as_u16(),
-                message,
-            })
-        } else {
-            Err(CliError::Server {
-                status: status.as_u16(),
-                message: body,
-            })
+            (code, message)
         }
+        _ => (None, body.to_string()),
+    }
+}
+
+/// Resolve a notebook given by ID or by name.
+///
+/// A valid UUID is used as is. Anything else is looked up among the
+/// notebooks listed by the server and must name exactly one of them.
+pub async fn resolve_notebook(
+    client: &ApiClient,
+    base_url: &str,
+    notebook: &str,
+) -> Result<Uuid, CliError> {
+    if let Ok(id) = Uuid::parse_str(notebook) {
+        return Ok(id);
+    }
+    let url = format!("{}/notebooks", base_url);
+    let response: ListNotebooksResponse = make_request(client, client.get(&url)).await?;
+    find_notebook_by_name(&response.notebooks, notebook)
+}
+
+/// Find the one notebook in `notebooks` called `name`.
+fn find_notebook_by_name(notebooks: &[NotebookSummary], name: &str) -> Result<Uuid, CliError> {
+    let ids: Vec<Uuid> = notebooks
+        .iter()
+        .filter(|notebook| notebook.name == name)
+        .map(|notebook| notebook.id)
+        .collect();
+    match ids.as_slice() {
+        [] => Err(CliError::NotebookNotFound(name.to_string())),
+        [id] => Ok(*id),
+        _ => Err(CliError::AmbiguousNotebook {
+            name: name.to_string(),
+            ids,
+        }),
+    }
+}
+
+/// Versions reported by the server's `GET /version`.
+#[derive(Debug, serde::Deserialize)]
+pub struct ServerVersion {
+    pub server_version: String,
+    pub api_version: u32,
+    pub schema_version: u32,
+    pub expected_schema_version: u32,
+}
+
+/// Describe any incompatibility between this CLI and the server.
+pub fn compatibility_warning(version: &ServerVersion) -> Option<String> {
+    if version.api_version != SUPPORTED_API_VERSION {
+        return Some(format!(
+            "server {} speaks API version {}, this CLI expects {}",
+            version.server_version, version.api_version, SUPPORTED_API_VERSION
+        ));
+    }
+    if version.schema_version != version.expected_schema_version {
+        return Some(format!(
+            "server {} runs on schema version {}, expected {}",
+            version.server_version, version.schema_version, version.expected_schema_version
+        ));
+    }
+    None
+}
+
+/// Warn on stderr when the server is incompatible with this CLI.
+///
+/// Sent once without retries; servers that cannot be reached or predate
+/// `GET /version` are not reported, the command itself surfaces errors.
+pub async fn check_server_version(client: &ApiClient, base_url: &str) {
+    let url = format!("{}/version", base_url);
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+    let Ok(response) = response else {
+        return;
+    };
+    if let Ok(version) = response.json::<ServerVersion>().await
+        && let Some(warning) = compatibility_warning(&version)
+    {
+        eprintln!("Warning: {}", warning);
     }
 }
 
@@ -99,11 +365,279 @@ pub fn format_timestamp(ts: &chrono::DateTime<chrono::Utc>) -> String {
     ts.format("%Y-%m-%d %H:%M:%S UTC").to_string()
 }
 
-/// Truncate a string for display, adding ellipsis if needed.
+/// Truncate a string for display, adding [`DEFAULT_ELLIPSIS`] if needed.
 pub fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+    truncate_with(s, max_len, DEFAULT_ELLIPSIS)
+}
+
+/// Truncate a string to at most `max_len` characters, ending it with
+/// `ellipsis` if anything was cut.
+///
+/// Lengths are counted in characters and the cut always falls on a
+/// character boundary, so multi-byte content is never split.
+pub fn truncate_with(s: &str, max_len: usize, ellipsis: &str) -> String {
+    if s.chars().count() <= max_len {
+        return s.to_string();
+    }
+    let ellipsis: String = ellipsis.chars().take(max_len).collect();
+    let keep = max_len - ellipsis.chars().count();
+    let cut = s.char_indices().nth(keep).map_or(s.len(), |(i, _)| i);
+    format!("{}{}", &s[..cut], ellipsis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one scripted status per connection and count the hits.
+    async fn scripted_server(statuses: Vec<u16>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+
+        tokio::spawn(async move {
+            for status in statuses {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    return;
+                };
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+
+                let body = if status == 200 {
+                    r#"{"ok":true}"#.to_string()
+                } else {
+                    format!(r#"{{"error":"status {}"}}"#, status)
+                };
+                let response = format!(
+                    "HTTP/1.1 {} X\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (format!("http://{}", addr), hits)
+    }
+
+    fn test_client(retries: u32) -> ApiClient {
+        let retry = RetryPolicy {
+            retries,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        };
+        build_client(None, Duration::from_secs(5), retry).unwrap()
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    struct OkBody {
+        ok: bool,
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let policy = RetryPolicy::new(5);
+        assert_eq!(policy.delay_for(0), RETRY_BASE_DELAY);
+        assert_eq!(policy.delay_for(1), RETRY_BASE_DELAY * 2);
+        assert_eq!(policy.delay_for(2), RETRY_BASE_DELAY * 4);
+        assert_eq!(policy.delay_for(40), RETRY_MAX_DELAY);
+    }
+
+    #[tokio::test]
+    async fn test_transient_503_is_retried_until_success() {
+        let (url, hits) = scripted_server(vec![503, 503, 200]).await;
+        let client = test_client(3);
+
+        let body: OkBody = make_request(&client, client.get(&url)).await.unwrap();
+
+        assert!(body.ok);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_persistent_503_is_surfaced_after_retries() {
+        let (url, hits) = scripted_server(vec![503, 503, 503]).await;
+        let client = test_client(2);
+
+        let err = make_request::<OkBody>(&client, client.get(&url))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, CliError::Server { status: 503, .. }));
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_post_without_idempotency_key_is_not_retried() {
+        let (url, hits) = scripted_server(vec![503, 200]).await;
+        let client = test_client(3);
+
+        let err = make_request::<OkBody>(&client, client.post(&url).json(&serde_json::json!({})))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, CliError::Server { status: 503, .. }));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_post_with_idempotency_key_is_retried() {
+        let (url, hits) = scripted_server(vec![503, 200]).await;
+        let client = test_client(3);
+
+        let request = client
+            .post(&url)
+            .header(IDEMPOTENCY_KEY_HEADER, "key-1")
+            .json(&serde_json::json!({}));
+        let body: OkBody = make_request(&client, request).await.unwrap();
+
+        assert!(body.ok);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_client_error_is_not_retried() {
+        let (url, hits) = scripted_server(vec![404, 200]).await;
+        let client = test_client(3);
+
+        let err = make_request::<OkBody>(&client, client.get(&url))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, CliError::Server { status: 404, .. }));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    fn server_version(api_version: u32, schema_version: u32) -> ServerVersion {
+        ServerVersion {
+            server_version: "0.1.0".to_string(),
+            api_version,
+            schema_version,
+            expected_schema_version: 5,
+        }
+    }
+
+    #[test]
+    fn test_compatible_server_has_no_warning() {
+        assert_eq!(
+            compatibility_warning(&server_version(SUPPORTED_API_VERSION, 5)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_version_mismatch_is_reported() {
+        let warning = compatibility_warning(&server_version(SUPPORTED_API_VERSION + 1, 5));
+        assert!(warning.unwrap().contains("API version"));
+
+        let warning = compatibility_warning(&server_version(SUPPORTED_API_VERSION, 2));
+        assert!(warning.unwrap().contains("schema version 2"));
+    }
+
+    #[test]
+    fn test_parse_structured_error_body() {
+        let body = r#"{"error":{"code":"NOT_FOUND","error_code":"NOTEBOOK_NOT_FOUND","message":"Not found: Notebook 1 not found","request_id":"abc"}}"#;
+        let (code, message) = parse_error_body(body);
+        assert_eq!(code.as_deref(), Some("NOTEBOOK_NOT_FOUND"));
+        assert_eq!(message, "Not found: Notebook 1 not found");
+    }
+
+    fn named(name: &str) -> NotebookSummary {
+        serde_json::from_value(serde_json::json!({
+            "id": Uuid::new_v4(),
+            "name": name,
+            "owner": "ab".repeat(32),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_notebook_name_resolves_to_unique_match() {
+        let notebooks = vec![named("research"), named("scratch")];
+        let id = find_notebook_by_name(&notebooks, "scratch").unwrap();
+        assert_eq!(id, notebooks[1].id);
+    }
+
+    #[test]
+    fn test_ambiguous_notebook_name_lists_ids() {
+        // Names are unique per owner, but shared notebooks of two owners may clash
+        let notebooks = vec![named("research"), named("research"), named("scratch")];
+        let err = find_notebook_by_name(&notebooks, "research").unwrap_err();
+
+        assert!(matches!(&err, CliError::AmbiguousNotebook { ids, .. } if ids.len() == 2));
+        let message = err.to_string();
+        assert!(message.contains(&notebooks[0].id.to_string()));
+        assert!(message.contains(&notebooks[1].id.to_string()));
+    }
+
+    #[test]
+    fn test_unknown_notebook_name_is_not_found() {
+        let notebooks = vec![named("research")];
+        let err = find_notebook_by_name(&notebooks, "Research").unwrap_err();
+        assert!(matches!(err, CliError::NotebookNotFound(ref name) if name == "Research"));
+        assert!(find_notebook_by_name(&[], "research").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_notebook_id_is_used_without_lookup() {
+        // The server answers no requests, so a lookup would fail
+        let (url, hits) = scripted_server(vec![]).await;
+        let client = test_client(0);
+        let id = Uuid::new_v4();
+
+        let resolved = resolve_notebook(&client, &url, &id.to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(resolved, id);
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_parse_plain_error_body() {
+        assert_eq!(
+            parse_error_body(r#"{"error":"status 503"}"#),
+            (None, "status 503".to_string())
+        );
+        assert_eq!(parse_error_body("oops"), (None, "oops".to_string()));
+    }
+
+    #[test]
+    fn test_truncate_short_text_is_unchanged() {
+        assert_eq!(truncate("short", 16), "short");
+        assert_eq!(truncate("exactly8", 8), "exactly8");
+        assert_eq!(truncate("", 0), "");
+    }
+
+    #[test]
+    fn test_truncate_adds_ellipsis() {
+        assert_eq!(truncate("a long summary line", 10), "a long ...");
+        assert_eq!(truncate_with("a long summary line", 10, "…"), "a long su…");
+        // An ellipsis longer than the limit is itself cut
+        assert_eq!(truncate_with("abcdef", 2, "..."), "..");
+        assert_eq!(truncate_with("abcdef", 0, "..."), "");
+    }
+
+    #[test]
+    fn test_truncate_multibyte_text_on_char_boundaries() {
+        // Byte-based cuts would land inside the accented letters and emoji
+        let text = "café crème 😀🎉 naïve über";
+        for max_len in 0..=text.chars().count() + 1 {
+            for ellipsis in ["...", "…", ""] {
+                let cut = truncate_with(text, max_len, ellipsis);
+                assert!(cut.chars().count() <= max_len);
+                let kept = cut.trim_end_matches(['.', '…']);
+                assert!(text.starts_with(kept));
+            }
+        }
+        assert_eq!(truncate("😀😀😀😀😀", 4), "😀...");
+        assert_eq!(truncate_with("éééé", 3, "…"), "éé…");
     }
 }
@@ -7,13 +7,16 @@ use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{HumanReadable, format_timestamp, make_request, output};
+use super::{
+    ApiClient, HumanReadable, OutputFormat, format_timestamp, make_request, output,
+    resolve_notebook,
+};
 
 /// Arguments for the observe command.
 #[derive(Args)]
 pub struct ObserveArgs {
-    /// Notebook ID to observe
-    pub notebook_id: Uuid,
+    /// Notebook ID or name to observe
+    pub notebook: String,
 
     /// Sequence number to observe changes since (exclusive)
     #[arg(long)]
@@ -124,15 +127,22 @@ impl HumanReadable for ObserveResponse {
             }
 
             if change.integration_cost.orphan {
-                println!("    {} {}", "Warning:".red().bold(), "Marked as orphan");
+                println!("    {} Marked as orphan", "Warning:".red().bold());
             }
         }
     }
 }
 
 /// Execute the observe command.
-pub async fn execute(client: &reqwest::Client, base_url: &str, human: bool, args: ObserveArgs) -> Result<()> {
-    let mut url = format!("{}/notebooks/{}/observe", base_url, args.notebook_id);
+pub async fn execute(
+    client: &ApiClient,
+    base_url: &str,
+    format: OutputFormat,
+    args: ObserveArgs,
+) -> Result<()> {
+    let notebook_id = resolve_notebook(client, base_url, &args.notebook).await?;
+
+    let mut url = format!("{}/notebooks/{}/observe", base_url, notebook_id);
 
     if let Some(since) = args.since {
         url = format!("{}?since={}", url, since);
@@ -140,5 +150,5 @@ pub async fn execute(client: &reqwest::Client, base_url: &str, human: bool, args
 
     let response: ObserveResponse = make_request(client, client.get(&url)).await?;
 
-    output(&response, human)
+    output(&response, format)
 }
@@ -7,13 +7,17 @@ use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{HumanReadable, format_timestamp, make_request, output, truncate};
+use super::{
+    ApiClient, HumanReadable, OutputFormat, format_timestamp, make_request, output,
+    resolve_notebook, truncate,
+};
+use crate::table::Table;
 
 /// Arguments for the read command.
 #[derive(Args)]
 pub struct ReadArgs {
-    /// Notebook ID containing the entry
-    pub notebook_id: Uuid,
+    /// Notebook ID or name containing the entry
+    pub notebook: String,
 
     /// Entry ID to read
     pub entry_id: Uuid,
@@ -27,6 +31,9 @@ pub struct ReadArgs {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ReadEntryResponse {
     pub entry: EntryResponse,
+    #[serde(default)]
+    pub revision_count: i64,
+    #[serde(default)]
     pub revisions: Vec<EntrySummary>,
     #[serde(default)]
     pub references: Vec<EntrySummary>,
@@ -37,7 +44,7 @@ pub struct ReadEntryResponse {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct EntryResponse {
     pub id: Uuid,
-    pub content: serde_json::Value, // Can be string or {data, encoding} for binary
+    pub content: serde_json::Value, // String, JSON document, or {data, encoding} for binary
     pub content_type: String,
     pub topic: Option<String>,
     pub author: String,
@@ -77,6 +84,20 @@ pub struct EntrySummary {
     pub created: DateTime<Utc>,
 }
 
+/// Render related entries as an aligned table.
+fn summary_table(summaries: &[EntrySummary]) -> Table {
+    let mut table = Table::new(["ID", "TOPIC", "AUTHOR", "CREATED"]);
+    for summary in summaries {
+        table.add_row([
+            summary.id.to_string(),
+            summary.topic.clone().unwrap_or_default(),
+            truncate(&summary.author, 16),
+            format_timestamp(&summary.created),
+        ]);
+    }
+    table
+}
+
 impl HumanReadable for ReadEntryResponse {
     fn print_human(&self) {
         let entry = &self.entry;
@@ -115,7 +136,7 @@ impl HumanReadable for ReadEntryResponse {
             serde_json::Value::String(s) => {
                 println!("{}", s);
             }
-            serde_json::Value::Object(obj) => {
+            serde_json::Value::Object(obj) if obj.len() == 2 && obj.contains_key("data") => {
                 if let Some(encoding) = obj.get("encoding") {
                     println!(
                         "[Binary content, {} encoded, {} bytes]",
@@ -127,8 +148,12 @@ impl HumanReadable for ReadEntryResponse {
                     );
                 }
             }
-            _ => {
-                println!("{}", entry.content);
+            other => {
+                // Structured JSON content
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(other).unwrap_or_else(|_| other.to_string())
+                );
             }
         }
 
@@ -138,9 +163,9 @@ impl HumanReadable for ReadEntryResponse {
         // Integration cost
         println!("{}", "Integration Cost:".yellow());
         println!(
-            "  {} {}",
+            "  {} {:.2}",
             "Catalog Shift:".cyan(),
-            format!("{:.2}", entry.integration_cost.catalog_shift)
+            entry.integration_cost.catalog_shift
         );
         println!(
             "  {} {}",
@@ -153,7 +178,7 @@ impl HumanReadable for ReadEntryResponse {
             entry.integration_cost.references_broken
         );
         if entry.integration_cost.orphan {
-            println!("  {} {}", "Status:".red().bold(), "ORPHAN");
+            println!("  {} ORPHAN", "Status:".red().bold());
         }
 
         // References
@@ -169,47 +194,37 @@ impl HumanReadable for ReadEntryResponse {
         if !self.referenced_by.is_empty() {
             println!();
             println!("{}", "Referenced By:".yellow());
-            for summary in &self.referenced_by {
-                println!(
-                    "  - {} {}",
-                    summary.id,
-                    summary
-                        .topic
-                        .as_ref()
-                        .map(|t| format!("({})", t))
-                        .unwrap_or_default()
-                );
-            }
+            print!("{}", summary_table(&self.referenced_by));
         }
 
         // Revisions
         if !self.revisions.is_empty() {
             println!();
             println!("{}", "Revision History:".yellow());
-            for (i, rev) in self.revisions.iter().enumerate() {
-                println!(
-                    "  {}. {} ({})",
-                    i + 1,
-                    rev.id,
-                    format_timestamp(&rev.created)
-                );
-            }
+            print!("{}", summary_table(&self.revisions));
         }
     }
 }
 
 /// Execute the read command.
-pub async fn execute(client: &reqwest::Client, base_url: &str, human: bool, args: ReadArgs) -> Result<()> {
+pub async fn execute(
+    client: &ApiClient,
+    base_url: &str,
+    format: OutputFormat,
+    args: ReadArgs,
+) -> Result<()> {
+    let notebook_id = resolve_notebook(client, base_url, &args.notebook).await?;
+
     let mut url = format!(
-        "{}/notebooks/{}/entries/{}",
-        base_url, args.notebook_id, args.entry_id
+        "{}/notebooks/{}/entries/{}?include=revisions,referenced_by",
+        base_url, notebook_id, args.entry_id
     );
 
     if let Some(rev) = args.revision {
-        url = format!("{}?revision={}", url, rev);
+        url = format!("{}&revision={}", url, rev);
     }
 
     let response: ReadEntryResponse = make_request(client, client.get(&url)).await?;
 
-    output(&response, human)
+    output(&response, format)
 }
@@ -6,13 +6,13 @@ use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{HumanReadable, make_request, output};
+use super::{ApiClient, HumanReadable, OutputFormat, make_request, output, resolve_notebook};
 
 /// Arguments for the rename command.
 #[derive(Args)]
 pub struct RenameArgs {
-    /// Notebook ID to rename
-    pub notebook_id: Uuid,
+    /// Notebook ID or name to rename
+    pub notebook: String,
 
     /// New name for the notebook
     pub name: String,
@@ -41,13 +41,20 @@ impl HumanReadable for RenameNotebookResponse {
 }
 
 /// Execute the rename command.
-pub async fn execute(client: &reqwest::Client, base_url: &str, human: bool, args: RenameArgs) -> Result<()> {
-    let url = format!("{}/notebooks/{}", base_url, args.notebook_id);
+pub async fn execute(
+    client: &ApiClient,
+    base_url: &str,
+    format: OutputFormat,
+    args: RenameArgs,
+) -> Result<()> {
+    let notebook_id = resolve_notebook(client, base_url, &args.notebook).await?;
+
+    let url = format!("{}/notebooks/{}", base_url, notebook_id);
 
     let request_body = RenameNotebookRequest { name: args.name };
 
     let response: RenameNotebookResponse =
         make_request(client, client.patch(&url).json(&request_body)).await?;
 
-    output(&response, human)
+    output(&response, format)
 }
@@ -6,13 +6,13 @@ use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{HumanReadable, make_request, output};
+use super::{ApiClient, HumanReadable, OutputFormat, make_request, output, resolve_notebook};
 
 /// Arguments for the revise command.
 #[derive(Args)]
 pub struct ReviseArgs {
-    /// Notebook ID containing the entry
-    pub notebook_id: Uuid,
+    /// Notebook ID or name containing the entry
+    pub notebook: String,
 
     /// Entry ID to revise
     pub entry_id: Uuid,
@@ -40,6 +40,9 @@ pub struct ReviseEntryResponse {
     pub revision_id: Uuid,
     pub causal_position: CausalPosition,
     pub integration_cost: IntegrationCost,
+    /// The content matched the latest revision and nothing was written.
+    #[serde(default)]
+    pub no_op: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -65,6 +68,14 @@ pub struct IntegrationCost {
 
 impl HumanReadable for ReviseEntryResponse {
     fn print_human(&self) {
+        if self.no_op {
+            println!(
+                "{}",
+                "Entry unchanged: it matches the latest revision.".yellow()
+            );
+            println!("  {} {}", "Revision ID:".cyan(), self.revision_id);
+            return;
+        }
         println!("{}", "Entry revised successfully!".green().bold());
         println!();
         println!("  {} {}", "Revision ID:".cyan(), self.revision_id);
@@ -72,9 +83,9 @@ impl HumanReadable for ReviseEntryResponse {
         println!();
         println!("{}", "Integration Cost:".yellow());
         println!(
-            "  {} {}",
+            "  {} {:.2}",
             "Catalog Shift:".cyan(),
-            format!("{:.2}", self.integration_cost.catalog_shift)
+            self.integration_cost.catalog_shift
         );
         println!(
             "  {} {}",
@@ -83,19 +94,25 @@ impl HumanReadable for ReviseEntryResponse {
         );
         if self.integration_cost.orphan {
             println!(
-                "  {} {}",
-                "Warning:".red().bold(),
-                "Revision marked as orphan (low coherence)"
+                "  {} Revision marked as orphan (low coherence)",
+                "Warning:".red().bold()
             );
         }
     }
 }
 
 /// Execute the revise command.
-pub async fn execute(client: &reqwest::Client, base_url: &str, human: bool, args: ReviseArgs) -> Result<()> {
+pub async fn execute(
+    client: &ApiClient,
+    base_url: &str,
+    format: OutputFormat,
+    args: ReviseArgs,
+) -> Result<()> {
+    let notebook_id = resolve_notebook(client, base_url, &args.notebook).await?;
+
     let url = format!(
         "{}/notebooks/{}/entries/{}",
-        base_url, args.notebook_id, args.entry_id
+        base_url, notebook_id, args.entry_id
     );
 
     // Handle special content sources
@@ -121,5 +138,5 @@ pub async fn execute(client: &reqwest::Client, base_url: &str, human: bool, args
     let response: ReviseEntryResponse =
         make_request(client, client.put(&url).json(&request_body)).await?;
 
-    output(&response, human)
+    output(&response, format)
 }
@@ -5,15 +5,17 @@ use chrono::{DateTime, Utc};
 use clap::{Args, Subcommand};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 
-use super::{HumanReadable, format_timestamp, make_request, output};
+use super::{
+    ApiClient, HumanReadable, OutputFormat, format_timestamp, make_request, output,
+    resolve_notebook,
+};
 
 /// Arguments for the share command.
 #[derive(Args)]
 pub struct ShareArgs {
-    /// Notebook ID to manage access for
-    pub notebook_id: Uuid,
+    /// Notebook ID or name to manage access for
+    pub notebook: String,
 
     #[command(subcommand)]
     pub action: ShareAction,
@@ -147,36 +149,40 @@ impl HumanReadable for ParticipantsResponse {
 }
 
 /// Execute the share command.
-pub async fn execute(client: &reqwest::Client, base_url: &str, human: bool, args: ShareArgs) -> Result<()> {
+pub async fn execute(
+    client: &ApiClient,
+    base_url: &str,
+    format: OutputFormat,
+    args: ShareArgs,
+) -> Result<()> {
+    let notebook_id = resolve_notebook(client, base_url, &args.notebook).await?;
+
     match args.action {
         ShareAction::Grant {
             author_id,
             read,
             write,
         } => {
-            let url = format!("{}/notebooks/{}/share", base_url, args.notebook_id);
+            let url = format!("{}/notebooks/{}/share", base_url, notebook_id);
             let request_body = ShareRequest {
                 author_id,
                 permissions: Permissions { read, write },
             };
             let response: ShareResponse =
                 make_request(client, client.post(&url).json(&request_body)).await?;
-            output(&response, human)
+            output(&response, format)
         }
 
         ShareAction::Revoke { author_id } => {
-            let url = format!(
-                "{}/notebooks/{}/share/{}",
-                base_url, args.notebook_id, author_id
-            );
+            let url = format!("{}/notebooks/{}/share/{}", base_url, notebook_id, author_id);
             let response: RevokeResponse = make_request(client, client.delete(&url)).await?;
-            output(&response, human)
+            output(&response, format)
         }
 
         ShareAction::List => {
-            let url = format!("{}/notebooks/{}/participants", base_url, args.notebook_id);
+            let url = format!("{}/notebooks/{}/participants", base_url, notebook_id);
             let response: ParticipantsResponse = make_request(client, client.get(&url)).await?;
-            output(&response, human)
+            output(&response, format)
         }
     }
 }
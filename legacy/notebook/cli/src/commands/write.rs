@@ -6,13 +6,13 @@ use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{HumanReadable, make_request, output};
+use super::{ApiClient, HumanReadable, OutputFormat, make_request, output, resolve_notebook};
 
 /// Arguments for the write command.
 #[derive(Args)]
 pub struct WriteArgs {
-    /// Notebook ID to write to
-    pub notebook_id: Uuid,
+    /// Notebook ID or name to write to
+    pub notebook: String,
 
     /// Content of the entry (use @filename to read from file, or - for stdin)
     #[arg(short, long)]
@@ -29,6 +29,10 @@ pub struct WriteArgs {
     /// References to other entries (UUIDs, can be specified multiple times)
     #[arg(short, long)]
     pub reference: Vec<Uuid>,
+
+    /// Allow references to entries in other notebooks
+    #[arg(long)]
+    pub allow_external_refs: bool,
 }
 
 /// Request body for creating an entry.
@@ -40,6 +44,8 @@ struct CreateEntryRequest {
     topic: Option<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     references: Vec<Uuid>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    allow_external_refs: bool,
 }
 
 /// Response from creating an entry.
@@ -80,9 +86,9 @@ impl HumanReadable for CreateEntryResponse {
         println!();
         println!("{}", "Integration Cost:".yellow());
         println!(
-            "  {} {}",
+            "  {} {:.2}",
             "Catalog Shift:".cyan(),
-            format!("{:.2}", self.integration_cost.catalog_shift)
+            self.integration_cost.catalog_shift
         );
         println!(
             "  {} {}",
@@ -91,17 +97,23 @@ impl HumanReadable for CreateEntryResponse {
         );
         if self.integration_cost.orphan {
             println!(
-                "  {} {}",
-                "Warning:".red().bold(),
-                "Entry marked as orphan (low coherence)"
+                "  {} Entry marked as orphan (low coherence)",
+                "Warning:".red().bold()
             );
         }
     }
 }
 
 /// Execute the write command.
-pub async fn execute(client: &reqwest::Client, base_url: &str, human: bool, args: WriteArgs) -> Result<()> {
-    let url = format!("{}/notebooks/{}/entries", base_url, args.notebook_id);
+pub async fn execute(
+    client: &ApiClient,
+    base_url: &str,
+    format: OutputFormat,
+    args: WriteArgs,
+) -> Result<()> {
+    let notebook_id = resolve_notebook(client, base_url, &args.notebook).await?;
+
+    let url = format!("{}/notebooks/{}/entries", base_url, notebook_id);
 
     // Handle special content sources
     let content = if args.content == "-" {
@@ -123,10 +135,11 @@ pub async fn execute(client: &reqwest::Client, base_url: &str, human: bool, args
         content_type: args.content_type,
         topic: args.topic,
         references: args.reference,
+        allow_external_refs: args.allow_external_refs,
     };
 
     let response: CreateEntryResponse =
         make_request(client, client.post(&url).json(&request_body)).await?;
 
-    output(&response, human)
+    output(&response, format)
 }
@@ -12,16 +12,25 @@
 //! - rename: Rename notebooks
 //! - delete: Delete notebooks
 //!
+//! Output is JSON by default; use `--format yaml` or `--format table`
+//! for other renderings.
+//!
+//! Commands take a notebook by ID or by name; a name is looked up among the
+//! accessible notebooks and must match exactly one of them.
+//!
 //! Configuration via environment:
 //! - NOTEBOOK_URL: Base URL of the notebook server (default: http://localhost:3000)
 //! - NOTEBOOK_TOKEN: JWT Bearer token for authentication
 
 mod commands;
+mod table;
+
+use std::time::Duration;
 
 use clap::{Parser, Subcommand};
 
 use commands::{
-    browse::BrowseArgs, create::CreateArgs, delete::DeleteArgs, list::ListArgs,
+    OutputFormat, browse::BrowseArgs, create::CreateArgs, delete::DeleteArgs, list::ListArgs,
     observe::ObserveArgs, read::ReadArgs, rename::RenameArgs, revise::ReviseArgs,
     share::ShareArgs, write::WriteArgs,
 };
@@ -29,13 +38,17 @@ use commands::{
 /// Knowledge Exchange Platform CLI
 ///
 /// Interact with notebooks from the command line. Designed for both
-/// AI agents (JSON output) and humans (--human flag for formatted output).
+/// AI agents (JSON output) and humans (--format table for formatted output).
 #[derive(Parser)]
 #[command(name = "notebook")]
 #[command(author, version, about, long_about = None)]
 #[command(propagate_version = true)]
 struct Cli {
-    /// Output human-readable formatted text instead of JSON
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json, global = true)]
+    format: OutputFormat,
+
+    /// Deprecated: same as `--format table`
     #[arg(long, global = true)]
     human: bool,
 
@@ -52,6 +65,18 @@ struct Cli {
     #[arg(long, env = "NOTEBOOK_TOKEN", global = true)]
     token: Option<String>,
 
+    /// Per-request timeout in seconds
+    #[arg(long, default_value_t = commands::DEFAULT_TIMEOUT_SECS, global = true)]
+    timeout: u64,
+
+    /// Retries for idempotent requests on connection errors and 5xx responses
+    #[arg(long, default_value_t = commands::DEFAULT_RETRIES, global = true)]
+    retries: u32,
+
+    /// Skip the server compatibility check on startup
+    #[arg(long, env = "NOTEBOOK_SKIP_VERSION_CHECK", global = true)]
+    skip_version_check: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -93,7 +118,11 @@ enum Commands {
 async fn main() {
     let cli = Cli::parse();
 
-    let client = match commands::build_client(cli.token.as_deref()) {
+    let client = match commands::build_client(
+        cli.token.as_deref(),
+        Duration::from_secs(cli.timeout),
+        commands::RetryPolicy::new(cli.retries),
+    ) {
         Ok(c) => c,
         Err(e) => {
             eprintln!("Error: {}", e);
@@ -101,31 +130,29 @@ async fn main() {
         }
     };
 
+    if !cli.skip_version_check {
+        commands::check_server_version(&client, &cli.url).await;
+    }
+
+    let format = if cli.human {
+        OutputFormat::Table
+    } else {
+        cli.format
+    };
+
     let result = match cli.command {
-        Commands::Write(args) => commands::write::execute(&client, &cli.url, cli.human, args).await,
-        Commands::Revise(args) => {
-            commands::revise::execute(&client, &cli.url, cli.human, args).await
-        }
-        Commands::Read(args) => commands::read::execute(&client, &cli.url, cli.human, args).await,
-        Commands::Browse(args) => {
-            commands::browse::execute(&client, &cli.url, cli.human, args).await
-        }
-        Commands::Share(args) => {
-            commands::share::execute(&client, &cli.url, cli.human, args).await
-        }
+        Commands::Write(args) => commands::write::execute(&client, &cli.url, format, args).await,
+        Commands::Revise(args) => commands::revise::execute(&client, &cli.url, format, args).await,
+        Commands::Read(args) => commands::read::execute(&client, &cli.url, format, args).await,
+        Commands::Browse(args) => commands::browse::execute(&client, &cli.url, format, args).await,
+        Commands::Share(args) => commands::share::execute(&client, &cli.url, format, args).await,
         Commands::Observe(args) => {
-            commands::observe::execute(&client, &cli.url, cli.human, args).await
-        }
-        Commands::List(args) => commands::list::execute(&client, &cli.url, cli.human, args).await,
-        Commands::Create(args) => {
-            commands::create::execute(&client, &cli.url, cli.human, args).await
-        }
-        Commands::Rename(args) => {
-            commands::rename::execute(&client, &cli.url, cli.human, args).await
-        }
-        Commands::Delete(args) => {
-            commands::delete::execute(&client, &cli.url, cli.human, args).await
+            commands::observe::execute(&client, &cli.url, format, args).await
         }
+        Commands::List(args) => commands::list::execute(&client, &cli.url, format, args).await,
+        Commands::Create(args) => commands::create::execute(&client, &cli.url, format, args).await,
+        Commands::Rename(args) => commands::rename::execute(&client, &cli.url, format, args).await,
+        Commands::Delete(args) => commands::delete::execute(&client, &cli.url, format, args).await,
     };
 
     if let Err(e) = result {
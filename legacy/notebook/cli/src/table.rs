@@ -0,0 +1,120 @@
+//! Minimal aligned-column table renderer for `--format table` output.
+//!
+//! Columns are padded to the widest cell (measured in characters) and
+//! separated by two spaces. Cells are plain text; callers colorize
+//! surrounding headings rather than cells so that widths stay correct.
+
+use std::fmt;
+
+/// Separator placed between two columns.
+const COLUMN_GAP: &str = "  ";
+
+/// A table of string cells with a header row.
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Create a table with the given column headers.
+    pub fn new<I, S>(headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            headers: headers.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Append a row. Missing cells render empty; extra cells are dropped.
+    pub fn add_row<I, S>(&mut self, row: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut row: Vec<String> = row.into_iter().map(Into::into).collect();
+        row.resize(self.headers.len(), String::new());
+        self.rows.push(row);
+    }
+
+    /// Width of each column in characters.
+    fn column_widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        widths
+    }
+
+    /// Render a single line, padding every column but the last.
+    fn render_line(cells: &[String], widths: &[usize]) -> String {
+        let mut line = String::new();
+        for (i, (cell, width)) in cells.iter().zip(widths).enumerate() {
+            if i > 0 {
+                line.push_str(COLUMN_GAP);
+            }
+            line.push_str(cell);
+            let pad = width.saturating_sub(cell.chars().count());
+            line.extend(std::iter::repeat_n(' ', pad));
+        }
+        line.trim_end().to_string()
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths = self.column_widths();
+        let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+
+        writeln!(f, "{}", Self::render_line(&self.headers, &widths))?;
+        writeln!(f, "{}", Self::render_line(&rule, &widths))?;
+        for row in &self.rows {
+            writeln!(f, "{}", Self::render_line(row, &widths))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_are_aligned() {
+        let mut table = Table::new(["NAME", "ENTRIES"]);
+        table.add_row(["a", "1"]);
+        table.add_row(["longer name", "12345"]);
+
+        let rendered = table.to_string();
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines[0], "NAME         ENTRIES");
+        assert_eq!(lines[1], "-----------  -------");
+        assert_eq!(lines[2], "a            1");
+        assert_eq!(lines[3], "longer name  12345");
+    }
+
+    #[test]
+    fn test_width_counts_characters_not_bytes() {
+        let mut table = Table::new(["A", "B"]);
+        table.add_row(["über", "x"]);
+        table.add_row(["abcd", "y"]);
+
+        let rendered = table.to_string();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[2].find('x'), Some("über  ".len()));
+        assert_eq!(lines[3].find('y'), Some("abcd  ".len()));
+    }
+
+    #[test]
+    fn test_short_rows_are_padded() {
+        let mut table = Table::new(["A", "B", "C"]);
+        table.add_row(["1"]);
+        assert_eq!(table.to_string().lines().nth(2), Some("1"));
+    }
+}
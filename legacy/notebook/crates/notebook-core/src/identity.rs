@@ -180,8 +180,8 @@ mod tests {
         let id2 = derive_author_id(&keypair2.public_key());
 
         let mut set = HashSet::new();
-        set.insert(id1.clone());
-        set.insert(id2.clone());
+        set.insert(id1);
+        set.insert(id2);
 
         assert_eq!(set.len(), 2);
         assert!(set.contains(&id1));
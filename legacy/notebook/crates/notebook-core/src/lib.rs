@@ -13,7 +13,7 @@ pub mod types;
 // Re-export commonly used types at crate root for convenience
 pub use types::{
     ActivityContext, AuthorId, AuthorIdParseError, CausalPosition, Entry, EntryBuilder, EntryId,
-    IntegrationCost, Notebook, NotebookId, Participant, Permissions,
+    IdStrategy, IntegrationCost, Notebook, NotebookId, Participant, Permissions,
 };
 
 // Cryptographic primitives (owned by agent-crypto)
@@ -24,8 +24,9 @@ use uuid::Uuid;
 
 /// Unique identifier for an entry in the notebook.
 ///
-/// Wraps a UUID v4, providing type safety to distinguish entry IDs from other
-/// UUID-based identifiers in the system.
+/// Wraps a UUID, providing type safety to distinguish entry IDs from other
+/// UUID-based identifiers in the system. New IDs are random (v4) or
+/// time-ordered (v7) depending on the [`IdStrategy`]; both parse the same way.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct EntryId(pub Uuid);
@@ -37,6 +38,12 @@ impl EntryId {
         Self(Uuid::new_v4())
     }
 
+    /// Creates a new EntryId using the given strategy.
+    #[must_use]
+    pub fn generate(strategy: IdStrategy) -> Self {
+        Self(strategy.generate())
+    }
+
     /// Creates an EntryId from an existing UUID.
     #[must_use]
     pub const fn from_uuid(uuid: Uuid) -> Self {
@@ -70,6 +77,49 @@ impl FromStr for EntryId {
     }
 }
 
+/// How new entry IDs are generated.
+///
+/// Random v4 IDs scatter inserts across the primary key index; time-ordered
+/// v7 IDs append near its end, so index pages stay dense under high insert
+/// rates and ID order roughly follows creation order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdStrategy {
+    /// Random UUID v4.
+    #[default]
+    UuidV4,
+    /// Time-ordered UUID v7 (millisecond timestamp prefix, like a ULID).
+    UuidV7,
+}
+
+impl IdStrategy {
+    /// Generates a new UUID with this strategy.
+    ///
+    /// v7 IDs generated in one process are strictly increasing.
+    #[must_use]
+    pub fn generate(self) -> Uuid {
+        match self {
+            Self::UuidV4 => Uuid::new_v4(),
+            Self::UuidV7 => Uuid::now_v7(),
+        }
+    }
+
+    /// Returns the configuration name of the strategy.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::UuidV4 => "uuid_v4",
+            Self::UuidV7 => "uuid_v7",
+        }
+    }
+}
+
+impl fmt::Display for IdStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Unique identifier for a notebook.
 ///
 /// Wraps a UUID v4, providing type safety to distinguish notebook IDs from
@@ -634,6 +684,43 @@ mod tests {
         assert_eq!(id, parsed);
     }
 
+    #[test]
+    fn entry_id_v7_ids_increase_over_time() {
+        let mut previous = EntryId::generate(IdStrategy::UuidV7);
+        for i in 0..1000 {
+            if i % 250 == 0 {
+                std::thread::sleep(std::time::Duration::from_millis(2));
+            }
+            let id = EntryId::generate(IdStrategy::UuidV7);
+            assert_eq!(id.0.get_version(), Some(uuid::Version::SortRand));
+            assert!(id.0 > previous.0, "{} not after {}", id, previous);
+            previous = id;
+        }
+    }
+
+    #[test]
+    fn entry_id_v4_ids_still_parse() {
+        let id = EntryId::generate(IdStrategy::UuidV4);
+        assert_eq!(id.0.get_version(), Some(uuid::Version::Random));
+        let parsed: EntryId = id.to_string().parse().unwrap();
+        assert_eq!(id, parsed);
+
+        let existing: EntryId = "6f1c7c1e-8b2a-4d3e-9f4a-2b1c0d9e8f7a".parse().unwrap();
+        assert_eq!(existing.0.get_version(), Some(uuid::Version::Random));
+    }
+
+    #[test]
+    fn id_strategy_serde_names() {
+        assert_eq!(IdStrategy::default(), IdStrategy::UuidV4);
+        assert_eq!(
+            serde_json::to_string(&IdStrategy::UuidV7).unwrap(),
+            "\"uuid_v7\""
+        );
+        let parsed: IdStrategy = serde_json::from_str("\"uuid_v4\"").unwrap();
+        assert_eq!(parsed, IdStrategy::UuidV4);
+        assert_eq!(IdStrategy::UuidV7.to_string(), "uuid_v7");
+    }
+
     #[test]
     fn notebook_id_roundtrip() {
         let id = NotebookId::new();
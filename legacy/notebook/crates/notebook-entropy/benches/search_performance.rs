@@ -80,9 +80,8 @@ fn setup_index(size: usize) -> (TempDir, SearchIndex, NotebookId, Vec<String>) {
         index.index_entry(notebook_id, &entry).unwrap();
     }
 
-    // Allow index to settle
-    std::thread::sleep(std::time::Duration::from_millis(100));
-    index.reload().unwrap();
+    // Make the entries searchable
+    index.flush().unwrap();
 
     (temp_dir, index, notebook_id, topics_used)
 }
@@ -196,11 +195,13 @@ fn index_update_benchmark(c: &mut Criterion) {
             let (entry, _) = generate_entry(&mut rng);
             index.index_entry(notebook_id, &entry).unwrap();
         }
+        index.flush().unwrap();
 
         group.bench_function("add_entry_to_1k", |b| {
             b.iter(|| {
                 let (entry, _) = generate_entry(&mut rng);
-                black_box(index.index_entry(notebook_id, &entry).unwrap())
+                index.index_entry(notebook_id, &entry).unwrap();
+                black_box(())
             })
         });
     }
@@ -218,12 +219,14 @@ fn index_update_benchmark(c: &mut Criterion) {
             entry_ids.push(entry.id);
             index.index_entry(notebook_id, &entry).unwrap();
         }
+        index.flush().unwrap();
 
         let mut idx = 0;
         group.bench_function("delete_entry_from_1k", |b| {
             b.iter(|| {
                 if idx < entry_ids.len() {
-                    black_box(index.delete_entry(entry_ids[idx]).unwrap());
+                    index.delete_entry(entry_ids[idx]).unwrap();
+                    black_box(());
                     idx += 1;
                 }
             })
@@ -255,8 +258,8 @@ fn search_after_update_benchmark(c: &mut Criterion) {
             let (entry, _) = generate_entry(&mut rng);
             index.index_entry(notebook_id, &entry).unwrap();
 
-            // Reload and search
-            index.reload().unwrap();
+            // Commit and search
+            index.flush().unwrap();
             black_box(index.search("learning", notebook_id, 10).unwrap())
         })
     });
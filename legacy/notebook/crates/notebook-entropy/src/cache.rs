@@ -9,6 +9,7 @@
 //! - Configurable shift threshold for invalidation
 //! - Time-based expiration with configurable max age
 //! - Stale-while-revalidate pattern support
+//! - Hit and miss counters for lookups of current catalogs
 //!
 //! ## Example
 //!
@@ -39,6 +40,7 @@
 use crate::catalog::Catalog;
 use notebook_core::types::NotebookId;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
@@ -174,6 +176,12 @@ pub struct CatalogCache {
 
     /// Cache configuration.
     config: CacheConfig,
+
+    /// Lookups served from the cache by [`CatalogCache::get_current`].
+    hits: Arc<AtomicU64>,
+
+    /// Lookups [`CatalogCache::get_current`] could not serve.
+    misses: Arc<AtomicU64>,
 }
 
 impl Default for CatalogCache {
@@ -185,10 +193,7 @@ impl Default for CatalogCache {
 impl CatalogCache {
     /// Creates a new catalog cache with default configuration.
     pub fn new() -> Self {
-        Self {
-            cache: Arc::new(RwLock::new(HashMap::new())),
-            config: CacheConfig::default(),
-        }
+        Self::with_config(CacheConfig::default())
     }
 
     /// Creates a catalog cache with custom configuration.
@@ -196,6 +201,8 @@ impl CatalogCache {
         Self {
             cache: Arc::new(RwLock::new(HashMap::new())),
             config,
+            hits: Arc::default(),
+            misses: Arc::default(),
         }
     }
 
@@ -241,6 +248,29 @@ impl CatalogCache {
         Some((entry.clone(), status))
     }
 
+    /// Gets a fresh catalog generated at `sequence`, the notebook's current
+    /// sequence.
+    ///
+    /// Returns `None` if nothing is cached, the entry is no longer fresh, or
+    /// entries were written since it was generated. Counts as a hit or a miss
+    /// in [`CatalogCache::stats`].
+    pub fn get_current(&self, notebook_id: &NotebookId, sequence: u64) -> Option<CachedCatalog> {
+        let current = self
+            .get_with_status(notebook_id)
+            .filter(|(cached, status)| {
+                *status == CacheStatus::Fresh && cached.cached_at_sequence == sequence
+            })
+            .map(|(cached, _)| cached);
+
+        let counter = if current.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        current
+    }
+
     /// Stores a catalog in the cache.
     ///
     /// # Arguments
@@ -353,6 +383,8 @@ impl CatalogCache {
                 fresh,
                 stale,
                 expired,
+                hits: self.hits.load(Ordering::Relaxed),
+                misses: self.misses.load(Ordering::Relaxed),
             }
         } else {
             CacheStats::default()
@@ -371,27 +403,35 @@ pub struct CacheStats {
     pub stale: usize,
     /// Number of expired entries.
     pub expired: usize,
+    /// Lookups of current catalogs served from the cache.
+    pub hits: u64,
+    /// Lookups of current catalogs that had to regenerate.
+    pub misses: u64,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::catalog::{Catalog, ClusterSummary};
+    use crate::clustering::ClusterId;
     use notebook_core::types::{CausalPosition, EntryId};
 
     fn make_test_catalog(entropy: f64) -> Catalog {
         Catalog {
             clusters: vec![ClusterSummary {
+                cluster_id: ClusterId::new(1),
                 topic: "test topic".to_string(),
                 summary: "Test summary.".to_string(),
                 entry_count: 5,
                 cumulative_cost: 1.5,
                 stability: 10,
                 representative_entry_ids: vec![EntryId::new()],
+                pinned: false,
             }],
             notebook_entropy: entropy,
             total_entries: 100,
             generated_at: CausalPosition::first(),
+            locale: Default::default(),
         }
     }
 
@@ -705,6 +745,26 @@ mod tests {
         assert_eq!(cached.cached_at_sequence, 2);
     }
 
+    #[test]
+    fn catalog_cache_get_current_counts_hits_and_misses() {
+        let cache = CatalogCache::new();
+        let notebook_id = NotebookId::new();
+
+        assert!(cache.get_current(&notebook_id, 1).is_none());
+        cache.set(notebook_id, make_test_catalog(1.0), 1);
+        assert!(cache.get_current(&notebook_id, 1).is_some());
+        // Written to since it was cached
+        assert!(cache.get_current(&notebook_id, 2).is_none());
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 2);
+
+        // Clones share the counters
+        cache.clone().get_current(&notebook_id, 1);
+        assert_eq!(cache.stats().hits, 2);
+    }
+
     #[test]
     fn cache_status_equality() {
         assert_eq!(CacheStatus::Fresh, CacheStatus::Fresh);
@@ -721,6 +781,8 @@ mod tests {
         assert_eq!(stats.fresh, 0);
         assert_eq!(stats.stale, 0);
         assert_eq!(stats.expired, 0);
+        assert_eq!(stats.hits, 0);
+        assert_eq!(stats.misses, 0);
     }
 
     #[test]
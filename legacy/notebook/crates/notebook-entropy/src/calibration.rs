@@ -560,7 +560,7 @@ mod tests {
         }
 
         // Mean should be approximately 1_000_000 + 0.0495
-        assert!((calibrator.mean() - 1_000_000.0495).abs() < 1e-6);
+        assert!((calibrator.mean() - 1_000_000.049_5).abs() < 1e-6);
 
         // Stddev should be small (based on the 0.001 increments)
         assert!(calibrator.stddev() > 0.0);
@@ -12,6 +12,45 @@
 //! 3. Truncate to fit the token budget
 //! 4. Return the Catalog with overall entropy metrics
 //!
+//! ## Pinned Entries
+//!
+//! Entries passed to [`CatalogGenerator::with_pinned`] are always listed
+//! among their cluster's representatives, ahead of the unpinned ones and
+//! beyond the usual cap. Clusters holding a pinned entry sort ahead of all
+//! others under every [`CatalogSort`], and are never truncated away.
+//!
+//! A generated catalog can be re-sorted with [`Catalog::sort_clusters`] and
+//! paged with [`Catalog::page`] without summarizing the clusters again.
+//!
+//! ## Digest
+//!
+//! [`Catalog::digest`] renders the catalog as a single paragraph of prose
+//! for agents with tight context budgets: an overview sentence, then one
+//! sentence per cluster in order of cumulative cost, until the token budget
+//! is spent. Tokens are estimated at ~4 characters each.
+//!
+//! ## Locale
+//!
+//! Text the catalog writes itself (the digest's sentences and the fallback
+//! summaries of clusters without text) follows the generator's
+//! [`Locale`], set with [`CatalogGenerator::with_locale`]. Topic keywords
+//! come from the snapshot, whose [`ClusteringConfig`](crate::clustering::ClusteringConfig)
+//! selects the stop words; both should name the notebook's language.
+//!
+//! ## Summary Modes
+//!
+//! How a cluster's `summary` is written is chosen with
+//! [`CatalogGenerator::with_summary_mode`]:
+//!
+//! - [`SummaryMode::Extractive`] (default): the first sentence of the
+//!   cluster's first text entry.
+//! - [`SummaryMode::Keyword`]: the cluster's topic keywords as a
+//!   comma-separated list.
+//! - [`SummaryMode::RepresentativeSentence`]: the sentence, from any text
+//!   entry of the cluster, most central to the others by TF-IDF cosine
+//!   similarity. Denser than the first sentence and more uniform across
+//!   clusters, which suits LLM prompts.
+//!
 //! ## Token Budget
 //!
 //! Each ClusterSummary is estimated at ~75 tokens. The default budget
@@ -19,11 +58,14 @@
 //!
 //! Owned by: agent-catalog (Task 3-1)
 
-use crate::clustering::Cluster;
+use crate::clustering::{Cluster, ClusterId};
 use crate::coherence::CoherenceSnapshot;
+use crate::text_extraction::extract_text;
+use crate::tfidf::{CorpusStats, Locale, TfIdfVector, tokenize_in};
 use notebook_core::types::{CausalPosition, Entry, EntryId};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 
 /// Default maximum tokens for catalog generation.
 pub const DEFAULT_MAX_TOKENS: usize = 4000;
@@ -40,6 +82,17 @@ const MAX_REPRESENTATIVE_ENTRIES: usize = 3;
 /// Maximum keywords to include in topic.
 const MAX_TOPIC_KEYWORDS: usize = 3;
 
+/// Maximum keywords listed in a [`SummaryMode::Keyword`] summary.
+const MAX_SUMMARY_KEYWORDS: usize = 8;
+
+/// Estimated characters per token of prose.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Estimates the number of tokens in `text`, rounding up.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
 /// A dense catalog of notebook contents.
 ///
 /// The catalog provides a quick overview of notebook structure, showing
@@ -59,11 +112,18 @@ pub struct Catalog {
 
     /// Causal position when this catalog was generated.
     pub generated_at: CausalPosition,
+
+    /// Language of the text the catalog writes itself.
+    #[serde(default)]
+    pub locale: Locale,
 }
 
 /// Summary of a single cluster for the catalog.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClusterSummary {
+    /// The cluster; stable across rebuilds from the same entries.
+    pub cluster_id: ClusterId,
+
     /// Topic extracted from cluster keywords.
     pub topic: String,
 
@@ -76,11 +136,261 @@ pub struct ClusterSummary {
     /// Total integration cost caused by entries in this cluster.
     pub cumulative_cost: f64,
 
-    /// Entries since last cluster modification (higher = more stable).
+    /// Entries written to the notebook since this cluster last gained a
+    /// member (higher = more stable). Zero for a cluster the latest write
+    /// joined. Says how long the cluster has been quiet, not how much it
+    /// moved while active; see [`crate::history`] for churn.
     pub stability: u64,
 
-    /// Representative entry IDs from this cluster.
+    /// Representative entry IDs from this cluster, pinned entries first.
     pub representative_entry_ids: Vec<EntryId>,
+
+    /// Whether the cluster holds a pinned entry.
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// A catalog rendered as prose; see [`Catalog::digest`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogDigest {
+    /// The digest text.
+    pub text: String,
+
+    /// Number of clusters described in the text.
+    pub clusters_included: usize,
+}
+
+/// How cluster summaries are written; see the [module docs](self#summary-modes).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryMode {
+    /// First sentence of the cluster's first text entry.
+    #[default]
+    Extractive,
+    /// The cluster's topic keywords, comma-separated.
+    Keyword,
+    /// The cluster's most central sentence by TF-IDF similarity.
+    RepresentativeSentence,
+}
+
+impl SummaryMode {
+    /// Looks up a mode by name, case-insensitively.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "extractive" => Some(Self::Extractive),
+            "keyword" => Some(Self::Keyword),
+            "representative_sentence" => Some(Self::RepresentativeSentence),
+            _ => None,
+        }
+    }
+
+    /// The mode's name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Extractive => "extractive",
+            Self::Keyword => "keyword",
+            Self::RepresentativeSentence => "representative_sentence",
+        }
+    }
+}
+
+/// Order in which catalog clusters are listed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CatalogSort {
+    /// Highest cumulative cost first, then most stable.
+    #[default]
+    Cost,
+    /// Most entries first, then highest cumulative cost.
+    Size,
+    /// Most recently modified (least stable) first, then highest cumulative cost.
+    Recency,
+}
+
+impl CatalogSort {
+    /// Compares two cluster summaries under this ordering.
+    ///
+    /// Pinned clusters come first. Ties are broken by topic so pages over
+    /// the same catalog are stable.
+    fn compare(self, a: &ClusterSummary, b: &ClusterSummary) -> Ordering {
+        let by_cost = || {
+            b.cumulative_cost
+                .partial_cmp(&a.cumulative_cost)
+                .unwrap_or(Ordering::Equal)
+        };
+
+        let by_sort = || match self {
+            CatalogSort::Cost => by_cost().then_with(|| b.stability.cmp(&a.stability)),
+            CatalogSort::Size => b.entry_count.cmp(&a.entry_count).then_with(by_cost),
+            CatalogSort::Recency => a.stability.cmp(&b.stability).then_with(by_cost),
+        };
+
+        b.pinned
+            .cmp(&a.pinned)
+            .then_with(by_sort)
+            .then_with(|| a.topic.cmp(&b.topic))
+    }
+}
+
+impl Catalog {
+    /// Reorders the clusters in place.
+    pub fn sort_clusters(&mut self, sort: CatalogSort) {
+        self.clusters.sort_by(|a, b| sort.compare(a, b));
+    }
+
+    /// Returns the window of clusters starting at `offset`, at most `limit` long.
+    pub fn page(&self, offset: usize, limit: usize) -> &[ClusterSummary] {
+        let start = offset.min(self.clusters.len());
+        let end = start.saturating_add(limit).min(self.clusters.len());
+        &self.clusters[start..end]
+    }
+
+    /// Renders the catalog as a prose digest of at most `max_tokens` tokens.
+    ///
+    /// Clusters are described in [`CatalogSort::Cost`] order, one sentence
+    /// each. Description stops at the first cluster that does not fit, so a
+    /// digest never mentions a cluster while leaving out a costlier one; a
+    /// closing sentence counts the clusters left out, if it fits.
+    pub fn digest(&self, max_tokens: usize) -> CatalogDigest {
+        let mut clusters: Vec<&ClusterSummary> = self.clusters.iter().collect();
+        clusters.sort_by(|a, b| CatalogSort::Cost.compare(a, b));
+
+        let mut text = String::new();
+        // Appends a sentence if the text stays within budget
+        let push = |text: &mut String, sentence: &str| {
+            let candidate = if text.is_empty() {
+                sentence.to_string()
+            } else {
+                format!("{} {}", text, sentence)
+            };
+            let fits = estimate_tokens(&candidate) <= max_tokens;
+            if fits {
+                *text = candidate;
+            }
+            fits
+        };
+
+        let phrasing = Phrasing(self.locale);
+        let overview = phrasing.overview(self.total_entries, clusters.len(), self.notebook_entropy);
+        if !push(&mut text, &overview) {
+            return CatalogDigest {
+                text,
+                clusters_included: 0,
+            };
+        }
+
+        let mut clusters_included = 0;
+        for (rank, cluster) in clusters.iter().enumerate() {
+            let sentence = phrasing.cluster(rank, cluster);
+            if !push(&mut text, &sentence) {
+                break;
+            }
+            clusters_included += 1;
+        }
+
+        let omitted = clusters.len() - clusters_included;
+        if omitted > 0 {
+            push(&mut text, &phrasing.omitted(omitted));
+        }
+
+        CatalogDigest {
+            text,
+            clusters_included,
+        }
+    }
+}
+
+/// Sentences the catalog writes itself, in one language.
+#[derive(Debug, Clone, Copy)]
+struct Phrasing(Locale);
+
+impl Phrasing {
+    /// Opening sentence of a digest.
+    fn overview(self, entries: u32, topics: usize, entropy: f64) -> String {
+        match self.0 {
+            Locale::English => format!(
+                "This notebook holds {} entries in {} topics, with a total entropy of {:.2}.",
+                entries, topics, entropy
+            ),
+            Locale::French => format!(
+                "Ce carnet contient {} entrées réparties en {} sujets, pour une entropie totale de {:.2}.",
+                entries, topics, entropy
+            ),
+        }
+    }
+
+    /// Digest sentence for the cluster at `rank` in cost order.
+    fn cluster(self, rank: usize, cluster: &ClusterSummary) -> String {
+        let (lead, untitled) = match (self.0, rank) {
+            (Locale::English, 0) => ("Most significant is", "an untitled topic"),
+            (Locale::English, _) => ("Next is", "an untitled topic"),
+            (Locale::French, 0) => ("Le plus important est", "un sujet sans titre"),
+            (Locale::French, _) => ("Vient ensuite", "un sujet sans titre"),
+        };
+        let topic = if cluster.topic.is_empty() {
+            untitled
+        } else {
+            &cluster.topic
+        };
+        let counts = match self.0 {
+            Locale::English => format!(
+                "{} entries, cost {:.2}",
+                cluster.entry_count, cluster.cumulative_cost
+            ),
+            Locale::French => format!(
+                "{} entrées, coût {:.2}",
+                cluster.entry_count, cluster.cumulative_cost
+            ),
+        };
+        format!(
+            "{} {} ({}): {}",
+            lead,
+            topic,
+            counts,
+            self.as_sentence(&cluster.summary)
+        )
+    }
+
+    /// Closing sentence counting the clusters a digest left out.
+    fn omitted(self, count: usize) -> String {
+        match self.0 {
+            Locale::English => format!("{} lower-cost topics are not described.", count),
+            Locale::French => format!("{} sujets de moindre coût ne sont pas décrits.", count),
+        }
+    }
+
+    /// Topic of the uncategorized cluster.
+    fn uncategorized_topic(self) -> String {
+        match self.0 {
+            Locale::English => "uncategorized".to_string(),
+            Locale::French => "non classé".to_string(),
+        }
+    }
+
+    /// Summary of a cluster without text entries.
+    fn untitled_summary(self, size: usize, keyword: Option<&String>) -> String {
+        match (self.0, keyword) {
+            (Locale::English, None) => format!("[{} entries]", size),
+            (Locale::English, Some(keyword)) => format!("[{} entries about {}]", size, keyword),
+            (Locale::French, None) => format!("[{} entrées]", size),
+            (Locale::French, Some(keyword)) => format!("[{} entrées sur {}]", size, keyword),
+        }
+    }
+
+    /// Trims a summary and makes sure it ends like a sentence.
+    fn as_sentence(self, summary: &str) -> String {
+        let summary = summary.trim();
+        if summary.is_empty() {
+            match self.0 {
+                Locale::English => "no text summary.".to_string(),
+                Locale::French => "pas de résumé textuel.".to_string(),
+            }
+        } else if summary.ends_with(['.', '!', '?', ']']) {
+            summary.to_string()
+        } else {
+            format!("{}.", summary)
+        }
+    }
 }
 
 /// Generator for creating catalogs from coherence snapshots.
@@ -97,19 +407,46 @@ pub struct ClusterSummary {
 pub struct CatalogGenerator {
     /// Token budget for generated catalogs.
     max_tokens: usize,
+    /// Entries always listed as representatives of their cluster.
+    pinned: HashSet<EntryId>,
+    /// Language of the text the generator writes itself.
+    locale: Locale,
+    /// How cluster summaries are written.
+    summary_mode: SummaryMode,
 }
 
 impl CatalogGenerator {
     /// Creates a new CatalogGenerator with the default token budget.
     pub fn new() -> Self {
-        Self {
-            max_tokens: DEFAULT_MAX_TOKENS,
-        }
+        Self::with_max_tokens(DEFAULT_MAX_TOKENS)
     }
 
     /// Creates a CatalogGenerator with a custom token budget.
     pub fn with_max_tokens(max_tokens: usize) -> Self {
-        Self { max_tokens }
+        Self {
+            max_tokens,
+            pinned: HashSet::new(),
+            locale: Locale::default(),
+            summary_mode: SummaryMode::default(),
+        }
+    }
+
+    /// Sets the pinned entries, which are exempt from truncation.
+    pub fn with_pinned(mut self, pinned: impl IntoIterator<Item = EntryId>) -> Self {
+        self.pinned = pinned.into_iter().collect();
+        self
+    }
+
+    /// Sets the language of generated summaries and digests.
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Sets how cluster summaries are written.
+    pub fn with_summary_mode(mut self, summary_mode: SummaryMode) -> Self {
+        self.summary_mode = summary_mode;
+        self
     }
 
     /// Sets the maximum token budget.
@@ -133,6 +470,7 @@ impl CatalogGenerator {
     /// # Returns
     ///
     /// A Catalog with cluster summaries truncated to fit the token budget.
+    /// Clusters holding a pinned entry are kept even past the budget.
     pub fn generate(
         &self,
         snapshot: &CoherenceSnapshot,
@@ -140,38 +478,52 @@ impl CatalogGenerator {
         max_tokens: Option<usize>,
     ) -> Catalog {
         let budget = max_tokens.unwrap_or(self.max_tokens);
-        let max_clusters = budget / TOKENS_PER_SUMMARY;
 
+        let mut catalog = self.generate_all(snapshot, entries);
+
+        // Truncate to fit token budget; pinned clusters sort first and are kept
+        let pinned_clusters = catalog.clusters.iter().filter(|c| c.pinned).count();
+        catalog
+            .clusters
+            .truncate(Self::clusters_within(budget).max(pinned_clusters));
+
+        // Compute overall notebook entropy
+        catalog.notebook_entropy = catalog.clusters.iter().map(|s| s.cumulative_cost).sum();
+
+        catalog
+    }
+
+    /// Generates a catalog summarizing every cluster, ignoring the token budget.
+    ///
+    /// Clusters are ordered by [`CatalogSort::Cost`]. Callers that page
+    /// through the catalog should bound each page with [`Self::clusters_within`].
+    pub fn generate_all(&self, snapshot: &CoherenceSnapshot, entries: &[Entry]) -> Catalog {
         // Build entry lookup for efficient access
         let entry_map: HashMap<EntryId, &Entry> = entries.iter().map(|e| (e.id, e)).collect();
 
         // Generate summaries for each cluster
-        let mut summaries: Vec<ClusterSummary> = snapshot
+        let summaries: Vec<ClusterSummary> = snapshot
             .clusters
             .iter()
             .map(|cluster| self.summarize_cluster(cluster, &entry_map, snapshot))
             .collect();
 
-        // Sort by cumulative_cost DESC, then stability DESC
-        summaries.sort_by(|a, b| {
-            b.cumulative_cost
-                .partial_cmp(&a.cumulative_cost)
-                .unwrap_or(std::cmp::Ordering::Equal)
-                .then_with(|| b.stability.cmp(&a.stability))
-        });
-
-        // Truncate to fit token budget
-        summaries.truncate(max_clusters);
-
-        // Compute overall notebook entropy
         let notebook_entropy: f64 = summaries.iter().map(|s| s.cumulative_cost).sum();
 
-        Catalog {
+        let mut catalog = Catalog {
             clusters: summaries,
             notebook_entropy,
             total_entries: snapshot.entry_count() as u32,
             generated_at: snapshot.timestamp,
-        }
+            locale: self.locale,
+        };
+        catalog.sort_clusters(CatalogSort::Cost);
+        catalog
+    }
+
+    /// Returns how many cluster summaries fit within a token budget.
+    pub fn clusters_within(max_tokens: usize) -> usize {
+        max_tokens / TOKENS_PER_SUMMARY
     }
 
     /// Summarizes a single cluster.
@@ -181,17 +533,32 @@ impl CatalogGenerator {
         entry_map: &HashMap<EntryId, &Entry>,
         snapshot: &CoherenceSnapshot,
     ) -> ClusterSummary {
-        // Extract topic from keywords
-        let topic = cluster
-            .topic_keywords
-            .iter()
-            .take(MAX_TOPIC_KEYWORDS)
-            .cloned()
-            .collect::<Vec<_>>()
-            .join(", ");
-
-        // Extract summary from first text entry
-        let summary = self.extract_summary(cluster, entry_map);
+        // Extract topic from keywords, and summary from first text entry.
+        // The uncategorized cluster's entries share no topic, so no one
+        // entry can stand for them.
+        let (topic, summary) = if cluster.uncategorized {
+            let phrasing = Phrasing(self.locale);
+            (
+                phrasing.uncategorized_topic(),
+                phrasing.untitled_summary(cluster.size(), None),
+            )
+        } else {
+            let topic = cluster
+                .topic_keywords
+                .iter()
+                .take(MAX_TOPIC_KEYWORDS)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ");
+            let summary = match self.summary_mode {
+                SummaryMode::Extractive => self.extract_summary(cluster, entry_map),
+                SummaryMode::Keyword => self.keyword_summary(cluster),
+                SummaryMode::RepresentativeSentence => {
+                    self.representative_sentence(cluster, entry_map)
+                }
+            };
+            (topic, summary)
+        };
 
         // Compute cumulative cost from all entries in cluster
         let cumulative_cost = self.compute_cumulative_cost(cluster, entry_map);
@@ -200,45 +567,132 @@ impl CatalogGenerator {
         let stability = self.compute_stability(cluster, entry_map, snapshot);
 
         // Get representative entry IDs
-        let representative_entry_ids: Vec<EntryId> = cluster
-            .entry_ids
-            .iter()
-            .take(MAX_REPRESENTATIVE_ENTRIES)
-            .copied()
-            .collect();
+        let representative_entry_ids = self.representatives(cluster, entry_map);
+        let pinned = representative_entry_ids
+            .first()
+            .is_some_and(|id| self.pinned.contains(id));
 
         ClusterSummary {
+            cluster_id: cluster.id,
             topic,
             summary,
             entry_count: cluster.size() as u32,
             cumulative_cost,
             stability,
             representative_entry_ids,
+            pinned,
         }
     }
 
+    /// Picks a cluster's representative entries.
+    ///
+    /// Candidates are ranked by [`representative_order`], so the choice does
+    /// not depend on the order of the cluster's entry list. Every pinned
+    /// entry is included, even past the cap; remaining slots are filled with
+    /// the top-ranked unpinned entries.
+    fn representatives(
+        &self,
+        cluster: &Cluster,
+        entry_map: &HashMap<EntryId, &Entry>,
+    ) -> Vec<EntryId> {
+        let mut candidates = cluster.entry_ids.clone();
+        candidates.sort_by(|a, b| representative_order(*a, *b, entry_map));
+
+        let (pinned, unpinned): (Vec<EntryId>, Vec<EntryId>) = candidates
+            .into_iter()
+            .partition(|id| self.pinned.contains(id));
+
+        let fill = MAX_REPRESENTATIVE_ENTRIES.saturating_sub(pinned.len());
+        pinned
+            .into_iter()
+            .chain(unpinned.into_iter().take(fill))
+            .collect()
+    }
+
     /// Extracts a summary from the first text entry in the cluster.
     fn extract_summary(&self, cluster: &Cluster, entry_map: &HashMap<EntryId, &Entry>) -> String {
         // Find first text entry
         for entry_id in &cluster.entry_ids {
             if let Some(entry) = entry_map.get(entry_id)
-                && entry.content_type.starts_with("text/")
+                && let Some(text) = extract_text(&entry.content, &entry.content_type)
             {
-                let text = String::from_utf8_lossy(&entry.content);
                 return self.extract_first_sentence(&text);
             }
         }
 
         // Fallback for non-text clusters
+        Phrasing(self.locale).untitled_summary(cluster.size(), cluster.topic_keywords.first())
+    }
+
+    /// Lists the cluster's topic keywords.
+    fn keyword_summary(&self, cluster: &Cluster) -> String {
         if cluster.topic_keywords.is_empty() {
-            format!("[{} entries]", cluster.size())
-        } else {
-            format!(
-                "[{} entries about {}]",
-                cluster.size(),
-                cluster.topic_keywords.first().unwrap_or(&String::new())
-            )
+            return Phrasing(self.locale).untitled_summary(cluster.size(), None);
         }
+        cluster
+            .topic_keywords
+            .iter()
+            .take(MAX_SUMMARY_KEYWORDS)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Picks the sentence of the cluster's text most similar to the others.
+    ///
+    /// Each sentence of each text entry is a document of a corpus; the one
+    /// with the highest mean TF-IDF cosine similarity to the other sentences
+    /// wins, the earliest on ties.
+    fn representative_sentence(
+        &self,
+        cluster: &Cluster,
+        entry_map: &HashMap<EntryId, &Entry>,
+    ) -> String {
+        let texts: Vec<String> = cluster
+            .entry_ids
+            .iter()
+            .filter_map(|id| entry_map.get(id))
+            .filter_map(|entry| extract_text(&entry.content, &entry.content_type))
+            .collect();
+        let sentences: Vec<(&str, Vec<String>)> = texts
+            .iter()
+            .flat_map(|text| split_sentences(text))
+            .map(|sentence| (sentence, tokenize_in(sentence, self.locale)))
+            .filter(|(_, tokens)| !tokens.is_empty())
+            .collect();
+
+        if sentences.is_empty() {
+            return self.extract_summary(cluster, entry_map);
+        }
+
+        let mut corpus = CorpusStats::new();
+        for (_, tokens) in &sentences {
+            corpus.add_document(tokens);
+        }
+        let vectors: Vec<TfIdfVector> = sentences
+            .iter()
+            .map(|(_, tokens)| TfIdfVector::from_tokens(tokens, &corpus))
+            .collect();
+
+        let centrality = |i: usize| -> f64 {
+            vectors
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, other)| vectors[i].cosine_similarity(other))
+                .sum()
+        };
+        let mut best = 0;
+        let mut best_score = centrality(0);
+        for i in 1..sentences.len() {
+            let score = centrality(i);
+            if score > best_score {
+                best = i;
+                best_score = score;
+            }
+        }
+
+        self.extract_first_sentence(sentences[best].0)
     }
 
     /// Extracts the first sentence or truncated content.
@@ -318,6 +772,54 @@ impl CatalogGenerator {
     }
 }
 
+/// Splits text into sentences at `.`, `!` or `?` followed by whitespace,
+/// and at line breaks.
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let end = match c {
+            '\n' => Some(i),
+            '.' | '!' | '?' if chars.peek().is_none_or(|(_, next)| next.is_whitespace()) => {
+                Some(i + c.len_utf8())
+            }
+            _ => None,
+        };
+        if let Some(end) = end {
+            sentences.push(&text[start..end]);
+            start = end;
+        }
+    }
+    sentences.push(&text[start..]);
+    sentences
+        .into_iter()
+        .map(str::trim)
+        .filter(|sentence| !sentence.is_empty())
+        .collect()
+}
+
+/// Ranks candidate representatives: highest integration cost first, then
+/// earliest sequence, then entry ID. Entries missing from `entry_map` rank
+/// last.
+fn representative_order(a: EntryId, b: EntryId, entry_map: &HashMap<EntryId, &Entry>) -> Ordering {
+    let key = |id: EntryId| {
+        entry_map
+            .get(&id)
+            .map(|e| (e.integration_cost.catalog_shift, e.causal_position.sequence))
+    };
+
+    match (key(a), key(b)) {
+        (Some((cost_a, seq_a)), Some((cost_b, seq_b))) => {
+            cost_b.total_cmp(&cost_a).then(seq_a.cmp(&seq_b))
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+    .then_with(|| a.0.cmp(&b.0))
+}
+
 impl Default for CatalogGenerator {
     fn default() -> Self {
         Self::new()
@@ -327,7 +829,7 @@ impl Default for CatalogGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::clustering::ClusterId;
+    use crate::clustering::ClusteringConfig;
     use notebook_core::types::{AuthorId, EntryBuilder, IntegrationCost};
 
     fn make_text_entry(content: &str, sequence: u64) -> Entry {
@@ -352,6 +854,7 @@ mod tests {
             topic_keywords: keywords.iter().map(|s| s.to_string()).collect(),
             entry_ids,
             reference_density: 1.0,
+            uncategorized: false,
         }
     }
 
@@ -499,7 +1002,7 @@ mod tests {
         let mut snapshot = CoherenceSnapshot::new();
 
         for i in 0..100 {
-            let entry = make_text_entry(&format!("Entry {}", i), i as u64);
+            let entry = make_text_entry(&format!("Entry {}", i), i);
             let entry_id = entry.id;
             entries.push(entry);
 
@@ -511,7 +1014,7 @@ mod tests {
         let catalog = generator.generate(&snapshot, &entries, Some(300)); // ~4 clusters
 
         assert!(catalog.clusters.len() <= 4);
-        assert!(catalog.clusters.len() > 0);
+        assert!(!catalog.clusters.is_empty());
     }
 
     #[test]
@@ -539,15 +1042,116 @@ mod tests {
         assert!(catalog.clusters[0].summary.contains("entries"));
     }
 
+    /// A baking cluster whose first sentence is off-topic.
+    fn baking_cluster() -> (CoherenceSnapshot, Vec<Entry>) {
+        let entries = vec![
+            make_text_entry(
+                "Our team met on Tuesday. Sourdough starter needs daily flour feeding.",
+                1,
+            ),
+            make_text_entry(
+                "Feed the sourdough starter flour and water. Ovens preheat slowly.",
+                2,
+            ),
+            make_text_entry("A sourdough starter doubles after feeding with flour.", 3),
+        ];
+        let cluster = make_cluster(
+            0,
+            &["sourdough", "starter", "flour", "feeding", "water"],
+            entries.iter().map(|e| e.id).collect(),
+        );
+        let mut snapshot = CoherenceSnapshot::new();
+        snapshot.clusters.push(cluster);
+        (snapshot, entries)
+    }
+
+    fn summary_in(mode: SummaryMode) -> String {
+        let (snapshot, entries) = baking_cluster();
+        let catalog = CatalogGenerator::new()
+            .with_summary_mode(mode)
+            .generate(&snapshot, &entries, None);
+        catalog.clusters[0].summary.clone()
+    }
+
+    #[test]
+    fn extractive_summary_is_first_sentence() {
+        assert_eq!(
+            summary_in(SummaryMode::Extractive),
+            "Our team met on Tuesday."
+        );
+    }
+
+    #[test]
+    fn keyword_summary_lists_cluster_keywords() {
+        assert_eq!(
+            summary_in(SummaryMode::Keyword),
+            "sourdough, starter, flour, feeding, water"
+        );
+    }
+
+    #[test]
+    fn representative_sentence_is_most_central() {
+        assert_eq!(
+            summary_in(SummaryMode::RepresentativeSentence),
+            "A sourdough starter doubles after feeding with flour."
+        );
+    }
+
+    #[test]
+    fn representative_sentence_falls_back_without_text() {
+        let entry = EntryBuilder::default()
+            .content(vec![0xFF, 0xD8, 0xFF, 0xE0])
+            .content_type("image/jpeg")
+            .author(AuthorId::zero())
+            .build();
+        let mut snapshot = CoherenceSnapshot::new();
+        snapshot
+            .clusters
+            .push(make_cluster(0, &["image"], vec![entry.id]));
+
+        let catalog = CatalogGenerator::new()
+            .with_summary_mode(SummaryMode::RepresentativeSentence)
+            .generate(&snapshot, &[entry], None);
+        assert!(catalog.clusters[0].summary.contains("entries"));
+    }
+
+    #[test]
+    fn split_sentences_at_terminators_and_line_breaks() {
+        assert_eq!(
+            split_sentences("Pi is 3.14 roughly. Really?\nYes!  "),
+            vec!["Pi is 3.14 roughly.", "Really?", "Yes!"]
+        );
+        assert!(split_sentences("  ").is_empty());
+    }
+
+    #[test]
+    fn summary_mode_names_round_trip() {
+        for mode in [
+            SummaryMode::Extractive,
+            SummaryMode::Keyword,
+            SummaryMode::RepresentativeSentence,
+        ] {
+            assert_eq!(SummaryMode::from_name(mode.name()), Some(mode));
+            assert_eq!(serde_json::to_value(mode).unwrap(), mode.name());
+        }
+        assert_eq!(
+            SummaryMode::from_name(" Keyword "),
+            Some(SummaryMode::Keyword)
+        );
+        assert_eq!(SummaryMode::from_name("abstractive"), None);
+    }
+
     #[test]
     fn cluster_summary_serialization() {
         let summary = ClusterSummary {
+            cluster_id: ClusterId::new(3),
             topic: "test topic".to_string(),
             summary: "Test summary text.".to_string(),
             entry_count: 5,
             cumulative_cost: 1.5,
             stability: 10,
             representative_entry_ids: vec![EntryId::new()],
+            pinned: false,
         };
 
         let json = serde_json::to_string(&summary).unwrap();
@@ -566,6 +1170,7 @@ mod tests {
             notebook_entropy: 5.5,
             total_entries: 100,
             generated_at: CausalPosition::first(),
+            locale: Locale::default(),
         };
 
         let json = serde_json::to_string(&catalog).unwrap();
@@ -606,6 +1211,158 @@ mod tests {
         assert!(catalog.clusters[0].representative_entry_ids.len() <= MAX_REPRESENTATIVE_ENTRIES);
     }
 
+    #[test]
+    fn representatives_ranked_by_cost_then_sequence_then_id() {
+        let mut entries: Vec<Entry> = (0..5)
+            .map(|i| make_text_entry(&format!("Entry {}", i), i))
+            .collect();
+        entries[4].integration_cost.catalog_shift = 0.9;
+        // Same cost and sequence as entries[1]: the smaller id wins
+        entries[2].causal_position.sequence = 1;
+        let (first_tie, second_tie) = if entries[1].id.0 < entries[2].id.0 {
+            (entries[1].id, entries[2].id)
+        } else {
+            (entries[2].id, entries[1].id)
+        };
+        // Unknown entries rank last
+        let unknown = EntryId::new();
+
+        let mut ids: Vec<EntryId> = entries.iter().map(|e| e.id).collect();
+        ids.insert(0, unknown);
+        let mut snapshot = CoherenceSnapshot::new();
+        snapshot.clusters.push(make_cluster(0, &["test"], ids));
+
+        let catalog = CatalogGenerator::new().generate(&snapshot, &entries, None);
+        assert_eq!(
+            catalog.clusters[0].representative_entry_ids,
+            vec![entries[4].id, entries[0].id, first_tie]
+        );
+        assert!(
+            !catalog.clusters[0]
+                .representative_entry_ids
+                .contains(&second_tie)
+        );
+    }
+
+    #[test]
+    fn representatives_identical_across_generations() {
+        let entries: Vec<Entry> = (0..12)
+            .map(|i| {
+                let mut entry = make_text_entry(&format!("Entry {}", i), i % 4);
+                entry.integration_cost.catalog_shift = (i % 3) as f64 * 0.25;
+                entry
+            })
+            .collect();
+        let ids: Vec<EntryId> = entries.iter().map(|e| e.id).collect();
+
+        // Same cluster, member list in a different order each time
+        let generate = |order: Vec<EntryId>| {
+            let mut snapshot = CoherenceSnapshot::new();
+            snapshot.clusters.push(make_cluster(0, &["test"], order));
+            let catalog = CatalogGenerator::new().generate(&snapshot, &entries, None);
+            serde_json::to_vec(&catalog.clusters[0].representative_entry_ids).unwrap()
+        };
+
+        let forward = generate(ids.clone());
+        let reversed = generate(ids.iter().rev().copied().collect());
+        let mut rotated = ids.clone();
+        rotated.rotate_left(5);
+
+        assert_eq!(forward, reversed);
+        assert_eq!(forward, generate(rotated));
+        assert_eq!(forward, generate(ids));
+    }
+
+    #[test]
+    fn pinned_entry_is_always_representative() {
+        let mut entries = Vec::new();
+        let mut entry_ids = Vec::new();
+        for i in 0..10 {
+            let entry = make_text_entry(&format!("Entry {}", i), i as u64);
+            entry_ids.push(entry.id);
+            entries.push(entry);
+        }
+        // Past the representative cap, so it would normally be left out
+        let pinned = entry_ids[7];
+
+        let mut snapshot = CoherenceSnapshot::new();
+        snapshot
+            .clusters
+            .push(make_cluster(0, &["test"], entry_ids));
+
+        let unpinned = CatalogGenerator::new().generate(&snapshot, &entries, None);
+        assert!(
+            !unpinned.clusters[0]
+                .representative_entry_ids
+                .contains(&pinned)
+        );
+
+        let generator = CatalogGenerator::new().with_pinned([pinned]);
+        let catalog = generator.generate(&snapshot, &entries, None);
+        let summary = &catalog.clusters[0];
+        assert!(summary.pinned);
+        assert_eq!(summary.representative_entry_ids[0], pinned);
+        assert_eq!(
+            summary.representative_entry_ids.len(),
+            MAX_REPRESENTATIVE_ENTRIES
+        );
+    }
+
+    #[test]
+    fn pinned_entries_exceed_representative_cap() {
+        let entries: Vec<Entry> = (0..6)
+            .map(|i| make_text_entry(&format!("Entry {}", i), i))
+            .collect();
+        let entry_ids: Vec<EntryId> = entries.iter().map(|e| e.id).collect();
+
+        let mut snapshot = CoherenceSnapshot::new();
+        snapshot
+            .clusters
+            .push(make_cluster(0, &["test"], entry_ids.clone()));
+
+        // Every pinned entry is listed, even past the cap
+        let generator = CatalogGenerator::new().with_pinned(entry_ids[1..5].iter().copied());
+        let catalog = generator.generate(&snapshot, &entries, None);
+        assert_eq!(
+            catalog.clusters[0].representative_entry_ids,
+            entry_ids[1..5]
+        );
+    }
+
+    #[test]
+    fn pinned_cluster_survives_budget_truncation() {
+        let mut entries = Vec::new();
+        let mut snapshot = CoherenceSnapshot::new();
+        for i in 0..100 {
+            let entry = make_text_entry(&format!("Entry {}", i), i);
+            snapshot
+                .clusters
+                .push(make_cluster(i, &[&format!("topic{}", i)], vec![entry.id]));
+            entries.push(entry);
+        }
+        // Zero cost sorts this cluster last
+        entries[42].integration_cost.catalog_shift = 0.0;
+        let pinned = entries[42].id;
+
+        let unpinned = CatalogGenerator::new().generate(&snapshot, &entries, Some(300));
+        assert!(
+            unpinned
+                .clusters
+                .iter()
+                .all(|c| !c.representative_entry_ids.contains(&pinned))
+        );
+
+        let generator = CatalogGenerator::new().with_pinned([pinned]);
+        let catalog = generator.generate(&snapshot, &entries, Some(300));
+        assert_eq!(catalog.clusters.len(), 4);
+        assert_eq!(catalog.clusters[0].representative_entry_ids, vec![pinned]);
+
+        // Pinned clusters are kept even when they alone exceed the budget
+        let catalog = generator.generate(&snapshot, &entries, Some(0));
+        assert_eq!(catalog.clusters.len(), 1);
+        assert!(catalog.clusters[0].pinned);
+    }
+
     #[test]
     fn cumulative_cost_sums_entries() {
         let generator = CatalogGenerator::new();
@@ -628,6 +1385,205 @@ mod tests {
         assert!((catalog.clusters[0].cumulative_cost - 1.0).abs() < 0.001);
     }
 
+    fn make_summary(topic: &str, entry_count: u32, cost: f64, stability: u64) -> ClusterSummary {
+        ClusterSummary {
+            cluster_id: ClusterId::new(entry_count as u64),
+            topic: topic.to_string(),
+            summary: String::new(),
+            entry_count,
+            cumulative_cost: cost,
+            stability,
+            representative_entry_ids: vec![],
+            pinned: false,
+        }
+    }
+
+    fn make_catalog(clusters: Vec<ClusterSummary>) -> Catalog {
+        Catalog {
+            clusters,
+            notebook_entropy: 0.0,
+            total_entries: 0,
+            generated_at: CausalPosition::first(),
+            locale: Locale::default(),
+        }
+    }
+
+    fn topics(clusters: &[ClusterSummary]) -> Vec<&str> {
+        clusters.iter().map(|c| c.topic.as_str()).collect()
+    }
+
+    #[test]
+    fn sort_clusters_by_cost_descending() {
+        let mut catalog = make_catalog(vec![
+            make_summary("mid", 1, 0.5, 0),
+            make_summary("high", 1, 2.0, 0),
+            make_summary("low", 9, 0.1, 0),
+        ]);
+
+        catalog.sort_clusters(CatalogSort::Cost);
+
+        assert_eq!(topics(&catalog.clusters), ["high", "mid", "low"]);
+    }
+
+    #[test]
+    fn sort_clusters_by_size_and_recency() {
+        let mut catalog = make_catalog(vec![
+            make_summary("small-fresh", 1, 0.5, 0),
+            make_summary("big-stale", 9, 0.1, 50),
+            make_summary("mid", 4, 0.3, 10),
+        ]);
+
+        catalog.sort_clusters(CatalogSort::Size);
+        assert_eq!(
+            topics(&catalog.clusters),
+            ["big-stale", "mid", "small-fresh"]
+        );
+
+        catalog.sort_clusters(CatalogSort::Recency);
+        assert_eq!(
+            topics(&catalog.clusters),
+            ["small-fresh", "mid", "big-stale"]
+        );
+    }
+
+    #[test]
+    fn sort_clusters_puts_pinned_first() {
+        let mut pinned = make_summary("pinned", 1, 0.0, 0);
+        pinned.pinned = true;
+        let mut catalog = make_catalog(vec![
+            make_summary("big", 9, 5.0, 1),
+            pinned,
+            make_summary("mid", 5, 2.0, 3),
+        ]);
+
+        for sort in [CatalogSort::Cost, CatalogSort::Size, CatalogSort::Recency] {
+            catalog.sort_clusters(sort);
+            assert_eq!(topics(&catalog.clusters)[0], "pinned");
+        }
+    }
+
+    #[test]
+    fn page_yields_disjoint_windows() {
+        let catalog = make_catalog(
+            (0..10)
+                .map(|i| make_summary(&format!("topic{}", i), 1, i as f64, 0))
+                .collect(),
+        );
+
+        let first = topics(catalog.page(0, 4));
+        let second = topics(catalog.page(4, 4));
+        let last = topics(catalog.page(8, 4));
+
+        assert_eq!(first.len(), 4);
+        assert_eq!(second.len(), 4);
+        assert_eq!(last.len(), 2);
+        assert!(
+            first
+                .iter()
+                .all(|t| !second.contains(t) && !last.contains(t))
+        );
+        assert!(second.iter().all(|t| !last.contains(t)));
+        assert!(catalog.page(20, 4).is_empty());
+    }
+
+    #[test]
+    fn generate_all_ignores_budget() {
+        let generator = CatalogGenerator::with_max_tokens(TOKENS_PER_SUMMARY);
+        let mut entries = Vec::new();
+        let mut snapshot = CoherenceSnapshot::new();
+        for i in 0..5 {
+            let entry = make_text_entry(&format!("Entry {}", i), i);
+            snapshot
+                .clusters
+                .push(make_cluster(i, &[&format!("topic{}", i)], vec![entry.id]));
+            entries.push(entry);
+        }
+
+        assert_eq!(
+            generator.generate(&snapshot, &entries, None).clusters.len(),
+            1
+        );
+        assert_eq!(
+            generator.generate_all(&snapshot, &entries).clusters.len(),
+            5
+        );
+    }
+
+    #[test]
+    fn estimate_tokens_rounds_up() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+
+    fn make_digest_catalog() -> Catalog {
+        let mut clusters: Vec<ClusterSummary> = (0..20)
+            .map(|i| {
+                let mut summary = make_summary(&format!("topic{:02}", i), 3, i as f64, 0);
+                summary.summary = format!("Entries about subject number {} and its details.", i);
+                summary
+            })
+            .collect();
+        clusters.reverse();
+        let mut catalog = make_catalog(clusters);
+        catalog.total_entries = 60;
+        catalog.notebook_entropy = 190.0;
+        catalog
+    }
+
+    #[test]
+    fn digest_stays_within_budget() {
+        let catalog = make_digest_catalog();
+        for max_tokens in [0, 10, 40, 100, 250, 10_000] {
+            let digest = catalog.digest(max_tokens);
+            assert!(
+                estimate_tokens(&digest.text) <= max_tokens,
+                "{} tokens over a budget of {}",
+                estimate_tokens(&digest.text),
+                max_tokens
+            );
+        }
+
+        let digest = catalog.digest(10_000);
+        assert_eq!(digest.clusters_included, 20);
+        assert!(!digest.text.contains("not described"));
+    }
+
+    #[test]
+    fn digest_mentions_highest_cost_clusters_first() {
+        let mut catalog = make_digest_catalog();
+        // Listing order must not matter
+        catalog.sort_clusters(CatalogSort::Size);
+
+        let digest = catalog.digest(150);
+        assert!(digest.clusters_included > 0);
+        assert!(digest.clusters_included < 20);
+        assert!(
+            digest
+                .text
+                .starts_with("This notebook holds 60 entries in 20 topics")
+        );
+        assert!(digest.text.contains("Most significant is topic19"));
+
+        let positions: Vec<usize> = (0..20)
+            .rev()
+            .map_while(|i| digest.text.find(&format!("topic{:02}", i)))
+            .collect();
+        assert_eq!(positions.len(), digest.clusters_included);
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+        assert!(digest.text.ends_with(&format!(
+            "{} lower-cost topics are not described.",
+            20 - digest.clusters_included
+        )));
+    }
+
+    #[test]
+    fn digest_of_tiny_budget_is_empty() {
+        let digest = make_digest_catalog().digest(5);
+        assert!(digest.text.is_empty());
+        assert_eq!(digest.clusters_included, 0);
+    }
+
     #[test]
     fn topic_limits_keywords() {
         let generator = CatalogGenerator::new();
@@ -647,4 +1603,115 @@ mod tests {
         let topic_parts: Vec<_> = catalog.clusters[0].topic.split(", ").collect();
         assert!(topic_parts.len() <= MAX_TOPIC_KEYWORDS);
     }
+
+    #[test]
+    fn french_notebook_keywords_are_content_words() {
+        let entries: Vec<Entry> = [
+            "Le château a des murs de pierre et une tour médiévale.",
+            "La tour du château est construite avec des pierres taillées.",
+            "Les murs de pierre du château sont très épais.",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, content)| make_text_entry(content, i as u64 + 1))
+        .collect();
+
+        let config = ClusteringConfig {
+            similarity_threshold: 0.0,
+            locale: Locale::French,
+            ..ClusteringConfig::default()
+        };
+        let mut snapshot = CoherenceSnapshot::with_config(config);
+        snapshot.rebuild(&entries, CausalPosition::first());
+        let catalog = CatalogGenerator::new()
+            .with_locale(Locale::French)
+            .generate_all(&snapshot, &entries);
+
+        let keywords: Vec<&String> = snapshot
+            .clusters
+            .iter()
+            .flat_map(|c| &c.topic_keywords)
+            .collect();
+        assert!(!keywords.is_empty());
+        for keyword in &keywords {
+            assert!(
+                !Locale::French.stop_words().contains(&keyword.as_str()),
+                "stop word {} used as a keyword",
+                keyword
+            );
+        }
+        assert!(catalog.clusters.iter().any(|c| c.topic.contains("château")
+            || c.topic.contains("pierre")
+            || c.topic.contains("murs")));
+
+        // With English stop words, French function words crowd in
+        let mut english = CoherenceSnapshot::with_config(ClusteringConfig {
+            similarity_threshold: 0.0,
+            ..ClusteringConfig::default()
+        });
+        english.rebuild(&entries, CausalPosition::first());
+        let english_keywords: Vec<&String> = english
+            .clusters
+            .iter()
+            .flat_map(|c| &c.topic_keywords)
+            .collect();
+        assert!(
+            english_keywords
+                .iter()
+                .any(|k| Locale::French.stop_words().contains(&k.as_str()))
+        );
+    }
+
+    #[test]
+    fn digest_follows_catalog_locale() {
+        let mut catalog = make_digest_catalog();
+        catalog.locale = Locale::French;
+        let digest = catalog.digest(150);
+        assert!(
+            digest
+                .text
+                .starts_with("Ce carnet contient 60 entrées réparties en 20 sujets")
+        );
+        assert!(digest.text.contains("Le plus important est topic19"));
+        assert!(digest.text.contains("Vient ensuite topic18"));
+    }
+
+    #[test]
+    fn untitled_summary_follows_generator_locale() {
+        let entry = EntryBuilder::default()
+            .content(vec![0u8, 1, 2])
+            .content_type("application/octet-stream")
+            .author(AuthorId::zero())
+            .build();
+        let cluster = make_cluster(0, &["données"], vec![entry.id]);
+        let mut snapshot = CoherenceSnapshot::new();
+        snapshot.clusters.push(cluster);
+
+        let catalog = CatalogGenerator::new()
+            .with_locale(Locale::French)
+            .generate_all(&snapshot, &[entry]);
+        assert_eq!(catalog.clusters[0].summary, "[1 entrées sur données]");
+        assert_eq!(catalog.locale, Locale::French);
+    }
+
+    #[test]
+    fn uncategorized_cluster_is_labelled_as_such() {
+        let entries = vec![
+            make_text_entry("Tides follow the moon.", 1),
+            make_text_entry("Sourdough needs a lively starter.", 2),
+        ];
+        let mut cluster = make_cluster(0, &[], entries.iter().map(|e| e.id).collect());
+        cluster.uncategorized = true;
+        let mut snapshot = CoherenceSnapshot::new();
+        snapshot.clusters.push(cluster);
+
+        let catalog = CatalogGenerator::new().generate_all(&snapshot, &entries);
+        assert_eq!(catalog.clusters[0].topic, "uncategorized");
+        assert_eq!(catalog.clusters[0].summary, "[2 entries]");
+
+        let catalog = CatalogGenerator::new()
+            .with_locale(Locale::French)
+            .generate_all(&snapshot, &entries);
+        assert_eq!(catalog.clusters[0].topic, "non classé");
+    }
 }
@@ -8,14 +8,23 @@
 //! 1. Start with each entry as a singleton cluster
 //! 2. Iteratively merge the two most similar clusters
 //! 3. Stop when no pair exceeds the similarity threshold
+//! 4. Optionally, fold clusters below a minimum size into their nearest
+//!    larger cluster, or into a catch-all "uncategorized" cluster when none
+//!    is similar enough
 
-use crate::tfidf::{TfIdfVector, merge_vectors};
+use crate::tfidf::{Locale, TfIdfVector, merge_vectors};
 use notebook_core::types::EntryId;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 /// Unique identifier for a cluster.
+///
+/// Ids are derived from the cluster's founding entry (its earliest member)
+/// rather than allocated from a counter, so rebuilding a snapshot from the
+/// same entries reproduces the same ids. The founding entry is used instead
+/// of the topic keywords because keywords drift as members join and
+/// unrelated clusters (e.g. non-text entries) can share them.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct ClusterId(pub u64);
@@ -25,6 +34,28 @@ impl ClusterId {
     pub const fn new(id: u64) -> Self {
         Self(id)
     }
+
+    /// Derives the ClusterId for a cluster founded by the given entry.
+    pub fn from_founder(entry_id: EntryId) -> Self {
+        let (high, low) = entry_id.0.as_u64_pair();
+        Self(high ^ low)
+    }
+
+    /// Derives a founder-based ClusterId that is not already taken.
+    ///
+    /// Collisions only occur if two founders fold to the same value; they are
+    /// resolved by probing forward, which is deterministic for a given
+    /// insertion order.
+    pub(crate) fn from_founder_unique(
+        entry_id: EntryId,
+        is_taken: impl Fn(&ClusterId) -> bool,
+    ) -> Self {
+        let mut id = Self::from_founder(entry_id);
+        while is_taken(&id) {
+            id = Self(id.0.wrapping_add(1));
+        }
+        id
+    }
 }
 
 impl fmt::Display for ClusterId {
@@ -37,6 +68,11 @@ impl fmt::Display for ClusterId {
 /// Pairs with similarity below this threshold will not be merged.
 pub const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.3;
 
+/// Default minimum similarity for folding a small cluster into a larger one.
+/// Small clusters less similar than this to every larger cluster go to the
+/// uncategorized cluster.
+pub const DEFAULT_MERGE_FLOOR: f64 = 0.1;
+
 /// Number of top keywords to extract per cluster.
 const TOP_KEYWORDS_COUNT: usize = 5;
 
@@ -55,6 +91,12 @@ pub struct Cluster {
     /// Reference density within the cluster (edges / possible_edges).
     /// 1.0 for singleton clusters, 0.0-1.0 for larger clusters.
     pub reference_density: f64,
+
+    /// Whether this is the catch-all cluster for entries too unrelated to
+    /// join any other; see [`ClusteringConfig::min_cluster_size`]. It has
+    /// no topic keywords and new entries are never assigned to it.
+    #[serde(default)]
+    pub uncategorized: bool,
 }
 
 impl Cluster {
@@ -65,6 +107,7 @@ impl Cluster {
             topic_keywords: keywords,
             entry_ids: vec![entry_id],
             reference_density: 1.0, // Singleton has perfect density by convention
+            uncategorized: false,
         }
     }
 
@@ -84,6 +127,17 @@ impl Cluster {
     }
 }
 
+/// How to choose between equally similar candidates when merging clusters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TieBreak {
+    /// Prefer the cluster founded first.
+    #[default]
+    Earliest,
+    /// Prefer the larger cluster, then the one founded first.
+    Largest,
+}
+
 /// Configuration for the clustering algorithm.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClusteringConfig {
@@ -91,7 +145,63 @@ pub struct ClusteringConfig {
     pub similarity_threshold: f64,
 
     /// Maximum number of clusters (0 = unlimited).
+    ///
+    /// Merging stops once the limit is reached. If more clusters than this
+    /// remain after merging, the smallest are folded into the uncategorized
+    /// cluster, which counts towards the limit. Like
+    /// [`min_cluster_size`](Self::min_cluster_size), applies when clusters
+    /// are rebuilt.
     pub max_clusters: usize,
+
+    /// Half-life of a member's weight in its cluster's vector, in entries.
+    ///
+    /// A member `n` sequence numbers older than the cluster's newest entry
+    /// counts `0.5^(n / half_life)` as much, so cluster keywords and
+    /// similarity follow what the cluster is currently about. `None` weighs
+    /// all members equally.
+    #[serde(default)]
+    pub decay_half_life: Option<f64>,
+
+    /// Language of the entries' text, selecting the stop words left out of
+    /// term vectors and cluster keywords.
+    #[serde(default)]
+    pub locale: Locale,
+
+    /// Clusters with fewer members than this are folded after clustering:
+    /// into their most similar larger cluster if it is at least
+    /// [`merge_floor`](Self::merge_floor) similar, otherwise into the
+    /// uncategorized cluster. 0 or 1 keeps every cluster.
+    ///
+    /// Applies when clusters are rebuilt; an entry written afterwards that
+    /// matches no cluster still starts its own.
+    #[serde(default)]
+    pub min_cluster_size: usize,
+
+    /// Minimum similarity for folding a small cluster into a larger one.
+    #[serde(default = "default_merge_floor")]
+    pub merge_floor: f64,
+
+    /// How ties between equally similar merge candidates are broken.
+    #[serde(default)]
+    pub tie_break: TieBreak,
+}
+
+fn default_merge_floor() -> f64 {
+    DEFAULT_MERGE_FLOOR
+}
+
+impl ClusteringConfig {
+    /// Weight of a member `age` sequence numbers older than the newest.
+    ///
+    /// Always 1.0 when decay is disabled or the half-life is not positive.
+    pub fn decay_weight(&self, age: u64) -> f64 {
+        match self.decay_half_life {
+            Some(half_life) if half_life > 0.0 && half_life.is_finite() => {
+                0.5f64.powf(age as f64 / half_life)
+            }
+            _ => 1.0,
+        }
+    }
 }
 
 impl Default for ClusteringConfig {
@@ -99,6 +209,11 @@ impl Default for ClusteringConfig {
         Self {
             similarity_threshold: DEFAULT_SIMILARITY_THRESHOLD,
             max_clusters: 0,
+            decay_half_life: None,
+            locale: Locale::default(),
+            min_cluster_size: 0,
+            merge_floor: DEFAULT_MERGE_FLOOR,
+            tie_break: TieBreak::default(),
         }
     }
 }
@@ -111,8 +226,11 @@ struct ClusterState {
     /// TF-IDF vectors for each cluster (merged from member entries).
     cluster_vectors: HashMap<ClusterId, TfIdfVector>,
 
-    /// Next cluster ID to assign.
-    next_id: u64,
+    /// Input position of each cluster's founding entry.
+    ///
+    /// Keeps merges, tie-breaking, and output order independent of hash map
+    /// iteration order.
+    founded_at: HashMap<ClusterId, usize>,
 }
 
 impl ClusterState {
@@ -120,26 +238,42 @@ impl ClusterState {
         Self {
             clusters: HashMap::new(),
             cluster_vectors: HashMap::new(),
-            next_id: 0,
+            founded_at: HashMap::new(),
         }
     }
 
-    fn allocate_id(&mut self) -> ClusterId {
-        let id = ClusterId::new(self.next_id);
-        self.next_id += 1;
-        id
-    }
-
-    fn add_singleton(&mut self, entry_id: EntryId, vector: TfIdfVector) -> ClusterId {
-        let id = self.allocate_id();
+    fn add_singleton(
+        &mut self,
+        position: usize,
+        entry_id: EntryId,
+        vector: TfIdfVector,
+    ) -> ClusterId {
+        let id = ClusterId::from_founder_unique(entry_id, |id| self.clusters.contains_key(id));
         let keywords = vector.top_terms(TOP_KEYWORDS_COUNT);
         let cluster = Cluster::singleton(id, entry_id, keywords);
         self.clusters.insert(id, cluster);
         self.cluster_vectors.insert(id, vector);
+        self.founded_at.insert(id, position);
         id
     }
 
+    /// Cluster ids ordered by founding position.
+    fn ordered_ids(&self) -> Vec<ClusterId> {
+        let mut ids: Vec<_> = self.clusters.keys().copied().collect();
+        ids.sort_by_key(|id| self.founded_at[id]);
+        ids
+    }
+
+    /// Merges two clusters. The result keeps the id of whichever cluster was
+    /// founded first, mirroring incremental assignment where new members join
+    /// an existing cluster.
     fn merge(&mut self, id1: ClusterId, id2: ClusterId, references: &ReferenceGraph) -> ClusterId {
+        let (id1, id2) = if self.founded_at[&id1] <= self.founded_at[&id2] {
+            (id1, id2)
+        } else {
+            (id2, id1)
+        };
+
         let c1 = self.clusters.remove(&id1).expect("cluster 1 exists");
         let c2 = self.clusters.remove(&id2).expect("cluster 2 exists");
         let v1 = self.cluster_vectors.remove(&id1).expect("vector 1 exists");
@@ -156,12 +290,14 @@ impl ClusterState {
         // Calculate reference density for merged cluster
         let density = calculate_reference_density(&entry_ids, references);
 
-        let new_id = self.allocate_id();
+        let new_id = id1;
+        self.founded_at.remove(&id2);
         let cluster = Cluster {
             id: new_id,
             topic_keywords: keywords,
             entry_ids,
             reference_density: density,
+            uncategorized: false,
         };
 
         self.clusters.insert(new_id, cluster);
@@ -169,8 +305,16 @@ impl ClusterState {
         new_id
     }
 
-    fn find_best_merge(&self, threshold: f64) -> Option<(ClusterId, ClusterId, f64)> {
-        let ids: Vec<_> = self.clusters.keys().copied().collect();
+    fn size(&self, id: &ClusterId) -> usize {
+        self.clusters[id].size()
+    }
+
+    fn find_best_merge(
+        &self,
+        threshold: f64,
+        tie_break: TieBreak,
+    ) -> Option<(ClusterId, ClusterId, f64)> {
+        let ids = self.ordered_ids();
         let mut best: Option<(ClusterId, ClusterId, f64)> = None;
 
         for i in 0..ids.len() {
@@ -178,8 +322,21 @@ impl ClusterState {
                 let v1 = &self.cluster_vectors[&ids[i]];
                 let v2 = &self.cluster_vectors[&ids[j]];
                 let sim = v1.cosine_similarity(v2);
+                if sim < threshold {
+                    continue;
+                }
 
-                if sim >= threshold && (best.is_none() || sim > best.as_ref().unwrap().2) {
+                let better = match best {
+                    None => true,
+                    Some((b1, b2, best_sim)) => {
+                        sim > best_sim
+                            || (sim == best_sim
+                                && tie_break == TieBreak::Largest
+                                && self.size(&ids[i]) + self.size(&ids[j])
+                                    > self.size(&b1) + self.size(&b2))
+                    }
+                };
+                if better {
                     best = Some((ids[i], ids[j], sim));
                 }
             }
@@ -187,6 +344,90 @@ impl ClusterState {
 
         best
     }
+
+    /// Folds clusters smaller than `config.min_cluster_size` into the most
+    /// similar cluster of at least that size, or, when none reaches
+    /// `config.merge_floor`, into one uncategorized cluster.
+    fn fold_small_clusters(&mut self, config: &ClusteringConfig, references: &ReferenceGraph) {
+        let (small, mut large): (Vec<_>, Vec<_>) = self
+            .ordered_ids()
+            .into_iter()
+            .partition(|id| self.size(id) < config.min_cluster_size);
+
+        let mut stray: Option<ClusterId> = None;
+        for id in small {
+            let mut target: Option<(usize, f64)> = None;
+            for (index, candidate) in large.iter().enumerate() {
+                let sim =
+                    self.cluster_vectors[&id].cosine_similarity(&self.cluster_vectors[candidate]);
+                if sim < config.merge_floor {
+                    continue;
+                }
+                let better = match target {
+                    None => true,
+                    Some((best, best_sim)) => {
+                        sim > best_sim
+                            || (sim == best_sim
+                                && config.tie_break == TieBreak::Largest
+                                && self.size(candidate) > self.size(&large[best]))
+                    }
+                };
+                if better {
+                    target = Some((index, sim));
+                }
+            }
+
+            match target {
+                Some((index, _)) => large[index] = self.merge(large[index], id, references),
+                None => {
+                    stray = Some(match stray {
+                        Some(bucket) => self.merge(bucket, id, references),
+                        None => id,
+                    })
+                }
+            }
+        }
+
+        if let Some(bucket) = stray.and_then(|id| self.clusters.get_mut(&id)) {
+            bucket.uncategorized = true;
+            bucket.topic_keywords.clear();
+        }
+    }
+
+    /// Folds the smallest clusters into the uncategorized cluster until at
+    /// most `max_clusters` remain, the uncategorized cluster included.
+    ///
+    /// Of equally sized clusters, the one founded first is kept.
+    fn fold_overflow(&mut self, max_clusters: usize, references: &ReferenceGraph) {
+        if self.clusters.len() <= max_clusters {
+            return;
+        }
+
+        let mut bucket = self
+            .clusters
+            .values()
+            .find(|cluster| cluster.uncategorized)
+            .map(|cluster| cluster.id);
+        let mut named: Vec<ClusterId> = self
+            .ordered_ids()
+            .into_iter()
+            .filter(|id| Some(*id) != bucket)
+            .collect();
+        // Stable, so founding order breaks ties
+        named.sort_by_key(|id| std::cmp::Reverse(self.size(id)));
+
+        for id in named.into_iter().skip(max_clusters.saturating_sub(1)) {
+            bucket = Some(match bucket {
+                Some(bucket) => self.merge(bucket, id, references),
+                None => id,
+            });
+        }
+
+        if let Some(bucket) = bucket.and_then(|id| self.clusters.get_mut(&id)) {
+            bucket.uncategorized = true;
+            bucket.topic_keywords.clear();
+        }
+    }
 }
 
 /// Graph of references between entries.
@@ -278,8 +519,8 @@ pub fn cluster_entries(
     let mut state = ClusterState::new();
 
     // Initialize with singleton clusters
-    for (entry_id, vector) in entries {
-        state.add_singleton(entry_id, vector);
+    for (position, (entry_id, vector)) in entries.into_iter().enumerate() {
+        state.add_singleton(position, entry_id, vector);
     }
 
     // Agglomerative merging
@@ -290,7 +531,7 @@ pub fn cluster_entries(
         }
 
         // Find best merge candidate
-        match state.find_best_merge(config.similarity_threshold) {
+        match state.find_best_merge(config.similarity_threshold, config.tie_break) {
             Some((id1, id2, _sim)) => {
                 state.merge(id1, id2, references);
             }
@@ -298,7 +539,18 @@ pub fn cluster_entries(
         }
     }
 
-    state.clusters.into_values().collect()
+    if config.min_cluster_size > 1 {
+        state.fold_small_clusters(config, references);
+    }
+    if config.max_clusters > 0 {
+        state.fold_overflow(config.max_clusters, references);
+    }
+
+    state
+        .ordered_ids()
+        .into_iter()
+        .filter_map(|id| state.clusters.remove(&id))
+        .collect()
 }
 
 /// Finds the best matching cluster for a new entry.
@@ -327,13 +579,31 @@ pub fn find_best_cluster(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
 
     fn make_vector(terms: &[(&str, f64)]) -> TfIdfVector {
         let weights = terms.iter().map(|(t, w)| (t.to_string(), *w)).collect();
         TfIdfVector { weights }
     }
 
+    #[test]
+    fn decay_weight_halves_per_half_life() {
+        let config = ClusteringConfig {
+            decay_half_life: Some(10.0),
+            ..ClusteringConfig::default()
+        };
+        assert_eq!(config.decay_weight(0), 1.0);
+        assert!((config.decay_weight(10) - 0.5).abs() < 1e-12);
+        assert!((config.decay_weight(20) - 0.25).abs() < 1e-12);
+
+        let disabled = ClusteringConfig::default();
+        assert_eq!(disabled.decay_weight(1_000), 1.0);
+        let invalid = ClusteringConfig {
+            decay_half_life: Some(0.0),
+            ..ClusteringConfig::default()
+        };
+        assert_eq!(invalid.decay_weight(1_000), 1.0);
+    }
+
     #[test]
     fn cluster_id_display() {
         let id = ClusterId::new(42);
@@ -440,6 +710,7 @@ mod tests {
         let config = ClusteringConfig {
             similarity_threshold: 0.5,
             max_clusters: 0,
+            ..ClusteringConfig::default()
         };
         let references = ReferenceGraph::new();
 
@@ -458,11 +729,41 @@ mod tests {
         assert!(clusters[0].contains(&e2));
     }
 
+    #[test]
+    fn merged_cluster_keeps_founder_id() {
+        let config = ClusteringConfig {
+            similarity_threshold: 0.5,
+            max_clusters: 0,
+            ..ClusteringConfig::default()
+        };
+        let references = ReferenceGraph::new();
+
+        let e1 = EntryId::new();
+        let e2 = EntryId::new();
+        let v1 = make_vector(&[("cat", 1.0), ("dog", 0.5)]);
+        let v2 = make_vector(&[("cat", 0.8), ("dog", 0.6)]);
+
+        let clusters = cluster_entries(vec![(e1, v1), (e2, v2)], &references, &config);
+
+        assert_eq!(clusters[0].id, ClusterId::from_founder(e1));
+        assert_eq!(clusters[0].entry_ids, vec![e1, e2]);
+    }
+
+    #[test]
+    fn founder_id_probes_past_taken_ids() {
+        let entry_id = EntryId::new();
+        let taken = ClusterId::from_founder(entry_id);
+
+        let id = ClusterId::from_founder_unique(entry_id, |id| *id == taken);
+        assert_eq!(id, ClusterId::new(taken.0.wrapping_add(1)));
+    }
+
     #[test]
     fn cluster_entries_dissimilar_separate() {
         let config = ClusteringConfig {
             similarity_threshold: 0.5,
             max_clusters: 0,
+            ..ClusteringConfig::default()
         };
         let references = ReferenceGraph::new();
 
@@ -511,6 +812,7 @@ mod tests {
             topic_keywords: vec!["test".into(), "demo".into()],
             entry_ids: vec![EntryId::new()],
             reference_density: 0.75,
+            uncategorized: false,
         };
 
         let json = serde_json::to_string(&cluster).unwrap();
@@ -526,5 +828,186 @@ mod tests {
         let config = ClusteringConfig::default();
         assert_eq!(config.similarity_threshold, DEFAULT_SIMILARITY_THRESHOLD);
         assert_eq!(config.max_clusters, 0);
+        assert_eq!(config.min_cluster_size, 0);
+        assert_eq!(config.merge_floor, DEFAULT_MERGE_FLOOR);
+        assert_eq!(config.tie_break, TieBreak::Earliest);
+    }
+
+    /// Two "cat" entries, three "dog" entries, one entry equally close to
+    /// both groups but below the merge threshold, and one unrelated entry.
+    /// Both groups sum to the same weight, so the straggler ties exactly.
+    fn entries_with_stragglers() -> (Vec<(EntryId, TfIdfVector)>, EntryId, EntryId) {
+        let straggler = EntryId::new();
+        let unrelated = EntryId::new();
+        let mut entries: Vec<_> = [
+            make_vector(&[("cat", 1.5)]),
+            make_vector(&[("cat", 1.5)]),
+            make_vector(&[("dog", 1.0)]),
+            make_vector(&[("dog", 1.0)]),
+            make_vector(&[("dog", 1.0)]),
+        ]
+        .into_iter()
+        .map(|v| (EntryId::new(), v))
+        .collect();
+        entries.push((
+            straggler,
+            make_vector(&[("cat", 1.0), ("dog", 1.0), ("fish", 1.0)]),
+        ));
+        entries.push((unrelated, make_vector(&[("bird", 1.0)])));
+        (entries, straggler, unrelated)
+    }
+
+    fn folding_config(min_cluster_size: usize, tie_break: TieBreak) -> ClusteringConfig {
+        ClusteringConfig {
+            similarity_threshold: 0.6,
+            min_cluster_size,
+            tie_break,
+            ..ClusteringConfig::default()
+        }
+    }
+
+    #[test]
+    fn folding_disabled_keeps_singletons() {
+        let (entries, _, _) = entries_with_stragglers();
+        let clusters = cluster_entries(
+            entries,
+            &ReferenceGraph::new(),
+            &folding_config(0, TieBreak::Earliest),
+        );
+        assert_eq!(clusters.len(), 4);
+        assert_eq!(clusters.iter().filter(|c| c.is_singleton()).count(), 2);
+        assert!(clusters.iter().all(|c| !c.uncategorized));
+    }
+
+    #[test]
+    fn folding_reduces_singletons_and_collects_unrelated() {
+        let (entries, straggler, unrelated) = entries_with_stragglers();
+        let clusters = cluster_entries(
+            entries,
+            &ReferenceGraph::new(),
+            &folding_config(2, TieBreak::Earliest),
+        );
+
+        assert_eq!(clusters.len(), 3);
+        assert_eq!(clusters.iter().map(Cluster::size).sum::<usize>(), 7);
+
+        // The straggler joins a related cluster
+        let home = clusters.iter().find(|c| c.contains(&straggler)).unwrap();
+        assert!(!home.uncategorized);
+        assert!(home.size() > 1);
+
+        // The unrelated entry is set aside rather than left on its own topic
+        let bucket = clusters.iter().find(|c| c.contains(&unrelated)).unwrap();
+        assert!(bucket.uncategorized);
+        assert!(bucket.topic_keywords.is_empty());
+        assert_eq!(clusters.iter().filter(|c| c.uncategorized).count(), 1);
+    }
+
+    #[test]
+    fn unrelated_small_clusters_share_one_bucket() {
+        let entries: Vec<_> = ["bird", "fish", "tree"]
+            .into_iter()
+            .map(|term| (EntryId::new(), make_vector(&[(term, 1.0)])))
+            .collect();
+        let clusters = cluster_entries(
+            entries,
+            &ReferenceGraph::new(),
+            &folding_config(2, TieBreak::Earliest),
+        );
+        assert_eq!(clusters.len(), 1);
+        assert!(clusters[0].uncategorized);
+        assert_eq!(clusters[0].size(), 3);
+    }
+
+    #[test]
+    fn tie_break_chooses_fold_target() {
+        let (entries, straggler, _) = entries_with_stragglers();
+        let cat_entry = entries[0].0;
+
+        let earliest = cluster_entries(
+            entries.clone(),
+            &ReferenceGraph::new(),
+            &folding_config(2, TieBreak::Earliest),
+        );
+        let home = earliest.iter().find(|c| c.contains(&straggler)).unwrap();
+        assert!(home.contains(&cat_entry));
+        assert_eq!(home.size(), 3);
+
+        let largest = cluster_entries(
+            entries,
+            &ReferenceGraph::new(),
+            &folding_config(2, TieBreak::Largest),
+        );
+        let home = largest.iter().find(|c| c.contains(&straggler)).unwrap();
+        assert!(!home.contains(&cat_entry));
+        assert_eq!(home.size(), 4);
+    }
+
+    #[test]
+    fn overflow_folds_smallest_clusters_into_one_bucket() {
+        // Ten unrelated topics; the first two have extra members
+        let mut entries = Vec::new();
+        for (index, term) in [
+            "alpha", "beta", "gamma", "delta", "epsilon", "zeta", "eta", "theta", "iota", "kappa",
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let copies = match index {
+                0 => 3,
+                1 => 2,
+                _ => 1,
+            };
+            for _ in 0..copies {
+                entries.push((EntryId::new(), make_vector(&[(term, 1.0)])));
+            }
+        }
+        let (alpha, beta) = (entries[0].0, entries[3].0);
+
+        let config = ClusteringConfig {
+            similarity_threshold: 0.6,
+            max_clusters: 3,
+            ..ClusteringConfig::default()
+        };
+        let clusters = cluster_entries(entries, &ReferenceGraph::new(), &config);
+
+        assert_eq!(clusters.len(), 3);
+        assert_eq!(clusters.iter().map(Cluster::size).sum::<usize>(), 13);
+        assert_eq!(clusters.iter().filter(|c| c.uncategorized).count(), 1);
+
+        // The two largest topics keep their own clusters
+        for (founder, size) in [(alpha, 3), (beta, 2)] {
+            let home = clusters.iter().find(|c| c.contains(&founder)).unwrap();
+            assert!(!home.uncategorized);
+            assert_eq!(home.size(), size);
+        }
+        let bucket = clusters.iter().find(|c| c.uncategorized).unwrap();
+        assert_eq!(bucket.size(), 8);
+        assert!(bucket.topic_keywords.is_empty());
+    }
+
+    #[test]
+    fn overflow_counts_existing_bucket() {
+        let (entries, _, unrelated) = entries_with_stragglers();
+        let config = ClusteringConfig {
+            max_clusters: 2,
+            ..folding_config(2, TieBreak::Earliest)
+        };
+        let clusters = cluster_entries(entries, &ReferenceGraph::new(), &config);
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters.iter().map(Cluster::size).sum::<usize>(), 7);
+        let bucket = clusters.iter().find(|c| c.contains(&unrelated)).unwrap();
+        assert!(bucket.uncategorized);
+        assert_eq!(clusters.iter().filter(|c| c.uncategorized).count(), 1);
+    }
+
+    #[test]
+    fn clustering_config_deserializes_without_folding_fields() {
+        let json = r#"{"similarity_threshold": 0.4, "max_clusters": 0}"#;
+        let config: ClusteringConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.min_cluster_size, 0);
+        assert_eq!(config.merge_floor, DEFAULT_MERGE_FLOOR);
+        assert_eq!(config.tie_break, TieBreak::Earliest);
     }
 }
@@ -13,7 +13,8 @@ use crate::clustering::{
     Cluster, ClusterId, ClusteringConfig, ReferenceGraph, calculate_reference_density,
     cluster_entries, find_best_cluster,
 };
-use crate::tfidf::{CorpusStats, TfIdfVector, tokenize};
+use crate::text_extraction::extract_text;
+use crate::tfidf::{CorpusStats, TfIdfVector, term_frequency, tokenize_in, weighted_keywords};
 use notebook_core::types::{CausalPosition, Entry, EntryId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -36,6 +37,18 @@ pub struct CoherenceSnapshot {
     /// TF-IDF vectors for each entry (for incremental updates).
     entry_vectors: HashMap<EntryId, TfIdfVector>,
 
+    /// Term frequencies for each entry.
+    ///
+    /// Entry vectors are weighted against the corpus as it was when the entry
+    /// arrived; keeping term frequencies lets similarity queries re-weight
+    /// against the current corpus instead.
+    #[serde(default)]
+    entry_terms: HashMap<EntryId, HashMap<String, f64>>,
+
+    /// Sequence number of each entry, for recency decay of cluster vectors.
+    #[serde(default)]
+    entry_sequences: HashMap<EntryId, u64>,
+
     /// Reference graph for density calculation.
     #[serde(skip, default)]
     reference_graph: ReferenceGraph,
@@ -45,9 +58,6 @@ pub struct CoherenceSnapshot {
 
     /// Configuration for clustering.
     pub config: ClusteringConfig,
-
-    /// Next cluster ID to assign.
-    next_cluster_id: u64,
 }
 
 impl CoherenceSnapshot {
@@ -58,10 +68,11 @@ impl CoherenceSnapshot {
             corpus_stats: CorpusStats::new(),
             cluster_vectors: HashMap::new(),
             entry_vectors: HashMap::new(),
+            entry_terms: HashMap::new(),
+            entry_sequences: HashMap::new(),
             reference_graph: ReferenceGraph::new(),
             timestamp: CausalPosition::first(),
             config: ClusteringConfig::default(),
-            next_cluster_id: 0,
         }
     }
 
@@ -93,22 +104,13 @@ impl CoherenceSnapshot {
         self.entry_vectors.len()
     }
 
-    /// Allocates a new cluster ID.
-    fn allocate_cluster_id(&mut self) -> ClusterId {
-        let id = ClusterId::new(self.next_cluster_id);
-        self.next_cluster_id += 1;
-        id
-    }
-
     /// Extracts text content from an entry for tokenization.
     ///
-    /// For text content types, decodes as UTF-8. For others, returns empty.
+    /// Markup is stripped according to the content type (see
+    /// [`text_extraction`](crate::text_extraction)). Binary content yields
+    /// an empty string.
     fn extract_text(entry: &Entry) -> String {
-        if entry.content_type.starts_with("text/") {
-            String::from_utf8_lossy(&entry.content).into_owned()
-        } else {
-            String::new()
-        }
+        extract_text(&entry.content, &entry.content_type).unwrap_or_default()
     }
 
     /// Finds the best matching cluster for a new entry.
@@ -122,7 +124,7 @@ impl CoherenceSnapshot {
     /// The best matching cluster ID if similarity exceeds threshold, or None.
     pub fn assign_to_cluster(&self, entry: &Entry) -> Option<ClusterId> {
         let text = Self::extract_text(entry);
-        let tokens = tokenize(&text);
+        let tokens = tokenize_in(&text, self.config.locale);
 
         if tokens.is_empty() {
             // Non-text entry: try to match by topic if present
@@ -135,8 +137,7 @@ impl CoherenceSnapshot {
         }
 
         let cluster_data: Vec<_> = self
-            .cluster_vectors
-            .iter()
+            .assignable_vectors()
             .map(|(id, vec)| (*id, vec.clone()))
             .collect();
 
@@ -144,6 +145,41 @@ impl CoherenceSnapshot {
             .map(|(id, _)| id)
     }
 
+    /// Finds the existing cluster most similar to an entry's text.
+    ///
+    /// Unlike [`assign_to_cluster`](Self::assign_to_cluster) this ignores the
+    /// similarity threshold and the topic fallback, so it reports how close
+    /// an entry came even when it matched nothing. Returns `None` for
+    /// entries without text or when there are no clusters.
+    pub fn nearest_cluster(&self, entry: &Entry) -> Option<(ClusterId, f64)> {
+        let tokens = tokenize_in(&Self::extract_text(entry), self.config.locale);
+        self.nearest_to(&TfIdfVector::from_tokens(&tokens, &self.corpus_stats))
+    }
+
+    /// Most similar cluster to a vector, ties broken by lowest cluster id.
+    fn nearest_to(&self, vector: &TfIdfVector) -> Option<(ClusterId, f64)> {
+        if vector.is_empty() {
+            return None;
+        }
+        self.assignable_vectors()
+            .map(|(id, cluster_vec)| (*id, vector.cosine_similarity(cluster_vec)))
+            .max_by(|a, b| {
+                a.1.partial_cmp(&b.1)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| b.0.0.cmp(&a.0.0))
+            })
+    }
+
+    /// Vectors of the clusters new entries may join: all but the
+    /// uncategorized cluster.
+    fn assignable_vectors(&self) -> impl Iterator<Item = (&ClusterId, &TfIdfVector)> {
+        self.cluster_vectors.iter().filter(|(id, _)| {
+            !self
+                .get_cluster(**id)
+                .is_some_and(|cluster| cluster.uncategorized)
+        })
+    }
+
     /// Tries to match an entry to a cluster by its topic.
     fn match_by_topic(&self, entry: &Entry) -> Option<ClusterId> {
         let topic = entry.topic.as_ref()?;
@@ -169,6 +205,22 @@ impl CoherenceSnapshot {
             .map(|(id, _)| id)
     }
 
+    /// Suggests a topic for an entry from its top TF-IDF keywords.
+    ///
+    /// The entry's terms are weighted against the corpus as if the entry had
+    /// been added to it (see [`weighted_keywords`]), so that a notebook's
+    /// first entry still has keywords. Up to `terms` keywords are joined
+    /// with spaces, strongest first. Returns `None` for entries without
+    /// text.
+    pub fn infer_topic(&self, entry: &Entry, terms: usize) -> Option<String> {
+        let tokens = tokenize_in(&Self::extract_text(entry), self.config.locale);
+        let keywords: Vec<String> = weighted_keywords(&tokens, &self.corpus_stats, terms)
+            .into_iter()
+            .map(|(term, _)| term)
+            .collect();
+        (!keywords.is_empty()).then(|| keywords.join(" "))
+    }
+
     /// Gets a cluster by its ID.
     pub fn get_cluster(&self, id: ClusterId) -> Option<&Cluster> {
         self.clusters.iter().find(|c| c.id == id)
@@ -179,6 +231,49 @@ impl CoherenceSnapshot {
         self.clusters.iter().find(|c| c.contains(entry_id))
     }
 
+    /// Checks whether an entry is tracked by this snapshot.
+    pub fn contains_entry(&self, entry_id: &EntryId) -> bool {
+        self.entry_terms.contains_key(entry_id)
+    }
+
+    /// Finds the tracked entries most similar to a given entry.
+    ///
+    /// Compares TF-IDF vectors, weighted against the current corpus, by cosine
+    /// similarity, skipping the entry itself and anything in `exclude`. Only
+    /// entries with positive similarity are returned, most similar first.
+    ///
+    /// Returns `None` if the entry is not tracked by this snapshot.
+    pub fn similar_entries(
+        &self,
+        entry_id: &EntryId,
+        exclude: &[EntryId],
+        limit: usize,
+    ) -> Option<Vec<(EntryId, f64)>> {
+        let vector =
+            TfIdfVector::from_term_frequencies(self.entry_terms.get(entry_id)?, &self.corpus_stats);
+
+        let mut similar: Vec<(EntryId, f64)> = self
+            .entry_terms
+            .iter()
+            .filter(|(id, _)| *id != entry_id && !exclude.contains(id))
+            .map(|(id, tf)| {
+                let other = TfIdfVector::from_term_frequencies(tf, &self.corpus_stats);
+                (*id, vector.cosine_similarity(&other))
+            })
+            .filter(|(_, sim)| *sim > 0.0)
+            .collect();
+
+        // Most similar first; break ties by id so results are stable
+        similar.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.0.cmp(&b.0.0))
+        });
+        similar.truncate(limit);
+
+        Some(similar)
+    }
+
     /// Adds an entry to the coherence model.
     ///
     /// This updates corpus statistics and either assigns the entry to an
@@ -192,13 +287,22 @@ impl CoherenceSnapshot {
     ///
     /// The cluster ID the entry was assigned to (new or existing).
     pub fn add_entry(&mut self, entry: &Entry) -> ClusterId {
+        self.add_entry_with_assignment(entry).cluster_id
+    }
+
+    /// Adds an entry to the coherence model, reporting how it was assigned.
+    ///
+    /// Same as [`add_entry`](Self::add_entry), but also returns whether a
+    /// new cluster was created and the nearest existing cluster, measured
+    /// against the corpus including the new entry, as the assignment is.
+    pub fn add_entry_with_assignment(&mut self, entry: &Entry) -> AssignmentResult {
         // Update reference graph
         self.reference_graph
             .add_entry_references(entry.id, &entry.references);
 
         // Extract and tokenize text
         let text = Self::extract_text(entry);
-        let tokens = tokenize(&text);
+        let tokens = tokenize_in(&text, self.config.locale);
 
         // Update corpus stats
         self.corpus_stats.add_document(&tokens);
@@ -206,18 +310,63 @@ impl CoherenceSnapshot {
         // Compute TF-IDF vector
         let vector = TfIdfVector::from_tokens(&tokens, &self.corpus_stats);
         self.entry_vectors.insert(entry.id, vector.clone());
+        self.entry_terms.insert(entry.id, term_frequency(&tokens));
+        self.entry_sequences
+            .insert(entry.id, entry.causal_position.sequence);
+
+        let nearest = self.nearest_to(&vector);
 
         // Try to find matching cluster
         if let Some(cluster_id) = self.assign_to_cluster(entry) {
             // Add to existing cluster
             self.add_entry_to_cluster(entry.id, cluster_id, &vector);
-            cluster_id
+            AssignmentResult {
+                cluster_id,
+                new_cluster: false,
+                similarity: nearest
+                    .filter(|(id, _)| *id == cluster_id)
+                    .map(|(_, similarity)| similarity),
+                nearest,
+            }
         } else {
             // Create new singleton cluster
-            self.create_singleton_cluster(entry.id, &vector)
+            AssignmentResult {
+                cluster_id: self.create_singleton_cluster(entry.id, &vector),
+                new_cluster: true,
+                similarity: None,
+                nearest,
+            }
         }
     }
 
+    /// Merged vector of a cluster's members.
+    ///
+    /// With [`decay_half_life`](ClusteringConfig::decay_half_life) set, each
+    /// member is weighted by its age relative to the newest member, so the
+    /// vector leans toward what the cluster has been about lately. Entries
+    /// without a known sequence count fully.
+    fn member_vector(&self, entry_ids: &[EntryId]) -> TfIdfVector {
+        let newest = entry_ids
+            .iter()
+            .filter_map(|id| self.entry_sequences.get(id))
+            .max()
+            .copied()
+            .unwrap_or(0);
+
+        let weighted: Vec<_> = entry_ids
+            .iter()
+            .filter_map(|id| {
+                let vector = self.entry_vectors.get(id)?;
+                let weight = self.entry_sequences.get(id).map_or(1.0, |seq| {
+                    self.config.decay_weight(newest.saturating_sub(*seq))
+                });
+                Some((vector, weight))
+            })
+            .collect();
+
+        crate::tfidf::merge_weighted_vectors(&weighted)
+    }
+
     /// Adds an entry to an existing cluster.
     fn add_entry_to_cluster(
         &mut self,
@@ -225,31 +374,29 @@ impl CoherenceSnapshot {
         cluster_id: ClusterId,
         _vector: &TfIdfVector,
     ) {
-        if let Some(cluster) = self.clusters.iter_mut().find(|c| c.id == cluster_id) {
-            cluster.entry_ids.push(entry_id);
+        let Some(index) = self.clusters.iter().position(|c| c.id == cluster_id) else {
+            return;
+        };
+        self.clusters[index].entry_ids.push(entry_id);
+        let merged = self.member_vector(&self.clusters[index].entry_ids);
 
-            // Update cluster keywords
-            let entry_vectors: Vec<_> = cluster
-                .entry_ids
-                .iter()
-                .filter_map(|id| self.entry_vectors.get(id))
-                .collect();
+        let cluster = &mut self.clusters[index];
 
-            let merged = crate::tfidf::merge_vectors(&entry_vectors);
-            cluster.topic_keywords = merged.top_terms(5);
+        // Update cluster keywords
+        cluster.topic_keywords = merged.top_terms(5);
 
-            // Update cluster vector
-            self.cluster_vectors.insert(cluster_id, merged);
+        // Update cluster vector
+        self.cluster_vectors.insert(cluster_id, merged);
 
-            // Update reference density
-            cluster.reference_density =
-                calculate_reference_density(&cluster.entry_ids, &self.reference_graph);
-        }
+        // Update reference density
+        cluster.reference_density =
+            calculate_reference_density(&cluster.entry_ids, &self.reference_graph);
     }
 
     /// Creates a new singleton cluster for an entry.
     fn create_singleton_cluster(&mut self, entry_id: EntryId, vector: &TfIdfVector) -> ClusterId {
-        let cluster_id = self.allocate_cluster_id();
+        let cluster_id =
+            ClusterId::from_founder_unique(entry_id, |id| self.cluster_vectors.contains_key(id));
         let keywords = vector.top_terms(5);
 
         let cluster = Cluster {
@@ -257,6 +404,7 @@ impl CoherenceSnapshot {
             topic_keywords: keywords,
             entry_ids: vec![entry_id],
             reference_density: 1.0,
+            uncategorized: false,
         };
 
         self.clusters.push(cluster);
@@ -278,9 +426,10 @@ impl CoherenceSnapshot {
         self.clusters.clear();
         self.cluster_vectors.clear();
         self.entry_vectors.clear();
+        self.entry_terms.clear();
+        self.entry_sequences.clear();
         self.corpus_stats = CorpusStats::new();
         self.reference_graph = ReferenceGraph::new();
-        self.next_cluster_id = 0;
         self.timestamp = timestamp;
 
         if entries.is_empty() {
@@ -294,11 +443,14 @@ impl CoherenceSnapshot {
                 .add_entry_references(entry.id, &entry.references);
 
             let text = Self::extract_text(entry);
-            let tokens = tokenize(&text);
+            let tokens = tokenize_in(&text, self.config.locale);
             self.corpus_stats.add_document(&tokens);
 
             let vector = TfIdfVector::from_tokens(&tokens, &self.corpus_stats);
             self.entry_vectors.insert(entry.id, vector.clone());
+            self.entry_terms.insert(entry.id, term_frequency(&tokens));
+            self.entry_sequences
+                .insert(entry.id, entry.causal_position.sequence);
             entry_data.push((entry.id, vector));
         }
 
@@ -306,22 +458,15 @@ impl CoherenceSnapshot {
         let clusters = cluster_entries(entry_data, &self.reference_graph, &self.config);
 
         // Store clusters and their vectors
-        for cluster in clusters {
+        for mut cluster in clusters {
             // Compute cluster vector from member entries
-            let entry_vectors: Vec<_> = cluster
-                .entry_ids
-                .iter()
-                .filter_map(|id| self.entry_vectors.get(id))
-                .collect();
-            let merged = crate::tfidf::merge_vectors(&entry_vectors);
+            let merged = self.member_vector(&cluster.entry_ids);
+            if self.config.decay_half_life.is_some() && !cluster.uncategorized {
+                cluster.topic_keywords = merged.top_terms(5);
+            }
 
             self.cluster_vectors.insert(cluster.id, merged);
 
-            // Update next_cluster_id to be higher than any existing ID
-            if cluster.id.0 >= self.next_cluster_id {
-                self.next_cluster_id = cluster.id.0 + 1;
-            }
-
             self.clusters.push(cluster);
         }
     }
@@ -407,6 +552,10 @@ pub struct AssignmentResult {
     pub new_cluster: bool,
     /// Similarity to the matched cluster (if not new).
     pub similarity: Option<f64>,
+    /// Most similar existing cluster and its similarity, whether or not it
+    /// reached the threshold. `None` for entries without text or when the
+    /// notebook had no clusters.
+    pub nearest: Option<(ClusterId, f64)>,
 }
 
 #[cfg(test)]
@@ -422,6 +571,7 @@ mod tests {
             .build()
     }
 
+    #[allow(dead_code)]
     fn make_text_entry_with_topic(content: &str, topic: &str) -> Entry {
         EntryBuilder::default()
             .content(content.as_bytes().to_vec())
@@ -451,6 +601,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn infer_topic_picks_distinctive_keywords() {
+        let entry = make_text_entry(
+            "Feed the sourdough starter daily. A lively sourdough starter \
+             raises sourdough bread without commercial yeast.",
+        );
+
+        // Without a corpus the most frequent terms win
+        let snapshot = CoherenceSnapshot::new();
+        assert_eq!(
+            snapshot.infer_topic(&entry, 2).as_deref(),
+            Some("sourdough starter")
+        );
+
+        // Terms the notebook already uses everywhere count for less
+        let mut snapshot = CoherenceSnapshot::new();
+        for text in [
+            "Starter cultures for yogurt",
+            "Starter motors in old cars",
+            "A starter pistol opens the race",
+        ] {
+            snapshot.add_entry(&make_text_entry(text));
+        }
+        assert_eq!(
+            snapshot.infer_topic(&entry, 1).as_deref(),
+            Some("sourdough")
+        );
+
+        assert!(snapshot.infer_topic(&make_text_entry(""), 2).is_none());
+    }
+
     #[test]
     fn add_first_entry() {
         let mut snapshot = CoherenceSnapshot::new();
@@ -474,8 +655,8 @@ mod tests {
         let entry1 = make_text_entry("machine learning algorithms neural networks");
         let entry2 = make_text_entry("neural networks deep learning algorithms");
 
-        let cluster1 = snapshot.add_entry(&entry1);
-        let cluster2 = snapshot.add_entry(&entry2);
+        let _cluster1 = snapshot.add_entry(&entry1);
+        let _cluster2 = snapshot.add_entry(&entry2);
 
         // With low threshold, similar entries should be in same cluster
         // Note: depends on TF-IDF similarity computation
@@ -554,6 +735,86 @@ mod tests {
         assert!(snapshot.cluster_count() >= 1);
     }
 
+    #[test]
+    fn rebuild_yields_identical_cluster_ids() {
+        let entries = vec![
+            make_text_entry("machine learning neural networks"),
+            make_text_entry("cooking recipes food kitchen"),
+            make_text_entry("machine learning deep learning"),
+            make_text_entry("kitchen knives and cooking tools"),
+            make_text_entry("gardening tomatoes in summer"),
+        ];
+
+        let cluster_ids = |snapshot: &CoherenceSnapshot| -> Vec<(ClusterId, Vec<EntryId>)> {
+            snapshot
+                .clusters
+                .iter()
+                .map(|c| (c.id, c.entry_ids.clone()))
+                .collect()
+        };
+
+        let mut first = CoherenceSnapshot::new();
+        first.rebuild(&entries, CausalPosition::first());
+
+        let mut second = CoherenceSnapshot::new();
+        second.rebuild(&entries, CausalPosition::first());
+
+        // Rebuilding an existing snapshot in place gives the same result too
+        let before = cluster_ids(&first);
+        first.rebuild(&entries, CausalPosition::first());
+
+        assert_eq!(cluster_ids(&first), before);
+        assert_eq!(cluster_ids(&second), before);
+    }
+
+    #[test]
+    fn singleton_cluster_id_derives_from_entry() {
+        let mut snapshot = CoherenceSnapshot::new();
+        let entry = make_text_entry("a lonely entry about astronomy");
+
+        let cluster_id = snapshot.add_entry(&entry);
+        assert_eq!(cluster_id, ClusterId::from_founder(entry.id));
+    }
+
+    #[test]
+    fn similar_entries_ranks_related_entry_first() {
+        let mut snapshot = CoherenceSnapshot::new();
+
+        let rust = make_text_entry("rust ownership borrowing lifetimes compiler");
+        let cooking = make_text_entry("cooking pasta with tomato sauce");
+        let garden = make_text_entry("planting roses in the spring garden");
+        let query = make_text_entry("rust borrowing rules and lifetimes");
+        for entry in [&rust, &cooking, &garden, &query] {
+            snapshot.add_entry(entry);
+        }
+
+        let similar = snapshot.similar_entries(&query.id, &[], 5).unwrap();
+        assert_eq!(similar[0].0, rust.id);
+        assert!(similar.iter().all(|(id, _)| *id != query.id));
+    }
+
+    #[test]
+    fn similar_entries_respects_exclusions_and_limit() {
+        let mut snapshot = CoherenceSnapshot::new();
+
+        let a = make_text_entry("raft consensus in distributed systems");
+        let b = make_text_entry("raft consensus leader election");
+        let c = make_text_entry("paxos consensus for distributed databases");
+        let d = make_text_entry("cooking pasta with tomato sauce");
+        for entry in [&a, &b, &c, &d] {
+            snapshot.add_entry(entry);
+        }
+
+        let similar = snapshot.similar_entries(&a.id, &[b.id], 5).unwrap();
+        assert_eq!(similar.len(), 1);
+        assert_eq!(similar[0].0, c.id);
+
+        let limited = snapshot.similar_entries(&a.id, &[], 1).unwrap();
+        assert_eq!(limited.len(), 1);
+
+        assert!(snapshot.similar_entries(&EntryId::new(), &[], 5).is_none());
+    }
+
     #[test]
     fn stats_computation() {
         let mut snapshot = CoherenceSnapshot::new();
@@ -602,7 +863,7 @@ mod tests {
             .build();
 
         // Should match based on topic keyword overlap
-        let result = snapshot.assign_to_cluster(&entry2);
+        let _result = snapshot.assign_to_cluster(&entry2);
         // May or may not match depending on extracted keywords
         // At minimum, should not panic
     }
@@ -617,7 +878,7 @@ mod tests {
             .author(AuthorId::zero())
             .build();
 
-        let cluster_id = snapshot.add_entry(&entry);
+        let _cluster_id = snapshot.add_entry(&entry);
 
         // Should create a singleton cluster even for empty content
         assert_eq!(snapshot.cluster_count(), 1);
@@ -633,12 +894,58 @@ mod tests {
             .author(AuthorId::zero())
             .build();
 
-        let cluster_id = snapshot.add_entry(&entry);
+        let _cluster_id = snapshot.add_entry(&entry);
 
         // Should create singleton cluster for non-text content
         assert_eq!(snapshot.cluster_count(), 1);
     }
 
+    fn make_entry_with_type(content: &str, content_type: &str) -> Entry {
+        EntryBuilder::default()
+            .content(content.as_bytes().to_vec())
+            .content_type(content_type)
+            .author(AuthorId::zero())
+            .build()
+    }
+
+    #[test]
+    fn html_and_plain_text_with_same_prose_cluster_together() {
+        let mut snapshot = CoherenceSnapshot::new();
+
+        snapshot.add_entry(&make_text_entry("baking sourdough bread with starter"));
+        snapshot.add_entry(&make_text_entry("watering houseplants during winter"));
+        let plain = make_text_entry("tokio runtime schedules async tasks across worker threads");
+        snapshot.add_entry(&plain);
+
+        let html = make_entry_with_type(
+            r#"<div class="post"><h1>tokio runtime</h1><p>schedules <em>async tasks</em> across worker threads</p></div>"#,
+            "text/html",
+        );
+        snapshot.add_entry(&html);
+
+        let plain_cluster = snapshot.get_entry_cluster(&plain.id).unwrap().id;
+        let html_cluster = snapshot.get_entry_cluster(&html.id).unwrap().id;
+        assert_eq!(plain_cluster, html_cluster);
+        assert!(!snapshot.entry_terms[&html.id].contains_key("div"));
+    }
+
+    #[test]
+    fn json_keys_do_not_become_keywords() {
+        let mut snapshot = CoherenceSnapshot::new();
+        let entry = make_entry_with_type(
+            r#"{"description": "tokio runtime scheduling", "metadata": {"severity": "warning"}}"#,
+            "application/json",
+        );
+        snapshot.add_entry(&entry);
+
+        let terms = &snapshot.entry_terms[&entry.id];
+        assert!(terms.contains_key("tokio"));
+        assert!(terms.contains_key("warning"));
+        for key in ["description", "metadata", "severity"] {
+            assert!(!terms.contains_key(key), "JSON key {key} leaked into terms");
+        }
+    }
+
     #[test]
     fn average_density() {
         let snapshot = CoherenceSnapshot::new();
@@ -652,15 +959,167 @@ mod tests {
         assert_eq!(snapshot2.average_density(), 1.0);
     }
 
+    #[test]
+    fn add_entry_with_assignment_reports_nearest_cluster() {
+        let mut snapshot = CoherenceSnapshot::new();
+        snapshot.set_threshold(0.1);
+
+        let founder =
+            snapshot.add_entry_with_assignment(&make_text_entry("medieval castle stone walls"));
+        assert!(founder.new_cluster);
+        assert_eq!(founder.nearest, None);
+
+        // Unrelated entries give the shared terms a meaningful weight
+        snapshot.add_entry(&make_text_entry("ocean tides moon gravity"));
+        let first = snapshot.add_entry_with_assignment(&make_text_entry(
+            "machine learning algorithms neural networks",
+        ));
+        assert!(first.new_cluster);
+
+        let related = snapshot.add_entry_with_assignment(&make_text_entry(
+            "neural networks deep learning algorithms",
+        ));
+        assert!(!related.new_cluster);
+        assert_eq!(related.cluster_id, first.cluster_id);
+        let (nearest_id, similarity) = related.nearest.unwrap();
+        assert_eq!(nearest_id, first.cluster_id);
+        assert_eq!(related.similarity, Some(similarity));
+
+        // Still reports how close an unmatched entry came
+        let unrelated =
+            snapshot.add_entry_with_assignment(&make_text_entry("sourdough bread baking yeast"));
+        assert!(unrelated.new_cluster);
+        assert_eq!(unrelated.similarity, None);
+        assert!(unrelated.nearest.unwrap().1 < snapshot.threshold());
+    }
+
     #[test]
     fn with_config() {
         let config = ClusteringConfig {
             similarity_threshold: 0.5,
             max_clusters: 10,
+            ..ClusteringConfig::default()
         };
 
         let snapshot = CoherenceSnapshot::with_config(config.clone());
         assert_eq!(snapshot.threshold(), 0.5);
         assert_eq!(snapshot.config.max_clusters, 10);
     }
+
+    #[test]
+    fn rebuild_folds_singletons_into_uncategorized() {
+        let entries: Vec<Entry> = [
+            "watering houseplants in winter",
+            "mountain hiking boots",
+            "tokio async runtime tasks",
+            "tasks on the tokio async runtime",
+            "sourdough bread starter",
+            "bread from a sourdough starter",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, content)| make_sequenced_entry(content, i as u64 + 1))
+        .collect();
+        let timestamp = entries[5].causal_position;
+
+        let mut unfolded = CoherenceSnapshot::new();
+        unfolded.rebuild(&entries, timestamp);
+        assert_eq!(unfolded.stats().singleton_count, 2);
+
+        let mut snapshot = CoherenceSnapshot::with_config(ClusteringConfig {
+            min_cluster_size: 2,
+            ..ClusteringConfig::default()
+        });
+        snapshot.rebuild(&entries, timestamp);
+        assert_eq!(snapshot.stats().singleton_count, 0);
+        assert_eq!(snapshot.cluster_count(), 3);
+        let bucket = snapshot.get_entry_cluster(&entries[0].id).unwrap();
+        assert!(bucket.uncategorized);
+        assert!(bucket.contains(&entries[1].id));
+
+        // New entries never join the catch-all, even one close to a member
+        let bucket_id = bucket.id;
+        let assignment = snapshot
+            .add_entry_with_assignment(&make_text_entry("houseplants need watering in winter"));
+        assert!(assignment.new_cluster);
+        assert_ne!(assignment.cluster_id, bucket_id);
+        assert_ne!(assignment.nearest.map(|(id, _)| id), Some(bucket_id));
+    }
+
+    fn make_sequenced_entry(content: &str, sequence: u64) -> Entry {
+        EntryBuilder::default()
+            .content(content.as_bytes().to_vec())
+            .content_type("text/plain")
+            .author(AuthorId::zero())
+            .causal_position(CausalPosition {
+                sequence,
+                ..CausalPosition::first()
+            })
+            .build()
+    }
+
+    /// Entries on a long-running topic followed by a burst on a new one.
+    fn drifting_entries() -> Vec<Entry> {
+        let preamble = ["sourdough bread", "garden tomatoes", "mountain hiking"];
+        let old = (0..12).map(|_| "btree btree btree pages storage");
+        let new = (0..6).map(|_| "raft consensus leader election storage");
+        preamble
+            .into_iter()
+            .chain(old)
+            .chain(new)
+            .enumerate()
+            .map(|(i, content)| make_sequenced_entry(content, i as u64 + 1))
+            .collect()
+    }
+
+    fn drifting_config(decay_half_life: Option<f64>) -> ClusteringConfig {
+        ClusteringConfig {
+            // Everything joins a single cluster
+            similarity_threshold: 0.0,
+            max_clusters: 0,
+            decay_half_life,
+            ..ClusteringConfig::default()
+        }
+    }
+
+    fn drifted_snapshot(decay_half_life: Option<f64>) -> CoherenceSnapshot {
+        let mut snapshot = CoherenceSnapshot::with_config(drifting_config(decay_half_life));
+        for entry in drifting_entries() {
+            snapshot.add_entry(&entry);
+        }
+        assert_eq!(snapshot.cluster_count(), 1);
+        snapshot
+    }
+
+    #[test]
+    fn decay_shifts_cluster_toward_recent_entries() {
+        let decayed = drifted_snapshot(Some(2.0));
+        let keywords = &decayed.clusters[0].topic_keywords;
+        assert!(keywords.contains(&"raft".to_string()));
+        assert!(!keywords.contains(&"btree".to_string()));
+
+        // An entry on the recent topic also matches the cluster more closely
+        let undecayed = drifted_snapshot(None);
+        let probe = make_text_entry("raft leader election");
+        let (_, decayed_sim) = decayed.nearest_cluster(&probe).unwrap();
+        let (_, undecayed_sim) = undecayed.nearest_cluster(&probe).unwrap();
+        assert!(decayed_sim > undecayed_sim);
+    }
+
+    #[test]
+    fn without_decay_cluster_follows_all_members() {
+        let snapshot = drifted_snapshot(None);
+        assert_eq!(snapshot.clusters[0].topic_keywords[0], "btree");
+    }
+
+    #[test]
+    fn rebuild_applies_decay_to_cluster_keywords() {
+        let mut snapshot = CoherenceSnapshot::with_config(drifting_config(Some(2.0)));
+        snapshot.rebuild(&drifting_entries(), CausalPosition::first());
+
+        assert_eq!(snapshot.cluster_count(), 1);
+        let keywords = &snapshot.clusters[0].topic_keywords;
+        assert!(keywords.contains(&"raft".to_string()));
+        assert!(!keywords.contains(&"btree".to_string()));
+    }
 }
@@ -14,10 +14,19 @@
 //!    - `entries_revised`: entries that changed clusters
 //!    - `references_broken`: references now crossing cluster boundaries
 //!    - `catalog_shift`: cosine distance of cluster summary vectors
-//!    - `orphan`: entry has no cluster match AND no references
+//!    - `orphan`: entry has no cluster match AND no references that resolve
 //! 5. Commit the change to the real snapshot
 //! 6. Return the computed IntegrationCost
 //!
+//! The same computation can record its intermediate signals instead, as a
+//! [`CostExplanation`]: which clusters shifted, which references cross
+//! cluster boundaries, how close the nearest cluster was, and why the entry
+//! was or was not flagged as an orphan.
+//!
+//! A reference only integrates an entry if it points to an entry that still
+//! exists. The engine cannot see storage, so callers replaying stored entries
+//! pass a [`ReferenceResolution`] naming the references known to be missing.
+//!
 //! ## Performance
 //!
 //! Target: complete within 500ms for notebooks with up to 10,000 entries.
@@ -25,11 +34,16 @@
 //!
 //! Owned by: agent-entropy (Task 2-2)
 
-use crate::clustering::ClusterId;
+use crate::clustering::{ClusterId, ClusteringConfig};
 use crate::coherence::CoherenceSnapshot;
 use crate::tfidf::TfIdfVector;
 use notebook_core::types::{Entry, EntryId, IntegrationCost, NotebookId};
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+/// Number of keywords in an inferred topic.
+pub const INFERRED_TOPIC_TERMS: usize = 2;
 
 /// Error types for integration cost computation.
 #[derive(Debug, Clone, thiserror::Error)]
@@ -43,6 +57,188 @@ pub enum EntropyError {
     CoherenceError(String),
 }
 
+/// Tunable coefficients for integration cost computation.
+///
+/// Lets deployments make orphan detection and catalog shift stricter or more
+/// lenient. The defaults reproduce the engine's built-in behaviour.
+#[derive(Debug, Clone)]
+pub struct CostConfig {
+    /// Clustering settings for new coherence snapshots.
+    pub clustering: ClusteringConfig,
+    /// Catalog shift above which an entry is flagged as an orphan even if it
+    /// joined a cluster or has references. `None` relies on clustering alone.
+    pub orphan_threshold: Option<f64>,
+    /// Multiplier applied to the raw catalog shift, clamped to 0.0-1.0.
+    pub catalog_shift_weight: f64,
+}
+
+impl Default for CostConfig {
+    fn default() -> Self {
+        Self {
+            clustering: ClusteringConfig::default(),
+            orphan_threshold: None,
+            catalog_shift_weight: 1.0,
+        }
+    }
+}
+
+impl CostConfig {
+    /// Scale a raw catalog shift by the configured weight.
+    fn weigh_catalog_shift(&self, raw: f64) -> f64 {
+        (raw * self.catalog_shift_weight).clamp(0.0, 1.0)
+    }
+
+    /// Whether a catalog shift exceeds the configured orphan threshold.
+    fn exceeds_orphan_threshold(&self, catalog_shift: f64) -> bool {
+        self.orphan_threshold
+            .is_some_and(|threshold| catalog_shift > threshold)
+    }
+}
+
+/// Which referenced entries still exist.
+///
+/// References to entries outside the notebook's snapshot are allowed, so the
+/// snapshot alone cannot tell a deleted entry from one in another notebook.
+/// The default resolves every reference, which is right for new entries
+/// whose references were just validated.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReferenceResolution {
+    missing: HashSet<EntryId>,
+}
+
+impl ReferenceResolution {
+    /// A resolution in which the given entries no longer exist.
+    pub fn with_missing(missing: impl IntoIterator<Item = EntryId>) -> Self {
+        Self {
+            missing: missing.into_iter().collect(),
+        }
+    }
+
+    /// Whether a reference to `id` points to an existing entry.
+    pub fn resolves(&self, id: EntryId) -> bool {
+        !self.missing.contains(&id)
+    }
+
+    /// Number of `references` that point to existing entries.
+    fn live_count(&self, references: &[EntryId]) -> usize {
+        references.iter().filter(|id| self.resolves(**id)).count()
+    }
+}
+
+/// Why an entry was or was not flagged as an orphan.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OrphanReason {
+    /// Orphan: the entry started its own cluster and references nothing.
+    NoClusterMatchAndNoReferences,
+    /// Orphan: the entry started its own cluster and every entry it
+    /// references is missing.
+    ReferencesMissing { count: usize },
+    /// Orphan: the catalog shift exceeded the configured orphan threshold.
+    CatalogShiftAboveThreshold { catalog_shift: f64, threshold: f64 },
+    /// Not an orphan: the entry joined an existing cluster.
+    JoinedCluster,
+    /// Not an orphan: the entry started its own cluster but references
+    /// other entries.
+    HasReferences { count: usize },
+}
+
+impl OrphanReason {
+    /// Whether this reason flags the entry as an orphan.
+    pub fn is_orphan(&self) -> bool {
+        matches!(
+            self,
+            Self::NoClusterMatchAndNoReferences
+                | Self::ReferencesMissing { .. }
+                | Self::CatalogShiftAboveThreshold { .. }
+        )
+    }
+}
+
+impl fmt::Display for OrphanReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoClusterMatchAndNoReferences => write!(
+                f,
+                "orphan: matched no existing cluster and references no entries"
+            ),
+            Self::ReferencesMissing { count } => write!(
+                f,
+                "orphan: matched no existing cluster and all {} referenced entries are missing",
+                count
+            ),
+            Self::CatalogShiftAboveThreshold {
+                catalog_shift,
+                threshold,
+            } => write!(
+                f,
+                "orphan: catalog shift {:.3} exceeds the orphan threshold {:.3}",
+                catalog_shift, threshold
+            ),
+            Self::JoinedCluster => write!(f, "not an orphan: joined an existing cluster"),
+            Self::HasReferences { count } => write!(
+                f,
+                "not an orphan: started a new cluster but references {} entries",
+                count
+            ),
+        }
+    }
+}
+
+/// The existing cluster most similar to an entry.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NearestCluster {
+    /// The cluster.
+    pub cluster_id: ClusterId,
+    /// Cosine similarity between the entry and the cluster (0.0 to 1.0).
+    pub similarity: f64,
+    /// The cluster's topic keywords before the entry was added.
+    pub keywords: Vec<String>,
+}
+
+/// A cluster whose membership or keywords changed when an entry was added.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClusterShift {
+    /// The cluster.
+    pub cluster_id: ClusterId,
+    /// Number of entries before (0 for a cluster the entry founded).
+    pub size_before: usize,
+    /// Number of entries after.
+    pub size_after: usize,
+    /// Topic keywords before.
+    pub keywords_before: Vec<String>,
+    /// Topic keywords after.
+    pub keywords_after: Vec<String>,
+}
+
+/// The signals behind an entry's integration cost.
+///
+/// Produced by the same computation as [`IntegrationCost`], so `cost` always
+/// agrees with the signals and `orphan_reason.is_orphan()` with `cost.orphan`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CostExplanation {
+    /// The resulting integration cost.
+    pub cost: IntegrationCost,
+    /// Cluster the entry was assigned to.
+    pub cluster_id: ClusterId,
+    /// Whether the entry founded a new cluster.
+    pub new_cluster: bool,
+    /// The closest existing cluster, whether or not the entry joined it.
+    /// `None` for entries without text or in an empty notebook.
+    pub nearest_cluster: Option<NearestCluster>,
+    /// Similarity needed to join a cluster.
+    pub similarity_threshold: f64,
+    /// Clusters whose membership or keywords changed, by cluster id.
+    pub shifted_clusters: Vec<ClusterShift>,
+    /// References from the entry that cross into another cluster; their
+    /// count is `cost.references_broken`.
+    pub boundary_references: Vec<EntryId>,
+    /// Catalog shift before the configured weight was applied.
+    pub raw_catalog_shift: f64,
+    /// Why the orphan flag was or was not set.
+    pub orphan_reason: OrphanReason,
+}
+
 /// Engine for computing integration costs of new entries.
 ///
 /// Maintains coherence snapshots for notebooks and provides the core
@@ -65,21 +261,39 @@ pub enum EntropyError {
 pub struct IntegrationCostEngine {
     /// Coherence snapshots indexed by notebook ID.
     snapshots: HashMap<NotebookId, CoherenceSnapshot>,
+
+    /// Cost coefficients applied to every notebook.
+    config: CostConfig,
 }
 
 impl IntegrationCostEngine {
     /// Creates a new IntegrationCostEngine with no cached snapshots.
     pub fn new() -> Self {
+        Self::with_config(CostConfig::default())
+    }
+
+    /// Creates an engine with custom cost coefficients.
+    pub fn with_config(config: CostConfig) -> Self {
         Self {
             snapshots: HashMap::new(),
+            config,
         }
     }
 
+    /// Returns the engine's cost coefficients.
+    pub fn config(&self) -> &CostConfig {
+        &self.config
+    }
+
     /// Gets or creates a coherence snapshot for a notebook.
     ///
-    /// If the notebook doesn't have a snapshot, creates an empty one.
+    /// If the notebook doesn't have a snapshot, creates an empty one using
+    /// the engine's clustering settings.
     fn get_or_create_snapshot(&mut self, notebook_id: NotebookId) -> &mut CoherenceSnapshot {
-        self.snapshots.entry(notebook_id).or_default()
+        let clustering = &self.config.clustering;
+        self.snapshots
+            .entry(notebook_id)
+            .or_insert_with(|| CoherenceSnapshot::with_config(clustering.clone()))
     }
 
     /// Returns the coherence snapshot for a notebook if it exists.
@@ -122,30 +336,26 @@ impl IntegrationCostEngine {
         entry: &Entry,
         notebook_id: NotebookId,
     ) -> Result<IntegrationCost, EntropyError> {
-        let snapshot = self.get_or_create_snapshot(notebook_id);
-
-        // Capture state BEFORE adding entry
-        let before_state = CostState::capture(snapshot, entry);
-
-        // Add entry to snapshot (mutates the snapshot)
-        let assigned_cluster = snapshot.add_entry(entry);
-
-        // Capture state AFTER adding entry
-        let after_state = CostState::capture(snapshot, entry);
+        self.compute_cost_resolved(entry, notebook_id, &ReferenceResolution::default())
+    }
 
-        // Compute each cost component
-        let entries_revised = compute_entries_revised(&before_state, &after_state);
-        let references_broken =
-            compute_references_broken(entry, snapshot, &before_state, &after_state);
-        let catalog_shift = compute_catalog_shift(&before_state, &after_state);
-        let orphan = compute_orphan(entry, assigned_cluster, &before_state);
+    /// Computes the integration cost for adding an entry whose references
+    /// may point to missing entries.
+    ///
+    /// Like [`compute_cost`](Self::compute_cost), but references that
+    /// `references` does not resolve do not keep the entry from being an
+    /// orphan.
+    pub fn compute_cost_resolved(
+        &mut self,
+        entry: &Entry,
+        notebook_id: NotebookId,
+        references: &ReferenceResolution,
+    ) -> Result<IntegrationCost, EntropyError> {
+        let config = self.config.clone();
+        let snapshot = self.get_or_create_snapshot(notebook_id);
 
-        Ok(IntegrationCost {
-            entries_revised,
-            references_broken,
-            catalog_shift,
-            orphan,
-        })
+        // Adds the entry to the real snapshot
+        Ok(evaluate(&config, snapshot, entry, references).cost)
     }
 
     /// Computes integration cost without committing the change.
@@ -160,34 +370,103 @@ impl IntegrationCostEngine {
         if let Some(snapshot) = self.snapshots.get(&notebook_id) {
             // Clone for tentative analysis
             let mut preview_snapshot = snapshot.clone();
-            let before_state = CostState::capture(snapshot, entry);
-
-            let assigned_cluster = preview_snapshot.add_entry(entry);
-            let after_state = CostState::capture(&preview_snapshot, entry);
-
-            let entries_revised = compute_entries_revised(&before_state, &after_state);
-            let references_broken =
-                compute_references_broken(entry, &preview_snapshot, &before_state, &after_state);
-            let catalog_shift = compute_catalog_shift(&before_state, &after_state);
-            let orphan = compute_orphan(entry, assigned_cluster, &before_state);
-
-            Ok(IntegrationCost {
-                entries_revised,
-                references_broken,
-                catalog_shift,
-                orphan,
-            })
+            let references = ReferenceResolution::default();
+            Ok(evaluate(&self.config, &mut preview_snapshot, entry, &references).cost)
         } else {
             // No snapshot means first entry - minimal cost
             Ok(IntegrationCost {
                 entries_revised: 0,
                 references_broken: 0,
-                catalog_shift: 0.5, // First entry shifts catalog from nothing
+                // First entry shifts catalog from nothing
+                catalog_shift: self.config.weigh_catalog_shift(0.5),
                 orphan: entry.references.is_empty(),
             })
         }
     }
 
+    /// Computes the integration costs of adding entries in order, without
+    /// committing them.
+    ///
+    /// Each entry is evaluated against a copy of the notebook's snapshot
+    /// (an empty one if there is none yet) that already holds the entries
+    /// before it, so the costs are the ones [`compute_cost`](Self::compute_cost)
+    /// would return for the same sequence of writes. Does NOT modify the
+    /// snapshot.
+    pub fn compute_cost_preview_batch(
+        &self,
+        entries: &[Entry],
+        notebook_id: NotebookId,
+    ) -> Result<Vec<IntegrationCost>, EntropyError> {
+        let mut snapshot = self
+            .snapshots
+            .get(&notebook_id)
+            .cloned()
+            .unwrap_or_else(|| CoherenceSnapshot::with_config(self.config.clustering.clone()));
+        let references = ReferenceResolution::default();
+        Ok(entries
+            .iter()
+            .map(|entry| evaluate(&self.config, &mut snapshot, entry, &references).cost)
+            .collect())
+    }
+
+    /// Explains the integration cost of adding an entry to a notebook.
+    ///
+    /// Runs the same computation as [`compute_cost`](Self::compute_cost) on a
+    /// copy of the notebook's snapshot (an empty one if there is none yet)
+    /// and records its intermediate signals. Does NOT modify the snapshot.
+    pub fn explain_cost(
+        &self,
+        entry: &Entry,
+        notebook_id: NotebookId,
+    ) -> Result<CostExplanation, EntropyError> {
+        self.explain_cost_resolved(entry, notebook_id, &ReferenceResolution::default())
+    }
+
+    /// Explains the integration cost of adding an entry whose references may
+    /// point to missing entries, as [`compute_cost_resolved`] would compute it.
+    ///
+    /// [`compute_cost_resolved`]: Self::compute_cost_resolved
+    pub fn explain_cost_resolved(
+        &self,
+        entry: &Entry,
+        notebook_id: NotebookId,
+        references: &ReferenceResolution,
+    ) -> Result<CostExplanation, EntropyError> {
+        let mut snapshot = self
+            .snapshots
+            .get(&notebook_id)
+            .cloned()
+            .unwrap_or_else(|| CoherenceSnapshot::with_config(self.config.clustering.clone()));
+        Ok(evaluate(&self.config, &mut snapshot, entry, references))
+    }
+
+    /// Suggests a topic for an entry from its top keywords, weighted
+    /// against the notebook's corpus; see [`CoherenceSnapshot::infer_topic`].
+    pub fn infer_topic(&self, entry: &Entry, notebook_id: NotebookId) -> Option<String> {
+        match self.snapshots.get(&notebook_id) {
+            Some(snapshot) => snapshot.infer_topic(entry, INFERRED_TOPIC_TERMS),
+            None => CoherenceSnapshot::with_config(self.config.clustering.clone())
+                .infer_topic(entry, INFERRED_TOPIC_TERMS),
+        }
+    }
+
+    /// Returns the other entries in the cluster an entry belongs to: the
+    /// ones whose cost propagation a write of that entry affects.
+    pub fn cluster_peers(&self, entry_id: &EntryId, notebook_id: NotebookId) -> Vec<EntryId> {
+        self.snapshots
+            .get(&notebook_id)
+            .and_then(|snapshot| snapshot.get_entry_cluster(entry_id))
+            .map(|cluster| {
+                cluster
+                    .entry_ids
+                    .iter()
+                    .filter(|id| *id != entry_id)
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Removes a notebook's coherence snapshot from the cache.
     pub fn remove_snapshot(&mut self, notebook_id: NotebookId) {
         self.snapshots.remove(&notebook_id);
@@ -205,25 +484,104 @@ impl Default for IntegrationCostEngine {
     }
 }
 
+/// Adds an entry to a snapshot and measures the disruption it caused.
+///
+/// The single cost computation behind [`IntegrationCostEngine::compute_cost`],
+/// its preview, and [`IntegrationCostEngine::explain_cost`].
+fn evaluate(
+    config: &CostConfig,
+    snapshot: &mut CoherenceSnapshot,
+    entry: &Entry,
+    references: &ReferenceResolution,
+) -> CostExplanation {
+    // Capture state BEFORE adding entry
+    let before_state = CostState::capture(snapshot);
+
+    // Add entry to snapshot (mutates the snapshot)
+    let assignment = snapshot.add_entry_with_assignment(entry);
+
+    // Capture state AFTER adding entry
+    let after_state = CostState::capture(snapshot);
+
+    // Compute each cost component
+    let entries_revised = compute_entries_revised(&before_state, &after_state);
+    let boundary_references = compute_boundary_references(entry, &after_state);
+    let raw_catalog_shift = compute_catalog_shift(&before_state, &after_state);
+    let catalog_shift = config.weigh_catalog_shift(raw_catalog_shift);
+    let orphan_reason = compute_orphan_reason(
+        config,
+        entry,
+        references,
+        assignment.cluster_id,
+        &before_state,
+        catalog_shift,
+    );
+
+    CostExplanation {
+        cost: IntegrationCost {
+            entries_revised,
+            references_broken: boundary_references.len() as u32,
+            catalog_shift,
+            orphan: orphan_reason.is_orphan(),
+        },
+        cluster_id: assignment.cluster_id,
+        new_cluster: assignment.new_cluster,
+        nearest_cluster: assignment
+            .nearest
+            .map(|(cluster_id, similarity)| NearestCluster {
+                cluster_id,
+                similarity,
+                keywords: before_state
+                    .clusters
+                    .get(&cluster_id)
+                    .map(|c| c.keywords.clone())
+                    .unwrap_or_default(),
+            }),
+        similarity_threshold: snapshot.threshold(),
+        shifted_clusters: compute_shifted_clusters(&before_state, &after_state),
+        boundary_references,
+        raw_catalog_shift,
+        orphan_reason,
+    }
+}
+
+/// Size and keywords of a cluster at capture time.
+#[derive(Debug, PartialEq)]
+struct ClusterState {
+    size: usize,
+    keywords: Vec<String>,
+}
+
 /// Captured state for cost comparison.
 #[derive(Debug)]
 struct CostState {
     /// Entry to cluster mapping before/after.
     entry_clusters: HashMap<EntryId, ClusterId>,
 
+    /// Cluster sizes and keywords before/after.
+    clusters: HashMap<ClusterId, ClusterState>,
+
     /// Merged TF-IDF vector across all clusters (for catalog shift).
     catalog_vector: TfIdfVector,
 }
 
 impl CostState {
     /// Captures the current state of a coherence snapshot.
-    fn capture(snapshot: &CoherenceSnapshot, _entry: &Entry) -> Self {
+    fn capture(snapshot: &CoherenceSnapshot) -> Self {
         // Build entry -> cluster mapping
         let mut entry_clusters = HashMap::new();
+        let mut clusters = HashMap::new();
         for cluster in &snapshot.clusters {
             for entry_id in &cluster.entry_ids {
                 entry_clusters.insert(*entry_id, cluster.id);
             }
+            clusters.insert(
+                cluster.id,
+                ClusterState {
+                    size: cluster.size(),
+                    keywords: cluster.topic_keywords.clone(),
+                },
+            );
         }
 
         // Compute catalog vector (merge of all cluster summaries)
@@ -232,6 +590,7 @@ impl CostState {
 
         CostState {
             entry_clusters,
+            clusters,
             catalog_vector,
         }
     }
@@ -272,52 +631,55 @@ fn compute_entries_revised(before: &CostState, after: &CostState) -> u32 {
     revised
 }
 
-/// Computes how many references now cross cluster boundaries.
-fn compute_references_broken(
-    entry: &Entry,
-    snapshot: &CoherenceSnapshot,
-    before: &CostState,
-    after: &CostState,
-) -> u32 {
-    let mut broken = 0;
-
-    // Check references from the new entry
-    if let Some(entry_cluster) = after.entry_clusters.get(&entry.id) {
-        for ref_id in &entry.references {
-            if let Some(ref_cluster) = after.entry_clusters.get(ref_id)
-                && ref_cluster != entry_cluster
-            {
-                // Reference crosses cluster boundary
-                // This is only "broken" if it was internal before
-                // For a new entry, all cross-cluster refs count
-                broken += 1;
-            }
-        }
-    }
-
-    // Also check if existing references now cross boundaries
-    // due to re-clustering caused by the new entry
-    for cluster in &snapshot.clusters {
-        for entry_id in &cluster.entry_ids {
-            if entry_id == &entry.id {
-                continue; // Skip the new entry itself
-            }
-
-            // Get this entry's cluster before and after
-            let cluster_before = before.entry_clusters.get(entry_id);
-            let cluster_after = after.entry_clusters.get(entry_id);
+/// Finds the entry's references that cross cluster boundaries.
+///
+/// For a new entry, every reference into another cluster counts. References
+/// between existing entries that re-clustering pulled apart are not counted:
+/// that would require storing the reference graph in `CostState`.
+fn compute_boundary_references(entry: &Entry, after: &CostState) -> Vec<EntryId> {
+    let Some(entry_cluster) = after.entry_clusters.get(&entry.id) else {
+        return Vec::new();
+    };
+
+    entry
+        .references
+        .iter()
+        .filter(|ref_id| {
+            after
+                .entry_clusters
+                .get(ref_id)
+                .is_some_and(|ref_cluster| ref_cluster != entry_cluster)
+        })
+        .copied()
+        .collect()
+}
 
-            // If cluster changed, check if any references became cross-cluster
-            if cluster_before != cluster_after {
-                // Would need to look up the entry's references
-                // For performance, we count cluster changes as potential breaks
-                // This is an approximation - full accuracy would require
-                // storing reference graph in CostState
+/// Lists the clusters whose size or keywords changed, by cluster id.
+fn compute_shifted_clusters(before: &CostState, after: &CostState) -> Vec<ClusterShift> {
+    let ids: BTreeSet<u64> = before
+        .clusters
+        .keys()
+        .chain(after.clusters.keys())
+        .map(|id| id.0)
+        .collect();
+
+    ids.into_iter()
+        .map(ClusterId)
+        .filter_map(|cluster_id| {
+            let old = before.clusters.get(&cluster_id);
+            let new = after.clusters.get(&cluster_id);
+            if old == new {
+                return None;
             }
-        }
-    }
-
-    broken
+            Some(ClusterShift {
+                cluster_id,
+                size_before: old.map_or(0, |c| c.size),
+                size_after: new.map_or(0, |c| c.size),
+                keywords_before: old.map(|c| c.keywords.clone()).unwrap_or_default(),
+                keywords_after: new.map(|c| c.keywords.clone()).unwrap_or_default(),
+            })
+        })
+        .collect()
 }
 
 /// Computes how much the catalog summary changed.
@@ -332,8 +694,44 @@ fn compute_catalog_shift(before: &CostState, after: &CostState) -> f64 {
     1.0 - similarity
 }
 
+/// Determines why the entry is or is not an orphan.
+fn compute_orphan_reason(
+    config: &CostConfig,
+    entry: &Entry,
+    references: &ReferenceResolution,
+    assigned_cluster: ClusterId,
+    before: &CostState,
+    catalog_shift: f64,
+) -> OrphanReason {
+    if compute_orphan(entry, references, assigned_cluster, before) {
+        if entry.references.is_empty() {
+            OrphanReason::NoClusterMatchAndNoReferences
+        } else {
+            OrphanReason::ReferencesMissing {
+                count: entry.references.len(),
+            }
+        }
+    } else if config.exceeds_orphan_threshold(catalog_shift) {
+        OrphanReason::CatalogShiftAboveThreshold {
+            catalog_shift,
+            threshold: config.orphan_threshold.unwrap_or_default(),
+        }
+    } else if before.clusters.contains_key(&assigned_cluster) {
+        OrphanReason::JoinedCluster
+    } else {
+        OrphanReason::HasReferences {
+            count: references.live_count(&entry.references),
+        }
+    }
+}
+
 /// Determines if the entry is an orphan.
-fn compute_orphan(entry: &Entry, assigned_cluster: ClusterId, before: &CostState) -> bool {
+fn compute_orphan(
+    entry: &Entry,
+    references: &ReferenceResolution,
+    assigned_cluster: ClusterId,
+    before: &CostState,
+) -> bool {
     // An entry is orphan if:
     // 1. It created a new singleton cluster (no semantic match)
     // 2. AND it has no references to existing entries
@@ -344,8 +742,9 @@ fn compute_orphan(entry: &Entry, assigned_cluster: ClusterId, before: &CostState
         .values()
         .any(|c| *c == assigned_cluster);
 
-    // Check if entry has no valid references
-    let has_references = !entry.references.is_empty();
+    // Any resolving reference counts as integrating, including references to
+    // entries outside this notebook's snapshot (explicitly allowed external refs)
+    let has_references = references.live_count(&entry.references) > 0;
 
     // Orphan = new cluster AND no references
     is_new_cluster && !has_references
@@ -354,6 +753,7 @@ fn compute_orphan(entry: &Entry, assigned_cluster: ClusterId, before: &CostState
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clustering::DEFAULT_SIMILARITY_THRESHOLD;
     use notebook_core::types::{AuthorId, EntryBuilder};
 
     fn make_text_entry(content: &str) -> Entry {
@@ -373,6 +773,7 @@ mod tests {
             .build()
     }
 
+    #[allow(dead_code)]
     fn make_text_entry_with_topic(content: &str, topic: &str) -> Entry {
         EntryBuilder::default()
             .content(content.as_bytes().to_vec())
@@ -423,6 +824,126 @@ mod tests {
         assert_eq!(cost.entries_revised, 0);
     }
 
+    #[test]
+    fn infer_topic_uses_notebook_corpus() {
+        let mut engine = IntegrationCostEngine::new();
+        let notebook_id = NotebookId::new();
+        let entry = make_text_entry(
+            "Telescope mirrors need grinding. A polished telescope mirror \
+             gathers faint light.",
+        );
+
+        // Works before the notebook has any entries
+        let topic = engine.infer_topic(&entry, notebook_id).unwrap();
+        assert!(topic.starts_with("telescope"));
+        assert_eq!(topic.split(' ').count(), INFERRED_TOPIC_TERMS);
+
+        // Inference never changes the snapshot
+        engine
+            .compute_cost(&make_text_entry("Telescope eyepieces"), notebook_id)
+            .unwrap();
+        engine.infer_topic(&entry, notebook_id);
+        assert_eq!(engine.get_snapshot(notebook_id).unwrap().entry_count(), 1);
+    }
+
+    #[test]
+    fn cluster_peers_exclude_the_entry() {
+        let mut engine = IntegrationCostEngine::new();
+        let notebook_id = NotebookId::new();
+        for text in [
+            "baking sourdough bread with starter",
+            "watering houseplants during winter",
+        ] {
+            engine
+                .compute_cost(&make_text_entry(text), notebook_id)
+                .unwrap();
+        }
+        let first = make_text_entry("tokio runtime schedules async tasks across worker threads");
+        assert!(engine.cluster_peers(&first.id, notebook_id).is_empty());
+
+        engine.compute_cost(&first, notebook_id).unwrap();
+        assert!(engine.cluster_peers(&first.id, notebook_id).is_empty());
+
+        let second = make_text_entry("the tokio runtime schedules async tasks on worker threads");
+        engine.compute_cost(&second, notebook_id).unwrap();
+        assert_eq!(
+            engine.cluster_peers(&second.id, notebook_id),
+            vec![first.id]
+        );
+        assert_eq!(
+            engine.cluster_peers(&first.id, notebook_id),
+            vec![second.id]
+        );
+    }
+
+    #[test]
+    fn compute_cost_external_reference_not_orphan() {
+        let mut engine = IntegrationCostEngine::new();
+        let notebook_id = NotebookId::new();
+
+        engine
+            .compute_cost(
+                &make_text_entry("Machine learning fundamentals"),
+                notebook_id,
+            )
+            .unwrap();
+
+        // Reference an entry this notebook's snapshot has never seen
+        let entry = make_text_entry_with_refs("Medieval castle architecture", vec![EntryId::new()]);
+        let cost = engine.compute_cost(&entry, notebook_id).unwrap();
+
+        assert!(!cost.orphan);
+    }
+
+    #[test]
+    fn compute_cost_reference_to_deleted_entry_is_orphan() {
+        let mut engine = IntegrationCostEngine::new();
+        let notebook_id = NotebookId::new();
+        let deleted = make_text_entry("Machine learning fundamentals");
+        engine.compute_cost(&deleted, notebook_id).unwrap();
+
+        let entry = make_text_entry_with_refs("Medieval castle architecture", vec![deleted.id]);
+        let references = ReferenceResolution::with_missing([deleted.id]);
+        let explanation = engine
+            .explain_cost_resolved(&entry, notebook_id, &references)
+            .unwrap();
+        assert_eq!(
+            explanation.orphan_reason,
+            OrphanReason::ReferencesMissing { count: 1 }
+        );
+
+        let cost = engine
+            .compute_cost_resolved(&entry, notebook_id, &references)
+            .unwrap();
+        assert!(cost.orphan);
+    }
+
+    #[test]
+    fn compute_cost_live_reference_beside_deleted_one_is_not_orphan() {
+        let mut engine = IntegrationCostEngine::new();
+        let notebook_id = NotebookId::new();
+        let deleted = make_text_entry("Machine learning fundamentals");
+        let live = make_text_entry("Gradient descent optimizers");
+        engine.compute_cost(&deleted, notebook_id).unwrap();
+        engine.compute_cost(&live, notebook_id).unwrap();
+
+        let entry =
+            make_text_entry_with_refs("Medieval castle architecture", vec![deleted.id, live.id]);
+        let references = ReferenceResolution::with_missing([deleted.id]);
+        let explanation = engine
+            .explain_cost_resolved(&entry, notebook_id, &references)
+            .unwrap();
+        assert_eq!(
+            explanation.orphan_reason,
+            OrphanReason::HasReferences { count: 1 }
+        );
+
+        let cost = engine
+            .compute_cost_resolved(&entry, notebook_id, &references)
+            .unwrap();
+        assert!(!cost.orphan);
+    }
+
     #[test]
     fn compute_cost_entry_with_reference() {
         let mut engine = IntegrationCostEngine::new();
@@ -478,6 +999,79 @@ mod tests {
         assert_eq!(snapshot_count_before, snapshot_count_after);
     }
 
+    /// Costs agree up to float summation order, which follows hash map
+    /// iteration.
+    fn assert_same_cost(left: &IntegrationCost, right: &IntegrationCost) {
+        assert_eq!(left.entries_revised, right.entries_revised);
+        assert_eq!(left.references_broken, right.references_broken);
+        assert_eq!(left.orphan, right.orphan);
+        assert!(
+            (left.catalog_shift - right.catalog_shift).abs() < 1e-9,
+            "{} != {}",
+            left.catalog_shift,
+            right.catalog_shift
+        );
+    }
+
+    #[test]
+    fn compute_cost_preview_batch_matches_sequential_writes() {
+        let mut engine = IntegrationCostEngine::new();
+        let mut written = IntegrationCostEngine::new();
+        let notebook_id = NotebookId::new();
+
+        let existing = make_text_entry("Machine learning algorithms");
+        engine.compute_cost(&existing, notebook_id).unwrap();
+        written.compute_cost(&existing, notebook_id).unwrap();
+        let entry_count_before = engine.get_snapshot(notebook_id).unwrap().entry_count();
+
+        let batch = [
+            make_text_entry("Deep learning neural networks"),
+            make_text_entry("Cooking recipes ingredients kitchen baking"),
+            make_text_entry("Baking bread in the kitchen oven"),
+        ];
+        let previewed = engine
+            .compute_cost_preview_batch(&batch, notebook_id)
+            .unwrap();
+        let sequential: Vec<IntegrationCost> = batch
+            .iter()
+            .map(|entry| written.compute_cost(entry, notebook_id).unwrap())
+            .collect();
+
+        assert_eq!(previewed.len(), sequential.len());
+        for (preview, cost) in previewed.iter().zip(&sequential) {
+            assert_same_cost(preview, cost);
+        }
+        assert_eq!(
+            engine.get_snapshot(notebook_id).unwrap().entry_count(),
+            entry_count_before
+        );
+    }
+
+    #[test]
+    fn compute_cost_preview_batch_on_new_notebook() {
+        let engine = IntegrationCostEngine::new();
+        let mut written = IntegrationCostEngine::new();
+        let notebook_id = NotebookId::new();
+
+        let batch = [
+            make_text_entry("Machine learning algorithms"),
+            make_text_entry("Machine learning models"),
+        ];
+        let previewed = engine
+            .compute_cost_preview_batch(&batch, notebook_id)
+            .unwrap();
+
+        assert_same_cost(
+            &previewed[0],
+            &written.compute_cost(&batch[0], notebook_id).unwrap(),
+        );
+        assert_same_cost(
+            &previewed[1],
+            &written.compute_cost(&batch[1], notebook_id).unwrap(),
+        );
+        assert!(engine.get_snapshot(notebook_id).is_none());
+    }
+
     #[test]
     fn catalog_shift_increases_with_diversity() {
         let mut engine = IntegrationCostEngine::new();
@@ -555,7 +1149,7 @@ mod tests {
             "Using machine learning in recipe recommendation systems",
             vec![ml_entry.id, cooking_entry.id],
         );
-        let cost = engine.compute_cost(&bridge_entry, notebook_id).unwrap();
+        let _cost = engine.compute_cost(&bridge_entry, notebook_id).unwrap();
 
         // Should detect cross-cluster references
         // Exact count depends on cluster assignment
@@ -605,12 +1199,245 @@ mod tests {
 
         // Add entry that might cause existing entries to re-cluster
         let entry1 = make_text_entry("alpha beta gamma delta");
-        let cost1 = engine.compute_cost(&entry1, notebook_id).unwrap();
+        let _cost1 = engine.compute_cost(&entry1, notebook_id).unwrap();
 
         let entry2 = make_text_entry("alpha beta gamma epsilon");
-        let cost2 = engine.compute_cost(&entry2, notebook_id).unwrap();
+        let _cost2 = engine.compute_cost(&entry2, notebook_id).unwrap();
 
         // Similar entries at low threshold should merge
         // entries_revised may be 0 or low since we're building up
     }
+
+    #[test]
+    fn higher_similarity_threshold_orphans_borderline_entry() {
+        let first = make_text_entry("Machine learning algorithms neural networks deep learning");
+        let borderline = make_text_entry("Neural networks deep learning machine learning models");
+
+        let cost_with = |similarity_threshold: f64| {
+            let mut engine = IntegrationCostEngine::with_config(CostConfig {
+                clustering: ClusteringConfig {
+                    similarity_threshold,
+                    ..ClusteringConfig::default()
+                },
+                ..CostConfig::default()
+            });
+            let notebook_id = NotebookId::new();
+            // Unrelated entries give the shared terms a meaningful weight
+            for content in [
+                "Medieval castle architecture stone walls",
+                "Cooking pasta tomato sauce recipe",
+                "Ocean tides moon gravity",
+                "Jazz saxophone improvisation rhythm",
+            ] {
+                engine
+                    .compute_cost(&make_text_entry(content), notebook_id)
+                    .unwrap();
+            }
+            engine.compute_cost(&first, notebook_id).unwrap();
+            engine.compute_cost(&borderline, notebook_id).unwrap()
+        };
+
+        // Joins the existing cluster at the default threshold...
+        assert!(!cost_with(DEFAULT_SIMILARITY_THRESHOLD).orphan);
+        // ...but a strict threshold leaves it in its own cluster
+        assert!(cost_with(0.9).orphan);
+    }
+
+    #[test]
+    fn orphan_threshold_flags_large_catalog_shift() {
+        let notebook_id = NotebookId::new();
+        let mut engine = IntegrationCostEngine::with_config(CostConfig {
+            orphan_threshold: Some(0.0),
+            ..CostConfig::default()
+        });
+        engine
+            .compute_cost(
+                &make_text_entry("Machine learning fundamentals"),
+                notebook_id,
+            )
+            .unwrap();
+
+        // Referencing entries are normally never orphans
+        let entry = make_text_entry_with_refs("Medieval castle architecture", vec![EntryId::new()]);
+        let cost = engine.compute_cost(&entry, notebook_id).unwrap();
+        assert!(cost.catalog_shift > 0.0);
+        assert!(cost.orphan);
+    }
+
+    #[test]
+    fn catalog_shift_weight_scales_and_clamps() {
+        let entry = make_text_entry("Hello world, this is the first entry");
+        let shift_with = |catalog_shift_weight: f64| {
+            let engine = IntegrationCostEngine::with_config(CostConfig {
+                catalog_shift_weight,
+                ..CostConfig::default()
+            });
+            engine
+                .compute_cost_preview(&entry, NotebookId::new())
+                .unwrap()
+                .catalog_shift
+        };
+
+        assert_eq!(shift_with(1.0), 0.5);
+        assert_eq!(shift_with(0.5), 0.25);
+        assert_eq!(shift_with(4.0), 1.0);
+    }
+
+    /// Unrelated entries that give shared terms a meaningful weight.
+    fn seed_background(engine: &mut IntegrationCostEngine, notebook_id: NotebookId) {
+        for content in [
+            "Medieval castle architecture stone walls",
+            "Cooking pasta tomato sauce recipe",
+            "Ocean tides moon gravity",
+            "Jazz saxophone improvisation rhythm",
+        ] {
+            engine
+                .compute_cost(&make_text_entry(content), notebook_id)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn explain_cost_crafted_orphan() {
+        let mut engine = IntegrationCostEngine::new();
+        let notebook_id = NotebookId::new();
+        seed_background(&mut engine, notebook_id);
+
+        let orphan = make_text_entry("Quantum chromodynamics gluon confinement");
+        let explanation = engine.explain_cost(&orphan, notebook_id).unwrap();
+
+        assert_eq!(
+            explanation.orphan_reason,
+            OrphanReason::NoClusterMatchAndNoReferences
+        );
+        assert_eq!(
+            explanation.orphan_reason.is_orphan(),
+            explanation.cost.orphan
+        );
+        assert!(explanation.cost.orphan);
+        assert!(explanation.new_cluster);
+        assert!(explanation.boundary_references.is_empty());
+        // The founded cluster is listed as shifted from nothing
+        assert!(
+            explanation
+                .shifted_clusters
+                .iter()
+                .any(|s| s.cluster_id == explanation.cluster_id && s.size_before == 0)
+        );
+        if let Some(nearest) = &explanation.nearest_cluster {
+            assert!(nearest.similarity < explanation.similarity_threshold);
+        }
+    }
+
+    #[test]
+    fn explain_cost_matches_compute_cost_without_mutating() {
+        let mut engine = IntegrationCostEngine::new();
+        let notebook_id = NotebookId::new();
+        seed_background(&mut engine, notebook_id);
+        let first = make_text_entry("Machine learning algorithms neural networks deep learning");
+        engine.compute_cost(&first, notebook_id).unwrap();
+
+        let entry = make_text_entry("Neural networks deep learning machine learning models");
+        let count_before = engine.get_snapshot(notebook_id).unwrap().entry_count();
+        let explanation = engine.explain_cost(&entry, notebook_id).unwrap();
+        assert_eq!(
+            engine.get_snapshot(notebook_id).unwrap().entry_count(),
+            count_before
+        );
+
+        assert_eq!(explanation.orphan_reason, OrphanReason::JoinedCluster);
+        assert!(!explanation.new_cluster);
+        let nearest = explanation.nearest_cluster.as_ref().unwrap();
+        assert_eq!(nearest.cluster_id, explanation.cluster_id);
+        assert!(nearest.similarity >= explanation.similarity_threshold);
+
+        // Catalog shift may differ in the last bits (hash-ordered sums)
+        let cost = engine.compute_cost(&entry, notebook_id).unwrap();
+        assert_eq!(explanation.cost.entries_revised, cost.entries_revised);
+        assert_eq!(explanation.cost.references_broken, cost.references_broken);
+        assert_eq!(explanation.cost.orphan, cost.orphan);
+        assert!((explanation.cost.catalog_shift - cost.catalog_shift).abs() < 1e-9);
+    }
+
+    #[test]
+    fn explain_cost_lists_boundary_references() {
+        let mut engine = IntegrationCostEngine::new();
+        let notebook_id = NotebookId::new();
+        seed_background(&mut engine, notebook_id);
+        let target = make_text_entry("Machine learning algorithms neural networks");
+        engine.compute_cost(&target, notebook_id).unwrap();
+
+        let entry = make_text_entry_with_refs("Baroque harpsichord fugue", vec![target.id]);
+        let explanation = engine.explain_cost(&entry, notebook_id).unwrap();
+
+        assert_eq!(
+            explanation.orphan_reason,
+            OrphanReason::HasReferences { count: 1 }
+        );
+        assert!(!explanation.cost.orphan);
+        assert_eq!(explanation.boundary_references, vec![target.id]);
+        assert_eq!(explanation.cost.references_broken, 1);
+    }
+
+    #[test]
+    fn explain_cost_reports_orphan_threshold() {
+        let notebook_id = NotebookId::new();
+        let mut engine = IntegrationCostEngine::with_config(CostConfig {
+            orphan_threshold: Some(0.0),
+            ..CostConfig::default()
+        });
+        engine
+            .compute_cost(
+                &make_text_entry("Machine learning fundamentals"),
+                notebook_id,
+            )
+            .unwrap();
+
+        let entry = make_text_entry_with_refs("Medieval castle architecture", vec![EntryId::new()]);
+        let explanation = engine.explain_cost(&entry, notebook_id).unwrap();
+
+        match explanation.orphan_reason {
+            OrphanReason::CatalogShiftAboveThreshold {
+                catalog_shift,
+                threshold,
+            } => {
+                assert_eq!(catalog_shift, explanation.cost.catalog_shift);
+                assert_eq!(threshold, 0.0);
+            }
+            other => panic!("unexpected reason {:?}", other),
+        }
+        assert!(explanation.cost.orphan);
+    }
+
+    #[test]
+    fn explain_cost_without_snapshot() {
+        let engine = IntegrationCostEngine::new();
+        let notebook_id = NotebookId::new();
+        let entry = make_text_entry("Hello world, this is the first entry");
+        let explanation = engine.explain_cost(&entry, notebook_id).unwrap();
+
+        assert!(engine.get_snapshot(notebook_id).is_none());
+        assert!(explanation.nearest_cluster.is_none());
+        assert!(explanation.new_cluster);
+
+        let cost = IntegrationCostEngine::new()
+            .compute_cost(&entry, notebook_id)
+            .unwrap();
+        assert_eq!(explanation.cost.orphan, cost.orphan);
+        assert!((explanation.cost.catalog_shift - cost.catalog_shift).abs() < 1e-9);
+    }
+
+    #[test]
+    fn orphan_reason_serializes_with_kind() {
+        let json = serde_json::to_value(OrphanReason::HasReferences { count: 2 }).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"kind": "has_references", "count": 2})
+        );
+        assert!(
+            OrphanReason::NoClusterMatchAndNoReferences
+                .to_string()
+                .starts_with("orphan")
+        );
+    }
 }
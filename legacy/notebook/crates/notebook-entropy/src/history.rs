@@ -0,0 +1,264 @@
+//! Cluster churn over recent writes.
+//!
+//! A catalog's `stability` counts the entries written since a cluster last
+//! gained a member, which says how long a cluster has been quiet but not how
+//! much it moved while it was active. This module replays a notebook's most
+//! recent writes against a coherence snapshot of the entries before them and
+//! records, write by write, how one cluster's membership and topic keywords
+//! changed. Volatile knowledge areas show high churn; settled ones stay near
+//! zero.
+//!
+//! Clusters are followed by [`ClusterId`], which is derived from the
+//! cluster's founding entry and so survives the replay.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use notebook_core::types::{CausalPosition, Entry, EntryId};
+use serde::Serialize;
+
+use crate::clustering::{ClusterId, ClusteringConfig};
+use crate::coherence::CoherenceSnapshot;
+
+/// Default number of recent writes replayed for a cluster history.
+pub const DEFAULT_HISTORY_WRITES: usize = 50;
+
+/// How one write changed a cluster.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClusterChange {
+    /// The write.
+    pub entry_id: EntryId,
+    /// Sequence number of the write.
+    pub sequence: u64,
+    /// Number of entries in the cluster after the write.
+    pub entry_count: usize,
+    /// Topic keywords after the write.
+    pub topic_keywords: Vec<String>,
+    /// Jaccard distance between the members before and after (0.0 to 1.0).
+    pub membership_churn: f64,
+    /// Jaccard distance between the keywords before and after (0.0 to 1.0).
+    pub keyword_churn: f64,
+}
+
+/// How a cluster churned across a notebook's recent writes.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClusterHistory {
+    /// The cluster.
+    pub cluster_id: ClusterId,
+    /// Number of writes replayed.
+    pub writes_observed: usize,
+    /// Mean membership churn per replayed write (0.0 to 1.0).
+    pub churn: f64,
+    /// Mean keyword churn per replayed write (0.0 to 1.0).
+    pub keyword_churn: f64,
+    /// The writes that changed the cluster, oldest first.
+    pub changes: Vec<ClusterChange>,
+}
+
+/// Members and keywords of a cluster at one point of the replay.
+#[derive(Default)]
+struct ClusterState {
+    members: HashSet<EntryId>,
+    keywords: HashSet<String>,
+    keyword_list: Vec<String>,
+}
+
+impl ClusterState {
+    fn capture(snapshot: &CoherenceSnapshot, cluster_id: ClusterId) -> Self {
+        snapshot
+            .get_cluster(cluster_id)
+            .map(|cluster| Self {
+                members: cluster.entry_ids.iter().copied().collect(),
+                keywords: cluster.topic_keywords.iter().cloned().collect(),
+                keyword_list: cluster.topic_keywords.clone(),
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Jaccard distance between two sets; 0.0 when both are empty.
+fn jaccard_distance<T: Eq + Hash>(a: &HashSet<T>, b: &HashSet<T>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    1.0 - a.intersection(b).count() as f64 / union as f64
+}
+
+/// Replays the last `writes` entries and records how `cluster_id` changed.
+///
+/// `entries` must be in sequence order. The entries before the replayed
+/// writes are clustered from scratch; each replayed write is then added
+/// incrementally, as it was when written. A cluster that does not exist at
+/// some point of the replay is treated as empty, so founding or dissolving
+/// it counts as full churn.
+pub fn cluster_history(
+    config: &ClusteringConfig,
+    entries: &[Entry],
+    cluster_id: ClusterId,
+    writes: usize,
+) -> ClusterHistory {
+    let split = entries.len().saturating_sub(writes);
+    let (before, replayed) = entries.split_at(split);
+
+    let mut snapshot = CoherenceSnapshot::with_config(config.clone());
+    if !before.is_empty() {
+        let timestamp = before
+            .last()
+            .map(|e| e.causal_position)
+            .unwrap_or_else(CausalPosition::first);
+        snapshot.rebuild(before, timestamp);
+    }
+
+    let mut previous = ClusterState::capture(&snapshot, cluster_id);
+    let mut total_churn = 0.0;
+    let mut total_keyword_churn = 0.0;
+    let mut changes = Vec::new();
+
+    for entry in replayed {
+        snapshot.add_entry(entry);
+        let current = ClusterState::capture(&snapshot, cluster_id);
+
+        let membership_churn = jaccard_distance(&previous.members, &current.members);
+        let keyword_churn = jaccard_distance(&previous.keywords, &current.keywords);
+        if membership_churn > 0.0 || keyword_churn > 0.0 {
+            total_churn += membership_churn;
+            total_keyword_churn += keyword_churn;
+            changes.push(ClusterChange {
+                entry_id: entry.id,
+                sequence: entry.causal_position.sequence,
+                entry_count: current.members.len(),
+                topic_keywords: current.keyword_list.clone(),
+                membership_churn,
+                keyword_churn,
+            });
+        }
+        previous = current;
+    }
+
+    let writes_observed = replayed.len();
+    let mean = |total: f64| {
+        if writes_observed == 0 {
+            0.0
+        } else {
+            total / writes_observed as f64
+        }
+    };
+
+    ClusterHistory {
+        cluster_id,
+        writes_observed,
+        churn: mean(total_churn),
+        keyword_churn: mean(total_keyword_churn),
+        changes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notebook_core::types::{AuthorId, EntryBuilder};
+
+    fn make_text_entry(content: &str, sequence: u64) -> Entry {
+        EntryBuilder::default()
+            .content(content.as_bytes().to_vec())
+            .content_type("text/plain")
+            .author(AuthorId::zero())
+            .causal_position(CausalPosition {
+                sequence,
+                ..Default::default()
+            })
+            .build()
+    }
+
+    fn notebook(contents: &[&str]) -> Vec<Entry> {
+        contents
+            .iter()
+            .enumerate()
+            .map(|(i, content)| make_text_entry(content, i as u64 + 1))
+            .collect()
+    }
+
+    fn cluster_of(entries: &[Entry], entry: &Entry) -> ClusterId {
+        let mut snapshot = CoherenceSnapshot::new();
+        snapshot.rebuild(entries, CausalPosition::first());
+        snapshot.get_entry_cluster(&entry.id).unwrap().id
+    }
+
+    #[test]
+    fn test_active_cluster_churns_more_than_settled_one() {
+        let entries = notebook(&[
+            "medieval castle architecture stone walls",
+            "castle stone walls medieval towers",
+            "medieval castle towers stone architecture",
+            "pasta tomato sauce recipe",
+            "pasta tomato sauce garlic basil",
+            "pasta sauce garlic basil olive oil",
+            "pasta olive oil garlic parmesan",
+            "pasta parmesan basil tomato sauce",
+        ]);
+        let settled = cluster_of(&entries, &entries[0]);
+        let active = cluster_of(&entries, &entries[3]);
+        assert_ne!(settled, active);
+
+        let config = ClusteringConfig::default();
+        let settled_history = cluster_history(&config, &entries, settled, 5);
+        let active_history = cluster_history(&config, &entries, active, 5);
+
+        assert_eq!(settled_history.writes_observed, 5);
+        assert_eq!(settled_history.churn, 0.0);
+        assert!(settled_history.changes.is_empty());
+
+        assert!(
+            active_history.churn > settled_history.churn,
+            "active churn {} not above settled churn {}",
+            active_history.churn,
+            settled_history.churn
+        );
+        assert!(!active_history.changes.is_empty());
+        let last = active_history.changes.last().unwrap();
+        assert_eq!(last.entry_id, entries[7].id);
+        assert_eq!(last.sequence, 8);
+    }
+
+    #[test]
+    fn test_founding_a_cluster_is_full_churn() {
+        let entries = notebook(&[
+            "medieval castle architecture stone walls",
+            "ocean tides moon gravity",
+        ]);
+        let founded = cluster_of(&entries, &entries[1]);
+
+        let history = cluster_history(&ClusteringConfig::default(), &entries, founded, 1);
+        assert_eq!(history.writes_observed, 1);
+        assert_eq!(history.churn, 1.0);
+        assert_eq!(history.changes.len(), 1);
+        assert_eq!(history.changes[0].entry_count, 1);
+    }
+
+    #[test]
+    fn test_history_of_unknown_cluster_is_empty() {
+        let entries = notebook(&["ocean tides moon gravity"]);
+        let history = cluster_history(
+            &ClusteringConfig::default(),
+            &entries,
+            ClusterId::new(7),
+            10,
+        );
+        assert_eq!(history.writes_observed, 1);
+        assert_eq!(history.churn, 0.0);
+        assert!(history.changes.is_empty());
+    }
+
+    #[test]
+    fn test_jaccard_distance() {
+        let a: HashSet<u32> = [1, 2].into_iter().collect();
+        let b: HashSet<u32> = [2, 3].into_iter().collect();
+        assert_eq!(jaccard_distance(&a, &a), 0.0);
+        assert!((jaccard_distance(&a, &b) - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(
+            jaccard_distance(&HashSet::<u32>::new(), &HashSet::new()),
+            0.0
+        );
+    }
+}
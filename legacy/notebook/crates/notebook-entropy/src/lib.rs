@@ -4,15 +4,18 @@
 //! - Information-theoretic entropy metrics for notebook coherence
 //! - Integration cost calculation for new entries
 //! - Catalog surprise and orphan detection
-//! - Topic clustering for knowledge organization
+//! - Topic clustering for knowledge organization, in English or French
 //! - Adaptive threshold calibration for orphan detection
 //! - Retroactive cost propagation for affected entries
 //! - Full-text search indexing with Tantivy
 //! - Catalog generation for dense notebook summaries
 //! - Catalog caching with intelligent invalidation
+//! - Cluster churn across recent writes
+//! - Notebook-level similarity for discovery
 //!
 //! ## Modules
 //!
+//! - [`text_extraction`]: Content-type-aware text extraction (strips HTML, JSON, Markdown syntax)
 //! - [`tfidf`]: TF-IDF text analysis for keyword extraction and document similarity
 //! - [`clustering`]: Agglomerative clustering based on keyword similarity
 //! - [`coherence`]: Coherence model snapshot for tracking cluster state
@@ -22,6 +25,8 @@
 //! - [`search`]: Full-text search with Tantivy (Task 3-2)
 //! - [`catalog`]: Dense catalog generation for BROWSE endpoint (Task 3-1)
 //! - [`cache`]: Catalog caching with stale-while-revalidate support (Task 3-4)
+//! - [`history`]: Per-cluster churn replayed over recent writes
+//! - [`similarity`]: Notebook profiles ranked by TF-IDF similarity
 //!
 //! ## Example Usage
 //!
@@ -59,6 +64,7 @@
 //! let config = ClusteringConfig {
 //!     similarity_threshold: 0.3,
 //!     max_clusters: 0,
+//!     ..ClusteringConfig::default()
 //! };
 //! let mut snapshot = CoherenceSnapshot::with_config(config);
 //!
@@ -81,8 +87,11 @@ pub mod catalog;
 pub mod clustering;
 pub mod coherence;
 pub mod engine;
+pub mod history;
 pub mod propagation;
 pub mod search;
+pub mod similarity;
+pub mod text_extraction;
 pub mod tfidf;
 
 // Re-export main types for convenience
@@ -91,13 +100,23 @@ pub use cache::{
     DEFAULT_SHIFT_THRESHOLD,
 };
 pub use calibration::{NotebookConfig, ThresholdCalibrator};
-pub use catalog::{Catalog, CatalogGenerator, ClusterSummary, DEFAULT_MAX_TOKENS};
-pub use clustering::{Cluster, ClusterId, ClusteringConfig, ReferenceGraph};
+pub use catalog::{
+    Catalog, CatalogDigest, CatalogGenerator, CatalogSort, ClusterSummary, DEFAULT_MAX_TOKENS,
+    SummaryMode,
+};
+pub use clustering::{Cluster, ClusterId, ClusteringConfig, ReferenceGraph, TieBreak};
 pub use coherence::{CoherenceSnapshot, CoherenceStats};
-pub use engine::{EntropyError, IntegrationCostEngine};
+pub use engine::{
+    ClusterShift, CostConfig, CostExplanation, EntropyError, INFERRED_TOPIC_TERMS,
+    IntegrationCostEngine, NearestCluster, OrphanReason, ReferenceResolution,
+};
+pub use history::{ClusterChange, ClusterHistory, DEFAULT_HISTORY_WRITES, cluster_history};
 pub use propagation::{
-    CostUpdater, NoOpCostUpdater, PropagationError, PropagationJob, PropagationQueue,
-    PropagationWorker, WorkerStats, create_propagation_job,
+    CostUpdater, NoOpCostUpdater, PropagationError, PropagationJob, PropagationMonitor,
+    PropagationQueue, PropagationWorker, WorkerStats, create_propagation_job,
+};
+pub use search::{CommitPolicy, SearchError, SearchHit, SearchIndex};
+pub use similarity::{
+    DEFAULT_MIN_SIMILARITY, NotebookProfile, NotebookProfileCache, SimilarNotebook, rank_similar,
 };
-pub use search::{SearchError, SearchHit, SearchIndex};
-pub use tfidf::{CorpusStats, TfIdfVector};
+pub use tfidf::{CorpusStats, Locale, TfIdfVector, weighted_keywords};
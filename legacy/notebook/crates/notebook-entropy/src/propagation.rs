@@ -7,8 +7,16 @@
 //! ## Architecture
 //!
 //! - `PropagationJob`: A unit of work representing cost updates to affected entries
-//! - `PropagationQueue`: In-memory FIFO queue for pending jobs
+//! - `PropagationQueue`: In-memory per-notebook queues for pending jobs,
+//!   drained round-robin so one busy notebook cannot starve the others
 //! - `PropagationWorker`: Background task that processes the queue asynchronously
+//! - `PropagationMonitor`: Cloneable view of a worker's statistics, queue
+//!   depth and dead letters, for reporting while the worker runs
+//!
+//! ## Dead Letters
+//!
+//! A job whose cost update fails is not retried; it is kept as a dead letter
+//! so operators can see how many updates were lost and inspect them.
 //!
 //! ## Idempotency
 //!
@@ -39,13 +47,16 @@
 //! Owned by: agent-propagation (Task 2-4)
 
 use notebook_core::types::{EntryId, NotebookId};
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::sync::watch;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+/// How often [`PropagationMonitor::wait_for`] checks on a job.
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 /// Error types for propagation operations.
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum PropagationError {
@@ -132,20 +143,73 @@ impl PropagationJob {
     }
 }
 
+/// Pending jobs, one FIFO sub-queue per notebook.
+#[derive(Debug, Default)]
+struct FairQueue {
+    /// Pending jobs by notebook; only notebooks with pending jobs are present.
+    by_notebook: HashMap<NotebookId, VecDeque<PropagationJob>>,
+    /// Notebooks with pending jobs, in the order they are next served.
+    turns: VecDeque<NotebookId>,
+    /// Total pending jobs.
+    len: usize,
+    /// Copies of each job that are queued or taken but not yet finished.
+    unfinished: HashMap<Uuid, usize>,
+}
+
+impl FairQueue {
+    fn push(&mut self, job: PropagationJob) {
+        let notebook_id = job.notebook_id;
+        let pending = self.by_notebook.entry(notebook_id).or_default();
+        if pending.is_empty() {
+            self.turns.push_back(notebook_id);
+        }
+        *self.unfinished.entry(job.job_id).or_default() += 1;
+        pending.push_back(job);
+        self.len += 1;
+    }
+
+    /// Takes the oldest job of the notebook whose turn it is, then moves
+    /// that notebook to the back of the rotation.
+    fn pop(&mut self) -> Option<PropagationJob> {
+        let notebook_id = self.turns.pop_front()?;
+        let pending = self.by_notebook.get_mut(&notebook_id)?;
+        let job = pending.pop_front();
+        if pending.is_empty() {
+            self.by_notebook.remove(&notebook_id);
+        } else {
+            self.turns.push_back(notebook_id);
+        }
+        if job.is_some() {
+            self.len -= 1;
+        }
+        job
+    }
+
+    fn finish(&mut self, job_id: Uuid) {
+        if let Some(count) = self.unfinished.get_mut(&job_id) {
+            *count -= 1;
+            if *count == 0 {
+                self.unfinished.remove(&job_id);
+            }
+        }
+    }
+}
+
 /// Thread-safe queue for propagation jobs.
 ///
-/// Uses a VecDeque internally with Mutex protection for concurrent access.
-/// Jobs are processed in FIFO order.
+/// Keeps a FIFO sub-queue per notebook behind a Mutex. Jobs come out
+/// round-robin across notebooks, so a burst for one notebook is interleaved
+/// with jobs for the others; within a notebook they keep FIFO order.
 #[derive(Debug, Clone)]
 pub struct PropagationQueue {
-    inner: Arc<Mutex<VecDeque<PropagationJob>>>,
+    inner: Arc<Mutex<FairQueue>>,
 }
 
 impl PropagationQueue {
     /// Creates a new empty propagation queue.
     pub fn new() -> Self {
         Self {
-            inner: Arc::new(Mutex::new(VecDeque::new())),
+            inner: Arc::new(Mutex::new(FairQueue::default())),
         }
     }
 
@@ -165,7 +229,7 @@ impl PropagationQueue {
                     job.job_id,
                     job.affected_count()
                 );
-                queue.push_back(job);
+                queue.push(job);
             }
             Err(e) => {
                 warn!("Failed to enqueue job: lock poisoned: {}", e);
@@ -174,9 +238,12 @@ impl PropagationQueue {
     }
 
     /// Dequeues and returns the next job, if any.
+    ///
+    /// Takes from the next notebook in the rotation. The job stays
+    /// [pending](Self::is_pending) until it is [finished](Self::finish).
     pub fn process_next(&self) -> Option<PropagationJob> {
         match self.inner.lock() {
-            Ok(mut queue) => queue.pop_front(),
+            Ok(mut queue) => queue.pop(),
             Err(e) => {
                 warn!("Failed to process job: lock poisoned: {}", e);
                 None
@@ -184,14 +251,49 @@ impl PropagationQueue {
         }
     }
 
+    /// Marks a dequeued job as done, whether it succeeded or failed.
+    pub fn finish(&self, job_id: Uuid) {
+        if let Ok(mut queue) = self.inner.lock() {
+            queue.finish(job_id);
+        }
+    }
+
+    /// Returns true if a job is queued, or taken and not yet finished.
+    pub fn is_pending(&self, job_id: Uuid) -> bool {
+        match self.inner.lock() {
+            Ok(queue) => queue.unfinished.contains_key(&job_id),
+            Err(_) => false,
+        }
+    }
+
     /// Returns the number of pending jobs.
     pub fn len(&self) -> usize {
         match self.inner.lock() {
-            Ok(queue) => queue.len(),
+            Ok(queue) => queue.len,
+            Err(_) => 0,
+        }
+    }
+
+    /// Returns the number of pending jobs for one notebook.
+    pub fn depth(&self, notebook_id: NotebookId) -> usize {
+        match self.inner.lock() {
+            Ok(queue) => queue.by_notebook.get(&notebook_id).map_or(0, VecDeque::len),
             Err(_) => 0,
         }
     }
 
+    /// Returns the number of pending jobs per notebook with pending jobs.
+    pub fn depths(&self) -> HashMap<NotebookId, usize> {
+        match self.inner.lock() {
+            Ok(queue) => queue
+                .by_notebook
+                .iter()
+                .map(|(notebook_id, pending)| (*notebook_id, pending.len()))
+                .collect(),
+            Err(_) => HashMap::new(),
+        }
+    }
+
     /// Returns true if there are no pending jobs.
     pub fn is_empty(&self) -> bool {
         self.len() == 0
@@ -200,7 +302,7 @@ impl PropagationQueue {
     /// Clears all pending jobs from the queue.
     pub fn clear(&self) {
         if let Ok(mut queue) = self.inner.lock() {
-            queue.clear();
+            *queue = FairQueue::default();
         }
     }
 }
@@ -263,10 +365,72 @@ pub struct WorkerStats {
     pub jobs_failed: u64,
 }
 
+/// Cloneable view of a worker's progress.
+///
+/// Shares the worker's queue, statistics and dead letters, so it reports
+/// live figures while the worker runs on its own task.
+#[derive(Debug, Clone)]
+pub struct PropagationMonitor {
+    queue: PropagationQueue,
+    stats: Arc<Mutex<WorkerStats>>,
+    dead_letters: Arc<Mutex<Vec<PropagationJob>>>,
+}
+
+impl PropagationMonitor {
+    /// Returns the current worker statistics.
+    pub fn stats(&self) -> WorkerStats {
+        self.stats.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// Returns the current queue depth.
+    pub fn queue_depth(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns the number of jobs that failed and were set aside.
+    pub fn dead_letter_count(&self) -> usize {
+        self.dead_letters.lock().map(|d| d.len()).unwrap_or(0)
+    }
+
+    /// Returns true if a job is queued or being processed.
+    pub fn is_pending(&self, job_id: Uuid) -> bool {
+        self.queue.is_pending(job_id)
+    }
+
+    /// Waits until a job is no longer pending, for at most `timeout`.
+    ///
+    /// Returns false if the job was still pending at the deadline. A job
+    /// that was never enqueued is not pending.
+    pub async fn wait_for(&self, job_id: Uuid, timeout: Duration) -> bool {
+        let deadline = tokio::time::Instant::now() + timeout;
+        while self.queue.is_pending(job_id) {
+            if tokio::time::Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+        }
+        true
+    }
+
+    /// Returns the queue the worker drains.
+    pub fn queue(&self) -> &PropagationQueue {
+        &self.queue
+    }
+
+    /// Returns the jobs that failed, oldest first.
+    pub fn dead_letters(&self) -> Vec<PropagationJob> {
+        self.dead_letters
+            .lock()
+            .map(|d| d.clone())
+            .unwrap_or_default()
+    }
+}
+
 /// Background worker that processes the propagation queue.
 ///
 /// The worker polls the queue at a configurable interval and processes
-/// jobs asynchronously. It tracks completed job IDs to ensure idempotency.
+/// jobs asynchronously. It tracks completed job IDs to ensure idempotency,
+/// and keeps failed jobs as dead letters.
 pub struct PropagationWorker<U: CostUpdater> {
     /// The queue to process jobs from.
     queue: PropagationQueue,
@@ -280,6 +444,9 @@ pub struct PropagationWorker<U: CostUpdater> {
     /// Processing statistics.
     stats: Arc<Mutex<WorkerStats>>,
 
+    /// Jobs whose cost update failed.
+    dead_letters: Arc<Mutex<Vec<PropagationJob>>>,
+
     /// Poll interval for checking the queue.
     poll_interval: Duration,
 
@@ -299,6 +466,7 @@ impl<U: CostUpdater + 'static> PropagationWorker<U> {
             updater: Arc::new(updater),
             completed_jobs: Arc::new(Mutex::new(HashSet::new())),
             stats: Arc::new(Mutex::new(WorkerStats::default())),
+            dead_letters: Arc::new(Mutex::new(Vec::new())),
             poll_interval: Duration::from_millis(100),
             shutdown_tx: Some(shutdown_tx),
             shutdown_rx,
@@ -321,6 +489,25 @@ impl<U: CostUpdater + 'static> PropagationWorker<U> {
         self.queue.len()
     }
 
+    /// Returns the current queue depth per notebook with pending jobs.
+    pub fn queue_depths(&self) -> HashMap<NotebookId, usize> {
+        self.queue.depths()
+    }
+
+    /// Returns the number of jobs that failed and were set aside.
+    pub fn dead_letter_count(&self) -> usize {
+        self.monitor().dead_letter_count()
+    }
+
+    /// Returns a monitor that keeps reporting on this worker once started.
+    pub fn monitor(&self) -> PropagationMonitor {
+        PropagationMonitor {
+            queue: self.queue.clone(),
+            stats: self.stats.clone(),
+            dead_letters: self.dead_letters.clone(),
+        }
+    }
+
     /// Starts the background worker.
     ///
     /// Spawns a tokio task that polls the queue and processes jobs.
@@ -330,6 +517,7 @@ impl<U: CostUpdater + 'static> PropagationWorker<U> {
         let updater = self.updater.clone();
         let completed_jobs = self.completed_jobs.clone();
         let stats = self.stats.clone();
+        let dead_letters = self.dead_letters.clone();
         let poll_interval = self.poll_interval;
         let mut shutdown_rx = self.shutdown_rx.clone();
 
@@ -339,55 +527,7 @@ impl<U: CostUpdater + 'static> PropagationWorker<U> {
             loop {
                 tokio::select! {
                     _ = interval.tick() => {
-                        // Process all available jobs
-                        while let Some(job) = queue.process_next() {
-                            let job_id = job.job_id;
-                            let start = std::time::Instant::now();
-
-                            // Idempotency check
-                            let is_completed = completed_jobs
-                                .lock()
-                                .map(|set| set.contains(&job_id))
-                                .unwrap_or(false);
-
-                            if is_completed {
-                                debug!("Skipping already-completed job {}", job_id);
-                                if let Ok(mut s) = stats.lock() {
-                                    s.jobs_skipped += 1;
-                                }
-                                continue;
-                            }
-
-                            // Process the job
-                            match updater.update_cumulative_cost(
-                                job.notebook_id,
-                                &job.affected_entry_ids,
-                                job.cost_delta,
-                            ) {
-                                Ok(count) => {
-                                    let elapsed = start.elapsed();
-                                    info!(
-                                        "Processed propagation job {} in {:?}: {} entries updated",
-                                        job_id, elapsed, count
-                                    );
-
-                                    // Mark as completed and update stats
-                                    if let Ok(mut set) = completed_jobs.lock() {
-                                        set.insert(job_id);
-                                    }
-                                    if let Ok(mut s) = stats.lock() {
-                                        s.jobs_processed += 1;
-                                        s.entries_updated += count as u64;
-                                    }
-                                }
-                                Err(e) => {
-                                    warn!("Failed to process job {}: {}", job_id, e);
-                                    if let Ok(mut s) = stats.lock() {
-                                        s.jobs_failed += 1;
-                                    }
-                                }
-                            }
-                        }
+                        process_queue(&queue, updater.as_ref(), &completed_jobs, &stats, &dead_letters);
 
                         // Log queue depth periodically
                         let depth = queue.len();
@@ -397,6 +537,12 @@ impl<U: CostUpdater + 'static> PropagationWorker<U> {
                     }
                     _ = shutdown_rx.changed() => {
                         if *shutdown_rx.borrow() {
+                            // Flush queued jobs so their cost updates are not lost
+                            let remaining = queue.len();
+                            if remaining > 0 {
+                                info!("Draining {} propagation jobs before shutdown", remaining);
+                                process_queue(&queue, updater.as_ref(), &completed_jobs, &stats, &dead_letters);
+                            }
                             info!("Propagation worker shutting down");
                             break;
                         }
@@ -407,6 +553,9 @@ impl<U: CostUpdater + 'static> PropagationWorker<U> {
     }
 
     /// Signals the worker to shut down.
+    ///
+    /// The worker processes any jobs still queued before its task exits;
+    /// await the handle returned by [`start`](Self::start) to wait for that.
     pub fn shutdown(&mut self) {
         if let Some(tx) = self.shutdown_tx.take() {
             let _ = tx.send(true);
@@ -414,6 +563,77 @@ impl<U: CostUpdater + 'static> PropagationWorker<U> {
     }
 }
 
+/// Processes every job currently in the queue.
+fn process_queue<U: CostUpdater>(
+    queue: &PropagationQueue,
+    updater: &U,
+    completed_jobs: &Mutex<HashSet<Uuid>>,
+    stats: &Mutex<WorkerStats>,
+    dead_letters: &Mutex<Vec<PropagationJob>>,
+) {
+    while let Some(job) = queue.process_next() {
+        let job_id = job.job_id;
+        process_job(job, updater, completed_jobs, stats, dead_letters);
+        queue.finish(job_id);
+    }
+}
+
+/// Processes one job, skipping it if it already completed and setting it
+/// aside as a dead letter if its update fails.
+fn process_job<U: CostUpdater>(
+    job: PropagationJob,
+    updater: &U,
+    completed_jobs: &Mutex<HashSet<Uuid>>,
+    stats: &Mutex<WorkerStats>,
+    dead_letters: &Mutex<Vec<PropagationJob>>,
+) {
+    let job_id = job.job_id;
+    let start = std::time::Instant::now();
+
+    // Idempotency check
+    let is_completed = completed_jobs
+        .lock()
+        .map(|set| set.contains(&job_id))
+        .unwrap_or(false);
+
+    if is_completed {
+        debug!("Skipping already-completed job {}", job_id);
+        if let Ok(mut s) = stats.lock() {
+            s.jobs_skipped += 1;
+        }
+        return;
+    }
+
+    // Process the job
+    match updater.update_cumulative_cost(job.notebook_id, &job.affected_entry_ids, job.cost_delta) {
+        Ok(count) => {
+            let elapsed = start.elapsed();
+            info!(
+                "Processed propagation job {} in {:?}: {} entries updated",
+                job_id, elapsed, count
+            );
+
+            // Mark as completed and update stats
+            if let Ok(mut set) = completed_jobs.lock() {
+                set.insert(job_id);
+            }
+            if let Ok(mut s) = stats.lock() {
+                s.jobs_processed += 1;
+                s.entries_updated += count as u64;
+            }
+        }
+        Err(e) => {
+            warn!("Failed to process job {}: {}", job_id, e);
+            if let Ok(mut s) = stats.lock() {
+                s.jobs_failed += 1;
+            }
+            if let Ok(mut dead) = dead_letters.lock() {
+                dead.push(job);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 impl<U: CostUpdater + 'static> PropagationWorker<U> {
     /// Checks if a job has already been processed (test-only helper).
@@ -424,53 +644,15 @@ impl<U: CostUpdater + 'static> PropagationWorker<U> {
             .unwrap_or(false)
     }
 
-    /// Marks a job as completed (test-only helper).
-    fn mark_completed(&self, job_id: Uuid) {
-        if let Ok(mut set) = self.completed_jobs.lock() {
-            set.insert(job_id);
-        }
-    }
-
     /// Processes a single job synchronously (test-only helper).
     fn process_job(&self, job: PropagationJob) {
-        let job_id = job.job_id;
-        let start = std::time::Instant::now();
-
-        // Idempotency check
-        if self.is_completed(&job_id) {
-            debug!("Skipping already-completed job {}", job_id);
-            if let Ok(mut stats) = self.stats.lock() {
-                stats.jobs_skipped += 1;
-            }
-            return;
-        }
-
-        // Process the job
-        match self.updater.update_cumulative_cost(
-            job.notebook_id,
-            &job.affected_entry_ids,
-            job.cost_delta,
-        ) {
-            Ok(count) => {
-                let elapsed = start.elapsed();
-                info!(
-                    "Processed propagation job {} in {:?}: {} entries updated",
-                    job_id, elapsed, count
-                );
-
-                self.mark_completed(job_id);
-                if let Ok(mut stats) = self.stats.lock() {
-                    stats.jobs_processed += 1;
-                    stats.entries_updated += count as u64;
-                }
-            }
-            Err(e) => {
-                warn!("Failed to process job {}: {}", job_id, e);
-                if let Ok(mut stats) = self.stats.lock() {
-                    stats.jobs_failed += 1;
-                }
-            }
-        }
+        process_job(
+            job,
+            self.updater.as_ref(),
+            &self.completed_jobs,
+            &self.stats,
+            &self.dead_letters,
+        );
     }
 }
 
@@ -626,6 +808,103 @@ mod tests {
         assert!(queue1.is_empty());
     }
 
+    #[test]
+    fn propagation_queue_round_robin_across_notebooks() {
+        let queue = PropagationQueue::new();
+        let hot = make_notebook_id();
+        let quiet = make_notebook_id();
+
+        let hot_jobs: Vec<_> = (0..3)
+            .map(|_| PropagationJob::new(hot, vec![make_entry_id()], 0.5))
+            .collect();
+        let quiet_jobs: Vec<_> = (0..2)
+            .map(|_| PropagationJob::new(quiet, vec![make_entry_id()], 0.5))
+            .collect();
+        for job in hot_jobs.iter().chain(&quiet_jobs) {
+            queue.enqueue(job.clone());
+        }
+
+        let order: Vec<Uuid> = std::iter::from_fn(|| queue.process_next())
+            .map(|job| job.job_id)
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                hot_jobs[0].job_id,
+                quiet_jobs[0].job_id,
+                hot_jobs[1].job_id,
+                quiet_jobs[1].job_id,
+                hot_jobs[2].job_id,
+            ]
+        );
+    }
+
+    #[test]
+    fn propagation_queue_per_notebook_depth() {
+        let queue = PropagationQueue::new();
+        let first = make_notebook_id();
+        let second = make_notebook_id();
+
+        for _ in 0..3 {
+            queue.enqueue(PropagationJob::new(first, vec![make_entry_id()], 0.5));
+        }
+        queue.enqueue(PropagationJob::new(second, vec![make_entry_id()], 0.5));
+
+        assert_eq!(queue.len(), 4);
+        assert_eq!(queue.depth(first), 3);
+        assert_eq!(queue.depth(second), 1);
+        assert_eq!(queue.depth(make_notebook_id()), 0);
+        assert_eq!(queue.depths(), HashMap::from([(first, 3), (second, 1)]));
+
+        queue.process_next();
+        queue.process_next();
+        assert_eq!(queue.depth(second), 0);
+        assert_eq!(queue.depths(), HashMap::from([(first, 2)]));
+    }
+
+    /// Records the notebook of each update, in order.
+    #[derive(Default)]
+    struct RecordingUpdater {
+        calls: Mutex<Vec<NotebookId>>,
+    }
+
+    impl CostUpdater for RecordingUpdater {
+        fn update_cumulative_cost(
+            &self,
+            notebook_id: NotebookId,
+            entry_ids: &[EntryId],
+            _cost_delta: f64,
+        ) -> Result<usize, PropagationError> {
+            self.calls.lock().unwrap().push(notebook_id);
+            Ok(entry_ids.len())
+        }
+    }
+
+    #[test]
+    fn worker_interleaves_notebooks() {
+        let queue = PropagationQueue::new();
+        let hot = make_notebook_id();
+        let quiet = make_notebook_id();
+
+        // A burst for the hot notebook lands before the quiet notebook's jobs
+        for _ in 0..4 {
+            queue.enqueue(PropagationJob::new(hot, vec![make_entry_id()], 0.5));
+        }
+        for _ in 0..2 {
+            queue.enqueue(PropagationJob::new(quiet, vec![make_entry_id()], 0.5));
+        }
+
+        let updater = RecordingUpdater::default();
+        let completed = Mutex::new(HashSet::new());
+        let stats = Mutex::new(WorkerStats::default());
+        let dead_letters = Mutex::new(Vec::new());
+        process_queue(&queue, &updater, &completed, &stats, &dead_letters);
+
+        let calls = updater.calls.into_inner().unwrap();
+        assert_eq!(calls, vec![hot, quiet, hot, quiet, hot, hot]);
+        assert_eq!(stats.into_inner().unwrap().jobs_processed, 6);
+    }
+
     #[test]
     fn no_op_cost_updater() {
         let updater = NoOpCostUpdater;
@@ -674,6 +953,99 @@ mod tests {
         assert_eq!(stats.jobs_failed, 0);
     }
 
+    /// Fails every update.
+    struct FailingUpdater;
+
+    impl CostUpdater for FailingUpdater {
+        fn update_cumulative_cost(
+            &self,
+            _notebook_id: NotebookId,
+            _entry_ids: &[EntryId],
+            _cost_delta: f64,
+        ) -> Result<usize, PropagationError> {
+            Err(PropagationError::UpdateFailed("storage offline".into()))
+        }
+    }
+
+    #[test]
+    fn worker_keeps_failed_jobs_as_dead_letters() {
+        let queue = PropagationQueue::new();
+        let worker = PropagationWorker::new(queue.clone(), FailingUpdater);
+        let monitor = worker.monitor();
+        let job = PropagationJob::new(make_notebook_id(), vec![make_entry_id()], 0.5);
+
+        worker.process_job(job.clone());
+
+        assert_eq!(worker.stats().jobs_failed, 1);
+        assert_eq!(worker.dead_letter_count(), 1);
+        assert_eq!(monitor.dead_letters(), vec![job.clone()]);
+        // A failed job is not marked completed
+        assert!(!worker.is_completed(&job.job_id));
+    }
+
+    #[test]
+    fn monitor_shares_worker_state() {
+        let queue = PropagationQueue::new();
+        let worker = PropagationWorker::new(queue.clone(), NoOpCostUpdater);
+        let monitor = worker.monitor();
+        let notebook_id = make_notebook_id();
+
+        queue.enqueue(PropagationJob::new(notebook_id, vec![make_entry_id()], 0.5));
+        assert_eq!(monitor.queue_depth(), 1);
+
+        worker.process_job(queue.process_next().unwrap());
+        assert_eq!(monitor.queue_depth(), 0);
+        assert_eq!(monitor.stats().jobs_processed, 1);
+        assert_eq!(monitor.stats().entries_updated, 1);
+        assert_eq!(monitor.dead_letter_count(), 0);
+    }
+
+    #[test]
+    fn queue_tracks_jobs_until_finished() {
+        let queue = PropagationQueue::new();
+        let job = PropagationJob::new(make_notebook_id(), vec![make_entry_id()], 0.5);
+        assert!(!queue.is_pending(job.job_id));
+
+        queue.enqueue(job.clone());
+        assert!(queue.is_pending(job.job_id));
+
+        // Taken but not finished: still pending, no longer queued
+        queue.process_next().unwrap();
+        assert!(queue.is_empty());
+        assert!(queue.is_pending(job.job_id));
+
+        queue.finish(job.job_id);
+        assert!(!queue.is_pending(job.job_id));
+    }
+
+    #[tokio::test]
+    async fn monitor_waits_for_job_completion() {
+        let queue = PropagationQueue::new();
+        // Long poll interval: only the shutdown drain processes the job
+        let mut worker = PropagationWorker::new(queue.clone(), NoOpCostUpdater)
+            .with_poll_interval(Duration::from_secs(3600));
+        let monitor = worker.monitor();
+        let handle = worker.start();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let job = PropagationJob::new(make_notebook_id(), vec![make_entry_id()], 0.5);
+        let job_id = job.job_id;
+        queue.enqueue(job);
+        assert!(!monitor.wait_for(job_id, Duration::from_millis(30)).await);
+
+        let waiter = tokio::spawn({
+            let monitor = monitor.clone();
+            async move { monitor.wait_for(job_id, Duration::from_secs(5)).await }
+        });
+        worker.shutdown();
+        handle.await.unwrap();
+
+        assert!(waiter.await.unwrap());
+        assert!(worker.is_completed(&job_id));
+        // Never-enqueued jobs need no wait
+        assert!(monitor.wait_for(Uuid::new_v4(), Duration::ZERO).await);
+    }
+
     #[test]
     fn create_propagation_job_none_for_empty() {
         let notebook_id = make_notebook_id();
@@ -716,6 +1088,7 @@ mod tests {
 
         queue.enqueue(PropagationJob::new(notebook_id, vec![make_entry_id()], 0.5));
         assert_eq!(worker.queue_depth(), 1);
+        assert_eq!(worker.queue_depths(), HashMap::from([(notebook_id, 1)]));
     }
 
     #[tokio::test]
@@ -767,4 +1140,31 @@ mod tests {
         worker.shutdown();
         let _ = tokio::time::timeout(Duration::from_millis(100), handle).await;
     }
+
+    #[tokio::test]
+    async fn worker_flushes_queue_on_shutdown() {
+        let queue = PropagationQueue::new();
+        // Long poll interval: only the shutdown drain can process the jobs
+        let mut worker = PropagationWorker::new(queue.clone(), NoOpCostUpdater)
+            .with_poll_interval(Duration::from_secs(3600));
+
+        let handle = worker.start();
+        // Let the immediate first tick pass on an empty queue
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let notebook_id = make_notebook_id();
+        for _ in 0..3 {
+            queue.enqueue(PropagationJob::new(notebook_id, vec![make_entry_id()], 0.5));
+        }
+        assert_eq!(worker.queue_depth(), 3);
+
+        worker.shutdown();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("worker exits after draining")
+            .unwrap();
+
+        assert!(queue.is_empty());
+        assert_eq!(worker.stats().jobs_processed, 3);
+    }
 }
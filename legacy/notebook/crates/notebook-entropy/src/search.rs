@@ -5,6 +5,20 @@
 //! - Notebook-scoped search filtering
 //! - Match snippet generation with highlighting
 //!
+//! The index stores entry content (for snippets). Entries of encrypted
+//! notebooks are therefore only indexed by an in-memory index: use
+//! [`SearchIndex::index_notebook_entry`] so their plaintext never reaches disk.
+//!
+//! Changes are buffered and committed in batches, per [`CommitPolicy`],
+//! rather than one commit (and fsync) per document. Call
+//! [`SearchIndex::flush`] to commit outstanding changes, e.g. on shutdown;
+//! uncommitted changes are lost when the index is dropped.
+//!
+//! An on-disk index is derived data and can always be rebuilt from storage.
+//! [`SearchIndex::open_or_recreate`] replaces a corrupt index with an empty
+//! one instead of failing; searches then return [`SearchError::Rebuilding`]
+//! until [`SearchIndex::rebuild_from_entries`] has repopulated it.
+//!
 //! ## Example Usage
 //!
 //! ```rust,ignore
@@ -28,8 +42,10 @@
 //!
 //! Owned by: agent-search (Task 3-2)
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 use tantivy::collector::TopDocs;
@@ -49,6 +65,12 @@ const MAX_SNIPPET_LENGTH: usize = 200;
 /// Default heap size for the index writer (50 MB).
 const WRITER_HEAP_SIZE: usize = 50_000_000;
 
+/// Default number of buffered changes that triggers a commit.
+pub const DEFAULT_COMMIT_MAX_PENDING: usize = 100;
+
+/// Default age of the oldest buffered change that triggers a commit.
+pub const DEFAULT_COMMIT_MAX_DELAY: Duration = Duration::from_secs(1);
+
 /// Errors that can occur during search operations.
 #[derive(Error, Debug)]
 pub enum SearchError {
@@ -71,6 +93,10 @@ pub enum SearchError {
     /// Internal lock error.
     #[error("internal lock error")]
     LockError,
+
+    /// The index is being rebuilt and cannot serve searches yet.
+    #[error("search index is being rebuilt")]
+    Rebuilding,
 }
 
 impl<T> From<std::sync::PoisonError<T>> for SearchError {
@@ -92,6 +118,74 @@ pub struct SearchHit {
     pub snippet: String,
 }
 
+/// When buffered index changes are committed.
+///
+/// A commit happens on the write that reaches either limit. Changes that
+/// stop short of both wait for the next write, [`SearchIndex::flush_if_due`]
+/// or [`SearchIndex::flush`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommitPolicy {
+    /// Commit once this many changes are buffered.
+    pub max_pending: usize,
+    /// Commit once the oldest buffered change is this old.
+    pub max_delay: Duration,
+}
+
+impl CommitPolicy {
+    /// Commit every change as it is made.
+    pub fn immediate() -> Self {
+        Self {
+            max_pending: 1,
+            max_delay: Duration::ZERO,
+        }
+    }
+}
+
+impl Default for CommitPolicy {
+    fn default() -> Self {
+        Self {
+            max_pending: DEFAULT_COMMIT_MAX_PENDING,
+            max_delay: DEFAULT_COMMIT_MAX_DELAY,
+        }
+    }
+}
+
+/// Index writer with its uncommitted changes.
+struct BufferedWriter {
+    writer: IndexWriter,
+    /// Changes made since the last commit.
+    pending: usize,
+    /// When the oldest uncommitted change was made.
+    pending_since: Option<Instant>,
+}
+
+impl BufferedWriter {
+    /// Counts one uncommitted change.
+    fn record_change(&mut self) {
+        self.pending += 1;
+        self.pending_since.get_or_insert_with(Instant::now);
+    }
+
+    /// True if the buffered changes should be committed under `policy`.
+    fn is_due(&self, policy: &CommitPolicy) -> bool {
+        self.pending > 0
+            && (self.pending >= policy.max_pending
+                || self
+                    .pending_since
+                    .is_some_and(|since| since.elapsed() >= policy.max_delay))
+    }
+
+    /// Commits all changes made through the writer.
+    fn commit(&mut self) -> Result<(), SearchError> {
+        self.writer
+            .commit()
+            .map_err(|e| SearchError::IndexingError(format!("failed to commit: {}", e)))?;
+        self.pending = 0;
+        self.pending_since = None;
+        Ok(())
+    }
+}
+
 /// Schema field indices for the search index.
 #[derive(Clone)]
 struct SearchFields {
@@ -112,9 +206,15 @@ pub struct SearchIndex {
     #[allow(dead_code)]
     index: Index,
     reader: IndexReader,
-    writer: Arc<Mutex<IndexWriter>>,
+    writer: Arc<Mutex<BufferedWriter>>,
     fields: SearchFields,
     query_parser: QueryParser,
+    /// Whether the index is stored on disk.
+    persistent: bool,
+    /// Set while the index is empty or partial pending a rebuild.
+    rebuilding: AtomicBool,
+    /// When buffered changes are committed.
+    commit_policy: CommitPolicy,
 }
 
 impl SearchIndex {
@@ -144,6 +244,66 @@ impl SearchIndex {
         )
         .map_err(|e| SearchError::IndexError(format!("failed to open/create index: {}", e)))?;
 
+        Self::from_index(index, fields, true)
+    }
+
+    /// Opens the index at `index_path`, replacing it with an empty one if it
+    /// cannot be opened.
+    ///
+    /// A corrupt index directory is moved aside to `<index_path>.corrupt`
+    /// (replacing any earlier one) and a fresh index is created in its place.
+    /// The fresh index reports [`SearchIndex::is_rebuilding`] and refuses
+    /// searches until [`SearchIndex::rebuild_from_entries`] repopulates it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::IndexError` if the corrupt directory cannot be
+    /// moved aside or the fresh index cannot be created.
+    pub fn open_or_recreate(index_path: &Path) -> Result<Self, SearchError> {
+        let error = match Self::new(index_path) {
+            Ok(index) => return Ok(index),
+            Err(e) => e,
+        };
+        tracing::warn!(
+            path = %index_path.display(),
+            error = %error,
+            "Search index is unreadable, recreating it"
+        );
+
+        let aside = corrupt_path(index_path);
+        if aside.exists() {
+            std::fs::remove_dir_all(&aside).map_err(|e| {
+                SearchError::IndexError(format!("failed to remove old corrupt index: {}", e))
+            })?;
+        }
+        std::fs::rename(index_path, &aside).map_err(|e| {
+            SearchError::IndexError(format!("failed to move corrupt index aside: {}", e))
+        })?;
+
+        let index = Self::new(index_path)?;
+        index.rebuilding.store(true, Ordering::SeqCst);
+        Ok(index)
+    }
+
+    /// Creates a search index held entirely in memory.
+    ///
+    /// Nothing is written to disk, so it may index entries of encrypted
+    /// notebooks. The index is lost when dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::IndexError` if the index cannot be created.
+    pub fn in_memory() -> Result<Self, SearchError> {
+        let (schema, fields) = Self::build_schema();
+        Self::from_index(Index::create_in_ram(schema), fields, false)
+    }
+
+    /// Sets up the writer, reader and query parser for an index.
+    fn from_index(
+        index: Index,
+        fields: SearchFields,
+        persistent: bool,
+    ) -> Result<Self, SearchError> {
         // Create the writer
         let writer = index
             .writer(WRITER_HEAP_SIZE)
@@ -162,12 +322,35 @@ impl SearchIndex {
         Ok(Self {
             index,
             reader,
-            writer: Arc::new(Mutex::new(writer)),
+            writer: Arc::new(Mutex::new(BufferedWriter {
+                writer,
+                pending: 0,
+                pending_since: None,
+            })),
             fields,
             query_parser,
+            persistent,
+            rebuilding: AtomicBool::new(false),
+            commit_policy: CommitPolicy::default(),
         })
     }
 
+    /// Sets when buffered changes are committed.
+    pub fn with_commit_policy(mut self, policy: CommitPolicy) -> Self {
+        self.commit_policy = policy;
+        self
+    }
+
+    /// Returns true if the index is stored on disk.
+    pub fn is_persistent(&self) -> bool {
+        self.persistent
+    }
+
+    /// Returns true while the index awaits or undergoes a rebuild.
+    pub fn is_rebuilding(&self) -> bool {
+        self.rebuilding.load(Ordering::SeqCst)
+    }
+
     /// Builds the Tantivy schema for the search index.
     fn build_schema() -> (Schema, SearchFields) {
         let mut schema_builder = Schema::builder();
@@ -218,6 +401,7 @@ impl SearchIndex {
     /// Indexes an entry for full-text search.
     ///
     /// If an entry with the same ID already exists, it will be updated.
+    /// The change becomes searchable once committed; see [`CommitPolicy`].
     ///
     /// # Arguments
     ///
@@ -231,34 +415,114 @@ impl SearchIndex {
         let mut writer = self.writer.lock()?;
 
         // Delete any existing document with this entry_id
-        let entry_id_str = entry.id.to_string();
-        writer.delete_term(Term::from_field_text(self.fields.entry_id, &entry_id_str));
+        writer.writer.delete_term(Term::from_field_text(
+            self.fields.entry_id,
+            &entry.id.to_string(),
+        ));
+
+        writer
+            .writer
+            .add_document(self.document(notebook_id, entry))
+            .map_err(|e| SearchError::IndexingError(format!("failed to add document: {}", e)))?;
+
+        writer.record_change();
+        if writer.is_due(&self.commit_policy) {
+            writer.commit()?;
+        }
+
+        Ok(())
+    }
 
+    /// Builds the index document for an entry.
+    fn document(&self, notebook_id: NotebookId, entry: &Entry) -> tantivy::TantivyDocument {
         // Extract content as string
         let content_str = String::from_utf8_lossy(&entry.content);
 
         // Extract topic (empty string if none)
         let topic_str = entry.topic.as_deref().unwrap_or("");
 
-        // Build and add the document
-        let doc = doc!(
-            self.fields.entry_id => entry_id_str,
+        doc!(
+            self.fields.entry_id => entry.id.to_string(),
             self.fields.notebook_id => notebook_id.to_string(),
             self.fields.content => content_str.to_string(),
             self.fields.topic => topic_str,
             self.fields.author_id => entry.author.to_string(),
             self.fields.content_type => entry.content_type.clone(),
-        );
+        )
+    }
 
-        writer
-            .add_document(doc)
-            .map_err(|e| SearchError::IndexingError(format!("failed to add document: {}", e)))?;
+    /// Replaces the whole index with `entries`.
+    ///
+    /// Each item is an entry with its notebook and whether that notebook is
+    /// encrypted; encrypted entries are skipped as in
+    /// [`SearchIndex::index_notebook_entry`]. The old documents, and any
+    /// uncommitted changes, are dropped and the new ones added in a single
+    /// commit. Searches are refused while
+    /// the rebuild runs, and stay refused if it fails, so a partial index is
+    /// never served. Returns the number of entries indexed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::IndexingError` if the index cannot be rewritten.
+    pub fn rebuild_from_entries<'a, I>(&self, entries: I) -> Result<usize, SearchError>
+    where
+        I: IntoIterator<Item = (NotebookId, &'a Entry, bool)>,
+    {
+        self.rebuilding.store(true, Ordering::SeqCst);
+        let mut writer = self.writer.lock()?;
 
+        // Buffered changes would outlive the clear; the rebuild supersedes them
         writer
-            .commit()
-            .map_err(|e| SearchError::IndexingError(format!("failed to commit: {}", e)))?;
+            .writer
+            .rollback()
+            .map_err(|e| SearchError::IndexingError(format!("failed to discard changes: {}", e)))?;
+        writer
+            .writer
+            .delete_all_documents()
+            .map_err(|e| SearchError::IndexingError(format!("failed to clear index: {}", e)))?;
+
+        let mut indexed = 0;
+        for (notebook_id, entry, encrypted) in entries {
+            if encrypted && self.persistent {
+                continue;
+            }
+            writer
+                .writer
+                .add_document(self.document(notebook_id, entry))
+                .map_err(|e| {
+                    SearchError::IndexingError(format!("failed to add document: {}", e))
+                })?;
+            indexed += 1;
+        }
 
-        Ok(())
+        writer.commit()?;
+        drop(writer);
+
+        self.reload()?;
+        self.rebuilding.store(false, Ordering::SeqCst);
+        Ok(indexed)
+    }
+
+    /// Indexes an entry, respecting its notebook's encryption.
+    ///
+    /// Entries of encrypted notebooks are only indexed when the index is in
+    /// memory: a persistent index stores content, which would leave their
+    /// plaintext at rest. Returns whether the entry was indexed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::IndexingError` if the entry cannot be indexed.
+    pub fn index_notebook_entry(
+        &self,
+        notebook_id: NotebookId,
+        entry: &Entry,
+        encrypted: bool,
+    ) -> Result<bool, SearchError> {
+        if encrypted && self.persistent {
+            return Ok(false);
+        }
+        self.index_entry(notebook_id, entry)?;
+        Ok(true)
     }
 
     /// Searches for entries matching the query within a specific notebook.
@@ -271,7 +535,8 @@ impl SearchIndex {
     ///
     /// # Errors
     ///
-    /// Returns `SearchError::QueryParseError` if the query cannot be parsed,
+    /// Returns `SearchError::Rebuilding` while the index is being rebuilt,
+    /// `SearchError::QueryParseError` if the query cannot be parsed,
     /// or `SearchError::SearchExecutionError` if the search fails.
     pub fn search(
         &self,
@@ -279,6 +544,10 @@ impl SearchIndex {
         notebook_id: NotebookId,
         limit: usize,
     ) -> Result<Vec<SearchHit>, SearchError> {
+        if self.is_rebuilding() {
+            return Err(SearchError::Rebuilding);
+        }
+
         let searcher = self.reader.searcher();
 
         // Parse the text query
@@ -360,6 +629,8 @@ impl SearchIndex {
 
     /// Deletes an entry from the search index.
     ///
+    /// Like additions, the deletion takes effect once committed.
+    ///
     /// # Arguments
     ///
     /// * `entry_id` - The ID of the entry to delete.
@@ -371,15 +642,52 @@ impl SearchIndex {
         let mut writer = self.writer.lock()?;
 
         let term = Term::from_field_text(self.fields.entry_id, &entry_id.to_string());
-        writer.delete_term(term);
+        writer.writer.delete_term(term);
 
-        writer
-            .commit()
-            .map_err(|e| SearchError::IndexingError(format!("failed to commit delete: {}", e)))?;
+        writer.record_change();
+        if writer.is_due(&self.commit_policy) {
+            writer.commit()?;
+        }
 
         Ok(())
     }
 
+    /// Commits all buffered changes and makes them searchable.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::IndexingError` if the commit fails.
+    pub fn flush(&self) -> Result<(), SearchError> {
+        let mut writer = self.writer.lock()?;
+        if writer.pending > 0 {
+            writer.commit()?;
+        }
+        drop(writer);
+
+        self.reload()
+    }
+
+    /// Commits buffered changes if the commit policy calls for it.
+    ///
+    /// Meant to be called periodically, so changes that stop short of
+    /// [`CommitPolicy::max_pending`] still land within about
+    /// [`CommitPolicy::max_delay`]. Returns whether a commit happened.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::IndexingError` if the commit fails.
+    pub fn flush_if_due(&self) -> Result<bool, SearchError> {
+        let mut writer = self.writer.lock()?;
+        if !writer.is_due(&self.commit_policy) {
+            return Ok(false);
+        }
+        writer.commit()?;
+        drop(writer);
+
+        self.reload()?;
+        Ok(true)
+    }
+
     /// Forces a reload of the index reader.
     ///
     /// Normally, the reader auto-reloads after commits. This method can be used
@@ -391,6 +699,13 @@ impl SearchIndex {
     }
 }
 
+/// Where [`SearchIndex::open_or_recreate`] moves a corrupt index.
+fn corrupt_path(index_path: &Path) -> PathBuf {
+    let mut name = index_path.as_os_str().to_owned();
+    name.push(".corrupt");
+    PathBuf::from(name)
+}
+
 /// Truncates a string to a maximum number of characters, respecting UTF-8 boundaries.
 fn truncate_to_char_boundary(s: &str, max_chars: usize) -> &str {
     if s.chars().count() <= max_chars {
@@ -453,9 +768,7 @@ mod tests {
 
         index.index_entry(notebook_id, &entry).unwrap();
 
-        // Wait for commit to be visible
-        std::thread::sleep(std::time::Duration::from_millis(100));
-        index.reload().unwrap();
+        index.flush().unwrap();
 
         let hits = index.search("quick fox", notebook_id, 10).unwrap();
         assert_eq!(hits.len(), 1);
@@ -477,8 +790,7 @@ mod tests {
         index.index_entry(notebook1, &entry1).unwrap();
         index.index_entry(notebook2, &entry2).unwrap();
 
-        std::thread::sleep(std::time::Duration::from_millis(100));
-        index.reload().unwrap();
+        index.flush().unwrap();
 
         // Search in notebook1 should only find entry1
         let hits1 = index.search("knowledge", notebook1, 10).unwrap();
@@ -501,8 +813,7 @@ mod tests {
         let entry_id = entry.id;
 
         index.index_entry(notebook_id, &entry).unwrap();
-        std::thread::sleep(std::time::Duration::from_millis(100));
-        index.reload().unwrap();
+        index.flush().unwrap();
 
         // Should find the entry
         let hits = index.search("unique", notebook_id, 10).unwrap();
@@ -510,8 +821,7 @@ mod tests {
 
         // Delete the entry
         index.delete_entry(entry_id).unwrap();
-        std::thread::sleep(std::time::Duration::from_millis(100));
-        index.reload().unwrap();
+        index.flush().unwrap();
 
         // Should not find the entry anymore
         let hits = index.search("unique", notebook_id, 10).unwrap();
@@ -538,8 +848,7 @@ mod tests {
             .build();
 
         index.index_entry(notebook_id, &updated_entry).unwrap();
-        std::thread::sleep(std::time::Duration::from_millis(100));
-        index.reload().unwrap();
+        index.flush().unwrap();
 
         // Should find with new content
         let hits = index.search("updated different", notebook_id, 10).unwrap();
@@ -559,14 +868,213 @@ mod tests {
         let entry = create_test_entry("Some generic content", Some("architecture design"));
 
         index.index_entry(notebook_id, &entry).unwrap();
-        std::thread::sleep(std::time::Duration::from_millis(100));
-        index.reload().unwrap();
+        index.flush().unwrap();
 
         // Should find by topic
         let hits = index.search("architecture", notebook_id, 10).unwrap();
         assert_eq!(hits.len(), 1);
     }
 
+    /// True if any file under `dir` contains `needle`.
+    fn directory_contains(dir: &Path, needle: &[u8]) -> bool {
+        std::fs::read_dir(dir).unwrap().any(|file| {
+            let path = file.unwrap().path();
+            if path.is_dir() {
+                directory_contains(&path, needle)
+            } else {
+                let bytes = std::fs::read(&path).unwrap();
+                bytes.windows(needle.len()).any(|w| w == needle)
+            }
+        })
+    }
+
+    #[test]
+    fn test_encrypted_entries_not_indexed_on_disk() {
+        let temp_dir = TempDir::new().unwrap();
+        let index = SearchIndex::new(temp_dir.path()).unwrap();
+        assert!(index.is_persistent());
+
+        let notebook_id = NotebookId::new();
+        let secret = create_test_entry("classified zeppelin itinerary", None);
+        let public = create_test_entry("public zeppelin timetable", None);
+
+        assert!(
+            !index
+                .index_notebook_entry(notebook_id, &secret, true)
+                .unwrap()
+        );
+        assert!(
+            index
+                .index_notebook_entry(notebook_id, &public, false)
+                .unwrap()
+        );
+        index.flush().unwrap();
+
+        let hits = index.search("zeppelin", notebook_id, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entry_id, public.id);
+        assert!(!directory_contains(temp_dir.path(), b"classified"));
+        assert!(directory_contains(temp_dir.path(), b"timetable"));
+    }
+
+    #[test]
+    fn test_in_memory_index_searches_encrypted_entries() {
+        let index = SearchIndex::in_memory().unwrap();
+        assert!(!index.is_persistent());
+
+        let notebook_id = NotebookId::new();
+        let secret = create_test_entry("classified zeppelin itinerary", None);
+        assert!(
+            index
+                .index_notebook_entry(notebook_id, &secret, true)
+                .unwrap()
+        );
+        index.flush().unwrap();
+
+        let hits = index.search("itinerary", notebook_id, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entry_id, secret.id);
+    }
+
+    #[test]
+    fn test_flushed_entries_survive_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let notebook_id = NotebookId::new();
+        let kept = create_test_entry("Persistent pelican sighting", None);
+        let deleted = create_test_entry("Deleted pelican sighting", None);
+
+        let index = SearchIndex::new(temp_dir.path()).unwrap();
+        index.index_entry(notebook_id, &kept).unwrap();
+        index.index_entry(notebook_id, &deleted).unwrap();
+        index.delete_entry(deleted.id).unwrap();
+        index.flush().unwrap();
+        drop(index);
+
+        let index = SearchIndex::new(temp_dir.path()).unwrap();
+        let hits = index.search("pelican", notebook_id, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entry_id, kept.id);
+    }
+
+    #[test]
+    fn test_changes_are_committed_in_batches() {
+        let temp_dir = TempDir::new().unwrap();
+        let policy = CommitPolicy {
+            max_pending: 2,
+            max_delay: Duration::from_secs(3600),
+        };
+        let index = SearchIndex::new(temp_dir.path())
+            .unwrap()
+            .with_commit_policy(policy);
+        let notebook_id = NotebookId::new();
+
+        index
+            .index_entry(notebook_id, &create_test_entry("First heron", None))
+            .unwrap();
+        index.reload().unwrap();
+        assert!(index.search("heron", notebook_id, 10).unwrap().is_empty());
+        assert!(!index.flush_if_due().unwrap());
+
+        // The second change fills the batch
+        index
+            .index_entry(notebook_id, &create_test_entry("Second heron", None))
+            .unwrap();
+        index.reload().unwrap();
+        assert_eq!(index.search("heron", notebook_id, 10).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_flush_if_due_commits_after_delay() {
+        let index = SearchIndex::in_memory()
+            .unwrap()
+            .with_commit_policy(CommitPolicy {
+                max_pending: usize::MAX,
+                max_delay: Duration::from_millis(50),
+            });
+        let notebook_id = NotebookId::new();
+
+        assert!(!index.flush_if_due().unwrap());
+        index
+            .index_entry(notebook_id, &create_test_entry("Lone egret", None))
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(index.flush_if_due().unwrap());
+        assert_eq!(index.search("egret", notebook_id, 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_corrupt_index_is_recreated_and_rebuilt() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("index");
+        let notebook_id = NotebookId::new();
+        let entry = create_test_entry("Recoverable knowledge", None);
+
+        let index = SearchIndex::new(&path).unwrap();
+        index.index_entry(notebook_id, &entry).unwrap();
+        drop(index);
+
+        // Clobber the index metadata
+        std::fs::write(path.join("meta.json"), b"not json").unwrap();
+        assert!(SearchIndex::new(&path).is_err());
+
+        let index = SearchIndex::open_or_recreate(&path).unwrap();
+        assert!(index.is_rebuilding());
+        assert!(corrupt_path(&path).join("meta.json").exists());
+        assert!(matches!(
+            index.search("recoverable", notebook_id, 10),
+            Err(SearchError::Rebuilding)
+        ));
+
+        let indexed = index
+            .rebuild_from_entries([(notebook_id, &entry, false)])
+            .unwrap();
+        assert_eq!(indexed, 1);
+        assert!(!index.is_rebuilding());
+
+        let hits = index.search("recoverable", notebook_id, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entry_id, entry.id);
+    }
+
+    #[test]
+    fn test_open_or_recreate_keeps_healthy_index() {
+        let temp_dir = TempDir::new().unwrap();
+        let notebook_id = NotebookId::new();
+        let entry = create_test_entry("Durable knowledge", None);
+
+        let index = SearchIndex::new(temp_dir.path()).unwrap();
+        index.index_entry(notebook_id, &entry).unwrap();
+        index.flush().unwrap();
+        drop(index);
+
+        let index = SearchIndex::open_or_recreate(temp_dir.path()).unwrap();
+        assert!(!index.is_rebuilding());
+        assert!(!corrupt_path(temp_dir.path()).exists());
+        let hits = index.search("durable", notebook_id, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+    }
+
+    #[test]
+    fn test_rebuild_replaces_stale_documents() {
+        let temp_dir = TempDir::new().unwrap();
+        let index = SearchIndex::new(temp_dir.path()).unwrap();
+        let notebook_id = NotebookId::new();
+
+        let stale = create_test_entry("Stale walrus notes", None);
+        index.index_entry(notebook_id, &stale).unwrap();
+
+        let fresh = create_test_entry("Fresh walrus notes", None);
+        let secret = create_test_entry("Secret walrus notes", None);
+        let indexed = index
+            .rebuild_from_entries([(notebook_id, &fresh, false), (notebook_id, &secret, true)])
+            .unwrap();
+        assert_eq!(indexed, 1);
+
+        let hits = index.search("walrus", notebook_id, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entry_id, fresh.id);
+    }
+
     #[test]
     fn test_truncate_to_char_boundary() {
         assert_eq!(truncate_to_char_boundary("hello", 10), "hello");
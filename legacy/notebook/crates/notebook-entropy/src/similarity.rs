@@ -0,0 +1,307 @@
+//! Notebook-level similarity for "similar notebooks" discovery.
+//!
+//! Each notebook is summarized as a [`NotebookProfile`]: the average term
+//! frequency of its entries. To compare notebooks, every profile in the
+//! comparison is treated as one document of a corpus and weighted by TF-IDF
+//! over that corpus, so terms that many of the notebooks use count for less
+//! than the ones that set a notebook apart. Notebooks are ranked by the
+//! cosine similarity of their weighted profiles.
+//!
+//! The IDF is smoothed (`1 + ln(N / df)`): with only a handful of notebooks
+//! the plain `ln(N / df)` would zero out every term two notebooks share.
+//!
+//! Building a profile means reading every entry, so profiles are kept in a
+//! [`NotebookProfileCache`] keyed by the notebook's sequence number.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use notebook_core::types::{Entry, NotebookId};
+
+use crate::text_extraction::extract_text;
+use crate::tfidf::{CorpusStats, Locale, TfIdfVector, term_frequency, tokenize_in};
+
+/// Similarity below which notebooks are not reported as similar.
+pub const DEFAULT_MIN_SIMILARITY: f64 = 0.1;
+
+/// Number of shared terms reported per similar notebook.
+const SHARED_TERMS: usize = 5;
+
+/// Average term frequencies over a notebook's entries.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NotebookProfile {
+    /// Term to mean frequency across entries.
+    terms: HashMap<String, f64>,
+    /// Entries the profile was built from.
+    entry_count: usize,
+}
+
+impl NotebookProfile {
+    /// Builds the profile of a notebook from its entries.
+    ///
+    /// Entries without extractable text still count towards the average.
+    pub fn from_entries(entries: &[Entry], locale: Locale) -> Self {
+        let mut terms: HashMap<String, f64> = HashMap::new();
+        for entry in entries {
+            let text = extract_text(&entry.content, &entry.content_type).unwrap_or_default();
+            for (term, frequency) in term_frequency(&tokenize_in(&text, locale)) {
+                *terms.entry(term).or_insert(0.0) += frequency;
+            }
+        }
+
+        let count = entries.len().max(1) as f64;
+        for frequency in terms.values_mut() {
+            *frequency /= count;
+        }
+        Self {
+            terms,
+            entry_count: entries.len(),
+        }
+    }
+
+    /// Number of entries the profile was built from.
+    pub fn entry_count(&self) -> usize {
+        self.entry_count
+    }
+
+    /// Whether the notebook has no terms to compare.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// The profile weighted against a corpus of notebook profiles.
+    fn weighted(&self, corpus: &CorpusStats) -> TfIdfVector {
+        TfIdfVector {
+            weights: self
+                .terms
+                .iter()
+                .map(|(term, frequency)| (term.clone(), frequency * (1.0 + corpus.idf(term))))
+                .collect(),
+        }
+    }
+}
+
+/// A notebook ranked by similarity to another.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimilarNotebook<K> {
+    /// The candidate's key, as passed to [`rank_similar`].
+    pub key: K,
+    /// Cosine similarity of the weighted profiles, 0.0-1.0.
+    pub similarity: f64,
+    /// Terms contributing most to the similarity, strongest first.
+    pub shared_terms: Vec<String>,
+}
+
+/// Ranks `candidates` by similarity to `target`, most similar first.
+///
+/// The target and all candidates form the corpus for IDF weighting.
+/// Candidates less similar than `min_similarity` are left out.
+pub fn rank_similar<K: Clone>(
+    target: &NotebookProfile,
+    candidates: &[(K, &NotebookProfile)],
+    min_similarity: f64,
+) -> Vec<SimilarNotebook<K>> {
+    let mut corpus = CorpusStats::new();
+    for profile in std::iter::once(target).chain(candidates.iter().map(|(_, p)| *p)) {
+        let terms: Vec<String> = profile.terms.keys().cloned().collect();
+        corpus.add_document(&terms);
+    }
+
+    let target_vector = target.weighted(&corpus);
+    let mut ranked: Vec<SimilarNotebook<K>> = candidates
+        .iter()
+        .filter_map(|(key, profile)| {
+            let vector = profile.weighted(&corpus);
+            let similarity = target_vector.cosine_similarity(&vector);
+            (similarity >= min_similarity && similarity > 0.0).then(|| SimilarNotebook {
+                key: key.clone(),
+                similarity,
+                shared_terms: shared_terms(&target_vector, &vector),
+            })
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.similarity
+            .partial_cmp(&a.similarity)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    ranked
+}
+
+/// Terms with the largest product of weights in both vectors.
+fn shared_terms(a: &TfIdfVector, b: &TfIdfVector) -> Vec<String> {
+    let products: HashMap<String, f64> = a
+        .weights
+        .iter()
+        .filter_map(|(term, weight)| b.weights.get(term).map(|w| (term.clone(), weight * w)))
+        .collect();
+    TfIdfVector { weights: products }.top_terms(SHARED_TERMS)
+}
+
+/// A cached profile with the sequence it was built at.
+type ProfileAtSequence = (i64, Arc<NotebookProfile>);
+
+/// Notebook profiles, each valid while its notebook's sequence is unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct NotebookProfileCache {
+    profiles: Arc<RwLock<HashMap<NotebookId, ProfileAtSequence>>>,
+}
+
+impl NotebookProfileCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The cached profile of a notebook, if it was built at `sequence`.
+    pub fn get(&self, notebook_id: &NotebookId, sequence: i64) -> Option<Arc<NotebookProfile>> {
+        let profiles = self.profiles.read().expect("profile cache lock poisoned");
+        profiles
+            .get(notebook_id)
+            .filter(|(built_at, _)| *built_at == sequence)
+            .map(|(_, profile)| profile.clone())
+    }
+
+    /// Caches a notebook's profile as built at `sequence`.
+    pub fn insert(
+        &self,
+        notebook_id: NotebookId,
+        sequence: i64,
+        profile: NotebookProfile,
+    ) -> Arc<NotebookProfile> {
+        let profile = Arc::new(profile);
+        let mut profiles = self.profiles.write().expect("profile cache lock poisoned");
+        profiles.insert(notebook_id, (sequence, profile.clone()));
+        profile
+    }
+
+    /// Drops a notebook's profile.
+    pub fn invalidate(&self, notebook_id: &NotebookId) {
+        let mut profiles = self.profiles.write().expect("profile cache lock poisoned");
+        profiles.remove(notebook_id);
+    }
+
+    /// Number of cached profiles.
+    pub fn len(&self) -> usize {
+        self.profiles
+            .read()
+            .expect("profile cache lock poisoned")
+            .len()
+    }
+
+    /// Whether no profiles are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notebook_core::types::{AuthorId, EntryBuilder};
+
+    fn profile(contents: &[&str]) -> NotebookProfile {
+        let entries: Vec<Entry> = contents
+            .iter()
+            .map(|content| {
+                EntryBuilder::default()
+                    .content(content.as_bytes().to_vec())
+                    .content_type("text/plain")
+                    .author(AuthorId::zero())
+                    .build()
+            })
+            .collect();
+        NotebookProfile::from_entries(&entries, Locale::English)
+    }
+
+    fn baking() -> NotebookProfile {
+        profile(&[
+            "Sourdough bread needs a lively starter",
+            "Feed the sourdough starter with flour and water",
+            "Bake the bread in a hot dutch oven",
+        ])
+    }
+
+    fn more_baking() -> NotebookProfile {
+        profile(&[
+            "A rye sourdough starter ferments slowly",
+            "Bread flour gives the loaf more structure",
+        ])
+    }
+
+    fn astronomy() -> NotebookProfile {
+        profile(&[
+            "Jupiter has dozens of moons",
+            "The telescope resolves Saturn's rings",
+        ])
+    }
+
+    #[test]
+    fn same_topic_notebooks_rank_as_similar() {
+        let target = baking();
+        let (related, unrelated) = (more_baking(), astronomy());
+        let ranked = rank_similar(
+            &target,
+            &[("astronomy", &unrelated), ("baking", &related)],
+            DEFAULT_MIN_SIMILARITY,
+        );
+
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].key, "baking");
+        assert!(ranked[0].similarity > DEFAULT_MIN_SIMILARITY);
+        assert!(ranked[0].shared_terms.contains(&"sourdough".to_string()));
+        assert!(ranked[0].shared_terms.contains(&"starter".to_string()));
+    }
+
+    #[test]
+    fn closer_notebooks_rank_first() {
+        let target = baking();
+        let (close, loose) = (
+            more_baking(),
+            profile(&["Flour prices rose this year", "Jupiter has dozens of moons"]),
+        );
+        let ranked = rank_similar(&target, &[("loose", &loose), ("close", &close)], 0.0);
+
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].key, "close");
+        assert!(ranked[0].similarity > ranked[1].similarity);
+    }
+
+    #[test]
+    fn empty_notebooks_are_never_similar() {
+        let empty = profile(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.entry_count(), 0);
+        assert!(rank_similar(&baking(), &[("empty", &empty)], 0.0).is_empty());
+        assert!(rank_similar(&empty, &[("baking", &baking())], 0.0).is_empty());
+    }
+
+    #[test]
+    fn profile_averages_term_frequencies() {
+        let single = profile(&["sourdough starter"]);
+        let doubled = profile(&["sourdough starter", "sourdough starter"]);
+        assert_eq!(
+            single,
+            NotebookProfile {
+                entry_count: 1,
+                ..doubled.clone()
+            }
+        );
+        assert_eq!(doubled.entry_count(), 2);
+    }
+
+    #[test]
+    fn cache_is_keyed_by_sequence() {
+        let cache = NotebookProfileCache::new();
+        let notebook_id = NotebookId::new();
+        assert!(cache.get(&notebook_id, 3).is_none());
+
+        cache.insert(notebook_id, 3, baking());
+        assert_eq!(*cache.get(&notebook_id, 3).unwrap(), baking());
+        // A write since the profile was built makes it stale
+        assert!(cache.get(&notebook_id, 4).is_none());
+
+        cache.invalidate(&notebook_id);
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,242 @@
+//! Content-type-aware text extraction.
+//!
+//! The coherence model tokenizes entry content for TF-IDF. Feeding it raw
+//! markup makes tag names, JSON keys and URLs show up as keywords, so this
+//! module reduces each supported content type to its prose first:
+//!
+//! - HTML and XML: tags, comments, scripts and styles are removed
+//! - JSON: only string values are kept; keys and punctuation are dropped
+//! - Markdown: headings, emphasis, code fences and link targets are removed
+//! - Other `text/*` types: used as-is
+//!
+//! Binary and unknown content types yield no text at all.
+
+use serde_json::Value;
+
+/// Extracts the prose from entry content for tokenization.
+///
+/// Returns `None` for content types that carry no text (e.g. images or
+/// `application/octet-stream`).
+pub fn extract_text(content: &[u8], content_type: &str) -> Option<String> {
+    let media_type = media_type(content_type);
+    let raw = || String::from_utf8_lossy(content);
+
+    match media_type.as_str() {
+        "text/html" | "application/xhtml+xml" | "text/xml" | "application/xml" => {
+            Some(strip_markup(&raw()))
+        }
+        "text/markdown" | "text/x-markdown" => Some(strip_markdown(&raw())),
+        "application/json" => Some(json_strings(&raw())),
+        t if t.ends_with("+json") => Some(json_strings(&raw())),
+        t if t.ends_with("+xml") => Some(strip_markup(&raw())),
+        t if t.starts_with("text/") => Some(raw().into_owned()),
+        _ => None,
+    }
+}
+
+/// Normalizes a content type to its lowercase media type, without parameters.
+fn media_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Removes tags, comments, and script/style bodies from HTML or XML.
+///
+/// Tags are replaced by spaces so adjacent elements don't merge words.
+fn strip_markup(markup: &str) -> String {
+    let mut text = String::with_capacity(markup.len());
+    let mut rest = markup;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        text.push(' ');
+        rest = &rest[start..];
+
+        if rest.starts_with("<!--") {
+            rest = rest.find("-->").map_or("", |end| &rest[end + 3..]);
+            continue;
+        }
+
+        let Some(end) = rest.find('>') else {
+            // Not a tag after all (e.g. "a < b"): keep the text
+            rest = &rest[1..];
+            break;
+        };
+        let tag = rest[1..end].trim_start().to_ascii_lowercase();
+        rest = &rest[end + 1..];
+
+        // Script and style bodies are code, not prose
+        for skipped in ["script", "style"] {
+            if tag.starts_with(skipped) && !tag.ends_with('/') {
+                let closing = format!("</{}", skipped);
+                rest = rest
+                    .to_ascii_lowercase()
+                    .find(&closing)
+                    .map_or("", |pos| &rest[pos..]);
+            }
+        }
+    }
+    text.push_str(rest);
+
+    decode_entities(&text)
+}
+
+/// Decodes the handful of character entities common in prose.
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Collects the string values of a JSON document, ignoring keys.
+///
+/// Falls back to the raw text if the content is not valid JSON.
+fn json_strings(json: &str) -> String {
+    fn collect(value: &Value, out: &mut Vec<String>) {
+        match value {
+            Value::String(s) => out.push(s.clone()),
+            Value::Array(items) => items.iter().for_each(|item| collect(item, out)),
+            Value::Object(map) => map.values().for_each(|item| collect(item, out)),
+            Value::Null | Value::Bool(_) | Value::Number(_) => {}
+        }
+    }
+
+    match serde_json::from_str::<Value>(json) {
+        Ok(value) => {
+            let mut strings = Vec::new();
+            collect(&value, &mut strings);
+            strings.join("\n")
+        }
+        Err(_) => json.to_string(),
+    }
+}
+
+/// Removes Markdown syntax, keeping the readable text.
+///
+/// Code fences are dropped along with their contents; inline link and image
+/// targets are removed while their labels are kept.
+fn strip_markdown(markdown: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_fence = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let line = trimmed
+            .trim_start_matches('>')
+            .trim_start()
+            .trim_start_matches('#')
+            .trim_start();
+        let line = line
+            .strip_prefix("- ")
+            .or_else(|| line.strip_prefix("* "))
+            .or_else(|| line.strip_prefix("+ "))
+            .unwrap_or(line);
+
+        lines.push(strip_link_targets(line));
+    }
+
+    let text = lines.join("\n");
+    let text = strip_markup(&text);
+    text.chars()
+        .map(|c| match c {
+            '*' | '_' | '`' | '~' | '|' => ' ',
+            c => c,
+        })
+        .collect()
+}
+
+/// Replaces `[label](target)` and `![alt](target)` with their label.
+fn strip_link_targets(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(close) = rest.find("](") {
+        let Some(end) = rest[close..].find(')') else {
+            break;
+        };
+        let label_start = rest[..close].rfind('[').unwrap_or(close);
+        let before = rest[..label_start].trim_end_matches('!');
+        out.push_str(before);
+        if label_start < close {
+            out.push_str(&rest[label_start + 1..close]);
+        }
+        rest = &rest[close + end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_text_is_unchanged() {
+        let text = extract_text(b"Hello world", "text/plain; charset=utf-8");
+        assert_eq!(text.as_deref(), Some("Hello world"));
+    }
+
+    #[test]
+    fn binary_content_yields_no_text() {
+        assert_eq!(extract_text(&[0xFF, 0xD8, 0xFF], "image/jpeg"), None);
+        assert_eq!(extract_text(b"abc", "application/octet-stream"), None);
+    }
+
+    #[test]
+    fn html_tags_are_stripped() {
+        let html = br#"<div class="note"><p>Rust &amp; tokio</p><script>var x = 1;</script><!-- hidden --></div>"#;
+        let text = extract_text(html, "text/html").unwrap();
+        assert!(text.contains("Rust & tokio"));
+        assert!(!text.contains("div"));
+        assert!(!text.contains("class"));
+        assert!(!text.contains("var"));
+        assert!(!text.contains("hidden"));
+    }
+
+    #[test]
+    fn json_keeps_string_values_only() {
+        let json =
+            br#"{"description": "async runtime", "metadata": {"tags": ["tokio"], "count": 3}}"#;
+        let text = extract_text(json, "application/json").unwrap();
+        assert!(text.contains("async runtime"));
+        assert!(text.contains("tokio"));
+        assert!(!text.contains("description"));
+        assert!(!text.contains("metadata"));
+    }
+
+    #[test]
+    fn invalid_json_falls_back_to_raw_text() {
+        let text = extract_text(b"not { json", "application/json").unwrap();
+        assert_eq!(text, "not { json");
+    }
+
+    #[test]
+    fn markdown_syntax_is_stripped() {
+        let md = b"# Title\n\nSee the [tokio docs](https://tokio.rs/docs) for **details**.\n\n```rust\nfn main() {}\n```\n- item";
+        let text = extract_text(md, "text/markdown").unwrap();
+        assert!(text.contains("Title"));
+        assert!(text.contains("tokio docs"));
+        assert!(text.contains("details"));
+        assert!(text.contains("item"));
+        assert!(!text.contains("https"));
+        assert!(!text.contains("fn main"));
+        assert!(!text.contains('#'));
+        assert!(!text.contains("**"));
+    }
+}
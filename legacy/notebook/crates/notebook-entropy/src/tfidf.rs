@@ -2,7 +2,7 @@
 //!
 //! This module provides text analysis capabilities for the coherence model:
 //! - Tokenization with Unicode support
-//! - Stop word removal for English text
+//! - Stop word removal for English and French text
 //! - TF-IDF weight computation
 //! - Cosine similarity for document comparison
 //!
@@ -10,6 +10,8 @@
 //! and hash maps rather than external NLP libraries.
 
 use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
 use unicode_segmentation::UnicodeSegmentation;
 
 /// Common English stop words to filter from text analysis.
@@ -143,6 +145,81 @@ const STOP_WORDS: &[&str] = &[
     "yourselves",
 ];
 
+/// Common French stop words to filter from text analysis.
+const FRENCH_STOP_WORDS: &[&str] = &[
+    "ai", "ainsi", "alors", "as", "au", "aussi", "autre", "autres", "aux", "avaient", "avait",
+    "avant", "avec", "avez", "avoir", "avons", "ce", "ceci", "cela", "celle", "celles", "celui",
+    "ces", "cet", "cette", "ceux", "chez", "comme", "comment", "dans", "de", "depuis", "des",
+    "donc", "dont", "du", "elle", "elles", "en", "encore", "entre", "es", "est", "et", "eu", "eux",
+    "faire", "fait", "il", "ils", "je", "la", "le", "les", "leur", "leurs", "lors", "lui", "ma",
+    "mais", "me", "mes", "moi", "mon", "même", "ne", "ni", "nos", "notre", "nous", "on", "ont",
+    "ou", "où", "par", "parce", "pas", "peu", "peut", "plus", "pour", "pourquoi", "quand", "que",
+    "quel", "quelle", "quelles", "quels", "qui", "quoi", "sa", "sans", "se", "sera", "ses", "si",
+    "sinon", "soit", "son", "sont", "sous", "suis", "sur", "ta", "te", "tes", "toi", "ton", "tous",
+    "tout", "toute", "toutes", "très", "tu", "un", "une", "vers", "vos", "votre", "vous", "ça",
+    "étaient", "était", "étant", "été", "êtes", "être",
+];
+
+/// Language of notebook text.
+///
+/// Selects the stop words removed during tokenization and the phrasing of
+/// generated catalog text. Serialized as its language code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Locale {
+    /// English (`en`).
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    /// French (`fr`).
+    #[serde(rename = "fr")]
+    French,
+}
+
+impl Locale {
+    /// Looks up a locale by language code.
+    ///
+    /// Accepts a bare code (`fr`) or one with a region (`fr-CA`, `fr_FR`),
+    /// case-insensitively.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let language = code.trim().split(['-', '_']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "en" => Some(Self::English),
+            "fr" => Some(Self::French),
+            _ => None,
+        }
+    }
+
+    /// The language code.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::French => "fr",
+        }
+    }
+
+    /// Stop words removed from text in this language.
+    pub fn stop_words(self) -> &'static [&'static str] {
+        match self {
+            Self::English => STOP_WORDS,
+            Self::French => FRENCH_STOP_WORDS,
+        }
+    }
+
+    /// Strips an elided article or pronoun (`l'`, `d'`, `qu'`) from a word.
+    fn strip_elision(self, word: &str) -> &str {
+        match self {
+            Self::English => word,
+            Self::French => word.rsplit(['\'', '\u{2019}']).next().unwrap_or(word),
+        }
+    }
+}
+
+impl std::fmt::Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
 /// Minimum token length to consider (shorter tokens are filtered).
 const MIN_TOKEN_LENGTH: usize = 2;
 
@@ -163,10 +240,19 @@ const MIN_TOKEN_LENGTH: usize = 2;
 ///
 /// A vector of normalized token strings
 pub fn tokenize(text: &str) -> Vec<String> {
-    let stop_words: HashSet<&str> = STOP_WORDS.iter().copied().collect();
+    tokenize_in(text, Locale::English)
+}
+
+/// Tokenizes text in the given language.
+///
+/// Same as [`tokenize`], but removes the stop words of `locale`. French
+/// words also lose an elided article or pronoun, so `l'architecture`
+/// yields `architecture`.
+pub fn tokenize_in(text: &str, locale: Locale) -> Vec<String> {
+    let stop_words: HashSet<&str> = locale.stop_words().iter().copied().collect();
 
     text.unicode_words()
-        .map(normalize_token)
+        .map(|word| normalize_token(locale.strip_elision(word)))
         .filter(|token| token.len() >= MIN_TOKEN_LENGTH && !stop_words.contains(token.as_str()))
         .collect()
 }
@@ -263,13 +349,14 @@ pub struct TfIdfVector {
 impl TfIdfVector {
     /// Creates a TF-IDF vector from document tokens and corpus statistics.
     pub fn from_tokens(tokens: &[String], corpus: &CorpusStats) -> Self {
-        let tf = term_frequency(tokens);
+        Self::from_term_frequencies(&term_frequency(tokens), corpus)
+    }
+
+    /// Creates a TF-IDF vector from precomputed term frequencies.
+    pub fn from_term_frequencies(tf: &HashMap<String, f64>, corpus: &CorpusStats) -> Self {
         let weights = tf
-            .into_iter()
-            .map(|(term, freq)| {
-                let idf = corpus.idf(&term);
-                (term, freq * idf)
-            })
+            .iter()
+            .map(|(term, freq)| (term.clone(), freq * corpus.idf(term)))
             .filter(|(_, weight)| *weight > 0.0)
             .collect();
 
@@ -308,14 +395,27 @@ impl TfIdfVector {
         self.dot(other) / (mag_self * mag_other)
     }
 
-    /// Returns the top N terms by TF-IDF weight.
+    /// Returns the top N terms by TF-IDF weight, ties broken alphabetically.
     pub fn top_terms(&self, n: usize) -> Vec<String> {
+        self.top_weighted(n)
+            .into_iter()
+            .map(|(term, _)| term)
+            .collect()
+    }
+
+    /// Returns the top N terms with their weights, ordered like
+    /// [`top_terms`](Self::top_terms).
+    pub fn top_weighted(&self, n: usize) -> Vec<(String, f64)> {
         let mut terms: Vec<_> = self.weights.iter().collect();
-        terms.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap_or(std::cmp::Ordering::Equal));
+        terms.sort_by(|a, b| {
+            b.1.partial_cmp(a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(b.0))
+        });
         terms
             .into_iter()
             .take(n)
-            .map(|(term, _)| term.clone())
+            .map(|(term, weight)| (term.clone(), *weight))
             .collect()
     }
 
@@ -325,6 +425,28 @@ impl TfIdfVector {
     }
 }
 
+/// The top `n` keywords of a document with their weights, strongest first.
+///
+/// The document is weighted against `corpus` as if it had been added to it,
+/// with the IDF smoothed to `1 + ln((N + 1) / (df + 1))`. Against an empty
+/// corpus every IDF is 1, so the most frequent terms win; terms the corpus
+/// uses often count for less than the ones that set the document apart.
+pub fn weighted_keywords(tokens: &[String], corpus: &CorpusStats, n: usize) -> Vec<(String, f64)> {
+    let documents = corpus.document_count as f64 + 1.0;
+    let weights = term_frequency(tokens)
+        .into_iter()
+        .map(|(term, frequency)| {
+            let df = corpus
+                .document_frequencies
+                .get(&term)
+                .map_or(1.0, |&df| df as f64 + 1.0);
+            let weight = frequency * (1.0 + (documents / df).ln());
+            (term, weight)
+        })
+        .collect();
+    TfIdfVector { weights }.top_weighted(n)
+}
+
 /// Merges multiple TF-IDF vectors by summing their weights.
 ///
 /// Useful for computing cluster-level keyword importance.
@@ -340,6 +462,21 @@ pub fn merge_vectors(vectors: &[&TfIdfVector]) -> TfIdfVector {
     TfIdfVector { weights: merged }
 }
 
+/// Merges TF-IDF vectors by summing their weights, each scaled by a factor.
+///
+/// Lets more recent cluster members count for more than older ones.
+pub fn merge_weighted_vectors(vectors: &[(&TfIdfVector, f64)]) -> TfIdfVector {
+    let mut merged = HashMap::new();
+
+    for (vector, factor) in vectors {
+        for (term, weight) in &vector.weights {
+            *merged.entry(term.clone()).or_insert(0.0) += weight * factor;
+        }
+    }
+
+    TfIdfVector { weights: merged }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -382,6 +519,50 @@ mod tests {
         assert!(!tokens.contains(&"i".to_string()));
     }
 
+    #[test]
+    fn tokenize_french_removes_french_stop_words() {
+        let text =
+            "Les murs du château sont construits avec des pierres et l'architecture est médiévale.";
+        let tokens = tokenize_in(text, Locale::French);
+        for word in [
+            "murs",
+            "château",
+            "construits",
+            "pierres",
+            "architecture",
+            "médiévale",
+        ] {
+            assert!(tokens.contains(&word.to_string()), "missing {}", word);
+        }
+        for word in [
+            "les",
+            "du",
+            "sont",
+            "avec",
+            "des",
+            "et",
+            "est",
+            "larchitecture",
+        ] {
+            assert!(!tokens.contains(&word.to_string()), "kept {}", word);
+        }
+
+        // English stop words leave the French ones in
+        let tokens = tokenize(text);
+        assert!(tokens.contains(&"les".to_string()));
+        assert!(tokens.contains(&"des".to_string()));
+    }
+
+    #[test]
+    fn locale_from_code() {
+        assert_eq!(Locale::from_code("fr"), Some(Locale::French));
+        assert_eq!(Locale::from_code("FR-ca"), Some(Locale::French));
+        assert_eq!(Locale::from_code("en_US"), Some(Locale::English));
+        assert_eq!(Locale::from_code("de"), None);
+        assert_eq!(Locale::default(), Locale::English);
+        assert_eq!(serde_json::to_string(&Locale::French).unwrap(), "\"fr\"");
+    }
+
     #[test]
     fn term_frequency_basic() {
         let tokens = vec!["cat".into(), "dog".into(), "cat".into(), "bird".into()];
@@ -486,6 +667,17 @@ mod tests {
         assert_eq!(sim, 0.0);
     }
 
+    #[test]
+    fn top_terms_breaks_ties_alphabetically() {
+        let weights: HashMap<String, f64> = ["delta", "alpha", "charlie", "bravo"]
+            .into_iter()
+            .map(|term| (term.to_string(), 0.5))
+            .collect();
+
+        let vector = TfIdfVector { weights };
+        assert_eq!(vector.top_terms(3), vec!["alpha", "bravo", "charlie"]);
+    }
+
     #[test]
     fn top_terms() {
         let mut weights = HashMap::new();
@@ -520,6 +712,23 @@ mod tests {
         assert!((merged.weights["bird"] - 0.4).abs() < 0.001);
     }
 
+    #[test]
+    fn merge_weighted_vectors_scales_each_vector() {
+        let mut w1 = HashMap::new();
+        w1.insert("cat".into(), 0.5);
+        let v1 = TfIdfVector { weights: w1 };
+
+        let mut w2 = HashMap::new();
+        w2.insert("cat".into(), 0.2);
+        w2.insert("bird".into(), 0.4);
+        let v2 = TfIdfVector { weights: w2 };
+
+        let merged = merge_weighted_vectors(&[(&v1, 0.5), (&v2, 1.0)]);
+
+        assert!((merged.weights["cat"] - 0.45).abs() < 0.001);
+        assert!((merged.weights["bird"] - 0.4).abs() < 0.001);
+    }
+
     #[test]
     fn corpus_stats_serialization() {
         let mut corpus = CorpusStats::new();
@@ -543,4 +752,35 @@ mod tests {
 
         assert_eq!(parsed.weights, vector.weights);
     }
+
+    #[test]
+    fn weighted_keywords_are_ranked_by_weight() {
+        let tokens = tokenize("sourdough starter sourdough bread sourdough starter");
+        let keywords = weighted_keywords(&tokens, &CorpusStats::new(), 5);
+
+        let terms: Vec<&str> = keywords.iter().map(|(t, _)| t.as_str()).collect();
+        assert_eq!(terms, vec!["sourdough", "starter", "bread"]);
+        assert!(keywords.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        assert_eq!(weighted_keywords(&tokens, &CorpusStats::new(), 1).len(), 1);
+    }
+
+    #[test]
+    fn weighted_keywords_use_corpus_context() {
+        let tokens = tokenize("starter starter sourdough");
+        let standalone = weighted_keywords(&tokens, &CorpusStats::new(), 2);
+        assert_eq!(standalone[0].0, "starter");
+
+        // A corpus that mentions starters everywhere demotes the term
+        let mut corpus = CorpusStats::new();
+        for text in [
+            "starter motors in old cars",
+            "a starter pistol opens the race",
+            "starter cultures for yogurt",
+            "starter homes in the suburbs",
+        ] {
+            corpus.add_document(&tokenize(text));
+        }
+        let in_context = weighted_keywords(&tokens, &corpus, 2);
+        assert_eq!(in_context[0].0, "sourdough");
+    }
 }
@@ -471,11 +471,7 @@ fn integration_cost_components_non_negative() {
             "Catalog shift should be non-negative. Got: {}",
             cost.catalog_shift
         );
-        assert!(
-            cost.references_broken == 0 || cost.references_broken > 0,
-            "References broken should be non-negative"
-        );
-        // entries_revised is u32, always >= 0
+        // references_broken and entries_revised are u32, always >= 0
     }
 }
 
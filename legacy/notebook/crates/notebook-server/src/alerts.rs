@@ -0,0 +1,414 @@
+//! Entropy alert webhooks.
+//!
+//! A notebook can have one alert: an entropy threshold and a webhook URL.
+//! After each write the server compares the notebook's recent entropy (the
+//! summed catalog shift of its last ten entries) with the threshold. When a
+//! write pushes it above, the alert is claimed in the store and a JSON
+//! [`AlertPayload`] is POSTed to the webhook, retrying failed deliveries with
+//! exponential backoff. The claim makes one crossing fire one alert, even
+//! with concurrent writers; the alert re-arms once entropy falls back to the
+//! threshold.
+//!
+//! Checks run as background tasks, off the write path.
+
+use std::future::Future;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use notebook_store::{NotebookAlertRow, StoreResult};
+
+use crate::state::AppState;
+
+/// Event name carried by entropy alerts.
+pub const ENTROPY_ALERT_EVENT: &str = "entropy_threshold_exceeded";
+
+/// Time allowed for one webhook delivery attempt.
+pub const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// JSON body POSTed to an alert webhook.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertPayload {
+    /// Always [`ENTROPY_ALERT_EVENT`].
+    pub event: String,
+    /// The notebook whose entropy crossed the threshold.
+    pub notebook_id: Uuid,
+    /// The write that pushed entropy over.
+    pub entry_id: Uuid,
+    /// Recent entropy after the write.
+    pub entropy: f64,
+    /// The configured threshold.
+    pub threshold: f64,
+    /// When the alert fired.
+    pub fired_at: DateTime<Utc>,
+}
+
+/// Delivers alert payloads to webhooks.
+pub trait WebhookSender: Send + Sync {
+    /// POST `payload` to `url`, failing on transport errors and non-2xx replies.
+    fn send<'a>(
+        &'a self,
+        url: &'a str,
+        payload: &'a AlertPayload,
+    ) -> BoxFuture<'a, Result<(), String>>;
+}
+
+/// [`WebhookSender`] that POSTs JSON over HTTP.
+#[derive(Debug, Clone)]
+pub struct HttpWebhookSender {
+    client: reqwest::Client,
+}
+
+impl HttpWebhookSender {
+    /// Create a sender with [`WEBHOOK_TIMEOUT`] per attempt.
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self { client }
+    }
+}
+
+impl Default for HttpWebhookSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebhookSender for HttpWebhookSender {
+    fn send<'a>(
+        &'a self,
+        url: &'a str,
+        payload: &'a AlertPayload,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .post(url)
+                .json(payload)
+                .send()
+                .await
+                .map_err(|e| e.to_string())?;
+            if response.status().is_success() {
+                Ok(())
+            } else {
+                Err(format!("webhook responded with {}", response.status()))
+            }
+        })
+    }
+}
+
+/// How failed deliveries are retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total delivery attempts, including the first.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for each further retry.
+    pub initial_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Deliver a payload, retrying failures. Returns whether it was delivered.
+pub async fn deliver_with_retry(
+    sender: &dyn WebhookSender,
+    url: &str,
+    payload: &AlertPayload,
+    policy: RetryPolicy,
+) -> bool {
+    let mut backoff = policy.initial_backoff;
+    for attempt in 1..=policy.max_attempts.max(1) {
+        match sender.send(url, payload).await {
+            Ok(()) => return true,
+            Err(e) => {
+                tracing::warn!(
+                    notebook_id = %payload.notebook_id,
+                    attempt,
+                    error = %e,
+                    "Entropy alert delivery failed"
+                );
+                if attempt < policy.max_attempts {
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+            }
+        }
+    }
+    false
+}
+
+/// What a write did to a notebook's alert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertOutcome {
+    /// Entropy crossed the threshold; the alert fired.
+    Fired {
+        /// Whether the webhook accepted it.
+        delivered: bool,
+    },
+    /// Entropy crossed, but a concurrent write claimed the alert first.
+    AlreadyClaimed,
+    /// Entropy fell back to the threshold; the alert re-armed.
+    Rearmed,
+    /// Nothing changed.
+    Unchanged,
+}
+
+/// Compare entropy with an alert and fire it if this write crossed over.
+///
+/// `set_triggered` flips the stored flag and reports whether this call
+/// changed it (see `Store::set_alert_triggered`).
+pub async fn evaluate_alert<F, Fut>(
+    alert: &NotebookAlertRow,
+    entropy: f64,
+    entry_id: Uuid,
+    sender: &dyn WebhookSender,
+    policy: RetryPolicy,
+    set_triggered: F,
+) -> StoreResult<AlertOutcome>
+where
+    F: FnOnce(bool) -> Fut,
+    Fut: Future<Output = StoreResult<bool>>,
+{
+    let above = entropy > alert.entropy_threshold;
+    if above == alert.triggered {
+        return Ok(AlertOutcome::Unchanged);
+    }
+
+    let changed = set_triggered(above).await?;
+    if !above {
+        return Ok(if changed {
+            AlertOutcome::Rearmed
+        } else {
+            AlertOutcome::Unchanged
+        });
+    }
+    if !changed {
+        return Ok(AlertOutcome::AlreadyClaimed);
+    }
+
+    let payload = AlertPayload {
+        event: ENTROPY_ALERT_EVENT.to_string(),
+        notebook_id: alert.notebook_id,
+        entry_id,
+        entropy,
+        threshold: alert.entropy_threshold,
+        fired_at: Utc::now(),
+    };
+    let delivered = deliver_with_retry(sender, &alert.webhook_url, &payload, policy).await;
+    Ok(AlertOutcome::Fired { delivered })
+}
+
+/// Check a notebook's alert after a write, in the background.
+pub fn spawn_entropy_alert_check(state: AppState, notebook_id: Uuid, entry_id: Uuid) {
+    let tasks = state.background_tasks().clone();
+    tasks.spawn(async move {
+        if let Err(e) = check_entropy_alert(&state, notebook_id, entry_id).await {
+            tracing::warn!(notebook_id = %notebook_id, error = %e, "Entropy alert check failed");
+        }
+    });
+}
+
+/// Compare a notebook's recent entropy with its alert, firing on a crossing.
+pub async fn check_entropy_alert(
+    state: &AppState,
+    notebook_id: Uuid,
+    entry_id: Uuid,
+) -> StoreResult<()> {
+    let store = state.store();
+    let Some(alert) = store.get_notebook_alert(notebook_id).await? else {
+        return Ok(());
+    };
+    let entropy = store.get_recent_entropy(notebook_id).await?;
+
+    let outcome = evaluate_alert(
+        &alert,
+        entropy,
+        entry_id,
+        state.webhook_sender().as_ref(),
+        RetryPolicy::default(),
+        |triggered| store.set_alert_triggered(notebook_id, triggered),
+    )
+    .await?;
+
+    if outcome != AlertOutcome::Unchanged {
+        tracing::info!(
+            notebook_id = %notebook_id,
+            entropy,
+            threshold = alert.entropy_threshold,
+            outcome = ?outcome,
+            "Entropy alert updated"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Records payloads; fails the first `failures` attempts.
+    #[derive(Default)]
+    struct MockSender {
+        failures: u32,
+        attempts: AtomicU32,
+        delivered: Mutex<Vec<AlertPayload>>,
+    }
+
+    impl WebhookSender for MockSender {
+        fn send<'a>(
+            &'a self,
+            _url: &'a str,
+            payload: &'a AlertPayload,
+        ) -> BoxFuture<'a, Result<(), String>> {
+            Box::pin(async move {
+                let attempt = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+                if attempt <= self.failures {
+                    return Err("unavailable".to_string());
+                }
+                self.delivered.lock().unwrap().push(payload.clone());
+                Ok(())
+            })
+        }
+    }
+
+    const NO_BACKOFF: RetryPolicy = RetryPolicy {
+        max_attempts: 3,
+        initial_backoff: Duration::ZERO,
+    };
+
+    fn alert(threshold: f64) -> NotebookAlertRow {
+        NotebookAlertRow {
+            notebook_id: Uuid::new_v4(),
+            entropy_threshold: threshold,
+            webhook_url: "https://example.com/hook".to_string(),
+            triggered: false,
+            created: Utc::now(),
+            last_fired: None,
+        }
+    }
+
+    /// Run writes with the given entropies against an in-memory alert row,
+    /// flipping `triggered` the way the store does.
+    async fn run_writes(
+        sender: &MockSender,
+        threshold: f64,
+        entropies: &[f64],
+    ) -> Vec<AlertOutcome> {
+        let row = Mutex::new(alert(threshold));
+        let mut outcomes = Vec::new();
+        for &entropy in entropies {
+            let snapshot = row.lock().unwrap().clone();
+            let outcome = evaluate_alert(
+                &snapshot,
+                entropy,
+                Uuid::new_v4(),
+                sender,
+                NO_BACKOFF,
+                |triggered| {
+                    let row = &row;
+                    async move {
+                        let mut row = row.lock().unwrap();
+                        let changed = row.triggered != triggered;
+                        row.triggered = triggered;
+                        Ok(changed)
+                    }
+                },
+            )
+            .await
+            .unwrap();
+            outcomes.push(outcome);
+        }
+        outcomes
+    }
+
+    #[tokio::test]
+    async fn test_crossing_threshold_fires_exactly_once() {
+        let sender = MockSender::default();
+        let outcomes = run_writes(&sender, 0.5, &[0.2, 0.4, 0.7, 0.9, 0.8]).await;
+
+        let delivered = sender.delivered.lock().unwrap();
+        assert_eq!(delivered.len(), 1);
+        assert_eq!(delivered[0].entropy, 0.7);
+        assert_eq!(delivered[0].threshold, 0.5);
+        assert_eq!(delivered[0].event, ENTROPY_ALERT_EVENT);
+        assert_eq!(outcomes[2], AlertOutcome::Fired { delivered: true });
+        assert_eq!(outcomes[3], AlertOutcome::Unchanged);
+    }
+
+    #[tokio::test]
+    async fn test_staying_below_threshold_never_fires() {
+        let sender = MockSender::default();
+        let outcomes = run_writes(&sender, 0.5, &[0.1, 0.3, 0.5, 0.2]).await;
+
+        assert_eq!(sender.attempts.load(Ordering::SeqCst), 0);
+        assert!(outcomes.iter().all(|o| *o == AlertOutcome::Unchanged));
+    }
+
+    #[tokio::test]
+    async fn test_alert_rearms_after_dropping_below() {
+        let sender = MockSender::default();
+        let outcomes = run_writes(&sender, 0.5, &[0.7, 0.3, 0.6]).await;
+
+        assert_eq!(outcomes[1], AlertOutcome::Rearmed);
+        assert_eq!(sender.delivered.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_lost_claim_does_not_fire() {
+        let sender = MockSender::default();
+        let outcome = evaluate_alert(
+            &alert(0.5),
+            0.9,
+            Uuid::new_v4(),
+            &sender,
+            NO_BACKOFF,
+            |_| async { Ok(false) },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(outcome, AlertOutcome::AlreadyClaimed);
+        assert_eq!(sender.attempts.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_delivery_is_retried() {
+        let sender = MockSender {
+            failures: 2,
+            ..MockSender::default()
+        };
+        let outcomes = run_writes(&sender, 0.5, &[0.9]).await;
+
+        assert_eq!(outcomes[0], AlertOutcome::Fired { delivered: true });
+        assert_eq!(sender.attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(sender.delivered.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_delivery_gives_up_after_max_attempts() {
+        let sender = MockSender {
+            failures: u32::MAX,
+            ..MockSender::default()
+        };
+        let outcomes = run_writes(&sender, 0.5, &[0.9]).await;
+
+        assert_eq!(outcomes[0], AlertOutcome::Fired { delivered: false });
+        assert_eq!(
+            sender.attempts.load(Ordering::SeqCst),
+            NO_BACKOFF.max_attempts
+        );
+    }
+}
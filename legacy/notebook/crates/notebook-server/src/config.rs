@@ -1,6 +1,74 @@
 //! Server configuration from environment variables.
 
 use std::env;
+use std::time::Duration;
+
+use http::{HeaderName, Method, Uri};
+use notebook_core::IdStrategy;
+use notebook_entropy::clustering::DEFAULT_SIMILARITY_THRESHOLD;
+use notebook_entropy::{ClusteringConfig, CommitPolicy, CostConfig, Locale, SummaryMode};
+use regex::Regex;
+use tracing_subscriber::EnvFilter;
+
+use crate::content_policy::{ContentTypePolicy, media_type_essence};
+use crate::content_validation::BUILTIN_VALIDATORS;
+use crate::events::DEFAULT_CHANNEL_CAPACITY;
+use crate::throttle::WriteBudget;
+
+/// Default deadline for integration cost computation, in milliseconds.
+pub const DEFAULT_COST_TIMEOUT_MS: u64 = 500;
+
+/// Default time allowed for draining connections and tasks on shutdown, in seconds.
+pub const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 30;
+
+/// Default multiplier applied to catalog shift.
+pub const DEFAULT_CATALOG_SHIFT_WEIGHT: f64 = 1.0;
+
+/// Default maximum request body size, in bytes (2 MiB).
+pub const DEFAULT_MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Default maximum number of references a single entry may carry.
+pub const DEFAULT_MAX_REFERENCES_PER_ENTRY: usize = 100;
+
+/// Default content type of entries written without one.
+pub const DEFAULT_CONTENT_TYPE: &str = "text/plain";
+
+/// Default maximum size of an image entry, in bytes (1 MiB).
+pub const DEFAULT_MAX_IMAGE_BYTES: usize = 1024 * 1024;
+
+/// Default maximum total size of request headers, in bytes (32 KiB).
+pub const DEFAULT_MAX_HEADER_BYTES: usize = 32 * 1024;
+
+/// Default time allowed for receiving a request and producing a response, in seconds.
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Default pattern an inbound `X-Request-Id` must match to be reused.
+pub const DEFAULT_REQUEST_ID_PATTERN: &str = r"^[A-Za-z0-9][A-Za-z0-9._:-]{0,127}$";
+
+/// Default number of buffered search index changes that triggers a commit.
+pub const DEFAULT_SEARCH_COMMIT_DOCS: usize = 100;
+
+/// Default age of buffered search index changes that triggers a commit, in milliseconds.
+pub const DEFAULT_SEARCH_COMMIT_INTERVAL_MS: u64 = 1000;
+
+/// Default number of recently active notebooks whose catalogs are warmed on startup.
+pub const DEFAULT_CATALOG_WARMUP_NOTEBOOKS: usize = 20;
+
+/// Default number of catalogs generated concurrently during warmup.
+pub const DEFAULT_CATALOG_WARMUP_CONCURRENCY: usize = 4;
+
+/// Default window over which an author's entropy contribution is summed, in seconds.
+pub const DEFAULT_WRITE_BUDGET_WINDOW_SECS: u64 = 300;
+
+/// Default minimum interval between writes of an author at their budget, in seconds.
+pub const DEFAULT_WRITE_THROTTLE_INTERVAL_SECS: u64 = 10;
+
+/// Default number of anonymous reads of public notebooks allowed per client
+/// address per minute.
+pub const DEFAULT_ANONYMOUS_READS_PER_MINUTE: u32 = 60;
+
+/// Default share of traces recorded.
+pub const DEFAULT_TRACE_SAMPLE_RATIO: f64 = 1.0;
 
 /// Server configuration.
 #[derive(Debug, Clone)]
@@ -11,8 +79,27 @@ pub struct ServerConfig {
     pub port: u16,
     /// Log level (trace, debug, info, warn, error).
     pub log_level: String,
+    /// Log output format.
+    pub log_format: LogFormat,
+    /// Per-target log directives applied on top of `log_level`, e.g.
+    /// `sqlx=warn,notebook_entropy=debug`. Uses the `RUST_LOG` syntax.
+    pub log_directives: String,
+    /// Share of traces (root spans) exported over OTLP, 0.0-1.0. A request
+    /// whose `traceparent` carries the caller's decision follows it instead.
+    /// Logs are written for every request either way.
+    pub trace_sample_ratio: f64,
+    /// OTLP/HTTP collector to export spans to, e.g. `http://localhost:4318`.
+    /// Export is off when unset.
+    pub otlp_endpoint: Option<String>,
     /// CORS allowed origins (comma-separated or "*" for all).
+    ///
+    /// Each origin may restrict methods and headers, e.g.
+    /// `https://app.example.com;methods=GET|POST;headers=authorization`.
+    /// See [`parse_cors_origins`].
     pub cors_allowed_origins: String,
+    /// Send `Access-Control-Allow-Credentials: true` to allowed origins.
+    /// Requires explicit origins; cannot be combined with "*".
+    pub cors_allow_credentials: bool,
     /// Ed25519 public key in PEM format for JWT validation.
     /// If empty, JWT validation is disabled (dev mode only).
     pub jwt_public_key: String,
@@ -23,6 +110,152 @@ pub struct ServerConfig {
     /// When true, endpoints require matching scope (e.g. `notebook:read`).
     /// When false, any valid JWT grants full access (backward-compatible).
     pub enforce_scopes: bool,
+    /// Deadline for integration cost computation on writes, in milliseconds.
+    /// On timeout the entry is stored with a zero cost that is backfilled later.
+    pub cost_timeout_ms: u64,
+    /// Time allowed on shutdown for in-flight requests and background tasks
+    /// to finish, in seconds. Work still running afterwards is dropped.
+    pub shutdown_timeout_secs: u64,
+    /// Minimum cosine similarity for an entry to join an existing cluster.
+    /// Higher values make orphans more likely.
+    pub similarity_threshold: f64,
+    /// Catalog shift above which an entry is flagged as an orphan.
+    /// `None` leaves orphan detection to clustering alone.
+    pub orphan_threshold: Option<f64>,
+    /// Half-life, in entries, of a member's weight in its cluster's vector.
+    /// `None` weighs old and recent members equally.
+    pub cluster_decay_half_life: Option<f64>,
+    /// Clusters smaller than this are folded into a related cluster, or into
+    /// an uncategorized one, when clusters are rebuilt. 0 or 1 keeps every
+    /// cluster.
+    pub min_cluster_size: usize,
+    /// Multiplier applied to the computed catalog shift.
+    pub catalog_shift_weight: f64,
+    /// Largest accepted request body, in bytes. Larger bodies get 413.
+    pub max_body_bytes: usize,
+    /// Largest accepted total size of request headers, in bytes. Larger
+    /// header sets get 431.
+    pub max_header_bytes: usize,
+    /// Most references a single entry may carry. Entries with more get 400.
+    pub max_references_per_entry: usize,
+    /// Content-type globs new entries may use (e.g. `text/*`). Empty allows
+    /// any type not denied. Entries outside the policy get 415.
+    pub allowed_content_types: Vec<String>,
+    /// Content-type globs new entries may not use. Deny wins over allow.
+    pub denied_content_types: Vec<String>,
+    /// Content type of entries written without one.
+    pub default_content_type: String,
+    /// Derive a topic from the content's keywords for entries written
+    /// without one.
+    pub infer_topics: bool,
+    /// Built-in content validators to run on new entries, by name; see
+    /// [`crate::content_validation`]. Content that fails gets 400. None run
+    /// unless configured.
+    pub content_validators: Vec<String>,
+    /// Largest accepted image entry, in bytes, when the `image` validator
+    /// runs. Larger images get 400.
+    pub max_image_bytes: usize,
+    /// Time allowed for reading a request and producing the response head,
+    /// in seconds. Slower requests get 408. Streams (SSE, WebSocket) are
+    /// not cut off once their response has started.
+    pub request_timeout_secs: u64,
+    /// Regular expression an inbound `X-Request-Id` must match to be reused.
+    /// Other inbound IDs are replaced with a generated one.
+    pub request_id_pattern: String,
+    /// Pre-generate browse catalogs on startup, in the background.
+    pub catalog_warmup: bool,
+    /// How many of the most recently active notebooks to warm.
+    pub catalog_warmup_notebooks: usize,
+    /// How many catalogs to generate at once while warming.
+    pub catalog_warmup_concurrency: usize,
+    /// Directory of the on-disk full-text search index. `None` disables
+    /// the index.
+    pub search_index_path: Option<String>,
+    /// Commit the search index once this many changes are buffered.
+    pub search_commit_docs: usize,
+    /// Commit the search index once buffered changes are this old, in
+    /// milliseconds.
+    pub search_commit_interval_ms: u64,
+    /// Entropy an author may contribute to a notebook per budget window
+    /// before their writes are spaced out. `None` leaves notebooks without
+    /// their own budget unthrottled.
+    pub write_entropy_budget: Option<f64>,
+    /// Window over which an author's entropy contribution is summed, in
+    /// seconds.
+    pub write_budget_window_secs: u64,
+    /// Minimum interval between writes of an author who has spent exactly
+    /// their budget, in seconds. Grows with the overspend.
+    pub write_throttle_interval_secs: u64,
+    /// Events buffered per SSE or WebSocket subscriber. A subscriber that
+    /// falls further behind is told it lagged and is resynced.
+    pub event_channel_capacity: usize,
+    /// How new entry IDs are generated. Time-ordered IDs keep inserts
+    /// local in the primary key index.
+    pub id_strategy: IdStrategy,
+    /// Anonymous reads of public notebooks allowed per client address per
+    /// minute. Authenticated reads are not limited.
+    pub anonymous_reads_per_minute: u32,
+    /// Language of notebook text, selecting the stop words used for topic
+    /// keywords and the phrasing of catalog text. Browse requests can ask
+    /// for another.
+    pub catalog_locale: Locale,
+    /// How browse catalogs summarize each cluster.
+    pub catalog_summary_mode: SummaryMode,
+    /// Answer revisions identical to the entry's latest revision with that
+    /// revision instead of writing a new one.
+    pub skip_noop_revisions: bool,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            database_url: String::new(),
+            port: 3000,
+            log_level: "info".to_string(),
+            log_format: LogFormat::Pretty,
+            log_directives: String::new(),
+            trace_sample_ratio: DEFAULT_TRACE_SAMPLE_RATIO,
+            otlp_endpoint: None,
+            cors_allowed_origins: "*".to_string(),
+            cors_allow_credentials: false,
+            jwt_public_key: String::new(),
+            allow_dev_identity: false,
+            enforce_scopes: true,
+            cost_timeout_ms: DEFAULT_COST_TIMEOUT_MS,
+            shutdown_timeout_secs: DEFAULT_SHUTDOWN_TIMEOUT_SECS,
+            similarity_threshold: DEFAULT_SIMILARITY_THRESHOLD,
+            orphan_threshold: None,
+            cluster_decay_half_life: None,
+            min_cluster_size: 0,
+            catalog_shift_weight: DEFAULT_CATALOG_SHIFT_WEIGHT,
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            max_header_bytes: DEFAULT_MAX_HEADER_BYTES,
+            max_references_per_entry: DEFAULT_MAX_REFERENCES_PER_ENTRY,
+            allowed_content_types: Vec::new(),
+            denied_content_types: Vec::new(),
+            default_content_type: DEFAULT_CONTENT_TYPE.to_string(),
+            infer_topics: false,
+            content_validators: Vec::new(),
+            max_image_bytes: DEFAULT_MAX_IMAGE_BYTES,
+            request_timeout_secs: DEFAULT_REQUEST_TIMEOUT_SECS,
+            request_id_pattern: DEFAULT_REQUEST_ID_PATTERN.to_string(),
+            catalog_warmup: false,
+            catalog_warmup_notebooks: DEFAULT_CATALOG_WARMUP_NOTEBOOKS,
+            catalog_warmup_concurrency: DEFAULT_CATALOG_WARMUP_CONCURRENCY,
+            search_index_path: None,
+            search_commit_docs: DEFAULT_SEARCH_COMMIT_DOCS,
+            search_commit_interval_ms: DEFAULT_SEARCH_COMMIT_INTERVAL_MS,
+            write_entropy_budget: None,
+            write_budget_window_secs: DEFAULT_WRITE_BUDGET_WINDOW_SECS,
+            write_throttle_interval_secs: DEFAULT_WRITE_THROTTLE_INTERVAL_SECS,
+            event_channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+            id_strategy: IdStrategy::UuidV4,
+            anonymous_reads_per_minute: DEFAULT_ANONYMOUS_READS_PER_MINUTE,
+            catalog_locale: Locale::default(),
+            catalog_summary_mode: SummaryMode::default(),
+            skip_noop_revisions: true,
+        }
+    }
 }
 
 impl ServerConfig {
@@ -34,7 +267,47 @@ impl ServerConfig {
     /// Optional:
     /// - `PORT`: Server port (default: 3000)
     /// - `LOG_LEVEL`: Logging level (default: "info")
+    /// - `LOG_FORMAT`: Log output format, "pretty" or "json" (default: "pretty")
+    /// - `LOG_DIRECTIVES`: Per-target log levels, e.g. "sqlx=warn,notebook_entropy=debug" (default: none)
+    /// - `TRACE_SAMPLE_RATIO`: Share of traces exported, 0.0-1.0 (default: 1.0)
+    /// - `OTLP_ENDPOINT`: OTLP/HTTP collector for span export (default: unset, no export)
     /// - `CORS_ALLOWED_ORIGINS`: Allowed CORS origins (default: "*")
+    /// - `CORS_ALLOW_CREDENTIALS`: Allow credentialed CORS requests (default: false)
+    /// - `COST_TIMEOUT_MS`: Integration cost deadline (default: 500)
+    /// - `SHUTDOWN_TIMEOUT_SECS`: Graceful shutdown deadline (default: 30)
+    /// - `SIMILARITY_THRESHOLD`: Clustering similarity threshold, 0.0-1.0 (default: 0.3)
+    /// - `ORPHAN_THRESHOLD`: Catalog shift that flags an orphan, 0.0-1.0 (default: unset)
+    /// - `CLUSTER_DECAY_HALF_LIFE`: Cluster recency half-life in entries (default: unset)
+    /// - `MIN_CLUSTER_SIZE`: Smallest cluster kept on its own when rebuilding (default: 0, keep all)
+    /// - `CATALOG_SHIFT_WEIGHT`: Catalog shift multiplier, at least 0.0 (default: 1.0)
+    /// - `MAX_BODY_BYTES`: Request body limit (default: 2097152)
+    /// - `MAX_HEADER_BYTES`: Request header limit (default: 32768)
+    /// - `MAX_REFERENCES_PER_ENTRY`: References allowed per entry (default: 100)
+    /// - `ALLOWED_CONTENT_TYPES`: Comma-separated content-type globs (default: any)
+    /// - `DENIED_CONTENT_TYPES`: Comma-separated content-type globs (default: none)
+    /// - `DEFAULT_CONTENT_TYPE`: Content type of entries written without one (default: "text/plain")
+    /// - `INFER_TOPICS`: Infer a topic for entries written without one (default: false)
+    /// - `CONTENT_VALIDATORS`: Comma-separated content validators, "json" and "image" (default: none)
+    /// - `MAX_IMAGE_BYTES`: Image entry limit (default: 1048576)
+    /// - `REQUEST_TIMEOUT_SECS`: Per-request deadline (default: 30)
+    /// - `REQUEST_ID_PATTERN`: Regex for reusable inbound request IDs (default: 1-128 of `A-Za-z0-9._:-`)
+    /// - `CATALOG_WARMUP`: Warm browse catalogs on startup (default: false)
+    /// - `CATALOG_WARMUP_NOTEBOOKS`: Notebooks to warm (default: 20)
+    /// - `CATALOG_WARMUP_CONCURRENCY`: Catalogs generated at once (default: 4)
+    /// - `SEARCH_INDEX_PATH`: Search index directory (default: unset, no index)
+    /// - `SEARCH_COMMIT_DOCS`: Buffered index changes per commit (default: 100)
+    /// - `SEARCH_COMMIT_INTERVAL_MS`: Longest wait before a commit (default: 1000)
+    /// - `WRITE_ENTROPY_BUDGET`: Entropy per author per window before throttling (default: unset)
+    /// - `WRITE_BUDGET_WINDOW_SECS`: Entropy budget window (default: 300)
+    /// - `WRITE_THROTTLE_INTERVAL_SECS`: Write interval at the budget (default: 10)
+    /// - `EVENT_CHANNEL_CAPACITY`: Events buffered per subscriber (default: 256)
+    /// - `ENTRY_ID_STRATEGY`: Entry ID generation, "uuid_v4" or "uuid_v7" (default: "uuid_v4")
+    /// - `ANONYMOUS_READS_PER_MINUTE`: Anonymous public reads per client address (default: 60)
+    /// - `CATALOG_LOCALE`: Language of notebook text, "en" or "fr" (default: "en")
+    /// - `CATALOG_SUMMARY_MODE`: Cluster summaries, "extractive", "keyword" or "representative_sentence" (default: "extractive")
+    /// - `SKIP_NOOP_REVISIONS`: Don't write revisions identical to the latest one (default: true)
+    ///
+    /// The loaded configuration is validated; see [`ServerConfig::validate`].
     pub fn from_env() -> Result<Self, ConfigError> {
         let database_url = env::var("DATABASE_URL")
             .map_err(|_| ConfigError::MissingEnvVar("DATABASE_URL".to_string()))?;
@@ -46,9 +319,27 @@ impl ServerConfig {
 
         let log_level = env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string());
 
+        let log_format = match env::var("LOG_FORMAT") {
+            Ok(value) => value.parse()?,
+            Err(_) => LogFormat::Pretty,
+        };
+
+        let log_directives = env::var("LOG_DIRECTIVES").unwrap_or_default();
+
+        let trace_sample_ratio = env::var("TRACE_SAMPLE_RATIO")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_TRACE_SAMPLE_RATIO);
+
+        let otlp_endpoint = env::var("OTLP_ENDPOINT").ok().filter(|s| !s.is_empty());
+
         let cors_allowed_origins =
             env::var("CORS_ALLOWED_ORIGINS").unwrap_or_else(|_| "*".to_string());
 
+        let cors_allow_credentials = env::var("CORS_ALLOW_CREDENTIALS")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+
         let jwt_public_key = env::var("JWT_PUBLIC_KEY").unwrap_or_default();
 
         let allow_dev_identity = env::var("ALLOW_DEV_IDENTITY")
@@ -59,23 +350,617 @@ impl ServerConfig {
             .map(|v| v == "true" || v == "1")
             .unwrap_or(true);
 
-        Ok(Self {
+        let cost_timeout_ms = env::var("COST_TIMEOUT_MS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_COST_TIMEOUT_MS);
+
+        let shutdown_timeout_secs = env::var("SHUTDOWN_TIMEOUT_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT_SECS);
+
+        let similarity_threshold = env::var("SIMILARITY_THRESHOLD")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_SIMILARITY_THRESHOLD);
+
+        let orphan_threshold = env::var("ORPHAN_THRESHOLD")
+            .ok()
+            .and_then(|s| s.parse().ok());
+
+        let cluster_decay_half_life = env::var("CLUSTER_DECAY_HALF_LIFE")
+            .ok()
+            .and_then(|s| s.parse().ok());
+
+        let min_cluster_size = env::var("MIN_CLUSTER_SIZE")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+
+        let catalog_shift_weight = env::var("CATALOG_SHIFT_WEIGHT")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_CATALOG_SHIFT_WEIGHT);
+
+        let max_body_bytes = env::var("MAX_BODY_BYTES")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_MAX_BODY_BYTES);
+
+        let max_header_bytes = env::var("MAX_HEADER_BYTES")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_MAX_HEADER_BYTES);
+
+        let max_references_per_entry = env::var("MAX_REFERENCES_PER_ENTRY")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_MAX_REFERENCES_PER_ENTRY);
+
+        let allowed_content_types = env::var("ALLOWED_CONTENT_TYPES")
+            .map(|v| parse_list(&v))
+            .unwrap_or_default();
+
+        let denied_content_types = env::var("DENIED_CONTENT_TYPES")
+            .map(|v| parse_list(&v))
+            .unwrap_or_default();
+
+        let default_content_type = env::var("DEFAULT_CONTENT_TYPE")
+            .ok()
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| DEFAULT_CONTENT_TYPE.to_string());
+
+        let infer_topics = env::var("INFER_TOPICS")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+
+        let content_validators = env::var("CONTENT_VALIDATORS")
+            .map(|v| parse_list(&v))
+            .unwrap_or_default();
+
+        let max_image_bytes = env::var("MAX_IMAGE_BYTES")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_MAX_IMAGE_BYTES);
+
+        let request_timeout_secs = env::var("REQUEST_TIMEOUT_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECS);
+
+        let request_id_pattern = env::var("REQUEST_ID_PATTERN")
+            .ok()
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| DEFAULT_REQUEST_ID_PATTERN.to_string());
+
+        let catalog_warmup = env::var("CATALOG_WARMUP")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+
+        let catalog_warmup_notebooks = env::var("CATALOG_WARMUP_NOTEBOOKS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_CATALOG_WARMUP_NOTEBOOKS);
+
+        let catalog_warmup_concurrency = env::var("CATALOG_WARMUP_CONCURRENCY")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_CATALOG_WARMUP_CONCURRENCY);
+
+        let search_index_path = env::var("SEARCH_INDEX_PATH").ok().filter(|s| !s.is_empty());
+
+        let search_commit_docs = env::var("SEARCH_COMMIT_DOCS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_SEARCH_COMMIT_DOCS);
+
+        let search_commit_interval_ms = env::var("SEARCH_COMMIT_INTERVAL_MS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_SEARCH_COMMIT_INTERVAL_MS);
+
+        let write_entropy_budget = env::var("WRITE_ENTROPY_BUDGET")
+            .ok()
+            .and_then(|s| s.parse().ok());
+
+        let write_budget_window_secs = env::var("WRITE_BUDGET_WINDOW_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_WRITE_BUDGET_WINDOW_SECS);
+
+        let write_throttle_interval_secs = env::var("WRITE_THROTTLE_INTERVAL_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_WRITE_THROTTLE_INTERVAL_SECS);
+
+        let event_channel_capacity = env::var("EVENT_CHANNEL_CAPACITY")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_CHANNEL_CAPACITY);
+
+        let id_strategy = match env::var("ENTRY_ID_STRATEGY") {
+            Ok(value) => parse_id_strategy(&value)?,
+            Err(_) => IdStrategy::UuidV4,
+        };
+
+        let anonymous_reads_per_minute = env::var("ANONYMOUS_READS_PER_MINUTE")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_ANONYMOUS_READS_PER_MINUTE);
+
+        let catalog_locale = match env::var("CATALOG_LOCALE") {
+            Ok(value) => parse_catalog_locale(&value)?,
+            Err(_) => Locale::default(),
+        };
+
+        let catalog_summary_mode = match env::var("CATALOG_SUMMARY_MODE") {
+            Ok(value) => parse_catalog_summary_mode(&value)?,
+            Err(_) => SummaryMode::default(),
+        };
+
+        let skip_noop_revisions = env::var("SKIP_NOOP_REVISIONS")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(true);
+
+        let config = Self {
             database_url,
             port,
             log_level,
+            log_format,
+            log_directives,
+            trace_sample_ratio,
+            otlp_endpoint,
             cors_allowed_origins,
+            cors_allow_credentials,
             jwt_public_key,
             allow_dev_identity,
             enforce_scopes,
+            cost_timeout_ms,
+            shutdown_timeout_secs,
+            similarity_threshold,
+            orphan_threshold,
+            cluster_decay_half_life,
+            min_cluster_size,
+            catalog_shift_weight,
+            max_body_bytes,
+            max_header_bytes,
+            max_references_per_entry,
+            allowed_content_types,
+            denied_content_types,
+            default_content_type,
+            infer_topics,
+            content_validators,
+            max_image_bytes,
+            request_timeout_secs,
+            request_id_pattern,
+            catalog_warmup,
+            catalog_warmup_notebooks,
+            catalog_warmup_concurrency,
+            search_index_path,
+            search_commit_docs,
+            search_commit_interval_ms,
+            write_entropy_budget,
+            write_budget_window_secs,
+            write_throttle_interval_secs,
+            event_channel_capacity,
+            id_strategy,
+            anonymous_reads_per_minute,
+            catalog_locale,
+            catalog_summary_mode,
+            skip_noop_revisions,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Validate settings that would otherwise fail at runtime.
+    ///
+    /// Rejects malformed CORS origins, credentials combined with "*",
+    /// unparsable log directives, a trace sample ratio outside 0.0-1.0, an
+    /// OTLP endpoint that is not an http(s) URL, integration cost coefficients outside their ranges, a default content
+    /// type that is not `type/subtype`, a request ID pattern that does not
+    /// compile, a non-positive write budget, and zero
+    /// request limits, warmup concurrency, search commit limits, write
+    /// throttle durations, event channel capacity or anonymous read limit.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let origins = parse_cors_origins(&self.cors_allowed_origins)?;
+        if self.cors_allow_credentials && origins == CorsOrigins::Any {
+            return Err(ConfigError::InvalidValue {
+                name: "CORS_ALLOW_CREDENTIALS".to_string(),
+                reason: "credentials require explicit CORS_ALLOWED_ORIGINS, not \"*\"".to_string(),
+            });
+        }
+
+        for (name, directives) in [
+            ("LOG_LEVEL", &self.log_level),
+            ("LOG_DIRECTIVES", &self.log_directives),
+        ] {
+            if let Err(e) = EnvFilter::try_new(directives) {
+                return Err(ConfigError::InvalidValue {
+                    name: name.to_string(),
+                    reason: e.to_string(),
+                });
+            }
+        }
+        validate_unit_interval("TRACE_SAMPLE_RATIO", self.trace_sample_ratio)?;
+        if let Some(endpoint) = &self.otlp_endpoint {
+            let is_http = endpoint
+                .parse::<Uri>()
+                .is_ok_and(|uri| matches!(uri.scheme_str(), Some("http" | "https")));
+            if !is_http {
+                return Err(ConfigError::InvalidValue {
+                    name: "OTLP_ENDPOINT".to_string(),
+                    reason: format!("expected an http(s) URL, got {:?}", endpoint),
+                });
+            }
+        }
+
+        validate_unit_interval("SIMILARITY_THRESHOLD", self.similarity_threshold)?;
+        if let Some(threshold) = self.orphan_threshold {
+            validate_unit_interval("ORPHAN_THRESHOLD", threshold)?;
+        }
+        if let Some(half_life) = self.cluster_decay_half_life
+            && !(half_life.is_finite() && half_life > 0.0)
+        {
+            return Err(ConfigError::InvalidValue {
+                name: "CLUSTER_DECAY_HALF_LIFE".to_string(),
+                reason: format!("expected a positive number, got {}", half_life),
+            });
+        }
+        if let Some(budget) = self.write_entropy_budget
+            && !(budget.is_finite() && budget > 0.0)
+        {
+            return Err(ConfigError::InvalidValue {
+                name: "WRITE_ENTROPY_BUDGET".to_string(),
+                reason: format!("expected a positive number, got {}", budget),
+            });
+        }
+        if !(self.catalog_shift_weight.is_finite() && self.catalog_shift_weight >= 0.0) {
+            return Err(ConfigError::InvalidValue {
+                name: "CATALOG_SHIFT_WEIGHT".to_string(),
+                reason: format!(
+                    "expected a non-negative number, got {}",
+                    self.catalog_shift_weight
+                ),
+            });
+        }
+        if let Some(name) = self
+            .content_validators
+            .iter()
+            .find(|name| !BUILTIN_VALIDATORS.contains(&name.as_str()))
+        {
+            return Err(ConfigError::InvalidValue {
+                name: "CONTENT_VALIDATORS".to_string(),
+                reason: format!(
+                    "unknown validator {:?}, expected one of {}",
+                    name,
+                    BUILTIN_VALIDATORS.join(", ")
+                ),
+            });
+        }
+        if !is_media_type(&self.default_content_type) {
+            return Err(ConfigError::InvalidValue {
+                name: "DEFAULT_CONTENT_TYPE".to_string(),
+                reason: format!(
+                    "expected a type/subtype, got {:?}",
+                    self.default_content_type
+                ),
+            });
+        }
+        if let Err(e) = Regex::new(&self.request_id_pattern) {
+            return Err(ConfigError::InvalidValue {
+                name: "REQUEST_ID_PATTERN".to_string(),
+                reason: e.to_string(),
+            });
+        }
+
+        for (name, value) in [
+            ("MAX_BODY_BYTES", self.max_body_bytes as u64),
+            ("MAX_HEADER_BYTES", self.max_header_bytes as u64),
+            ("MAX_IMAGE_BYTES", self.max_image_bytes as u64),
+            (
+                "MAX_REFERENCES_PER_ENTRY",
+                self.max_references_per_entry as u64,
+            ),
+            ("REQUEST_TIMEOUT_SECS", self.request_timeout_secs),
+            (
+                "CATALOG_WARMUP_CONCURRENCY",
+                self.catalog_warmup_concurrency as u64,
+            ),
+            ("SEARCH_COMMIT_DOCS", self.search_commit_docs as u64),
+            ("SEARCH_COMMIT_INTERVAL_MS", self.search_commit_interval_ms),
+            ("WRITE_BUDGET_WINDOW_SECS", self.write_budget_window_secs),
+            (
+                "WRITE_THROTTLE_INTERVAL_SECS",
+                self.write_throttle_interval_secs,
+            ),
+            ("EVENT_CHANNEL_CAPACITY", self.event_channel_capacity as u64),
+            (
+                "ANONYMOUS_READS_PER_MINUTE",
+                u64::from(self.anonymous_reads_per_minute),
+            ),
+        ] {
+            if value == 0 {
+                return Err(ConfigError::InvalidValue {
+                    name: name.to_string(),
+                    reason: "must be greater than 0".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Integration cost coefficients for the entropy engine.
+    pub fn cost_config(&self) -> CostConfig {
+        CostConfig {
+            clustering: ClusteringConfig {
+                similarity_threshold: self.similarity_threshold,
+                decay_half_life: self.cluster_decay_half_life,
+                locale: self.catalog_locale,
+                min_cluster_size: self.min_cluster_size,
+                ..ClusteringConfig::default()
+            },
+            orphan_threshold: self.orphan_threshold,
+            catalog_shift_weight: self.catalog_shift_weight,
+        }
+    }
+
+    /// Server-wide write budget, if one is configured.
+    pub fn write_budget(&self) -> Option<WriteBudget> {
+        self.write_entropy_budget.map(|entropy_budget| WriteBudget {
+            entropy_budget,
+            window: Duration::from_secs(self.write_budget_window_secs),
         })
     }
 
+    /// Minimum interval between writes of an author at their budget.
+    pub fn write_throttle_interval(&self) -> Duration {
+        Duration::from_secs(self.write_throttle_interval_secs)
+    }
+
+    /// Filter directives for the tracing subscriber: `log_level` as the
+    /// default, followed by the per-target `log_directives`.
+    pub fn log_filter_directives(&self) -> String {
+        if self.log_directives.trim().is_empty() {
+            self.log_level.clone()
+        } else {
+            format!("{},{}", self.log_level, self.log_directives)
+        }
+    }
+
+    /// Deadline for integration cost computation.
+    pub fn cost_timeout(&self) -> Duration {
+        Duration::from_millis(self.cost_timeout_ms)
+    }
+
+    /// Deadline for graceful shutdown.
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(self.shutdown_timeout_secs)
+    }
+
+    /// When the search index commits buffered changes.
+    pub fn search_commit_policy(&self) -> CommitPolicy {
+        CommitPolicy {
+            max_pending: self.search_commit_docs,
+            max_delay: Duration::from_millis(self.search_commit_interval_ms),
+        }
+    }
+
+    /// Server-wide content-type policy for new entries.
+    pub fn content_type_policy(&self) -> ContentTypePolicy {
+        ContentTypePolicy::new(&self.allowed_content_types, &self.denied_content_types)
+    }
+
+    /// Deadline for each request.
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_secs)
+    }
+
     /// Get the socket address for the server.
     pub fn socket_addr(&self) -> std::net::SocketAddr {
         std::net::SocketAddr::from(([0, 0, 0, 0], self.port))
     }
 }
 
+/// Split a comma-separated setting, dropping blank items.
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether `value` is a `type/subtype` content type, parameters allowed.
+fn is_media_type(value: &str) -> bool {
+    media_type_essence(value)
+        .split_once('/')
+        .is_some_and(|(kind, subtype)| !kind.is_empty() && !subtype.is_empty())
+}
+
+/// Check that a threshold lies in 0.0-1.0.
+fn validate_unit_interval(name: &str, value: f64) -> Result<(), ConfigError> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(())
+    } else {
+        Err(ConfigError::InvalidValue {
+            name: name.to_string(),
+            reason: format!("expected a value between 0.0 and 1.0, got {}", value),
+        })
+    }
+}
+
+/// Log output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable multi-field lines.
+    Pretty,
+    /// One JSON object per line, for log pipelines.
+    Json,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            other => Err(ConfigError::InvalidValue {
+                name: "LOG_FORMAT".to_string(),
+                reason: format!("expected \"pretty\" or \"json\", got \"{}\"", other),
+            }),
+        }
+    }
+}
+
+/// Parse an entry ID strategy name.
+///
+/// Accepts "uuid_v4" and "uuid_v7", case-insensitively; "ulid" is taken as
+/// "uuid_v7", which has the same time-ordered layout.
+pub fn parse_id_strategy(value: &str) -> Result<IdStrategy, ConfigError> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "uuid_v4" => Ok(IdStrategy::UuidV4),
+        "uuid_v7" | "ulid" => Ok(IdStrategy::UuidV7),
+        other => Err(ConfigError::InvalidValue {
+            name: "ENTRY_ID_STRATEGY".to_string(),
+            reason: format!("expected \"uuid_v4\" or \"uuid_v7\", got \"{}\"", other),
+        }),
+    }
+}
+
+/// Parse a catalog locale.
+///
+/// Accepts a language code, optionally with a region ("fr", "fr-CA").
+pub fn parse_catalog_locale(value: &str) -> Result<Locale, ConfigError> {
+    Locale::from_code(value).ok_or_else(|| ConfigError::InvalidValue {
+        name: "CATALOG_LOCALE".to_string(),
+        reason: format!("expected \"en\" or \"fr\", got \"{}\"", value.trim()),
+    })
+}
+
+/// Parse a catalog summary mode.
+pub fn parse_catalog_summary_mode(value: &str) -> Result<SummaryMode, ConfigError> {
+    SummaryMode::from_name(value).ok_or_else(|| ConfigError::InvalidValue {
+        name: "CATALOG_SUMMARY_MODE".to_string(),
+        reason: format!(
+            "expected \"extractive\", \"keyword\" or \"representative_sentence\", got \"{}\"",
+            value.trim()
+        ),
+    })
+}
+
+/// Allowed CORS origins.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CorsOrigins {
+    /// Any origin ("*").
+    Any,
+    /// Only the listed origins.
+    List(Vec<CorsOriginRule>),
+}
+
+/// An allowed CORS origin with optional method and header restrictions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorsOriginRule {
+    /// Origin as `scheme://host[:port]`.
+    pub origin: String,
+    /// Allowed methods; `None` allows any.
+    pub methods: Option<Vec<Method>>,
+    /// Allowed request headers; `None` allows any.
+    pub headers: Option<Vec<HeaderName>>,
+}
+
+/// Parse the `CORS_ALLOWED_ORIGINS` setting.
+///
+/// The value is "*" or a comma-separated list of origins. Each origin may be
+/// followed by `;methods=A|B` and/or `;headers=a|b` to restrict what that
+/// origin may use. Fails on the first malformed entry.
+pub fn parse_cors_origins(value: &str) -> Result<CorsOrigins, ConfigError> {
+    if value.trim() == "*" {
+        return Ok(CorsOrigins::Any);
+    }
+    value
+        .split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(parse_cors_origin)
+        .collect::<Result<Vec<_>, _>>()
+        .map(CorsOrigins::List)
+}
+
+/// Parse a single `CORS_ALLOWED_ORIGINS` entry.
+pub fn parse_cors_origin(entry: &str) -> Result<CorsOriginRule, ConfigError> {
+    let invalid = |reason: String| ConfigError::InvalidValue {
+        name: "CORS_ALLOWED_ORIGINS".to_string(),
+        reason,
+    };
+
+    let mut parts = entry.split(';').map(str::trim);
+    let origin = parts.next().unwrap_or_default();
+
+    let uri: Uri = origin
+        .parse()
+        .map_err(|_| invalid(format!("malformed origin '{}'", origin)))?;
+    let well_formed = matches!(uri.scheme_str(), Some("http" | "https"))
+        && uri.authority().is_some()
+        && matches!(uri.path(), "" | "/")
+        && uri.query().is_none()
+        && !origin.ends_with('/');
+    if !well_formed {
+        return Err(invalid(format!(
+            "malformed origin '{}': expected scheme://host[:port]",
+            origin
+        )));
+    }
+
+    let mut rule = CorsOriginRule {
+        origin: origin.to_string(),
+        methods: None,
+        headers: None,
+    };
+
+    for option in parts {
+        let (key, values) = option.split_once('=').ok_or_else(|| {
+            invalid(format!(
+                "expected key=value for '{}', got '{}'",
+                origin, option
+            ))
+        })?;
+        let values = values.split('|').map(str::trim).filter(|v| !v.is_empty());
+        match key.trim() {
+            "methods" => {
+                let methods = values
+                    .map(|m| {
+                        Method::from_bytes(m.to_ascii_uppercase().as_bytes()).map_err(|_| {
+                            invalid(format!("invalid method '{}' for '{}'", m, origin))
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                rule.methods = Some(methods);
+            }
+            "headers" => {
+                let headers = values
+                    .map(|h| {
+                        HeaderName::from_bytes(h.as_bytes()).map_err(|_| {
+                            invalid(format!("invalid header '{}' for '{}'", h, origin))
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                rule.headers = Some(headers);
+            }
+            other => {
+                return Err(invalid(format!(
+                    "unknown option '{}' for '{}' (expected methods or headers)",
+                    other, origin
+                )));
+            }
+        }
+    }
+
+    Ok(rule)
+}
+
 /// Configuration errors.
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
@@ -102,12 +987,388 @@ mod tests {
 
         assert_eq!(config.port, 3000);
         assert_eq!(config.log_level, "info");
+        assert_eq!(config.log_format, LogFormat::Pretty);
+        assert!(config.log_directives.is_empty());
+        assert_eq!(config.trace_sample_ratio, DEFAULT_TRACE_SAMPLE_RATIO);
+        assert_eq!(config.otlp_endpoint, None);
         assert_eq!(config.cors_allowed_origins, "*");
+        assert!(!config.cors_allow_credentials);
         assert!(config.jwt_public_key.is_empty());
         assert!(!config.allow_dev_identity);
         assert!(config.enforce_scopes);
+        assert_eq!(config.cost_timeout_ms, DEFAULT_COST_TIMEOUT_MS);
+        assert_eq!(config.shutdown_timeout_secs, DEFAULT_SHUTDOWN_TIMEOUT_SECS);
+        assert_eq!(config.orphan_threshold, None);
+        assert_eq!(config.cluster_decay_half_life, None);
+        assert_eq!(config.min_cluster_size, 0);
+        assert_eq!(config.catalog_shift_weight, DEFAULT_CATALOG_SHIFT_WEIGHT);
+        assert_eq!(config.max_body_bytes, DEFAULT_MAX_BODY_BYTES);
+        assert_eq!(config.max_header_bytes, DEFAULT_MAX_HEADER_BYTES);
+        assert_eq!(
+            config.max_references_per_entry,
+            DEFAULT_MAX_REFERENCES_PER_ENTRY
+        );
+        assert!(config.allowed_content_types.is_empty());
+        assert!(config.denied_content_types.is_empty());
+        assert_eq!(config.default_content_type, DEFAULT_CONTENT_TYPE);
+        assert!(!config.infer_topics);
+        assert!(config.content_validators.is_empty());
+        assert_eq!(config.max_image_bytes, DEFAULT_MAX_IMAGE_BYTES);
+        assert_eq!(config.request_timeout_secs, DEFAULT_REQUEST_TIMEOUT_SECS);
+        assert_eq!(config.request_id_pattern, DEFAULT_REQUEST_ID_PATTERN);
+        assert!(!config.catalog_warmup);
+        assert_eq!(
+            config.catalog_warmup_notebooks,
+            DEFAULT_CATALOG_WARMUP_NOTEBOOKS
+        );
+        assert_eq!(
+            config.catalog_warmup_concurrency,
+            DEFAULT_CATALOG_WARMUP_CONCURRENCY
+        );
+        assert_eq!(config.search_index_path, None);
+        assert_eq!(config.search_commit_docs, DEFAULT_SEARCH_COMMIT_DOCS);
+        assert_eq!(
+            config.search_commit_interval_ms,
+            DEFAULT_SEARCH_COMMIT_INTERVAL_MS
+        );
+        assert_eq!(config.write_entropy_budget, None);
+        assert_eq!(
+            config.write_budget_window_secs,
+            DEFAULT_WRITE_BUDGET_WINDOW_SECS
+        );
+        assert_eq!(
+            config.write_throttle_interval_secs,
+            DEFAULT_WRITE_THROTTLE_INTERVAL_SECS
+        );
+        assert!(config.write_budget().is_none());
+        assert_eq!(config.event_channel_capacity, DEFAULT_CHANNEL_CAPACITY);
+        assert_eq!(config.id_strategy, IdStrategy::UuidV4);
+        assert_eq!(config.catalog_locale, Locale::English);
+        assert_eq!(config.catalog_summary_mode, SummaryMode::Extractive);
+        assert!(config.skip_noop_revisions);
+        assert_eq!(
+            config.anonymous_reads_per_minute,
+            DEFAULT_ANONYMOUS_READS_PER_MINUTE
+        );
 
         // SAFETY: This test is not run in parallel with other tests that read DATABASE_URL.
         unsafe { env::remove_var("DATABASE_URL") };
     }
+
+    #[test]
+    fn test_parse_list_trims_and_drops_blanks() {
+        assert_eq!(
+            parse_list(" text/*, ,application/json,"),
+            vec!["text/*".to_string(), "application/json".to_string()]
+        );
+        assert!(parse_list("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_log_format() {
+        assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("Pretty".parse::<LogFormat>().unwrap(), LogFormat::Pretty);
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn test_parse_id_strategy() {
+        assert_eq!(parse_id_strategy("uuid_v4").unwrap(), IdStrategy::UuidV4);
+        assert_eq!(parse_id_strategy("UUID_V7").unwrap(), IdStrategy::UuidV7);
+        assert_eq!(parse_id_strategy("ulid").unwrap(), IdStrategy::UuidV7);
+        assert!(parse_id_strategy("uuid_v1").is_err());
+    }
+
+    #[test]
+    fn test_parse_catalog_locale() {
+        assert_eq!(parse_catalog_locale("fr").unwrap(), Locale::French);
+        assert_eq!(parse_catalog_locale(" en-GB ").unwrap(), Locale::English);
+        assert!(parse_catalog_locale("de").is_err());
+
+        let config = ServerConfig {
+            catalog_locale: Locale::French,
+            ..ServerConfig::default()
+        };
+        assert_eq!(config.cost_config().clustering.locale, Locale::French);
+    }
+
+    #[test]
+    fn test_parse_catalog_summary_mode() {
+        assert_eq!(
+            parse_catalog_summary_mode("keyword").unwrap(),
+            SummaryMode::Keyword
+        );
+        assert_eq!(
+            parse_catalog_summary_mode(" Representative_Sentence ").unwrap(),
+            SummaryMode::RepresentativeSentence
+        );
+        assert!(parse_catalog_summary_mode("abstractive").is_err());
+    }
+
+    #[test]
+    fn test_parse_cors_wildcard() {
+        assert_eq!(parse_cors_origins("*").unwrap(), CorsOrigins::Any);
+    }
+
+    #[test]
+    fn test_parse_cors_origins_with_rules() {
+        let origins = parse_cors_origins(
+            "https://app.example.com;methods=GET|post;headers=authorization, http://localhost:5173",
+        )
+        .unwrap();
+        let CorsOrigins::List(rules) = origins else {
+            panic!("expected explicit origins");
+        };
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].origin, "https://app.example.com");
+        assert_eq!(rules[0].methods, Some(vec![Method::GET, Method::POST]));
+        assert_eq!(
+            rules[0].headers,
+            Some(vec![HeaderName::from_static("authorization")])
+        );
+        assert_eq!(rules[1].origin, "http://localhost:5173");
+        assert_eq!(rules[1].methods, None);
+    }
+
+    #[test]
+    fn test_malformed_cors_origin_is_descriptive_error() {
+        for bad in [
+            "not a url",
+            "example.com",
+            "https://example.com/path",
+            "ftp://x",
+        ] {
+            let err = parse_cors_origins(bad).unwrap_err();
+            let message = err.to_string();
+            assert!(message.contains("CORS_ALLOWED_ORIGINS"), "{message}");
+            assert!(message.contains("malformed origin"), "{message}");
+        }
+
+        let err = parse_cors_origins("https://ok.example.com;verbs=GET").unwrap_err();
+        assert!(err.to_string().contains("unknown option 'verbs'"));
+    }
+
+    #[test]
+    fn test_credentials_require_explicit_origins() {
+        let config = ServerConfig {
+            cors_allow_credentials: true,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = ServerConfig {
+            cors_allowed_origins: "https://app.example.com".to_string(),
+            cors_allow_credentials: true,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_cost_thresholds_must_be_in_unit_interval() {
+        let config = ServerConfig {
+            similarity_threshold: 1.5,
+            ..ServerConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = ServerConfig {
+            orphan_threshold: Some(-0.1),
+            ..ServerConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = ServerConfig {
+            catalog_shift_weight: f64::NAN,
+            ..ServerConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = ServerConfig {
+            cluster_decay_half_life: Some(0.0),
+            ..ServerConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = ServerConfig {
+            write_entropy_budget: Some(-1.0),
+            ..ServerConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = ServerConfig {
+            similarity_threshold: 1.0,
+            orphan_threshold: Some(0.0),
+            cluster_decay_half_life: Some(50.0),
+            catalog_shift_weight: 2.0,
+            ..ServerConfig::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_log_filter_directives_follow_log_level() {
+        let config = ServerConfig {
+            log_level: "warn".to_string(),
+            ..ServerConfig::default()
+        };
+        assert_eq!(config.log_filter_directives(), "warn");
+
+        let config = ServerConfig {
+            log_directives: "sqlx=warn,notebook_entropy=debug".to_string(),
+            ..config
+        };
+        assert_eq!(
+            config.log_filter_directives(),
+            "warn,sqlx=warn,notebook_entropy=debug"
+        );
+    }
+
+    #[test]
+    fn test_tracing_settings_are_validated() {
+        let config = ServerConfig {
+            log_directives: "sqlx=loud".to_string(),
+            ..ServerConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("LOG_DIRECTIVES"));
+
+        let config = ServerConfig {
+            trace_sample_ratio: 1.5,
+            ..ServerConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("TRACE_SAMPLE_RATIO"));
+
+        let config = ServerConfig {
+            otlp_endpoint: Some("localhost:4318".to_string()),
+            ..ServerConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("OTLP_ENDPOINT"));
+
+        let config = ServerConfig {
+            log_directives: "sqlx=warn,notebook_entropy[cost]=debug".to_string(),
+            trace_sample_ratio: 0.25,
+            otlp_endpoint: Some("http://collector:4318".to_string()),
+            ..ServerConfig::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_request_id_pattern_must_compile() {
+        let config = ServerConfig {
+            request_id_pattern: "([a-z".to_string(),
+            ..ServerConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("REQUEST_ID_PATTERN"));
+    }
+
+    #[test]
+    fn test_default_content_type_must_be_a_media_type() {
+        for value in ["plain", "text/", " /json", ""] {
+            let config = ServerConfig {
+                default_content_type: value.to_string(),
+                ..ServerConfig::default()
+            };
+            let err = config.validate().unwrap_err();
+            assert!(
+                err.to_string().contains("DEFAULT_CONTENT_TYPE"),
+                "{:?}",
+                value
+            );
+        }
+
+        let config = ServerConfig {
+            default_content_type: "text/markdown; charset=utf-8".to_string(),
+            ..ServerConfig::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_content_validators_must_be_known() {
+        let config = ServerConfig {
+            content_validators: vec!["json".to_string(), "xml".to_string()],
+            ..ServerConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("CONTENT_VALIDATORS"));
+        assert!(err.to_string().contains("\"xml\""));
+
+        let config = ServerConfig {
+            content_validators: Vec::new(),
+            ..ServerConfig::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_request_limits_must_be_positive() {
+        for config in [
+            ServerConfig {
+                max_body_bytes: 0,
+                ..ServerConfig::default()
+            },
+            ServerConfig {
+                max_header_bytes: 0,
+                ..ServerConfig::default()
+            },
+            ServerConfig {
+                max_image_bytes: 0,
+                ..ServerConfig::default()
+            },
+            ServerConfig {
+                max_references_per_entry: 0,
+                ..ServerConfig::default()
+            },
+            ServerConfig {
+                request_timeout_secs: 0,
+                ..ServerConfig::default()
+            },
+            ServerConfig {
+                catalog_warmup_concurrency: 0,
+                ..ServerConfig::default()
+            },
+            ServerConfig {
+                search_commit_docs: 0,
+                ..ServerConfig::default()
+            },
+            ServerConfig {
+                search_commit_interval_ms: 0,
+                ..ServerConfig::default()
+            },
+            ServerConfig {
+                event_channel_capacity: 0,
+                ..ServerConfig::default()
+            },
+            ServerConfig {
+                anonymous_reads_per_minute: 0,
+                ..ServerConfig::default()
+            },
+        ] {
+            assert!(config.validate().is_err());
+        }
+        assert!(ServerConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_cost_config_carries_coefficients() {
+        let config = ServerConfig {
+            similarity_threshold: 0.6,
+            orphan_threshold: Some(0.8),
+            cluster_decay_half_life: Some(25.0),
+            min_cluster_size: 2,
+            catalog_shift_weight: 0.5,
+            ..ServerConfig::default()
+        };
+        let cost = config.cost_config();
+        assert_eq!(cost.clustering.similarity_threshold, 0.6);
+        assert_eq!(cost.clustering.decay_half_life, Some(25.0));
+        assert_eq!(cost.clustering.min_cluster_size, 2);
+        assert_eq!(cost.orphan_threshold, Some(0.8));
+        assert_eq!(cost.catalog_shift_weight, 0.5);
+    }
 }
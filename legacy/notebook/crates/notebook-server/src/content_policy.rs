@@ -0,0 +1,179 @@
+//! Content-type policies for new entries.
+//!
+//! A policy is a list of allowed and a list of denied content-type globs,
+//! such as `text/*` or `application/x-msdownload`. `*` matches any run of
+//! characters. A content type passes when it matches no denied glob and
+//! either the allow list is empty or it matches an allowed glob.
+//!
+//! The server-wide policy comes from [`ServerConfig`](crate::config::ServerConfig);
+//! a notebook owner can add a stricter per-notebook policy. An entry must
+//! pass both, otherwise it is rejected with 415 Unsupported Media Type.
+
+use uuid::Uuid;
+
+use notebook_store::NotebookContentPolicyRow;
+
+use crate::error::{ApiError, ApiResult, ErrorCode};
+use crate::state::AppState;
+
+/// Allowed and denied content-type globs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentTypePolicy {
+    allowed: Vec<String>,
+    denied: Vec<String>,
+}
+
+impl ContentTypePolicy {
+    /// Build a policy from allow and deny globs, matched case-insensitively.
+    pub fn new(allowed: &[String], denied: &[String]) -> Self {
+        let lower = |globs: &[String]| {
+            globs
+                .iter()
+                .map(|g| g.trim().to_ascii_lowercase())
+                .collect()
+        };
+        Self {
+            allowed: lower(allowed),
+            denied: lower(denied),
+        }
+    }
+
+    /// Whether the policy admits every content type.
+    pub fn is_unrestricted(&self) -> bool {
+        self.allowed.is_empty() && self.denied.is_empty()
+    }
+
+    /// Whether `content_type` passes the policy.
+    ///
+    /// Parameters such as `; charset=utf-8` are ignored.
+    pub fn permits(&self, content_type: &str) -> bool {
+        let essence = media_type_essence(content_type);
+        if self.denied.iter().any(|glob| glob_matches(glob, &essence)) {
+            return false;
+        }
+        self.allowed.is_empty() || self.allowed.iter().any(|glob| glob_matches(glob, &essence))
+    }
+}
+
+impl From<&NotebookContentPolicyRow> for ContentTypePolicy {
+    fn from(row: &NotebookContentPolicyRow) -> Self {
+        Self::new(&row.allowed_content_types, &row.denied_content_types)
+    }
+}
+
+/// Lowercased media type without parameters.
+pub(crate) fn media_type_essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Match `text` against `glob`, where `*` matches any run of characters.
+pub(crate) fn glob_matches(glob: &str, text: &str) -> bool {
+    let mut parts = glob.split('*');
+    // split always yields at least one item
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No `*`: the whole glob must match
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Reject `content_type` unless both the server-wide policy and the
+/// notebook's own policy admit it.
+pub async fn enforce_content_policy(
+    state: &AppState,
+    notebook_id: Uuid,
+    content_type: &str,
+) -> ApiResult<()> {
+    if !state.config().content_type_policy().permits(content_type) {
+        return Err(unsupported(content_type, "on this server"));
+    }
+
+    if let Some(row) = state.store().get_content_policy(notebook_id).await?
+        && !ContentTypePolicy::from(&row).permits(content_type)
+    {
+        return Err(unsupported(content_type, "in this notebook"));
+    }
+    Ok(())
+}
+
+fn unsupported(content_type: &str, scope: &str) -> ApiError {
+    ApiError::Coded(
+        ErrorCode::UnsupportedContentType,
+        format!("Content type '{}' is not allowed {}", content_type, scope),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allowed: &[&str], denied: &[&str]) -> ContentTypePolicy {
+        let owned = |globs: &[&str]| globs.iter().map(|g| g.to_string()).collect::<Vec<_>>();
+        ContentTypePolicy::new(&owned(allowed), &owned(denied))
+    }
+
+    #[test]
+    fn test_empty_policy_permits_everything() {
+        let policy = ContentTypePolicy::default();
+        assert!(policy.is_unrestricted());
+        assert!(policy.permits("application/x-msdownload"));
+        assert!(policy.permits("text/plain"));
+    }
+
+    #[test]
+    fn test_denied_type_is_rejected() {
+        let policy = policy(&[], &["application/x-msdownload"]);
+        assert!(!policy.permits("application/x-msdownload"));
+        assert!(policy.permits("application/json"));
+    }
+
+    #[test]
+    fn test_text_only_policy() {
+        let policy = policy(&["text/*"], &[]);
+        assert!(policy.permits("text/markdown"));
+        assert!(policy.permits("text/plain; charset=utf-8"));
+        assert!(!policy.permits("application/x-msdownload"));
+        assert!(!policy.permits("application/json"));
+    }
+
+    #[test]
+    fn test_deny_wins_over_allow() {
+        let policy = policy(&["text/*"], &["text/html"]);
+        assert!(policy.permits("text/markdown"));
+        assert!(!policy.permits("text/html"));
+    }
+
+    #[test]
+    fn test_matching_ignores_case() {
+        let policy = policy(&["Text/*"], &[]);
+        assert!(policy.permits("TEXT/Markdown"));
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("text/plain", "text/plain"));
+        assert!(!glob_matches("text/plain", "text/plain2"));
+        assert!(glob_matches("*", "anything/at-all"));
+        assert!(glob_matches("application/*+json", "application/ld+json"));
+        assert!(!glob_matches("application/*+json", "application/xml"));
+        assert!(glob_matches("*/*", "image/png"));
+        assert!(!glob_matches("image/*", "text/png"));
+    }
+}
@@ -0,0 +1,330 @@
+//! Content validation for new entries.
+//!
+//! The content-type policy decides which types may be written at all;
+//! validators check that the content is what its type claims. They are
+//! registered against content-type globs (see
+//! [`ContentTypePolicy`](crate::content_policy::ContentTypePolicy) for the
+//! glob syntax) and every validator whose glob matches runs on the entry's
+//! content before it is stored.
+//!
+//! Two validators are built in; none runs unless named in
+//! `CONTENT_VALIDATORS`:
+//!
+//! - `json`: `application/json` and `+json` types must parse as JSON.
+//! - `image`: `image/*` content must start with the signature of the
+//!   declared format and stay within
+//!   [`ServerConfig::max_image_bytes`](crate::config::ServerConfig::max_image_bytes).
+//!   Image types whose signature is unknown are rejected.
+//!
+//! Content that fails validation is rejected with 400, naming the problem;
+//! a type no validator can check is rejected with 415. The set of built-in
+//! validators is chosen by `CONTENT_VALIDATORS`, and further validators can
+//! be registered with [`ContentValidators::register`].
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::config::ServerConfig;
+use crate::content_policy::{glob_matches, media_type_essence};
+use crate::error::{ApiError, ApiResult, ErrorCode};
+
+/// Names of the built-in validators, as used in `CONTENT_VALIDATORS`.
+pub const BUILTIN_VALIDATORS: &[&str] = &["json", "image"];
+
+/// Why a validator rejected content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentRejection {
+    /// The content is not valid for its declared type (400).
+    Invalid(String),
+    /// The declared type cannot be checked (415).
+    Unsupported(String),
+}
+
+impl From<ContentRejection> for ApiError {
+    fn from(rejection: ContentRejection) -> Self {
+        match rejection {
+            ContentRejection::Invalid(reason) => ApiError::Coded(ErrorCode::InvalidContent, reason),
+            ContentRejection::Unsupported(reason) => {
+                ApiError::Coded(ErrorCode::UnsupportedContentType, reason)
+            }
+        }
+    }
+}
+
+/// A check run on the content of new entries.
+pub trait ContentValidator: Send + Sync {
+    /// Check `content`, declared as `content_type` (lowercased, without
+    /// parameters).
+    fn validate(&self, content_type: &str, content: &[u8]) -> Result<(), ContentRejection>;
+}
+
+/// Requires content to parse as JSON.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonValidator;
+
+impl ContentValidator for JsonValidator {
+    fn validate(&self, content_type: &str, content: &[u8]) -> Result<(), ContentRejection> {
+        serde_json::from_slice::<serde::de::IgnoredAny>(content)
+            .map(|_| ())
+            .map_err(|e| {
+                ContentRejection::Invalid(format!(
+                    "Content declared as {} is not valid JSON: {}",
+                    content_type, e
+                ))
+            })
+    }
+}
+
+/// Requires images to carry the signature of their format and to stay
+/// within a size cap.
+#[derive(Debug, Clone, Copy)]
+pub struct ImageValidator {
+    /// Largest accepted image, in bytes.
+    pub max_bytes: usize,
+}
+
+impl ImageValidator {
+    /// Whether `content` starts like an image of `content_type`, or `None`
+    /// if the format is not known.
+    fn has_signature(content_type: &str, content: &[u8]) -> Option<bool> {
+        let matches = match content_type {
+            "image/png" => content.starts_with(b"\x89PNG\r\n\x1a\n"),
+            "image/jpeg" | "image/jpg" => content.starts_with(&[0xFF, 0xD8, 0xFF]),
+            "image/gif" => content.starts_with(b"GIF87a") || content.starts_with(b"GIF89a"),
+            "image/webp" => {
+                content.len() >= 12 && &content[..4] == b"RIFF" && &content[8..12] == b"WEBP"
+            }
+            "image/bmp" => content.starts_with(b"BM"),
+            "image/tiff" => content.starts_with(b"II*\0") || content.starts_with(b"MM\0*"),
+            "image/avif" | "image/heic" | "image/heif" => {
+                content.len() >= 12 && &content[4..8] == b"ftyp"
+            }
+            "image/x-icon" | "image/vnd.microsoft.icon" => content.starts_with(&[0, 0, 1, 0]),
+            "image/svg+xml" => std::str::from_utf8(content).is_ok_and(|text| text.contains("<svg")),
+            _ => return None,
+        };
+        Some(matches)
+    }
+}
+
+impl ContentValidator for ImageValidator {
+    fn validate(&self, content_type: &str, content: &[u8]) -> Result<(), ContentRejection> {
+        if content.len() > self.max_bytes {
+            return Err(ContentRejection::Invalid(format!(
+                "Image is {} bytes, more than the {} allowed",
+                content.len(),
+                self.max_bytes
+            )));
+        }
+        match Self::has_signature(content_type, content) {
+            Some(true) => Ok(()),
+            Some(false) => Err(ContentRejection::Invalid(format!(
+                "Content declared as {} does not look like one",
+                content_type
+            ))),
+            None => Err(ContentRejection::Unsupported(format!(
+                "Image type '{}' cannot be verified",
+                content_type
+            ))),
+        }
+    }
+}
+
+/// Validators keyed by the content-type globs they apply to.
+#[derive(Clone, Default)]
+pub struct ContentValidators {
+    validators: Vec<(String, Arc<dyn ContentValidator>)>,
+}
+
+impl ContentValidators {
+    /// No validators; every content passes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The built-in validators named in `config.content_validators`.
+    ///
+    /// Unknown names are skipped; [`ServerConfig::validate`] rejects them.
+    pub fn from_config(config: &ServerConfig) -> Self {
+        let mut validators = Self::new();
+        for name in &config.content_validators {
+            match name.as_str() {
+                "json" => {
+                    validators = validators
+                        .register("application/json", JsonValidator)
+                        .register("application/*+json", JsonValidator);
+                }
+                "image" => {
+                    validators = validators.register(
+                        "image/*",
+                        ImageValidator {
+                            max_bytes: config.max_image_bytes,
+                        },
+                    );
+                }
+                _ => {}
+            }
+        }
+        validators
+    }
+
+    /// Adds a validator for content types matching `glob`.
+    pub fn register(mut self, glob: &str, validator: impl ContentValidator + 'static) -> Self {
+        self.validators
+            .push((glob.trim().to_ascii_lowercase(), Arc::new(validator)));
+        self
+    }
+
+    /// Whether no validators are registered.
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+
+    /// Run every validator registered for `content_type` on `content`.
+    pub fn validate(&self, content_type: &str, content: &[u8]) -> ApiResult<()> {
+        let essence = media_type_essence(content_type);
+        for (glob, validator) in &self.validators {
+            if glob_matches(glob, &essence) {
+                validator.validate(&essence, content)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ContentValidators {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let globs: Vec<&str> = self.validators.iter().map(|(g, _)| g.as_str()).collect();
+        f.debug_struct("ContentValidators")
+            .field("globs", &globs)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    fn builtins() -> ContentValidators {
+        ContentValidators::from_config(&ServerConfig {
+            content_validators: BUILTIN_VALIDATORS.iter().map(|v| v.to_string()).collect(),
+            ..ServerConfig::default()
+        })
+    }
+
+    fn code(result: ApiResult<()>) -> Option<ErrorCode> {
+        match result {
+            Err(ApiError::Coded(code, _)) => Some(code),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_valid_json_passes() {
+        let validators = builtins();
+        assert!(
+            validators
+                .validate("application/json", br#"{"key": [1, 2]}"#)
+                .is_ok()
+        );
+        assert!(
+            validators
+                .validate("application/ld+json; charset=utf-8", b"[]")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_invalid_json_is_rejected() {
+        let validators = builtins();
+        let result = validators.validate("application/json", b"{\"key\": ");
+        assert!(matches!(
+            &result,
+            Err(ApiError::Coded(ErrorCode::InvalidContent, msg)) if msg.contains("not valid JSON")
+        ));
+        assert_eq!(
+            code(validators.validate("Application/JSON", b"not json")),
+            Some(ErrorCode::InvalidContent)
+        );
+    }
+
+    #[test]
+    fn test_images_need_their_signature() {
+        let validators = builtins();
+        assert!(validators.validate("image/png", PNG).is_ok());
+        assert!(
+            validators
+                .validate("image/jpeg", &[0xFF, 0xD8, 0xFF, 0xE0])
+                .is_ok()
+        );
+        assert!(
+            validators
+                .validate("image/svg+xml", b"<?xml version=\"1.0\"?><svg/>")
+                .is_ok()
+        );
+        assert_eq!(
+            code(validators.validate("image/png", b"GIF89a")),
+            Some(ErrorCode::InvalidContent)
+        );
+        assert_eq!(
+            code(validators.validate("image/x-unknown", PNG)),
+            Some(ErrorCode::UnsupportedContentType)
+        );
+    }
+
+    #[test]
+    fn test_oversized_image_is_rejected() {
+        let config = ServerConfig {
+            content_validators: vec!["image".to_string()],
+            max_image_bytes: 8,
+            ..ServerConfig::default()
+        };
+        let validators = ContentValidators::from_config(&config);
+        let result = validators.validate("image/png", PNG);
+        assert!(matches!(
+            &result,
+            Err(ApiError::Coded(ErrorCode::InvalidContent, msg)) if msg.contains("8 allowed")
+        ));
+    }
+
+    #[test]
+    fn test_other_types_are_not_checked() {
+        let validators = builtins();
+        assert!(validators.validate("text/plain", b"{not json").is_ok());
+        assert!(
+            validators
+                .validate("application/octet-stream", &[0, 1, 2])
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_validators_are_configurable_and_extensible() {
+        let config = ServerConfig {
+            content_validators: vec!["image".to_string()],
+            ..ServerConfig::default()
+        };
+        let validators = ContentValidators::from_config(&config);
+        assert!(validators.validate("application/json", b"not json").is_ok());
+
+        struct NoTabs;
+        impl ContentValidator for NoTabs {
+            fn validate(&self, _: &str, content: &[u8]) -> Result<(), ContentRejection> {
+                if content.contains(&b'\t') {
+                    return Err(ContentRejection::Invalid("tabs are not allowed".into()));
+                }
+                Ok(())
+            }
+        }
+        let validators = validators.register("text/tab-separated-values", NoTabs);
+        assert_eq!(
+            code(validators.validate("text/tab-separated-values", b"a\tb")),
+            Some(ErrorCode::InvalidContent)
+        );
+
+        // None run unless configured
+        assert!(ContentValidators::from_config(&ServerConfig::default()).is_empty());
+    }
+}
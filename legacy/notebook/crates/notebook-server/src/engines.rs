@@ -0,0 +1,283 @@
+//! Per-notebook sharding of the integration cost engine.
+//!
+//! Cost computation can take hundreds of milliseconds. With a single engine
+//! behind one mutex, writes to unrelated notebooks would serialize. Instead,
+//! each notebook gets its own `IntegrationCostEngine` behind its own mutex:
+//!
+//! - Writes to different notebooks compute cost in parallel
+//! - Writes to the same notebook stay serialized, keeping the coherence
+//!   snapshot consistent
+//!
+//! Shards are created lazily on first use, mirroring how
+//! [`EventBroadcaster`](crate::events::EventBroadcaster) manages channels.
+//!
+//! Cost computation is CPU-bound, so [`compute_cost_bounded`] runs it on the
+//! blocking pool under a deadline. A computation that misses the deadline
+//! keeps running; its result is handed back so the caller can backfill it.
+//!
+//! Engines keep extracted text and TF-IDF/cluster state in memory only and
+//! never persist it. Entries of encrypted notebooks reach them already
+//! decrypted by the store, so their plaintext exists only in this process.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use notebook_core::{IntegrationCost, NotebookId};
+use notebook_entropy::{CostConfig, IntegrationCostEngine};
+use tokio::sync::{Mutex, OwnedMutexGuard, RwLock};
+use tokio::task::JoinHandle;
+
+/// A single notebook's engine shard.
+pub type EngineShard = Arc<Mutex<IntegrationCostEngine>>;
+
+/// Integration cost engines keyed by notebook.
+#[derive(Default)]
+pub struct EngineShards {
+    shards: RwLock<HashMap<NotebookId, EngineShard>>,
+    /// Cost coefficients for newly created engines.
+    config: CostConfig,
+}
+
+impl EngineShards {
+    /// Create an empty shard map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty shard map whose engines use `config`.
+    pub fn with_config(config: CostConfig) -> Self {
+        Self {
+            shards: RwLock::default(),
+            config,
+        }
+    }
+
+    /// Get the engine shard for a notebook, creating it if needed.
+    pub async fn shard(&self, notebook_id: NotebookId) -> EngineShard {
+        {
+            let shards = self.shards.read().await;
+            if let Some(shard) = shards.get(&notebook_id) {
+                return shard.clone();
+            }
+        }
+
+        let mut shards = self.shards.write().await;
+        shards
+            .entry(notebook_id)
+            .or_insert_with(|| {
+                Arc::new(Mutex::new(IntegrationCostEngine::with_config(
+                    self.config.clone(),
+                )))
+            })
+            .clone()
+    }
+
+    /// Lock the engine for a notebook.
+    ///
+    /// Holds only that notebook's shard; other notebooks remain available.
+    pub async fn lock(&self, notebook_id: NotebookId) -> OwnedMutexGuard<IntegrationCostEngine> {
+        self.shard(notebook_id).await.lock_owned().await
+    }
+
+    /// Number of notebooks with an engine shard.
+    pub async fn shard_count(&self) -> usize {
+        self.shards.read().await.len()
+    }
+}
+
+// ============================================================================
+// Bounded Cost Computation
+// ============================================================================
+
+/// Result of a cost computation that may still be running.
+pub type PendingCost<T = IntegrationCost> = JoinHandle<Result<T, String>>;
+
+/// Outcome of [`compute_cost_bounded`].
+#[derive(Debug)]
+pub enum CostOutcome<T = IntegrationCost> {
+    /// The cost was computed within the deadline.
+    Computed(T),
+    /// The computation failed.
+    Failed(String),
+    /// The deadline passed; the computation continues in the background.
+    TimedOut(PendingCost<T>),
+}
+
+/// Run a cost computation on the blocking pool with a deadline.
+///
+/// Usually computes an [`IntegrationCost`]; previews that need more of the
+/// engine's reasoning, such as a cost explanation, run the same way.
+pub async fn compute_cost_bounded<F, T, E>(compute: F, timeout: Duration) -> CostOutcome<T>
+where
+    F: FnOnce() -> Result<T, E> + Send + 'static,
+    T: Send + 'static,
+    E: std::fmt::Display,
+{
+    let mut handle: PendingCost<T> =
+        tokio::task::spawn_blocking(move || compute().map_err(|e| e.to_string()));
+
+    match tokio::time::timeout(timeout, &mut handle).await {
+        Ok(Ok(Ok(cost))) => CostOutcome::Computed(cost),
+        Ok(Ok(Err(e))) => CostOutcome::Failed(e),
+        Ok(Err(e)) => CostOutcome::Failed(format!("cost computation panicked: {}", e)),
+        Err(_) => CostOutcome::TimedOut(handle),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    use notebook_core::types::{AuthorId, EntryBuilder};
+
+    fn make_entry(content: &str) -> notebook_core::Entry {
+        EntryBuilder::default()
+            .content(content.as_bytes().to_vec())
+            .content_type("text/plain")
+            .author(AuthorId::zero())
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_shard_created_once_per_notebook() {
+        let shards = EngineShards::new();
+        let notebook_id = NotebookId::new();
+
+        let a = shards.shard(notebook_id).await;
+        let b = shards.shard(notebook_id).await;
+
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(shards.shard_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_shards_use_configured_coefficients() {
+        let shards = EngineShards::with_config(CostConfig {
+            catalog_shift_weight: 0.5,
+            ..CostConfig::default()
+        });
+        let engine = shards.lock(NotebookId::new()).await;
+        assert_eq!(engine.config().catalog_shift_weight, 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_different_notebooks_do_not_block() {
+        let shards = EngineShards::new();
+        let held = shards.lock(NotebookId::new()).await;
+
+        // Another notebook's shard is immediately available
+        let other =
+            tokio::time::timeout(Duration::from_millis(100), shards.lock(NotebookId::new())).await;
+        assert!(other.is_ok());
+        drop(held);
+    }
+
+    #[tokio::test]
+    async fn test_same_notebook_is_serialized() {
+        let shards = EngineShards::new();
+        let notebook_id = NotebookId::new();
+        let held = shards.lock(notebook_id).await;
+
+        assert!(shards.shard(notebook_id).await.try_lock().is_err());
+        drop(held);
+        assert!(shards.shard(notebook_id).await.try_lock().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_bounded_cost_within_deadline() {
+        let outcome = compute_cost_bounded(
+            || Ok::<_, String>(IntegrationCost::zero()),
+            Duration::from_secs(1),
+        )
+        .await;
+        assert!(matches!(outcome, CostOutcome::Computed(_)));
+    }
+
+    #[tokio::test]
+    async fn test_bounded_cost_failure() {
+        let outcome =
+            compute_cost_bounded(|| Err::<IntegrationCost, _>("boom"), Duration::from_secs(1))
+                .await;
+        assert!(matches!(outcome, CostOutcome::Failed(e) if e == "boom"));
+    }
+
+    #[tokio::test]
+    async fn test_bounded_cost_timeout_returns_pending_result() {
+        let slow = || {
+            std::thread::sleep(Duration::from_millis(200));
+            Ok::<_, String>(IntegrationCost {
+                entries_revised: 3,
+                ..IntegrationCost::zero()
+            })
+        };
+
+        let start = Instant::now();
+        let outcome = compute_cost_bounded(slow, Duration::from_millis(20)).await;
+        assert!(start.elapsed() < Duration::from_millis(150));
+
+        let CostOutcome::TimedOut(pending) = outcome else {
+            panic!("expected timeout");
+        };
+        // The computation finishes in the background for backfilling
+        let cost = pending.await.unwrap().unwrap();
+        assert_eq!(cost.entries_revised, 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_parallel_throughput_across_notebooks() {
+        const HOLD: Duration = Duration::from_millis(50);
+        const NOTEBOOKS: usize = 4;
+
+        let shards = Arc::new(EngineShards::new());
+        let start = Instant::now();
+
+        // Each task simulates a slow cost computation in its own notebook
+        let tasks: Vec<_> = (0..NOTEBOOKS)
+            .map(|_| {
+                let shards = shards.clone();
+                tokio::spawn(async move {
+                    let _engine = shards.lock(NotebookId::new()).await;
+                    tokio::time::sleep(HOLD).await;
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        // Serialized execution would take NOTEBOOKS * HOLD
+        assert!(start.elapsed() < HOLD * NOTEBOOKS as u32);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_same_notebook_writes_are_all_recorded() {
+        const WRITES: usize = 16;
+
+        let shards = Arc::new(EngineShards::new());
+        let notebook_id = NotebookId::new();
+
+        let tasks: Vec<_> = (0..WRITES)
+            .map(|i| {
+                let shards = shards.clone();
+                tokio::spawn(async move {
+                    let entry = make_entry(&format!("concurrent entry number {}", i));
+                    let mut engine = shards.lock(notebook_id).await;
+                    engine.compute_cost(&entry, notebook_id).unwrap();
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let engine = shards.lock(notebook_id).await;
+        let stats = engine.get_snapshot(notebook_id).unwrap().stats();
+        assert_eq!(stats.entry_count, WRITES);
+    }
+}
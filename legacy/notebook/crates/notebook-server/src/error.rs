@@ -1,11 +1,188 @@
 //! API error types with JSON responses.
+//!
+//! Every error response carries a stable machine-readable `error_code` so
+//! clients can branch on it instead of matching message text:
+//!
+//! ```json
+//! {
+//!   "error": {
+//!     "code": "NOT_FOUND",
+//!     "error_code": "NOTEBOOK_NOT_FOUND",
+//!     "message": "not found: Notebook 0b6e... not found",
+//!     "request_id": "5f1c..."
+//!   }
+//! }
+//! ```
+//!
+//! `code` is the coarse category and is kept for existing clients.
+//!
+//! A `DUPLICATE_NOTEBOOK_NAME` error also carries `existing_notebook_id`,
+//! the notebook that already has the name, so clients can reuse it.
+
+use std::time::Duration;
 
 use axum::{
     Json,
-    http::StatusCode,
+    http::{HeaderValue, StatusCode, header::RETRY_AFTER},
     response::{IntoResponse, Response},
 };
+use notebook_store::StoreError;
 use serde::Serialize;
+use uuid::Uuid;
+
+use crate::middleware::request_id::current_request_id;
+
+/// Stable machine-readable error codes.
+///
+/// Codes are part of the API contract: new codes may be added, but existing
+/// codes keep their name and HTTP status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    /// Malformed or invalid request (400).
+    BadRequest,
+    /// A referenced entry does not exist or is not allowed (400).
+    InvalidReference,
+    /// The revision target does not exist (400).
+    InvalidRevision,
+    /// A signature or key has the wrong shape (400).
+    InvalidSignature,
+    /// Missing or invalid credentials (401).
+    Unauthorized,
+    /// The bearer token could not be validated (401).
+    InvalidToken,
+    /// The caller lacks permission (403).
+    Forbidden,
+    /// The token lacks a required scope (403).
+    MissingScope,
+    /// Access to the notebook was denied (403).
+    PermissionDenied,
+    /// Resource not found (404).
+    NotFound,
+    /// The notebook does not exist (404).
+    NotebookNotFound,
+    /// The entry does not exist (404).
+    EntryNotFound,
+    /// The author does not exist (404).
+    AuthorNotFound,
+    /// The request was not completed in time (408).
+    RequestTimeout,
+    /// Conflict with the current resource state (409).
+    Conflict,
+    /// The notebook is locked against writes (409).
+    NotebookLocked,
+    /// The entry has a newer revision than the one the client read (409).
+    RevisionConflict,
+    /// The notebook has entries past the sequence the write expected (409).
+    SequenceConflict,
+    /// An entry with this ID already exists (409).
+    DuplicateEntry,
+    /// The owner already has a notebook with this name (409).
+    DuplicateNotebookName,
+    /// The entry has been deleted (410).
+    EntryDeleted,
+    /// The request body exceeds the configured limit (413).
+    PayloadTooLarge,
+    /// The content is not valid for its declared content type (400).
+    InvalidContent,
+    /// The content type is not accepted by the content-type policy (415).
+    UnsupportedContentType,
+    /// The entry is an orphan and the notebook rejects orphans (422).
+    OrphanRejected,
+    /// The author exceeded the notebook's write budget and must wait (429).
+    WriteThrottled,
+    /// An anonymous client exceeded its read rate and must wait (429).
+    ReadThrottled,
+    /// The request headers exceed the configured limit (431).
+    HeadersTooLarge,
+    /// Unexpected server failure (500).
+    InternalError,
+    /// Storage layer failure (500).
+    StorageError,
+    /// Not implemented (501).
+    NotImplemented,
+    /// Graph queries are unavailable on this deployment (501).
+    GraphUnavailable,
+    /// A database statement ran past the statement timeout (504).
+    DatabaseTimeout,
+}
+
+impl ErrorCode {
+    /// The HTTP status for this code.
+    pub fn status_code(self) -> StatusCode {
+        match self {
+            Self::BadRequest
+            | Self::InvalidReference
+            | Self::InvalidRevision
+            | Self::InvalidSignature
+            | Self::InvalidContent => StatusCode::BAD_REQUEST,
+            Self::Unauthorized | Self::InvalidToken => StatusCode::UNAUTHORIZED,
+            Self::Forbidden | Self::MissingScope | Self::PermissionDenied => StatusCode::FORBIDDEN,
+            Self::NotFound
+            | Self::NotebookNotFound
+            | Self::EntryNotFound
+            | Self::AuthorNotFound => StatusCode::NOT_FOUND,
+            Self::RequestTimeout => StatusCode::REQUEST_TIMEOUT,
+            Self::Conflict
+            | Self::NotebookLocked
+            | Self::RevisionConflict
+            | Self::SequenceConflict
+            | Self::DuplicateEntry
+            | Self::DuplicateNotebookName => StatusCode::CONFLICT,
+            Self::EntryDeleted => StatusCode::GONE,
+            Self::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::UnsupportedContentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::OrphanRejected => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::WriteThrottled | Self::ReadThrottled => StatusCode::TOO_MANY_REQUESTS,
+            Self::HeadersTooLarge => StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
+            Self::InternalError | Self::StorageError => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::NotImplemented | Self::GraphUnavailable => StatusCode::NOT_IMPLEMENTED,
+            Self::DatabaseTimeout => StatusCode::GATEWAY_TIMEOUT,
+        }
+    }
+
+    /// The coarse category reported in the `code` field.
+    fn category(self) -> &'static str {
+        match self.status_code() {
+            StatusCode::BAD_REQUEST => "BAD_REQUEST",
+            StatusCode::UNAUTHORIZED => "UNAUTHORIZED",
+            StatusCode::FORBIDDEN => "FORBIDDEN",
+            StatusCode::NOT_FOUND => "NOT_FOUND",
+            StatusCode::REQUEST_TIMEOUT => "REQUEST_TIMEOUT",
+            StatusCode::CONFLICT => "CONFLICT",
+            StatusCode::GONE => "GONE",
+            StatusCode::PAYLOAD_TOO_LARGE => "PAYLOAD_TOO_LARGE",
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => "UNSUPPORTED_MEDIA_TYPE",
+            StatusCode::UNPROCESSABLE_ENTITY => "UNPROCESSABLE_ENTITY",
+            StatusCode::TOO_MANY_REQUESTS => "TOO_MANY_REQUESTS",
+            StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE => "HEADERS_TOO_LARGE",
+            StatusCode::NOT_IMPLEMENTED => "NOT_IMPLEMENTED",
+            StatusCode::GATEWAY_TIMEOUT => "GATEWAY_TIMEOUT",
+            _ => "INTERNAL_ERROR",
+        }
+    }
+
+    /// Lowercase prefix used in the human-readable message.
+    fn label(self) -> &'static str {
+        match self.status_code() {
+            StatusCode::BAD_REQUEST => "bad request",
+            StatusCode::UNAUTHORIZED => "unauthorized",
+            StatusCode::FORBIDDEN => "forbidden",
+            StatusCode::NOT_FOUND => "not found",
+            StatusCode::REQUEST_TIMEOUT => "request timeout",
+            StatusCode::CONFLICT => "conflict",
+            StatusCode::GONE => "gone",
+            StatusCode::PAYLOAD_TOO_LARGE => "payload too large",
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => "unsupported media type",
+            StatusCode::UNPROCESSABLE_ENTITY => "unprocessable entity",
+            StatusCode::TOO_MANY_REQUESTS => "too many requests",
+            StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE => "headers too large",
+            StatusCode::NOT_IMPLEMENTED => "not implemented",
+            StatusCode::GATEWAY_TIMEOUT => "gateway timeout",
+            _ => "internal error",
+        }
+    }
+}
 
 /// API error that can be returned from handlers.
 #[derive(Debug, thiserror::Error)]
@@ -30,50 +207,123 @@ pub enum ApiError {
     #[error("forbidden: {0}")]
     Forbidden(String),
 
+    /// Conflict with the current resource state (409).
+    #[error("conflict: {0}")]
+    Conflict(String),
+
     /// Internal server error (500).
     #[error("internal error: {0}")]
     Internal(String),
 
+    /// Error with a specific code; the status follows from the code.
+    #[error("{}: {}", .0.label(), .1)]
+    Coded(ErrorCode, String),
+
+    /// Write rejected until `retry_after` has passed (429). The response
+    /// carries a `Retry-After` header.
+    #[error("too many requests: {message}")]
+    Throttled {
+        message: String,
+        retry_after: Duration,
+    },
+
+    /// Anonymous read rejected until `retry_after` has passed (429). The
+    /// response carries a `Retry-After` header.
+    #[error("too many requests: {message}")]
+    ReadThrottled {
+        message: String,
+        retry_after: Duration,
+    },
+
+    /// The owner already has a notebook named `name` (409). The response
+    /// names the existing notebook.
+    #[error(
+        "conflict: Notebook {existing_id} is already named {name:?}; use it, or choose another name"
+    )]
+    DuplicateNotebookName { name: String, existing_id: Uuid },
+
     /// Store error.
     #[error("storage error: {0}")]
-    Store(#[from] notebook_store::StoreError),
+    Store(#[from] StoreError),
 }
 
 impl ApiError {
-    /// Get the error code string for this error.
+    /// The notebook does not exist.
+    pub fn notebook_not_found(id: impl std::fmt::Display) -> Self {
+        Self::Coded(
+            ErrorCode::NotebookNotFound,
+            format!("Notebook {} not found", id),
+        )
+    }
+
+    /// The entry does not exist.
+    pub fn entry_not_found(id: impl std::fmt::Display) -> Self {
+        Self::Coded(ErrorCode::EntryNotFound, format!("Entry {} not found", id))
+    }
+
+    /// Get the coarse error category for this error.
     pub fn code(&self) -> &'static str {
         match self {
-            Self::NotImplemented(_) => "NOT_IMPLEMENTED",
-            Self::BadRequest(_) => "BAD_REQUEST",
-            Self::NotFound(_) => "NOT_FOUND",
-            Self::Unauthorized(_) => "UNAUTHORIZED",
-            Self::Forbidden(_) => "FORBIDDEN",
-            Self::Internal(_) => "INTERNAL_ERROR",
+            Self::Store(StoreError::GraphUnavailable(_)) => "NOT_IMPLEMENTED",
+            Self::Store(StoreError::Timeout(_)) => "GATEWAY_TIMEOUT",
             Self::Store(_) => "STORAGE_ERROR",
+            other => other.error_code().category(),
         }
     }
 
-    /// Get the HTTP status code for this error.
-    pub fn status_code(&self) -> StatusCode {
+    /// Get the stable machine-readable code for this error.
+    pub fn error_code(&self) -> ErrorCode {
         match self {
-            Self::NotImplemented(_) => StatusCode::NOT_IMPLEMENTED,
-            Self::BadRequest(_) => StatusCode::BAD_REQUEST,
-            Self::NotFound(_) => StatusCode::NOT_FOUND,
-            Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-            Self::Forbidden(_) => StatusCode::FORBIDDEN,
-            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::NotImplemented(_) => ErrorCode::NotImplemented,
+            Self::BadRequest(_) => ErrorCode::BadRequest,
+            Self::NotFound(_) => ErrorCode::NotFound,
+            Self::Unauthorized(_) => ErrorCode::Unauthorized,
+            Self::Forbidden(_) => ErrorCode::Forbidden,
+            Self::Conflict(_) => ErrorCode::Conflict,
+            Self::Internal(_) => ErrorCode::InternalError,
+            Self::Coded(code, _) => *code,
+            Self::Throttled { .. } => ErrorCode::WriteThrottled,
+            Self::ReadThrottled { .. } => ErrorCode::ReadThrottled,
+            Self::DuplicateNotebookName { .. } => ErrorCode::DuplicateNotebookName,
             Self::Store(e) => match e {
-                notebook_store::StoreError::EntryNotFound(_) => StatusCode::NOT_FOUND,
-                notebook_store::StoreError::NotebookNotFound(_) => StatusCode::NOT_FOUND,
-                notebook_store::StoreError::AuthorNotFound(_) => StatusCode::NOT_FOUND,
-                notebook_store::StoreError::PermissionDenied { .. } => StatusCode::FORBIDDEN,
-                notebook_store::StoreError::InvalidReference(_) => StatusCode::BAD_REQUEST,
-                notebook_store::StoreError::InvalidRevision(_) => StatusCode::BAD_REQUEST,
-                notebook_store::StoreError::DuplicateEntry(_) => StatusCode::CONFLICT,
-                _ => StatusCode::INTERNAL_SERVER_ERROR,
+                StoreError::EntryNotFound(_) => ErrorCode::EntryNotFound,
+                StoreError::NotebookNotFound(_) => ErrorCode::NotebookNotFound,
+                StoreError::AuthorNotFound(_) => ErrorCode::AuthorNotFound,
+                StoreError::PermissionDenied { .. } => ErrorCode::PermissionDenied,
+                StoreError::InvalidReference(_) => ErrorCode::InvalidReference,
+                StoreError::InvalidRevision(_) => ErrorCode::InvalidRevision,
+                StoreError::DuplicateEntry(_) => ErrorCode::DuplicateEntry,
+                StoreError::RevisionConflict { .. } => ErrorCode::RevisionConflict,
+                StoreError::SequenceConflict { .. } => ErrorCode::SequenceConflict,
+                StoreError::DuplicateNotebookName { .. } => ErrorCode::DuplicateNotebookName,
+                StoreError::EntryDeleted(_) => ErrorCode::EntryDeleted,
+                StoreError::NotebookLocked(_) => ErrorCode::NotebookLocked,
+                StoreError::InvalidSignatureLength(_) | StoreError::InvalidPublicKeyLength(_) => {
+                    ErrorCode::InvalidSignature
+                }
+                StoreError::GraphUnavailable(_) => ErrorCode::GraphUnavailable,
+                StoreError::Timeout(_) => ErrorCode::DatabaseTimeout,
+                _ => ErrorCode::StorageError,
             },
         }
     }
+
+    /// The notebook that already has the requested name, if this error is
+    /// a name clash.
+    pub fn existing_notebook_id(&self) -> Option<Uuid> {
+        match self {
+            Self::DuplicateNotebookName { existing_id, .. }
+            | Self::Store(StoreError::DuplicateNotebookName { existing_id, .. }) => {
+                Some(*existing_id)
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the HTTP status code for this error.
+    pub fn status_code(&self) -> StatusCode {
+        self.error_code().status_code()
+    }
 }
 
 /// JSON error response body.
@@ -86,25 +336,300 @@ pub struct ErrorResponse {
 /// Error details within the response.
 #[derive(Debug, Serialize)]
 pub struct ErrorDetails {
-    /// Error code (e.g., "NOT_FOUND", "BAD_REQUEST").
+    /// Error category (e.g., "NOT_FOUND", "BAD_REQUEST").
     pub code: String,
+    /// Stable machine-readable code (e.g., "NOTEBOOK_NOT_FOUND").
+    pub error_code: ErrorCode,
     /// Human-readable error message.
     pub message: String,
+    /// ID of the request that failed, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// The notebook that already has the requested name, for
+    /// `DUPLICATE_NOTEBOOK_NAME`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub existing_notebook_id: Option<Uuid>,
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let status = self.status_code();
+        let retry_after = match &self {
+            Self::Throttled { retry_after, .. } | Self::ReadThrottled { retry_after, .. } => {
+                Some(retry_after_secs(*retry_after))
+            }
+            _ => None,
+        };
         let body = ErrorResponse {
             error: ErrorDetails {
                 code: self.code().to_string(),
+                error_code: self.error_code(),
                 message: self.to_string(),
+                request_id: current_request_id(),
+                existing_notebook_id: self.existing_notebook_id(),
             },
         };
 
-        (status, Json(body)).into_response()
+        let mut response = (status, Json(body)).into_response();
+        if let Some(secs) = retry_after {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(secs));
+        }
+        response
+    }
+}
+
+/// Whole seconds to put in `Retry-After`, rounded up so clients never retry
+/// early.
+fn retry_after_secs(wait: Duration) -> u64 {
+    let secs = wait.as_secs();
+    if wait.subsec_nanos() > 0 {
+        secs + 1
+    } else {
+        secs
     }
+    .max(1)
 }
 
 /// Result type for API handlers.
 pub type ApiResult<T> = Result<T, ApiError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body_of(error: ApiError) -> serde_json::Value {
+        serde_json::to_value(ErrorResponse {
+            error: ErrorDetails {
+                code: error.code().to_string(),
+                error_code: error.error_code(),
+                message: error.to_string(),
+                request_id: None,
+                existing_notebook_id: error.existing_notebook_id(),
+            },
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_variants_have_stable_codes_and_statuses() {
+        let id = Uuid::nil();
+        let cases = [
+            (ApiError::BadRequest("x".into()), "BAD_REQUEST", 400),
+            (ApiError::Unauthorized("x".into()), "UNAUTHORIZED", 401),
+            (ApiError::Forbidden("x".into()), "FORBIDDEN", 403),
+            (ApiError::NotFound("x".into()), "NOT_FOUND", 404),
+            (ApiError::Conflict("x".into()), "CONFLICT", 409),
+            (ApiError::Internal("x".into()), "INTERNAL_ERROR", 500),
+            (ApiError::NotImplemented("x".into()), "NOT_IMPLEMENTED", 501),
+            (ApiError::notebook_not_found(id), "NOTEBOOK_NOT_FOUND", 404),
+            (ApiError::entry_not_found(id), "ENTRY_NOT_FOUND", 404),
+            (
+                ApiError::Coded(ErrorCode::NotebookLocked, "x".into()),
+                "NOTEBOOK_LOCKED",
+                409,
+            ),
+            (
+                ApiError::Coded(ErrorCode::SequenceConflict, "x".into()),
+                "SEQUENCE_CONFLICT",
+                409,
+            ),
+            (
+                ApiError::Coded(ErrorCode::InvalidContent, "x".into()),
+                "INVALID_CONTENT",
+                400,
+            ),
+            (
+                ApiError::Coded(ErrorCode::UnsupportedContentType, "x".into()),
+                "UNSUPPORTED_CONTENT_TYPE",
+                415,
+            ),
+            (
+                ApiError::Coded(ErrorCode::OrphanRejected, "x".into()),
+                "ORPHAN_REJECTED",
+                422,
+            ),
+            (
+                ApiError::Throttled {
+                    message: "x".into(),
+                    retry_after: Duration::from_secs(1),
+                },
+                "WRITE_THROTTLED",
+                429,
+            ),
+            (
+                ApiError::ReadThrottled {
+                    message: "x".into(),
+                    retry_after: Duration::from_secs(1),
+                },
+                "READ_THROTTLED",
+                429,
+            ),
+            (
+                ApiError::Store(StoreError::NotebookNotFound(id)),
+                "NOTEBOOK_NOT_FOUND",
+                404,
+            ),
+            (
+                ApiError::Store(StoreError::EntryNotFound(id)),
+                "ENTRY_NOT_FOUND",
+                404,
+            ),
+            (
+                ApiError::Store(StoreError::AuthorNotFound(id)),
+                "AUTHOR_NOT_FOUND",
+                404,
+            ),
+            (
+                ApiError::Store(StoreError::InvalidReference(id)),
+                "INVALID_REFERENCE",
+                400,
+            ),
+            (
+                ApiError::Store(StoreError::InvalidRevision(id)),
+                "INVALID_REVISION",
+                400,
+            ),
+            (
+                ApiError::Store(StoreError::DuplicateEntry(id)),
+                "DUPLICATE_ENTRY",
+                409,
+            ),
+            (
+                ApiError::Store(StoreError::RevisionConflict {
+                    entry_id: id,
+                    latest: id,
+                }),
+                "REVISION_CONFLICT",
+                409,
+            ),
+            (
+                ApiError::Store(StoreError::SequenceConflict {
+                    notebook_id: id,
+                    current: 5,
+                    expected: 4,
+                }),
+                "SEQUENCE_CONFLICT",
+                409,
+            ),
+            (
+                ApiError::DuplicateNotebookName {
+                    name: "x".into(),
+                    existing_id: id,
+                },
+                "DUPLICATE_NOTEBOOK_NAME",
+                409,
+            ),
+            (
+                ApiError::Store(StoreError::DuplicateNotebookName {
+                    name: "x".into(),
+                    existing_id: id,
+                }),
+                "DUPLICATE_NOTEBOOK_NAME",
+                409,
+            ),
+            (
+                ApiError::Store(StoreError::EntryDeleted(id)),
+                "ENTRY_DELETED",
+                410,
+            ),
+            (
+                ApiError::Store(StoreError::NotebookLocked(id)),
+                "NOTEBOOK_LOCKED",
+                409,
+            ),
+            (
+                ApiError::Store(StoreError::InvalidSignatureLength(12)),
+                "INVALID_SIGNATURE",
+                400,
+            ),
+            (
+                ApiError::Store(StoreError::GraphUnavailable("x".into())),
+                "GRAPH_UNAVAILABLE",
+                501,
+            ),
+            (
+                ApiError::Store(StoreError::Timeout("x".into())),
+                "DATABASE_TIMEOUT",
+                504,
+            ),
+            (
+                ApiError::Store(StoreError::ConfigError("x".into())),
+                "STORAGE_ERROR",
+                500,
+            ),
+        ];
+
+        for (error, code, status) in cases {
+            assert_eq!(error.status_code().as_u16(), status, "{}", code);
+            assert_eq!(body_of(error)["error"]["error_code"], code);
+        }
+    }
+
+    #[test]
+    fn test_category_code_is_preserved() {
+        let body = body_of(ApiError::notebook_not_found(Uuid::nil()));
+        assert_eq!(body["error"]["code"], "NOT_FOUND");
+        assert_eq!(
+            body["error"]["message"],
+            format!("not found: Notebook {} not found", Uuid::nil())
+        );
+
+        let body = body_of(ApiError::Store(StoreError::GraphUnavailable("x".into())));
+        assert_eq!(body["error"]["code"], "NOT_IMPLEMENTED");
+        let body = body_of(ApiError::Store(StoreError::Timeout("x".into())));
+        assert_eq!(body["error"]["code"], "GATEWAY_TIMEOUT");
+        let body = body_of(ApiError::Store(StoreError::NotebookNotFound(Uuid::nil())));
+        assert_eq!(body["error"]["code"], "STORAGE_ERROR");
+        assert_eq!(body["error"]["error_code"], "NOTEBOOK_NOT_FOUND");
+    }
+
+    #[test]
+    fn test_throttled_sets_retry_after() {
+        let response = ApiError::Throttled {
+            message: "x".into(),
+            retry_after: Duration::from_millis(2500),
+        }
+        .into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RETRY_AFTER], "3");
+
+        let response = ApiError::ReadThrottled {
+            message: "x".into(),
+            retry_after: Duration::from_secs(30),
+        }
+        .into_response();
+        assert_eq!(response.headers()[RETRY_AFTER], "30");
+
+        let response = ApiError::BadRequest("x".into()).into_response();
+        assert!(response.headers().get(RETRY_AFTER).is_none());
+        assert_eq!(retry_after_secs(Duration::ZERO), 1);
+    }
+
+    #[test]
+    fn test_request_id_omitted_when_unknown() {
+        let body = body_of(ApiError::BadRequest("x".into()));
+        assert!(body["error"].get("request_id").is_none());
+    }
+
+    #[test]
+    fn test_duplicate_notebook_name_names_existing_notebook() {
+        let existing_id = Uuid::new_v4();
+        let body = body_of(ApiError::DuplicateNotebookName {
+            name: "Research".into(),
+            existing_id,
+        });
+        assert_eq!(body["error"]["code"], "CONFLICT");
+        assert_eq!(
+            body["error"]["existing_notebook_id"],
+            existing_id.to_string()
+        );
+        let message = body["error"]["message"].as_str().unwrap();
+        assert!(message.contains(&existing_id.to_string()));
+        assert!(message.contains("\"Research\""));
+
+        let body = body_of(ApiError::BadRequest("x".into()));
+        assert!(body["error"].get("existing_notebook_id").is_none());
+    }
+}
@@ -1,32 +1,52 @@
 //! Event broadcasting for real-time notifications.
 //!
 //! This module provides a pub/sub mechanism for broadcasting notebook events
-//! to connected SSE clients. Events are published when entries are created
-//! or revised, allowing clients to receive real-time updates.
+//! to connected SSE and WebSocket clients. Events are published when entries
+//! are created or revised, allowing clients to receive real-time updates.
 //!
 //! # Architecture
 //!
 //! - Uses `tokio::sync::broadcast` for multi-subscriber pub/sub
 //! - One channel per notebook (created lazily on first subscription)
 //! - Channels are cleaned up when all subscribers disconnect
+//! - Notebook changes are read back from the durable `notebook_events` log
+//!   and published in `seq` order, so every logged event carries an
+//!   `event_seq` that subscribers can resume from
 //!
 //! # Event Types
 //!
 //! - `entry`: Published on WRITE/REVISE operations
+//! - `notebook_renamed`: Published when a notebook is renamed
+//! - `notebook_locked`: Published when a notebook is locked or unlocked
+//! - `notebook_visibility`: Published when a notebook is made public or private
+//! - `notebook_orphan_policy`: Published when a notebook's orphan policy changes
+//! - `access_granted` / `access_revoked`: Published when sharing changes
+//! - `topic_renamed`: Published when a topic is renamed across entries
+//! - `entry_pinned` / `entry_unpinned`: Published when an entry is pinned or
+//!   unpinned
+//! - `content_policy_set` / `content_policy_removed`: Published when the
+//!   content-type policy changes
+//! - `write_budget_set` / `write_budget_removed`: Published when the write
+//!   budget changes
+//! - `alert_set` / `alert_removed`: Published when the entropy alert changes
 //! - `heartbeat`: Sent periodically to keep connections alive
-//! - `catchup`: Sent when a subscriber falls behind
+//! - `lagged`: Sent to an SSE subscriber that fell behind, with where to
+//!   resume from
+//! - `catchup`: Sent to a WebSocket subscriber that fell behind
 //!
 //! Owned by: agent-events
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use chrono::{DateTime, Utc};
-use serde::Serialize;
-use tokio::sync::{RwLock, broadcast};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock, broadcast};
 use uuid::Uuid;
 
 use notebook_core::IntegrationCost;
+use notebook_store::{IntegrationCostJson, NotebookEventRow, Store, StoreResult, event_type};
 
 /// Default channel capacity for broadcast channels.
 pub const DEFAULT_CHANNEL_CAPACITY: usize = 256;
@@ -34,6 +54,10 @@ pub const DEFAULT_CHANNEL_CAPACITY: usize = 256;
 /// Heartbeat interval in seconds.
 pub const HEARTBEAT_INTERVAL_SECS: u64 = 30;
 
+/// Maximum number of change log events read per query when publishing or
+/// replaying.
+pub const LOG_PAGE_SIZE: i64 = 500;
+
 // ============================================================================
 // Event Types
 // ============================================================================
@@ -44,10 +68,42 @@ pub const HEARTBEAT_INTERVAL_SECS: u64 = 30;
 pub enum NotebookEvent {
     /// An entry was created or revised.
     Entry(EntryEvent),
+    /// The notebook was renamed.
+    NotebookRenamed(NotebookRenamedEvent),
+    /// The notebook was locked or unlocked.
+    NotebookLocked(NotebookLockedEvent),
+    /// The notebook was made public or private.
+    NotebookVisibility(NotebookVisibilityEvent),
+    /// The notebook's orphan policy changed.
+    NotebookOrphanPolicy(NotebookOrphanPolicyEvent),
+    /// An author was granted access, or had their permissions changed.
+    AccessGranted(AccessGrantedEvent),
+    /// An author's access was revoked.
+    AccessRevoked(AccessRevokedEvent),
+    /// A topic was renamed across the notebook's entries.
+    TopicRenamed(TopicRenamedEvent),
+    /// An entry was pinned.
+    EntryPinned(EntryPinEvent),
+    /// An entry was unpinned.
+    EntryUnpinned(EntryPinEvent),
+    /// The notebook's content-type policy was set or replaced.
+    ContentPolicySet(ContentPolicySetEvent),
+    /// The notebook's content-type policy was removed.
+    ContentPolicyRemoved(SettingRemovedEvent),
+    /// The notebook's write budget was set or replaced.
+    WriteBudgetSet(WriteBudgetSetEvent),
+    /// The notebook's write budget was removed.
+    WriteBudgetRemoved(SettingRemovedEvent),
+    /// The notebook's entropy alert was set or replaced.
+    AlertSet(AlertSetEvent),
+    /// The notebook's entropy alert was removed.
+    AlertRemoved(SettingRemovedEvent),
     /// Periodic heartbeat to keep connection alive.
     Heartbeat(HeartbeatEvent),
     /// Client fell behind and should sync via OBSERVE.
     Catchup(CatchupEvent),
+    /// Client fell behind; missed events follow if the log could be read.
+    Lagged(LaggedEvent),
 }
 
 /// Event data for entry creation/revision.
@@ -57,10 +113,169 @@ pub struct EntryEvent {
     pub entry_id: Uuid,
     /// Operation type: "write" or "revise".
     pub operation: String,
+    /// Topic of the entry, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
     /// Integration cost of the entry.
     pub integration_cost: IntegrationCost,
     /// The sequence number of the entry.
     pub sequence: u64,
+    /// Position of the event in the notebook's change log.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_seq: Option<u64>,
+    /// Timestamp of the event.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Event data for a notebook rename.
+#[derive(Debug, Clone, Serialize)]
+pub struct NotebookRenamedEvent {
+    /// The notebook ID.
+    pub notebook_id: Uuid,
+    /// The new notebook name.
+    pub name: String,
+    /// Position of the event in the notebook's change log.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_seq: Option<u64>,
+    /// Timestamp of the event.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Event data for a notebook lock change.
+#[derive(Debug, Clone, Serialize)]
+pub struct NotebookLockedEvent {
+    /// The notebook ID.
+    pub notebook_id: Uuid,
+    /// Whether the notebook is now locked.
+    pub locked: bool,
+    /// Position of the event in the notebook's change log.
+    pub event_seq: u64,
+    /// Timestamp of the event.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Event data for a notebook visibility change.
+#[derive(Debug, Clone, Serialize)]
+pub struct NotebookVisibilityEvent {
+    /// The notebook ID.
+    pub notebook_id: Uuid,
+    /// Whether the notebook can now be read without authentication.
+    pub public: bool,
+    /// Position of the event in the notebook's change log.
+    pub event_seq: u64,
+    /// Timestamp of the event.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Event data for a notebook orphan policy change.
+#[derive(Debug, Clone, Serialize)]
+pub struct NotebookOrphanPolicyEvent {
+    /// The notebook ID.
+    pub notebook_id: Uuid,
+    /// The new policy: `allow`, `warn` or `reject`.
+    pub orphan_policy: String,
+    /// Position of the event in the notebook's change log.
+    pub event_seq: u64,
+    /// Timestamp of the event.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Event data for an access grant.
+#[derive(Debug, Clone, Serialize)]
+pub struct AccessGrantedEvent {
+    /// The author granted access (hex-encoded).
+    pub author_id: String,
+    /// Whether the author can read entries.
+    pub read: bool,
+    /// Whether the author can write entries.
+    pub write: bool,
+    /// Position of the event in the notebook's change log.
+    pub event_seq: u64,
+    /// Timestamp of the event.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Event data for an access revocation.
+#[derive(Debug, Clone, Serialize)]
+pub struct AccessRevokedEvent {
+    /// The author whose access was revoked (hex-encoded).
+    pub author_id: String,
+    /// Position of the event in the notebook's change log.
+    pub event_seq: u64,
+    /// Timestamp of the event.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Event data for a topic rename.
+#[derive(Debug, Clone, Serialize)]
+pub struct TopicRenamedEvent {
+    /// The previous topic.
+    pub from: String,
+    /// The new topic.
+    pub to: String,
+    /// Number of entries whose topic changed.
+    pub entries: u64,
+    /// Position of the event in the notebook's change log.
+    pub event_seq: u64,
+    /// Timestamp of the event.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Event data for an entry being pinned or unpinned.
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryPinEvent {
+    /// The entry ID.
+    pub entry_id: Uuid,
+    /// Position of the event in the notebook's change log.
+    pub event_seq: u64,
+    /// Timestamp of the event.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Event data for a content-type policy being set.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContentPolicySetEvent {
+    /// Content-type patterns an entry must match, if any.
+    pub allowed_content_types: Vec<String>,
+    /// Content-type patterns an entry must not match.
+    pub denied_content_types: Vec<String>,
+    /// Position of the event in the notebook's change log.
+    pub event_seq: u64,
+    /// Timestamp of the event.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Event data for a write budget being set.
+#[derive(Debug, Clone, Serialize)]
+pub struct WriteBudgetSetEvent {
+    /// Summed entry entropy an author may add per window before being slowed.
+    pub entropy_budget: f64,
+    /// Length of the sliding window, in seconds.
+    pub window_secs: i32,
+    /// Position of the event in the notebook's change log.
+    pub event_seq: u64,
+    /// Timestamp of the event.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Event data for an entropy alert being set.
+///
+/// The webhook URL is not included.
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertSetEvent {
+    /// Recent entropy above which the alert fires.
+    pub entropy_threshold: f64,
+    /// Position of the event in the notebook's change log.
+    pub event_seq: u64,
+    /// Timestamp of the event.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Event data for a notebook setting being removed.
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingRemovedEvent {
+    /// Position of the event in the notebook's change log.
+    pub event_seq: u64,
     /// Timestamp of the event.
     pub timestamp: DateTime<Utc>,
 }
@@ -83,10 +298,329 @@ pub struct CatchupEvent {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Lagged event sent when a subscriber's buffer overflowed.
+///
+/// The missed events are replayed from the change log right after this
+/// notice. If the log could not be read, the client should re-observe from
+/// `resume_after`, e.g. by reconnecting with it as `Last-Event-ID`.
+#[derive(Debug, Clone, Serialize)]
+pub struct LaggedEvent {
+    /// Number of events dropped from the subscriber's buffer.
+    pub missed: u64,
+    /// Change log `seq` of the last event delivered before the overflow.
+    pub resume_after: u64,
+    /// Entry sequence of the last entry event delivered.
+    pub current_sequence: u64,
+    /// Timestamp of the lagged event.
+    pub timestamp: DateTime<Utc>,
+}
+
+// ============================================================================
+// Change Log
+// ============================================================================
+
+/// Payload of an `entry` change log event.
+#[derive(Debug, Deserialize)]
+struct EntryPayload {
+    entry_id: Uuid,
+    operation: String,
+    topic: Option<String>,
+    sequence: i64,
+    #[serde(default)]
+    integration_cost: IntegrationCostJson,
+}
+
+/// Payload of a `topic_renamed` change log event.
+#[derive(Debug, Deserialize)]
+struct TopicRenamedPayload {
+    from: String,
+    to: String,
+    entries: u64,
+}
+
+/// Payload of a `content_policy_set` change log event.
+#[derive(Debug, Deserialize)]
+struct ContentPolicyPayload {
+    allowed_content_types: Vec<String>,
+    denied_content_types: Vec<String>,
+}
+
+/// Payload of an `access_granted` change log event.
+#[derive(Debug, Deserialize)]
+struct AccessPayload {
+    author_id: String,
+    #[serde(default)]
+    read: bool,
+    #[serde(default)]
+    write: bool,
+}
+
+impl NotebookEvent {
+    /// Convert a change log row into the event sent to subscribers.
+    ///
+    /// Returns `None` for event types this server doesn't know or payloads
+    /// that don't parse, so a newer log never breaks an older stream.
+    pub fn from_log(row: &NotebookEventRow) -> Option<Self> {
+        let event_seq = row.seq as u64;
+        let timestamp = row.created;
+        let payload = row.payload.clone();
+
+        let event = match row.event_type.as_str() {
+            event_type::ENTRY => {
+                let p: EntryPayload = serde_json::from_value(payload).ok()?;
+                NotebookEvent::Entry(EntryEvent {
+                    entry_id: p.entry_id,
+                    operation: p.operation,
+                    topic: p.topic,
+                    integration_cost: p.integration_cost.into(),
+                    sequence: p.sequence as u64,
+                    event_seq: Some(event_seq),
+                    timestamp,
+                })
+            }
+            event_type::NOTEBOOK_RENAMED => NotebookEvent::NotebookRenamed(NotebookRenamedEvent {
+                notebook_id: row.notebook_id,
+                name: payload.get("name")?.as_str()?.to_string(),
+                event_seq: Some(event_seq),
+                timestamp,
+            }),
+            event_type::NOTEBOOK_LOCKED => NotebookEvent::NotebookLocked(NotebookLockedEvent {
+                notebook_id: row.notebook_id,
+                locked: payload.get("locked")?.as_bool()?,
+                event_seq,
+                timestamp,
+            }),
+            event_type::NOTEBOOK_VISIBILITY => {
+                NotebookEvent::NotebookVisibility(NotebookVisibilityEvent {
+                    notebook_id: row.notebook_id,
+                    public: payload.get("public")?.as_bool()?,
+                    event_seq,
+                    timestamp,
+                })
+            }
+            event_type::NOTEBOOK_ORPHAN_POLICY => {
+                NotebookEvent::NotebookOrphanPolicy(NotebookOrphanPolicyEvent {
+                    notebook_id: row.notebook_id,
+                    orphan_policy: payload.get("orphan_policy")?.as_str()?.to_string(),
+                    event_seq,
+                    timestamp,
+                })
+            }
+            event_type::ACCESS_GRANTED => {
+                let p: AccessPayload = serde_json::from_value(payload).ok()?;
+                NotebookEvent::AccessGranted(AccessGrantedEvent {
+                    author_id: p.author_id,
+                    read: p.read,
+                    write: p.write,
+                    event_seq,
+                    timestamp,
+                })
+            }
+            event_type::ACCESS_REVOKED => NotebookEvent::AccessRevoked(AccessRevokedEvent {
+                author_id: payload.get("author_id")?.as_str()?.to_string(),
+                event_seq,
+                timestamp,
+            }),
+            event_type::TOPIC_RENAMED => {
+                let p: TopicRenamedPayload = serde_json::from_value(payload).ok()?;
+                NotebookEvent::TopicRenamed(TopicRenamedEvent {
+                    from: p.from,
+                    to: p.to,
+                    entries: p.entries,
+                    event_seq,
+                    timestamp,
+                })
+            }
+            event_type::ENTRY_PINNED | event_type::ENTRY_UNPINNED => {
+                let pin = EntryPinEvent {
+                    entry_id: payload.get("entry_id")?.as_str()?.parse().ok()?,
+                    event_seq,
+                    timestamp,
+                };
+                if row.event_type == event_type::ENTRY_PINNED {
+                    NotebookEvent::EntryPinned(pin)
+                } else {
+                    NotebookEvent::EntryUnpinned(pin)
+                }
+            }
+            event_type::CONTENT_POLICY_SET => {
+                let p: ContentPolicyPayload = serde_json::from_value(payload).ok()?;
+                NotebookEvent::ContentPolicySet(ContentPolicySetEvent {
+                    allowed_content_types: p.allowed_content_types,
+                    denied_content_types: p.denied_content_types,
+                    event_seq,
+                    timestamp,
+                })
+            }
+            event_type::CONTENT_POLICY_REMOVED => {
+                NotebookEvent::ContentPolicyRemoved(SettingRemovedEvent {
+                    event_seq,
+                    timestamp,
+                })
+            }
+            event_type::WRITE_BUDGET_SET => NotebookEvent::WriteBudgetSet(WriteBudgetSetEvent {
+                entropy_budget: payload.get("entropy_budget")?.as_f64()?,
+                window_secs: payload.get("window_secs")?.as_i64()?.try_into().ok()?,
+                event_seq,
+                timestamp,
+            }),
+            event_type::WRITE_BUDGET_REMOVED => {
+                NotebookEvent::WriteBudgetRemoved(SettingRemovedEvent {
+                    event_seq,
+                    timestamp,
+                })
+            }
+            event_type::ALERT_SET => NotebookEvent::AlertSet(AlertSetEvent {
+                entropy_threshold: payload.get("entropy_threshold")?.as_f64()?,
+                event_seq,
+                timestamp,
+            }),
+            event_type::ALERT_REMOVED => NotebookEvent::AlertRemoved(SettingRemovedEvent {
+                event_seq,
+                timestamp,
+            }),
+            _ => return None,
+        };
+        Some(event)
+    }
+
+    /// The event's position in the change log, if it came from the log.
+    pub fn event_seq(&self) -> Option<u64> {
+        match self {
+            NotebookEvent::Entry(e) => e.event_seq,
+            NotebookEvent::NotebookRenamed(e) => e.event_seq,
+            NotebookEvent::NotebookLocked(e) => Some(e.event_seq),
+            NotebookEvent::NotebookVisibility(e) => Some(e.event_seq),
+            NotebookEvent::NotebookOrphanPolicy(e) => Some(e.event_seq),
+            NotebookEvent::AccessGranted(e) => Some(e.event_seq),
+            NotebookEvent::AccessRevoked(e) => Some(e.event_seq),
+            NotebookEvent::TopicRenamed(e) => Some(e.event_seq),
+            NotebookEvent::EntryPinned(e) | NotebookEvent::EntryUnpinned(e) => Some(e.event_seq),
+            NotebookEvent::ContentPolicySet(e) => Some(e.event_seq),
+            NotebookEvent::ContentPolicyRemoved(e) => Some(e.event_seq),
+            NotebookEvent::WriteBudgetSet(e) => Some(e.event_seq),
+            NotebookEvent::WriteBudgetRemoved(e) => Some(e.event_seq),
+            NotebookEvent::AlertSet(e) => Some(e.event_seq),
+            NotebookEvent::AlertRemoved(e) => Some(e.event_seq),
+            NotebookEvent::Heartbeat(_) | NotebookEvent::Catchup(_) | NotebookEvent::Lagged(_) => {
+                None
+            }
+        }
+    }
+
+    /// The SSE event name for this event (matches the serialized `type`).
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            NotebookEvent::Entry(_) => "entry",
+            NotebookEvent::NotebookRenamed(_) => "notebook_renamed",
+            NotebookEvent::NotebookLocked(_) => "notebook_locked",
+            NotebookEvent::NotebookVisibility(_) => "notebook_visibility",
+            NotebookEvent::NotebookOrphanPolicy(_) => "notebook_orphan_policy",
+            NotebookEvent::AccessGranted(_) => "access_granted",
+            NotebookEvent::AccessRevoked(_) => "access_revoked",
+            NotebookEvent::TopicRenamed(_) => "topic_renamed",
+            NotebookEvent::EntryPinned(_) => "entry_pinned",
+            NotebookEvent::EntryUnpinned(_) => "entry_unpinned",
+            NotebookEvent::ContentPolicySet(_) => "content_policy_set",
+            NotebookEvent::ContentPolicyRemoved(_) => "content_policy_removed",
+            NotebookEvent::WriteBudgetSet(_) => "write_budget_set",
+            NotebookEvent::WriteBudgetRemoved(_) => "write_budget_removed",
+            NotebookEvent::AlertSet(_) => "alert_set",
+            NotebookEvent::AlertRemoved(_) => "alert_removed",
+            NotebookEvent::Heartbeat(_) => "heartbeat",
+            NotebookEvent::Catchup(_) => "catchup",
+            NotebookEvent::Lagged(_) => "lagged",
+        }
+    }
+}
+
+/// Read every change log event after `after_seq`, oldest first.
+///
+/// Also returns the `seq` of the last row read (or `after_seq` if there were
+/// none). Rows of unknown type are skipped but still count as read.
+pub async fn read_log(
+    store: &Store,
+    notebook_id: Uuid,
+    after_seq: u64,
+) -> StoreResult<(Vec<NotebookEvent>, u64)> {
+    let mut events = Vec::new();
+    let mut after = after_seq as i64;
+    loop {
+        let rows = store
+            .events_after(notebook_id, after, LOG_PAGE_SIZE)
+            .await?;
+        let Some(last) = rows.last() else { break };
+        after = last.seq;
+        let page_len = rows.len() as i64;
+        events.extend(rows.iter().filter_map(NotebookEvent::from_log));
+        if page_len < LOG_PAGE_SIZE {
+            break;
+        }
+    }
+    Ok((events, after as u64))
+}
+
+/// What a subscriber should do with an event it received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorStep {
+    /// Send the event.
+    Deliver,
+    /// Already delivered; drop it.
+    Skip,
+    /// Events between the cursor and this one are missing; replay the log
+    /// after `after` instead of sending this event.
+    Gap { after: u64 },
+}
+
+/// Tracks the last change log `seq` a subscriber has been sent.
+///
+/// Feeding every received event through [`step`](Self::step) makes a stream
+/// gapless and duplicate-free: events at or before the cursor are dropped,
+/// and a jump ahead means the log must be replayed first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogCursor {
+    last: u64,
+}
+
+impl LogCursor {
+    /// Start a cursor after the given `seq` (0 for the beginning of the log).
+    pub fn new(last: u64) -> Self {
+        Self { last }
+    }
+
+    /// The last `seq` delivered.
+    pub fn last(&self) -> u64 {
+        self.last
+    }
+
+    /// Mark everything up to `seq` as delivered, e.g. after replaying the log.
+    pub fn advance(&mut self, seq: u64) {
+        self.last = self.last.max(seq);
+    }
+
+    /// Decide what to do with an event, advancing the cursor if it is
+    /// delivered. Events that aren't from the log are always delivered.
+    pub fn step(&mut self, event: &NotebookEvent) -> CursorStep {
+        match event.event_seq() {
+            None => CursorStep::Deliver,
+            Some(seq) if seq <= self.last => CursorStep::Skip,
+            Some(seq) if seq == self.last + 1 => {
+                self.last = seq;
+                CursorStep::Deliver
+            }
+            Some(_) => CursorStep::Gap { after: self.last },
+        }
+    }
+}
+
 // ============================================================================
 // Event Broadcaster
 // ============================================================================
 
+/// Last change log `seq` published for one notebook, or `None` until a
+/// log-following subscriber has joined.
+type PublishCursor = Arc<Mutex<Option<u64>>>;
+
 /// Manages broadcast channels for notebook events.
 ///
 /// Each notebook has its own broadcast channel. Channels are created lazily
@@ -96,8 +630,14 @@ pub struct CatchupEvent {
 pub struct EventBroadcaster {
     /// Map of notebook_id -> broadcast sender.
     channels: Arc<RwLock<HashMap<Uuid, broadcast::Sender<NotebookEvent>>>>,
+    /// Map of notebook_id -> last published change log seq. Each cursor is
+    /// held locked while the log is read and published, so events go out
+    /// in order.
+    cursors: Arc<RwLock<HashMap<Uuid, PublishCursor>>>,
     /// Channel capacity for new channels.
     capacity: usize,
+    /// Set once the broadcaster is closed for shutdown.
+    closed: Arc<AtomicBool>,
 }
 
 impl Default for EventBroadcaster {
@@ -111,7 +651,9 @@ impl EventBroadcaster {
     pub fn new() -> Self {
         Self {
             channels: Arc::new(RwLock::new(HashMap::new())),
+            cursors: Arc::new(RwLock::new(HashMap::new())),
             capacity: DEFAULT_CHANNEL_CAPACITY,
+            closed: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -119,15 +661,23 @@ impl EventBroadcaster {
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             channels: Arc::new(RwLock::new(HashMap::new())),
+            cursors: Arc::new(RwLock::new(HashMap::new())),
             capacity,
+            closed: Arc::new(AtomicBool::new(false)),
         }
     }
 
     /// Subscribe to events for a notebook.
     ///
     /// Creates the channel if it doesn't exist.
-    /// Returns a receiver that can be used to receive events.
+    /// Returns a receiver that can be used to receive events. After
+    /// [`close`](Self::close) the receiver is already closed.
     pub async fn subscribe(&self, notebook_id: Uuid) -> broadcast::Receiver<NotebookEvent> {
+        if self.closed.load(Ordering::SeqCst) {
+            let (_sender, receiver) = broadcast::channel(1);
+            return receiver;
+        }
+
         // First try to get existing channel
         {
             let channels = self.channels.read().await;
@@ -191,19 +741,106 @@ impl EventBroadcaster {
         notebook_id: Uuid,
         entry_id: Uuid,
         operation: &str,
+        topic: Option<String>,
         integration_cost: IntegrationCost,
         sequence: u64,
     ) -> Option<usize> {
         let event = NotebookEvent::Entry(EntryEvent {
             entry_id,
             operation: operation.to_string(),
+            topic,
             integration_cost,
             sequence,
+            event_seq: None,
+            timestamp: Utc::now(),
+        });
+        self.publish(notebook_id, event).await
+    }
+
+    /// Publish a notebook rename event (convenience method).
+    pub async fn publish_rename(&self, notebook_id: Uuid, name: &str) -> Option<usize> {
+        let event = NotebookEvent::NotebookRenamed(NotebookRenamedEvent {
+            notebook_id,
+            name: name.to_string(),
+            event_seq: None,
             timestamp: Utc::now(),
         });
         self.publish(notebook_id, event).await
     }
 
+    /// Get the publish cursor for a notebook, creating it if needed.
+    async fn cursor(&self, notebook_id: Uuid) -> PublishCursor {
+        if let Some(cursor) = self.cursors.read().await.get(&notebook_id) {
+            return cursor.clone();
+        }
+        self.cursors
+            .write()
+            .await
+            .entry(notebook_id)
+            .or_default()
+            .clone()
+    }
+
+    /// Subscribe to a notebook and follow its change log.
+    ///
+    /// Returns the receiver and the change log `seq` it starts after: every
+    /// logged event with a greater `seq` will be published to the receiver
+    /// (unless it lags).
+    pub async fn subscribe_from_log(
+        &self,
+        store: &Store,
+        notebook_id: Uuid,
+    ) -> StoreResult<(broadcast::Receiver<NotebookEvent>, u64)> {
+        let cursor = self.cursor(notebook_id).await;
+        let mut cursor = cursor.lock().await;
+
+        let receiver = self.subscribe(notebook_id).await;
+        let start = match *cursor {
+            Some(seq) => seq,
+            None => {
+                let seq = store.latest_event_seq(notebook_id).await? as u64;
+                *cursor = Some(seq);
+                seq
+            }
+        };
+        Ok((receiver, start))
+    }
+
+    /// Publish change log events that haven't been published yet.
+    ///
+    /// Called after each notebook mutation. Events are read from the log,
+    /// not built by the caller, so subscribers see exactly what was
+    /// committed, in `seq` order. Does nothing until a subscriber has
+    /// joined via [`subscribe_from_log`](Self::subscribe_from_log).
+    ///
+    /// Returns the number of events published. Failing to read the log is
+    /// logged rather than returned: the change is already committed, and
+    /// subscribers pick it up with the next publish or by replaying.
+    pub async fn publish_from_log(&self, store: &Store, notebook_id: Uuid) -> usize {
+        let cursor = self.cursor(notebook_id).await;
+        let mut cursor = cursor.lock().await;
+        let Some(after) = *cursor else {
+            return 0;
+        };
+
+        let (events, last) = match read_log(store, notebook_id, after).await {
+            Ok(read) => read,
+            Err(e) => {
+                tracing::warn!(
+                    notebook_id = %notebook_id,
+                    error = %e,
+                    "Failed to read change log for publishing"
+                );
+                return 0;
+            }
+        };
+        for event in &events {
+            self.publish(notebook_id, event.clone()).await;
+        }
+        *cursor = Some(last);
+        events.len()
+    }
+
     /// Get the number of active channels.
     pub async fn channel_count(&self) -> usize {
         self.channels.read().await.len()
@@ -218,6 +855,20 @@ impl EventBroadcaster {
             .unwrap_or(0)
     }
 
+    /// Close all channels for shutdown.
+    ///
+    /// Dropping the senders ends every subscriber's stream once buffered
+    /// events are delivered, so SSE clients see a clean end of stream instead
+    /// of holding the server's graceful shutdown open.
+    pub async fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        let mut channels = self.channels.write().await;
+        let count = channels.len();
+        channels.clear();
+        self.cursors.write().await.clear();
+        tracing::info!(channels = count, "Closed event channels for shutdown");
+    }
+
     /// Clean up channels with no subscribers.
     ///
     /// This can be called periodically to free up resources.
@@ -234,6 +885,10 @@ impl EventBroadcaster {
             }
             has_receivers
         });
+        self.cursors
+            .write()
+            .await
+            .retain(|id, cursor| channels.contains_key(id) || Arc::strong_count(cursor) > 1);
         before - channels.len()
     }
 }
@@ -245,6 +900,7 @@ impl EventBroadcaster {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tokio::sync::broadcast::error::RecvError;
 
     #[tokio::test]
     async fn test_broadcaster_subscribe() {
@@ -281,6 +937,7 @@ mod tests {
                 notebook_id,
                 Uuid::new_v4(),
                 "write",
+                None,
                 IntegrationCost::zero(),
                 1,
             )
@@ -308,6 +965,7 @@ mod tests {
                 notebook_id,
                 Uuid::new_v4(),
                 "write",
+                None,
                 IntegrationCost::zero(),
                 1,
             )
@@ -337,8 +995,10 @@ mod tests {
         let event = NotebookEvent::Entry(EntryEvent {
             entry_id: Uuid::nil(),
             operation: "write".to_string(),
+            topic: None,
             integration_cost: IntegrationCost::zero(),
             sequence: 42,
+            event_seq: None,
             timestamp: Utc::now(),
         });
 
@@ -346,6 +1006,235 @@ mod tests {
         assert!(json.contains("\"type\":\"entry\""));
         assert!(json.contains("\"operation\":\"write\""));
         assert!(json.contains("\"sequence\":42"));
+        assert!(!json.contains("topic"));
+        assert!(!json.contains("event_seq"));
+    }
+
+    fn log_row(seq: i64, event_type: &str, payload: serde_json::Value) -> NotebookEventRow {
+        NotebookEventRow {
+            notebook_id: Uuid::nil(),
+            seq,
+            event_type: event_type.to_string(),
+            payload,
+            created: Utc::now(),
+        }
+    }
+
+    fn renamed(event_seq: u64) -> NotebookEvent {
+        NotebookEvent::NotebookRenamed(NotebookRenamedEvent {
+            notebook_id: Uuid::nil(),
+            name: "n".to_string(),
+            event_seq: Some(event_seq),
+            timestamp: Utc::now(),
+        })
+    }
+
+    #[test]
+    fn test_from_log_entry() {
+        let entry_id = Uuid::new_v4();
+        let row = log_row(
+            3,
+            event_type::ENTRY,
+            serde_json::json!({
+                "entry_id": entry_id,
+                "operation": "revise",
+                "topic": "rust",
+                "author_id": "ab".repeat(32),
+                "sequence": 9,
+                "integration_cost": {
+                    "entries_revised": 1,
+                    "references_broken": 0,
+                    "catalog_shift": 0.5,
+                    "orphan": false
+                }
+            }),
+        );
+
+        let event = NotebookEvent::from_log(&row).unwrap();
+        assert_eq!(event.event_seq(), Some(3));
+        assert_eq!(event.event_name(), "entry");
+        match event {
+            NotebookEvent::Entry(e) => {
+                assert_eq!(e.entry_id, entry_id);
+                assert_eq!(e.operation, "revise");
+                assert_eq!(e.topic.as_deref(), Some("rust"));
+                assert_eq!(e.sequence, 9);
+                assert_eq!(e.integration_cost.entries_revised, 1);
+            }
+            other => panic!("Expected Entry event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_from_log_notebook_and_access_events() {
+        let cases = [
+            (
+                event_type::NOTEBOOK_RENAMED,
+                serde_json::json!({"name": "New"}),
+                "notebook_renamed",
+            ),
+            (
+                event_type::NOTEBOOK_LOCKED,
+                serde_json::json!({"locked": true}),
+                "notebook_locked",
+            ),
+            (
+                event_type::NOTEBOOK_VISIBILITY,
+                serde_json::json!({"public": true}),
+                "notebook_visibility",
+            ),
+            (
+                event_type::NOTEBOOK_ORPHAN_POLICY,
+                serde_json::json!({"orphan_policy": "warn"}),
+                "notebook_orphan_policy",
+            ),
+            (
+                event_type::ACCESS_GRANTED,
+                serde_json::json!({"author_id": "cd".repeat(32), "read": true, "write": false}),
+                "access_granted",
+            ),
+            (
+                event_type::ACCESS_REVOKED,
+                serde_json::json!({"author_id": "cd".repeat(32)}),
+                "access_revoked",
+            ),
+            (
+                event_type::TOPIC_RENAMED,
+                serde_json::json!({"from": "ml", "to": "machine-learning", "entries": 3}),
+                "topic_renamed",
+            ),
+            (
+                event_type::ENTRY_PINNED,
+                serde_json::json!({"entry_id": Uuid::new_v4()}),
+                "entry_pinned",
+            ),
+            (
+                event_type::ENTRY_UNPINNED,
+                serde_json::json!({"entry_id": Uuid::new_v4()}),
+                "entry_unpinned",
+            ),
+            (
+                event_type::CONTENT_POLICY_SET,
+                serde_json::json!({"allowed_content_types": ["text/*"], "denied_content_types": []}),
+                "content_policy_set",
+            ),
+            (
+                event_type::CONTENT_POLICY_REMOVED,
+                serde_json::json!({}),
+                "content_policy_removed",
+            ),
+            (
+                event_type::WRITE_BUDGET_SET,
+                serde_json::json!({"entropy_budget": 2.5, "window_secs": 60}),
+                "write_budget_set",
+            ),
+            (
+                event_type::WRITE_BUDGET_REMOVED,
+                serde_json::json!({}),
+                "write_budget_removed",
+            ),
+            (
+                event_type::ALERT_SET,
+                serde_json::json!({"entropy_threshold": 0.5}),
+                "alert_set",
+            ),
+            (
+                event_type::ALERT_REMOVED,
+                serde_json::json!({}),
+                "alert_removed",
+            ),
+        ];
+
+        for (seq, (event_type, payload, name)) in cases.into_iter().enumerate() {
+            let event = NotebookEvent::from_log(&log_row(seq as i64 + 1, event_type, payload))
+                .unwrap_or_else(|| panic!("{} should convert", event_type));
+            assert_eq!(event.event_name(), name);
+            assert_eq!(event.event_seq(), Some(seq as u64 + 1));
+
+            let json = serde_json::to_value(&event).unwrap();
+            assert_eq!(json["type"], name);
+            assert_eq!(json["event_seq"], seq + 1);
+        }
+    }
+
+    #[test]
+    fn test_from_log_skips_unknown_or_malformed() {
+        assert!(
+            NotebookEvent::from_log(&log_row(1, "notebook_exploded", serde_json::json!({})))
+                .is_none()
+        );
+        assert!(
+            NotebookEvent::from_log(&log_row(
+                1,
+                event_type::NOTEBOOK_LOCKED,
+                serde_json::json!({})
+            ))
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_log_cursor_delivers_in_order_and_drops_duplicates() {
+        let mut cursor = LogCursor::new(4);
+
+        assert_eq!(cursor.step(&renamed(3)), CursorStep::Skip);
+        assert_eq!(cursor.step(&renamed(4)), CursorStep::Skip);
+        assert_eq!(cursor.step(&renamed(5)), CursorStep::Deliver);
+        assert_eq!(cursor.step(&renamed(5)), CursorStep::Skip);
+        assert_eq!(cursor.step(&renamed(6)), CursorStep::Deliver);
+        assert_eq!(cursor.last(), 6);
+
+        // Events outside the log never move the cursor
+        let heartbeat = NotebookEvent::Heartbeat(HeartbeatEvent {
+            timestamp: Utc::now(),
+        });
+        assert_eq!(cursor.step(&heartbeat), CursorStep::Deliver);
+        assert_eq!(cursor.last(), 6);
+    }
+
+    #[test]
+    fn test_log_cursor_reports_gaps() {
+        let mut cursor = LogCursor::new(2);
+
+        assert_eq!(cursor.step(&renamed(5)), CursorStep::Gap { after: 2 });
+        assert_eq!(cursor.last(), 2);
+
+        // After replaying 3..=5 from the log, the live copy of 5 is a duplicate
+        cursor.advance(5);
+        assert_eq!(cursor.step(&renamed(5)), CursorStep::Skip);
+        assert_eq!(cursor.step(&renamed(6)), CursorStep::Deliver);
+
+        // advance never moves backwards
+        cursor.advance(1);
+        assert_eq!(cursor.last(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_publish_from_log_waits_for_log_subscriber() {
+        // No subscriber has joined via subscribe_from_log, so the log is
+        // never read (the lazy pool has no database behind it)
+        let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let store = Store::from_pool(pool);
+        let broadcaster = EventBroadcaster::new();
+        let notebook_id = Uuid::new_v4();
+        let _receiver = broadcaster.subscribe(notebook_id).await;
+
+        assert_eq!(broadcaster.publish_from_log(&store, notebook_id).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_broadcaster_publish_rename() {
+        let broadcaster = EventBroadcaster::new();
+        let notebook_id = Uuid::new_v4();
+
+        let mut receiver = broadcaster.subscribe(notebook_id).await;
+        let count = broadcaster.publish_rename(notebook_id, "New Name").await;
+        assert_eq!(count, Some(1));
+
+        let event = receiver.recv().await.unwrap();
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"type\":\"notebook_renamed\""));
+        assert!(json.contains("\"name\":\"New Name\""));
     }
 
     #[tokio::test]
@@ -372,4 +1261,37 @@ mod tests {
         assert!(json.contains("\"events_missed\":100"));
         assert!(json.contains("\"current_sequence\":150"));
     }
+
+    #[test]
+    fn test_lagged_event_serialization() {
+        let event = NotebookEvent::Lagged(LaggedEvent {
+            missed: 3,
+            resume_after: 12,
+            current_sequence: 40,
+            timestamp: Utc::now(),
+        });
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "lagged");
+        assert_eq!(json["missed"], 3);
+        assert_eq!(json["resume_after"], 12);
+        assert_eq!(event.event_name(), "lagged");
+        assert_eq!(event.event_seq(), None);
+    }
+
+    #[tokio::test]
+    async fn test_close_ends_subscriber_streams() {
+        let broadcaster = EventBroadcaster::new();
+        let notebook_id = Uuid::new_v4();
+        let mut rx = broadcaster.subscribe(notebook_id).await;
+
+        broadcaster.close().await;
+
+        assert!(matches!(rx.recv().await, Err(RecvError::Closed)));
+        assert_eq!(broadcaster.channel_count().await, 0);
+
+        // Late subscribers get an already-closed receiver
+        let mut late = broadcaster.subscribe(notebook_id).await;
+        assert!(matches!(late.recv().await, Err(RecvError::Closed)));
+    }
 }
@@ -1,11 +1,17 @@
 //! Author identity extraction from JWT Bearer token or X-Author-Id header (dev mode).
 
-use axum::{extract::FromRequestParts, http::request::Parts};
+use std::net::{IpAddr, SocketAddr};
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::request::Parts,
+};
 use jsonwebtoken::{Algorithm, DecodingKey, TokenData, Validation};
 use notebook_core::AuthorId;
 use serde::Deserialize;
 
-use crate::error::ApiError;
+use crate::error::{ApiError, ErrorCode};
+use crate::middleware::AccessLogAuthor;
 use crate::state::AppState;
 
 /// JWT claims structure.
@@ -47,7 +53,7 @@ const ALL_SCOPES: &[&str] = &[
 /// Check that `identity` has the required `scope`.
 ///
 /// If `config.enforce_scopes` is false, this always succeeds.
-/// Otherwise, returns `Forbidden` if the scope is missing.
+/// Otherwise, returns a `MISSING_SCOPE` forbidden error if the scope is missing.
 pub fn require_scope(
     identity: &AuthorIdentity,
     scope: &str,
@@ -59,10 +65,10 @@ pub fn require_scope(
     if identity.scopes.iter().any(|s| s == scope) {
         Ok(())
     } else {
-        Err(ApiError::Forbidden(format!(
-            "Missing required scope: {}",
-            scope
-        )))
+        Err(ApiError::Coded(
+            ErrorCode::MissingScope,
+            format!("Missing required scope: {}", scope),
+        ))
     }
 }
 
@@ -73,28 +79,93 @@ impl FromRequestParts<AppState> for AuthorIdentity {
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
-        let config = state.config();
+        let identity = extract_identity(parts, state.config())?;
 
-        // Try JWT Bearer token first
-        if let Some(auth_header) = parts.headers.get("Authorization") {
-            let auth_str = auth_header.to_str().map_err(|_| {
-                ApiError::Unauthorized("Authorization header contains invalid characters".into())
-            })?;
+        // Let the access log name the author
+        if let Some(slot) = parts.extensions.get::<AccessLogAuthor>() {
+            slot.record(identity.author_id);
+        }
 
-            if let Some(token) = auth_str.strip_prefix("Bearer ") {
-                return extract_from_jwt(token.trim(), config);
-            }
+        Ok(identity)
+    }
+}
+
+/// The caller of a read endpoint: an authenticated author, or an anonymous
+/// client when the request carries no credentials.
+///
+/// Anonymous readers are only admitted to public notebooks; see
+/// [`readable_notebook`](crate::public_reads::readable_notebook). Invalid
+/// credentials are rejected as for [`AuthorIdentity`], and in dev mode every
+/// request has an identity, so no reader is anonymous.
+pub enum ReaderIdentity {
+    /// The request carried valid credentials.
+    Author(AuthorIdentity),
+    /// The request carried no credentials.
+    Anonymous {
+        /// Address of the connecting client, if known.
+        client: Option<IpAddr>,
+    },
+}
+
+impl FromRequestParts<AppState> for ReaderIdentity {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let reader = extract_reader(parts, state.config())?;
+
+        if let ReaderIdentity::Author(identity) = &reader
+            && let Some(slot) = parts.extensions.get::<AccessLogAuthor>()
+        {
+            slot.record(identity.author_id);
         }
 
-        // Fall back to X-Author-Id header (dev mode only)
-        if config.allow_dev_identity {
-            return extract_from_dev_header(parts);
+        Ok(reader)
+    }
+}
+
+/// Authenticate the request if it carries credentials.
+fn extract_reader(
+    parts: &Parts,
+    config: &crate::config::ServerConfig,
+) -> Result<ReaderIdentity, ApiError> {
+    if parts.headers.contains_key("Authorization") || config.allow_dev_identity {
+        return extract_identity(parts, config).map(ReaderIdentity::Author);
+    }
+
+    let client = parts
+        .extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    Ok(ReaderIdentity::Anonymous { client })
+}
+
+/// Authenticate the request from its headers.
+fn extract_identity(
+    parts: &Parts,
+    config: &crate::config::ServerConfig,
+) -> Result<AuthorIdentity, ApiError> {
+    // Try JWT Bearer token first
+    if let Some(auth_header) = parts.headers.get("Authorization") {
+        let auth_str = auth_header.to_str().map_err(|_| {
+            ApiError::Unauthorized("Authorization header contains invalid characters".into())
+        })?;
+
+        if let Some(token) = auth_str.strip_prefix("Bearer ") {
+            return extract_from_jwt(token.trim(), config);
         }
+    }
 
-        Err(ApiError::Unauthorized(
-            "Missing Authorization: Bearer <jwt> header".into(),
-        ))
+    // Fall back to X-Author-Id header (dev mode only)
+    if config.allow_dev_identity {
+        return extract_from_dev_header(parts);
     }
+
+    Err(ApiError::Unauthorized(
+        "Missing Authorization: Bearer <jwt> header".into(),
+    ))
 }
 
 /// Validate JWT and extract AuthorId + scopes from claims.
@@ -121,7 +192,7 @@ fn extract_from_jwt(
     let token_data: TokenData<Claims> =
         jsonwebtoken::decode(token, &key, &validation).map_err(|e| {
             tracing::debug!(error = %e, "JWT validation failed");
-            ApiError::Unauthorized(format!("Invalid token: {}", e))
+            ApiError::Coded(ErrorCode::InvalidToken, format!("Invalid token: {}", e))
         })?;
 
     let author_id = parse_author_id_hex(&token_data.claims.sub)?;
@@ -196,13 +267,10 @@ mod tests {
 
     fn test_config(public_key: &str, allow_dev: bool) -> crate::config::ServerConfig {
         crate::config::ServerConfig {
-            database_url: String::new(),
-            port: 3000,
-            log_level: "info".into(),
-            cors_allowed_origins: "*".into(),
             jwt_public_key: public_key.to_string(),
             allow_dev_identity: allow_dev,
             enforce_scopes: true,
+            ..Default::default()
         }
     }
 
@@ -348,4 +416,57 @@ mod tests {
         let result = extract_from_jwt(&token, &config);
         assert!(result.is_err());
     }
+
+    fn request_parts(authorization: Option<&str>) -> Parts {
+        let mut builder = axum::http::Request::builder().uri("/notebooks");
+        if let Some(value) = authorization {
+            builder = builder.header("Authorization", value);
+        }
+        let mut parts = builder.body(()).unwrap().into_parts().0;
+        parts
+            .extensions
+            .insert(ConnectInfo(SocketAddr::from(([192, 0, 2, 1], 4000))));
+        parts
+    }
+
+    #[test]
+    fn test_reader_without_credentials_is_anonymous() {
+        let config = test_config(TEST_PUBLIC_KEY_PEM, false);
+        let reader = extract_reader(&request_parts(None), &config).unwrap();
+        match reader {
+            ReaderIdentity::Anonymous { client } => {
+                assert_eq!(client, Some(IpAddr::from([192, 0, 2, 1])));
+            }
+            ReaderIdentity::Author(_) => panic!("expected an anonymous reader"),
+        }
+    }
+
+    #[test]
+    fn test_reader_with_token_is_author() {
+        let author_hex = "e".repeat(64);
+        let token = create_test_token(&author_hex);
+        let config = test_config(TEST_PUBLIC_KEY_PEM, false);
+        let parts = request_parts(Some(&format!("Bearer {}", token)));
+        let ReaderIdentity::Author(identity) = extract_reader(&parts, &config).unwrap() else {
+            panic!("expected an authenticated reader");
+        };
+        assert_eq!(
+            identity.author_id,
+            parse_author_id_hex(&author_hex).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_reader_with_invalid_token_is_rejected() {
+        let config = test_config(TEST_PUBLIC_KEY_PEM, false);
+        let parts = request_parts(Some("Bearer not-a-jwt"));
+        assert!(extract_reader(&parts, &config).is_err());
+    }
+
+    #[test]
+    fn test_reader_in_dev_mode_is_author() {
+        let config = test_config("", true);
+        let reader = extract_reader(&request_parts(None), &config).unwrap();
+        assert!(matches!(reader, ReaderIdentity::Author(_)));
+    }
 }
@@ -30,19 +30,34 @@
 //!
 //! Owned by: agent-server
 
+pub mod alerts;
 pub mod config;
+pub mod content_policy;
+pub mod content_validation;
+pub mod engines;
 pub mod error;
 pub mod events;
 pub mod extract;
 pub mod middleware;
+pub mod orphan_policy;
+pub mod pagination;
+pub mod public_reads;
+pub mod readiness;
+pub mod recompute;
+pub mod reindex;
 pub mod routes;
 pub mod state;
+pub mod tasks;
+pub mod telemetry;
+pub mod throttle;
+pub mod warmup;
 
 // Re-exports for convenience
 pub use config::{ConfigError, ServerConfig};
+pub use engines::EngineShards;
 pub use error::{ApiError, ApiResult};
 pub use events::EventBroadcaster;
-pub use extract::AuthorIdentity;
+pub use extract::{AuthorIdentity, ReaderIdentity};
 pub use state::AppState;
 
 // Re-export dependent crates
@@ -1,16 +1,29 @@
 //! Entry point for the notebook-server binary.
 
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::DefaultBodyLimit;
 use axum::middleware;
+use notebook_entropy::{NoOpCostUpdater, PropagationQueue, PropagationWorker, SearchIndex};
 use notebook_server::{
-    config::ServerConfig,
-    middleware::request_id::{propagate_request_id, request_id_layer},
+    config::{LogFormat, ServerConfig},
+    middleware::access_log::access_log,
+    middleware::cors::{CorsPolicy, cors},
+    middleware::limits::{RequestLimits, request_limits},
+    middleware::request_id::{RequestIdPolicy, assign_request_id, propagate_request_id},
+    readiness::check_database,
+    reindex::rebuild_search_index_in_background,
     routes,
     state::AppState,
+    telemetry::{OtlpHandle, OtlpLayer, TraceSampler, log_filter, request_span},
+    warmup::warm_catalog_cache,
 };
 use notebook_store::{Store, StoreConfig};
 use tokio::net::TcpListener;
 use tokio::signal;
-use tower_http::cors::{Any, CorsLayer};
+use tokio::sync::watch;
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -20,32 +33,103 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config = ServerConfig::from_env()?;
 
     // Initialize tracing
-    init_tracing(&config.log_level);
+    let otlp = init_tracing(&config);
 
     tracing::info!("Starting notebook-server");
     tracing::info!(
-        "Configuration: port={}, log_level={}",
+        "Configuration: port={}, log_filter={}, trace_sample_ratio={}",
         config.port,
-        config.log_level
+        config.log_filter_directives(),
+        config.trace_sample_ratio
     );
+    if let Some(endpoint) = &config.otlp_endpoint {
+        tracing::info!(endpoint = %endpoint, "Exporting spans over OTLP");
+    }
 
     // Connect to database
-    let store_config = StoreConfig::from_env()?;
+    let mut store_config = StoreConfig::from_env()?;
+    if std::env::args()
+        .skip(1)
+        .any(|arg| arg == "--check-migrations")
+    {
+        // Dry run: report pending migrations without applying them
+        store_config.run_migrations = false;
+        let store = Store::connect(store_config).await?;
+        return report_pending_migrations(&store).await;
+    }
     let store = Store::connect(store_config).await?;
     tracing::info!("Connected to database");
 
     // Build application state
-    let state = AppState::new(store, config.clone());
+    let mut state = AppState::new(store, config.clone());
+
+    // Open the search index, recreating it if it is unreadable
+    if let Some(path) = &config.search_index_path {
+        let index = SearchIndex::open_or_recreate(std::path::Path::new(path))?
+            .with_commit_policy(config.search_commit_policy());
+        let needs_rebuild = index.is_rebuilding();
+        state = state.with_search_index(index);
+        tracing::info!(path = %path, "Opened search index");
+
+        if needs_rebuild {
+            state
+                .background_tasks()
+                .spawn(rebuild_search_index_in_background(state.clone()));
+        }
+    }
+
+    // Pre-generate browse catalogs without delaying startup
+    if config.catalog_warmup {
+        state
+            .background_tasks()
+            .spawn(warm_catalog_cache(state.clone()));
+    }
+
+    // Run the cost propagation worker. Entries do not store a cumulative
+    // cost yet, so its updates are counted but not persisted
+    let mut propagation_worker = PropagationWorker::new(PropagationQueue::new(), NoOpCostUpdater);
+    let propagation_handle = propagation_worker.start();
+    state = state.with_propagation_monitor(propagation_worker.monitor());
+
+    // Build CORS policy and request limits
+    let cors_policy = Arc::new(CorsPolicy::from_config(&config));
+    let request_limits_config = Arc::new(RequestLimits::from_config(&config));
+    let request_id_policy = Arc::new(RequestIdPolicy::from_config(&config));
 
-    // Build CORS layer
-    let cors = build_cors_layer(&config.cors_allowed_origins);
+    // Keep handles needed after the router takes ownership of the state
+    let broadcaster = state.broadcaster().clone();
+    let background_tasks = state.background_tasks().clone();
+    let search_index = state.search_index().cloned();
+
+    // Commit search index changes that stop short of a full batch
+    if let Some(index) = &search_index {
+        tokio::spawn(flush_search_index_periodically(
+            index.clone(),
+            config.search_commit_policy().max_delay,
+        ));
+    }
+
+    // Hold readiness until the workers above run and the database answers
+    state.readiness().mark_workers_started();
+    tokio::spawn(check_database(state.clone()));
 
     // Build router with middleware
+    // Bodies are already capped by the limits middleware; lift the
+    // extractors' own default cap to match it
     let app = routes::build_router(state)
+        .layer(DefaultBodyLimit::max(config.max_body_bytes))
+        .layer(middleware::from_fn_with_state(
+            request_limits_config,
+            request_limits,
+        ))
+        .layer(middleware::from_fn(access_log))
         .layer(middleware::from_fn(propagate_request_id))
-        .layer(request_id_layer())
-        .layer(cors)
-        .layer(TraceLayer::new_for_http());
+        .layer(middleware::from_fn_with_state(
+            request_id_policy,
+            assign_request_id,
+        ))
+        .layer(middleware::from_fn_with_state(cors_policy, cors))
+        .layer(TraceLayer::new_for_http().make_span_with(request_span));
 
     // Create listener
     let addr = config.socket_addr();
@@ -53,42 +137,139 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     tracing::info!("Listening on {}", addr);
 
     // Run server with graceful shutdown
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    let (signaled_tx, mut signaled_rx) = watch::channel(false);
+    // Peer addresses let anonymous reads be rate-limited per client
+    let app = app.into_make_service_with_connect_info::<SocketAddr>();
+    let server = axum::serve(listener, app).with_graceful_shutdown(async move {
+        shutdown_signal().await;
+        // End SSE streams so open subscriptions don't hold up draining
+        broadcaster.close().await;
+        let _ = signaled_tx.send(true);
+    });
+    let shutdown_timeout = config.shutdown_timeout();
+    let deadline = async move {
+        let _ = signaled_rx.wait_for(|signaled| *signaled).await;
+        tokio::time::sleep(shutdown_timeout).await;
+    };
+
+    tokio::select! {
+        result = server => result?,
+        _ = deadline => {
+            tracing::warn!(
+                timeout_secs = shutdown_timeout.as_secs(),
+                "Timed out draining connections, dropping remaining requests"
+            );
+        }
+    }
+
+    // Let spawned work (e.g. cost backfills) finish within the same bound
+    let pending = background_tasks.len();
+    if pending > 0 {
+        tracing::info!(tasks = pending, "Waiting for background tasks");
+        let aborted = background_tasks.drain(shutdown_timeout).await;
+        if aborted > 0 {
+            tracing::warn!(
+                tasks = aborted,
+                "Aborted background tasks at shutdown deadline"
+            );
+        }
+    }
+
+    // Let the propagation worker drain its queue
+    propagation_worker.shutdown();
+    if tokio::time::timeout(shutdown_timeout, propagation_handle)
+        .await
+        .is_err()
+    {
+        tracing::warn!("Timed out draining the propagation queue");
+    }
+
+    // Commit search index changes still buffered
+    if let Some(index) = search_index
+        && let Err(e) = index.flush()
+    {
+        tracing::error!(error = %e, "Failed to flush search index");
+    }
+
+    // Export spans still queued
+    if let Some(otlp) = otlp
+        && !otlp.flush(shutdown_timeout).await
+    {
+        tracing::warn!("Timed out exporting queued spans");
+    }
 
     tracing::info!("Server shutdown complete");
     Ok(())
 }
 
-/// Initialize the tracing subscriber.
-fn init_tracing(log_level: &str) {
-    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(log_level));
+/// Print the migrations that startup would apply.
+///
+/// Exits with status 1 if any non-optional migration is pending, so deploy
+/// scripts can gate on it.
+async fn report_pending_migrations(store: &Store) -> Result<(), Box<dyn std::error::Error>> {
+    let pending = store.pending_migrations().await?;
+    if pending.is_empty() {
+        println!("No pending migrations");
+        return Ok(());
+    }
+
+    println!("Pending migrations:");
+    for migration in &pending {
+        let note = if migration.optional {
+            " (optional)"
+        } else {
+            ""
+        };
+        println!("  {} - {}{}", migration.name, migration.description, note);
+    }
+    if pending.iter().any(|migration| !migration.optional) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
 
-    tracing_subscriber::registry()
+/// Initialize the tracing subscriber.
+///
+/// `RUST_LOG`, when set, replaces the configured log filter. Returns the
+/// span exporter's handle if `OTLP_ENDPOINT` is set.
+fn init_tracing(config: &ServerConfig) -> Option<OtlpHandle> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        log_filter(config).unwrap_or_else(|_| EnvFilter::new(&config.log_level))
+    });
+    let (otlp_layer, otlp_worker) = match &config.otlp_endpoint {
+        Some(endpoint) => {
+            let (layer, worker) = OtlpLayer::new(endpoint);
+            (Some(layer), Some(worker))
+        }
+        None => (None, None),
+    };
+    let registry = tracing_subscriber::registry()
         .with(filter)
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+        .with(TraceSampler::new(config.trace_sample_ratio))
+        .with(otlp_layer);
+
+    match config.log_format {
+        LogFormat::Pretty => registry.with(tracing_subscriber::fmt::layer()).init(),
+        LogFormat::Json => registry
+            .with(tracing_subscriber::fmt::layer().json().flatten_event(true))
+            .init(),
+    }
+    otlp_worker.map(|worker| worker.spawn())
 }
 
-/// Build CORS layer from configuration.
-fn build_cors_layer(allowed_origins: &str) -> CorsLayer {
-    if allowed_origins == "*" {
-        CorsLayer::new()
-            .allow_origin(Any)
-            .allow_methods(Any)
-            .allow_headers(Any)
-    } else {
-        // Parse comma-separated origins
-        let origins: Vec<_> = allowed_origins
-            .split(',')
-            .map(|s| s.trim().parse().expect("Invalid CORS origin"))
-            .collect();
-
-        CorsLayer::new()
-            .allow_origin(origins)
-            .allow_methods(Any)
-            .allow_headers(Any)
+/// Commit the search index whenever its commit policy calls for it.
+///
+/// Runs for the life of the process.
+async fn flush_search_index_periodically(index: Arc<SearchIndex>, period: Duration) {
+    let mut interval = tokio::time::interval(period);
+    loop {
+        interval.tick().await;
+        let index = index.clone();
+        match tokio::task::spawn_blocking(move || index.flush_if_due()).await {
+            Ok(Err(e)) => tracing::warn!(error = %e, "Failed to commit search index"),
+            Err(e) => tracing::warn!(error = %e, "Search index commit task failed"),
+            Ok(Ok(_)) => {}
+        }
     }
 }
 
@@ -0,0 +1,221 @@
+//! Structured access logging.
+//!
+//! Emits one event per request on completion with the request ID, method,
+//! path, status, latency, author, and notebook. With `LOG_FORMAT=json` each
+//! event is a single JSON line that log pipelines can index directly.
+
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use axum::{extract::Request, middleware::Next, response::Response};
+use notebook_core::AuthorId;
+use uuid::Uuid;
+
+use super::request_id::REQUEST_ID_HEADER;
+
+/// Tracing target for access log events.
+pub const ACCESS_LOG_TARGET: &str = "access_log";
+
+/// Slot for the authenticated author of a request.
+///
+/// The access log middleware inserts it into the request extensions; the
+/// `AuthorIdentity` extractor fills it in once the caller is authenticated.
+#[derive(Debug, Clone, Default)]
+pub struct AccessLogAuthor(Arc<Mutex<Option<AuthorId>>>);
+
+impl AccessLogAuthor {
+    /// Record the authenticated author.
+    pub fn record(&self, author: AuthorId) {
+        if let Ok(mut slot) = self.0.lock() {
+            *slot = Some(author);
+        }
+    }
+
+    /// The recorded author, if any.
+    pub fn get(&self) -> Option<AuthorId> {
+        self.0.lock().ok().and_then(|slot| *slot)
+    }
+}
+
+/// Extract the notebook ID from a `/notebooks/{id}/...` path.
+fn notebook_id_from_path(path: &str) -> Option<Uuid> {
+    let mut segments = path.trim_start_matches('/').split('/');
+    match (segments.next(), segments.next()) {
+        (Some("notebooks"), Some(id)) => Uuid::parse_str(id).ok(),
+        _ => None,
+    }
+}
+
+/// Middleware that logs a structured access record per request.
+///
+/// Must run inside the request ID layer so the ID is already assigned.
+pub async fn access_log(mut request: Request, next: Next) -> Response {
+    let start = Instant::now();
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+    let notebook_id = notebook_id_from_path(&path);
+
+    let author = AccessLogAuthor::default();
+    request.extensions_mut().insert(author.clone());
+
+    let response = next.run(request).await;
+
+    let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+    tracing::info!(
+        target: ACCESS_LOG_TARGET,
+        request_id = request_id.as_deref(),
+        method = %method,
+        path = %path,
+        status = response.status().as_u16(),
+        latency_ms,
+        author = author.get().map(|a| a.to_string()).as_deref(),
+        notebook_id = notebook_id.map(|id| id.to_string()).as_deref(),
+        "request completed"
+    );
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Extension, Router, body::Body, http::StatusCode, middleware::from_fn, routing::post,
+    };
+    use std::io;
+    use tower::ServiceExt;
+    use tracing_subscriber::fmt::MakeWriter;
+
+    /// Collects formatted log output in memory.
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Capture {
+        type Writer = Capture;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn test_notebook_id_from_path() {
+        let id = Uuid::new_v4();
+        assert_eq!(
+            notebook_id_from_path(&format!("/notebooks/{}/entries", id)),
+            Some(id)
+        );
+        assert_eq!(notebook_id_from_path("/notebooks"), None);
+        assert_eq!(notebook_id_from_path("/health"), None);
+    }
+
+    #[tokio::test]
+    async fn test_access_log_emits_structured_fields() {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .flatten_event(true)
+            .with_writer(capture.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let author = AuthorId::from_bytes([7u8; 32]);
+        let app = Router::new()
+            .route(
+                "/notebooks/{id}/entries",
+                post(
+                    move |Extension(slot): Extension<AccessLogAuthor>| async move {
+                        slot.record(author);
+                        StatusCode::CREATED
+                    },
+                ),
+            )
+            .layer(from_fn(access_log));
+
+        let notebook_id = Uuid::new_v4();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/notebooks/{}/entries?limit=1", notebook_id))
+                    .header(REQUEST_ID_HEADER, "req-123")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        let line = output
+            .lines()
+            .find(|line| line.contains(ACCESS_LOG_TARGET))
+            .expect("access log line emitted");
+        let record: serde_json::Value = serde_json::from_str(line).unwrap();
+
+        assert_eq!(record["request_id"], "req-123");
+        assert_eq!(record["method"], "POST");
+        assert_eq!(
+            record["path"],
+            format!("/notebooks/{}/entries", notebook_id)
+        );
+        assert_eq!(record["status"], 201);
+        assert!(record["latency_ms"].as_f64().unwrap() >= 0.0);
+        assert_eq!(record["author"], author.to_string());
+        assert_eq!(record["notebook_id"], notebook_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_unsampled_request_is_still_logged() {
+        use crate::telemetry::{TraceSampler, request_span};
+        use tower_http::trace::TraceLayer;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(TraceSampler::new(0.0))
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_writer(capture.clone()),
+            );
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let app = Router::new()
+            .route("/health", axum::routing::get(|| async { "ok" }))
+            .layer(from_fn(access_log))
+            .layer(TraceLayer::new_for_http().make_span_with(request_span));
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/health")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        assert!(
+            output.lines().any(|line| line.contains(ACCESS_LOG_TARGET)),
+            "{output}"
+        );
+    }
+}
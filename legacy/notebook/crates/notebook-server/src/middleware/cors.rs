@@ -0,0 +1,213 @@
+//! CORS middleware with per-origin method and header allowances.
+//!
+//! `tower_http`'s `CorsLayer` applies one set of allowed methods and headers
+//! to every origin. To let each configured origin carry its own allowances,
+//! this middleware keeps one layer per origin and dispatches on the request's
+//! `Origin` header.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use http::{HeaderValue, header::ORIGIN};
+use tower::{Layer, ServiceExt};
+use tower_http::cors::{AllowHeaders, AllowMethods, Any, CorsLayer};
+
+use crate::config::{ConfigError, CorsOriginRule, ServerConfig, parse_cors_origin};
+
+/// CORS policy built from the server configuration.
+#[derive(Debug, Clone)]
+pub struct CorsPolicy {
+    /// Layer for explicitly configured origins, keyed by origin.
+    per_origin: HashMap<HeaderValue, CorsLayer>,
+    /// Layer for requests from any other origin (or none).
+    fallback: CorsLayer,
+}
+
+impl CorsPolicy {
+    /// Build the policy from configuration.
+    ///
+    /// `ServerConfig::from_env` rejects malformed origins up front; for
+    /// configurations built in code, invalid entries are logged and skipped
+    /// rather than failing the server.
+    pub fn from_config(config: &ServerConfig) -> Self {
+        let credentials = config.cors_allow_credentials;
+
+        if config.cors_allowed_origins.trim() == "*" {
+            if credentials {
+                tracing::warn!("CORS credentials cannot be combined with \"*\"; ignoring");
+            }
+            return Self {
+                per_origin: HashMap::new(),
+                fallback: CorsLayer::new()
+                    .allow_origin(Any)
+                    .allow_methods(Any)
+                    .allow_headers(Any),
+            };
+        }
+
+        let mut per_origin = HashMap::new();
+        for entry in config
+            .cors_allowed_origins
+            .split(',')
+            .filter(|entry| !entry.trim().is_empty())
+        {
+            match parse_cors_origin(entry).and_then(|rule| {
+                let origin =
+                    HeaderValue::from_str(&rule.origin).map_err(|_| ConfigError::InvalidValue {
+                        name: "CORS_ALLOWED_ORIGINS".to_string(),
+                        reason: format!("origin '{}' is not a valid header value", rule.origin),
+                    })?;
+                Ok((origin, rule))
+            }) {
+                Ok((origin, rule)) => {
+                    let layer = origin_layer(origin.clone(), &rule, credentials);
+                    per_origin.insert(origin, layer);
+                }
+                Err(e) => tracing::warn!(error = %e, "Skipping invalid CORS origin"),
+            }
+        }
+
+        // No origin matches the fallback's (empty) list, so it only adds `Vary`
+        let fallback = CorsLayer::new().allow_origin(Vec::<HeaderValue>::new());
+
+        Self {
+            per_origin,
+            fallback,
+        }
+    }
+
+    /// Select the layer for a request's `Origin` header.
+    fn layer_for(&self, origin: Option<&HeaderValue>) -> &CorsLayer {
+        origin
+            .and_then(|origin| self.per_origin.get(origin))
+            .unwrap_or(&self.fallback)
+    }
+}
+
+/// Build the CORS layer for a single configured origin.
+///
+/// Credentialed responses may not use wildcards, so unrestricted methods and
+/// headers mirror the preflight request instead.
+fn origin_layer(origin: HeaderValue, rule: &CorsOriginRule, credentials: bool) -> CorsLayer {
+    let methods = match (&rule.methods, credentials) {
+        (Some(methods), _) => AllowMethods::list(methods.iter().cloned()),
+        (None, true) => AllowMethods::mirror_request(),
+        (None, false) => AllowMethods::any(),
+    };
+    let headers = match (&rule.headers, credentials) {
+        (Some(headers), _) => AllowHeaders::list(headers.iter().cloned()),
+        (None, true) => AllowHeaders::mirror_request(),
+        (None, false) => AllowHeaders::any(),
+    };
+
+    CorsLayer::new()
+        .allow_origin(origin)
+        .allow_methods(methods)
+        .allow_headers(headers)
+        .allow_credentials(credentials)
+}
+
+/// Middleware applying the CORS policy for the request's origin.
+pub async fn cors(State(policy): State<Arc<CorsPolicy>>, request: Request, next: Next) -> Response {
+    let layer = policy.layer_for(request.headers().get(ORIGIN));
+    match layer.layer(next).oneshot(request).await {
+        Ok(response) => response,
+        Err(never) => match never {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Body, middleware::from_fn_with_state, routing::get};
+    use http::{
+        Method, StatusCode,
+        header::{
+            ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_METHODS,
+            ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_REQUEST_METHOD,
+        },
+    };
+
+    fn app(origins: &str, credentials: bool) -> Router {
+        let config = ServerConfig {
+            cors_allowed_origins: origins.to_string(),
+            cors_allow_credentials: credentials,
+            ..Default::default()
+        };
+        let policy = Arc::new(CorsPolicy::from_config(&config));
+        Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(from_fn_with_state(policy, cors))
+    }
+
+    async fn preflight(app: Router, origin: &str) -> Response {
+        app.oneshot(
+            Request::builder()
+                .method(Method::OPTIONS)
+                .uri("/")
+                .header(ORIGIN, origin)
+                .header(ACCESS_CONTROL_REQUEST_METHOD, "GET")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_valid_origin_is_allowed() {
+        let app = app("https://app.example.com", true);
+        let response = preflight(app, "https://app.example.com").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(
+            headers[ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://app.example.com"
+        );
+        assert_eq!(headers[ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+    }
+
+    #[tokio::test]
+    async fn test_unlisted_origin_gets_no_allow_origin() {
+        let app = app("https://app.example.com", false);
+        let response = preflight(app, "https://evil.example.com").await;
+        assert!(!response.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+    }
+
+    #[tokio::test]
+    async fn test_per_origin_methods() {
+        let app = app(
+            "https://read.example.com;methods=GET, https://write.example.com;methods=GET|POST",
+            false,
+        );
+
+        let response = preflight(app.clone(), "https://read.example.com").await;
+        assert_eq!(response.headers()[ACCESS_CONTROL_ALLOW_METHODS], "GET");
+
+        let response = preflight(app, "https://write.example.com").await;
+        assert_eq!(response.headers()[ACCESS_CONTROL_ALLOW_METHODS], "GET,POST");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_origin_is_skipped_not_fatal() {
+        let app = app("not a url, https://app.example.com", false);
+        let response = preflight(app, "https://app.example.com").await;
+        assert_eq!(
+            response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://app.example.com"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_allows_any_origin() {
+        let app = app("*", false);
+        let response = preflight(app, "https://anywhere.example.com").await;
+        assert_eq!(response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+    }
+}
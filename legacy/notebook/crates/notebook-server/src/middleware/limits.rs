@@ -0,0 +1,265 @@
+//! Request size and time limits.
+//!
+//! Rejects requests whose headers or body exceed the configured sizes, and
+//! requests that take too long to arrive or be answered. Runs ahead of the
+//! handlers, so an oversized body is refused without being parsed:
+//!
+//! - 431 Request Header Fields Too Large: headers over `max_header_bytes`
+//! - 413 Payload Too Large: a `Content-Length` over `max_body_bytes`, or a
+//!   streamed body that grows past it
+//! - 408 Request Timeout: no response head within `request_timeout_secs`
+//!
+//! The timeout covers receiving the body and running the handler. Streaming
+//! responses (SSE, WebSocket) are not cut off once they have started.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Request, State},
+    http::{HeaderMap, header::CONTENT_LENGTH},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use futures::StreamExt;
+
+use crate::config::ServerConfig;
+use crate::error::{ApiError, ErrorCode};
+
+/// Request limits built from the server configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimits {
+    /// Largest accepted request body, in bytes.
+    pub max_body_bytes: usize,
+    /// Largest accepted total size of request headers, in bytes.
+    pub max_header_bytes: usize,
+    /// Time allowed for reading the request and producing a response.
+    pub timeout: Duration,
+}
+
+impl RequestLimits {
+    /// Build the limits from configuration.
+    pub fn from_config(config: &ServerConfig) -> Self {
+        Self {
+            max_body_bytes: config.max_body_bytes,
+            max_header_bytes: config.max_header_bytes,
+            timeout: config.request_timeout(),
+        }
+    }
+}
+
+/// Total size of the headers as sent on the wire (`name: value\r\n`).
+fn header_bytes(headers: &HeaderMap) -> usize {
+    headers
+        .iter()
+        .map(|(name, value)| name.as_str().len() + value.len() + 4)
+        .sum()
+}
+
+/// The declared `Content-Length`, if present and well-formed.
+fn content_length(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+fn payload_too_large(limit: usize) -> ApiError {
+    ApiError::Coded(
+        ErrorCode::PayloadTooLarge,
+        format!("Request body exceeds {} bytes", limit),
+    )
+}
+
+/// Read the whole body, failing as soon as it grows past `limit`.
+async fn read_body(body: Body, limit: usize) -> Result<Bytes, ApiError> {
+    let mut stream = body.into_data_stream();
+    let mut buffer = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk
+            .map_err(|e| ApiError::BadRequest(format!("Failed to read request body: {}", e)))?;
+        if buffer.len() + chunk.len() > limit {
+            return Err(payload_too_large(limit));
+        }
+        buffer.extend_from_slice(&chunk);
+    }
+    Ok(Bytes::from(buffer))
+}
+
+/// Check the limits, then run the rest of the stack on the buffered request.
+async fn limited(
+    limits: RequestLimits,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if header_bytes(request.headers()) > limits.max_header_bytes {
+        return Err(ApiError::Coded(
+            ErrorCode::HeadersTooLarge,
+            format!("Request headers exceed {} bytes", limits.max_header_bytes),
+        ));
+    }
+
+    if content_length(request.headers()).is_some_and(|len| len > limits.max_body_bytes as u64) {
+        return Err(payload_too_large(limits.max_body_bytes));
+    }
+
+    let (parts, body) = request.into_parts();
+    let body = read_body(body, limits.max_body_bytes).await?;
+    Ok(next.run(Request::from_parts(parts, Body::from(body))).await)
+}
+
+/// Middleware enforcing [`RequestLimits`].
+pub async fn request_limits(
+    State(limits): State<Arc<RequestLimits>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+
+    match tokio::time::timeout(limits.timeout, limited(*limits, request, next)).await {
+        Ok(Ok(response)) => response,
+        Ok(Err(e)) => {
+            tracing::warn!(method = %method, path = %path, error = %e, "Request rejected by limits");
+            e.into_response()
+        }
+        Err(_) => {
+            tracing::warn!(
+                method = %method,
+                path = %path,
+                timeout_secs = limits.timeout.as_secs_f64(),
+                "Request timed out"
+            );
+            ApiError::Coded(
+                ErrorCode::RequestTimeout,
+                format!("Request not completed within {:?}", limits.timeout),
+            )
+            .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use axum::{Router, http::StatusCode, middleware, routing::post};
+    use tower::ServiceExt;
+
+    const LIMITS: RequestLimits = RequestLimits {
+        max_body_bytes: 16,
+        max_header_bytes: 256,
+        timeout: Duration::from_millis(200),
+    };
+
+    /// A router whose handler records that it ran and echoes the body length.
+    fn app(limits: RequestLimits, reached: Arc<AtomicBool>) -> Router {
+        Router::new()
+            .route(
+                "/echo",
+                post(move |body: Bytes| async move {
+                    reached.store(true, Ordering::SeqCst);
+                    body.len().to_string()
+                }),
+            )
+            .layer(middleware::from_fn_with_state(
+                Arc::new(limits),
+                request_limits,
+            ))
+    }
+
+    async fn send(limits: RequestLimits, request: Request) -> (StatusCode, bool, String) {
+        let reached = Arc::new(AtomicBool::new(false));
+        let response = app(limits, reached.clone()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            reached.load(Ordering::SeqCst),
+            String::from_utf8(body.to_vec()).unwrap(),
+        )
+    }
+
+    fn post_echo() -> axum::http::request::Builder {
+        Request::builder().method("POST").uri("/echo")
+    }
+
+    #[tokio::test]
+    async fn test_body_within_limit_reaches_handler() {
+        let request = post_echo().body(Body::from("0123456789")).unwrap();
+        let (status, reached, body) = send(LIMITS, request).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(reached);
+        assert_eq!(body, "10");
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_rejected_before_handler() {
+        let request = post_echo()
+            .header(CONTENT_LENGTH, "17")
+            .body(Body::from("x".repeat(17)))
+            .unwrap();
+        let (status, reached, body) = send(LIMITS, request).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(!reached);
+        assert!(body.contains("PAYLOAD_TOO_LARGE"));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_streamed_body_rejected_before_handler() {
+        // No Content-Length: the limit is enforced while reading
+        let chunks = futures::stream::iter(
+            (0..4).map(|_| Ok::<_, std::io::Error>(Bytes::from_static(b"12345678"))),
+        );
+        let request = post_echo().body(Body::from_stream(chunks)).unwrap();
+        let (status, reached, _) = send(LIMITS, request).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(!reached);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_headers_rejected() {
+        let request = post_echo()
+            .header("x-padding", "p".repeat(300))
+            .body(Body::empty())
+            .unwrap();
+        let (status, reached, body) = send(LIMITS, request).await;
+        assert_eq!(status, StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE);
+        assert!(!reached);
+        assert!(body.contains("HEADERS_TOO_LARGE"));
+    }
+
+    #[tokio::test]
+    async fn test_slow_body_times_out() {
+        // One chunk arrives, then the client stalls
+        let chunks = futures::stream::iter([Ok::<_, std::io::Error>(Bytes::from_static(b"ab"))])
+            .chain(futures::stream::pending());
+        let request = post_echo().body(Body::from_stream(chunks)).unwrap();
+        let (status, reached, body) = send(LIMITS, request).await;
+        assert_eq!(status, StatusCode::REQUEST_TIMEOUT);
+        assert!(!reached);
+        assert!(body.contains("REQUEST_TIMEOUT"));
+    }
+
+    #[test]
+    fn test_limits_from_config() {
+        let config = ServerConfig {
+            max_body_bytes: 10,
+            max_header_bytes: 20,
+            request_timeout_secs: 3,
+            ..ServerConfig::default()
+        };
+        assert_eq!(
+            RequestLimits::from_config(&config),
+            RequestLimits {
+                max_body_bytes: 10,
+                max_header_bytes: 20,
+                timeout: Duration::from_secs(3),
+            }
+        );
+    }
+}
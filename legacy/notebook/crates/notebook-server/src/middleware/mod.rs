@@ -1,5 +1,13 @@
 //! Middleware stack for the HTTP server.
 
+pub mod access_log;
+pub mod cors;
+pub mod limits;
 pub mod request_id;
 
+pub use access_log::AccessLogAuthor;
+pub use cors::CorsPolicy;
+pub use limits::RequestLimits;
+#[allow(deprecated)]
 pub use request_id::RequestIdLayer;
+pub use request_id::RequestIdPolicy;
@@ -1,40 +1,219 @@
 //! Request ID middleware for tracing requests.
+//!
+//! Every request carries an `X-Request-Id`, echoed on the response. When the
+//! server sits behind the ASP.NET shell, the shell's ID is reused so logs on
+//! both sides correlate:
+//!
+//! 1. An inbound `X-Request-Id` matching `request_id_pattern` is kept.
+//! 2. Otherwise the trace ID of a valid W3C `traceparent` header is used.
+//! 3. Otherwise a UUID is generated.
+//!
+//! The deprecated [`request_id_layer`] remains for existing callers; it
+//! applies the default policy rather than the configured one.
 
-use axum::{extract::Request, middleware::Next, response::Response};
-use http::HeaderValue;
-use tower_http::request_id::{MakeRequestId, RequestId};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use http::{HeaderMap, HeaderValue};
+use regex::Regex;
+use tower::{Layer, Service};
 use uuid::Uuid;
 
+use crate::config::{DEFAULT_REQUEST_ID_PATTERN, ServerConfig};
+
 /// Header name for request ID.
 pub const REQUEST_ID_HEADER: &str = "x-request-id";
 
-/// Generate UUID-based request IDs.
-#[derive(Clone, Copy, Debug, Default)]
-pub struct MakeRequestUuid;
+/// W3C trace context header.
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+tokio::task_local! {
+    /// Request ID of the request being handled by the current task.
+    static CURRENT_REQUEST_ID: Option<String>;
+}
+
+/// Request ID of the request currently being handled, if any.
+///
+/// Only set inside `propagate_request_id`, so error responses built by
+/// handlers can echo the ID back in their body.
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(|id| id.clone()).ok().flatten()
+}
+
+/// Which inbound request IDs are reused.
+#[derive(Debug, Clone)]
+pub struct RequestIdPolicy {
+    accepted: Regex,
+}
+
+impl RequestIdPolicy {
+    /// Build the policy from configuration.
+    ///
+    /// `ServerConfig::from_env` rejects a pattern that does not compile; for
+    /// configurations built in code, an invalid pattern is logged and the
+    /// default is used instead.
+    pub fn from_config(config: &ServerConfig) -> Self {
+        let accepted = Regex::new(&config.request_id_pattern).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Invalid request ID pattern; using the default");
+            Regex::new(DEFAULT_REQUEST_ID_PATTERN).expect("default pattern compiles")
+        });
+        Self { accepted }
+    }
+
+    /// The ID to use for a request with these headers.
+    pub fn resolve(&self, headers: &HeaderMap) -> HeaderValue {
+        let inbound = headers
+            .get(REQUEST_ID_HEADER)
+            .filter(|value| value.to_str().is_ok_and(|id| self.accepted.is_match(id)));
+        if let Some(value) = inbound {
+            return value.clone();
+        }
+
+        let trace_id = headers
+            .get(TRACEPARENT_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(traceparent_trace_id);
+        let id = trace_id.map_or_else(|| Uuid::new_v4().to_string(), str::to_string);
+        HeaderValue::from_str(&id).expect("hex and UUID strings are valid header values")
+    }
+}
+
+impl Default for RequestIdPolicy {
+    fn default() -> Self {
+        Self::from_config(&ServerConfig::default())
+    }
+}
+
+/// Extract the trace ID from a W3C `traceparent` header.
+fn traceparent_trace_id(value: &str) -> Option<&str> {
+    parse_traceparent(value).map(|parent| parent.trace_id)
+}
+
+/// The fields of a valid W3C `traceparent` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceParent<'a> {
+    /// Trace ID, 32 lowercase hex digits.
+    pub trace_id: &'a str,
+    /// The caller's span ID, 16 lowercase hex digits.
+    pub parent_id: &'a str,
+    /// Whether the caller sampled the trace (the `01` flag).
+    pub sampled: bool,
+}
+
+/// Parse a W3C `traceparent` header.
+///
+/// The header is `version-traceid-parentid-flags` in lowercase hex. Version
+/// `ff` and all-zero IDs are invalid; version `00` has exactly four fields,
+/// later versions may append more.
+pub fn parse_traceparent(value: &str) -> Option<TraceParent<'_>> {
+    let is_hex = |s: &str, len: usize| {
+        s.len() == len && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+    };
+    let is_zero = |s: &str| s.bytes().all(|b| b == b'0');
 
-impl MakeRequestId for MakeRequestUuid {
-    fn make_request_id<B>(&mut self, _request: &http::Request<B>) -> Option<RequestId> {
-        let id = Uuid::new_v4().to_string();
-        HeaderValue::from_str(&id).ok().map(RequestId::new)
+    let fields: Vec<&str> = value.trim().split('-').collect();
+    let [version, trace_id, parent_id, flags, rest @ ..] = fields.as_slice() else {
+        return None;
+    };
+    let valid = is_hex(version, 2)
+        && *version != "ff"
+        && (*version != "00" || rest.is_empty())
+        && is_hex(trace_id, 32)
+        && !is_zero(trace_id)
+        && is_hex(parent_id, 16)
+        && !is_zero(parent_id)
+        && is_hex(flags, 2);
+    if !valid {
+        return None;
     }
+    let flags = u8::from_str_radix(flags, 16).ok()?;
+    Some(TraceParent {
+        trace_id,
+        parent_id,
+        sampled: flags & 0x01 != 0,
+    })
 }
 
-/// Tower layer for request ID generation.
-pub type RequestIdLayer = tower_http::request_id::SetRequestIdLayer<MakeRequestUuid>;
+/// Middleware that gives each request an `X-Request-Id`.
+///
+/// Replaces an inbound ID that does not match the policy.
+pub async fn assign_request_id(
+    State(policy): State<Arc<RequestIdPolicy>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let id = policy.resolve(request.headers());
+    request.headers_mut().insert(REQUEST_ID_HEADER, id);
+    next.run(request).await
+}
+
+/// Tower layer giving each request an `X-Request-Id` under the default
+/// [`RequestIdPolicy`].
+#[deprecated(note = "use `assign_request_id` with a configured `RequestIdPolicy`")]
+#[derive(Debug, Clone, Default)]
+pub struct RequestIdLayer {
+    policy: Arc<RequestIdPolicy>,
+}
 
 /// Create a new request ID layer.
+#[deprecated(note = "use `assign_request_id` with a configured `RequestIdPolicy`")]
+#[allow(deprecated)]
 pub fn request_id_layer() -> RequestIdLayer {
-    tower_http::request_id::SetRequestIdLayer::new(
-        REQUEST_ID_HEADER.parse().unwrap(),
-        MakeRequestUuid,
-    )
+    RequestIdLayer::default()
+}
+
+#[allow(deprecated)]
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdService {
+            inner,
+            policy: self.policy.clone(),
+        }
+    }
+}
+
+/// Service applied by [`RequestIdLayer`].
+#[derive(Debug, Clone)]
+pub struct RequestIdService<S> {
+    inner: S,
+    policy: Arc<RequestIdPolicy>,
+}
+
+impl<S, B> Service<http::Request<B>> for RequestIdService<S>
+where
+    S: Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        let id = self.policy.resolve(request.headers());
+        request.headers_mut().insert(REQUEST_ID_HEADER, id);
+        self.inner.call(request)
+    }
 }
 
 /// Middleware that propagates request ID to response headers.
 pub async fn propagate_request_id(request: Request, next: Next) -> Response {
     let request_id = request.headers().get(REQUEST_ID_HEADER).cloned();
+    let id_string = request_id
+        .as_ref()
+        .and_then(|id| id.to_str().ok())
+        .map(String::from);
 
-    let mut response = next.run(request).await;
+    let mut response = CURRENT_REQUEST_ID.scope(id_string, next.run(request)).await;
 
     if let Some(id) = request_id {
         response.headers_mut().insert(REQUEST_ID_HEADER, id);
@@ -42,3 +221,185 @@ pub async fn propagate_request_id(request: Request, next: Next) -> Response {
 
     response
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiError;
+    use axum::{Router, body::Body, middleware, routing::get};
+    use tower::ServiceExt;
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    /// Router with the request ID middleware as the server stacks it.
+    fn app() -> Router {
+        Router::new()
+            .route("/ok", get(|| async { "ok" }))
+            .layer(middleware::from_fn(propagate_request_id))
+            .layer(middleware::from_fn_with_state(
+                Arc::new(RequestIdPolicy::default()),
+                assign_request_id,
+            ))
+    }
+
+    async fn response_id(headers: &[(&str, &str)]) -> String {
+        let mut request = Request::builder().uri("/ok");
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let response = app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        response.headers()[REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_error_body_includes_request_id() {
+        let app = Router::new()
+            .route(
+                "/missing",
+                get(|| async { Err::<(), _>(ApiError::NotFound("gone".into())) }),
+            )
+            .layer(middleware::from_fn(propagate_request_id));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/missing")
+                    .header(REQUEST_ID_HEADER, "req-123")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "req-123");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["request_id"], "req-123");
+        assert_eq!(json["error"]["error_code"], "NOT_FOUND");
+    }
+
+    #[test]
+    fn test_no_request_id_outside_a_request() {
+        assert_eq!(current_request_id(), None);
+    }
+
+    #[tokio::test]
+    async fn test_valid_inbound_id_is_preserved() {
+        let id = response_id(&[(REQUEST_ID_HEADER, "shell-7f3a.42")]).await;
+        assert_eq!(id, "shell-7f3a.42");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_inbound_id_is_replaced() {
+        let too_long = "a".repeat(200);
+        for invalid in ["has spaces", "<script>", too_long.as_str()] {
+            let id = response_id(&[(REQUEST_ID_HEADER, invalid)]).await;
+            assert_ne!(id, invalid);
+            assert!(Uuid::parse_str(&id).is_ok(), "{id}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_absent_id_is_generated() {
+        let id = response_id(&[]).await;
+        assert!(Uuid::parse_str(&id).is_ok(), "{id}");
+    }
+
+    #[tokio::test]
+    async fn test_traceparent_trace_id_is_used() {
+        let id = response_id(&[(TRACEPARENT_HEADER, TRACEPARENT)]).await;
+        assert_eq!(id, "4bf92f3577b34da6a3ce929d0e0e4736");
+
+        // A valid X-Request-Id still takes precedence
+        let id = response_id(&[
+            (REQUEST_ID_HEADER, "req-1"),
+            (TRACEPARENT_HEADER, TRACEPARENT),
+        ])
+        .await;
+        assert_eq!(id, "req-1");
+    }
+
+    #[test]
+    fn test_traceparent_parsing() {
+        assert_eq!(
+            traceparent_trace_id(TRACEPARENT),
+            Some("4bf92f3577b34da6a3ce929d0e0e4736")
+        );
+        assert_eq!(
+            parse_traceparent(TRACEPARENT),
+            Some(TraceParent {
+                trace_id: "4bf92f3577b34da6a3ce929d0e0e4736",
+                parent_id: "00f067aa0ba902b7",
+                sampled: true,
+            })
+        );
+        let unsampled = format!("{}00", &TRACEPARENT[..TRACEPARENT.len() - 2]);
+        assert!(!parse_traceparent(&unsampled).unwrap().sampled);
+        // Later versions may carry extra fields
+        assert!(traceparent_trace_id(&format!("01{}-extra", &TRACEPARENT[2..])).is_some());
+
+        for invalid in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        ] {
+            assert_eq!(traceparent_trace_id(invalid), None, "{invalid}");
+        }
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn test_deprecated_layer_applies_default_policy() {
+        let app = Router::new()
+            .route("/ok", get(|| async { "ok" }))
+            .layer(middleware::from_fn(propagate_request_id))
+            .layer(request_id_layer());
+
+        for (inbound, expected) in [
+            (Some("shell-7f3a.42"), Some("shell-7f3a.42")),
+            (Some("has spaces"), None),
+            (None, None),
+        ] {
+            let mut request = Request::builder().uri("/ok");
+            if let Some(id) = inbound {
+                request = request.header(REQUEST_ID_HEADER, id);
+            }
+            let response = app
+                .clone()
+                .oneshot(request.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let id = response.headers()[REQUEST_ID_HEADER].to_str().unwrap();
+            match expected {
+                Some(expected) => assert_eq!(id, expected),
+                None => assert!(Uuid::parse_str(id).is_ok(), "{id}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_pattern_is_applied() {
+        let policy = RequestIdPolicy::from_config(&ServerConfig {
+            request_id_pattern: "^[0-9]+$".to_string(),
+            ..ServerConfig::default()
+        });
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("12345"));
+        assert_eq!(policy.resolve(&headers), "12345");
+
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("req-1"));
+        assert_ne!(policy.resolve(&headers), "req-1");
+    }
+}
@@ -0,0 +1,271 @@
+//! Per-notebook handling of orphan entries.
+//!
+//! An orphan is an entry the notebook cannot integrate: it matches no
+//! existing cluster and references nothing (see
+//! [`OrphanReason`](notebook_entropy::OrphanReason)). A notebook owner
+//! chooses how writes of orphans are handled:
+//!
+//! - `allow`: stored like any other entry (the default)
+//! - `warn`: stored, with an `X-Orphan-Warning` header on the write response
+//! - `reject`: refused with 422 `ORPHAN_REJECTED`, saying why the entry is
+//!   an orphan
+//!
+//! The decision is made on a preview of the entry's integration cost before
+//! anything is written, so rejected orphans never persist. The first entry
+//! of a notebook always starts its own cluster, so under `reject` it must
+//! reference another entry.
+//!
+//! The preview runs under the cost deadline (`COST_TIMEOUT_MS`). If it
+//! misses the deadline the write goes ahead unchecked and a warning is
+//! logged, rather than stalling the write behind the engine.
+
+use serde::{Deserialize, Serialize};
+
+use notebook_core::{Entry, NotebookId};
+use notebook_entropy::{CostExplanation, OrphanReason};
+use notebook_store::{NotebookRow, orphan_policy};
+
+use crate::engines::{CostOutcome, compute_cost_bounded};
+use crate::error::{ApiError, ApiResult, ErrorCode};
+use crate::state::AppState;
+
+/// Header carrying the orphan explanation under the `warn` policy.
+pub const ORPHAN_WARNING_HEADER: &str = "X-Orphan-Warning";
+
+/// How writes of orphan entries to a notebook are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrphanPolicy {
+    /// Orphans are stored like any other entry.
+    #[default]
+    Allow,
+    /// Orphans are stored, and the write response warns about them.
+    Warn,
+    /// Orphans are rejected.
+    Reject,
+}
+
+impl OrphanPolicy {
+    /// The policy's name, as stored.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => orphan_policy::ALLOW,
+            Self::Warn => orphan_policy::WARN,
+            Self::Reject => orphan_policy::REJECT,
+        }
+    }
+
+    /// The policy of a notebook. Unknown values are treated as `allow`.
+    pub fn of(notebook: &NotebookRow) -> Self {
+        match notebook.orphan_policy.as_str() {
+            orphan_policy::WARN => Self::Warn,
+            orphan_policy::REJECT => Self::Reject,
+            _ => Self::Allow,
+        }
+    }
+
+    /// Apply the policy to an entry's orphan verdict.
+    ///
+    /// Returns the warning to send with the write response, if any.
+    pub fn apply(self, reason: OrphanReason) -> ApiResult<Option<String>> {
+        if !reason.is_orphan() {
+            return Ok(None);
+        }
+        match self {
+            Self::Allow => Ok(None),
+            Self::Warn => Ok(Some(reason.to_string())),
+            Self::Reject => Err(ApiError::Coded(
+                ErrorCode::OrphanRejected,
+                format!("This notebook does not accept orphan entries; {}", reason),
+            )),
+        }
+    }
+}
+
+/// Enforce a notebook's orphan policy on a candidate entry.
+///
+/// Previews the entry's integration cost without changing the notebook's
+/// coherence snapshot. Returns the warning for the write response under
+/// `warn`, and 422 under `reject` if the entry would be an orphan.
+pub async fn enforce_orphan_policy(
+    state: &AppState,
+    notebook: &NotebookRow,
+    candidate: &Entry,
+) -> ApiResult<Option<String>> {
+    let policy = OrphanPolicy::of(notebook);
+    if policy == OrphanPolicy::Allow {
+        return Ok(None);
+    }
+
+    let notebook_id = NotebookId::from_uuid(notebook.id);
+    let engine = state.engines().lock(notebook_id).await;
+    let entry = candidate.clone();
+    let outcome = compute_cost_bounded(
+        move || engine.explain_cost(&entry, notebook_id),
+        state.config().cost_timeout(),
+    )
+    .await;
+    apply_to_preview(policy, outcome, candidate)
+}
+
+/// Apply the policy to a bounded preview of the entry's cost.
+///
+/// A preview that missed the deadline lets the entry through.
+fn apply_to_preview(
+    policy: OrphanPolicy,
+    outcome: CostOutcome<CostExplanation>,
+    candidate: &Entry,
+) -> ApiResult<Option<String>> {
+    match outcome {
+        CostOutcome::Computed(explanation) => policy.apply(explanation.orphan_reason),
+        CostOutcome::Failed(e) => Err(ApiError::Internal(format!(
+            "Failed to preview integration cost: {}",
+            e
+        ))),
+        CostOutcome::TimedOut(_) => {
+            tracing::warn!(
+                entry_id = %candidate.id,
+                policy = policy.as_str(),
+                "Orphan check timed out, accepting the entry unchecked"
+            );
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use notebook_core::{AuthorId, EntryId};
+    use notebook_store::Store;
+    use sqlx::PgPool;
+    use uuid::Uuid;
+
+    use crate::config::ServerConfig;
+
+    fn state() -> AppState {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        AppState::new(Store::from_pool(pool), ServerConfig::default())
+    }
+
+    fn notebook(policy: OrphanPolicy) -> NotebookRow {
+        NotebookRow {
+            id: Uuid::new_v4(),
+            name: "Orphans".to_string(),
+            owner_id: vec![0u8; 32],
+            created: Utc::now(),
+            current_sequence: 0,
+            is_locked: false,
+            encrypted: false,
+            is_public: false,
+            orphan_policy: policy.as_str().to_string(),
+        }
+    }
+
+    /// The first entry of a notebook, referencing nothing: it can only start
+    /// its own cluster.
+    fn orphan() -> Entry {
+        Entry::builder()
+            .content(b"Tide tables for the northern harbour".to_vec())
+            .content_type("text/plain")
+            .author(AuthorId::zero())
+            .build()
+    }
+
+    #[test]
+    fn test_policy_names_round_trip() {
+        for policy in [
+            OrphanPolicy::Allow,
+            OrphanPolicy::Warn,
+            OrphanPolicy::Reject,
+        ] {
+            let json = serde_json::to_value(policy).unwrap();
+            assert_eq!(json, policy.as_str());
+            assert_eq!(
+                serde_json::from_value::<OrphanPolicy>(json).unwrap(),
+                policy
+            );
+        }
+        assert!(serde_json::from_str::<OrphanPolicy>(r#""sometimes""#).is_err());
+    }
+
+    #[test]
+    fn test_non_orphans_pass_every_policy() {
+        for policy in [
+            OrphanPolicy::Allow,
+            OrphanPolicy::Warn,
+            OrphanPolicy::Reject,
+        ] {
+            assert_eq!(policy.apply(OrphanReason::JoinedCluster).unwrap(), None);
+            assert_eq!(
+                policy
+                    .apply(OrphanReason::HasReferences { count: 1 })
+                    .unwrap(),
+                None
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_allow_stores_orphans_silently() {
+        let state = state();
+        let notebook = notebook(OrphanPolicy::Allow);
+        let warning = enforce_orphan_policy(&state, &notebook, &orphan()).await;
+        assert_eq!(warning.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_warn_explains_the_orphan() {
+        let state = state();
+        let notebook = notebook(OrphanPolicy::Warn);
+        let warning = enforce_orphan_policy(&state, &notebook, &orphan())
+            .await
+            .unwrap()
+            .expect("orphan should be warned about");
+        assert_eq!(
+            warning,
+            OrphanReason::NoClusterMatchAndNoReferences.to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reject_refuses_orphans_without_touching_the_snapshot() {
+        let state = state();
+        let notebook = notebook(OrphanPolicy::Reject);
+        let result = enforce_orphan_policy(&state, &notebook, &orphan()).await;
+        assert!(matches!(
+            &result,
+            Err(ApiError::Coded(ErrorCode::OrphanRejected, msg)) if msg.contains("does not accept orphan")
+        ));
+        assert_eq!(result.unwrap_err().status_code().as_u16(), 422);
+
+        // The preview leaves nothing behind for the rejected entry
+        let notebook_id = NotebookId::from_uuid(notebook.id);
+        assert_eq!(state.engines().lock(notebook_id).await.snapshot_count(), 0);
+
+        // Referencing another entry keeps it from being an orphan
+        let mut referencing = orphan();
+        referencing.references = vec![EntryId::new()];
+        let warning = enforce_orphan_policy(&state, &notebook, &referencing).await;
+        assert_eq!(warning.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_preview_fails_open() {
+        let pending = tokio::task::spawn_blocking(|| Err("still running".to_string()));
+        let warning = apply_to_preview(
+            OrphanPolicy::Reject,
+            CostOutcome::TimedOut(pending),
+            &orphan(),
+        );
+        assert_eq!(warning.unwrap(), None);
+
+        let failed = apply_to_preview(
+            OrphanPolicy::Reject,
+            CostOutcome::Failed("engine unavailable".to_string()),
+            &orphan(),
+        );
+        assert!(matches!(failed, Err(ApiError::Internal(_))));
+    }
+}
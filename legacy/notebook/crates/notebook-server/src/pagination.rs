@@ -0,0 +1,205 @@
+//! Pagination headers for list endpoints.
+//!
+//! Paged list responses carry `X-Total-Count`, the number of items across
+//! all pages, and a `Link` header (RFC 8288) with `rel="next"` and
+//! `rel="prev"` links, so generic clients can page without reading the body.
+//! `next` is omitted on the last page and `prev` on the first.
+//!
+//! Links are relative to the request: the same path and query, with the
+//! paging parameters replaced.
+
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Uri, header::LINK};
+
+/// Header carrying the number of items across all pages.
+pub const TOTAL_COUNT_HEADER: HeaderName = HeaderName::from_static("x-total-count");
+
+/// Names of the query parameters that select a page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageParams {
+    /// Parameter giving the number of items to skip.
+    pub offset: &'static str,
+    /// Parameter giving the page size.
+    pub limit: &'static str,
+}
+
+/// The usual `offset` and `limit` parameters.
+pub const OFFSET_LIMIT: PageParams = PageParams {
+    offset: "offset",
+    limit: "limit",
+};
+
+/// Where one page sits in a list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageWindow {
+    /// Items skipped before this page.
+    pub offset: usize,
+    /// Page size in effect, after defaults and caps.
+    pub limit: usize,
+    /// Items on this page.
+    pub returned: usize,
+    /// Items across all pages.
+    pub total: usize,
+}
+
+impl PageWindow {
+    /// Offset of the next page, if there are items after this one.
+    fn next_offset(&self) -> Option<usize> {
+        let end = self.offset + self.returned;
+        (self.returned > 0 && end < self.total).then_some(end)
+    }
+
+    /// Offset of the previous page, if this is not the first.
+    fn prev_offset(&self) -> Option<usize> {
+        (self.offset > 0).then(|| self.offset.saturating_sub(self.limit))
+    }
+}
+
+/// Cut the page of at most `limit` items (all remaining if `None`) that
+/// starts at `offset` out of `items`.
+pub fn paginate<T>(mut items: Vec<T>, offset: usize, limit: Option<usize>) -> (Vec<T>, PageWindow) {
+    let total = items.len();
+    let start = offset.min(total);
+    let end = limit.map_or(total, |limit| start.saturating_add(limit).min(total));
+    let page: Vec<T> = items.drain(start..end).collect();
+    let window = PageWindow {
+        offset,
+        limit: limit.unwrap_or(total),
+        returned: page.len(),
+        total,
+    };
+    (page, window)
+}
+
+/// A link to the page at `offset`, keeping the rest of the request's query.
+fn page_link(uri: &Uri, params: PageParams, offset: usize, limit: usize) -> String {
+    let mut pairs: Vec<(String, String)> = uri
+        .query()
+        .and_then(|query| serde_urlencoded::from_str(query).ok())
+        .unwrap_or_default();
+    pairs.retain(|(name, _)| name != params.offset && name != params.limit);
+    pairs.push((params.offset.to_string(), offset.to_string()));
+    pairs.push((params.limit.to_string(), limit.to_string()));
+    let query = serde_urlencoded::to_string(&pairs).unwrap_or_default();
+    format!("{}?{}", uri.path(), query)
+}
+
+/// `X-Total-Count` and `Link` headers for a page of a list requested at `uri`.
+pub fn pagination_headers(uri: &Uri, params: PageParams, window: &PageWindow) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(TOTAL_COUNT_HEADER, HeaderValue::from(window.total));
+
+    let links: Vec<String> = [
+        ("next", window.next_offset()),
+        ("prev", window.prev_offset()),
+    ]
+    .into_iter()
+    .filter_map(|(rel, offset)| {
+        let link = page_link(uri, params, offset?, window.limit.max(1));
+        Some(format!("<{}>; rel=\"{}\"", link, rel))
+    })
+    .collect();
+    if !links.is_empty()
+        && let Ok(value) = HeaderValue::from_str(&links.join(", "))
+    {
+        headers.insert(LINK, value);
+    }
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(offset: usize, limit: usize, returned: usize, total: usize) -> PageWindow {
+        PageWindow {
+            offset,
+            limit,
+            returned,
+            total,
+        }
+    }
+
+    fn link(headers: &HeaderMap) -> Option<&str> {
+        headers.get(LINK).map(|value| value.to_str().unwrap())
+    }
+
+    #[test]
+    fn test_paginate_cuts_window() {
+        let items: Vec<u32> = (0..25).collect();
+        let (page, w) = paginate(items.clone(), 20, Some(10));
+        assert_eq!(page, vec![20, 21, 22, 23, 24]);
+        assert_eq!(w, window(20, 10, 5, 25));
+
+        let (page, w) = paginate(items.clone(), 0, None);
+        assert_eq!(page.len(), 25);
+        assert_eq!(w.next_offset(), None);
+
+        let (page, w) = paginate(items, 30, Some(10));
+        assert!(page.is_empty());
+        assert_eq!(w.next_offset(), None);
+        assert_eq!(w.prev_offset(), Some(20));
+    }
+
+    #[test]
+    fn test_first_page_links_next_only() {
+        let uri: Uri = "/notebooks?limit=10&include=permissions".parse().unwrap();
+        let headers = pagination_headers(&uri, OFFSET_LIMIT, &window(0, 10, 10, 25));
+
+        assert_eq!(headers[TOTAL_COUNT_HEADER], "25");
+        assert_eq!(
+            link(&headers),
+            Some("</notebooks?include=permissions&offset=10&limit=10>; rel=\"next\"")
+        );
+    }
+
+    #[test]
+    fn test_middle_page_links_both_ways() {
+        let uri: Uri = "/notebooks?offset=10&limit=10".parse().unwrap();
+        let headers = pagination_headers(&uri, OFFSET_LIMIT, &window(10, 10, 10, 25));
+        assert_eq!(
+            link(&headers),
+            Some(
+                "</notebooks?offset=20&limit=10>; rel=\"next\", \
+                 </notebooks?offset=0&limit=10>; rel=\"prev\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_last_page_omits_next() {
+        let uri: Uri = "/notebooks?offset=20&limit=10".parse().unwrap();
+        let headers = pagination_headers(&uri, OFFSET_LIMIT, &window(20, 10, 5, 25));
+        assert_eq!(
+            link(&headers),
+            Some("</notebooks?offset=10&limit=10>; rel=\"prev\"")
+        );
+        assert_eq!(headers[TOTAL_COUNT_HEADER], "25");
+    }
+
+    #[test]
+    fn test_single_page_has_no_link() {
+        let uri: Uri = "/notebooks".parse().unwrap();
+        let headers = pagination_headers(&uri, OFFSET_LIMIT, &window(0, 3, 3, 3));
+        assert_eq!(link(&headers), None);
+        assert_eq!(headers[TOTAL_COUNT_HEADER], "3");
+    }
+
+    #[test]
+    fn test_custom_parameter_names_are_replaced() {
+        let params = PageParams {
+            offset: "cluster_offset",
+            limit: "cluster_limit",
+        };
+        let uri: Uri = "/notebooks/x/browse?sort=size&cluster_offset=0&query=a%20b"
+            .parse()
+            .unwrap();
+        let headers = pagination_headers(&uri, params, &window(0, 5, 5, 12));
+        assert_eq!(
+            link(&headers),
+            Some(
+                "</notebooks/x/browse?sort=size&query=a+b&cluster_offset=5&cluster_limit=5>; \
+                 rel=\"next\""
+            )
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! Anonymous reads of public notebooks.
+//!
+//! A notebook its owner has made public can be read, browsed and searched
+//! without credentials; writes always need an authenticated author. Requests
+//! without credentials are limited per client address so an open notebook
+//! cannot be scraped at full speed: each address gets
+//! [`anonymous_reads_per_minute`](crate::config::ServerConfig::anonymous_reads_per_minute)
+//! reads per window, and further reads get 429 Too Many Requests with a
+//! `Retry-After` header.
+//!
+//! The address is the peer of the TCP connection. Behind a proxy all
+//! anonymous readers share the proxy's address.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use notebook_store::{NotebookRow, StoreError};
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{ReaderIdentity, require_scope};
+use crate::state::AppState;
+
+/// Window over which anonymous reads are counted.
+pub const ANONYMOUS_READ_WINDOW: Duration = Duration::from_secs(60);
+
+/// Number of tracked clients above which expired windows are dropped.
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// Anonymous reads per client address in the current window.
+#[derive(Debug)]
+pub struct AnonymousReadLimiter {
+    /// Reads allowed per client and window.
+    limit: u32,
+    /// Start of the current window and reads in it, per client.
+    windows: Mutex<HashMap<Option<IpAddr>, (Instant, u32)>>,
+}
+
+impl AnonymousReadLimiter {
+    /// Create a limiter allowing `limit` reads per client and window.
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count a read by `client` at `now`.
+    ///
+    /// Returns how much longer the client must wait if it has used up its
+    /// reads for the window.
+    pub fn check(&self, client: Option<IpAddr>, now: Instant) -> Result<(), Duration> {
+        let mut windows = self.windows.lock().expect("read limiter lock poisoned");
+        if windows.len() > PRUNE_THRESHOLD {
+            windows.retain(|_, (start, _)| {
+                now.saturating_duration_since(*start) < ANONYMOUS_READ_WINDOW
+            });
+        }
+
+        let (start, reads) = windows.entry(client).or_insert((now, 0));
+        if now.saturating_duration_since(*start) >= ANONYMOUS_READ_WINDOW {
+            *start = now;
+            *reads = 0;
+        }
+        if *reads >= self.limit {
+            return Err(ANONYMOUS_READ_WINDOW - now.saturating_duration_since(*start));
+        }
+        *reads += 1;
+        Ok(())
+    }
+}
+
+/// Decide whether `reader` may read `notebook`.
+///
+/// Authors are not checked here beyond the notebook existing. Anonymous
+/// readers may read public notebooks, subject to the rate limit; for any
+/// other notebook, existing or not, they get 401 so that private notebook
+/// ids are not disclosed.
+fn check_read(
+    limiter: &AnonymousReadLimiter,
+    reader: &ReaderIdentity,
+    notebook_id: Uuid,
+    notebook: Option<&NotebookRow>,
+    now: Instant,
+) -> ApiResult<()> {
+    match (reader, notebook) {
+        (ReaderIdentity::Author(_), Some(_)) => Ok(()),
+        (ReaderIdentity::Author(_), None) => Err(ApiError::notebook_not_found(notebook_id)),
+        (ReaderIdentity::Anonymous { client }, Some(notebook)) if notebook.is_public => {
+            limiter.check(*client, now).map_err(|retry_after| {
+                tracing::info!(
+                    notebook_id = %notebook_id,
+                    client = ?client,
+                    retry_after_ms = retry_after.as_millis() as u64,
+                    "Anonymous read throttled"
+                );
+                ApiError::ReadThrottled {
+                    message: format!(
+                        "Anonymous read limit exceeded; retry in {:.0}s",
+                        retry_after.as_secs_f64().ceil()
+                    ),
+                    retry_after,
+                }
+            })
+        }
+        (ReaderIdentity::Anonymous { .. }, _) => Err(ApiError::Unauthorized(
+            "Missing Authorization: Bearer <jwt> header".into(),
+        )),
+    }
+}
+
+/// Load `notebook_id` for a read by `reader`.
+///
+/// Authors need the `notebook:read` scope; anonymous readers need the
+/// notebook to be public and are rate-limited by address.
+pub async fn readable_notebook(
+    state: &AppState,
+    reader: &ReaderIdentity,
+    notebook_id: Uuid,
+) -> ApiResult<NotebookRow> {
+    if let ReaderIdentity::Author(identity) = reader {
+        require_scope(identity, "notebook:read", state.config())?;
+    }
+
+    let notebook = match state.store().get_notebook(notebook_id).await {
+        Ok(notebook) => Some(notebook),
+        Err(StoreError::NotebookNotFound(_)) => None,
+        Err(e) => return Err(ApiError::Store(e)),
+    };
+
+    check_read(
+        state.anonymous_reads(),
+        reader,
+        notebook_id,
+        notebook.as_ref(),
+        Instant::now(),
+    )?;
+    notebook.ok_or_else(|| ApiError::notebook_not_found(notebook_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use chrono::Utc;
+    use notebook_core::AuthorId;
+
+    use crate::extract::AuthorIdentity;
+
+    fn notebook(is_public: bool) -> NotebookRow {
+        NotebookRow {
+            id: Uuid::new_v4(),
+            name: "Test".to_string(),
+            owner_id: vec![0u8; 32],
+            created: Utc::now(),
+            current_sequence: 0,
+            is_locked: false,
+            encrypted: false,
+            is_public,
+            orphan_policy: "allow".to_string(),
+        }
+    }
+
+    fn anonymous() -> ReaderIdentity {
+        ReaderIdentity::Anonymous {
+            client: Some(IpAddr::from([192, 0, 2, 1])),
+        }
+    }
+
+    fn author() -> ReaderIdentity {
+        ReaderIdentity::Author(AuthorIdentity {
+            author_id: AuthorId::zero(),
+            scopes: vec!["notebook:read".to_string()],
+        })
+    }
+
+    fn status(result: ApiResult<()>) -> StatusCode {
+        result.unwrap_err().status_code()
+    }
+
+    #[test]
+    fn test_anonymous_read_of_public_notebook_succeeds() {
+        let limiter = AnonymousReadLimiter::new(10);
+        let notebook = notebook(true);
+        let result = check_read(
+            &limiter,
+            &anonymous(),
+            notebook.id,
+            Some(&notebook),
+            Instant::now(),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_anonymous_read_of_private_notebook_is_unauthorized() {
+        let limiter = AnonymousReadLimiter::new(10);
+        let notebook = notebook(false);
+        let result = check_read(
+            &limiter,
+            &anonymous(),
+            notebook.id,
+            Some(&notebook),
+            Instant::now(),
+        );
+        assert_eq!(status(result), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_missing_notebook_hidden_from_anonymous_readers() {
+        let limiter = AnonymousReadLimiter::new(10);
+        let id = Uuid::new_v4();
+        let now = Instant::now();
+        assert_eq!(
+            status(check_read(&limiter, &anonymous(), id, None, now)),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(check_read(&limiter, &author(), id, None, now)),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[test]
+    fn test_authors_read_private_notebooks_unlimited() {
+        let limiter = AnonymousReadLimiter::new(1);
+        let notebook = notebook(false);
+        let now = Instant::now();
+        for _ in 0..5 {
+            assert!(check_read(&limiter, &author(), notebook.id, Some(&notebook), now).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_anonymous_reads_are_limited_per_client() {
+        let limiter = AnonymousReadLimiter::new(2);
+        let notebook = notebook(true);
+        let now = Instant::now();
+        let read = |reader: &ReaderIdentity, at: Instant| {
+            check_read(&limiter, reader, notebook.id, Some(&notebook), at)
+        };
+
+        assert!(read(&anonymous(), now).is_ok());
+        assert!(read(&anonymous(), now).is_ok());
+        let err = read(&anonymous(), now).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::TOO_MANY_REQUESTS);
+
+        // Other clients have their own allowance
+        let other = ReaderIdentity::Anonymous {
+            client: Some(IpAddr::from([192, 0, 2, 2])),
+        };
+        assert!(read(&other, now).is_ok());
+
+        // The allowance is restored once the window has passed
+        assert!(read(&anonymous(), now + ANONYMOUS_READ_WINDOW).is_ok());
+    }
+
+    #[test]
+    fn test_retry_after_is_rest_of_window() {
+        let limiter = AnonymousReadLimiter::new(1);
+        let client = Some(IpAddr::from([192, 0, 2, 1]));
+        let now = Instant::now();
+        assert!(limiter.check(client, now).is_ok());
+        let wait = limiter
+            .check(client, now + Duration::from_secs(20))
+            .unwrap_err();
+        assert_eq!(wait, Duration::from_secs(40));
+    }
+}
@@ -0,0 +1,102 @@
+//! Startup readiness.
+//!
+//! `/health` answers as soon as the server listens. `/health/ready` answers
+//! only once the background workers have started and the database has
+//! answered a check, so an orchestrator (e.g. a Kubernetes readiness probe)
+//! holds traffic until then instead of sending requests whose follow-up work
+//! would be dropped.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use crate::state::AppState;
+
+/// Delay between database checks while the database is not answering.
+const DATABASE_CHECK_RETRY: Duration = Duration::from_secs(1);
+
+/// Startup conditions that must hold before the server takes traffic.
+#[derive(Debug, Default)]
+pub struct Readiness {
+    /// Background workers have been started.
+    workers_started: AtomicBool,
+    /// The database answered a query after startup.
+    database_checked: AtomicBool,
+}
+
+impl Readiness {
+    /// Create a readiness state with nothing started yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that the background workers have been started.
+    pub fn mark_workers_started(&self) {
+        self.workers_started.store(true, Ordering::Release);
+    }
+
+    /// Record that the database answered a check.
+    pub fn mark_database_checked(&self) {
+        self.database_checked.store(true, Ordering::Release);
+    }
+
+    /// Whether the background workers have been started.
+    pub fn workers_started(&self) -> bool {
+        self.workers_started.load(Ordering::Acquire)
+    }
+
+    /// Whether the database answered a check.
+    pub fn database_checked(&self) -> bool {
+        self.database_checked.load(Ordering::Acquire)
+    }
+
+    /// Whether the server is ready for traffic.
+    pub fn is_ready(&self) -> bool {
+        self.workers_started() && self.database_checked()
+    }
+}
+
+/// Query the database until it answers, then mark it checked.
+///
+/// Runs until the first successful check.
+pub async fn check_database(state: AppState) {
+    loop {
+        match state.store().schema_version().await {
+            Ok(_) => {
+                state.readiness().mark_database_checked();
+                tracing::info!("Database check passed");
+                return;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Database check failed, retrying");
+                tokio::time::sleep(DATABASE_CHECK_RETRY).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_ready_until_workers_started() {
+        let readiness = Readiness::new();
+        assert!(!readiness.is_ready());
+
+        readiness.mark_database_checked();
+        assert!(!readiness.is_ready());
+
+        readiness.mark_workers_started();
+        assert!(readiness.is_ready());
+    }
+
+    #[test]
+    fn test_not_ready_until_database_checked() {
+        let readiness = Readiness::new();
+        readiness.mark_workers_started();
+        assert!(!readiness.is_ready());
+
+        readiness.mark_database_checked();
+        assert!(readiness.is_ready());
+    }
+}
@@ -0,0 +1,432 @@
+//! Recomputing a notebook's integration costs from scratch.
+//!
+//! An entry's cost is computed once, when it is written, against the
+//! notebook as it stood then. Bulk changes such as imports, clones and topic
+//! renames leave stored costs and the coherence snapshot out of date. A
+//! recompute replays every entry in sequence order through a fresh engine,
+//! as if the notebook were written again from empty, stores the resulting
+//! costs (flagging entries whose references have all since been deleted as
+//! orphans), rebuilds the notebook's coherence snapshot and drops its cached
+//! catalog.
+//!
+//! Recomputes run in the background, started by
+//! `POST /notebooks/{id}/recompute`. [`RecomputeJobs`] tracks their
+//! progress for `GET /notebooks/{id}/recompute`.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use notebook_core::{CausalPosition, Entry, EntryId, IntegrationCost, NotebookId};
+use notebook_entropy::{CostConfig, IntegrationCostEngine, ReferenceResolution};
+use notebook_store::{EntryQuery, IntegrationCostJson, Store, StoreError};
+
+use crate::error::{ApiError, ApiResult};
+use crate::routes::suggest::entry_row_to_snapshot_entry;
+use crate::state::AppState;
+
+/// Where a recompute stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecomputeState {
+    /// Costs are being computed or stored.
+    Running,
+    /// Every cost is stored and the snapshot rebuilt.
+    Completed,
+    /// The recompute stopped; costs stored so far are kept.
+    Failed,
+}
+
+/// Progress of a notebook's latest recompute.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecomputeStatus {
+    /// Where the recompute stands.
+    pub state: RecomputeState,
+    /// Entries to recompute, once loaded.
+    pub total: usize,
+    /// Entries whose cost has been computed.
+    pub computed: usize,
+    /// Entries whose cost has been stored.
+    pub stored: usize,
+    /// Sum of the recomputed catalog shifts, once completed.
+    pub notebook_entropy: Option<f64>,
+    /// Why the recompute failed.
+    pub error: Option<String>,
+    /// When the recompute started.
+    pub started: DateTime<Utc>,
+    /// When the recompute completed or failed.
+    pub finished: Option<DateTime<Utc>>,
+}
+
+impl RecomputeStatus {
+    fn running() -> Self {
+        Self {
+            state: RecomputeState::Running,
+            total: 0,
+            computed: 0,
+            stored: 0,
+            notebook_entropy: None,
+            error: None,
+            started: Utc::now(),
+            finished: None,
+        }
+    }
+}
+
+/// Latest recompute of each notebook.
+#[derive(Debug, Default)]
+pub struct RecomputeJobs {
+    jobs: Mutex<HashMap<Uuid, RecomputeStatus>>,
+}
+
+impl RecomputeJobs {
+    /// Create an empty job table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a new recompute of `notebook_id`.
+    ///
+    /// Returns `false`, leaving the table unchanged, if one is already
+    /// running for the notebook.
+    pub fn start(&self, notebook_id: Uuid) -> bool {
+        let mut jobs = self.jobs.lock().expect("recompute jobs lock poisoned");
+        if jobs
+            .get(&notebook_id)
+            .is_some_and(|job| job.state == RecomputeState::Running)
+        {
+            return false;
+        }
+        jobs.insert(notebook_id, RecomputeStatus::running());
+        true
+    }
+
+    /// Status of the notebook's latest recompute, if there was one.
+    pub fn get(&self, notebook_id: Uuid) -> Option<RecomputeStatus> {
+        let jobs = self.jobs.lock().expect("recompute jobs lock poisoned");
+        jobs.get(&notebook_id).cloned()
+    }
+
+    fn update(&self, notebook_id: Uuid, f: impl FnOnce(&mut RecomputeStatus)) {
+        let mut jobs = self.jobs.lock().expect("recompute jobs lock poisoned");
+        if let Some(job) = jobs.get_mut(&notebook_id) {
+            f(job);
+        }
+    }
+
+    /// Record how a recompute ended.
+    fn finish(&self, notebook_id: Uuid, result: &ApiResult<f64>) {
+        self.update(notebook_id, |job| {
+            match result {
+                Ok(entropy) => {
+                    job.state = RecomputeState::Completed;
+                    job.notebook_entropy = Some(*entropy);
+                }
+                Err(e) => {
+                    job.state = RecomputeState::Failed;
+                    job.error = Some(e.to_string());
+                }
+            }
+            job.finished = Some(Utc::now());
+        });
+    }
+}
+
+/// Which of the entries referenced by `entries` still exist, in any notebook.
+pub async fn resolve_references(
+    store: &Store,
+    entries: &[Entry],
+) -> ApiResult<ReferenceResolution> {
+    let referenced: HashSet<Uuid> = entries
+        .iter()
+        .flat_map(|entry| entry.references.iter().map(|id| *id.as_uuid()))
+        .collect();
+    let referenced: Vec<Uuid> = referenced.into_iter().collect();
+    let existing: HashSet<Uuid> = store
+        .existing_entry_ids(&referenced)
+        .await?
+        .into_iter()
+        .collect();
+
+    Ok(ReferenceResolution::with_missing(
+        referenced
+            .into_iter()
+            .filter(|id| !existing.contains(id))
+            .map(EntryId::from_uuid),
+    ))
+}
+
+/// Integration costs of `entries` written in sequence order to an empty
+/// notebook, in that order.
+///
+/// `on_progress` is called with the number of costs computed so far.
+pub fn replay_costs(
+    config: &CostConfig,
+    notebook_id: NotebookId,
+    entries: &mut [Entry],
+    references: &ReferenceResolution,
+    mut on_progress: impl FnMut(usize),
+) -> ApiResult<Vec<IntegrationCost>> {
+    entries.sort_by_key(|entry| entry.causal_position.sequence);
+
+    let mut engine = IntegrationCostEngine::with_config(config.clone());
+    let mut costs = Vec::with_capacity(entries.len());
+    for entry in entries.iter() {
+        let cost = engine
+            .compute_cost_resolved(entry, notebook_id, references)
+            .map_err(|e| {
+                ApiError::Internal(format!("Failed to compute integration cost: {}", e))
+            })?;
+        costs.push(cost);
+        on_progress(costs.len());
+    }
+    Ok(costs)
+}
+
+/// Recompute and store the costs of every entry of `notebook_id`.
+///
+/// Returns the notebook's entropy: the sum of the recomputed catalog shifts.
+async fn recompute_notebook(state: &AppState, notebook_id: Uuid) -> ApiResult<f64> {
+    let store = state.store();
+    let jobs = state.recompute_jobs().clone();
+    let nb_id = NotebookId::from_uuid(notebook_id);
+
+    let rows = store.query_entries(&EntryQuery::new(notebook_id)).await?;
+    let mut entries: Vec<Entry> = rows.iter().map(entry_row_to_snapshot_entry).collect();
+    let references = resolve_references(store, &entries).await?;
+    jobs.update(notebook_id, |job| job.total = entries.len());
+
+    // Replaying is CPU-bound; keep it off the async workers
+    let config = state.config().cost_config();
+    let progress = jobs.clone();
+    let (entries, costs) = tokio::task::spawn_blocking(move || {
+        let costs = replay_costs(&config, nb_id, &mut entries, &references, |computed| {
+            progress.update(notebook_id, |job| job.computed = computed)
+        })?;
+        Ok::<_, ApiError>((entries, costs))
+    })
+    .await
+    .map_err(|e| ApiError::Internal(format!("cost recompute task failed: {}", e)))??;
+
+    for (stored, (entry, cost)) in entries.iter().zip(&costs).enumerate() {
+        match store
+            .update_integration_cost(*entry.id.as_uuid(), &IntegrationCostJson::from(*cost))
+            .await
+        {
+            // Deleted since it was loaded
+            Ok(()) | Err(StoreError::EntryNotFound(_)) => {}
+            Err(e) => return Err(e.into()),
+        }
+        jobs.update(notebook_id, |job| job.stored = stored + 1);
+    }
+
+    // Rebuild from storage so entries written during the recompute count
+    let rows = store.query_entries(&EntryQuery::new(notebook_id)).await?;
+    let current: Vec<Entry> = rows.iter().map(entry_row_to_snapshot_entry).collect();
+    let timestamp = current
+        .last()
+        .map(|e| e.causal_position)
+        .unwrap_or_else(CausalPosition::first);
+    let mut engine = state.engines().lock(nb_id).await;
+    engine.initialize_from_entries(nb_id, &current, timestamp);
+    drop(engine);
+    state.catalog_cache().invalidate(&nb_id);
+
+    Ok(costs.iter().map(|cost| cost.catalog_shift).sum())
+}
+
+/// Run a recompute of `notebook_id` already registered with
+/// [`RecomputeJobs::start`], recording its outcome.
+///
+/// Meant to be spawned; runs off the request path.
+pub async fn recompute_in_background(state: AppState, notebook_id: Uuid) {
+    let started = Instant::now();
+    let result = recompute_notebook(&state, notebook_id).await;
+    state.recompute_jobs().finish(notebook_id, &result);
+
+    match result {
+        Ok(entropy) => tracing::info!(
+            notebook_id = %notebook_id,
+            notebook_entropy = entropy,
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "Integration costs recomputed"
+        ),
+        Err(e) => tracing::error!(
+            notebook_id = %notebook_id,
+            error = %e,
+            "Failed to recompute integration costs"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notebook_core::{AuthorId, EntryBuilder};
+
+    fn entry(content: &str, sequence: u64, references: Vec<EntryId>) -> Entry {
+        EntryBuilder::default()
+            .content(content.as_bytes().to_vec())
+            .content_type("text/plain")
+            .author(AuthorId::zero())
+            .references(references)
+            .causal_position(CausalPosition {
+                sequence,
+                ..CausalPosition::first()
+            })
+            .build()
+    }
+
+    fn notebook() -> Vec<Entry> {
+        let first = entry("tokio async runtime tasks", 1, vec![]);
+        let second = entry("spawning tasks on the tokio runtime", 2, vec![first.id]);
+        let third = entry("baking sourdough bread", 3, vec![]);
+        let fourth = entry("sourdough starter for bread", 4, vec![third.id]);
+        let fifth = entry("watering houseplants in winter", 5, vec![]);
+        vec![first, second, third, fourth, fifth]
+    }
+
+    #[test]
+    fn test_recomputed_entropy_matches_sequential_writes() {
+        let config = CostConfig::default();
+        let nb_id = NotebookId::new();
+        let entries = notebook();
+
+        // Costs as each entry was written, one after another
+        let mut engine = IntegrationCostEngine::with_config(config.clone());
+        let written: Vec<IntegrationCost> = entries
+            .iter()
+            .map(|entry| engine.compute_cost(entry, nb_id).unwrap())
+            .collect();
+        let written_entropy: f64 = written.iter().map(|c| c.catalog_shift).sum();
+
+        // Recomputing, even from a shuffled load, reproduces them
+        let mut shuffled = entries.clone();
+        shuffled.reverse();
+        let mut progress = Vec::new();
+        let costs = replay_costs(
+            &config,
+            nb_id,
+            &mut shuffled,
+            &ReferenceResolution::default(),
+            |n| progress.push(n),
+        )
+        .unwrap();
+        let entropy: f64 = costs.iter().map(|c| c.catalog_shift).sum();
+
+        // Cluster vectors are summed in hash order, so shifts may differ in
+        // the last bits between engines
+        assert_eq!(costs.len(), written.len());
+        for (cost, expected) in costs.iter().zip(&written) {
+            assert_eq!(cost.entries_revised, expected.entries_revised);
+            assert_eq!(cost.references_broken, expected.references_broken);
+            assert_eq!(cost.orphan, expected.orphan);
+            assert!((cost.catalog_shift - expected.catalog_shift).abs() < 1e-9);
+        }
+        assert!((entropy - written_entropy).abs() < 1e-9);
+        assert!(entropy > 0.0);
+        assert_eq!(progress, vec![1, 2, 3, 4, 5]);
+        assert_eq!(
+            shuffled.iter().map(|e| e.id).collect::<Vec<_>>(),
+            entries.iter().map(|e| e.id).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_replay_follows_cost_config() {
+        let nb_id = NotebookId::new();
+        let halved = CostConfig {
+            catalog_shift_weight: 0.5,
+            ..CostConfig::default()
+        };
+        let references = ReferenceResolution::default();
+        let full = replay_costs(
+            &CostConfig::default(),
+            nb_id,
+            &mut notebook(),
+            &references,
+            |_| {},
+        )
+        .unwrap();
+        let half = replay_costs(&halved, nb_id, &mut notebook(), &references, |_| {}).unwrap();
+        assert!(half[0].catalog_shift < full[0].catalog_shift);
+    }
+
+    #[test]
+    fn test_replay_orphans_entries_whose_references_were_deleted() {
+        let nb_id = NotebookId::new();
+        let deleted = EntryId::new();
+        let mut entries = notebook();
+        entries.push(entry("medieval castle architecture", 6, vec![deleted]));
+
+        let resolved = ReferenceResolution::default();
+        let costs = replay_costs(
+            &CostConfig::default(),
+            nb_id,
+            &mut entries,
+            &resolved,
+            |_| {},
+        )
+        .unwrap();
+        assert!(!costs[5].orphan);
+
+        let missing = ReferenceResolution::with_missing([deleted]);
+        let costs = replay_costs(
+            &CostConfig::default(),
+            nb_id,
+            &mut entries,
+            &missing,
+            |_| {},
+        )
+        .unwrap();
+        assert!(costs[5].orphan);
+        // Entries with live references are unaffected
+        assert!(!costs[1].orphan);
+    }
+
+    #[test]
+    fn test_one_running_recompute_per_notebook() {
+        let jobs = RecomputeJobs::new();
+        let notebook_id = Uuid::new_v4();
+        assert!(jobs.get(notebook_id).is_none());
+
+        assert!(jobs.start(notebook_id));
+        assert!(!jobs.start(notebook_id));
+        assert!(jobs.start(Uuid::new_v4()));
+
+        jobs.update(notebook_id, |job| {
+            job.total = 3;
+            job.computed = 3;
+            job.stored = 2;
+        });
+        let status = jobs.get(notebook_id).unwrap();
+        assert_eq!(status.state, RecomputeState::Running);
+        assert_eq!((status.total, status.computed, status.stored), (3, 3, 2));
+
+        // Finished recomputes can be started again
+        jobs.finish(notebook_id, &Ok(1.5));
+        let status = jobs.get(notebook_id).unwrap();
+        assert_eq!(status.state, RecomputeState::Completed);
+        assert_eq!(status.notebook_entropy, Some(1.5));
+        assert!(status.finished.is_some());
+        assert!(jobs.start(notebook_id));
+        assert_eq!(jobs.get(notebook_id).unwrap().stored, 0);
+    }
+
+    #[test]
+    fn test_failed_recompute_records_error() {
+        let jobs = RecomputeJobs::new();
+        let notebook_id = Uuid::new_v4();
+        jobs.start(notebook_id);
+        jobs.finish(notebook_id, &Err(ApiError::Internal("boom".into())));
+
+        let status = jobs.get(notebook_id).unwrap();
+        assert_eq!(status.state, RecomputeState::Failed);
+        assert!(status.error.unwrap().contains("boom"));
+        assert_eq!(status.notebook_entropy, None);
+    }
+}
@@ -0,0 +1,66 @@
+//! Rebuilding the full-text search index from storage.
+//!
+//! The search index only holds data derived from stored entries, so a lost
+//! or corrupt index is repaired by re-indexing every notebook. The server
+//! runs a rebuild in the background on startup when the index had to be
+//! recreated, and on demand through `POST /admin/search/reindex`.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use notebook_core::{Entry, NotebookId};
+use notebook_entropy::SearchIndex;
+use notebook_store::{EntryQuery, Store};
+
+use crate::error::{ApiError, ApiResult};
+use crate::routes::suggest::entry_row_to_snapshot_entry;
+use crate::state::AppState;
+
+/// Re-index every notebook's entries into `index`.
+///
+/// Entries of encrypted notebooks are not loaded when the index is on disk,
+/// since it would skip them anyway. Returns the number of entries indexed.
+pub async fn rebuild_search_index(store: &Store, index: Arc<SearchIndex>) -> ApiResult<usize> {
+    let mut entries: Vec<(NotebookId, Entry, bool)> = Vec::new();
+    for notebook in store.list_all_notebooks().await? {
+        if notebook.encrypted && index.is_persistent() {
+            continue;
+        }
+        let rows = store.query_entries(&EntryQuery::new(notebook.id)).await?;
+        let notebook_id = NotebookId::from_uuid(notebook.id);
+        entries.extend(rows.iter().map(|row| {
+            (
+                notebook_id,
+                entry_row_to_snapshot_entry(row),
+                notebook.encrypted,
+            )
+        }));
+    }
+
+    // Tantivy writes block; keep them off the async workers
+    tokio::task::spawn_blocking(move || {
+        index.rebuild_from_entries(entries.iter().map(|(nb, entry, enc)| (*nb, entry, *enc)))
+    })
+    .await
+    .map_err(|e| ApiError::Internal(format!("search rebuild task failed: {}", e)))?
+    .map_err(|e| ApiError::Internal(format!("search rebuild failed: {}", e)))
+}
+
+/// Rebuild the configured search index, if any.
+///
+/// Meant to be spawned; runs off the request path and only logs failures.
+pub async fn rebuild_search_index_in_background(state: AppState) {
+    let Some(index) = state.search_index().cloned() else {
+        return;
+    };
+    let started = Instant::now();
+
+    match rebuild_search_index(state.store(), index).await {
+        Ok(indexed) => tracing::info!(
+            entries = indexed,
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "Search index rebuilt"
+        ),
+        Err(e) => tracing::error!(error = %e, "Failed to rebuild search index"),
+    }
+}
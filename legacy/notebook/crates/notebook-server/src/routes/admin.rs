@@ -0,0 +1,385 @@
+//! Server administration.
+//!
+//! Endpoints:
+//! - POST /admin/search/reindex
+//! - GET /admin/propagation/stats
+//! - POST /notebooks/{id}/recompute
+//! - GET /notebooks/{id}/recompute
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use notebook_store::StoreError;
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, require_scope};
+use crate::recompute::{RecomputeStatus, recompute_in_background};
+use crate::reindex::rebuild_search_index_in_background;
+use crate::state::AppState;
+
+// ============================================================================
+// Response Types
+// ============================================================================
+
+/// Response for POST /admin/search/reindex.
+#[derive(Debug, Serialize)]
+pub struct ReindexResponse {
+    /// Always "rebuilding"; the rebuild runs in the background.
+    pub status: &'static str,
+}
+
+/// Response for GET /admin/propagation/stats.
+#[derive(Debug, Serialize)]
+pub struct PropagationStatsResponse {
+    /// Jobs whose cost updates were applied.
+    pub jobs_processed: u64,
+    /// Entries updated across all processed jobs.
+    pub entries_updated: u64,
+    /// Jobs skipped because they had already completed.
+    pub jobs_skipped: u64,
+    /// Jobs whose cost update failed.
+    pub jobs_failed: u64,
+    /// Jobs waiting in the queue.
+    pub queue_depth: usize,
+    /// Failed jobs set aside as dead letters.
+    pub dead_letter_count: usize,
+}
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// POST /admin/search/reindex
+///
+/// Rebuilds the full-text search index from storage, in the background.
+/// Searches are refused until the rebuild completes.
+///
+/// # Response
+///
+/// - 202 Accepted: `{ "status": "rebuilding" }`
+/// - 403 Forbidden: Missing `notebook:admin` scope
+/// - 501 Not Implemented: No search index is configured
+async fn reindex_search(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+) -> ApiResult<(StatusCode, Json<ReindexResponse>)> {
+    require_scope(&identity, "notebook:admin", state.config())?;
+    if state.search_index().is_none() {
+        return Err(ApiError::NotImplemented(
+            "No search index is configured".to_string(),
+        ));
+    }
+
+    tracing::info!(author = %identity.author_id, "Search reindex requested");
+    state
+        .background_tasks()
+        .spawn(rebuild_search_index_in_background(state.clone()));
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ReindexResponse {
+            status: "rebuilding",
+        }),
+    ))
+}
+
+/// GET /admin/propagation/stats
+///
+/// Reports the cost propagation worker's counters since the server started,
+/// with its current queue depth and number of dead letters.
+///
+/// # Response
+///
+/// - 200 OK: `{ "jobs_processed": 12, "entries_updated": 40, "jobs_skipped": 0, "jobs_failed": 1, "queue_depth": 3, "dead_letter_count": 1 }`
+/// - 403 Forbidden: Missing `notebook:admin` scope
+/// - 501 Not Implemented: No propagation worker is running
+async fn propagation_stats(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+) -> ApiResult<Json<PropagationStatsResponse>> {
+    require_scope(&identity, "notebook:admin", state.config())?;
+    let monitor = state
+        .propagation()
+        .ok_or_else(|| ApiError::NotImplemented("No propagation worker is running".to_string()))?;
+
+    let stats = monitor.stats();
+    Ok(Json(PropagationStatsResponse {
+        jobs_processed: stats.jobs_processed,
+        entries_updated: stats.entries_updated,
+        jobs_skipped: stats.jobs_skipped,
+        jobs_failed: stats.jobs_failed,
+        queue_depth: monitor.queue_depth(),
+        dead_letter_count: monitor.dead_letter_count(),
+    }))
+}
+
+/// POST /notebooks/{notebook_id}/recompute
+///
+/// Recomputes the integration cost of every entry in the notebook, in the
+/// background, as if its entries were written again in sequence order. Use
+/// after bulk imports or other changes that leave stored costs stale.
+///
+/// # Response
+///
+/// - 202 Accepted: the new job's status, as for GET
+/// - 403 Forbidden: Missing `notebook:admin` scope
+/// - 404 Not Found: Notebook not found
+/// - 409 Conflict: A recompute of the notebook is already running
+async fn start_recompute(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+) -> ApiResult<(StatusCode, Json<RecomputeStatus>)> {
+    require_scope(&identity, "notebook:admin", state.config())?;
+    state
+        .store()
+        .get_notebook(notebook_id)
+        .await
+        .map_err(|e| match e {
+            StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+            other => ApiError::Store(other),
+        })?;
+
+    let jobs = state.recompute_jobs();
+    if !jobs.start(notebook_id) {
+        return Err(ApiError::Conflict(format!(
+            "A recompute of notebook {} is already running",
+            notebook_id
+        )));
+    }
+    let status = jobs
+        .get(notebook_id)
+        .ok_or_else(|| ApiError::Internal("Recompute job missing after start".into()))?;
+
+    tracing::info!(
+        notebook_id = %notebook_id,
+        author = %identity.author_id,
+        "Integration cost recompute requested"
+    );
+    state
+        .background_tasks()
+        .spawn(recompute_in_background(state.clone(), notebook_id));
+
+    Ok((StatusCode::ACCEPTED, Json(status)))
+}
+
+/// GET /notebooks/{notebook_id}/recompute
+///
+/// Reports the progress of the notebook's latest recompute since the
+/// server started.
+///
+/// # Response
+///
+/// - 200 OK: `{ "state": "running", "total": 120, "computed": 80, "stored": 40, ... }`
+/// - 403 Forbidden: Missing `notebook:admin` scope
+/// - 404 Not Found: No recompute of the notebook has been started
+async fn get_recompute(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+) -> ApiResult<Json<RecomputeStatus>> {
+    require_scope(&identity, "notebook:admin", state.config())?;
+    state
+        .recompute_jobs()
+        .get(notebook_id)
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("No recompute of notebook {}", notebook_id)))
+}
+
+/// Build admin routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/admin/search/reindex", post(reindex_search))
+        .route("/admin/propagation/stats", get(propagation_stats))
+        .route(
+            "/notebooks/{id}/recompute",
+            post(start_recompute).get(get_recompute),
+        )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use notebook_core::{AuthorId, EntryId, NotebookId};
+    use notebook_entropy::{
+        CostUpdater, NoOpCostUpdater, PropagationError, PropagationJob, PropagationQueue,
+        PropagationWorker, SearchIndex,
+    };
+    use notebook_store::Store;
+    use sqlx::PgPool;
+
+    use crate::config::ServerConfig;
+    use crate::error::ErrorCode;
+
+    /// App state over a pool that never connects.
+    fn state() -> AppState {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        AppState::new(Store::from_pool(pool), ServerConfig::default())
+    }
+
+    fn identity(scopes: &[&str]) -> AuthorIdentity {
+        AuthorIdentity {
+            author_id: AuthorId::zero(),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reindex_requires_admin_scope() {
+        let result = reindex_search(State(state()), identity(&["notebook:write"])).await;
+        assert!(matches!(
+            result,
+            Err(ApiError::Coded(ErrorCode::MissingScope, _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reindex_without_index_is_not_implemented() {
+        let result = reindex_search(State(state()), identity(&["notebook:admin"])).await;
+        assert!(matches!(result, Err(ApiError::NotImplemented(_))));
+    }
+
+    #[tokio::test]
+    async fn test_reindex_is_accepted_with_index() {
+        let state = state().with_search_index(SearchIndex::in_memory().unwrap());
+        let (status, Json(body)) = reindex_search(State(state), identity(&["notebook:admin"]))
+            .await
+            .unwrap();
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(body.status, "rebuilding");
+    }
+
+    #[tokio::test]
+    async fn test_propagation_stats_requires_admin_scope() {
+        let result = propagation_stats(State(state()), identity(&["notebook:read"])).await;
+        assert!(matches!(
+            result,
+            Err(ApiError::Coded(ErrorCode::MissingScope, _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_propagation_stats_without_worker_is_not_implemented() {
+        let result = propagation_stats(State(state()), identity(&["notebook:admin"])).await;
+        assert!(matches!(result, Err(ApiError::NotImplemented(_))));
+    }
+
+    /// Fails updates for one notebook.
+    struct FailsFor(NotebookId);
+
+    impl CostUpdater for FailsFor {
+        fn update_cumulative_cost(
+            &self,
+            notebook_id: NotebookId,
+            entry_ids: &[EntryId],
+            cost_delta: f64,
+        ) -> Result<usize, PropagationError> {
+            if notebook_id == self.0 {
+                return Err(PropagationError::UpdateFailed("notebook is locked".into()));
+            }
+            NoOpCostUpdater.update_cumulative_cost(notebook_id, entry_ids, cost_delta)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_propagation_stats_reflect_processed_jobs() {
+        let (healthy, broken) = (NotebookId::new(), NotebookId::new());
+        let queue = PropagationQueue::new();
+        // Long poll interval: jobs are only processed by the shutdown drain
+        let mut worker = PropagationWorker::new(queue.clone(), FailsFor(broken))
+            .with_poll_interval(Duration::from_secs(3600));
+        let handle = worker.start();
+        let state = state().with_propagation_monitor(worker.monitor());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let job = PropagationJob::new(healthy, vec![EntryId::new(), EntryId::new()], 0.5);
+        queue.enqueue(job.clone());
+        queue.enqueue(PropagationJob::new(healthy, vec![EntryId::new()], 0.5));
+        queue.enqueue(PropagationJob::new(broken, vec![EntryId::new()], 0.5));
+        // Replays an already-queued job, so it is skipped once the first completes
+        queue.enqueue(job);
+
+        let Json(before) = propagation_stats(State(state.clone()), identity(&["notebook:admin"]))
+            .await
+            .unwrap();
+        assert_eq!(before.queue_depth, 4);
+        assert_eq!(before.jobs_processed, 0);
+
+        worker.shutdown();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("worker exits after draining")
+            .unwrap();
+
+        let Json(after) = propagation_stats(State(state), identity(&["notebook:admin"]))
+            .await
+            .unwrap();
+        assert_eq!(after.jobs_processed, 2);
+        assert_eq!(after.entries_updated, 3);
+        assert_eq!(after.jobs_skipped, 1);
+        assert_eq!(after.jobs_failed, 1);
+        assert_eq!(after.queue_depth, 0);
+        assert_eq!(after.dead_letter_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_recompute_requires_admin_scope() {
+        let notebook_id = Uuid::new_v4();
+        let result = start_recompute(
+            State(state()),
+            identity(&["notebook:write"]),
+            Path(notebook_id),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ApiError::Coded(ErrorCode::MissingScope, _))
+        ));
+
+        let result = get_recompute(
+            State(state()),
+            identity(&["notebook:read"]),
+            Path(notebook_id),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ApiError::Coded(ErrorCode::MissingScope, _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_recompute_status_reports_latest_job() {
+        let state = state();
+        let notebook_id = Uuid::new_v4();
+        let result = get_recompute(
+            State(state.clone()),
+            identity(&["notebook:admin"]),
+            Path(notebook_id),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::NotFound(_))));
+
+        state.recompute_jobs().start(notebook_id);
+        let Json(status) = get_recompute(
+            State(state),
+            identity(&["notebook:admin"]),
+            Path(notebook_id),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status.state, crate::recompute::RecomputeState::Running);
+    }
+}
@@ -0,0 +1,270 @@
+//! Entropy alert configuration.
+//!
+//! Lets a notebook owner register a webhook that is called when writes push
+//! the notebook's recent entropy above a threshold; see [`crate::alerts`].
+//!
+//! Endpoints:
+//! - PUT /notebooks/{id}/alert - Set the threshold and webhook URL
+//! - GET /notebooks/{id}/alert - Get the configured alert
+//! - DELETE /notebooks/{id}/alert - Remove the alert
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    routing::put,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use notebook_store::{NotebookAlertRow, StoreError};
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, require_scope};
+use crate::state::AppState;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Request body for PUT /notebooks/{id}/alert.
+#[derive(Debug, Deserialize)]
+pub struct SetAlertRequest {
+    /// Recent entropy (summed catalog shift of the last 10 entries) above
+    /// which the alert fires.
+    pub entropy_threshold: f64,
+    /// HTTP(S) URL the alert is POSTed to.
+    pub webhook_url: String,
+}
+
+/// A notebook's entropy alert.
+#[derive(Debug, Serialize)]
+pub struct AlertResponse {
+    pub notebook_id: Uuid,
+    pub entropy_threshold: f64,
+    pub webhook_url: String,
+    /// Whether entropy is currently above the threshold (already alerted).
+    pub triggered: bool,
+    /// When the alert last fired, if ever.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_fired: Option<DateTime<Utc>>,
+}
+
+impl From<NotebookAlertRow> for AlertResponse {
+    fn from(row: NotebookAlertRow) -> Self {
+        Self {
+            notebook_id: row.notebook_id,
+            entropy_threshold: row.entropy_threshold,
+            webhook_url: row.webhook_url,
+            triggered: row.triggered,
+            last_fired: row.last_fired,
+        }
+    }
+}
+
+/// Response for DELETE /notebooks/{id}/alert.
+#[derive(Debug, Serialize)]
+pub struct DeleteAlertResponse {
+    pub notebook_id: Uuid,
+    pub deleted: bool,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Validate the threshold and webhook URL of an alert request.
+fn validate_alert_request(request: &SetAlertRequest) -> ApiResult<()> {
+    if !(request.entropy_threshold.is_finite() && request.entropy_threshold >= 0.0) {
+        return Err(ApiError::BadRequest(format!(
+            "entropy_threshold must be a non-negative number, got {}",
+            request.entropy_threshold
+        )));
+    }
+
+    let url = reqwest::Url::parse(&request.webhook_url)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid webhook_url: {}", e)))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(ApiError::BadRequest(format!(
+            "webhook_url must use http or https, got {}",
+            url.scheme()
+        )));
+    }
+    Ok(())
+}
+
+/// Require the caller to hold `notebook:admin` and own the notebook.
+async fn require_owner(
+    state: &AppState,
+    identity: &AuthorIdentity,
+    notebook_id: Uuid,
+) -> ApiResult<()> {
+    require_scope(identity, "notebook:admin", state.config())?;
+
+    let notebook = state
+        .store()
+        .get_notebook(notebook_id)
+        .await
+        .map_err(|e| match e {
+            StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+            other => ApiError::Store(other),
+        })?;
+
+    if notebook.owner_id.as_slice() != identity.author_id.as_bytes().as_slice() {
+        return Err(ApiError::Forbidden(
+            "Only the notebook owner can manage alerts".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// PUT /notebooks/{id}/alert - Set a notebook's entropy alert.
+///
+/// Replaces any existing alert and re-arms it.
+///
+/// # Response
+///
+/// - 200 OK: AlertResponse
+/// - 400 Bad Request: Negative threshold or invalid webhook URL
+/// - 403 Forbidden: Requester is not the owner
+/// - 404 Not Found: Notebook not found
+async fn set_alert(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+    Json(request): Json<SetAlertRequest>,
+) -> ApiResult<Json<AlertResponse>> {
+    validate_alert_request(&request)?;
+    require_owner(&state, &identity, notebook_id).await?;
+
+    let alert = state
+        .store()
+        .upsert_notebook_alert(notebook_id, request.entropy_threshold, &request.webhook_url)
+        .await
+        .map_err(|e| match e {
+            StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+            other => ApiError::Store(other),
+        })?;
+
+    tracing::info!(
+        notebook_id = %notebook_id,
+        entropy_threshold = alert.entropy_threshold,
+        "Entropy alert set"
+    );
+
+    state
+        .broadcaster()
+        .publish_from_log(state.store(), notebook_id)
+        .await;
+
+    Ok(Json(AlertResponse::from(alert)))
+}
+
+/// GET /notebooks/{id}/alert - Get a notebook's entropy alert.
+///
+/// # Response
+///
+/// - 200 OK: AlertResponse
+/// - 403 Forbidden: Requester is not the owner
+/// - 404 Not Found: Notebook not found or no alert configured
+async fn get_alert(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+) -> ApiResult<Json<AlertResponse>> {
+    require_owner(&state, &identity, notebook_id).await?;
+
+    let alert = state
+        .store()
+        .get_notebook_alert(notebook_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!("Notebook {} has no alert configured", notebook_id))
+        })?;
+
+    Ok(Json(AlertResponse::from(alert)))
+}
+
+/// DELETE /notebooks/{id}/alert - Remove a notebook's entropy alert.
+///
+/// # Response
+///
+/// - 200 OK: `{ "notebook_id": "...", "deleted": true }` (`false` if none was set)
+/// - 403 Forbidden: Requester is not the owner
+/// - 404 Not Found: Notebook not found
+async fn delete_alert(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+) -> ApiResult<Json<DeleteAlertResponse>> {
+    require_owner(&state, &identity, notebook_id).await?;
+
+    let deleted = state.store().delete_notebook_alert(notebook_id).await?;
+    if deleted {
+        state
+            .broadcaster()
+            .publish_from_log(state.store(), notebook_id)
+            .await;
+    }
+
+    Ok(Json(DeleteAlertResponse {
+        notebook_id,
+        deleted,
+    }))
+}
+
+/// Build alert routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route(
+        "/notebooks/{id}/alert",
+        put(set_alert).get(get_alert).delete(delete_alert),
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(entropy_threshold: f64, webhook_url: &str) -> SetAlertRequest {
+        SetAlertRequest {
+            entropy_threshold,
+            webhook_url: webhook_url.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_alert_request() {
+        assert!(validate_alert_request(&request(0.5, "https://example.com/hook")).is_ok());
+        assert!(validate_alert_request(&request(0.0, "http://localhost:8080/")).is_ok());
+
+        assert!(validate_alert_request(&request(-0.1, "https://example.com/hook")).is_err());
+        assert!(validate_alert_request(&request(f64::NAN, "https://example.com/hook")).is_err());
+        assert!(validate_alert_request(&request(0.5, "not a url")).is_err());
+        assert!(validate_alert_request(&request(0.5, "ftp://example.com/hook")).is_err());
+    }
+
+    #[test]
+    fn test_alert_response_omits_unfired_timestamp() {
+        let response = AlertResponse::from(NotebookAlertRow {
+            notebook_id: Uuid::new_v4(),
+            entropy_threshold: 0.5,
+            webhook_url: "https://example.com/hook".to_string(),
+            triggered: false,
+            created: Utc::now(),
+            last_fired: None,
+        });
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["entropy_threshold"], 0.5);
+        assert_eq!(json["triggered"], false);
+        assert!(json.get("last_fired").is_none());
+    }
+}
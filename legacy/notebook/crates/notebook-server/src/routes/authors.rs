@@ -12,6 +12,7 @@ use axum::{
     http::StatusCode,
     routing::{get, post},
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use notebook_store::NewAuthor;
@@ -41,7 +42,7 @@ pub struct AuthorResponse {
     /// Public key as hex.
     pub public_key: String,
     /// Creation timestamp.
-    pub created: String,
+    pub created: DateTime<Utc>,
 }
 
 /// POST /authors — Register a new author.
@@ -115,7 +116,7 @@ async fn get_author(
     Ok(Json(AuthorResponse {
         author_id: hex::encode(&row.id),
         public_key: hex::encode(&row.public_key),
-        created: row.created.to_rfc3339(),
+        created: row.created,
     }))
 }
 
@@ -146,4 +147,15 @@ mod tests {
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("author_id"));
     }
+
+    #[test]
+    fn test_author_created_is_rfc3339_utc() {
+        let response = AuthorResponse {
+            author_id: "00".repeat(32),
+            public_key: "11".repeat(32),
+            created: "2026-01-02T03:04:05+02:00".parse().unwrap(),
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["created"], "2026-01-02T01:04:05Z");
+    }
 }
@@ -5,29 +5,69 @@
 //!
 //! The catalog provides a dense summary of notebook contents within a token budget,
 //! allowing agents to quickly understand what's in a notebook without reading
-//! every entry.
+//! every entry. With `mode=digest` the catalog is rendered as a single prose
+//! paragraph instead, for the tightest context budgets.
+//!
+//! Responses carry `Cache-Control: max-age` matching the catalog cache's
+//! freshness window and an `Age` header giving how long ago the catalog was
+//! generated. `refresh=true` bypasses the cache and regenerates the catalog.
+//!
+//! `locale` names the language of the notebook's text, for notebooks not in
+//! the server's `CATALOG_LOCALE`. Catalogs in another locale are generated
+//! per request and not cached.
+//!
+//! `max_clusters` caps the number of clusters: the smallest are merged into
+//! one uncategorized cluster, so every entry is still counted. Capped
+//! catalogs are likewise generated per request.
+//!
+//! Catalog pages carry `X-Total-Count` and `Link` headers pointing at the
+//! neighbouring pages of clusters.
 //!
 //! Owned by: agent-browse (Task 3-3)
 
 use axum::{
     Json, Router,
-    extract::{Path, Query, State},
+    extract::{OriginalUri, Path, Query, State},
+    http::{
+        HeaderMap, HeaderValue,
+        header::{AGE, CACHE_CONTROL},
+    },
+    response::{IntoResponse, Response},
     routing::get,
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use notebook_core::{ActivityContext, AuthorId, CausalPosition, Entry, EntryId, IntegrationCost};
+use std::collections::HashSet;
+
+use notebook_core::{
+    ActivityContext, AuthorId, CausalPosition, Entry, EntryId, IntegrationCost, NotebookId,
+};
 use notebook_entropy::{
-    catalog::{CatalogGenerator, ClusterSummary, DEFAULT_MAX_TOKENS},
+    cache::{CacheConfig, CachedCatalog, CatalogCache},
+    catalog::{
+        Catalog, CatalogGenerator, CatalogSort, ClusterSummary, DEFAULT_MAX_TOKENS, SummaryMode,
+    },
+    clustering::ClusteringConfig,
     coherence::CoherenceSnapshot,
+    tfidf::Locale,
 };
-use notebook_store::{EntryQuery, StoreError};
+use notebook_store::{EntryQuery, EntryRow};
 
+use crate::config::ServerConfig;
 use crate::error::{ApiError, ApiResult};
-use crate::extract::{AuthorIdentity, require_scope};
+use crate::extract::ReaderIdentity;
+use crate::pagination::{PageParams, PageWindow, pagination_headers};
+use crate::public_reads::readable_notebook;
+use crate::routes::entries::{EntrySummary, summarize_entries};
 use crate::state::AppState;
 
+/// Query parameters that page through the catalog's clusters.
+const CLUSTER_PAGE_PARAMS: PageParams = PageParams {
+    offset: "cluster_offset",
+    limit: "cluster_limit",
+};
+
 // ============================================================================
 // Request/Response Types
 // ============================================================================
@@ -42,14 +82,80 @@ pub struct BrowseParams {
     /// Maximum tokens for the response (default: 4000).
     #[serde(default)]
     pub max_tokens: Option<usize>,
+
+    /// Maximum number of clusters to return (capped by the token budget).
+    #[serde(default)]
+    pub cluster_limit: Option<usize>,
+
+    /// Number of clusters to skip (default: 0).
+    #[serde(default)]
+    pub cluster_offset: Option<usize>,
+
+    /// Cluster ordering: `cost` (default), `size`, or `recency`.
+    #[serde(default)]
+    pub sort: Option<CatalogSort>,
+
+    /// Response shape: `catalog` (default) or `digest`.
+    #[serde(default)]
+    pub mode: Option<BrowseMode>,
+
+    /// Regenerate the catalog instead of serving it from the cache.
+    #[serde(default)]
+    pub refresh: bool,
+
+    /// Language of the notebook's text, e.g. `fr` (default: the server's).
+    #[serde(default)]
+    pub locale: Option<Locale>,
+
+    /// Maximum number of clusters, merging the smallest into one
+    /// uncategorized cluster (0 = unlimited).
+    #[serde(default)]
+    pub max_clusters: Option<usize>,
+}
+
+/// Shape of the BROWSE response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BrowseMode {
+    /// Cluster summaries as JSON ([`BrowseResponse`]).
+    #[default]
+    Catalog,
+    /// A single prose digest ([`DigestResponse`]).
+    Digest,
 }
 
 /// Response for the BROWSE endpoint.
 #[derive(Debug, Serialize)]
 pub struct BrowseResponse {
-    /// Cluster summaries ordered by significance.
+    /// Cluster summaries in the requested order and window.
     pub catalog: Vec<ClusterSummaryResponse>,
 
+    /// Total number of clusters (after query filtering) across all pages.
+    pub total_clusters: usize,
+
+    /// Overall entropy measure for the notebook.
+    pub notebook_entropy: f64,
+
+    /// Total number of entries in the notebook.
+    pub total_entries: u32,
+
+    /// Number of entries matching the query (only present if query was provided).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_matches: Option<usize>,
+}
+
+/// Response for the BROWSE endpoint with `mode=digest`.
+#[derive(Debug, Serialize)]
+pub struct DigestResponse {
+    /// Prose digest of the notebook, highest-cost clusters first.
+    pub digest: String,
+
+    /// Number of clusters described in the digest.
+    pub clusters_included: usize,
+
+    /// Total number of clusters (after query filtering).
+    pub total_clusters: usize,
+
     /// Overall entropy measure for the notebook.
     pub notebook_entropy: f64,
 
@@ -66,6 +172,9 @@ pub struct BrowseResponse {
 /// This is a simplified view of ClusterSummary for the API response.
 #[derive(Debug, Serialize)]
 pub struct ClusterSummaryResponse {
+    /// The cluster; see GET /notebooks/{id}/clusters/{cluster_id}/history.
+    pub cluster_id: u64,
+
     /// Topic extracted from cluster keywords.
     pub topic: String,
 
@@ -78,16 +187,24 @@ pub struct ClusterSummaryResponse {
     /// Total integration cost caused by entries in this cluster.
     pub cumulative_cost: f64,
 
-    /// Entries since last cluster modification (higher = more stable).
+    /// Entries written since the cluster last gained a member (higher =
+    /// more stable).
     pub stability: u64,
 
-    /// Representative entry IDs from this cluster.
+    /// Representative entry IDs from this cluster, pinned entries first.
     pub representative_entry_ids: Vec<Uuid>,
+
+    /// Summaries of the representative entries, in the same order.
+    pub representatives: Vec<EntrySummary>,
+
+    /// Whether the cluster holds a pinned entry.
+    pub pinned: bool,
 }
 
 impl From<&ClusterSummary> for ClusterSummaryResponse {
     fn from(summary: &ClusterSummary) -> Self {
         Self {
+            cluster_id: summary.cluster_id.0,
             topic: summary.topic.clone(),
             summary: summary.summary.clone(),
             entry_count: summary.entry_count,
@@ -98,67 +215,76 @@ impl From<&ClusterSummary> for ClusterSummaryResponse {
                 .iter()
                 .map(|id| id.0)
                 .collect(),
+            representatives: Vec::new(),
+            pinned: summary.pinned,
         }
     }
 }
 
 // ============================================================================
-// Route Handler
+// Helper Functions
 // ============================================================================
 
-/// GET /notebooks/{id}/browse - Get a dense catalog of notebook contents.
-///
-/// Returns a catalog of cluster summaries within the specified token budget.
-/// If a query is provided, filters to clusters containing matching entries.
-///
-/// # Query Parameters
-///
-/// - `query`: Optional search string to filter entries
-/// - `max_tokens`: Maximum token budget (default: 4000)
+/// Look up the cached catalog for an unfiltered browse.
 ///
-/// # Response
+/// With `refresh`, the cached catalog is dropped first, so the lookup misses
+/// and the caller regenerates it.
+fn cached_catalog(
+    cache: &CatalogCache,
+    notebook_id: &NotebookId,
+    sequence: u64,
+    refresh: bool,
+) -> Option<CachedCatalog> {
+    if refresh {
+        cache.invalidate(notebook_id);
+    }
+    cache.get_current(notebook_id, sequence)
+}
+
+/// `Cache-Control` and `Age` headers for a catalog generated `age_secs` ago.
 ///
-/// - 200 OK: BrowseResponse with catalog
-/// - 400 Bad Request: Invalid parameters
-/// - 404 Not Found: Notebook not found
-async fn browse_notebook(
-    State(state): State<AppState>,
-    identity: AuthorIdentity,
-    Path(notebook_id): Path<Uuid>,
-    Query(params): Query<BrowseParams>,
-) -> ApiResult<Json<BrowseResponse>> {
-    require_scope(&identity, "notebook:read", state.config())?;
-    let store = state.store();
+/// `max-age` is the catalog cache's freshness window; clients count the age
+/// against it. Catalogs depend on the caller's access, so only private
+/// caches may store them.
+fn cache_headers(config: &CacheConfig, age_secs: u64) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        CACHE_CONTROL,
+        HeaderValue::from_str(&format!("private, max-age={}", config.max_age_secs))
+            .expect("cache control is a valid header value"),
+    );
+    headers.insert(AGE, HeaderValue::from(age_secs));
+    headers
+}
 
-    // 1. Verify notebook exists
-    store.get_notebook(notebook_id).await.map_err(|e| match e {
-        StoreError::NotebookNotFound(id) => {
-            ApiError::NotFound(format!("Notebook {} not found", id))
-        }
-        other => ApiError::Store(other),
-    })?;
-
-    // 2. Get all entries for the notebook
-    let entry_query = EntryQuery {
-        notebook_id: Some(notebook_id),
-        topic: None,
-        author_id: None,
-        after_sequence: None,
-        limit: None,
-        newest_first: false,
-    };
+/// Fill in representative summaries for a page of clusters.
+async fn attach_representatives(
+    state: &AppState,
+    page: &mut [ClusterSummaryResponse],
+) -> ApiResult<()> {
+    let ids: Vec<Uuid> = page
+        .iter()
+        .flat_map(|cluster| cluster.representative_entry_ids.iter().copied())
+        .collect();
+    let summaries = summarize_entries(state.store(), &ids).await?;
 
-    let entry_rows = store.query_entries(&entry_query).await.map_err(|e| {
-        tracing::error!(error = %e, "Failed to query entries");
-        ApiError::Store(e)
-    })?;
+    for cluster in page {
+        cluster.representatives = cluster
+            .representative_entry_ids
+            .iter()
+            .filter_map(|id| summaries.get(id).cloned())
+            .collect();
+    }
+    Ok(())
+}
 
-    // 3. Convert EntryRows to Entries
+/// Convert stored entry rows into the entries catalog generation needs.
+///
+/// Activity context is not needed for catalogs and is left mostly empty.
+pub(crate) fn rows_to_entries(entry_rows: &[EntryRow]) -> ApiResult<Vec<Entry>> {
     let mut entries: Vec<Entry> = Vec::with_capacity(entry_rows.len());
 
-    for row in &entry_rows {
-        // Convert EntryRow to Entry using a simplified approach
-        // We don't need full activity context for catalog generation
+    for row in entry_rows {
         let author_bytes: [u8; 32] =
             row.author_id.as_slice().try_into().map_err(|_| {
                 ApiError::Internal("Invalid author_id length in database".to_string())
@@ -196,7 +322,47 @@ async fn browse_notebook(
         entries.push(entry);
     }
 
-    // 4. Build coherence snapshot from entries
+    Ok(entries)
+}
+
+/// IDs of the pinned entries among `entry_rows`.
+pub(crate) fn pinned_entry_ids(entry_rows: &[EntryRow]) -> Vec<EntryId> {
+    entry_rows
+        .iter()
+        .filter(|row| row.pinned)
+        .map(|row| EntryId::from_uuid(row.id))
+        .collect()
+}
+
+/// Build a coherence snapshot from entries and generate the full catalog.
+///
+/// Pinned entries are always listed as representatives of their cluster.
+/// Keywords and catalog text follow `locale`. Apart from the locale the
+/// catalog does not depend on any request parameters, so catalogs in the
+/// server's locale can be cached and shared between browse requests.
+pub(crate) fn generate_catalog(
+    config: &ServerConfig,
+    entries: &[Entry],
+    pinned: &[EntryId],
+    locale: Locale,
+) -> Catalog {
+    let clustering = ClusteringConfig {
+        locale,
+        ..config.cost_config().clustering
+    };
+    generate_catalog_with(clustering, config.catalog_summary_mode, entries, pinned)
+}
+
+/// Generate the full catalog with explicit clustering settings.
+///
+/// Keywords and catalog text follow `clustering.locale`.
+pub(crate) fn generate_catalog_with(
+    clustering: ClusteringConfig,
+    summary_mode: SummaryMode,
+    entries: &[Entry],
+    pinned: &[EntryId],
+) -> Catalog {
+    let locale = clustering.locale;
     let max_sequence = entries
         .iter()
         .map(|e| e.causal_position.sequence)
@@ -211,84 +377,219 @@ async fn browse_notebook(
         },
     };
 
-    let mut snapshot = CoherenceSnapshot::new();
-    snapshot.rebuild(&entries, timestamp);
+    let mut snapshot = CoherenceSnapshot::with_config(clustering);
+    snapshot.rebuild(entries, timestamp);
+
+    CatalogGenerator::new()
+        .with_pinned(pinned.iter().copied())
+        .with_locale(locale)
+        .with_summary_mode(summary_mode)
+        .generate_all(&snapshot, entries)
+}
 
-    // 5. Handle search query if provided
+/// Keep only clusters with a representative entry matching the query.
+///
+/// Returns the number of matching entries.
+pub(crate) fn filter_by_query(catalog: &mut Catalog, entries: &[Entry], query_str: &str) -> usize {
     // Note: Full Tantivy search integration depends on Task 3-2 completion.
     // For now, we use simple text matching as a fallback.
-    let (filtered_entry_ids, query_matches) = if let Some(ref query_str) = params.query {
-        // Simple text-based search fallback
-        // This will be replaced with Tantivy SearchIndex once Task 3-2 is fully integrated
-        let query_lower = query_str.to_lowercase();
-        let matching_ids: Vec<EntryId> = entries
+    let query_lower = query_str.to_lowercase();
+    let matching_set: HashSet<EntryId> = entries
+        .iter()
+        .filter(|entry| {
+            // Match against content (for text types)
+            let content_match = if entry.content_type.starts_with("text/") {
+                String::from_utf8_lossy(&entry.content)
+                    .to_lowercase()
+                    .contains(&query_lower)
+            } else {
+                false
+            };
+
+            // Match against topic
+            let topic_match = entry
+                .topic
+                .as_ref()
+                .map(|t| t.to_lowercase().contains(&query_lower))
+                .unwrap_or(false);
+
+            content_match || topic_match
+        })
+        .map(|e| e.id)
+        .collect();
+
+    tracing::debug!(
+        query = %query_str,
+        matches = matching_set.len(),
+        "Simple text search completed"
+    );
+
+    // Keep only clusters that contain at least one matching entry
+    catalog.clusters.retain(|cluster| {
+        cluster
+            .representative_entry_ids
             .iter()
-            .filter(|entry| {
-                // Match against content (for text types)
-                let content_match = if entry.content_type.starts_with("text/") {
-                    String::from_utf8_lossy(&entry.content)
-                        .to_lowercase()
-                        .contains(&query_lower)
-                } else {
-                    false
-                };
-
-                // Match against topic
-                let topic_match = entry
-                    .topic
-                    .as_ref()
-                    .map(|t| t.to_lowercase().contains(&query_lower))
-                    .unwrap_or(false);
-
-                content_match || topic_match
-            })
-            .map(|e| e.id)
-            .collect();
+            .any(|id| matching_set.contains(id))
+    });
 
-        let count = matching_ids.len();
-        tracing::debug!(
-            query = %query_str,
-            matches = count,
-            "Simple text search completed"
-        );
-        (Some(matching_ids), Some(count))
+    matching_set.len()
+}
+
+// ============================================================================
+// Route Handler
+// ============================================================================
+
+/// GET /notebooks/{id}/browse - Get a dense catalog of notebook contents.
+///
+/// Returns a catalog of cluster summaries within the specified token budget.
+/// If a query is provided, filters to clusters containing matching entries.
+/// Public notebooks can be browsed and searched without credentials.
+///
+/// # Query Parameters
+///
+/// - `query`: Optional search string to filter entries
+/// - `max_tokens`: Maximum token budget (default: 4000)
+/// - `cluster_limit`: Maximum clusters to return (capped by the token budget)
+/// - `cluster_offset`: Number of clusters to skip (default: 0)
+/// - `sort`: `cost` (default), `size`, or `recency`
+/// - `mode`: `catalog` (default), or `digest` for a prose digest of at most
+///   `max_tokens` tokens, ordered by cost; `sort` and paging do not apply
+/// - `refresh`: `true` to regenerate the catalog instead of serving it from
+///   the cache
+/// - `locale`: Language of the notebook's text, `en` or `fr` (default: the
+///   server's); selects stop words and the phrasing of generated text
+/// - `max_clusters`: Maximum clusters in the catalog; the smallest are merged
+///   into one uncategorized cluster (default: 0, unlimited)
+///
+/// # Response
+///
+/// - 200 OK: BrowseResponse with catalog, or DigestResponse in digest mode,
+///   with `Cache-Control` and `Age` headers; catalogs also carry
+///   `X-Total-Count` and `Link` pagination headers
+/// - 400 Bad Request: Invalid parameters
+/// - 401 Unauthorized: No credentials and the notebook is not public
+/// - 404 Not Found: Notebook not found
+/// - 429 Too Many Requests: Anonymous read limit exceeded
+async fn browse_notebook(
+    State(state): State<AppState>,
+    reader: ReaderIdentity,
+    OriginalUri(uri): OriginalUri,
+    Path(notebook_id): Path<Uuid>,
+    Query(params): Query<BrowseParams>,
+) -> ApiResult<Response> {
+    let store = state.store();
+
+    // 1. Verify notebook exists and may be read
+    let notebook = readable_notebook(&state, &reader, notebook_id).await?;
+
+    // 2. Serve an unfiltered browse from the catalog cache while it is fresh
+    // and no entry has been written since it was generated
+    let nb_id = NotebookId::from_uuid(notebook_id);
+    let sequence = notebook.current_sequence as u64;
+    let locale = params.locale.unwrap_or(state.config().catalog_locale);
+    let cacheable = locale == state.config().catalog_locale && params.max_clusters.is_none();
+    let cached = if params.query.is_none() && cacheable {
+        cached_catalog(state.catalog_cache(), &nb_id, sequence, params.refresh)
     } else {
-        (None, None)
+        None
     };
+    let mut headers = cache_headers(
+        state.catalog_cache().config(),
+        cached.as_ref().map_or(0, CachedCatalog::age_secs),
+    );
 
-    // 6. Generate catalog
     let max_tokens = params.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS);
-    let generator = CatalogGenerator::with_max_tokens(max_tokens);
-    let catalog = generator.generate(&snapshot, &entries, Some(max_tokens));
+    let (mut catalog, query_matches) = match cached {
+        Some(cached) => (cached.catalog, None),
+        None => {
+            // 3. Get all entries for the notebook and convert them
+            let entry_query = EntryQuery {
+                notebook_id: Some(notebook_id),
+                topic: None,
+                author_id: None,
+                after_sequence: None,
+                limit: None,
+                newest_first: false,
+            };
+
+            let entry_rows = store.query_entries(&entry_query).await.map_err(|e| {
+                tracing::error!(error = %e, "Failed to query entries");
+                ApiError::Store(e)
+            })?;
+            let entries = rows_to_entries(&entry_rows)?;
+
+            // 4. Generate the full catalog; the token budget bounds the page instead
+            let pinned = pinned_entry_ids(&entry_rows);
+            let mut clustering = ClusteringConfig {
+                locale,
+                ..state.config().cost_config().clustering
+            };
+            if let Some(max_clusters) = params.max_clusters {
+                clustering.max_clusters = max_clusters;
+            }
+            let mut catalog = generate_catalog_with(
+                clustering,
+                state.config().catalog_summary_mode,
+                &entries,
+                &pinned,
+            );
+            if cacheable {
+                state.catalog_cache().set(nb_id, catalog.clone(), sequence);
+            }
+
+            // 5. Filter catalog by search results if query was provided
+            let query_matches = params
+                .query
+                .as_deref()
+                .map(|query_str| filter_by_query(&mut catalog, &entries, query_str));
+            (catalog, query_matches)
+        }
+    };
 
-    // 7. Filter catalog by search results if query was provided
-    let filtered_catalog = if let Some(ref matching_ids) = filtered_entry_ids {
-        // Keep only clusters that contain at least one matching entry
-        let matching_set: std::collections::HashSet<EntryId> =
-            matching_ids.iter().copied().collect();
+    if params.mode.unwrap_or_default() == BrowseMode::Digest {
+        let digest = catalog.digest(max_tokens);
+        tracing::info!(
+            notebook_id = %notebook_id,
+            clusters = digest.clusters_included,
+            total_clusters = catalog.clusters.len(),
+            "Browse digest completed"
+        );
+        let response = DigestResponse {
+            digest: digest.text,
+            clusters_included: digest.clusters_included,
+            total_clusters: catalog.clusters.len(),
+            notebook_entropy: catalog.notebook_entropy,
+            total_entries: catalog.total_entries,
+            query_matches,
+        };
+        return Ok((headers, Json(response)).into_response());
+    }
 
-        catalog
-            .clusters
-            .iter()
-            .filter(|cluster| {
-                cluster
-                    .representative_entry_ids
-                    .iter()
-                    .any(|id| matching_set.contains(id))
-            })
-            .map(ClusterSummaryResponse::from)
-            .collect()
-    } else {
-        catalog
-            .clusters
-            .iter()
-            .map(ClusterSummaryResponse::from)
-            .collect()
+    if let Some(sort) = params.sort {
+        catalog.sort_clusters(sort);
+    }
+
+    let page_size = CatalogGenerator::clusters_within(max_tokens)
+        .min(params.cluster_limit.unwrap_or(usize::MAX));
+    let offset = params.cluster_offset.unwrap_or(0);
+    let mut page: Vec<ClusterSummaryResponse> = catalog
+        .page(offset, page_size)
+        .iter()
+        .map(ClusterSummaryResponse::from)
+        .collect();
+    attach_representatives(&state, &mut page).await?;
+    let window = PageWindow {
+        offset,
+        limit: page_size,
+        returned: page.len(),
+        total: catalog.clusters.len(),
     };
+    headers.extend(pagination_headers(&uri, CLUSTER_PAGE_PARAMS, &window));
 
-    // 8. Build response
+    // 6. Build response
     let response = BrowseResponse {
-        catalog: filtered_catalog,
+        catalog: page,
+        total_clusters: catalog.clusters.len(),
         notebook_entropy: catalog.notebook_entropy,
         total_entries: catalog.total_entries,
         query_matches,
@@ -298,11 +599,12 @@ async fn browse_notebook(
         notebook_id = %notebook_id,
         total_entries = response.total_entries,
         clusters = response.catalog.len(),
+        total_clusters = response.total_clusters,
         query_matches = ?response.query_matches,
         "Browse request completed"
     );
 
-    Ok(Json(response))
+    Ok((headers, Json(response)).into_response())
 }
 
 /// Build browse routes.
@@ -317,6 +619,7 @@ pub fn routes() -> Router<AppState> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use notebook_entropy::ClusterId;
 
     #[test]
     fn test_browse_params_deserialize_empty() {
@@ -347,31 +650,242 @@ mod tests {
         assert_eq!(params.max_tokens, Some(1000));
     }
 
+    #[test]
+    fn test_browse_params_deserialize_paging() {
+        let params: BrowseParams =
+            serde_urlencoded::from_str("cluster_limit=10&cluster_offset=20&sort=recency").unwrap();
+        assert_eq!(params.cluster_limit, Some(10));
+        assert_eq!(params.cluster_offset, Some(20));
+        assert_eq!(params.sort, Some(CatalogSort::Recency));
+    }
+
+    #[test]
+    fn test_browse_params_deserialize_mode() {
+        let params: BrowseParams =
+            serde_urlencoded::from_str("mode=digest&max_tokens=300").unwrap();
+        assert_eq!(params.mode, Some(BrowseMode::Digest));
+        assert_eq!(params.max_tokens, Some(300));
+
+        let params: BrowseParams = serde_urlencoded::from_str("").unwrap();
+        assert_eq!(params.mode.unwrap_or_default(), BrowseMode::Catalog);
+        assert!(serde_urlencoded::from_str::<BrowseParams>("mode=essay").is_err());
+    }
+
+    #[test]
+    fn test_browse_params_deserialize_locale() {
+        let params: BrowseParams = serde_urlencoded::from_str("locale=fr").unwrap();
+        assert_eq!(params.locale, Some(Locale::French));
+        let params: BrowseParams = serde_urlencoded::from_str("").unwrap();
+        assert!(params.locale.is_none());
+        assert!(serde_urlencoded::from_str::<BrowseParams>("locale=xx").is_err());
+    }
+
+    #[test]
+    fn test_generate_catalog_in_french() {
+        let entries: Vec<Entry> = [
+            "Le château a des murs de pierre et une tour médiévale.",
+            "La tour du château est construite avec des pierres taillées.",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, content)| {
+            notebook_core::EntryBuilder::default()
+                .content(content.as_bytes().to_vec())
+                .content_type("text/plain")
+                .author(notebook_core::AuthorId::zero())
+                .causal_position(CausalPosition {
+                    sequence: i as u64 + 1,
+                    ..Default::default()
+                })
+                .build()
+        })
+        .collect();
+
+        let catalog = generate_catalog(&ServerConfig::default(), &entries, &[], Locale::French);
+        assert_eq!(catalog.locale, Locale::French);
+        for cluster in &catalog.clusters {
+            for keyword in cluster.topic.split(", ") {
+                assert!(!Locale::French.stop_words().contains(&keyword));
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_catalog_uses_configured_summary_mode() {
+        let entries: Vec<Entry> = ["Sourdough starter needs flour. Feed it daily."]
+            .into_iter()
+            .map(|content| {
+                notebook_core::EntryBuilder::default()
+                    .content(content.as_bytes().to_vec())
+                    .content_type("text/plain")
+                    .author(notebook_core::AuthorId::zero())
+                    .causal_position(CausalPosition::first())
+                    .build()
+            })
+            .collect();
+
+        let config = ServerConfig {
+            catalog_summary_mode: SummaryMode::Keyword,
+            ..ServerConfig::default()
+        };
+        let catalog = generate_catalog(&config, &entries, &[], Locale::English);
+        let cluster = &catalog.clusters[0];
+        assert!(cluster.summary.starts_with(&cluster.topic));
+        assert!(!cluster.summary.ends_with('.'));
+    }
+
+    #[test]
+    fn test_max_clusters_merges_overflow_into_one_cluster() {
+        let topics = [
+            "tokio async runtime",
+            "sourdough bread starter",
+            "houseplant watering schedule",
+            "bicycle chain lubrication",
+            "jupiter moons telescope",
+            "violin bow rosin",
+            "chess opening theory",
+            "glacier ice retreat",
+            "espresso grind size",
+            "origami crane folding",
+        ];
+        let entries: Vec<Entry> = topics
+            .iter()
+            .enumerate()
+            .map(|(i, content)| {
+                notebook_core::EntryBuilder::default()
+                    .content(content.as_bytes().to_vec())
+                    .content_type("text/plain")
+                    .author(notebook_core::AuthorId::zero())
+                    .causal_position(CausalPosition {
+                        sequence: i as u64 + 1,
+                        ..Default::default()
+                    })
+                    .build()
+            })
+            .collect();
+
+        let uncapped = generate_catalog(&ServerConfig::default(), &entries, &[], Locale::English);
+        assert_eq!(uncapped.clusters.len(), topics.len());
+
+        let clustering = ClusteringConfig {
+            max_clusters: 3,
+            ..ServerConfig::default().cost_config().clustering
+        };
+        let catalog = generate_catalog_with(clustering, SummaryMode::default(), &entries, &[]);
+        assert_eq!(catalog.clusters.len(), 3);
+        assert_eq!(catalog.total_entries, topics.len() as u32);
+        let counted: u32 = catalog.clusters.iter().map(|c| c.entry_count).sum();
+        assert_eq!(counted, catalog.total_entries);
+        assert_eq!(
+            catalog
+                .clusters
+                .iter()
+                .filter(|c| c.topic == "uncategorized")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_browse_params_deserialize_max_clusters() {
+        let params: BrowseParams = serde_urlencoded::from_str("max_clusters=3").unwrap();
+        assert_eq!(params.max_clusters, Some(3));
+        let params: BrowseParams = serde_urlencoded::from_str("").unwrap();
+        assert!(params.max_clusters.is_none());
+    }
+
+    #[test]
+    fn test_browse_params_deserialize_refresh() {
+        let params: BrowseParams = serde_urlencoded::from_str("refresh=true").unwrap();
+        assert!(params.refresh);
+        let params: BrowseParams = serde_urlencoded::from_str("").unwrap();
+        assert!(!params.refresh);
+    }
+
+    fn empty_catalog() -> Catalog {
+        Catalog {
+            clusters: vec![],
+            notebook_entropy: 0.0,
+            total_entries: 0,
+            generated_at: CausalPosition::first(),
+            locale: Locale::default(),
+        }
+    }
+
+    #[test]
+    fn test_second_browse_is_served_from_cache() {
+        let cache = CatalogCache::new();
+        let notebook_id = NotebookId::new();
+
+        assert!(cached_catalog(&cache, &notebook_id, 1, false).is_none());
+        cache.set(notebook_id, empty_catalog(), 1);
+        assert!(cached_catalog(&cache, &notebook_id, 1, false).is_some());
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+    }
+
+    #[test]
+    fn test_refresh_bypasses_cache() {
+        let cache = CatalogCache::new();
+        let notebook_id = NotebookId::new();
+        cache.set(notebook_id, empty_catalog(), 1);
+
+        assert!(cached_catalog(&cache, &notebook_id, 1, true).is_none());
+        assert_eq!(cache.stats().misses, 1);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_cache_headers_report_age() {
+        let cached = CachedCatalog {
+            catalog: empty_catalog(),
+            cached_at: std::time::Instant::now() - std::time::Duration::from_secs(3),
+            cached_at_sequence: 1,
+        };
+        let headers = cache_headers(&CacheConfig::default(), cached.age_secs());
+
+        assert_eq!(headers[AGE], "3");
+        assert_eq!(headers[CACHE_CONTROL], "private, max-age=300");
+        assert_eq!(cache_headers(&CacheConfig::new(0.1, 60), 0)[AGE], "0");
+    }
+
+    #[test]
+    fn test_browse_params_reject_unknown_sort() {
+        assert!(serde_urlencoded::from_str::<BrowseParams>("sort=alphabetical").is_err());
+    }
+
     #[test]
     fn test_cluster_summary_response_from() {
         let summary = ClusterSummary {
+            cluster_id: ClusterId::new(9),
             topic: "test topic".to_string(),
             summary: "Test summary.".to_string(),
             entry_count: 5,
             cumulative_cost: 1.5,
             stability: 10,
             representative_entry_ids: vec![EntryId::new()],
+            pinned: true,
         };
 
         let response = ClusterSummaryResponse::from(&summary);
 
+        assert_eq!(response.cluster_id, 9);
         assert_eq!(response.topic, "test topic");
         assert_eq!(response.summary, "Test summary.");
         assert_eq!(response.entry_count, 5);
         assert_eq!(response.cumulative_cost, 1.5);
         assert_eq!(response.stability, 10);
         assert_eq!(response.representative_entry_ids.len(), 1);
+        assert!(response.representatives.is_empty());
+        assert!(response.pinned);
     }
 
     #[test]
     fn test_browse_response_serialize_without_query_matches() {
         let response = BrowseResponse {
             catalog: vec![],
+            total_clusters: 0,
             notebook_entropy: 5.5,
             total_entries: 100,
             query_matches: None,
@@ -389,6 +903,7 @@ mod tests {
     fn test_browse_response_serialize_with_query_matches() {
         let response = BrowseResponse {
             catalog: vec![],
+            total_clusters: 0,
             notebook_entropy: 5.5,
             total_entries: 100,
             query_matches: Some(25),
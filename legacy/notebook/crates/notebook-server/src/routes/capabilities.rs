@@ -0,0 +1,125 @@
+//! Server capability discovery.
+//!
+//! Some features depend on the deployment: native graph queries need the
+//! Apache AGE extension, and encrypted notebooks need a master key. Clients query this endpoint to adapt instead of
+//! discovering missing features through errors.
+//!
+//! Endpoint: GET /capabilities (no authentication required)
+
+use axum::{Json, Router, extract::State, routing::get};
+use serde::Serialize;
+
+use crate::state::AppState;
+
+/// Response for GET /capabilities.
+#[derive(Debug, Serialize)]
+pub struct CapabilitiesResponse {
+    /// Native graph queries (Cypher via Apache AGE). Reference traversal
+    /// works without it through SQL fallbacks; endpoints that need native
+    /// graph queries return 501 Not Implemented when this is false.
+    pub graph_queries: bool,
+    /// Text search when browsing a notebook.
+    pub search: bool,
+    /// Reference suggestions from the coherence model.
+    pub reference_suggestions: bool,
+    /// Notebooks whose entry content is encrypted at rest. Creating one
+    /// returns 400 Bad Request when this is false.
+    pub encrypted_notebooks: bool,
+}
+
+impl CapabilitiesResponse {
+    /// Derive capabilities from the store's detected features.
+    pub fn detect(age_available: bool, encryption_available: bool) -> Self {
+        Self {
+            graph_queries: age_available,
+            search: true,
+            reference_suggestions: true,
+            encrypted_notebooks: encryption_available,
+        }
+    }
+}
+
+/// GET /capabilities - Report optional server capabilities.
+async fn get_capabilities(State(state): State<AppState>) -> Json<CapabilitiesResponse> {
+    let store = state.store();
+    Json(CapabilitiesResponse::detect(
+        store.age_available(),
+        store.encryption_available(),
+    ))
+}
+
+/// Build capability routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/capabilities", get(get_capabilities))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use axum::response::IntoResponse;
+    use notebook_store::{Store, StoreError};
+    use sqlx::PgPool;
+    use tower::ServiceExt;
+
+    use crate::config::ServerConfig;
+    use crate::error::ApiError;
+
+    /// App state over a pool that never connects; AGE is reported unavailable.
+    fn state_without_age() -> AppState {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        AppState::new(Store::from_pool(pool), ServerConfig::default())
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_without_age() {
+        let app = crate::routes::build_router(state_without_age());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/capabilities")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["graph_queries"], false);
+        assert_eq!(json["search"], true);
+        assert_eq!(json["encrypted_notebooks"], false);
+    }
+
+    #[tokio::test]
+    async fn test_graph_query_without_age_returns_501() {
+        let state = state_without_age();
+        let err = state
+            .store()
+            .graph()
+            .execute_cypher("MATCH (n) RETURN n")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StoreError::GraphUnavailable(_)));
+
+        let response = ApiError::from(err).into_response();
+        assert_eq!(response.status(), StatusCode::NOT_IMPLEMENTED);
+    }
+
+    #[test]
+    fn test_detect_with_age() {
+        assert!(CapabilitiesResponse::detect(true, false).graph_queries);
+    }
+
+    #[test]
+    fn test_detect_with_master_key() {
+        let capabilities = CapabilitiesResponse::detect(false, true);
+        assert!(capabilities.encrypted_notebooks);
+        assert!(!capabilities.graph_queries);
+    }
+}
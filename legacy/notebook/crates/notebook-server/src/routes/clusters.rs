@@ -0,0 +1,289 @@
+//! Cluster churn history.
+//!
+//! A browse catalog reports each cluster's `stability`: how many entries
+//! were written since it last gained a member. This endpoint shows how much
+//! the cluster moved over the notebook's recent writes, replayed from the
+//! stored entries: which writes changed its membership or topic keywords,
+//! and by how much. Agents use it to tell volatile knowledge areas from
+//! settled ones.
+//!
+//! Cluster ids are the `cluster_id`s listed by browse.
+//!
+//! Endpoint: GET /notebooks/{notebook_id}/clusters/{cluster_id}/history?writes={n}
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    routing::get,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use notebook_core::{CausalPosition, Entry};
+use notebook_entropy::{
+    ClusterChange, ClusterHistory, ClusterId, ClusteringConfig, CoherenceSnapshot,
+    DEFAULT_HISTORY_WRITES, cluster_history,
+};
+use notebook_store::{EntryQuery, StoreError};
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, require_scope};
+use crate::routes::browse::rows_to_entries;
+use crate::state::AppState;
+
+/// Maximum number of recent writes replayed in one request.
+pub const MAX_HISTORY_WRITES: usize = 500;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Query parameters for the cluster history endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct ClusterHistoryParams {
+    /// Number of recent writes to replay.
+    #[serde(default)]
+    pub writes: Option<usize>,
+}
+
+/// Response for GET /notebooks/{id}/clusters/{cluster_id}/history.
+#[derive(Debug, Serialize)]
+pub struct ClusterHistoryResponse {
+    pub notebook_id: Uuid,
+    pub cluster_id: ClusterId,
+    /// Current topic keywords.
+    pub topic_keywords: Vec<String>,
+    /// Current number of entries.
+    pub entry_count: usize,
+    /// Entries written since the cluster last gained a member.
+    pub stability: u64,
+    /// Number of recent writes replayed.
+    pub writes_observed: usize,
+    /// Mean membership churn per replayed write (0.0 to 1.0).
+    pub churn: f64,
+    /// Mean keyword churn per replayed write (0.0 to 1.0).
+    pub keyword_churn: f64,
+    /// The replayed writes that changed the cluster, oldest first.
+    pub changes: Vec<ClusterChange>,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Resolve the number of writes to replay, applying the default and the
+/// maximum.
+fn effective_writes(params: &ClusterHistoryParams) -> ApiResult<usize> {
+    match params.writes {
+        Some(0) => Err(ApiError::BadRequest(
+            "writes must be at least 1".to_string(),
+        )),
+        Some(writes) => Ok(writes.min(MAX_HISTORY_WRITES)),
+        None => Ok(DEFAULT_HISTORY_WRITES),
+    }
+}
+
+/// Report how `cluster_id` churned over the last `writes` of `entries`.
+///
+/// The cluster is looked up in a snapshot rebuilt from all `entries`, as
+/// browse builds it. Returns `None` if there is no such cluster.
+fn build_history(
+    config: &ClusteringConfig,
+    notebook_id: Uuid,
+    entries: &[Entry],
+    cluster_id: ClusterId,
+    writes: usize,
+) -> Option<ClusterHistoryResponse> {
+    let latest = entries
+        .last()
+        .map(|e| e.causal_position)
+        .unwrap_or_else(CausalPosition::first);
+    let mut snapshot = CoherenceSnapshot::with_config(config.clone());
+    snapshot.rebuild(entries, latest);
+    let cluster = snapshot.get_cluster(cluster_id)?;
+
+    let last_joined = entries
+        .iter()
+        .filter(|e| cluster.entry_ids.contains(&e.id))
+        .map(|e| e.causal_position.sequence)
+        .max()
+        .unwrap_or(0);
+
+    let ClusterHistory {
+        writes_observed,
+        churn,
+        keyword_churn,
+        changes,
+        ..
+    } = cluster_history(config, entries, cluster_id, writes);
+
+    Some(ClusterHistoryResponse {
+        notebook_id,
+        cluster_id,
+        topic_keywords: cluster.topic_keywords.clone(),
+        entry_count: cluster.size(),
+        stability: latest.sequence.saturating_sub(last_joined),
+        writes_observed,
+        churn,
+        keyword_churn,
+        changes,
+    })
+}
+
+// ============================================================================
+// Route Handler
+// ============================================================================
+
+/// GET /notebooks/{notebook_id}/clusters/{cluster_id}/history
+///
+/// Reports how a cluster's membership and topic keywords churned across the
+/// notebook's recent writes.
+///
+/// # Query Parameters
+///
+/// - `writes`: Number of recent writes to replay (default: 50, max: 500)
+///
+/// # Response
+///
+/// - 200 OK: ClusterHistoryResponse
+/// - 400 Bad Request: `writes` is zero
+/// - 404 Not Found: Notebook or cluster not found
+async fn get_cluster_history(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path((notebook_id, cluster_id)): Path<(Uuid, u64)>,
+    Query(params): Query<ClusterHistoryParams>,
+) -> ApiResult<Json<ClusterHistoryResponse>> {
+    require_scope(&identity, "notebook:read", state.config())?;
+    let writes = effective_writes(&params)?;
+    let store = state.store();
+
+    store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+
+    let rows = store.query_entries(&EntryQuery::new(notebook_id)).await?;
+    let entries = rows_to_entries(&rows)?;
+
+    let cluster_id = ClusterId::new(cluster_id);
+    let config = state.config().cost_config().clustering;
+    build_history(&config, notebook_id, &entries, cluster_id, writes)
+        .map(Json)
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "Cluster {} not found in notebook {}",
+                cluster_id.0, notebook_id
+            ))
+        })
+}
+
+/// Build cluster history routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route(
+        "/notebooks/{id}/clusters/{cluster_id}/history",
+        get(get_cluster_history),
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notebook_core::{AuthorId, EntryBuilder};
+
+    fn make_text_entry(content: &str, sequence: u64) -> Entry {
+        EntryBuilder::default()
+            .content(content.as_bytes().to_vec())
+            .content_type("text/plain")
+            .author(AuthorId::zero())
+            .causal_position(CausalPosition {
+                sequence,
+                ..Default::default()
+            })
+            .build()
+    }
+
+    fn notebook() -> Vec<Entry> {
+        [
+            "medieval castle architecture stone walls",
+            "castle stone walls medieval towers",
+            "medieval castle towers stone architecture",
+            "pasta tomato sauce recipe",
+            "pasta tomato sauce garlic basil",
+            "pasta sauce garlic basil olive oil",
+            "pasta olive oil garlic parmesan",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, content)| make_text_entry(content, i as u64 + 1))
+        .collect()
+    }
+
+    #[test]
+    fn test_effective_writes() {
+        let params = |writes| ClusterHistoryParams { writes };
+        assert_eq!(
+            effective_writes(&params(None)).unwrap(),
+            DEFAULT_HISTORY_WRITES
+        );
+        assert_eq!(effective_writes(&params(Some(10))).unwrap(), 10);
+        assert_eq!(
+            effective_writes(&params(Some(MAX_HISTORY_WRITES + 1))).unwrap(),
+            MAX_HISTORY_WRITES
+        );
+        assert!(effective_writes(&params(Some(0))).is_err());
+    }
+
+    #[test]
+    fn test_active_cluster_reports_more_churn() {
+        let entries = notebook();
+        let config = ClusteringConfig::default();
+        let notebook_id = Uuid::new_v4();
+        let mut snapshot = CoherenceSnapshot::with_config(config.clone());
+        snapshot.rebuild(&entries, CausalPosition::first());
+        let castle = snapshot.get_entry_cluster(&entries[0].id).unwrap().id;
+        let pasta = snapshot.get_entry_cluster(&entries[3].id).unwrap().id;
+
+        let settled = build_history(&config, notebook_id, &entries, castle, 3).unwrap();
+        let active = build_history(&config, notebook_id, &entries, pasta, 3).unwrap();
+
+        assert!(settled.stability >= 4);
+        assert_eq!(settled.churn, 0.0);
+        assert!(settled.changes.is_empty());
+        assert_eq!(active.stability, 0);
+        assert!(active.churn > settled.churn);
+        assert_eq!(active.changes.last().unwrap().sequence, 7);
+    }
+
+    #[test]
+    fn test_unknown_cluster_is_none() {
+        let entries = notebook();
+        let history = build_history(
+            &ClusteringConfig::default(),
+            Uuid::new_v4(),
+            &entries,
+            ClusterId::new(42),
+            10,
+        );
+        assert!(history.is_none());
+    }
+
+    #[test]
+    fn test_response_serializes_cluster_id_as_number() {
+        let entries = notebook();
+        let config = ClusteringConfig::default();
+        let mut snapshot = CoherenceSnapshot::with_config(config.clone());
+        snapshot.rebuild(&entries, CausalPosition::first());
+        let cluster_id = snapshot.get_entry_cluster(&entries[0].id).unwrap().id;
+
+        let response = build_history(&config, Uuid::nil(), &entries, cluster_id, 5).unwrap();
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["cluster_id"], cluster_id.0);
+        assert_eq!(json["writes_observed"], 5);
+        assert!(json["changes"].is_array());
+    }
+}
@@ -0,0 +1,257 @@
+//! Coherence snapshot introspection.
+//!
+//! Shows the clusters the integration cost engine currently holds for a
+//! notebook: their keywords, sizes and member entries, with the snapshot's
+//! summary statistics. It is a debugging aid for clustering behaviour and
+//! is restricted to administrators, since it lists every entry id in the
+//! notebook regardless of what the caller would otherwise see.
+//!
+//! If the engine holds no snapshot for the notebook, typically after a
+//! restart, one is rebuilt from storage first, as it would be on the next
+//! write.
+//!
+//! Endpoint: GET /notebooks/{notebook_id}/coherence
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    routing::get,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use notebook_core::{CausalPosition, NotebookId};
+use notebook_entropy::{Cluster, CoherenceSnapshot, CoherenceStats};
+use notebook_store::{EntryQuery, StoreError};
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, require_scope};
+use crate::routes::suggest::entry_row_to_snapshot_entry;
+use crate::state::AppState;
+
+// ============================================================================
+// Response Types
+// ============================================================================
+
+/// One cluster of the snapshot.
+#[derive(Debug, Serialize)]
+pub struct ClusterView {
+    /// Id, keywords, member entry ids and reference density.
+    #[serde(flatten)]
+    pub cluster: Cluster,
+    /// Number of member entries.
+    pub size: usize,
+}
+
+/// Response for GET /notebooks/{id}/coherence.
+#[derive(Debug, Serialize)]
+pub struct CoherenceResponse {
+    /// The notebook the snapshot models.
+    pub notebook_id: Uuid,
+    /// Sequence of the last write the snapshot was built or updated at.
+    pub sequence: u64,
+    /// Whether the snapshot was rebuilt from storage for this request.
+    pub rebuilt: bool,
+    /// Summary statistics over the clusters.
+    pub stats: CoherenceStats,
+    /// Clusters, largest first.
+    pub clusters: Vec<ClusterView>,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Describe `snapshot` for the response.
+fn coherence_response(
+    notebook_id: Uuid,
+    snapshot: &CoherenceSnapshot,
+    rebuilt: bool,
+) -> CoherenceResponse {
+    let mut clusters: Vec<ClusterView> = snapshot
+        .clusters
+        .iter()
+        .map(|cluster| ClusterView {
+            size: cluster.size(),
+            cluster: cluster.clone(),
+        })
+        .collect();
+    clusters.sort_by(|a, b| {
+        b.size
+            .cmp(&a.size)
+            .then(a.cluster.id.0.cmp(&b.cluster.id.0))
+    });
+
+    CoherenceResponse {
+        notebook_id,
+        sequence: snapshot.timestamp.sequence,
+        rebuilt,
+        stats: snapshot.stats(),
+        clusters,
+    }
+}
+
+// ============================================================================
+// Route Handler
+// ============================================================================
+
+/// GET /notebooks/{notebook_id}/coherence
+///
+/// Returns the notebook's current coherence snapshot.
+///
+/// # Response
+///
+/// - 200 OK: `{ "notebook_id": "...", "sequence": 42, "rebuilt": false, "stats": {...}, "clusters": [...] }`
+/// - 403 Forbidden: Missing `notebook:admin` scope
+/// - 404 Not Found: Notebook not found
+async fn get_coherence(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+) -> ApiResult<Json<CoherenceResponse>> {
+    require_scope(&identity, "notebook:admin", state.config())?;
+    let store = state.store();
+
+    store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+
+    let nb_id = NotebookId::from_uuid(notebook_id);
+    let tracked = state
+        .engines()
+        .lock(nb_id)
+        .await
+        .get_snapshot(nb_id)
+        .is_some();
+
+    // Load entries without holding the engine lock, then rebuild
+    let rebuild_from = if tracked {
+        None
+    } else {
+        let rows = store.query_entries(&EntryQuery::new(notebook_id)).await?;
+        Some(
+            rows.iter()
+                .map(entry_row_to_snapshot_entry)
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    let mut engine = state.engines().lock(nb_id).await;
+    // A concurrent request may have built the snapshot in the meantime
+    let mut rebuilt = false;
+    if let Some(entries) = rebuild_from.filter(|_| engine.get_snapshot(nb_id).is_none()) {
+        let timestamp = entries
+            .last()
+            .map(|e| e.causal_position)
+            .unwrap_or_else(CausalPosition::first);
+        engine.initialize_from_entries(nb_id, &entries, timestamp);
+        rebuilt = true;
+        tracing::info!(
+            notebook_id = %notebook_id,
+            entries = entries.len(),
+            "Rebuilt coherence snapshot for introspection"
+        );
+    }
+
+    let snapshot = engine
+        .get_snapshot(nb_id)
+        .ok_or_else(|| ApiError::Internal("Coherence snapshot missing after rebuild".into()))?;
+    Ok(Json(coherence_response(notebook_id, snapshot, rebuilt)))
+}
+
+/// Build coherence introspection routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/notebooks/{id}/coherence", get(get_coherence))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notebook_core::{AuthorId, Entry};
+    use notebook_entropy::IntegrationCostEngine;
+    use notebook_store::{EntryRow, Store};
+    use sqlx::PgPool;
+
+    use crate::config::ServerConfig;
+    use crate::error::ErrorCode;
+
+    #[test]
+    fn test_cluster_membership_covers_every_entry() {
+        let notebook_id = Uuid::new_v4();
+        let rows: Vec<EntryRow> = [
+            "tokio async runtime tasks and executors",
+            "spawning async tasks on the tokio runtime",
+            "baking sourdough bread with starter",
+            "sourdough starter feeding for bread",
+            "watering houseplants in winter",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, content)| EntryRow::test_row(notebook_id, i as i64 + 1, content))
+        .collect();
+        let entries: Vec<Entry> = rows.iter().map(entry_row_to_snapshot_entry).collect();
+
+        let nb_id = NotebookId::from_uuid(notebook_id);
+        let mut engine = IntegrationCostEngine::new();
+        engine.initialize_from_entries(nb_id, &entries, entries[4].causal_position);
+
+        let response = coherence_response(notebook_id, engine.get_snapshot(nb_id).unwrap(), true);
+        let members: usize = response.clusters.iter().map(|c| c.size).sum();
+        assert_eq!(members, rows.len());
+        assert_eq!(response.stats.entry_count, rows.len());
+        assert_eq!(response.sequence, 5);
+        assert!(response.clusters.windows(2).all(|w| w[0].size >= w[1].size));
+
+        // Every entry is assigned to exactly one cluster
+        let mut assigned: Vec<Uuid> = response
+            .clusters
+            .iter()
+            .flat_map(|c| c.cluster.entry_ids.iter().map(|id| id.0))
+            .collect();
+        assigned.sort();
+        let mut expected: Vec<Uuid> = rows.iter().map(|r| r.id).collect();
+        expected.sort();
+        assert_eq!(assigned, expected);
+    }
+
+    #[test]
+    fn test_cluster_view_serializes_flat() {
+        let notebook_id = Uuid::new_v4();
+        let entries = vec![entry_row_to_snapshot_entry(&EntryRow::test_row(
+            notebook_id,
+            1,
+            "tokio tasks",
+        ))];
+        let nb_id = NotebookId::from_uuid(notebook_id);
+        let mut engine = IntegrationCostEngine::new();
+        engine.initialize_from_entries(nb_id, &entries, CausalPosition::first());
+
+        let response = coherence_response(notebook_id, engine.get_snapshot(nb_id).unwrap(), false);
+        let json = serde_json::to_value(&response).unwrap();
+        let cluster = &json["clusters"][0];
+        assert_eq!(cluster["size"], 1);
+        assert_eq!(cluster["entry_ids"][0], entries[0].id.0.to_string());
+        assert!(cluster["topic_keywords"].is_array());
+        assert!(cluster["id"].is_u64());
+    }
+
+    #[tokio::test]
+    async fn test_coherence_requires_admin_scope() {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let state = AppState::new(Store::from_pool(pool), ServerConfig::default());
+        let identity = AuthorIdentity {
+            author_id: AuthorId::zero(),
+            scopes: vec!["notebook:read".to_string(), "notebook:write".to_string()],
+        };
+        let result = get_coherence(State(state), identity, Path(Uuid::new_v4())).await;
+        assert!(matches!(
+            result,
+            Err(ApiError::Coded(ErrorCode::MissingScope, _))
+        ));
+    }
+}
@@ -0,0 +1,264 @@
+//! Per-notebook content-type policy.
+//!
+//! Lets a notebook owner restrict which content types new entries may use,
+//! on top of the server-wide policy; see [`crate::content_policy`].
+//!
+//! Endpoints:
+//! - PUT /notebooks/{id}/content-policy - Set the allowed and denied globs
+//! - GET /notebooks/{id}/content-policy - Get the configured policy
+//! - DELETE /notebooks/{id}/content-policy - Remove the policy
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    routing::put,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use notebook_store::{NotebookContentPolicyRow, StoreError};
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, require_scope};
+use crate::state::AppState;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Request body for PUT /notebooks/{id}/content-policy.
+#[derive(Debug, Deserialize)]
+pub struct SetContentPolicyRequest {
+    /// Content-type globs new entries may use. Empty allows any type not
+    /// denied.
+    #[serde(default)]
+    pub allowed_content_types: Vec<String>,
+    /// Content-type globs new entries may not use.
+    #[serde(default)]
+    pub denied_content_types: Vec<String>,
+}
+
+/// A notebook's content-type policy.
+#[derive(Debug, Serialize)]
+pub struct ContentPolicyResponse {
+    pub notebook_id: Uuid,
+    pub allowed_content_types: Vec<String>,
+    pub denied_content_types: Vec<String>,
+    pub updated: DateTime<Utc>,
+}
+
+impl From<NotebookContentPolicyRow> for ContentPolicyResponse {
+    fn from(row: NotebookContentPolicyRow) -> Self {
+        Self {
+            notebook_id: row.notebook_id,
+            allowed_content_types: row.allowed_content_types,
+            denied_content_types: row.denied_content_types,
+            updated: row.updated,
+        }
+    }
+}
+
+/// Response for DELETE /notebooks/{id}/content-policy.
+#[derive(Debug, Serialize)]
+pub struct DeleteContentPolicyResponse {
+    pub notebook_id: Uuid,
+    pub deleted: bool,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Validate the globs of a policy request.
+fn validate_policy_request(request: &SetContentPolicyRequest) -> ApiResult<()> {
+    let globs = request
+        .allowed_content_types
+        .iter()
+        .chain(&request.denied_content_types);
+    for glob in globs {
+        if glob.is_empty() || glob.contains(|c: char| c.is_whitespace() || c == ';') {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid content-type pattern '{}'",
+                glob
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Require the caller to hold `notebook:admin` and own the notebook.
+async fn require_owner(
+    state: &AppState,
+    identity: &AuthorIdentity,
+    notebook_id: Uuid,
+) -> ApiResult<()> {
+    require_scope(identity, "notebook:admin", state.config())?;
+
+    let notebook = state
+        .store()
+        .get_notebook(notebook_id)
+        .await
+        .map_err(|e| match e {
+            StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+            other => ApiError::Store(other),
+        })?;
+
+    if notebook.owner_id.as_slice() != identity.author_id.as_bytes().as_slice() {
+        return Err(ApiError::Forbidden(
+            "Only the notebook owner can manage the content policy".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// PUT /notebooks/{id}/content-policy - Set a notebook's content-type policy.
+///
+/// Replaces any existing policy. Applies to entries created afterwards.
+///
+/// # Response
+///
+/// - 200 OK: ContentPolicyResponse
+/// - 400 Bad Request: Empty or malformed pattern
+/// - 403 Forbidden: Requester is not the owner
+/// - 404 Not Found: Notebook not found
+async fn set_content_policy(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+    Json(request): Json<SetContentPolicyRequest>,
+) -> ApiResult<Json<ContentPolicyResponse>> {
+    validate_policy_request(&request)?;
+    require_owner(&state, &identity, notebook_id).await?;
+
+    let policy = state
+        .store()
+        .upsert_content_policy(
+            notebook_id,
+            &request.allowed_content_types,
+            &request.denied_content_types,
+        )
+        .await
+        .map_err(|e| match e {
+            StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+            other => ApiError::Store(other),
+        })?;
+
+    tracing::info!(
+        notebook_id = %notebook_id,
+        allowed = policy.allowed_content_types.len(),
+        denied = policy.denied_content_types.len(),
+        "Content policy set"
+    );
+
+    state
+        .broadcaster()
+        .publish_from_log(state.store(), notebook_id)
+        .await;
+
+    Ok(Json(ContentPolicyResponse::from(policy)))
+}
+
+/// GET /notebooks/{id}/content-policy - Get a notebook's content-type policy.
+///
+/// # Response
+///
+/// - 200 OK: ContentPolicyResponse
+/// - 403 Forbidden: Requester is not the owner
+/// - 404 Not Found: Notebook not found or no policy configured
+async fn get_content_policy(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+) -> ApiResult<Json<ContentPolicyResponse>> {
+    require_owner(&state, &identity, notebook_id).await?;
+
+    let policy = state
+        .store()
+        .get_content_policy(notebook_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "Notebook {} has no content policy configured",
+                notebook_id
+            ))
+        })?;
+
+    Ok(Json(ContentPolicyResponse::from(policy)))
+}
+
+/// DELETE /notebooks/{id}/content-policy - Remove a notebook's content-type policy.
+///
+/// # Response
+///
+/// - 200 OK: `{ "notebook_id": "...", "deleted": true }` (`false` if none was set)
+/// - 403 Forbidden: Requester is not the owner
+/// - 404 Not Found: Notebook not found
+async fn delete_content_policy(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+) -> ApiResult<Json<DeleteContentPolicyResponse>> {
+    require_owner(&state, &identity, notebook_id).await?;
+
+    let deleted = state.store().delete_content_policy(notebook_id).await?;
+    if deleted {
+        state
+            .broadcaster()
+            .publish_from_log(state.store(), notebook_id)
+            .await;
+    }
+
+    Ok(Json(DeleteContentPolicyResponse {
+        notebook_id,
+        deleted,
+    }))
+}
+
+/// Build content policy routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route(
+        "/notebooks/{id}/content-policy",
+        put(set_content_policy)
+            .get(get_content_policy)
+            .delete(delete_content_policy),
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(allowed: &[&str], denied: &[&str]) -> SetContentPolicyRequest {
+        SetContentPolicyRequest {
+            allowed_content_types: allowed.iter().map(|g| g.to_string()).collect(),
+            denied_content_types: denied.iter().map(|g| g.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_validate_policy_request() {
+        assert!(validate_policy_request(&request(&[], &[])).is_ok());
+        assert!(validate_policy_request(&request(&["text/*"], &["text/html"])).is_ok());
+
+        assert!(validate_policy_request(&request(&[""], &[])).is_err());
+        assert!(validate_policy_request(&request(&["text/ plain"], &[])).is_err());
+        assert!(validate_policy_request(&request(&[], &["text/plain; charset=utf-8"])).is_err());
+    }
+
+    #[test]
+    fn test_request_lists_default_to_empty() {
+        let request: SetContentPolicyRequest =
+            serde_json::from_str(r#"{"allowed_content_types": ["text/*"]}"#).unwrap();
+        assert_eq!(request.allowed_content_types, vec!["text/*"]);
+        assert!(request.denied_content_types.is_empty());
+    }
+}
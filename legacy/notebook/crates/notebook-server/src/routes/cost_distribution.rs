@@ -0,0 +1,361 @@
+//! Integration cost distribution.
+//!
+//! The summed catalog shift of a notebook says how much it moved, not how:
+//! a notebook that grew coherently has most entries shifting the catalog a
+//! little, one that grew chaotically has many large shifts and orphans.
+//! This endpoint buckets the stored per-entry costs to show the spread.
+//!
+//! Endpoint: GET /notebooks/{notebook_id}/cost/distribution
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    routing::get,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use notebook_store::IntegrationCostJson;
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::ReaderIdentity;
+use crate::public_reads::readable_notebook;
+use crate::state::AppState;
+
+/// Number of buckets when none is given.
+pub const DEFAULT_COST_BUCKETS: u32 = 10;
+
+/// Maximum number of buckets.
+pub const MAX_COST_BUCKETS: u32 = 100;
+
+/// Upper edge of the last bucket when none is given.
+pub const DEFAULT_COST_MAX: f64 = 1.0;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Query parameters for GET /notebooks/{id}/cost/distribution.
+#[derive(Debug, Default, Deserialize)]
+pub struct CostDistributionQuery {
+    /// Number of equal-width buckets (default: 10, max: 100).
+    pub buckets: Option<u32>,
+    /// Upper edge of the last bucket (default: 1.0).
+    pub max: Option<f64>,
+}
+
+/// One histogram bucket: entries with `lower <= catalog_shift < upper`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CostBucket {
+    pub lower: f64,
+    pub upper: f64,
+    /// Entries whose catalog shift falls in the bucket.
+    pub count: usize,
+    /// Of those, the orphans.
+    pub orphans: usize,
+}
+
+/// Response for GET /notebooks/{id}/cost/distribution.
+#[derive(Debug, Serialize)]
+pub struct CostDistributionResponse {
+    pub notebook_id: Uuid,
+    /// Entries counted: those whose cost was computed.
+    pub entries: usize,
+    /// Entries that were orphans when written.
+    pub orphans: usize,
+    /// `orphans / entries`; 0.0 for an empty notebook.
+    pub orphan_ratio: f64,
+    /// Mean catalog shift per entry.
+    pub mean_catalog_shift: f64,
+    /// Largest catalog shift of any entry.
+    pub max_catalog_shift: f64,
+    /// Histogram of catalog shift, lowest bucket first. The last bucket also
+    /// counts shifts at or above its upper edge.
+    pub buckets: Vec<CostBucket>,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Resolve the bucket count and upper edge, applying defaults.
+fn histogram_shape(query: &CostDistributionQuery) -> ApiResult<(usize, f64)> {
+    let buckets = query.buckets.unwrap_or(DEFAULT_COST_BUCKETS);
+    if !(1..=MAX_COST_BUCKETS).contains(&buckets) {
+        return Err(ApiError::BadRequest(format!(
+            "buckets must be between 1 and {}",
+            MAX_COST_BUCKETS
+        )));
+    }
+    let max = query.max.unwrap_or(DEFAULT_COST_MAX);
+    if !(max.is_finite() && max > 0.0) {
+        return Err(ApiError::BadRequest(
+            "max must be a positive number".to_string(),
+        ));
+    }
+    Ok((buckets as usize, max))
+}
+
+/// Bucket the catalog shifts of `costs` into `buckets` equal-width buckets
+/// spanning 0.0 to `max`.
+pub fn cost_histogram(costs: &[IntegrationCostJson], buckets: usize, max: f64) -> Vec<CostBucket> {
+    let width = max / buckets as f64;
+    let mut histogram: Vec<CostBucket> = (0..buckets)
+        .map(|i| CostBucket {
+            lower: width * i as f64,
+            upper: if i + 1 == buckets {
+                max
+            } else {
+                width * (i + 1) as f64
+            },
+            count: 0,
+            orphans: 0,
+        })
+        .collect();
+
+    for cost in costs {
+        let index = ((cost.catalog_shift.max(0.0) / width) as usize).min(buckets - 1);
+        histogram[index].count += 1;
+        histogram[index].orphans += usize::from(cost.orphan);
+    }
+    histogram
+}
+
+/// Summarize a notebook's entry costs.
+pub fn cost_distribution(
+    notebook_id: Uuid,
+    costs: &[IntegrationCostJson],
+    buckets: usize,
+    max: f64,
+) -> CostDistributionResponse {
+    let entries = costs.len();
+    let orphans = costs.iter().filter(|cost| cost.orphan).count();
+    let total_shift: f64 = costs.iter().map(|cost| cost.catalog_shift).sum();
+    let ratio = |value: f64| {
+        if entries == 0 {
+            0.0
+        } else {
+            value / entries as f64
+        }
+    };
+
+    CostDistributionResponse {
+        notebook_id,
+        entries,
+        orphans,
+        orphan_ratio: ratio(orphans as f64),
+        mean_catalog_shift: ratio(total_shift),
+        max_catalog_shift: costs
+            .iter()
+            .map(|cost| cost.catalog_shift)
+            .fold(0.0, f64::max),
+        buckets: cost_histogram(costs, buckets, max),
+    }
+}
+
+// ============================================================================
+// Route Handler
+// ============================================================================
+
+/// GET /notebooks/{notebook_id}/cost/distribution
+///
+/// Histogram of the catalog shift of the notebook's entries, with orphan
+/// counts, from the costs stored when each entry was written. Entries whose
+/// cost is still a fallback are left out. Public notebooks can be read
+/// without credentials.
+///
+/// # Query Parameters
+///
+/// - `buckets`: Number of equal-width buckets (default: 10, max: 100)
+/// - `max`: Upper edge of the last bucket (default: 1.0); larger shifts
+///   are counted in the last bucket
+///
+/// # Response
+///
+/// - 200 OK: `{ "notebook_id": "...", "entries": 12, "orphans": 2, "orphan_ratio": 0.17, "mean_catalog_shift": 0.21, "max_catalog_shift": 1.0, "buckets": [{ "lower": 0.0, "upper": 0.1, "count": 7, "orphans": 0 }] }`
+/// - 400 Bad Request: `buckets` or `max` out of range
+/// - 401 Unauthorized: No credentials and the notebook is not public
+/// - 404 Not Found: Notebook not found
+async fn get_cost_distribution(
+    State(state): State<AppState>,
+    reader: ReaderIdentity,
+    Path(notebook_id): Path<Uuid>,
+    Query(query): Query<CostDistributionQuery>,
+) -> ApiResult<Json<CostDistributionResponse>> {
+    let (buckets, max) = histogram_shape(&query)?;
+    readable_notebook(&state, &reader, notebook_id).await?;
+
+    let costs = state
+        .store()
+        .computed_integration_costs(notebook_id)
+        .await?;
+
+    Ok(Json(cost_distribution(notebook_id, &costs, buckets, max)))
+}
+
+/// Build cost distribution routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route(
+        "/notebooks/{id}/cost/distribution",
+        get(get_cost_distribution),
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notebook_core::{AuthorId, EntryBuilder, NotebookId};
+    use notebook_entropy::IntegrationCostEngine;
+
+    /// Costs of writing `contents` in order to a fresh notebook.
+    fn written_costs(contents: &[&str]) -> Vec<IntegrationCostJson> {
+        let mut engine = IntegrationCostEngine::new();
+        let notebook_id = NotebookId::new();
+        contents
+            .iter()
+            .map(|content| {
+                let entry = EntryBuilder::default()
+                    .content(content.as_bytes().to_vec())
+                    .content_type("text/plain")
+                    .author(AuthorId::zero())
+                    .build();
+                IntegrationCostJson::from(engine.compute_cost(&entry, notebook_id).unwrap())
+            })
+            .collect()
+    }
+
+    fn cost(catalog_shift: f64, orphan: bool) -> IntegrationCostJson {
+        IntegrationCostJson {
+            catalog_shift,
+            orphan,
+            ..IntegrationCostJson::default()
+        }
+    }
+
+    /// Share of entries in the lowest `n` buckets.
+    fn low_share(distribution: &CostDistributionResponse, n: usize) -> f64 {
+        let low: usize = distribution.buckets[..n].iter().map(|b| b.count).sum();
+        low as f64 / distribution.entries as f64
+    }
+
+    #[test]
+    fn test_histogram_buckets_and_overflow() {
+        let costs = [
+            cost(0.0, false),
+            cost(0.24, false),
+            cost(0.25, true),
+            cost(0.99, true),
+            cost(3.0, true),
+        ];
+        let histogram = cost_histogram(&costs, 4, 1.0);
+
+        assert_eq!(histogram.len(), 4);
+        assert_eq!(histogram[0].lower, 0.0);
+        assert_eq!(histogram[3].upper, 1.0);
+        let counts: Vec<usize> = histogram.iter().map(|b| b.count).collect();
+        assert_eq!(counts, vec![2, 1, 0, 2]);
+        let orphans: Vec<usize> = histogram.iter().map(|b| b.orphans).collect();
+        assert_eq!(orphans, vec![0, 1, 0, 2]);
+    }
+
+    #[test]
+    fn test_distribution_summary() {
+        let costs = [cost(0.1, false), cost(0.3, true)];
+        let distribution = cost_distribution(Uuid::nil(), &costs, 10, 1.0);
+
+        assert_eq!(distribution.entries, 2);
+        assert_eq!(distribution.orphans, 1);
+        assert_eq!(distribution.orphan_ratio, 0.5);
+        assert!((distribution.mean_catalog_shift - 0.2).abs() < 1e-12);
+        assert_eq!(distribution.max_catalog_shift, 0.3);
+
+        let empty = cost_distribution(Uuid::nil(), &[], 10, 1.0);
+        assert_eq!(empty.orphan_ratio, 0.0);
+        assert_eq!(empty.mean_catalog_shift, 0.0);
+        assert!(empty.buckets.iter().all(|b| b.count == 0));
+    }
+
+    #[test]
+    fn test_histogram_shape_defaults_and_limits() {
+        let shape = histogram_shape(&CostDistributionQuery::default()).unwrap();
+        assert_eq!(shape, (DEFAULT_COST_BUCKETS as usize, DEFAULT_COST_MAX));
+
+        for query in [
+            CostDistributionQuery {
+                buckets: Some(0),
+                max: None,
+            },
+            CostDistributionQuery {
+                buckets: Some(MAX_COST_BUCKETS + 1),
+                max: None,
+            },
+            CostDistributionQuery {
+                buckets: None,
+                max: Some(0.0),
+            },
+            CostDistributionQuery {
+                buckets: None,
+                max: Some(f64::NAN),
+            },
+        ] {
+            assert!(matches!(
+                histogram_shape(&query),
+                Err(ApiError::BadRequest(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_coherent_notebook_concentrates_in_low_buckets() {
+        let coherent = written_costs(&[
+            "Sourdough starter needs daily feeding with flour and water",
+            "Feed the sourdough starter flour and water daily",
+            "A sourdough starter fed flour and water daily stays lively",
+            "Sourdough starter feeding: flour, water, daily",
+            "Daily flour and water feeding keeps a sourdough starter lively",
+            "The lively sourdough starter wants flour and water daily",
+            "Sourdough starter flour water daily feeding",
+            "Feeding the sourdough starter daily with water and flour",
+            "A lively sourdough starter needs flour and water",
+            "Water and flour feed the sourdough starter daily",
+            "Sourdough starter: feed flour and water daily",
+            "Keep the sourdough starter lively with daily flour and water",
+        ]);
+        let diverse = written_costs(&[
+            "Sourdough starter needs daily feeding with flour and water",
+            "Tides follow the phases of the moon",
+            "Compilers translate source code into machine instructions",
+            "Glaciers carve valleys over millennia",
+            "Jazz musicians improvise over chord changes",
+            "Volcanic eruptions release sulfur dioxide",
+            "Chess openings control the center squares",
+            "Honeybees communicate with waggle dances",
+            "Satellites relay signals between continents",
+            "Medieval castles defended river crossings",
+            "Photosynthesis converts sunlight into sugar",
+            "Marathon runners pace their breathing",
+        ]);
+
+        let coherent = cost_distribution(Uuid::nil(), &coherent, 20, 1.0);
+        let diverse = cost_distribution(Uuid::nil(), &diverse, 20, 1.0);
+
+        // Once the topic is established, coherent entries barely move the
+        // catalog; unrelated ones keep shifting it.
+        assert!(
+            low_share(&coherent, 1) >= 0.5,
+            "coherent {:?}",
+            coherent.buckets
+        );
+        assert!(
+            low_share(&diverse, 1) <= 0.25,
+            "diverse {:?}",
+            diverse.buckets
+        );
+        assert!(coherent.mean_catalog_shift < diverse.mean_catalog_shift);
+        assert!(coherent.orphan_ratio < diverse.orphan_ratio);
+    }
+}
@@ -0,0 +1,193 @@
+//! Delete impact preview for an entry.
+//!
+//! Deleting an entry breaks every reference to it and leaves its revisions
+//! without the entry they revise. This endpoint lists both before anything
+//! is deleted, so a maintainer can judge the blast radius. It is read-only.
+//!
+//! Endpoint: GET /notebooks/{notebook_id}/entries/{entry_id}/delete-impact
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    routing::get,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use notebook_store::{EntryRow, RevisionChain, StoreError};
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, require_scope};
+use crate::routes::entries::{EntrySummary, summarize_entries};
+use crate::state::AppState;
+
+// ============================================================================
+// Response Types
+// ============================================================================
+
+/// Response for GET /notebooks/{id}/entries/{entry_id}/delete-impact.
+#[derive(Debug, Serialize)]
+pub struct DeleteImpactResponse {
+    /// The entry that would be deleted.
+    pub entry_id: Uuid,
+    /// Entries that reference it and would be left with a broken reference,
+    /// in sequence order. May include entries of other notebooks.
+    pub broken_references: Vec<EntrySummary>,
+    /// Revisions of the entry that would lose their original, nearest first.
+    pub orphaned_revisions: Vec<EntrySummary>,
+    /// The revision chain goes on past the server's revision depth limit.
+    pub revisions_truncated: bool,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Entries affected by deleting `entry_id`: those referencing it, and its
+/// revision chain.
+///
+/// An entry that references itself is not counted as broken by its own
+/// deletion.
+fn impacted_ids(
+    entry_id: Uuid,
+    referencing: &[EntryRow],
+    chain: &RevisionChain,
+) -> (Vec<Uuid>, Vec<Uuid>) {
+    let broken = referencing
+        .iter()
+        .map(|row| row.id)
+        .filter(|id| *id != entry_id)
+        .collect();
+    let orphaned = chain.revisions.iter().map(|row| row.id).collect();
+    (broken, orphaned)
+}
+
+// ============================================================================
+// Route Handler
+// ============================================================================
+
+/// GET /notebooks/{notebook_id}/entries/{entry_id}/delete-impact
+///
+/// Previews what deleting an entry would break. Nothing is changed.
+///
+/// # Response
+///
+/// - 200 OK: `{ "entry_id": "...", "broken_references": [...], "orphaned_revisions": [...], "revisions_truncated": false }`
+/// - 404 Not Found: Notebook or entry not found
+async fn delete_impact(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path((notebook_id, entry_id)): Path<(Uuid, Uuid)>,
+) -> ApiResult<Json<DeleteImpactResponse>> {
+    require_scope(&identity, "notebook:read", state.config())?;
+    let store = state.store();
+
+    store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+
+    // The entry must live in this notebook
+    let row = store.get_entry(entry_id).await.map_err(|e| match e {
+        StoreError::EntryNotFound(id) => ApiError::entry_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+    if row.notebook_id != notebook_id {
+        return Err(ApiError::entry_not_found(entry_id));
+    }
+
+    let referencing = store.get_entries_referencing(entry_id).await?;
+    let chain = store
+        .get_revisions(entry_id, store.max_revision_depth())
+        .await?;
+    if chain.cycle {
+        tracing::warn!(entry_id = %entry_id, "Revision chain loops back on itself");
+    }
+    let (broken, orphaned) = impacted_ids(entry_id, &referencing, &chain);
+
+    // Summarize both lists in one query, keeping each list's order
+    let related: Vec<Uuid> = broken.iter().chain(&orphaned).copied().collect();
+    let summaries = summarize_entries(store, &related).await?;
+    let summarize = |ids: &[Uuid]| -> Vec<EntrySummary> {
+        ids.iter()
+            .filter_map(|id| summaries.get(id).cloned())
+            .collect()
+    };
+
+    tracing::debug!(
+        entry_id = %entry_id,
+        broken_references = broken.len(),
+        orphaned_revisions = orphaned.len(),
+        "Delete impact previewed"
+    );
+
+    Ok(Json(DeleteImpactResponse {
+        entry_id,
+        broken_references: summarize(&broken),
+        orphaned_revisions: summarize(&orphaned),
+        revisions_truncated: chain.truncated,
+    }))
+}
+
+/// Build delete impact routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route(
+        "/notebooks/{id}/entries/{entry_id}/delete-impact",
+        get(delete_impact),
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_row(sequence: i64, revision_of: Option<Uuid>, references: Vec<Uuid>) -> EntryRow {
+        EntryRow {
+            revision_of,
+            references,
+            ..EntryRow::test_row(Uuid::nil(), sequence, "content")
+        }
+    }
+
+    #[test]
+    fn test_impact_lists_referencing_entries_and_revisions() {
+        let target = make_row(1, None, vec![]);
+        let citing = make_row(2, None, vec![target.id]);
+        let also_citing = make_row(3, None, vec![citing.id, target.id]);
+        let revision = make_row(4, Some(target.id), vec![]);
+        let second_revision = make_row(5, Some(revision.id), vec![]);
+
+        let chain = RevisionChain {
+            revisions: vec![revision.clone(), second_revision.clone()],
+            truncated: false,
+            cycle: false,
+        };
+        let (broken, orphaned) =
+            impacted_ids(target.id, &[citing.clone(), also_citing.clone()], &chain);
+
+        assert_eq!(broken, vec![citing.id, also_citing.id]);
+        assert_eq!(orphaned, vec![revision.id, second_revision.id]);
+    }
+
+    #[test]
+    fn test_self_reference_is_not_broken() {
+        let mut target = make_row(1, None, vec![]);
+        target.references = vec![target.id];
+        let (broken, orphaned) =
+            impacted_ids(target.id, &[target.clone()], &RevisionChain::default());
+        assert!(broken.is_empty());
+        assert!(orphaned.is_empty());
+    }
+
+    #[test]
+    fn test_unreferenced_entry_has_no_impact() {
+        let target = make_row(1, None, vec![]);
+        let (broken, orphaned) = impacted_ids(target.id, &[], &RevisionChain::default());
+        assert!(broken.is_empty());
+        assert!(orphaned.is_empty());
+    }
+}
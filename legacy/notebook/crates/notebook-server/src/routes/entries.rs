@@ -2,15 +2,21 @@
 //!
 //! This module implements the entry-related HTTP endpoints:
 //! - POST /notebooks/{id}/entries - Create a new entry
+//! - POST /notebooks/{id}/entries/preview - Preview integration cost without writing
+//! - POST /notebooks/{id}/entries/preview/batch - Preview the costs of a sequence of writes
 //! - PUT /notebooks/{id}/entries/{entry_id} - Revise an entry
 //! - GET /notebooks/{id}/entries/{entry_id} - Get an entry
+//! - POST /notebooks/{id}/entries/get - Get several entries at once
 //!
 //! Owned by: agent-revise (REVISE endpoint), agent-write (WRITE endpoint), agent-read (READ endpoint)
 
 use axum::{
     Json, Router,
     extract::{Path, Query, State},
-    http::{HeaderMap, HeaderValue, StatusCode},
+    http::{
+        HeaderMap, HeaderValue, StatusCode,
+        header::{ETAG, IF_MATCH},
+    },
     routing::{post, put},
 };
 use base64::Engine;
@@ -18,14 +24,35 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use std::collections::HashMap;
+use std::time::Duration;
+
 use notebook_core::{AuthorId, CausalPosition, Entry, EntryId, IntegrationCost, NotebookId};
+use notebook_entropy::create_propagation_job;
 use notebook_store::{
-    CausalPositionService, IntegrationCostJson, NewEntry, Repository, StoreEntryInput, StoreError,
+    CausalPositionService, EntrySummaryRow, IntegrationCostJson, NewEntry, NotebookRow, Repository,
+    RevisionChain, Store, StoreEntryInput, StoreError,
 };
 
-use crate::error::{ApiError, ApiResult};
-use crate::extract::{AuthorIdentity, require_scope};
+use crate::alerts::{check_entropy_alert, spawn_entropy_alert_check};
+use crate::config::ServerConfig;
+use crate::content_policy::enforce_content_policy;
+use crate::engines::{CostOutcome, PendingCost, compute_cost_bounded};
+use crate::error::{ApiError, ApiResult, ErrorCode};
+use crate::extract::{AuthorIdentity, ReaderIdentity, require_scope};
+use crate::orphan_policy::{ORPHAN_WARNING_HEADER, enforce_orphan_policy};
+use crate::public_reads::readable_notebook;
 use crate::state::AppState;
+use crate::throttle::{WriteCharge, enforce_write_budget};
+
+/// Maximum number of entries fetched by one bulk read.
+pub const MAX_BULK_READ_IDS: usize = 100;
+
+/// Maximum number of entries in one batch cost preview.
+pub const MAX_PREVIEW_BATCH_ENTRIES: usize = 100;
+
+/// Longest a read with `wait_for_propagation` waits for the entry's job.
+pub const PROPAGATION_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
 
 // ============================================================================
 // Request/Response Types
@@ -34,20 +61,38 @@ use crate::state::AppState;
 /// Request body for creating a new entry.
 #[derive(Debug, Deserialize)]
 pub struct CreateEntryRequest {
+    /// Client-chosen entry ID. Retrying a write with the same ID cannot
+    /// create a second entry. Generated by the server if absent.
+    #[serde(default)]
+    pub id: Option<Uuid>,
+
     /// Content as a string. For text content_types, used as-is.
     /// For binary content_types, should be base64 encoded.
     pub content: String,
 
     /// MIME-like content type (e.g., "text/plain", "application/json").
+    /// The server's default content type when omitted or empty.
+    #[serde(default)]
     pub content_type: String,
 
-    /// Optional topic/category for the entry.
+    /// Optional topic/category for the entry. Inferred from the content
+    /// when absent, if the server infers topics.
     #[serde(default)]
     pub topic: Option<String>,
 
     /// References to other entries (UUIDs).
     #[serde(default)]
     pub references: Vec<Uuid>,
+
+    /// Allow references to entries in other notebooks.
+    #[serde(default)]
+    pub allow_external_refs: bool,
+
+    /// Entry of the same notebook that this entry revises. Unlike a PUT
+    /// revision nothing is inherited from it, and it need not be the
+    /// latest revision, so two writes revising the same entry fork it.
+    #[serde(default)]
+    pub revision_of: Option<Uuid>,
 }
 
 /// Response for successful entry creation.
@@ -61,6 +106,65 @@ pub struct CreateEntryResponse {
 
     /// Integration cost (placeholder zeros for Phase 1).
     pub integration_cost: IntegrationCost,
+
+    /// The topic inferred for the entry, if it was written without one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+
+    /// Whether the entry's topic was inferred from its content.
+    pub topic_inferred: bool,
+}
+
+/// Response for POST /notebooks/{id}/entries/preview.
+#[derive(Debug, Serialize)]
+pub struct PreviewEntryResponse {
+    /// The integration cost the entry would incur if written now.
+    pub integration_cost: IntegrationCost,
+
+    /// Whether the entry would be an orphan (no cluster match, no references).
+    pub orphan: bool,
+}
+
+/// Request body for POST /notebooks/{id}/entries/preview/batch.
+#[derive(Debug, Deserialize)]
+pub struct PreviewBatchRequest {
+    /// The entries, in the order they would be written.
+    pub entries: Vec<CreateEntryRequest>,
+}
+
+/// Costs of a batch of entries, summed.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct BatchCostTotal {
+    /// Existing entries revised across the batch.
+    pub entries_revised: u32,
+    /// References broken across the batch.
+    pub references_broken: u32,
+    /// Summed catalog shift.
+    pub catalog_shift: f64,
+    /// Number of entries that would be orphans.
+    pub orphans: u32,
+}
+
+impl BatchCostTotal {
+    /// Sum the costs of a batch.
+    pub fn of(costs: &[IntegrationCost]) -> Self {
+        costs.iter().fold(Self::default(), |total, cost| Self {
+            entries_revised: total.entries_revised + cost.entries_revised,
+            references_broken: total.references_broken + cost.references_broken,
+            catalog_shift: total.catalog_shift + cost.catalog_shift,
+            orphans: total.orphans + u32::from(cost.orphan),
+        })
+    }
+}
+
+/// Response for POST /notebooks/{id}/entries/preview/batch.
+#[derive(Debug, Serialize)]
+pub struct PreviewBatchResponse {
+    /// Per-entry costs, in request order. Each reflects the entries before it.
+    pub costs: Vec<PreviewEntryResponse>,
+
+    /// The costs summed over the batch.
+    pub total: BatchCostTotal,
 }
 
 /// Request body for revising an entry.
@@ -71,17 +175,53 @@ pub struct ReviseRequest {
     /// Optional reason for the revision (for audit, not stored in entry).
     #[serde(default)]
     pub reason: Option<String>,
+    /// Topic for the revision. Inherited from the original when absent.
+    #[serde(default)]
+    pub topic: Option<String>,
+    /// References for the revision, validated as on create. Inherited from
+    /// the original when absent.
+    #[serde(default)]
+    pub references: Option<Vec<Uuid>>,
+    /// Allow `references` to entries in other notebooks.
+    #[serde(default)]
+    pub allow_external_refs: bool,
+}
+
+impl ReviseRequest {
+    /// Topic and references of the revision of `original`.
+    fn metadata_for(&self, original: &Entry) -> (Option<String>, Vec<EntryId>) {
+        let topic = self.topic.clone().or_else(|| original.topic.clone());
+        let references = match &self.references {
+            Some(references) => references.iter().map(|&u| EntryId::from_uuid(u)).collect(),
+            None => original.references.clone(),
+        };
+        (topic, references)
+    }
+
+    /// Whether revising `original` would write a revision identical to
+    /// `current`: the same content, compared by hash, topic and references.
+    fn is_noop_for(&self, original: &Entry, current: &Entry) -> bool {
+        let (topic, references) = self.metadata_for(original);
+        blake3::hash(self.content.as_bytes()) == blake3::hash(&current.content)
+            && original.content_type == current.content_type
+            && topic == current.topic
+            && references == current.references
+    }
 }
 
 /// Response for a successful revision.
 #[derive(Debug, Serialize)]
 pub struct ReviseResponse {
-    /// The ID of the newly created revision entry.
+    /// The ID of the newly created revision entry, or of the existing
+    /// revision a no-op revise matched.
     pub revision_id: EntryId,
     /// The causal position assigned to the revision.
     pub causal_position: CausalPosition,
     /// The integration cost of the revision (placeholder zeros).
     pub integration_cost: IntegrationCost,
+    /// True if the revise matched the latest revision and nothing was
+    /// written.
+    pub no_op: bool,
 }
 
 // ============================================================================
@@ -93,6 +233,42 @@ pub struct ReviseResponse {
 pub struct GetEntryParams {
     /// Optional revision number (0 = current, 1 = first revision, etc.)
     pub revision: Option<u32>,
+    /// Comma-separated related collections to load: `revisions`,
+    /// `references`, `referenced_by`.
+    pub include: Option<String>,
+    /// Wait for the entry's cost propagation job before reading.
+    #[serde(default)]
+    pub wait_for_propagation: bool,
+}
+
+/// Related collections requested through `?include=`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Includes {
+    revisions: bool,
+    references: bool,
+    referenced_by: bool,
+}
+
+impl Includes {
+    /// Parse an `include` parameter; nothing is included when it is absent.
+    fn parse(include: Option<&str>) -> ApiResult<Self> {
+        let mut includes = Self::default();
+        for name in include.unwrap_or_default().split(',').map(str::trim) {
+            match name {
+                "" => {}
+                "revisions" => includes.revisions = true,
+                "references" => includes.references = true,
+                "referenced_by" => includes.referenced_by = true,
+                other => {
+                    return Err(ApiError::BadRequest(format!(
+                        "Unknown include '{}'; expected revisions, references or referenced_by",
+                        other
+                    )));
+                }
+            }
+        }
+        Ok(includes)
+    }
 }
 
 /// Response for GET /notebooks/{notebook_id}/entries/{entry_id}
@@ -100,14 +276,35 @@ pub struct GetEntryParams {
 pub struct ReadEntryResponse {
     /// The full entry data.
     pub entry: EntryResponse,
-    /// Revision chain (entries that revise this entry).
+    /// Number of entries that revise this entry, always present.
+    pub revision_count: i64,
+    /// Revision chain (entries that revise this entry), if included.
     pub revisions: Vec<EntrySummary>,
-    /// Entries that this entry references.
+    /// Whether the included revision chain continues past the server's
+    /// revision depth limit.
+    pub revisions_truncated: bool,
+    /// Entries that this entry references, if included.
     pub references: Vec<EntrySummary>,
-    /// Entries that reference this entry.
+    /// Entries that reference this entry, if included.
     pub referenced_by: Vec<EntrySummary>,
 }
 
+/// Request body for POST /notebooks/{notebook_id}/entries/get
+#[derive(Debug, Deserialize)]
+pub struct BulkReadRequest {
+    /// Entry IDs to fetch, at most [`MAX_BULK_READ_IDS`].
+    pub ids: Vec<Uuid>,
+}
+
+/// Response for POST /notebooks/{notebook_id}/entries/get
+#[derive(Debug, Serialize)]
+pub struct BulkReadResponse {
+    /// Entries found in this notebook, in request order.
+    pub entries: Vec<EntryResponse>,
+    /// Requested IDs that don't exist in this notebook.
+    pub not_found: Vec<Uuid>,
+}
+
 /// Full entry data for the response.
 #[derive(Debug, Serialize)]
 pub struct EntryResponse {
@@ -117,6 +314,10 @@ pub struct EntryResponse {
     pub content: EntryContent,
     /// MIME content type.
     pub content_type: String,
+    /// BLAKE3 hash of the stored content bytes (hex-encoded).
+    pub content_hash: String,
+    /// Size of the stored content in bytes.
+    pub content_length: usize,
     /// Optional topic/category.
     pub topic: Option<String>,
     /// Author identity (hex-encoded).
@@ -131,6 +332,9 @@ pub struct EntryResponse {
     pub created: DateTime<Utc>,
     /// System-computed integration cost.
     pub integration_cost: IntegrationCost,
+    /// Whether `integration_cost` was computed. False while it is the zero
+    /// fallback written because computation failed or timed out.
+    pub cost_computed: bool,
 }
 
 /// Causal position in response format.
@@ -153,12 +357,14 @@ pub struct ActivityContextResponse {
     pub recent_entropy: f64,
 }
 
-/// Entry content that can be either text or base64-encoded binary.
+/// Entry content: text, structured JSON, or base64-encoded binary.
 #[derive(Debug, Serialize)]
 #[serde(untagged)]
 pub enum EntryContent {
-    /// Text content (for text/* content types).
+    /// Text content (for text/* and other textual content types).
     Text(String),
+    /// Parsed JSON document (for JSON content types with valid JSON).
+    Json(serde_json::Value),
     /// Base64-encoded binary content.
     Binary {
         /// Base64-encoded data.
@@ -169,7 +375,7 @@ pub enum EntryContent {
 }
 
 /// Summary of an entry for references and revisions lists.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct EntrySummary {
     /// Entry ID.
     pub id: EntryId,
@@ -179,6 +385,38 @@ pub struct EntrySummary {
     pub author: AuthorId,
     /// Creation timestamp.
     pub created: DateTime<Utc>,
+    /// Sequence of the newest revision, or of the entry itself if unrevised.
+    pub latest_sequence: u64,
+    /// Integration cost weight (catalog shift) of the entry.
+    pub integration_cost: f64,
+    /// Whether the integration cost was computed, rather than a fallback.
+    pub cost_computed: bool,
+    /// Number of entries that revise this entry.
+    pub revision_count: u32,
+    /// Entry this one revises. In a revisions list it names each
+    /// revision's parent, so forks show up as revisions of the same entry.
+    pub revision_of: Option<EntryId>,
+}
+
+impl From<&EntrySummaryRow> for EntrySummary {
+    fn from(row: &EntrySummaryRow) -> Self {
+        Self {
+            id: EntryId::from_uuid(row.id),
+            topic: row.topic.clone(),
+            author: row
+                .author_id
+                .as_slice()
+                .try_into()
+                .map(AuthorId::from_bytes)
+                .unwrap_or_else(|_| AuthorId::zero()),
+            created: row.created,
+            latest_sequence: row.latest_sequence as u64,
+            integration_cost: row.catalog_shift,
+            cost_computed: row.cost_computed,
+            revision_count: row.revision_count as u32,
+            revision_of: row.revision_of.map(EntryId::from_uuid),
+        }
+    }
 }
 
 // ============================================================================
@@ -197,66 +435,569 @@ fn is_binary_content_type(content_type: &str) -> bool {
         "application/x-www-form-urlencoded",
     ];
 
-    !text_types.iter().any(|t| content_type.starts_with(t))
+    !text_types.iter().any(|t| content_type.starts_with(t)) && !is_json_content_type(content_type)
+}
+
+/// Determine if content_type denotes a JSON document.
+///
+/// Matches `application/json` and structured-syntax suffixes such as
+/// `application/ld+json`, ignoring parameters like `charset`.
+fn is_json_content_type(content_type: &str) -> bool {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+    media_type.eq_ignore_ascii_case("application/json")
+        || media_type.to_ascii_lowercase().ends_with("+json")
+}
+
+/// Determine if content_type declares a `text/*` media type.
+fn is_text_content_type(content_type: &str) -> bool {
+    content_type
+        .trim_start()
+        .get(..5)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("text/"))
 }
 
 /// Get content bytes from request, decoding base64 if content is binary.
+///
+/// Content declared as `text/*` must be valid UTF-8, so reads can always
+/// return it as text rather than falling back to base64.
 fn get_content_bytes(request: &CreateEntryRequest) -> Result<Vec<u8>, ApiError> {
-    if is_binary_content_type(&request.content_type) {
+    let bytes = if is_binary_content_type(&request.content_type) {
         // Binary content - decode from base64
         base64::engine::general_purpose::STANDARD
             .decode(&request.content)
-            .map_err(|e| ApiError::BadRequest(format!("Invalid base64 content: {}", e)))
+            .map_err(|e| ApiError::BadRequest(format!("Invalid base64 content: {}", e)))?
     } else {
         // Text content - use as-is
-        Ok(request.content.as_bytes().to_vec())
+        request.content.as_bytes().to_vec()
+    };
+
+    if is_text_content_type(&request.content_type)
+        && let Err(e) = std::str::from_utf8(&bytes)
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Content declared as {} is not valid UTF-8: {}",
+            request.content_type, e
+        )));
     }
+
+    Ok(bytes)
 }
 
-/// Encode entry content based on content type for READ response.
+/// Use the server's default content type if the request has none.
+fn apply_default_content_type(request: &mut CreateEntryRequest, config: &ServerConfig) {
+    if request.content_type.trim().is_empty() {
+        request.content_type = config.default_content_type.clone();
+    }
+}
+
+/// Set an inferred topic on an entry written without one, if the server
+/// infers topics. Returns whether a topic was inferred.
+async fn infer_missing_topic(state: &AppState, entry: &mut Entry, notebook_id: NotebookId) -> bool {
+    if !state.config().infer_topics || entry.topic.is_some() {
+        return false;
+    }
+    entry.topic = state
+        .engines()
+        .lock(notebook_id)
+        .await
+        .infer_topic(entry, notebook_id);
+    entry.topic.is_some()
+}
+
+/// Reject writes to a locked notebook.
+pub(crate) fn ensure_unlocked(notebook: &NotebookRow) -> ApiResult<()> {
+    if notebook.is_locked {
+        return Err(ApiError::Coded(
+            ErrorCode::NotebookLocked,
+            format!("Notebook {} is locked", notebook.id),
+        ));
+    }
+    Ok(())
+}
+
+/// Find the first reference that is not among the notebook's own entries.
+fn find_external_reference(references: &[Uuid], local: &[Uuid]) -> Option<Uuid> {
+    references.iter().copied().find(|id| !local.contains(id))
+}
+
+/// Find the first reference that exists neither in the notebook (`local`)
+/// nor elsewhere (`external`).
+fn find_missing_reference(references: &[Uuid], local: &[Uuid], external: &[Uuid]) -> Option<Uuid> {
+    references
+        .iter()
+        .copied()
+        .find(|id| !local.contains(id) && !external.contains(id))
+}
+
+/// Reject entries carrying more than `max` references.
+fn check_reference_count(references: &[Uuid], max: usize) -> ApiResult<()> {
+    if references.len() > max {
+        return Err(ApiError::BadRequest(format!(
+            "An entry may have at most {} references, got {}",
+            max,
+            references.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Validate that there are not too many references, that all of them
+/// exist and, unless `allow_external_refs` is set, that they belong to the
+/// target notebook.
 ///
-/// If content_type starts with "text/", attempts to decode as UTF-8 string.
-/// Otherwise, returns base64-encoded binary.
-fn encode_content(content: &[u8], content_type: &str) -> EntryContent {
-    if content_type.starts_with("text/") {
-        // Try to decode as UTF-8
-        match std::str::from_utf8(content) {
-            Ok(s) => EntryContent::Text(s.to_string()),
-            Err(_) => {
-                // Invalid UTF-8, fall back to base64
-                let encoded = base64::engine::general_purpose::STANDARD.encode(content);
-                EntryContent::Binary {
-                    data: encoded,
-                    encoding: "base64",
-                }
+/// References are looked up in one query against the notebook, plus one
+/// for any that are not found there.
+async fn validate_references(
+    store: &Store,
+    config: &ServerConfig,
+    notebook_id: Uuid,
+    references: &[Uuid],
+    allow_external_refs: bool,
+) -> ApiResult<()> {
+    check_reference_count(references, config.max_references_per_entry)?;
+
+    let local = store.entries_in_notebook(notebook_id, references).await?;
+    let outside: Vec<Uuid> = references
+        .iter()
+        .copied()
+        .filter(|id| !local.contains(id))
+        .collect();
+    if outside.is_empty() {
+        return Ok(());
+    }
+
+    let external = store.existing_entry_ids(&outside).await?;
+    if let Some(ref_id) = find_missing_reference(references, &local, &external) {
+        return Err(ApiError::BadRequest(format!(
+            "Referenced entry {} does not exist",
+            ref_id
+        )));
+    }
+
+    if !allow_external_refs && let Some(ref_id) = find_external_reference(references, &local) {
+        return Err(ApiError::BadRequest(format!(
+            "Referenced entry {} belongs to another notebook",
+            ref_id
+        )));
+    }
+
+    Ok(())
+}
+
+/// Check that the entry a write revises belongs to the target notebook.
+async fn validate_revision_target(store: &Store, notebook_id: Uuid, target: Uuid) -> ApiResult<()> {
+    if !store
+        .entries_in_notebook(notebook_id, &[target])
+        .await?
+        .is_empty()
+    {
+        return Ok(());
+    }
+    let reason = if store.existing_entry_ids(&[target]).await?.is_empty() {
+        "does not exist"
+    } else {
+        "belongs to another notebook"
+    };
+    Err(ApiError::BadRequest(format!(
+        "Revision target {} {}",
+        target, reason
+    )))
+}
+
+/// The ID for a new entry: the one the client supplied, if it is not already
+/// taken, or a freshly generated one.
+///
+/// Runs before the entry reaches the cost engine, so a rejected ID leaves the
+/// notebook's coherence snapshot untouched. An ID taken in any notebook gets
+/// the same [`entry_id_taken`] conflict as one taken concurrently at insert.
+async fn resolve_entry_id(state: &AppState, requested: Option<Uuid>) -> ApiResult<Uuid> {
+    let Some(id) = requested else {
+        return Ok(state.config().id_strategy.generate());
+    };
+    if id.is_nil() {
+        return Err(ApiError::BadRequest(
+            "Entry id must not be the nil UUID".to_string(),
+        ));
+    }
+    if state.store().entry_exists(id).await? {
+        return Err(entry_id_taken(id));
+    }
+    Ok(id)
+}
+
+/// The conflict for a client-supplied ID that is already taken. It does not
+/// say where, so writes cannot probe other notebooks' entries.
+fn entry_id_taken(id: Uuid) -> ApiError {
+    ApiError::Coded(
+        ErrorCode::DuplicateEntry,
+        format!("Entry id {} is not available", id),
+    )
+}
+
+/// Build the in-memory entry used for integration cost computation.
+fn build_candidate_entry(
+    entry_id: Uuid,
+    content: Vec<u8>,
+    request: &CreateEntryRequest,
+    author: AuthorId,
+    causal_position: CausalPosition,
+) -> Entry {
+    Entry {
+        id: EntryId::from_uuid(entry_id),
+        content,
+        content_type: request.content_type.clone(),
+        topic: request.topic.clone(),
+        author,
+        signature: vec![0u8; 64],
+        references: request
+            .references
+            .iter()
+            .map(|&u| EntryId::from_uuid(u))
+            .collect(),
+        revision_of: request.revision_of.map(EntryId::from_uuid),
+        causal_position,
+        created: Utc::now(),
+        integration_cost: IntegrationCost::zero(),
+    }
+}
+
+/// Compute an entry's integration cost within the configured deadline.
+///
+/// Returns the cost, whether it was actually computed, and, if the deadline
+/// passed, the still-running computation to backfill once the entry is stored.
+async fn compute_entry_cost(
+    state: &AppState,
+    entry: Entry,
+    notebook_id: NotebookId,
+) -> (IntegrationCost, bool, Option<PendingCost>) {
+    let entry_id = entry.id;
+    let mut engine = state.engines().lock(notebook_id).await;
+    let outcome = compute_cost_bounded(
+        move || engine.compute_cost(&entry, notebook_id),
+        state.config().cost_timeout(),
+    )
+    .await;
+    resolve_cost_outcome(outcome, entry_id)
+}
+
+/// Turn a bounded cost computation into the cost to persist.
+///
+/// Failures and timeouts fall back to zero cost.
+fn resolve_cost_outcome(
+    outcome: CostOutcome,
+    entry_id: EntryId,
+) -> (IntegrationCost, bool, Option<PendingCost>) {
+    match outcome {
+        CostOutcome::Computed(cost) => {
+            tracing::debug!(
+                entry_id = %entry_id,
+                entries_revised = cost.entries_revised,
+                catalog_shift = cost.catalog_shift,
+                orphan = cost.orphan,
+                "Integration cost computed"
+            );
+            (cost, true, None)
+        }
+        CostOutcome::Failed(e) => {
+            tracing::warn!(
+                entry_id = %entry_id,
+                error = %e,
+                "Failed to compute integration cost, using zeros"
+            );
+            (IntegrationCost::zero(), false, None)
+        }
+        CostOutcome::TimedOut(pending) => {
+            tracing::warn!(
+                entry_id = %entry_id,
+                "Integration cost computation timed out, using zeros until backfilled"
+            );
+            (IntegrationCost::zero(), false, Some(pending))
+        }
+    }
+}
+
+/// Add a stored entry to the search index, if one is configured.
+///
+/// The entry becomes searchable with the index's next commit. Failures are
+/// logged; the entry is picked up by the next reindex.
+async fn index_for_search(
+    state: &AppState,
+    notebook_id: NotebookId,
+    entry: &Entry,
+    encrypted: bool,
+) {
+    let Some(index) = state.search_index().cloned() else {
+        return;
+    };
+    let entry_id = entry.id;
+    let entry = entry.clone();
+
+    // Tantivy writes block; keep them off the async workers
+    let result = tokio::task::spawn_blocking(move || {
+        index
+            .index_notebook_entry(notebook_id, &entry, encrypted)
+            .map_err(|e| e.to_string())
+    })
+    .await;
+    let error = match result {
+        Ok(Ok(_)) => return,
+        Ok(Err(e)) => e,
+        Err(e) => format!("indexing task failed: {}", e),
+    };
+    tracing::warn!(
+        entry_id = %entry_id,
+        error = %error,
+        "Failed to index entry for search"
+    );
+}
+
+/// Backfill an entry's stored cost once a timed-out computation finishes.
+///
+/// The notebook's entropy alert is checked once the real cost is stored, and
+/// the cost is charged to the author's write budget if `charge` is set.
+fn spawn_cost_backfill(
+    state: AppState,
+    notebook_id: Uuid,
+    entry_id: Uuid,
+    pending: PendingCost,
+    charge: Option<WriteCharge>,
+) {
+    let tasks = state.background_tasks().clone();
+    tasks.spawn(async move {
+        let cost = match pending.await {
+            Ok(Ok(cost)) => cost,
+            Ok(Err(e)) => {
+                tracing::warn!(entry_id = %entry_id, error = %e, "Cost backfill failed");
+                return;
+            }
+            Err(e) => {
+                tracing::warn!(entry_id = %entry_id, error = %e, "Cost backfill task failed");
+                return;
             }
+        };
+
+        if let Some(charge) = charge {
+            charge.apply(state.write_throttle(), &cost);
         }
-    } else {
-        // Binary content, base64 encode
-        let encoded = base64::engine::general_purpose::STANDARD.encode(content);
-        EntryContent::Binary {
-            data: encoded,
-            encoding: "base64",
+
+        match state
+            .store()
+            .update_integration_cost(entry_id, &IntegrationCostJson::from(cost))
+            .await
+        {
+            Ok(()) => tracing::info!(entry_id = %entry_id, "Integration cost backfilled"),
+            Err(e) => {
+                tracing::warn!(entry_id = %entry_id, error = %e, "Failed to store backfilled cost");
+                return;
+            }
         }
+
+        if let Err(e) = check_entropy_alert(&state, notebook_id, entry_id).await {
+            tracing::warn!(notebook_id = %notebook_id, error = %e, "Entropy alert check failed");
+        }
+    });
+}
+
+/// Queue the propagation of a new entry's cost to the entries sharing its
+/// cluster, if a propagation worker is running.
+///
+/// The job is keyed by the entry's ID, so reads can wait for it. Entries
+/// whose cost was not computed in time are not propagated.
+async fn enqueue_propagation(
+    state: &AppState,
+    notebook_id: NotebookId,
+    entry_id: EntryId,
+    cost: &IntegrationCost,
+) {
+    let Some(monitor) = state.propagation() else {
+        return;
+    };
+    let affected = state
+        .engines()
+        .lock(notebook_id)
+        .await
+        .cluster_peers(&entry_id, notebook_id);
+    let Some(mut job) = create_propagation_job(
+        notebook_id,
+        affected,
+        cost.entries_revised,
+        cost.references_broken,
+        cost.catalog_shift,
+    ) else {
+        return;
+    };
+    job.job_id = *entry_id.as_uuid();
+    monitor.queue().enqueue(job);
+}
+
+/// Add the `X-Propagation-Lag` header: the number of propagation jobs
+/// still queued, so clients can tell how stale cumulative costs may be.
+fn insert_propagation_lag(headers: &mut HeaderMap, state: &AppState) {
+    let depth = state
+        .propagation()
+        .map_or(0, |monitor| monitor.queue_depth());
+    headers.insert("X-Propagation-Lag", HeaderValue::from(depth));
+}
+
+/// Wait, bounded by [`PROPAGATION_WAIT_TIMEOUT`], until the propagation job
+/// of an entry is done.
+///
+/// Returns false if the job was still pending at the deadline.
+async fn wait_for_propagation(state: &AppState, entry_id: Uuid) -> bool {
+    match state.propagation() {
+        Some(monitor) => monitor.wait_for(entry_id, PROPAGATION_WAIT_TIMEOUT).await,
+        None => true,
     }
 }
 
-/// Convert a notebook_core::Entry to EntrySummary.
-fn entry_to_summary(entry: &Entry) -> EntrySummary {
-    EntrySummary {
-        id: entry.id,
-        topic: entry.topic.clone(),
-        author: entry.author,
-        created: entry.created,
+/// Build the `X-Integration-Cost-Computed` response header.
+fn cost_computed_headers(cost_computed: bool) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "X-Integration-Cost-Computed",
+        HeaderValue::from_static(if cost_computed { "true" } else { "false" }),
+    );
+    headers
+}
+
+/// Format an entry ID as a strong ETag header value.
+fn entry_etag(id: Uuid) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{}\"", id)).expect("UUID is a valid header value")
+}
+
+/// Check an `If-Match` header against the latest revision of an entry.
+///
+/// Each tag is an entry ID, optionally quoted or weak (`W/"..."`); `*`
+/// matches any revision. A missing header skips the check. If none of the
+/// tags names the latest revision, a newer revision was written since the
+/// client read the entry and the revise is rejected with 409 Conflict.
+fn check_if_match(if_match: Option<&str>, latest: Uuid) -> ApiResult<()> {
+    let Some(if_match) = if_match else {
+        return Ok(());
+    };
+
+    let matches = if_match.split(',').map(str::trim).any(|tag| {
+        tag == "*"
+            || Uuid::parse_str(tag.trim_start_matches("W/").trim_matches('"'))
+                .is_ok_and(|id| id == latest)
+    });
+
+    if matches {
+        Ok(())
+    } else {
+        Err(ApiError::Coded(
+            ErrorCode::RevisionConflict,
+            format!("Entry has a newer revision {}; re-read and retry", latest),
+        ))
     }
 }
 
+/// Header naming the notebook sequence a write expects to append after.
+pub const IF_NOTEBOOK_SEQUENCE_HEADER: &str = "If-Notebook-Sequence";
+
+/// Parse the `If-Notebook-Sequence` header, if present.
+fn expected_notebook_sequence(headers: &HeaderMap) -> ApiResult<Option<i64>> {
+    headers
+        .get(IF_NOTEBOOK_SEQUENCE_HEADER)
+        .map(|value| {
+            value
+                .to_str()
+                .ok()
+                .and_then(|v| v.trim().parse::<i64>().ok())
+                .filter(|sequence| *sequence >= 0)
+                .ok_or_else(|| {
+                    ApiError::BadRequest("Invalid If-Notebook-Sequence header".to_string())
+                })
+        })
+        .transpose()
+}
+
+/// Check the sequence a write expects against the notebook's current one.
+///
+/// A missing expectation skips the check. If the notebook has moved past
+/// the expected sequence, another write landed since the client observed
+/// the notebook and the write is rejected with 409 Conflict.
+fn check_notebook_sequence(expected: Option<i64>, current: i64) -> ApiResult<()> {
+    match expected {
+        Some(expected) if current > expected => Err(ApiError::Coded(
+            ErrorCode::SequenceConflict,
+            format!(
+                "Notebook is at sequence {}, past the expected {}; re-observe and retry",
+                current, expected
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Encode entry content based on content type for READ response.
+///
+/// JSON content types that parse as valid JSON are returned as structured
+/// JSON. Other textual content types (see [`is_binary_content_type`]) are
+/// decoded as UTF-8. Everything else, including invalid JSON or UTF-8, is
+/// returned base64-encoded.
+fn encode_content(content: &[u8], content_type: &str) -> EntryContent {
+    if is_json_content_type(content_type)
+        && let Ok(value) = serde_json::from_slice::<serde_json::Value>(content)
+    {
+        return EntryContent::Json(value);
+    }
+
+    if !is_binary_content_type(content_type)
+        && !is_json_content_type(content_type)
+        && let Ok(s) = std::str::from_utf8(content)
+    {
+        return EntryContent::Text(s.to_string());
+    }
+
+    // Binary content (or undecodable text), base64 encode
+    let encoded = base64::engine::general_purpose::STANDARD.encode(content);
+    EntryContent::Binary {
+        data: encoded,
+        encoding: "base64",
+    }
+}
+
+/// Summaries of the given entries, keyed by ID, fetched in one query.
+///
+/// Entries that do not exist are left out.
+pub(crate) async fn summarize_entries(
+    store: &Store,
+    ids: &[Uuid],
+) -> ApiResult<HashMap<Uuid, EntrySummary>> {
+    let rows = store.entry_summaries(ids).await?;
+    Ok(rows
+        .iter()
+        .map(|row| (row.id, EntrySummary::from(row)))
+        .collect())
+}
+
+/// Full responses for the given entries, in order, with each entry's
+/// `cost_computed` flag looked up in one query.
+pub(crate) async fn entries_to_responses(
+    store: &Store,
+    entries: &[Entry],
+) -> ApiResult<Vec<EntryResponse>> {
+    let ids: Vec<Uuid> = entries.iter().map(|e| *e.id.as_uuid()).collect();
+    let fallbacks: std::collections::HashSet<Uuid> = store
+        .fallback_cost_entry_ids(&ids)
+        .await?
+        .into_iter()
+        .collect();
+    Ok(entries
+        .iter()
+        .map(|entry| entry_to_response(entry, !fallbacks.contains(entry.id.as_uuid())))
+        .collect())
+}
+
 /// Convert a notebook_core::Entry to full EntryResponse.
-fn entry_to_response(entry: &Entry) -> EntryResponse {
+pub(crate) fn entry_to_response(entry: &Entry, cost_computed: bool) -> EntryResponse {
     EntryResponse {
         id: entry.id,
         content: encode_content(&entry.content, &entry.content_type),
         content_type: entry.content_type.clone(),
+        content_hash: blake3::hash(&entry.content).to_hex().to_string(),
+        content_length: entry.content.len(),
         topic: entry.topic.clone(),
         author: entry.author,
         references: entry.references.clone(),
@@ -277,6 +1018,7 @@ fn entry_to_response(entry: &Entry) -> EntryResponse {
         },
         created: entry.created,
         integration_cost: entry.integration_cost,
+        cost_computed,
     }
 }
 
@@ -290,107 +1032,130 @@ fn entry_to_response(entry: &Entry) -> EntryResponse {
 /// Validates that:
 /// - The notebook exists
 /// - All referenced entries exist
+/// - All referenced entries belong to the same notebook, unless
+///   `allow_external_refs` is set
+/// - The `revision_of` entry, if given, belongs to the same notebook
 ///
 /// # Request
 ///
-/// Body: `{ "content": "...", "content_type": "text/plain", "topic": "optional", "references": [] }`
+/// Body: `{ "id": null, "content": "...", "content_type": "text/plain", "topic": "optional", "references": [], "allow_external_refs": false, "revision_of": null }`
 ///
 /// For binary content, the content field should be base64 encoded.
 ///
+/// Setting `revision_of` writes a revision with its own content, topic and
+/// references. Revising an entry that already has revisions forks it.
+///
+/// Setting `id` stores the entry under that ID instead of a generated one,
+/// so a retried write is rejected rather than stored twice.
+///
+/// `content_type` defaults to the server's default content type. If the
+/// server infers topics, an entry without `topic` gets one built from its
+/// top keywords, reported in the response with `topic_inferred: true`.
+///
+/// Orphan entries are handled by the notebook's orphan policy; see
+/// [`crate::orphan_policy`].
+///
+/// # Headers
+///
+/// - `If-Notebook-Sequence` (optional): The notebook sequence the write is
+///   based on. The write is appended only if no entry was written since.
+///
 /// # Response
 ///
-/// - 201 Created: `{ "entry_id": "...", "causal_position": {...}, "integration_cost": {...} }`
-/// - 400 Bad Request: Invalid request body or invalid references
+/// - 201 Created: `{ "entry_id": "...", "causal_position": {...}, "integration_cost": {...}, "topic_inferred": false }`,
+///   with an `X-Orphan-Warning` header if the entry is an orphan and the notebook warns about them
+/// - 400 Bad Request: Invalid request body, invalid references, invalid revision target, nil `id`,
+///   content that fails validation for its content type, or an invalid `If-Notebook-Sequence`
 /// - 404 Not Found: Notebook not found
+/// - 409 Conflict: Notebook is locked, an entry with the given `id` already exists, or
+///   the notebook is past the sequence named by `If-Notebook-Sequence`
+/// - 415 Unsupported Media Type: Content type not allowed by the content-type policy, or one the
+///   content validators cannot check
+/// - 422 Unprocessable Entity: The entry is an orphan and the notebook rejects orphans
 /// - 500 Internal Server Error: Storage failure
 async fn create_entry(
     State(state): State<AppState>,
     identity: AuthorIdentity,
     Path(notebook_id): Path<Uuid>,
-    Json(request): Json<CreateEntryRequest>,
+    request_headers: HeaderMap,
+    Json(mut request): Json<CreateEntryRequest>,
 ) -> ApiResult<(StatusCode, HeaderMap, Json<CreateEntryResponse>)> {
     require_scope(&identity, "notebook:write", state.config())?;
+    let expected_sequence = expected_notebook_sequence(&request_headers)?;
+    apply_default_content_type(&mut request, state.config());
     let author_id = identity.author_id;
     let store = state.store();
     let pool = store.pool();
 
-    // 1. Validate notebook exists
-    store.get_notebook(notebook_id).await.map_err(|e| match e {
-        StoreError::NotebookNotFound(id) => {
-            ApiError::NotFound(format!("Notebook {} not found", id))
-        }
+    // 1. Validate notebook exists and accepts writes
+    let notebook = store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
         other => ApiError::Store(other),
     })?;
-
-    // 2. Validate all references exist
-    for ref_id in &request.references {
-        if !store.entry_exists(*ref_id).await? {
-            return Err(ApiError::BadRequest(format!(
-                "Referenced entry {} does not exist",
-                ref_id
-            )));
-        }
+    ensure_unlocked(&notebook)?;
+    check_notebook_sequence(expected_sequence, notebook.current_sequence)?;
+    enforce_content_policy(&state, notebook_id, &request.content_type).await?;
+    let charge = enforce_write_budget(&state, notebook_id, author_id).await?;
+
+    // 2. Validate references exist and belong to this notebook
+    validate_references(
+        store,
+        state.config(),
+        notebook_id,
+        &request.references,
+        request.allow_external_refs,
+    )
+    .await?;
+    if let Some(target) = request.revision_of {
+        validate_revision_target(store, notebook_id, target).await?;
     }
+    let entry_id = resolve_entry_id(&state, request.id).await?;
 
-    // 3. Get content bytes (decode base64 if binary)
+    // 3. Get content bytes (decode base64 if binary) and check them
     let content = get_content_bytes(&request)?;
+    state
+        .content_validators()
+        .validate(&request.content_type, &content)?;
 
-    // 4. Assign causal position
-    let causal_position =
-        CausalPositionService::assign_position(pool, NotebookId::from_uuid(notebook_id), author_id)
-            .await
-            .map_err(|e| match e {
-                StoreError::NotebookNotFound(id) => {
-                    ApiError::NotFound(format!("Notebook {} not found", id))
-                }
-                other => ApiError::Store(other),
-            })?;
-
-    // 6. Build Entry for cost computation
-    let entry_id = Uuid::new_v4();
-    let references: Vec<EntryId> = request
-        .references
-        .iter()
-        .map(|&u| EntryId::from_uuid(u))
-        .collect();
-    let temp_entry = Entry {
-        id: EntryId::from_uuid(entry_id),
-        content: content.clone(),
-        content_type: request.content_type.clone(),
-        topic: request.topic.clone(),
-        author: author_id,
-        signature: vec![0u8; 64],
-        references: references.clone(),
-        revision_of: None,
-        causal_position,
-        created: Utc::now(),
-        integration_cost: IntegrationCost::zero(),
-    };
-
-    // 7. Compute integration cost using entropy engine
-    let (integration_cost, cost_computed) = {
-        let mut engine = state.engine().lock().await;
-        match engine.compute_cost(&temp_entry, NotebookId::from_uuid(notebook_id)) {
-            Ok(cost) => {
-                tracing::debug!(
-                    entry_id = %entry_id,
-                    entries_revised = cost.entries_revised,
-                    catalog_shift = cost.catalog_shift,
-                    orphan = cost.orphan,
-                    "Integration cost computed"
-                );
-                (cost, true)
-            }
-            Err(e) => {
-                tracing::warn!(
-                    entry_id = %entry_id,
-                    error = %e,
-                    "Failed to compute integration cost, using zeros"
-                );
-                (IntegrationCost::zero(), false)
-            }
-        }
-    };
+    // 4. Build Entry for cost computation, inferring its topic if missing
+    let mut temp_entry = build_candidate_entry(
+        entry_id,
+        content.clone(),
+        &request,
+        author_id,
+        CausalPosition::first(),
+    );
+    let topic_inferred =
+        infer_missing_topic(&state, &mut temp_entry, NotebookId::from_uuid(notebook_id)).await;
+
+    // 5. Apply the notebook's orphan policy before anything is written
+    let orphan_warning = enforce_orphan_policy(&state, &notebook, &temp_entry).await?;
+
+    // 6. Assign causal position; the sequence expectation is rechecked
+    // atomically, since other writes may have landed meanwhile
+    let causal_position = CausalPositionService::assign_position_after(
+        pool,
+        NotebookId::from_uuid(notebook_id),
+        author_id,
+        expected_sequence,
+    )
+    .await
+    .map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+    temp_entry.causal_position = causal_position;
+
+    // 7. Compute integration cost using entropy engine (bounded by deadline)
+    let (integration_cost, cost_computed, pending_cost) = compute_entry_cost(
+        &state,
+        temp_entry.clone(),
+        NotebookId::from_uuid(notebook_id),
+    )
+    .await;
+    if cost_computed && let Some(charge) = &charge {
+        charge.apply(state.write_throttle(), &integration_cost);
+    }
 
     // 8. Build NewEntry with computed cost
     let cost_json = IntegrationCostJson {
@@ -403,10 +1168,12 @@ async fn create_entry(
         .id(entry_id)
         .content(content)
         .content_type(request.content_type)
-        .topic(request.topic)
+        .topic(temp_entry.topic.clone())
         .signature(vec![0u8; 64]) // Placeholder signature (Phase 1)
         .references(request.references)
+        .revision_of(request.revision_of)
         .integration_cost(cost_json)
+        .cost_computed(cost_computed)
         .build();
 
     // 9. Store the entry
@@ -417,9 +1184,35 @@ async fn create_entry(
         StoreError::InvalidRevision(id) => {
             ApiError::BadRequest(format!("Revision target {} does not exist", id))
         }
+        StoreError::DuplicateEntry(id) => entry_id_taken(id),
         other => ApiError::Store(other),
     })?;
 
+    // Check the entropy alert once the entry's cost is stored
+    match pending_cost {
+        Some(pending) => spawn_cost_backfill(state.clone(), notebook_id, entry_id, pending, charge),
+        None => spawn_entropy_alert_check(state.clone(), notebook_id, entry_id),
+    }
+    if cost_computed {
+        enqueue_propagation(
+            &state,
+            NotebookId::from_uuid(notebook_id),
+            temp_entry.id,
+            &integration_cost,
+        )
+        .await;
+    }
+    index_for_search(
+        &state,
+        NotebookId::from_uuid(notebook_id),
+        &temp_entry,
+        notebook.encrypted,
+    )
+    .await;
+    state
+        .notebook_profiles()
+        .invalidate(&NotebookId::from_uuid(notebook_id));
+
     tracing::info!(
         entry_id = %entry_id,
         notebook_id = %notebook_id,
@@ -427,41 +1220,224 @@ async fn create_entry(
         "Entry created successfully"
     );
 
-    // 10. Publish event to SSE subscribers
-    let broadcaster = state.broadcaster();
-    if let Some(subscriber_count) = broadcaster
-        .publish_entry(
-            notebook_id,
-            entry_id,
-            "write",
-            integration_cost,
-            causal_position.sequence,
-        )
-        .await
-    {
-        tracing::debug!(
-            entry_id = %entry_id,
-            subscribers = subscriber_count,
-            "Published write event to SSE subscribers"
-        );
-    }
+    // 10. Publish the logged event to SSE and WebSocket subscribers
+    let published = state
+        .broadcaster()
+        .publish_from_log(state.store(), notebook_id)
+        .await;
+    tracing::debug!(
+        entry_id = %entry_id,
+        events = published,
+        "Published write event to subscribers"
+    );
 
     // 11. Build response with headers
-    let mut headers = HeaderMap::new();
-    headers.insert(
-        "X-Integration-Cost-Computed",
-        HeaderValue::from_static(if cost_computed { "true" } else { "false" }),
-    );
+    let mut headers = cost_computed_headers(cost_computed);
+    insert_propagation_lag(&mut headers, &state);
+    if let Some(warning) = orphan_warning
+        && let Ok(value) = HeaderValue::from_str(&warning)
+    {
+        headers.insert(ORPHAN_WARNING_HEADER, value);
+    }
 
     let response = CreateEntryResponse {
         entry_id,
         causal_position,
         integration_cost,
+        topic: temp_entry.topic.filter(|_| topic_inferred),
+        topic_inferred,
     };
 
     Ok((StatusCode::CREATED, headers, Json(response)))
 }
 
+/// POST /notebooks/:id/entries/preview - Preview the integration cost of an entry.
+///
+/// Runs the same validation as entry creation and computes the integration
+/// cost the entry would incur, without persisting anything, assigning a
+/// causal position, or mutating the notebook's coherence snapshot.
+///
+/// # Request
+///
+/// Body: same as `POST /notebooks/:id/entries`.
+///
+/// # Response
+///
+/// - 200 OK: `{ "integration_cost": {...}, "orphan": false }`
+/// - 400 Bad Request: Invalid request body, invalid references or invalid content
+/// - 404 Not Found: Notebook not found
+/// - 415 Unsupported Media Type: Content type not allowed by the content-type policy
+async fn preview_entry(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+    Json(mut request): Json<CreateEntryRequest>,
+) -> ApiResult<Json<PreviewEntryResponse>> {
+    require_scope(&identity, "notebook:write", state.config())?;
+    state
+        .store()
+        .get_notebook(notebook_id)
+        .await
+        .map_err(|e| match e {
+            StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+            other => ApiError::Store(other),
+        })?;
+
+    let candidate = preview_candidate(&state, &identity, notebook_id, &mut request, &[]).await?;
+
+    let notebook_id = NotebookId::from_uuid(notebook_id);
+    let integration_cost = state
+        .engines()
+        .lock(notebook_id)
+        .await
+        .compute_cost_preview(&candidate, notebook_id)
+        .map_err(|e| ApiError::Internal(format!("Failed to compute integration cost: {}", e)))?;
+
+    Ok(Json(PreviewEntryResponse {
+        integration_cost,
+        orphan: integration_cost.orphan,
+    }))
+}
+
+/// Validate a previewed entry as on create and build its candidate entry.
+///
+/// `batch` holds the IDs of the entries previewed before this one in the
+/// same batch; references and a revision target naming one of them resolve
+/// without a lookup, as they would once those entries were written.
+async fn preview_candidate(
+    state: &AppState,
+    identity: &AuthorIdentity,
+    notebook_id: Uuid,
+    request: &mut CreateEntryRequest,
+    batch: &[Uuid],
+) -> ApiResult<Entry> {
+    apply_default_content_type(request, state.config());
+    let store = state.store();
+
+    enforce_content_policy(state, notebook_id, &request.content_type).await?;
+    check_reference_count(&request.references, state.config().max_references_per_entry)?;
+    let stored: Vec<Uuid> = request
+        .references
+        .iter()
+        .copied()
+        .filter(|id| !batch.contains(id))
+        .collect();
+    validate_references(
+        store,
+        state.config(),
+        notebook_id,
+        &stored,
+        request.allow_external_refs,
+    )
+    .await?;
+    if let Some(target) = request.revision_of
+        && !batch.contains(&target)
+    {
+        validate_revision_target(store, notebook_id, target).await?;
+    }
+    let content = get_content_bytes(request)?;
+    state
+        .content_validators()
+        .validate(&request.content_type, &content)?;
+
+    Ok(build_candidate_entry(
+        request.id.unwrap_or_else(Uuid::new_v4),
+        content,
+        request,
+        identity.author_id,
+        CausalPosition::first(),
+    ))
+}
+
+/// Check the size of a batch preview.
+fn check_preview_batch_size(count: usize) -> ApiResult<()> {
+    if count == 0 {
+        return Err(ApiError::BadRequest(
+            "entries must not be empty".to_string(),
+        ));
+    }
+    if count > MAX_PREVIEW_BATCH_ENTRIES {
+        return Err(ApiError::BadRequest(format!(
+            "Too many entries: {} requested, at most {} allowed",
+            count, MAX_PREVIEW_BATCH_ENTRIES
+        )));
+    }
+    Ok(())
+}
+
+/// POST /notebooks/:id/entries/preview/batch - Preview the integration costs
+/// of a sequence of writes.
+///
+/// Validates each entry as on create, then computes their costs in order
+/// against a copy of the notebook's coherence snapshot, so each cost
+/// reflects the entries before it: the costs writing them one after another
+/// would incur, assuming no other writes in between. Nothing is persisted
+/// and the notebook's snapshot is not modified.
+///
+/// References and `revision_of` may name the client-supplied `id` of an
+/// earlier entry in the batch.
+///
+/// # Request
+///
+/// Body: `{ "entries": [...] }`, each as in `POST /notebooks/:id/entries`.
+///
+/// # Response
+///
+/// - 200 OK: `{ "costs": [{ "integration_cost": {...}, "orphan": false }], "total": { "entries_revised": 0, "references_broken": 0, "catalog_shift": 0.4, "orphans": 1 } }`
+/// - 400 Bad Request: No entries, more than 100, or an invalid entry
+/// - 404 Not Found: Notebook not found
+/// - 415 Unsupported Media Type: Content type not allowed by the content-type policy
+async fn preview_entry_batch(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+    Json(mut request): Json<PreviewBatchRequest>,
+) -> ApiResult<Json<PreviewBatchResponse>> {
+    require_scope(&identity, "notebook:write", state.config())?;
+    check_preview_batch_size(request.entries.len())?;
+    state
+        .store()
+        .get_notebook(notebook_id)
+        .await
+        .map_err(|e| match e {
+            StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+            other => ApiError::Store(other),
+        })?;
+
+    let mut candidates: Vec<Entry> = Vec::with_capacity(request.entries.len());
+    let mut batch = Vec::with_capacity(request.entries.len());
+    for entry in &mut request.entries {
+        let candidate = preview_candidate(&state, &identity, notebook_id, entry, &batch).await?;
+        batch.push(*candidate.id.as_uuid());
+        candidates.push(candidate);
+    }
+
+    let notebook_id = NotebookId::from_uuid(notebook_id);
+    let costs = state
+        .engines()
+        .lock(notebook_id)
+        .await
+        .compute_cost_preview_batch(&candidates, notebook_id)
+        .map_err(|e| ApiError::Internal(format!("Failed to compute integration cost: {}", e)))?;
+
+    tracing::debug!(
+        notebook_id = %notebook_id,
+        entries = costs.len(),
+        "Previewed batch integration cost"
+    );
+
+    Ok(Json(PreviewBatchResponse {
+        total: BatchCostTotal::of(&costs),
+        costs: costs
+            .into_iter()
+            .map(|integration_cost| PreviewEntryResponse {
+                integration_cost,
+                orphan: integration_cost.orphan,
+            })
+            .collect(),
+    }))
+}
+
 /// PUT /notebooks/:id/entries/:entry_id - Revise an entry.
 ///
 /// Creates a new entry that is a revision of the specified entry.
@@ -470,18 +1446,28 @@ async fn create_entry(
 ///
 /// # Request
 ///
-/// - Body: `{ "content": "new content", "reason": "optional reason" }`
+/// - Body: `{ "content": "new content", "reason": "optional reason", "topic": "optional", "references": [...], "allow_external_refs": false }`.
+///   `topic` and `references` replace the original's when present.
+/// - `If-Match` (optional): ETag of the revision the edit is based on. The
+///   revise only succeeds if that is still the latest revision of the entry
+///   when the new revision is stored, and the new revision's `revision_of`
+///   is then that revision.
 ///
 /// # Response
 ///
-/// - 200 OK: `{ "revision_id": "...", "causal_position": {...}, "integration_cost": {...} }`
-/// - 400 Bad Request: Invalid request body
+/// - 200 OK: `{ "revision_id": "...", "causal_position": {...}, "integration_cost": {...}, "no_op": false }`,
+///   with an `ETag` header naming the new revision. If the server skips no-op revisions and
+///   the content, topic and references match the entry's latest revision, nothing is written
+///   and that revision is returned with `no_op: true`.
+/// - 400 Bad Request: Invalid request body, `If-Match` header, references or content
 /// - 404 Not Found: Notebook or entry not found
+/// - 409 Conflict: Notebook is locked, or `If-Match` names a stale revision
 /// - 500 Internal Server Error: Storage failure
 async fn revise_entry(
     State(state): State<AppState>,
     identity: AuthorIdentity,
     Path((notebook_id, entry_id)): Path<(Uuid, Uuid)>,
+    request_headers: HeaderMap,
     Json(request): Json<ReviseRequest>,
 ) -> ApiResult<(HeaderMap, Json<ReviseResponse>)> {
     require_scope(&identity, "notebook:write", state.config())?;
@@ -496,6 +1482,18 @@ async fn revise_entry(
         );
     }
 
+    // Locked notebooks are read-only
+    let notebook = state
+        .store()
+        .get_notebook(notebook_id)
+        .await
+        .map_err(|e| match e {
+            StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+            other => ApiError::Store(other),
+        })?;
+    ensure_unlocked(&notebook)?;
+    let charge = enforce_write_budget(&state, notebook_id, author_id).await?;
+
     // Create a Repository from the store
     let repo = Repository::new(state.store().clone());
 
@@ -508,6 +1506,73 @@ async fn revise_entry(
         tracing::warn!(error = %e, "Failed to fetch original entry");
         e
     })?;
+    state
+        .content_validators()
+        .validate(&original.content_type, request.content.as_bytes())?;
+
+    // Replacement references are validated like those of a new entry
+    if let Some(references) = &request.references {
+        validate_references(
+            state.store(),
+            state.config(),
+            *notebook_id.as_uuid(),
+            references,
+            request.allow_external_refs,
+        )
+        .await?;
+    }
+    let (topic, references) = request.metadata_for(&original);
+
+    // Optimistic concurrency: reject edits based on a superseded revision
+    let if_match = request_headers
+        .get(IF_MATCH)
+        .map(|value| {
+            value
+                .to_str()
+                .map_err(|_| ApiError::BadRequest("Invalid If-Match header".to_string()))
+        })
+        .transpose()?;
+    // A revise naming the revision it was based on extends that revision,
+    // provided it is still the latest when the revision is stored
+    let mut based_on = None;
+    if if_match.is_some() || state.config().skip_noop_revisions {
+        let latest = state
+            .store()
+            .latest_revision_of(*entry_id.as_uuid())
+            .await?;
+        check_if_match(if_match, latest)?;
+        if if_match.is_some_and(|tags| tags.trim() != "*") {
+            based_on = Some(EntryId::from_uuid(latest));
+        }
+
+        // A revise that would repeat the latest revision writes nothing
+        if state.config().skip_noop_revisions {
+            let latest = EntryId::from_uuid(latest);
+            let current = if latest == entry_id {
+                original.clone()
+            } else {
+                repo.get_entry(latest).await?
+            };
+            if request.is_noop_for(&original, &current) {
+                tracing::info!(
+                    entry_id = %entry_id,
+                    revision_id = %latest,
+                    "Revise matches the latest revision, nothing written"
+                );
+                let mut headers = HeaderMap::new();
+                headers.insert(ETAG, entry_etag(*latest.as_uuid()));
+                return Ok((
+                    headers,
+                    Json(ReviseResponse {
+                        revision_id: latest,
+                        causal_position: current.causal_position,
+                        integration_cost: current.integration_cost,
+                        no_op: true,
+                    }),
+                ));
+            }
+        }
+    }
 
     // Assign causal position for the new revision
     let causal_position =
@@ -519,45 +1584,27 @@ async fn revise_entry(
             })?;
 
     // Create the revision entry (with placeholder cost for now)
-    let revision_id = EntryId::new();
+    let revision_id = EntryId::generate(state.config().id_strategy);
     let revision_entry = Entry {
         id: revision_id,
         content: request.content.into_bytes(),
         content_type: original.content_type.clone(),
-        topic: original.topic.clone(),
+        topic,
         author: author_id,
         signature: vec![0u8; 64], // Placeholder signature
-        references: original.references.clone(),
-        revision_of: Some(entry_id),
+        references,
+        revision_of: Some(based_on.unwrap_or(entry_id)),
         causal_position,
         created: Utc::now(),
         integration_cost: IntegrationCost::zero(),
     };
 
-    // Compute integration cost using entropy engine
-    let (integration_cost, cost_computed) = {
-        let mut engine = state.engine().lock().await;
-        match engine.compute_cost(&revision_entry, notebook_id) {
-            Ok(cost) => {
-                tracing::debug!(
-                    revision_id = %revision_id,
-                    entries_revised = cost.entries_revised,
-                    catalog_shift = cost.catalog_shift,
-                    orphan = cost.orphan,
-                    "Integration cost computed for revision"
-                );
-                (cost, true)
-            }
-            Err(e) => {
-                tracing::warn!(
-                    revision_id = %revision_id,
-                    error = %e,
-                    "Failed to compute integration cost for revision, using zeros"
-                );
-                (IntegrationCost::zero(), false)
-            }
-        }
-    };
+    // Compute integration cost using entropy engine (bounded by deadline)
+    let (integration_cost, cost_computed, pending_cost) =
+        compute_entry_cost(&state, revision_entry.clone(), notebook_id).await;
+    if cost_computed && let Some(charge) = &charge {
+        charge.apply(state.write_throttle(), &integration_cost);
+    }
 
     // Update entry with computed cost
     let revision_entry = Entry {
@@ -569,12 +1616,35 @@ async fn revise_entry(
     let input = StoreEntryInput {
         entry: revision_entry,
         notebook_id,
+        cost_computed,
+        if_latest_revision_of: based_on.map(|_| entry_id),
     };
 
     repo.store_entry_in_notebook(&input).await.map_err(|e| {
         tracing::error!(error = %e, "Failed to store revision entry");
         e
     })?;
+    index_for_search(&state, notebook_id, &input.entry, notebook.encrypted).await;
+    state.notebook_profiles().invalidate(&notebook_id);
+
+    // Check the entropy alert once the revision's cost is stored
+    match pending_cost {
+        Some(pending) => spawn_cost_backfill(
+            state.clone(),
+            *notebook_id.as_uuid(),
+            *revision_id.as_uuid(),
+            pending,
+            charge,
+        ),
+        None => spawn_entropy_alert_check(
+            state.clone(),
+            *notebook_id.as_uuid(),
+            *revision_id.as_uuid(),
+        ),
+    }
+    if cost_computed {
+        enqueue_propagation(&state, notebook_id, revision_id, &integration_cost).await;
+    }
 
     tracing::info!(
         revision_id = %revision_id,
@@ -583,31 +1653,21 @@ async fn revise_entry(
         "Entry revised successfully"
     );
 
-    // Publish event to SSE subscribers
-    let broadcaster = state.broadcaster();
-    if let Some(subscriber_count) = broadcaster
-        .publish_entry(
-            *notebook_id.as_uuid(),
-            *revision_id.as_uuid(),
-            "revise",
-            integration_cost,
-            causal_position.sequence,
-        )
-        .await
-    {
-        tracing::debug!(
-            revision_id = %revision_id,
-            subscribers = subscriber_count,
-            "Published revise event to SSE subscribers"
-        );
-    }
+    // Publish the logged event to SSE and WebSocket subscribers
+    let published = state
+        .broadcaster()
+        .publish_from_log(state.store(), *notebook_id.as_uuid())
+        .await;
+    tracing::debug!(
+        revision_id = %revision_id,
+        events = published,
+        "Published revise event to subscribers"
+    );
 
     // Build response with headers
-    let mut headers = HeaderMap::new();
-    headers.insert(
-        "X-Integration-Cost-Computed",
-        HeaderValue::from_static(if cost_computed { "true" } else { "false" }),
-    );
+    let mut headers = cost_computed_headers(cost_computed);
+    headers.insert(ETAG, entry_etag(*revision_id.as_uuid()));
+    insert_propagation_lag(&mut headers, &state);
 
     Ok((
         headers,
@@ -615,45 +1675,65 @@ async fn revise_entry(
             revision_id,
             causal_position,
             integration_cost,
+            no_op: false,
         }),
     ))
 }
 
 /// GET /notebooks/:notebook_id/entries/:entry_id - Get an entry with metadata.
 ///
-/// Returns the full entry along with revision history, references, and
-/// entries that reference this one.
+/// Returns the entry and its revision count. Revision history, references,
+/// and entries that reference this one are loaded only when requested, since
+/// each costs extra queries.
+///
+/// Public notebooks can be read without credentials. Anonymous readers only
+/// see entries of the notebook itself, and related entries from other
+/// notebooks are left out.
 ///
 /// # Query Parameters
 ///
 /// - `revision`: Optional revision number (0 = current entry, 1 = first revision, etc.)
+/// - `include`: Comma-separated related collections to load:
+///   `revisions`, `references`, `referenced_by` (default: none)
+/// - `wait_for_propagation`: If `true`, first wait (up to 5 seconds) until
+///   the cost propagation job of the entry's write has completed
 ///
 /// # Response
 ///
-/// - 200 OK: `{ "entry": {...}, "revisions": [...], "references": [...], "referenced_by": [...] }`
-/// - 400 Bad Request: Invalid revision number
+/// - 200 OK: `{ "entry": {...}, "revision_count": N, "revisions": [...], "references": [...], "referenced_by": [...] }`,
+///   with an `ETag` header naming the returned entry (usable as `If-Match` on revise).
+///   Collections not named in `include` are empty. If the propagation wait
+///   timed out, an `X-Propagation-Lag` header gives the queue depth.
+/// - 400 Bad Request: Invalid revision number or unknown `include` value
+/// - 401 Unauthorized: No credentials and the notebook is not public
 /// - 404 Not Found: Notebook or entry not found
+/// - 429 Too Many Requests: Anonymous read limit exceeded
 async fn get_entry(
     State(state): State<AppState>,
-    identity: AuthorIdentity,
+    reader: ReaderIdentity,
     Path((notebook_id, entry_id)): Path<(Uuid, Uuid)>,
     Query(params): Query<GetEntryParams>,
-) -> ApiResult<Json<ReadEntryResponse>> {
-    require_scope(&identity, "notebook:read", state.config())?;
+) -> ApiResult<(HeaderMap, Json<ReadEntryResponse>)> {
+    let includes = Includes::parse(params.include.as_deref())?;
+
+    // First verify the notebook exists and may be read
+    readable_notebook(&state, &reader, notebook_id).await?;
+
     // Create repository from store
     let repo = Repository::new(state.store().clone());
+    let anonymous = matches!(reader, ReaderIdentity::Anonymous { .. });
+
+    if anonymous
+        && state
+            .store()
+            .entries_in_notebook(notebook_id, &[entry_id])
+            .await?
+            .is_empty()
+    {
+        return Err(ApiError::entry_not_found(entry_id));
+    }
 
-    // First verify the notebook exists
-    let _notebook = repo
-        .get_notebook(NotebookId::from_uuid(notebook_id))
-        .await
-        .map_err(|e| match e {
-            StoreError::NotebookNotFound(_) => {
-                ApiError::NotFound(format!("Notebook {} not found", notebook_id))
-            }
-            _ => ApiError::from(e),
-        })?;
-
+    let propagated = !params.wait_for_propagation || wait_for_propagation(&state, entry_id).await;
     let entry_id = EntryId::from_uuid(entry_id);
 
     // Get the entry (optionally at specific revision)
@@ -676,39 +1756,170 @@ async fn get_entry(
         _ => {
             // Get current entry (revision 0 or not specified)
             repo.get_entry(entry_id).await.map_err(|e| match e {
-                StoreError::EntryNotFound(_) => {
-                    ApiError::NotFound(format!("Entry {} not found", entry_id))
-                }
+                StoreError::EntryNotFound(_) => ApiError::entry_not_found(entry_id),
                 _ => ApiError::from(e),
             })?
         }
     };
 
+    let revision_count = state
+        .store()
+        .revision_count(*entry_id.as_uuid())
+        .await
+        .unwrap_or_default();
+
     // Get revision chain (entries that revise this entry)
-    let revision_chain = repo.get_revision_chain(entry_id).await.unwrap_or_default();
-    let revisions: Vec<EntrySummary> = revision_chain.iter().map(entry_to_summary).collect();
+    let chain = if includes.revisions {
+        repo.get_revision_chain(entry_id).await.unwrap_or_default()
+    } else {
+        RevisionChain::default()
+    };
+    if chain.cycle {
+        tracing::warn!(entry_id = %entry_id, "Revision chain loops back on itself");
+    }
+    let revision_chain = chain.revisions;
 
     // Get references (entries this entry references)
-    let refs = repo.get_references(entry_id).await.unwrap_or_default();
-    let references: Vec<EntrySummary> = refs.iter().map(entry_to_summary).collect();
+    let refs = if includes.references {
+        repo.get_references(entry_id).await.unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    // Get referenced_by (entries that reference this entry)
+    let citing = if includes.referenced_by {
+        repo.get_referencing(entry_id).await.unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    // Summarize all related entries in one query, keeping each list's order
+    let mut related: Vec<Uuid> = revision_chain
+        .iter()
+        .chain(&refs)
+        .chain(&citing)
+        .map(|e| *e.id.as_uuid())
+        .collect();
+    if anonymous {
+        related = state
+            .store()
+            .entries_in_notebook(notebook_id, &related)
+            .await?;
+    }
+    let summaries = summarize_entries(state.store(), &related).await?;
+    let summarize = |entries: &[Entry]| -> Vec<EntrySummary> {
+        entries
+            .iter()
+            .filter_map(|e| summaries.get(e.id.as_uuid()).cloned())
+            .collect()
+    };
+    let revisions = summarize(&revision_chain);
+    let references = summarize(&refs);
+    let referenced_by = summarize(&citing);
+
+    tracing::debug!(
+        entry_id = %entry_id,
+        revision_count,
+        revisions_count = revisions.len(),
+        references_count = references.len(),
+        referenced_by_count = referenced_by.len(),
+        "Entry retrieved"
+    );
+
+    let cost_computed = state
+        .store()
+        .fallback_cost_entry_ids(&[*entry.id.as_uuid()])
+        .await?
+        .is_empty();
+
+    let mut headers = HeaderMap::new();
+    headers.insert(ETAG, entry_etag(*entry.id.as_uuid()));
+    if !propagated {
+        tracing::debug!(entry_id = %entry_id, "Timed out waiting for cost propagation");
+        insert_propagation_lag(&mut headers, &state);
+    }
+
+    Ok((
+        headers,
+        Json(ReadEntryResponse {
+            entry: entry_to_response(&entry, cost_computed),
+            revision_count,
+            revisions,
+            revisions_truncated: chain.truncated,
+            references,
+            referenced_by,
+        }),
+    ))
+}
+
+/// Validate and deduplicate the IDs of a bulk read, keeping request order.
+fn bulk_read_ids(ids: &[Uuid]) -> ApiResult<Vec<EntryId>> {
+    if ids.len() > MAX_BULK_READ_IDS {
+        return Err(ApiError::BadRequest(format!(
+            "Too many ids: {} requested, at most {} allowed",
+            ids.len(),
+            MAX_BULK_READ_IDS
+        )));
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    Ok(ids
+        .iter()
+        .filter(|id| seen.insert(**id))
+        .map(|id| EntryId::from_uuid(*id))
+        .collect())
+}
+
+/// Requested IDs missing from the fetched entries, in request order.
+fn missing_ids(requested: &[EntryId], found: &[Entry]) -> Vec<Uuid> {
+    let found: std::collections::HashSet<EntryId> = found.iter().map(|e| e.id).collect();
+    requested
+        .iter()
+        .filter(|id| !found.contains(id))
+        .map(|id| *id.as_uuid())
+        .collect()
+}
+
+/// POST /notebooks/:notebook_id/entries/get - Get several entries at once.
+///
+/// Body: `{ "ids": ["...", "..."] }`
+///
+/// Fetches the entries with one batch query. IDs that don't exist, or that
+/// belong to another notebook, are listed in `not_found` instead of failing
+/// the request. Duplicate IDs are returned once.
+///
+/// # Response
+///
+/// - 200 OK: `{ "entries": [{...}], "not_found": ["..."] }`
+/// - 400 Bad Request: More than 100 ids
+/// - 401 Unauthorized: No credentials and the notebook is not public
+/// - 404 Not Found: Notebook not found
+/// - 429 Too Many Requests: Anonymous read limit exceeded
+async fn bulk_read_entries(
+    State(state): State<AppState>,
+    reader: ReaderIdentity,
+    Path(notebook_id): Path<Uuid>,
+    Json(request): Json<BulkReadRequest>,
+) -> ApiResult<Json<BulkReadResponse>> {
+    let ids = bulk_read_ids(&request.ids)?;
+    readable_notebook(&state, &reader, notebook_id).await?;
+
+    let repo = Repository::new(state.store().clone());
+    let notebook_id = NotebookId::from_uuid(notebook_id);
 
-    // Get referenced_by (entries that reference this entry)
-    let citing = repo.get_referencing(entry_id).await.unwrap_or_default();
-    let referenced_by: Vec<EntrySummary> = citing.iter().map(entry_to_summary).collect();
+    let entries = repo.get_entries_in_notebook(notebook_id, &ids).await?;
+    let not_found = missing_ids(&ids, &entries);
 
     tracing::debug!(
-        entry_id = %entry_id,
-        revisions_count = revisions.len(),
-        references_count = references.len(),
-        referenced_by_count = referenced_by.len(),
-        "Entry retrieved"
+        notebook_id = %notebook_id,
+        requested = ids.len(),
+        found = entries.len(),
+        "Bulk read entries"
     );
 
-    Ok(Json(ReadEntryResponse {
-        entry: entry_to_response(&entry),
-        revisions,
-        references,
-        referenced_by,
+    Ok(Json(BulkReadResponse {
+        entries: entries_to_responses(state.store(), &entries).await?,
+        not_found,
     }))
 }
 
@@ -716,6 +1927,12 @@ async fn get_entry(
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/notebooks/{id}/entries", post(create_entry))
+        .route("/notebooks/{id}/entries/preview", post(preview_entry))
+        .route(
+            "/notebooks/{id}/entries/preview/batch",
+            post(preview_entry_batch),
+        )
+        .route("/notebooks/{id}/entries/get", post(bulk_read_entries))
         .route(
             "/notebooks/{id}/entries/{entry_id}",
             put(revise_entry).get(get_entry),
@@ -742,6 +1959,151 @@ mod tests {
         assert_eq!(request.content_type, "text/plain");
         assert!(request.topic.is_none());
         assert!(request.references.is_empty());
+        assert!(!request.allow_external_refs);
+    }
+
+    #[test]
+    fn test_create_entry_request_allow_external_refs() {
+        let json = r#"{"content": "x", "content_type": "text/plain", "allow_external_refs": true}"#;
+        let request: CreateEntryRequest = serde_json::from_str(json).unwrap();
+        assert!(request.allow_external_refs);
+    }
+
+    fn make_notebook_row(is_locked: bool) -> NotebookRow {
+        NotebookRow {
+            id: Uuid::new_v4(),
+            name: "Test".to_string(),
+            owner_id: vec![0u8; 32],
+            created: Utc::now(),
+            current_sequence: 0,
+            is_locked,
+            encrypted: false,
+            is_public: false,
+            orphan_policy: "allow".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_write_to_locked_notebook_rejected() {
+        let mut notebook = make_notebook_row(true);
+
+        let err = ensure_unlocked(&notebook).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::NotebookLocked);
+        assert_eq!(err.status_code(), StatusCode::CONFLICT);
+
+        // Unlocking makes the notebook writable again
+        notebook.is_locked = false;
+        assert!(ensure_unlocked(&notebook).is_ok());
+    }
+
+    #[test]
+    fn test_stale_if_match_conflicts() {
+        let base = Uuid::new_v4();
+        let latest = Uuid::new_v4();
+
+        let etag = format!("\"{}\"", base);
+        let err = check_if_match(Some(&etag), latest).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::RevisionConflict);
+        assert_eq!(err.status_code(), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn test_current_if_match_succeeds() {
+        let latest = Uuid::new_v4();
+        let etag = entry_etag(latest);
+
+        assert!(check_if_match(Some(etag.to_str().unwrap()), latest).is_ok());
+        assert!(check_if_match(Some(&format!("W/\"{}\"", latest)), latest).is_ok());
+        assert!(check_if_match(Some(&latest.to_string()), latest).is_ok());
+        assert!(check_if_match(Some("*"), latest).is_ok());
+        assert!(check_if_match(None, latest).is_ok());
+    }
+
+    #[test]
+    fn test_if_match_list_contains_latest() {
+        let latest = Uuid::new_v4();
+        let header = format!("\"{}\", \"{}\"", Uuid::new_v4(), latest);
+        assert!(check_if_match(Some(&header), latest).is_ok());
+        assert!(check_if_match(Some("\"not-a-uuid\""), latest).is_err());
+    }
+
+    #[test]
+    fn test_stale_notebook_sequence_conflicts() {
+        let err = check_notebook_sequence(Some(4), 5).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::SequenceConflict);
+        assert_eq!(err.status_code(), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn test_current_notebook_sequence_succeeds() {
+        assert!(check_notebook_sequence(Some(5), 5).is_ok());
+        assert!(check_notebook_sequence(Some(0), 0).is_ok());
+        assert!(check_notebook_sequence(None, 5).is_ok());
+    }
+
+    #[test]
+    fn test_expected_notebook_sequence_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(expected_notebook_sequence(&headers).unwrap(), None);
+
+        headers.insert(
+            IF_NOTEBOOK_SEQUENCE_HEADER,
+            HeaderValue::from_static(" 12 "),
+        );
+        assert_eq!(expected_notebook_sequence(&headers).unwrap(), Some(12));
+
+        for invalid in ["-1", "twelve", ""] {
+            headers.insert(
+                IF_NOTEBOOK_SEQUENCE_HEADER,
+                HeaderValue::from_static(invalid),
+            );
+            assert!(matches!(
+                expected_notebook_sequence(&headers),
+                Err(ApiError::BadRequest(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_find_external_reference_rejects_other_notebook() {
+        let local = Uuid::new_v4();
+        let foreign = Uuid::new_v4();
+
+        // Only `local` was found in the target notebook
+        assert_eq!(
+            find_external_reference(&[local, foreign], &[local]),
+            Some(foreign)
+        );
+        assert_eq!(find_external_reference(&[local], &[local]), None);
+        assert_eq!(find_external_reference(&[], &[]), None);
+    }
+
+    #[test]
+    fn test_find_missing_reference() {
+        let local = Uuid::new_v4();
+        let external = Uuid::new_v4();
+        let missing = Uuid::new_v4();
+
+        assert_eq!(
+            find_missing_reference(&[local, external, missing], &[local], &[external]),
+            Some(missing)
+        );
+        assert_eq!(
+            find_missing_reference(&[local, external], &[local], &[external]),
+            None
+        );
+        assert_eq!(find_missing_reference(&[], &[], &[]), None);
+    }
+
+    #[test]
+    fn test_reference_count_is_capped() {
+        let references: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+
+        assert!(check_reference_count(&references, 3).is_ok());
+        assert!(matches!(
+            check_reference_count(&references, 2),
+            Err(ApiError::BadRequest(_))
+        ));
     }
 
     #[test]
@@ -765,11 +2127,356 @@ mod tests {
             entry_id: Uuid::nil(),
             causal_position: CausalPosition::first(),
             integration_cost: IntegrationCost::zero(),
+            topic: None,
+            topic_inferred: false,
         };
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("entry_id"));
         assert!(json.contains("causal_position"));
         assert!(json.contains("integration_cost"));
+        assert!(json.contains("\"topic_inferred\":false"));
+        assert!(!json.contains("\"topic\""));
+    }
+
+    #[test]
+    fn test_omitted_content_type_uses_default() {
+        let mut request: CreateEntryRequest =
+            serde_json::from_value(serde_json::json!({ "content": "hello" })).unwrap();
+        assert!(request.content_type.is_empty());
+        apply_default_content_type(&mut request, &ServerConfig::default());
+        assert_eq!(request.content_type, "text/plain");
+
+        let config = ServerConfig {
+            default_content_type: "text/markdown".to_string(),
+            ..ServerConfig::default()
+        };
+        let mut request: CreateEntryRequest =
+            serde_json::from_value(serde_json::json!({ "content": "# hi", "content_type": "" }))
+                .unwrap();
+        apply_default_content_type(&mut request, &config);
+        assert_eq!(request.content_type, "text/markdown");
+
+        // An explicit content type is kept
+        let mut request: CreateEntryRequest = serde_json::from_value(
+            serde_json::json!({ "content": "{}", "content_type": "application/json" }),
+        )
+        .unwrap();
+        apply_default_content_type(&mut request, &config);
+        assert_eq!(request.content_type, "application/json");
+    }
+
+    #[tokio::test]
+    async fn test_infer_missing_topic() {
+        let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let config = ServerConfig {
+            infer_topics: true,
+            ..ServerConfig::default()
+        };
+        let state = AppState::new(Store::from_pool(pool), config);
+        let notebook_id = NotebookId::new();
+        let entry = |topic: Option<&str>| {
+            let request = CreateEntryRequest {
+                id: None,
+                content: "Sourdough needs a lively starter. Feed the sourdough \
+                          starter before baking sourdough loaves."
+                    .to_string(),
+                content_type: "text/plain".to_string(),
+                topic: topic.map(str::to_string),
+                references: vec![],
+                allow_external_refs: false,
+                revision_of: None,
+            };
+            build_candidate_entry(
+                Uuid::new_v4(),
+                get_content_bytes(&request).unwrap(),
+                &request,
+                AuthorId::zero(),
+                CausalPosition::first(),
+            )
+        };
+
+        let mut themed = entry(None);
+        assert!(infer_missing_topic(&state, &mut themed, notebook_id).await);
+        assert_eq!(themed.topic.as_deref(), Some("sourdough starter"));
+
+        // A topic given by the client is kept
+        let mut named = entry(Some("bread"));
+        assert!(!infer_missing_topic(&state, &mut named, notebook_id).await);
+        assert_eq!(named.topic.as_deref(), Some("bread"));
+
+        // Inference is opt-in
+        let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let state = AppState::new(Store::from_pool(pool), ServerConfig::default());
+        let mut untouched = entry(None);
+        assert!(!infer_missing_topic(&state, &mut untouched, notebook_id).await);
+        assert!(untouched.topic.is_none());
+    }
+
+    /// App state with a propagation worker that only runs when started.
+    fn state_with_propagation() -> (
+        AppState,
+        notebook_entropy::PropagationWorker<notebook_entropy::NoOpCostUpdater>,
+    ) {
+        use notebook_entropy::{NoOpCostUpdater, PropagationQueue, PropagationWorker};
+
+        let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        // Long poll interval: jobs are only processed by the shutdown drain
+        let worker = PropagationWorker::new(PropagationQueue::new(), NoOpCostUpdater)
+            .with_poll_interval(Duration::from_secs(3600));
+        let state = AppState::new(Store::from_pool(pool), ServerConfig::default())
+            .with_propagation_monitor(worker.monitor());
+        (state, worker)
+    }
+
+    #[tokio::test]
+    async fn test_propagation_lag_reflects_queue_depth() {
+        let (state, _worker) = state_with_propagation();
+        let notebook_id = NotebookId::new();
+        let entry = |content: &str| {
+            let request = CreateEntryRequest {
+                id: None,
+                content: content.to_string(),
+                content_type: "text/plain".to_string(),
+                topic: None,
+                references: vec![],
+                allow_external_refs: false,
+                revision_of: None,
+            };
+            build_candidate_entry(
+                Uuid::new_v4(),
+                get_content_bytes(&request).unwrap(),
+                &request,
+                AuthorId::zero(),
+                CausalPosition::first(),
+            )
+        };
+        let lag = |state: &AppState| {
+            let mut headers = HeaderMap::new();
+            insert_propagation_lag(&mut headers, state);
+            headers["X-Propagation-Lag"].clone()
+        };
+        assert_eq!(lag(&state), "0");
+
+        let mut written = Vec::new();
+        for content in [
+            "baking sourdough bread with starter",
+            "watering houseplants during winter",
+            "tokio runtime schedules async tasks across worker threads",
+            "the tokio runtime schedules async tasks on worker threads",
+        ] {
+            let entry = entry(content);
+            let cost = state
+                .engines()
+                .lock(notebook_id)
+                .await
+                .compute_cost(&entry, notebook_id)
+                .unwrap();
+            enqueue_propagation(&state, notebook_id, entry.id, &cost).await;
+            written.push(entry.id);
+        }
+
+        // Only the entry that joined a cluster has entries to update
+        let monitor = state.propagation().unwrap();
+        assert_eq!(monitor.queue_depth(), 1);
+        assert!(monitor.is_pending(*written[3].as_uuid()));
+        assert_eq!(lag(&state), "1");
+
+        // Without a worker nothing is queued
+        let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let idle = AppState::new(Store::from_pool(pool), ServerConfig::default());
+        assert_eq!(lag(&idle), "0");
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_propagation_returns_after_job_completes() {
+        use notebook_entropy::PropagationJob;
+
+        let (state, mut worker) = state_with_propagation();
+        let handle = worker.start();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let entry_id = Uuid::new_v4();
+        let job = PropagationJob::with_id(entry_id, NotebookId::new(), vec![EntryId::new()], 0.5);
+        state.propagation().unwrap().queue().enqueue(job);
+
+        let waiter = tokio::spawn({
+            let state = state.clone();
+            async move { wait_for_propagation(&state, entry_id).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished());
+
+        worker.shutdown();
+        handle.await.unwrap();
+        assert!(waiter.await.unwrap());
+        assert_eq!(state.propagation().unwrap().stats().jobs_processed, 1);
+
+        // Entries without a job are read at once
+        assert!(wait_for_propagation(&state, Uuid::new_v4()).await);
+    }
+
+    #[test]
+    fn test_preview_returns_cost_without_mutating_snapshot() {
+        use notebook_entropy::IntegrationCostEngine;
+
+        let mut engine = IntegrationCostEngine::new();
+        let notebook_id = NotebookId::new();
+        let author = AuthorId::zero();
+
+        let request = |content: &str| CreateEntryRequest {
+            id: None,
+            content: content.to_string(),
+            content_type: "text/plain".to_string(),
+            topic: None,
+            references: vec![],
+            allow_external_refs: false,
+            revision_of: None,
+        };
+
+        let existing = request("Rust ownership and borrowing rules");
+        let entry = build_candidate_entry(
+            Uuid::new_v4(),
+            get_content_bytes(&existing).unwrap(),
+            &existing,
+            author,
+            CausalPosition::first(),
+        );
+        engine.compute_cost(&entry, notebook_id).unwrap();
+        let before = engine
+            .get_snapshot(notebook_id)
+            .unwrap()
+            .stats()
+            .entry_count;
+
+        let candidate_request = request("Medieval castle architecture");
+        let candidate = build_candidate_entry(
+            Uuid::new_v4(),
+            get_content_bytes(&candidate_request).unwrap(),
+            &candidate_request,
+            author,
+            CausalPosition::first(),
+        );
+        let cost = engine
+            .compute_cost_preview(&candidate, notebook_id)
+            .unwrap();
+
+        assert!(cost.catalog_shift >= 0.0);
+        assert!(cost.orphan);
+        let after = engine
+            .get_snapshot(notebook_id)
+            .unwrap()
+            .stats()
+            .entry_count;
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn test_batch_cost_total_sums_costs() {
+        let costs = [
+            IntegrationCost {
+                entries_revised: 1,
+                references_broken: 0,
+                catalog_shift: 0.25,
+                orphan: true,
+            },
+            IntegrationCost {
+                entries_revised: 2,
+                references_broken: 1,
+                catalog_shift: 0.5,
+                orphan: false,
+            },
+        ];
+
+        assert_eq!(
+            BatchCostTotal::of(&costs),
+            BatchCostTotal {
+                entries_revised: 3,
+                references_broken: 1,
+                catalog_shift: 0.75,
+                orphans: 1,
+            }
+        );
+        assert_eq!(BatchCostTotal::of(&[]), BatchCostTotal::default());
+    }
+
+    #[test]
+    fn test_preview_batch_size_limits() {
+        assert!(check_preview_batch_size(1).is_ok());
+        assert!(check_preview_batch_size(MAX_PREVIEW_BATCH_ENTRIES).is_ok());
+        assert!(matches!(
+            check_preview_batch_size(0),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            check_preview_batch_size(MAX_PREVIEW_BATCH_ENTRIES + 1),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_preview_batch_request_deserialize() {
+        let json = r#"{"entries": [
+            {"content": "first", "content_type": "text/plain"},
+            {"content": "second", "content_type": "text/plain", "topic": "notes"}
+        ]}"#;
+        let request: PreviewBatchRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.entries.len(), 2);
+        assert_eq!(request.entries[1].topic.as_deref(), Some("notes"));
+    }
+
+    #[tokio::test]
+    async fn test_slow_cost_falls_back_within_deadline() {
+        use std::time::{Duration, Instant};
+
+        // Mock engine that takes far longer than the deadline
+        let slow_engine = || {
+            std::thread::sleep(Duration::from_millis(300));
+            Ok::<_, String>(IntegrationCost {
+                entries_revised: 2,
+                ..IntegrationCost::zero()
+            })
+        };
+
+        let start = Instant::now();
+        let outcome = compute_cost_bounded(slow_engine, Duration::from_millis(20)).await;
+        let (cost, computed, pending) = resolve_cost_outcome(outcome, EntryId::new());
+        let headers = cost_computed_headers(computed);
+
+        assert!(start.elapsed() < Duration::from_millis(200));
+        assert_eq!(cost, IntegrationCost::zero());
+        assert_eq!(headers["X-Integration-Cost-Computed"], "false");
+
+        // The pending computation yields the real cost for backfilling
+        let backfill = pending.expect("pending cost").await.unwrap().unwrap();
+        assert_eq!(backfill.entries_revised, 2);
+    }
+
+    #[tokio::test]
+    async fn test_fast_cost_sets_computed_header() {
+        let outcome = compute_cost_bounded(
+            || Ok::<_, String>(IntegrationCost::zero()),
+            std::time::Duration::from_secs(1),
+        )
+        .await;
+        let (_, computed, pending) = resolve_cost_outcome(outcome, EntryId::new());
+
+        assert!(computed);
+        assert!(pending.is_none());
+        assert_eq!(
+            cost_computed_headers(computed)["X-Integration-Cost-Computed"],
+            "true"
+        );
+    }
+
+    #[test]
+    fn test_preview_response_serialize() {
+        let response = PreviewEntryResponse {
+            integration_cost: IntegrationCost::zero(),
+            orphan: false,
+        };
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("integration_cost"));
+        assert!(json.contains("\"orphan\":false"));
     }
 
     #[test]
@@ -780,6 +2487,7 @@ mod tests {
         assert!(!is_binary_content_type("application/json"));
         assert!(!is_binary_content_type("application/xml"));
         assert!(!is_binary_content_type("application/javascript"));
+        assert!(!is_binary_content_type("application/ld+json"));
 
         // Binary types SHOULD be treated as binary
         assert!(is_binary_content_type("application/octet-stream"));
@@ -787,13 +2495,87 @@ mod tests {
         assert!(is_binary_content_type("application/pdf"));
     }
 
+    #[test]
+    fn test_candidate_entry_carries_revision_of() {
+        let target = Uuid::new_v4();
+        let request: CreateEntryRequest = serde_json::from_value(serde_json::json!({
+            "content": "a different take",
+            "content_type": "text/plain",
+            "revision_of": target,
+        }))
+        .unwrap();
+        let entry = build_candidate_entry(
+            Uuid::new_v4(),
+            b"a different take".to_vec(),
+            &request,
+            AuthorId::zero(),
+            CausalPosition::first(),
+        );
+        assert_eq!(entry.revision_of, Some(EntryId::from_uuid(target)));
+        assert!(entry.references.is_empty());
+        assert_eq!(entry.topic, None);
+
+        // Plain writes revise nothing
+        let request: CreateEntryRequest = serde_json::from_value(serde_json::json!({
+            "content": "new",
+            "content_type": "text/plain",
+        }))
+        .unwrap();
+        assert_eq!(request.revision_of, None);
+    }
+
+    #[test]
+    fn test_create_request_accepts_client_id() {
+        let id = Uuid::new_v4();
+        let request: CreateEntryRequest = serde_json::from_value(serde_json::json!({
+            "id": id,
+            "content": "offline draft",
+            "content_type": "text/plain",
+        }))
+        .unwrap();
+        assert_eq!(request.id, Some(id));
+
+        let malformed = serde_json::from_value::<CreateEntryRequest>(serde_json::json!({
+            "id": "not-a-uuid",
+            "content": "offline draft",
+            "content_type": "text/plain",
+        }));
+        assert!(malformed.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_entry_id() {
+        let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let state = AppState::new(Store::from_pool(pool), ServerConfig::default());
+
+        let generated = resolve_entry_id(&state, None).await.unwrap();
+        assert!(!generated.is_nil());
+        assert!(matches!(
+            resolve_entry_id(&state, Some(Uuid::nil())).await,
+            Err(ApiError::BadRequest(msg)) if msg.contains("nil")
+        ));
+    }
+
+    #[test]
+    fn test_taken_entry_id_is_a_generic_conflict() {
+        let id = Uuid::new_v4();
+        assert!(matches!(
+            entry_id_taken(id),
+            ApiError::Coded(ErrorCode::DuplicateEntry, msg)
+                if msg == format!("Entry id {} is not available", id)
+        ));
+    }
+
     #[test]
     fn test_get_content_bytes_text() {
         let request = CreateEntryRequest {
+            id: None,
             content: "hello world".to_string(),
             content_type: "text/plain".to_string(),
             topic: None,
             references: vec![],
+            allow_external_refs: false,
+            revision_of: None,
         };
         let bytes = get_content_bytes(&request).unwrap();
         assert_eq!(bytes, b"hello world");
@@ -802,10 +2584,13 @@ mod tests {
     #[test]
     fn test_get_content_bytes_json() {
         let request = CreateEntryRequest {
+            id: None,
             content: r#"{"key": "value"}"#.to_string(),
             content_type: "application/json".to_string(),
             topic: None,
             references: vec![],
+            allow_external_refs: false,
+            revision_of: None,
         };
         let bytes = get_content_bytes(&request).unwrap();
         assert_eq!(bytes, br#"{"key": "value"}"#);
@@ -818,10 +2603,13 @@ mod tests {
         let encoded = BASE64.encode(original);
 
         let request = CreateEntryRequest {
+            id: None,
             content: encoded,
             content_type: "application/octet-stream".to_string(),
             topic: None,
             references: vec![],
+            allow_external_refs: false,
+            revision_of: None,
         };
         let bytes = get_content_bytes(&request).unwrap();
         assert_eq!(bytes, original);
@@ -830,15 +2618,61 @@ mod tests {
     #[test]
     fn test_get_content_bytes_invalid_base64() {
         let request = CreateEntryRequest {
+            id: None,
             content: "not valid base64!!!".to_string(),
             content_type: "application/octet-stream".to_string(),
             topic: None,
             references: vec![],
+            allow_external_refs: false,
+            revision_of: None,
         };
         let result = get_content_bytes(&request);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_get_content_bytes_rejects_invalid_utf8_text() {
+        use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+
+        // Mixed-case text types take the base64 path, which can yield
+        // arbitrary bytes
+        let request = CreateEntryRequest {
+            id: None,
+            content: BASE64.encode([0x68, 0x69, 0xFF, 0xFE]),
+            content_type: "Text/Plain".to_string(),
+            topic: None,
+            references: vec![],
+            allow_external_refs: false,
+            revision_of: None,
+        };
+        let result = get_content_bytes(&request);
+        assert!(matches!(result, Err(ApiError::BadRequest(msg)) if msg.contains("UTF-8")));
+    }
+
+    #[test]
+    fn test_get_content_bytes_accepts_valid_utf8_text() {
+        use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+
+        let request = CreateEntryRequest {
+            id: None,
+            content: BASE64.encode("héllo"),
+            content_type: "TEXT/plain; charset=utf-8".to_string(),
+            topic: None,
+            references: vec![],
+            allow_external_refs: false,
+            revision_of: None,
+        };
+        assert_eq!(get_content_bytes(&request).unwrap(), "héllo".as_bytes());
+    }
+
+    #[test]
+    fn test_is_text_content_type() {
+        assert!(is_text_content_type("text/plain"));
+        assert!(is_text_content_type("Text/Markdown; charset=utf-8"));
+        assert!(!is_text_content_type("application/json"));
+        assert!(!is_text_content_type("tex"));
+    }
+
     // ========================================================================
     // ReviseEntry Tests
     // ========================================================================
@@ -859,17 +2693,115 @@ mod tests {
         assert_eq!(request.reason, Some("fixing typo".to_string()));
     }
 
+    #[test]
+    fn test_revise_request_with_metadata() {
+        let reference = Uuid::new_v4();
+        let json = format!(
+            r#"{{"content": "new content", "topic": "fixed", "references": ["{}"]}}"#,
+            reference
+        );
+        let request: ReviseRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(request.topic.as_deref(), Some("fixed"));
+        assert_eq!(request.references, Some(vec![reference]));
+        assert!(!request.allow_external_refs);
+    }
+
+    fn original_with_metadata() -> Entry {
+        notebook_core::types::EntryBuilder::default()
+            .content(b"original".to_vec())
+            .content_type("text/plain")
+            .topic("typo")
+            .author(AuthorId::zero())
+            .references(vec![EntryId::new()])
+            .build()
+    }
+
+    #[test]
+    fn test_revise_inherits_metadata_by_default() {
+        let original = original_with_metadata();
+        let request: ReviseRequest = serde_json::from_str(r#"{"content": "new"}"#).unwrap();
+
+        let (topic, references) = request.metadata_for(&original);
+        assert_eq!(topic, original.topic);
+        assert_eq!(references, original.references);
+    }
+
+    #[test]
+    fn test_revise_overrides_metadata() {
+        let original = original_with_metadata();
+        let replacement = Uuid::new_v4();
+        let request = ReviseRequest {
+            content: "new".to_string(),
+            reason: None,
+            topic: Some("fixed".to_string()),
+            references: Some(vec![replacement]),
+            allow_external_refs: false,
+        };
+
+        let (topic, references) = request.metadata_for(&original);
+        assert_eq!(topic.as_deref(), Some("fixed"));
+        assert_eq!(references, vec![EntryId::from_uuid(replacement)]);
+        assert_ne!(references, original.references);
+    }
+
     #[test]
     fn test_revise_response_serialize() {
         let response = ReviseResponse {
             revision_id: EntryId::from_uuid(Uuid::nil()),
             causal_position: CausalPosition::first(),
             integration_cost: IntegrationCost::zero(),
+            no_op: false,
         };
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("revision_id"));
         assert!(json.contains("causal_position"));
         assert!(json.contains("integration_cost"));
+        assert!(json.contains("\"no_op\":false"));
+    }
+
+    #[test]
+    fn test_identical_revise_is_noop() {
+        let original = original_with_metadata();
+        let request: ReviseRequest = serde_json::from_str(r#"{"content": "original"}"#).unwrap();
+        assert!(request.is_noop_for(&original, &original));
+
+        // Restating the inherited metadata is still a no-op
+        let request = ReviseRequest {
+            content: "original".to_string(),
+            reason: Some("no change".to_string()),
+            topic: original.topic.clone(),
+            references: Some(original.references.iter().map(|r| *r.as_uuid()).collect()),
+            allow_external_refs: false,
+        };
+        assert!(request.is_noop_for(&original, &original));
+    }
+
+    #[test]
+    fn test_changed_revise_is_not_noop() {
+        let original = original_with_metadata();
+        let changed: ReviseRequest = serde_json::from_str(r#"{"content": "originals"}"#).unwrap();
+        assert!(!changed.is_noop_for(&original, &original));
+
+        let retagged: ReviseRequest =
+            serde_json::from_str(r#"{"content": "original", "topic": "fixed"}"#).unwrap();
+        assert!(!retagged.is_noop_for(&original, &original));
+
+        let unlinked: ReviseRequest =
+            serde_json::from_str(r#"{"content": "original", "references": []}"#).unwrap();
+        assert!(!unlinked.is_noop_for(&original, &original));
+
+        // Compared against the latest revision, not the entry being revised
+        let latest = notebook_core::types::EntryBuilder::default()
+            .content(b"revised".to_vec())
+            .content_type("text/plain")
+            .topic("typo")
+            .author(AuthorId::zero())
+            .references(original.references.clone())
+            .build();
+        let request: ReviseRequest = serde_json::from_str(r#"{"content": "original"}"#).unwrap();
+        assert!(!request.is_noop_for(&original, &latest));
+        let request: ReviseRequest = serde_json::from_str(r#"{"content": "revised"}"#).unwrap();
+        assert!(request.is_noop_for(&original, &latest));
     }
 
     // ========================================================================
@@ -911,21 +2843,57 @@ mod tests {
 
     #[test]
     fn test_encode_content_json() {
-        let content = b"{\"key\": \"value\"}";
+        let content = b"{\"key\": \"value\", \"n\": [1, 2]}";
         let result = encode_content(content, "application/json");
         match result {
+            EntryContent::Json(value) => {
+                assert_eq!(value, serde_json::json!({"key": "value", "n": [1, 2]}));
+            }
+            _ => panic!("Expected Json variant"),
+        }
+
+        // Serializes as a JSON object, not a base64 string
+        let json = serde_json::to_value(encode_content(content, "application/json")).unwrap();
+        assert_eq!(json["key"], "value");
+    }
+
+    #[test]
+    fn test_encode_content_json_suffix_and_charset() {
+        let content = b"{\"@id\": \"x\"}";
+        assert!(matches!(
+            encode_content(content, "application/ld+json"),
+            EntryContent::Json(_)
+        ));
+        assert!(matches!(
+            encode_content(content, "application/json; charset=utf-8"),
+            EntryContent::Json(_)
+        ));
+    }
+
+    #[test]
+    fn test_encode_content_invalid_json_falls_back_to_base64() {
+        let content = b"{not json";
+        match encode_content(content, "application/json") {
             EntryContent::Binary { data, encoding } => {
                 assert_eq!(encoding, "base64");
-                // JSON is not text/*, so it gets base64 encoded
                 let decoded = base64::engine::general_purpose::STANDARD
                     .decode(&data)
                     .unwrap();
-                assert_eq!(decoded, b"{\"key\": \"value\"}");
+                assert_eq!(decoded, content);
             }
             _ => panic!("Expected Binary variant"),
         }
     }
 
+    #[test]
+    fn test_encode_content_textual_application_types() {
+        // Types accepted as plain text on write read back as text
+        match encode_content(b"<a/>", "application/xml") {
+            EntryContent::Text(s) => assert_eq!(s, "<a/>"),
+            _ => panic!("Expected Text variant"),
+        }
+    }
+
     #[test]
     fn test_encode_content_invalid_utf8_text() {
         // Invalid UTF-8 sequence
@@ -950,6 +2918,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_entry_response_hashes_text_content() {
+        let entry = Entry::builder()
+            .content("caf\u{e9} au lait")
+            .content_type("text/plain")
+            .author(AuthorId::zero())
+            .build();
+        let response = entry_to_response(&entry, true);
+        assert_eq!(
+            response.content_hash,
+            blake3::hash("caf\u{e9} au lait".as_bytes())
+                .to_hex()
+                .as_str()
+        );
+        // Bytes, not characters
+        assert_eq!(response.content_length, 13);
+        assert_eq!(response.content_hash.len(), 64);
+    }
+
+    #[test]
+    fn test_entry_response_hashes_binary_content() {
+        let bytes = vec![0x89, 0x50, 0x4E, 0x47, 0x00, 0xFF, 0xFE];
+        let entry = Entry::builder()
+            .content(bytes.clone())
+            .content_type("image/png")
+            .author(AuthorId::zero())
+            .build();
+        let response = entry_to_response(&entry, true);
+        // Hashed over the raw bytes, not the base64 encoding
+        assert_eq!(
+            response.content_hash,
+            blake3::hash(&bytes).to_hex().as_str()
+        );
+        assert_eq!(response.content_length, bytes.len());
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["content_length"], 7);
+        assert_eq!(json["content_hash"], response.content_hash);
+    }
+
+    #[test]
+    fn test_fallback_cost_reads_back_as_not_computed() {
+        let entry = Entry::builder()
+            .content("notes")
+            .author(AuthorId::zero())
+            .build();
+        let (cost, computed, pending) = resolve_cost_outcome(
+            CostOutcome::Failed("engine unavailable".to_string()),
+            entry.id,
+        );
+        assert_eq!(cost, IntegrationCost::zero());
+        assert!(!computed && pending.is_none());
+
+        let json = serde_json::to_value(entry_to_response(&entry, computed)).unwrap();
+        assert_eq!(json["cost_computed"], false);
+        assert_eq!(json["integration_cost"]["catalog_shift"], 0.0);
+    }
+
     #[test]
     fn test_get_entry_params_deserialize_none() {
         let params: GetEntryParams = serde_urlencoded::from_str("").unwrap();
@@ -970,6 +2996,8 @@ mod tests {
                 id: EntryId::from_uuid(Uuid::nil()),
                 content: EntryContent::Text("test".to_string()),
                 content_type: "text/plain".to_string(),
+                content_hash: blake3::hash(b"test").to_hex().to_string(),
+                content_length: 4,
                 topic: Some("test-topic".to_string()),
                 author,
                 references: vec![],
@@ -984,18 +3012,48 @@ mod tests {
                 },
                 created: Utc::now(),
                 integration_cost: IntegrationCost::zero(),
+                cost_computed: true,
             },
+            revision_count: 0,
             revisions: vec![],
+            revisions_truncated: false,
             references: vec![],
             referenced_by: vec![],
         };
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("entry"));
+        assert!(json.contains("revision_count"));
         assert!(json.contains("revisions"));
+        assert!(json.contains("\"revisions_truncated\":false"));
         assert!(json.contains("references"));
         assert!(json.contains("referenced_by"));
     }
 
+    #[test]
+    fn test_include_defaults_to_nothing() {
+        assert_eq!(Includes::parse(None).unwrap(), Includes::default());
+        assert_eq!(Includes::parse(Some("")).unwrap(), Includes::default());
+    }
+
+    #[test]
+    fn test_include_selects_collections() {
+        let includes = Includes::parse(Some("revisions")).unwrap();
+        assert!(includes.revisions);
+        assert!(!includes.references);
+        assert!(!includes.referenced_by);
+
+        let includes = Includes::parse(Some("references, referenced_by")).unwrap();
+        assert!(!includes.revisions);
+        assert!(includes.references);
+        assert!(includes.referenced_by);
+    }
+
+    #[test]
+    fn test_unknown_include_rejected() {
+        let err = Includes::parse(Some("revisions,comments")).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+    }
+
     #[test]
     fn test_entry_summary_serialize() {
         let summary = EntrySummary {
@@ -1003,11 +3061,111 @@ mod tests {
             topic: Some("test".to_string()),
             author: AuthorId::zero(),
             created: Utc::now(),
+            latest_sequence: 7,
+            integration_cost: 0.5,
+            cost_computed: true,
+            revision_count: 2,
+            revision_of: None,
         };
         let json = serde_json::to_string(&summary).unwrap();
         assert!(json.contains("id"));
         assert!(json.contains("topic"));
         assert!(json.contains("author"));
         assert!(json.contains("created"));
+        assert!(json.contains("\"latest_sequence\":7"));
+        assert!(json.contains("\"integration_cost\":0.5"));
+        assert!(json.contains("\"revision_count\":2"));
+    }
+
+    #[test]
+    fn test_entry_summary_from_row() {
+        let author = AuthorId::from_bytes([7u8; 32]);
+        let row = EntrySummaryRow {
+            id: Uuid::new_v4(),
+            topic: Some("notes".to_string()),
+            author_id: author.as_bytes().to_vec(),
+            created: Utc::now(),
+            sequence: 3,
+            catalog_shift: 0.25,
+            cost_computed: false,
+            revision_count: 2,
+            latest_sequence: 9,
+            revision_of: Some(Uuid::nil()),
+        };
+
+        let summary = EntrySummary::from(&row);
+        assert_eq!(*summary.id.as_uuid(), row.id);
+        assert_eq!(summary.topic.as_deref(), Some("notes"));
+        assert_eq!(summary.author, author);
+        assert_eq!(summary.latest_sequence, 9);
+        assert_eq!(summary.integration_cost, 0.25);
+        assert!(!summary.cost_computed);
+        assert_eq!(summary.revision_count, 2);
+        assert_eq!(summary.revision_of, Some(EntryId::from_uuid(Uuid::nil())));
+    }
+
+    #[test]
+    fn test_bulk_read_ids_dedupes_in_order() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let ids = bulk_read_ids(&[a, b, a]).unwrap();
+        assert_eq!(ids, vec![EntryId::from_uuid(a), EntryId::from_uuid(b)]);
+    }
+
+    #[test]
+    fn test_bulk_read_ids_capped() {
+        let ids: Vec<Uuid> = (0..=MAX_BULK_READ_IDS).map(|_| Uuid::new_v4()).collect();
+        let err = bulk_read_ids(&ids).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+        assert!(bulk_read_ids(&ids[..MAX_BULK_READ_IDS]).is_ok());
+    }
+
+    #[test]
+    fn test_missing_ids_reports_unfound() {
+        let found = notebook_core::types::EntryBuilder::default()
+            .content(b"present".to_vec())
+            .content_type("text/plain")
+            .author(AuthorId::zero())
+            .build();
+        let missing = EntryId::new();
+
+        let requested = vec![missing, found.id];
+        assert_eq!(
+            missing_ids(&requested, std::slice::from_ref(&found)),
+            vec![*missing.as_uuid()]
+        );
+    }
+
+    #[test]
+    fn test_bulk_read_request_deserialize() {
+        let request: BulkReadRequest =
+            serde_json::from_str(r#"{"ids": ["550e8400-e29b-41d4-a716-446655440000"]}"#).unwrap();
+        assert_eq!(request.ids.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_anonymous_write_is_unauthorized() {
+        use axum::body::Body;
+        use axum::http::Request;
+        use tower::ServiceExt;
+
+        let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let state = AppState::new(Store::from_pool(pool), ServerConfig::default());
+        let app = crate::routes::build_router(state);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/notebooks/{}/entries", Uuid::new_v4()))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(
+                        r#"{"content": "hello", "content_type": "text/plain"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
 }
@@ -8,12 +8,23 @@
 //! # Event Types
 //!
 //! - `entry`: Published when an entry is created or revised
+//! - `notebook_renamed`: Published when the notebook is renamed
+//! - `notebook_locked`: Published when the notebook is locked or unlocked
+//! - `access_granted` / `access_revoked`: Published when sharing changes
 //! - `heartbeat`: Sent every 30 seconds to keep the connection alive
-//! - `catchup`: Sent when the client falls behind and needs to sync via OBSERVE
+//! - `lagged`: Sent when the client falls behind, before the missed events
+//!
+//! # Resuming
+//!
+//! Events from the notebook's change log carry their `seq` as the SSE `id`.
+//! A client that reconnects with a `Last-Event-ID` header is first sent every
+//! logged event after that id, then live events, with nothing missed or
+//! repeated.
 //!
 //! # Example
 //!
 //! ```text
+//! id: 7
 //! event: entry
 //! data: {"entry_id": "...", "operation": "write", "integration_cost": {...}}
 //!
@@ -23,158 +34,257 @@
 //!
 //! Owned by: agent-events
 
+use std::collections::VecDeque;
 use std::convert::Infallible;
 use std::time::Duration;
 
 use axum::{
     Router,
     extract::{Path, State},
+    http::HeaderMap,
     response::sse::{Event, KeepAlive, Sse},
     routing::get,
 };
 use chrono::Utc;
 use futures::stream::{self, Stream};
-use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::{self, error::RecvError};
 use uuid::Uuid;
 
-use notebook_store::StoreError;
+use notebook_store::{Store, StoreError, StoreResult};
 
-use crate::error::ApiError;
-use crate::events::{CatchupEvent, HEARTBEAT_INTERVAL_SECS, HeartbeatEvent, NotebookEvent};
+use crate::error::{ApiError, ApiResult};
+use crate::events::{
+    CursorStep, HEARTBEAT_INTERVAL_SECS, HeartbeatEvent, LaggedEvent, LogCursor, NotebookEvent,
+    read_log,
+};
 use crate::state::AppState;
 
+/// Header a reconnecting SSE client sends with the last `id` it received.
+const LAST_EVENT_ID: &str = "last-event-id";
+
+// ============================================================================
+// Stream State
+// ============================================================================
+
+/// State carried between items of one client's SSE stream.
+struct StreamState {
+    rx: broadcast::Receiver<NotebookEvent>,
+    store: Store,
+    notebook_id: Uuid,
+    cursor: LogCursor,
+    /// Events replayed from the change log, waiting to be sent.
+    pending: VecDeque<NotebookEvent>,
+    /// Entry sequence of the last entry event sent, reported in `lagged`.
+    last_sequence: u64,
+}
+
+impl StreamState {
+    /// Queue every logged event after the cursor.
+    async fn replay(&mut self) -> StoreResult<()> {
+        let after = self.cursor.last();
+        let (events, last) = read_log(&self.store, self.notebook_id, after).await?;
+        self.pending.extend(
+            events
+                .into_iter()
+                .filter(|event| event.event_seq().is_some_and(|seq| seq > after)),
+        );
+        self.cursor.advance(last);
+        Ok(())
+    }
+
+    /// Wait for the next event to send. Returns `None` once the channel closes.
+    async fn next_event(&mut self) -> Option<NotebookEvent> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(event);
+            }
+
+            match self.rx.recv().await {
+                Ok(event) => match self.cursor.step(&event) {
+                    CursorStep::Deliver => return Some(event),
+                    CursorStep::Skip => continue,
+                    CursorStep::Gap { .. } => {
+                        // The live event is in the log too; replay up to and
+                        // including it. If the log is unreadable, send it
+                        // anyway rather than stall the stream.
+                        if let Err(e) = self.replay().await {
+                            tracing::warn!(
+                                notebook_id = %self.notebook_id,
+                                error = %e,
+                                "Failed to replay change log, skipping gap"
+                            );
+                            if let Some(seq) = event.event_seq() {
+                                self.cursor.advance(seq);
+                            }
+                            return Some(event);
+                        }
+                    }
+                },
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::warn!(
+                        notebook_id = %self.notebook_id,
+                        events_missed = missed,
+                        "SSE client lagged, replaying from change log"
+                    );
+                    // Tell the client before replaying, so it can re-observe
+                    // from `resume_after` if the replay fails
+                    let resume_after = self.cursor.last();
+                    if let Err(e) = self.replay().await {
+                        tracing::warn!(
+                            notebook_id = %self.notebook_id,
+                            error = %e,
+                            "Failed to replay change log, client must re-observe"
+                        );
+                    }
+                    return Some(NotebookEvent::Lagged(LaggedEvent {
+                        missed,
+                        resume_after,
+                        current_sequence: self.last_sequence,
+                        timestamp: Utc::now(),
+                    }));
+                }
+                Err(RecvError::Closed) => {
+                    tracing::debug!(
+                        notebook_id = %self.notebook_id,
+                        "Event channel closed, ending SSE stream"
+                    );
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+/// Turn an event into an SSE frame, using its change log `seq` as the id.
+fn to_sse_event(event: &NotebookEvent) -> Option<Event> {
+    match serde_json::to_string(event) {
+        Ok(data) => {
+            let mut sse_event = Event::default().event(event.event_name()).data(data);
+            if let Some(seq) = event.event_seq() {
+                sse_event = sse_event.id(seq.to_string());
+            }
+            Some(sse_event)
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to serialize event");
+            None
+        }
+    }
+}
+
+/// Parse the `Last-Event-ID` header, if present.
+fn last_event_id(headers: &HeaderMap) -> ApiResult<Option<u64>> {
+    let Some(value) = headers.get(LAST_EVENT_ID) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Some)
+        .ok_or_else(|| ApiError::BadRequest("Last-Event-ID must be an event sequence".to_string()))
+}
+
 // ============================================================================
 // SSE Endpoint
 // ============================================================================
 
 /// GET /notebooks/{notebook_id}/events - Subscribe to real-time events.
 ///
-/// Returns a Server-Sent Events stream that emits events when entries are
-/// created or revised in the notebook. Heartbeats are sent every 30 seconds
-/// to keep the connection alive.
+/// Returns a Server-Sent Events stream that emits events when the notebook
+/// changes. Heartbeats are sent every 30 seconds to keep the connection
+/// alive.
+///
+/// # Headers
+///
+/// - `Last-Event-ID`: Resume after this change log `seq`; logged events
+///   since then are replayed before live events
 ///
 /// # Response
 ///
 /// - 200 OK: SSE stream (Content-Type: text/event-stream)
+/// - 400 Bad Request: Malformed `Last-Event-ID`
 /// - 404 Not Found: Notebook not found
 ///
 /// # Event Format
 ///
 /// ```text
+/// id: 7
 /// event: entry
-/// data: {"type":"entry","entry_id":"...","operation":"write","integration_cost":{...},"sequence":42,"timestamp":"..."}
+/// data: {"type":"entry","entry_id":"...","operation":"write","integration_cost":{...},"sequence":42,"event_seq":7,"timestamp":"..."}
+///
+/// id: 8
+/// event: notebook_renamed
+/// data: {"type":"notebook_renamed","notebook_id":"...","name":"...","event_seq":8,"timestamp":"..."}
 ///
 /// event: heartbeat
 /// data: {"type":"heartbeat","timestamp":"..."}
 ///
-/// event: catchup
-/// data: {"type":"catchup","events_missed":100,"current_sequence":150,"timestamp":"..."}
+/// event: lagged
+/// data: {"type":"lagged","missed":100,"resume_after":50,"current_sequence":150,"timestamp":"..."}
 /// ```
 ///
 /// # Backpressure
 ///
-/// If a client falls behind (channel buffer overflows), a `catchup` event is
-/// sent indicating how many events were missed. The client should then use
-/// the OBSERVE endpoint to sync up.
+/// Each subscriber buffers up to `EVENT_CHANNEL_CAPACITY` events. If a
+/// client falls behind and its buffer overflows, it is sent a `lagged`
+/// event, then the missed events replayed from the change log. If the log
+/// can't be read, the replay is skipped and the client should re-observe
+/// from `resume_after` (reconnect with it as `Last-Event-ID`, or sync via
+/// OBSERVE from `current_sequence`).
 async fn subscribe_events(
     State(state): State<AppState>,
     Path(notebook_id): Path<Uuid>,
+    headers: HeaderMap,
 ) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let resume_after = last_event_id(&headers)?;
+
     // Validate notebook exists
     state
         .store()
         .get_notebook(notebook_id)
         .await
         .map_err(|e| match e {
-            StoreError::NotebookNotFound(id) => {
-                ApiError::NotFound(format!("Notebook {} not found", id))
-            }
+            StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
             other => ApiError::Store(other),
         })?;
 
-    // Get broadcaster from state
-    let broadcaster = state.broadcaster();
-
     // Subscribe to events
-    let receiver = broadcaster.subscribe(notebook_id).await;
+    let (receiver, start) = state
+        .broadcaster()
+        .subscribe_from_log(state.store(), notebook_id)
+        .await?;
+
+    let mut stream_state = StreamState {
+        rx: receiver,
+        store: state.store().clone(),
+        notebook_id,
+        cursor: LogCursor::new(resume_after.unwrap_or(start)),
+        pending: VecDeque::new(),
+        last_sequence: 0,
+    };
+    if resume_after.is_some() {
+        stream_state.replay().await?;
+    }
 
     tracing::info!(
         notebook_id = %notebook_id,
+        resume_after = ?resume_after,
         "Client subscribed to SSE events"
     );
 
     // Create the event stream
-    let stream = stream::unfold(
-        (receiver, notebook_id, 0u64),
-        move |(mut rx, nb_id, mut last_sequence)| async move {
-            loop {
-                match rx.recv().await {
-                    Ok(event) => {
-                        // Update last_sequence for entry events
-                        if let NotebookEvent::Entry(ref e) = event {
-                            last_sequence = e.sequence;
-                        }
-
-                        let event_type = match &event {
-                            NotebookEvent::Entry(_) => "entry",
-                            NotebookEvent::Heartbeat(_) => "heartbeat",
-                            NotebookEvent::Catchup(_) => "catchup",
-                        };
-
-                        match serde_json::to_string(&event) {
-                            Ok(data) => {
-                                let sse_event = Event::default().event(event_type).data(data);
-                                return Some((Ok(sse_event), (rx, nb_id, last_sequence)));
-                            }
-                            Err(e) => {
-                                tracing::error!(
-                                    error = %e,
-                                    "Failed to serialize event"
-                                );
-                                continue;
-                            }
-                        }
-                    }
-                    Err(RecvError::Lagged(count)) => {
-                        // Client fell behind - send catchup event
-                        tracing::warn!(
-                            notebook_id = %nb_id,
-                            events_missed = count,
-                            "SSE client lagged, sending catchup event"
-                        );
-
-                        let catchup = NotebookEvent::Catchup(CatchupEvent {
-                            events_missed: count,
-                            current_sequence: last_sequence,
-                            timestamp: Utc::now(),
-                        });
-
-                        match serde_json::to_string(&catchup) {
-                            Ok(data) => {
-                                let sse_event = Event::default().event("catchup").data(data);
-                                return Some((Ok(sse_event), (rx, nb_id, last_sequence)));
-                            }
-                            Err(e) => {
-                                tracing::error!(
-                                    error = %e,
-                                    "Failed to serialize catchup event"
-                                );
-                                continue;
-                            }
-                        }
-                    }
-                    Err(RecvError::Closed) => {
-                        // Channel closed - end stream
-                        tracing::debug!(
-                            notebook_id = %nb_id,
-                            "Event channel closed, ending SSE stream"
-                        );
-                        return None;
-                    }
-                }
+    let stream = stream::unfold(stream_state, |mut stream_state| async move {
+        loop {
+            let event = stream_state.next_event().await?;
+            if let NotebookEvent::Entry(ref e) = event {
+                stream_state.last_sequence = e.sequence;
             }
-        },
-    );
+            if let Some(sse_event) = to_sse_event(&event) {
+                return Some((Ok(sse_event), stream_state));
+            }
+        }
+    });
 
     // Configure keep-alive with heartbeat
     let keep_alive = KeepAlive::new()
@@ -203,9 +313,145 @@ pub fn routes() -> Router<AppState> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::events::{EventBroadcaster, NotebookRenamedEvent};
+    use axum::http::HeaderValue;
 
     #[test]
     fn test_heartbeat_interval() {
         assert_eq!(HEARTBEAT_INTERVAL_SECS, 30);
     }
+
+    #[test]
+    fn test_last_event_id_parsing() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(last_event_id(&headers).unwrap(), None);
+
+        headers.insert(LAST_EVENT_ID, HeaderValue::from_static("17"));
+        assert_eq!(last_event_id(&headers).unwrap(), Some(17));
+
+        headers.insert(LAST_EVENT_ID, HeaderValue::from_static("abc"));
+        assert!(last_event_id(&headers).is_err());
+    }
+
+    fn renamed(event_seq: u64) -> NotebookEvent {
+        NotebookEvent::NotebookRenamed(NotebookRenamedEvent {
+            notebook_id: Uuid::nil(),
+            name: format!("name-{}", event_seq),
+            event_seq: Some(event_seq),
+            timestamp: Utc::now(),
+        })
+    }
+
+    /// A stream whose store has no database behind it, so replays fail.
+    async fn stream_state(
+        broadcaster: &EventBroadcaster,
+        notebook_id: Uuid,
+        last: u64,
+    ) -> StreamState {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(Duration::from_millis(100))
+            .connect_lazy("postgres://localhost/unused")
+            .unwrap();
+        StreamState {
+            rx: broadcaster.subscribe(notebook_id).await,
+            store: Store::from_pool(pool),
+            notebook_id,
+            cursor: LogCursor::new(last),
+            pending: VecDeque::new(),
+            last_sequence: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_drops_already_delivered_events() {
+        let broadcaster = EventBroadcaster::new();
+        let notebook_id = Uuid::new_v4();
+        let mut state = stream_state(&broadcaster, notebook_id, 2).await;
+
+        for seq in [1, 2, 3, 3, 4] {
+            broadcaster.publish(notebook_id, renamed(seq)).await;
+        }
+        broadcaster.close().await;
+
+        let mut seqs = Vec::new();
+        while let Some(event) = state.next_event().await {
+            seqs.push(event.event_seq().unwrap());
+        }
+        assert_eq!(seqs, vec![3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_sends_replayed_events_first() {
+        let broadcaster = EventBroadcaster::new();
+        let notebook_id = Uuid::new_v4();
+        let mut state = stream_state(&broadcaster, notebook_id, 0).await;
+
+        // As if replay() had read 1..=2 from the log
+        state.pending.extend([renamed(1), renamed(2)]);
+        state.cursor.advance(2);
+        for seq in [2, 3] {
+            broadcaster.publish(notebook_id, renamed(seq)).await;
+        }
+        broadcaster.close().await;
+
+        let mut seqs = Vec::new();
+        while let Some(event) = state.next_event().await {
+            seqs.push(event.event_seq().unwrap());
+        }
+        assert_eq!(seqs, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_gap_with_unreadable_log_still_delivers() {
+        let broadcaster = EventBroadcaster::new();
+        let notebook_id = Uuid::new_v4();
+        let mut state = stream_state(&broadcaster, notebook_id, 1).await;
+
+        broadcaster.publish(notebook_id, renamed(4)).await;
+        broadcaster.publish(notebook_id, renamed(5)).await;
+
+        assert_eq!(state.next_event().await.unwrap().event_seq(), Some(4));
+        assert_eq!(state.next_event().await.unwrap().event_seq(), Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_lagging_stream_gets_notice_in_order() {
+        let broadcaster = EventBroadcaster::with_capacity(2);
+        let notebook_id = Uuid::new_v4();
+        let mut state = stream_state(&broadcaster, notebook_id, 0).await;
+        state.last_sequence = 9;
+
+        // Overflow the buffer: 1..=3 are dropped, 4 and 5 remain
+        for seq in 1..=5 {
+            broadcaster.publish(notebook_id, renamed(seq)).await;
+        }
+        broadcaster.close().await;
+
+        let Some(NotebookEvent::Lagged(notice)) = state.next_event().await else {
+            panic!("expected a lagged notice");
+        };
+        assert_eq!(notice.missed, 3);
+        assert_eq!(notice.resume_after, 0);
+        assert_eq!(notice.current_sequence, 9);
+
+        // The log is unreadable, so the remaining live events follow in order
+        let mut seqs = Vec::new();
+        while let Some(event) = state.next_event().await {
+            seqs.push(event.event_seq().unwrap());
+        }
+        assert_eq!(seqs, vec![4, 5]);
+    }
+
+    #[test]
+    fn test_logged_events_carry_sse_id() {
+        // Event doesn't expose its fields; its Debug output is the wire format
+        let rendered = format!("{:?}", to_sse_event(&renamed(12)).unwrap());
+        assert!(rendered.contains("id: 12"));
+
+        let heartbeat = NotebookEvent::Heartbeat(HeartbeatEvent {
+            timestamp: Utc::now(),
+        });
+        let rendered = format!("{:?}", to_sse_event(&heartbeat).unwrap());
+        assert!(!rendered.contains("id:"));
+    }
 }
@@ -0,0 +1,281 @@
+//! Integration cost explanation for an entry.
+//!
+//! An entry's stored integration cost says how disruptive it was, but not
+//! why. This endpoint replays the cost computation against the entries that
+//! preceded it and returns the intermediate signals: which clusters shifted,
+//! which references cross cluster boundaries, how close the nearest cluster
+//! was, and why the entry was or was not flagged as an orphan. References to
+//! entries deleted since then no longer count against orphaning.
+//!
+//! Endpoint: GET /notebooks/{notebook_id}/entries/{entry_id}/cost/explain
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    routing::get,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use notebook_core::{CausalPosition, Entry, NotebookId};
+use notebook_entropy::{
+    CostConfig, CostExplanation, EntropyError, IntegrationCostEngine, ReferenceResolution,
+};
+use notebook_store::{EntryQuery, EntryRow, StoreError};
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, require_scope};
+use crate::recompute::resolve_references;
+use crate::routes::suggest::entry_row_to_snapshot_entry;
+use crate::state::AppState;
+
+// ============================================================================
+// Response Types
+// ============================================================================
+
+/// Response for GET /notebooks/{id}/entries/{entry_id}/cost/explain.
+#[derive(Debug, Serialize)]
+pub struct CostExplainResponse {
+    /// The explained entry.
+    pub entry_id: Uuid,
+    /// The integration cost stored when the entry was written.
+    pub stored_cost: serde_json::Value,
+    /// The replayed computation and its signals.
+    pub explanation: CostExplanation,
+    /// One-line, human-readable orphan verdict.
+    pub summary: String,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Replay the cost computation for `row` against the entries before it.
+///
+/// Entries with a later sequence, and the entry itself, are ignored, so the
+/// explanation reflects the notebook as it was when the entry was written.
+fn explain_entry(
+    config: CostConfig,
+    rows: &[EntryRow],
+    row: &EntryRow,
+    references: &ReferenceResolution,
+) -> Result<CostExplanation, EntropyError> {
+    let nb_id = NotebookId::from_uuid(row.notebook_id);
+    let preceding: Vec<Entry> = rows
+        .iter()
+        .filter(|r| r.sequence < row.sequence)
+        .map(entry_row_to_snapshot_entry)
+        .collect();
+    let timestamp = preceding
+        .last()
+        .map(|e| e.causal_position)
+        .unwrap_or_else(CausalPosition::first);
+
+    let mut engine = IntegrationCostEngine::with_config(config);
+    if !preceding.is_empty() {
+        engine.initialize_from_entries(nb_id, &preceding, timestamp);
+    }
+
+    engine.explain_cost_resolved(&entry_row_to_snapshot_entry(row), nb_id, references)
+}
+
+// ============================================================================
+// Route Handler
+// ============================================================================
+
+/// GET /notebooks/{notebook_id}/entries/{entry_id}/cost/explain
+///
+/// Explains an entry's integration cost. The computation is replayed with
+/// the server's current cost configuration, so `explanation.cost` can differ
+/// from `stored_cost` if the configuration changed since the entry was written.
+///
+/// # Response
+///
+/// - 200 OK: `{ "entry_id": "...", "stored_cost": {...}, "explanation": {...}, "summary": "..." }`
+/// - 404 Not Found: Notebook or entry not found
+/// - 500 Internal Server Error: Storage failure
+async fn explain_cost(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path((notebook_id, entry_id)): Path<(Uuid, Uuid)>,
+) -> ApiResult<Json<CostExplainResponse>> {
+    require_scope(&identity, "notebook:read", state.config())?;
+    let store = state.store();
+
+    // Validate notebook exists
+    store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+
+    // The entry must live in this notebook
+    let row = store.get_entry(entry_id).await.map_err(|e| match e {
+        StoreError::EntryNotFound(id) => ApiError::entry_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+    if row.notebook_id != notebook_id {
+        return Err(ApiError::entry_not_found(entry_id));
+    }
+
+    let rows = store.query_entries(&EntryQuery::new(notebook_id)).await?;
+    let references = resolve_references(store, &[entry_row_to_snapshot_entry(&row)]).await?;
+    let explanation = explain_entry(state.config().cost_config(), &rows, &row, &references)
+        .map_err(|e| ApiError::Internal(format!("Failed to explain integration cost: {}", e)))?;
+
+    Ok(Json(CostExplainResponse {
+        entry_id,
+        stored_cost: row.integration_cost.clone(),
+        summary: explanation.orphan_reason.to_string(),
+        explanation,
+    }))
+}
+
+/// Build cost explanation routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route(
+        "/notebooks/{id}/entries/{entry_id}/cost/explain",
+        get(explain_cost),
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notebook_entropy::OrphanReason;
+
+    fn make_row(
+        notebook_id: Uuid,
+        sequence: i64,
+        content: &str,
+        references: Vec<Uuid>,
+    ) -> EntryRow {
+        EntryRow {
+            references,
+            ..EntryRow::test_row(notebook_id, sequence, content)
+        }
+    }
+
+    /// Every reference points to an existing entry.
+    fn resolved() -> ReferenceResolution {
+        ReferenceResolution::default()
+    }
+
+    fn background(notebook_id: Uuid) -> Vec<EntryRow> {
+        [
+            "medieval castle architecture stone walls",
+            "cooking pasta tomato sauce recipe",
+            "ocean tides moon gravity",
+            "jazz saxophone improvisation rhythm",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, content)| make_row(notebook_id, i as i64 + 1, content, vec![]))
+        .collect()
+    }
+
+    #[test]
+    fn test_crafted_orphan_reason_matches_flag() {
+        let notebook_id = Uuid::new_v4();
+        let mut rows = background(notebook_id);
+        let orphan = make_row(
+            notebook_id,
+            5,
+            "quantum chromodynamics gluon confinement",
+            vec![],
+        );
+        rows.push(orphan.clone());
+
+        let explanation =
+            explain_entry(CostConfig::default(), &rows, &orphan, &resolved()).unwrap();
+        assert_eq!(
+            explanation.orphan_reason,
+            OrphanReason::NoClusterMatchAndNoReferences
+        );
+        assert!(explanation.cost.orphan);
+        assert_eq!(
+            explanation.orphan_reason.is_orphan(),
+            explanation.cost.orphan
+        );
+    }
+
+    #[test]
+    fn test_referencing_entry_lists_boundary_reference() {
+        let notebook_id = Uuid::new_v4();
+        let mut rows = background(notebook_id);
+        let target = rows[0].id;
+        let entry = make_row(notebook_id, 5, "baroque harpsichord fugue", vec![target]);
+        rows.push(entry.clone());
+
+        let explanation = explain_entry(CostConfig::default(), &rows, &entry, &resolved()).unwrap();
+        assert!(!explanation.cost.orphan);
+        assert_eq!(
+            explanation.orphan_reason.is_orphan(),
+            explanation.cost.orphan
+        );
+        assert_eq!(explanation.boundary_references.len(), 1);
+        assert_eq!(explanation.boundary_references[0].0, target);
+    }
+
+    #[test]
+    fn test_reference_to_deleted_entry_does_not_integrate() {
+        let notebook_id = Uuid::new_v4();
+        let mut rows = background(notebook_id);
+        let deleted = Uuid::new_v4();
+        let entry = make_row(notebook_id, 5, "baroque harpsichord fugue", vec![deleted]);
+        rows.push(entry.clone());
+
+        let missing =
+            ReferenceResolution::with_missing([notebook_core::EntryId::from_uuid(deleted)]);
+        let explanation = explain_entry(CostConfig::default(), &rows, &entry, &missing).unwrap();
+        assert_eq!(
+            explanation.orphan_reason,
+            OrphanReason::ReferencesMissing { count: 1 }
+        );
+        assert!(explanation.cost.orphan);
+    }
+
+    #[test]
+    fn test_later_entries_are_ignored() {
+        let notebook_id = Uuid::new_v4();
+        let first = make_row(notebook_id, 1, "tokio async runtime tasks", vec![]);
+        let later = make_row(notebook_id, 2, "tokio async runtime executors", vec![]);
+        let rows = vec![first.clone(), later];
+
+        // The first entry had nothing to join when it was written
+        let explanation = explain_entry(CostConfig::default(), &rows, &first, &resolved()).unwrap();
+        assert!(explanation.new_cluster);
+        assert!(explanation.nearest_cluster.is_none());
+    }
+
+    #[test]
+    fn test_response_serialization() {
+        let notebook_id = Uuid::new_v4();
+        let row = make_row(notebook_id, 1, "first entry", vec![]);
+        let explanation = explain_entry(
+            CostConfig::default(),
+            std::slice::from_ref(&row),
+            &row,
+            &resolved(),
+        )
+        .unwrap();
+        let response = CostExplainResponse {
+            entry_id: row.id,
+            stored_cost: row.integration_cost.clone(),
+            summary: explanation.orphan_reason.to_string(),
+            explanation,
+        };
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["entry_id"], serde_json::json!(row.id));
+        assert_eq!(
+            json["explanation"]["orphan_reason"]["kind"],
+            "no_cluster_match_and_no_references"
+        );
+        assert!(json["explanation"]["shifted_clusters"].is_array());
+        assert!(json["summary"].as_str().unwrap().starts_with("orphan"));
+    }
+}
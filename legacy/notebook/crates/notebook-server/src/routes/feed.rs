@@ -0,0 +1,230 @@
+//! Cross-notebook activity feed.
+//!
+//! Lists recent entries across every notebook the caller can read, so agents
+//! can catch up on activity without polling each notebook separately.
+//!
+//! Endpoint: GET /feed?limit={n}&since={timestamp}
+
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use notebook_store::ActivityRow;
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, require_scope};
+use crate::state::AppState;
+
+/// Number of feed items returned when no limit is given.
+pub const DEFAULT_FEED_LIMIT: u32 = 50;
+
+/// Maximum number of feed items returned in one response.
+pub const MAX_FEED_LIMIT: u32 = 500;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Query parameters for the feed endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct FeedParams {
+    /// Maximum number of items to return.
+    #[serde(default)]
+    pub limit: Option<u32>,
+
+    /// Only include entries created after this time (RFC 3339).
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// Response for GET /feed.
+#[derive(Debug, Serialize)]
+pub struct FeedResponse {
+    /// Recent entries, newest first.
+    pub items: Vec<FeedItem>,
+}
+
+/// A single entry in the activity feed.
+#[derive(Debug, Serialize)]
+pub struct FeedItem {
+    /// Entry ID.
+    pub entry_id: Uuid,
+    /// Notebook the entry belongs to.
+    pub notebook_id: Uuid,
+    /// Name of that notebook.
+    pub notebook_name: String,
+    /// Operation type: "write" for new entries, "revise" for revisions.
+    pub operation: &'static str,
+    /// Author identity (hex-encoded 32-byte AuthorId).
+    pub author: String,
+    /// Optional topic/category.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    /// Sequence number in the notebook.
+    pub sequence: u64,
+    /// Creation timestamp.
+    pub created: DateTime<Utc>,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Resolve the effective limit, applying the default and the maximum.
+fn effective_limit(params: &FeedParams) -> ApiResult<i64> {
+    match params.limit {
+        Some(0) => Err(ApiError::BadRequest("limit must be at least 1".to_string())),
+        Some(limit) => Ok(limit.min(MAX_FEED_LIMIT) as i64),
+        None => Ok(DEFAULT_FEED_LIMIT as i64),
+    }
+}
+
+/// Convert an ActivityRow to a FeedItem.
+fn activity_row_to_item(row: ActivityRow) -> FeedItem {
+    let entry = row.entry;
+    FeedItem {
+        entry_id: entry.id,
+        notebook_id: entry.notebook_id,
+        notebook_name: row.notebook_name,
+        operation: if entry.revision_of.is_some() {
+            "revise"
+        } else {
+            "write"
+        },
+        author: entry
+            .author_id
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect(),
+        topic: entry.topic,
+        sequence: entry.sequence as u64,
+        created: entry.created,
+    }
+}
+
+// ============================================================================
+// Route Handler
+// ============================================================================
+
+/// GET /feed - Recent activity across the caller's readable notebooks.
+///
+/// # Query Parameters
+///
+/// - `limit`: Maximum number of results (default 50, capped at 500).
+/// - `since`: Only entries created after this RFC 3339 timestamp.
+///
+/// # Response
+///
+/// - 200 OK: `{ "items": [{ "entry_id": "...", "notebook_id": "...", "notebook_name": "...", ... }] }`
+/// - 400 Bad Request: `limit` is zero or `since` is not a valid timestamp
+async fn get_feed(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Query(params): Query<FeedParams>,
+) -> ApiResult<Json<FeedResponse>> {
+    require_scope(&identity, "notebook:read", state.config())?;
+    let limit = effective_limit(&params)?;
+
+    let rows = state
+        .store()
+        .recent_activity(identity.author_id.as_bytes(), limit, params.since)
+        .await?;
+    let items: Vec<FeedItem> = rows.into_iter().map(activity_row_to_item).collect();
+
+    tracing::debug!(
+        author = %identity.author_id,
+        item_count = items.len(),
+        "Listed activity feed"
+    );
+
+    Ok(Json(FeedResponse { items }))
+}
+
+/// Build feed routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/feed", get(get_feed))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notebook_store::EntryRow;
+
+    #[test]
+    fn test_effective_limit_default_and_cap() {
+        assert_eq!(
+            effective_limit(&FeedParams::default()).unwrap(),
+            DEFAULT_FEED_LIMIT as i64
+        );
+        let params = FeedParams {
+            limit: Some(10_000),
+            since: None,
+        };
+        assert_eq!(effective_limit(&params).unwrap(), MAX_FEED_LIMIT as i64);
+    }
+
+    #[test]
+    fn test_effective_limit_zero_rejected() {
+        let params = FeedParams {
+            limit: Some(0),
+            since: None,
+        };
+        assert!(matches!(
+            effective_limit(&params),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_feed_params_deserialize_since() {
+        let params: FeedParams =
+            serde_urlencoded::from_str("limit=5&since=2026-01-02T03:04:05Z").unwrap();
+        assert_eq!(params.limit, Some(5));
+        assert_eq!(
+            params.since.unwrap().to_rfc3339(),
+            "2026-01-02T03:04:05+00:00"
+        );
+        assert!(serde_urlencoded::from_str::<FeedParams>("since=yesterday").is_err());
+    }
+
+    #[test]
+    fn test_activity_row_to_item() {
+        let notebook_id = Uuid::new_v4();
+        let row = ActivityRow {
+            entry: EntryRow {
+                id: Uuid::nil(),
+                notebook_id,
+                content: b"hello".to_vec(),
+                content_type: "text/plain".to_string(),
+                topic: Some("news".to_string()),
+                author_id: vec![0xAB; 32],
+                signature: vec![],
+                revision_of: Some(Uuid::new_v4()),
+                references: vec![],
+                sequence: 3,
+                created: Utc::now(),
+                integration_cost: serde_json::json!({}),
+                cost_computed: true,
+                sealed: None,
+                pinned: false,
+            },
+            notebook_name: "Research".to_string(),
+        };
+
+        let json = serde_json::to_value(activity_row_to_item(row)).unwrap();
+        assert_eq!(json["notebook_id"], notebook_id.to_string());
+        assert_eq!(json["notebook_name"], "Research");
+        assert_eq!(json["operation"], "revise");
+        assert_eq!(json["author"], "ab".repeat(32));
+        assert_eq!(json["sequence"], 3);
+    }
+}
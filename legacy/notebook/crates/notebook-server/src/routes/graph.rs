@@ -0,0 +1,715 @@
+//! Parameterized graph queries over a notebook's reference graph.
+//!
+//! Clients pick one of a fixed set of query templates (neighbors, referrers,
+//! referenced, shortest path) and supply typed parameters. Raw Cypher is
+//! never accepted: the store renders each template itself, interpolating
+//! only UUIDs and a bounded depth, and scopes every match to the notebook.
+//!
+//! Templates run on Apache AGE; without it the endpoint returns 501.
+//!
+//! The whole graph can also be exported for analysis in external tools, as
+//! GraphML or JSON. Entries become nodes carrying their topic, author and
+//! sequence; references and revisions become typed edges. The export works
+//! with or without AGE.
+//!
+//! An entry's lineage follows references in an explicit direction: its
+//! ancestors are the entries it references, transitively, and its
+//! descendants the entries referencing it. Lineage works with or without
+//! AGE. (The `referenced` and `referrers` query templates walk the same
+//! directions; their deprecated names `descendants` and `ancestors` are
+//! the other way round.)
+//!
+//! Endpoints:
+//! - POST /notebooks/{notebook_id}/graph/query
+//! - GET /notebooks/{notebook_id}/graph/export?format={graphml|json}&since_sequence={n}
+//! - GET /notebooks/{notebook_id}/entries/{entry_id}/ancestors?max_depth={n}
+//! - GET /notebooks/{notebook_id}/entries/{entry_id}/descendants?max_depth={n}
+
+use std::fmt::Write;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::header::CONTENT_TYPE,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use notebook_store::{
+    GraphEdgeKind, GraphQueryHit, GraphQueryTemplate, NotebookGraph, ReferenceDirection,
+    StoreError, TEMPLATE_DEFAULT_DEPTH, TEMPLATE_MAX_DEPTH,
+};
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, require_scope};
+use crate::state::AppState;
+
+/// Maximum number of entries in one graph export.
+pub const MAX_EXPORT_NODES: usize = 10_000;
+
+/// Content type of GraphML exports.
+pub const GRAPHML_CONTENT_TYPE: &str = "application/graphml+xml";
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Serialization of a graph export.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// GraphML document.
+    Graphml,
+    /// JSON node and edge lists.
+    #[default]
+    Json,
+}
+
+/// Query parameters for the graph export endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct GraphExportParams {
+    /// Output format (default JSON).
+    #[serde(default)]
+    pub format: ExportFormat,
+
+    /// Only export entries with a sequence greater than this.
+    #[serde(default)]
+    pub since_sequence: Option<u64>,
+}
+
+/// An entry in a JSON graph export.
+#[derive(Debug, Serialize)]
+pub struct ExportNodeView {
+    /// Entry ID.
+    pub id: Uuid,
+    /// Entry topic, if any.
+    pub topic: Option<String>,
+    /// Author identity (hex-encoded 32-byte AuthorId).
+    pub author: String,
+    /// Sequence number in the notebook.
+    pub sequence: u64,
+}
+
+/// An edge in a JSON graph export.
+#[derive(Debug, Serialize)]
+pub struct ExportEdgeView {
+    /// Entry the edge starts from.
+    pub source: Uuid,
+    /// Entry the edge points to.
+    pub target: Uuid,
+    /// `references` or `revision_of`.
+    pub kind: GraphEdgeKind,
+}
+
+/// Response for GET /notebooks/{id}/graph/export?format=json.
+#[derive(Debug, Serialize)]
+pub struct GraphExportResponse {
+    /// The exported notebook.
+    pub notebook_id: Uuid,
+    /// Entries, in sequence order.
+    pub nodes: Vec<ExportNodeView>,
+    /// References and revisions between the exported entries.
+    pub edges: Vec<ExportEdgeView>,
+    /// Whether entries were left out because of the size cap.
+    pub truncated: bool,
+}
+
+/// Response for POST /notebooks/{id}/graph/query.
+#[derive(Debug, Serialize)]
+pub struct GraphQueryResponse {
+    /// Name of the template that ran.
+    pub template: &'static str,
+    /// Matched entries, ordered by depth.
+    pub results: Vec<GraphQueryHit>,
+    /// Number of matched entries.
+    pub count: usize,
+}
+
+/// Query parameters for the ancestors and descendants endpoints.
+#[derive(Debug, Default, Deserialize)]
+pub struct LineageParams {
+    /// Maximum number of reference hops (default 10, max 20).
+    pub max_depth: Option<u32>,
+}
+
+/// Response for GET /notebooks/{id}/entries/{entry_id}/ancestors and
+/// /descendants.
+#[derive(Debug, Serialize)]
+pub struct LineageResponse {
+    /// The entry the traversal started from.
+    pub entry_id: Uuid,
+    /// `references` for ancestors, `referenced_by` for descendants.
+    pub direction: ReferenceDirection,
+    /// Depth the traversal was bounded to.
+    pub max_depth: u32,
+    /// Reached entries of this notebook, ordered by depth.
+    pub results: Vec<GraphQueryHit>,
+    /// Number of reached entries.
+    pub count: usize,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Reject depths outside `1..=TEMPLATE_MAX_DEPTH`.
+fn validate_depth(depth: u32) -> ApiResult<()> {
+    if !(1..=TEMPLATE_MAX_DEPTH).contains(&depth) {
+        return Err(ApiError::BadRequest(format!(
+            "max_depth must be between 1 and {}, got {}",
+            TEMPLATE_MAX_DEPTH, depth
+        )));
+    }
+    Ok(())
+}
+
+/// Reject template depths outside `1..=TEMPLATE_MAX_DEPTH`.
+fn validate_template(template: &GraphQueryTemplate) -> ApiResult<()> {
+    template.max_depth().map_or(Ok(()), validate_depth)
+}
+
+/// First template entry that is not among `found`.
+fn first_missing_entry(template: &GraphQueryTemplate, found: &[Uuid]) -> Option<Uuid> {
+    template
+        .entry_ids()
+        .into_iter()
+        .find(|id| !found.contains(id))
+}
+
+/// Hex-encode an author ID.
+fn author_hex(author_id: &[u8]) -> String {
+    author_id.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Escape text for use in XML content and attribute values.
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Convert an exported graph to its JSON response.
+fn export_to_json(notebook_id: Uuid, graph: NotebookGraph) -> GraphExportResponse {
+    GraphExportResponse {
+        notebook_id,
+        nodes: graph
+            .nodes
+            .into_iter()
+            .map(|node| ExportNodeView {
+                id: node.entry_id,
+                topic: node.topic,
+                author: author_hex(&node.author_id),
+                sequence: node.sequence as u64,
+            })
+            .collect(),
+        edges: graph
+            .edges
+            .into_iter()
+            .map(|edge| ExportEdgeView {
+                source: edge.source,
+                target: edge.target,
+                kind: edge.kind,
+            })
+            .collect(),
+        truncated: graph.truncated,
+    }
+}
+
+/// Render an exported graph as a GraphML document.
+fn export_to_graphml(notebook_id: Uuid, graph: &NotebookGraph) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n\
+         \x20 <key id=\"truncated\" for=\"graph\" attr.name=\"truncated\" attr.type=\"boolean\"/>\n\
+         \x20 <key id=\"topic\" for=\"node\" attr.name=\"topic\" attr.type=\"string\"/>\n\
+         \x20 <key id=\"author\" for=\"node\" attr.name=\"author\" attr.type=\"string\"/>\n\
+         \x20 <key id=\"sequence\" for=\"node\" attr.name=\"sequence\" attr.type=\"long\"/>\n\
+         \x20 <key id=\"kind\" for=\"edge\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+    );
+    // Writing to a String cannot fail
+    let _ = writeln!(
+        xml,
+        "  <graph id=\"{}\" edgedefault=\"directed\">\n    <data key=\"truncated\">{}</data>",
+        notebook_id, graph.truncated
+    );
+    for node in &graph.nodes {
+        let _ = writeln!(xml, "    <node id=\"{}\">", node.entry_id);
+        if let Some(topic) = &node.topic {
+            let _ = writeln!(
+                xml,
+                "      <data key=\"topic\">{}</data>",
+                xml_escape(topic)
+            );
+        }
+        let _ = writeln!(
+            xml,
+            "      <data key=\"author\">{}</data>\n      <data key=\"sequence\">{}</data>\n    </node>",
+            author_hex(&node.author_id),
+            node.sequence
+        );
+    }
+    for edge in &graph.edges {
+        let _ = writeln!(
+            xml,
+            "    <edge source=\"{}\" target=\"{}\">\n      <data key=\"kind\">{}</data>\n    </edge>",
+            edge.source,
+            edge.target,
+            edge.kind.label()
+        );
+    }
+    xml.push_str("  </graph>\n</graphml>\n");
+    xml
+}
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// POST /notebooks/{notebook_id}/graph/query
+///
+/// Runs a whitelisted graph query template within the notebook.
+///
+/// # Request Body
+///
+/// `{ "template": "referenced", "params": { "entry_id": "...", "max_depth": 5 } }`
+///
+/// # Response
+///
+/// - 200 OK: `{ "template": "referenced", "results": [{ "entry_id": "...", "depth": 1 }], "count": 1 }`
+/// - 400 Bad Request: Depth out of range
+/// - 404 Not Found: Notebook or entry not found
+/// - 501 Not Implemented: Apache AGE is not available
+async fn query_graph(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+    Json(template): Json<GraphQueryTemplate>,
+) -> ApiResult<Json<GraphQueryResponse>> {
+    require_scope(&identity, "notebook:read", state.config())?;
+    validate_template(&template)?;
+    let store = state.store();
+
+    // Validate notebook exists
+    store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+
+    // Every entry the template names must live in this notebook
+    let found = store
+        .entries_in_notebook(notebook_id, &template.entry_ids())
+        .await?;
+    if let Some(missing) = first_missing_entry(&template, &found) {
+        return Err(ApiError::entry_not_found(missing));
+    }
+
+    let results = store.graph().run_template(notebook_id, &template).await?;
+
+    Ok(Json(GraphQueryResponse {
+        template: template.name(),
+        count: results.len(),
+        results,
+    }))
+}
+
+/// GET /notebooks/{notebook_id}/graph/export
+///
+/// Exports the notebook's entries and the references and revisions between
+/// them.
+///
+/// # Query Parameters
+///
+/// - `format`: `json` (default) or `graphml`.
+/// - `since_sequence`: Only export entries written after this sequence.
+///
+/// At most 10,000 entries are exported, oldest first; `truncated` reports
+/// whether more remain, which can be fetched with `since_sequence` set to the
+/// last exported sequence. Edges to entries outside the export are left out.
+///
+/// # Response
+///
+/// - 200 OK: `{ "notebook_id": "...", "nodes": [{ "id": "...", "topic": "...", "author": "...", "sequence": 1 }], "edges": [{ "source": "...", "target": "...", "kind": "references" }], "truncated": false }`,
+///   or the same graph as `application/graphml+xml`
+/// - 400 Bad Request: Unknown format
+/// - 404 Not Found: Notebook not found
+async fn export_graph(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+    Query(params): Query<GraphExportParams>,
+) -> ApiResult<Response> {
+    require_scope(&identity, "notebook:read", state.config())?;
+    let store = state.store();
+
+    // Validate notebook exists
+    store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+
+    let since_sequence = params.since_sequence.unwrap_or(0) as i64;
+    let graph = store
+        .graph()
+        .export_notebook(notebook_id, since_sequence, MAX_EXPORT_NODES)
+        .await?;
+
+    tracing::debug!(
+        notebook_id = %notebook_id,
+        nodes = graph.nodes.len(),
+        edges = graph.edges.len(),
+        truncated = graph.truncated,
+        "Exported notebook graph"
+    );
+
+    Ok(match params.format {
+        ExportFormat::Json => Json(export_to_json(notebook_id, graph)).into_response(),
+        ExportFormat::Graphml => (
+            [(CONTENT_TYPE, GRAPHML_CONTENT_TYPE)],
+            export_to_graphml(notebook_id, &graph),
+        )
+            .into_response(),
+    })
+}
+
+/// Follows an entry's references in `direction` and lists the entries of
+/// the notebook reached.
+async fn lineage(
+    state: &AppState,
+    identity: &AuthorIdentity,
+    notebook_id: Uuid,
+    entry_id: Uuid,
+    direction: ReferenceDirection,
+    params: LineageParams,
+) -> ApiResult<Json<LineageResponse>> {
+    require_scope(identity, "notebook:read", state.config())?;
+    let max_depth = params.max_depth.unwrap_or(TEMPLATE_DEFAULT_DEPTH);
+    validate_depth(max_depth)?;
+    let store = state.store();
+
+    // Validate notebook exists
+    store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+
+    // The entry must live in this notebook
+    if store
+        .entries_in_notebook(notebook_id, &[entry_id])
+        .await?
+        .is_empty()
+    {
+        return Err(ApiError::entry_not_found(entry_id));
+    }
+
+    let hits = match direction {
+        ReferenceDirection::References => store.ancestors(entry_id, max_depth).await?,
+        ReferenceDirection::ReferencedBy => store.descendants(entry_id, max_depth).await?,
+    };
+
+    // References may cross into other notebooks; only report this one's
+    let ids: Vec<Uuid> = hits.iter().map(|hit| hit.entry_id).collect();
+    let local = store.entries_in_notebook(notebook_id, &ids).await?;
+    let results: Vec<GraphQueryHit> = hits
+        .into_iter()
+        .filter(|hit| local.contains(&hit.entry_id))
+        .collect();
+
+    Ok(Json(LineageResponse {
+        entry_id,
+        direction,
+        max_depth,
+        count: results.len(),
+        results,
+    }))
+}
+
+/// GET /notebooks/{notebook_id}/entries/{entry_id}/ancestors
+///
+/// Lists the entries an entry references, transitively.
+///
+/// # Query Parameters
+///
+/// - `max_depth`: Maximum reference hops (default 10, max 20).
+///
+/// # Response
+///
+/// - 200 OK: `{ "entry_id": "...", "direction": "references", "max_depth": 10, "results": [{ "entry_id": "...", "depth": 1 }], "count": 1 }`
+/// - 400 Bad Request: Depth out of range
+/// - 404 Not Found: Notebook or entry not found
+async fn ancestors(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path((notebook_id, entry_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<LineageParams>,
+) -> ApiResult<Json<LineageResponse>> {
+    lineage(
+        &state,
+        &identity,
+        notebook_id,
+        entry_id,
+        ReferenceDirection::References,
+        params,
+    )
+    .await
+}
+
+/// GET /notebooks/{notebook_id}/entries/{entry_id}/descendants
+///
+/// Lists the entries referencing an entry, transitively.
+///
+/// # Query Parameters
+///
+/// - `max_depth`: Maximum reference hops (default 10, max 20).
+///
+/// # Response
+///
+/// - 200 OK: `{ "entry_id": "...", "direction": "referenced_by", "max_depth": 10, "results": [{ "entry_id": "...", "depth": 1 }], "count": 1 }`
+/// - 400 Bad Request: Depth out of range
+/// - 404 Not Found: Notebook or entry not found
+async fn descendants(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path((notebook_id, entry_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<LineageParams>,
+) -> ApiResult<Json<LineageResponse>> {
+    lineage(
+        &state,
+        &identity,
+        notebook_id,
+        entry_id,
+        ReferenceDirection::ReferencedBy,
+        params,
+    )
+    .await
+}
+
+/// Build graph query routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/notebooks/{id}/graph/query", post(query_graph))
+        .route("/notebooks/{id}/graph/export", get(export_graph))
+        .route(
+            "/notebooks/{id}/entries/{entry_id}/ancestors",
+            get(ancestors),
+        )
+        .route(
+            "/notebooks/{id}/entries/{entry_id}/descendants",
+            get(descendants),
+        )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notebook_core::AuthorId;
+    use notebook_store::{GraphExportEdge, GraphExportNode, Store};
+    use sqlx::PgPool;
+
+    use crate::config::ServerConfig;
+    use crate::error::ErrorCode;
+
+    fn sample_graph() -> NotebookGraph {
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        let revised = Uuid::new_v4();
+        let node = |entry_id, topic: Option<&str>, sequence| GraphExportNode {
+            entry_id,
+            topic: topic.map(str::to_string),
+            author_id: vec![0xAB; 32],
+            sequence,
+        };
+        NotebookGraph {
+            nodes: vec![
+                node(first, Some("dough & <butter>"), 1),
+                node(second, None, 2),
+                node(revised, Some("dough"), 3),
+            ],
+            edges: vec![
+                GraphExportEdge {
+                    source: second,
+                    target: first,
+                    kind: GraphEdgeKind::References,
+                },
+                GraphExportEdge {
+                    source: revised,
+                    target: first,
+                    kind: GraphEdgeKind::RevisionOf,
+                },
+            ],
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn test_referenced_request_parses() {
+        let entry_id = Uuid::new_v4();
+        let body = serde_json::json!({
+            "template": "referenced",
+            "params": { "entry_id": entry_id, "max_depth": 3 }
+        });
+
+        let template: GraphQueryTemplate = serde_json::from_value(body).unwrap();
+        assert_eq!(
+            template,
+            GraphQueryTemplate::Referenced {
+                entry_id,
+                max_depth: 3
+            }
+        );
+        assert!(validate_template(&template).is_ok());
+    }
+
+    #[test]
+    fn test_depth_out_of_range_is_rejected() {
+        let entry_id = Uuid::new_v4();
+        for max_depth in [0, TEMPLATE_MAX_DEPTH + 1] {
+            let template = GraphQueryTemplate::Referrers {
+                entry_id,
+                max_depth,
+            };
+            assert!(matches!(
+                validate_template(&template),
+                Err(ApiError::BadRequest(_))
+            ));
+        }
+
+        // Neighbors has no depth to validate
+        assert!(validate_template(&GraphQueryTemplate::Neighbors { entry_id }).is_ok());
+    }
+
+    #[test]
+    fn test_lineage_response_names_direction() {
+        let entry_id = Uuid::new_v4();
+        let response = LineageResponse {
+            entry_id,
+            direction: ReferenceDirection::ReferencedBy,
+            max_depth: TEMPLATE_DEFAULT_DEPTH,
+            results: vec![GraphQueryHit { entry_id, depth: 1 }],
+            count: 1,
+        };
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["direction"], "referenced_by");
+        assert_eq!(json["max_depth"], TEMPLATE_DEFAULT_DEPTH);
+        assert_eq!(json["results"][0]["depth"], 1);
+        assert!(validate_depth(TEMPLATE_MAX_DEPTH).is_ok());
+        assert!(validate_depth(0).is_err());
+    }
+
+    #[test]
+    fn test_shortest_path_requires_both_entries() {
+        let from = Uuid::new_v4();
+        let to = Uuid::new_v4();
+        let template = GraphQueryTemplate::ShortestPath {
+            from,
+            to,
+            max_depth: 5,
+        };
+
+        assert_eq!(first_missing_entry(&template, &[from]), Some(to));
+        assert_eq!(first_missing_entry(&template, &[to, from]), None);
+    }
+
+    #[test]
+    fn test_response_serialization() {
+        let entry_id = Uuid::new_v4();
+        let response = GraphQueryResponse {
+            template: "referenced",
+            results: vec![GraphQueryHit { entry_id, depth: 2 }],
+            count: 1,
+        };
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["template"], "referenced");
+        assert_eq!(json["results"][0]["entry_id"], serde_json::json!(entry_id));
+        assert_eq!(json["results"][0]["depth"], 2);
+        assert_eq!(json["count"], 1);
+    }
+
+    #[test]
+    fn test_export_params_parse() {
+        let params: GraphExportParams =
+            serde_json::from_value(serde_json::json!({ "format": "graphml", "since_sequence": 7 }))
+                .unwrap();
+        assert_eq!(params.format, ExportFormat::Graphml);
+        assert_eq!(params.since_sequence, Some(7));
+
+        let params: GraphExportParams = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(params.format, ExportFormat::Json);
+        assert!(
+            serde_json::from_value::<GraphExportParams>(serde_json::json!({ "format": "dot" }))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_json_export_has_every_node_and_edge() {
+        let notebook_id = Uuid::new_v4();
+        let graph = sample_graph();
+        let json = serde_json::to_value(export_to_json(notebook_id, graph.clone())).unwrap();
+
+        assert_eq!(json["notebook_id"], serde_json::json!(notebook_id));
+        assert_eq!(json["nodes"].as_array().unwrap().len(), graph.nodes.len());
+        assert_eq!(json["edges"].as_array().unwrap().len(), graph.edges.len());
+        assert_eq!(json["nodes"][0]["author"], "ab".repeat(32));
+        assert_eq!(json["nodes"][1]["topic"], serde_json::Value::Null);
+        assert_eq!(json["nodes"][2]["sequence"], 3);
+        assert_eq!(json["edges"][0]["kind"], "references");
+        assert_eq!(json["edges"][1]["kind"], "revision_of");
+        assert_eq!(json["truncated"], false);
+    }
+
+    #[test]
+    fn test_graphml_export_has_every_node_and_edge() {
+        let graph = sample_graph();
+        let xml = export_to_graphml(Uuid::new_v4(), &graph);
+
+        assert!(xml.starts_with("<?xml"));
+        assert_eq!(xml.matches("<node ").count(), graph.nodes.len());
+        assert_eq!(xml.matches("<edge ").count(), graph.edges.len());
+        assert!(xml.contains(&format!(
+            "<edge source=\"{}\" target=\"{}\">",
+            graph.edges[1].source, graph.edges[1].target
+        )));
+        assert!(xml.contains("<data key=\"kind\">revision_of</data>"));
+        assert!(xml.contains("<data key=\"topic\">dough &amp; &lt;butter&gt;</data>"));
+        assert!(xml.contains("<data key=\"sequence\">3</data>"));
+        assert!(xml.trim_end().ends_with("</graphml>"));
+    }
+
+    #[tokio::test]
+    async fn test_export_requires_read_scope() {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let state = AppState::new(Store::from_pool(pool), ServerConfig::default());
+        let identity = AuthorIdentity {
+            author_id: AuthorId::zero(),
+            scopes: vec!["notebook:write".to_string()],
+        };
+        let result = export_graph(
+            State(state),
+            identity,
+            Path(Uuid::new_v4()),
+            Query(GraphExportParams::default()),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ApiError::Coded(ErrorCode::MissingScope, _))
+        ));
+    }
+}
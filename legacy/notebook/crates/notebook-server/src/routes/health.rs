@@ -1,6 +1,10 @@
-//! Health check endpoint.
+//! Health check endpoints.
+//!
+//! - GET /health - Liveness: the process is up and serving
+//! - GET /health/ready - Readiness: workers are running and the database
+//!   answered; see [`crate::readiness`]
 
-use axum::{Json, Router, routing::get};
+use axum::{Json, Router, extract::State, http::StatusCode, routing::get};
 use serde::Serialize;
 
 use crate::state::AppState;
@@ -12,6 +16,17 @@ pub struct HealthResponse {
     pub status: String,
 }
 
+/// Readiness check response.
+#[derive(Debug, Serialize)]
+pub struct ReadinessResponse {
+    /// `ready`, or `starting` while startup is unfinished.
+    pub status: String,
+    /// Whether the background workers have been started.
+    pub workers_started: bool,
+    /// Whether the database answered a check.
+    pub database_checked: bool,
+}
+
 /// GET /health - Health check endpoint.
 async fn health_check() -> Json<HealthResponse> {
     Json(HealthResponse {
@@ -19,18 +34,71 @@ async fn health_check() -> Json<HealthResponse> {
     })
 }
 
+/// GET /health/ready - Readiness check endpoint.
+///
+/// # Response
+///
+/// - 200 OK: Ready for traffic
+/// - 503 Service Unavailable: Still starting
+async fn readiness_check(State(state): State<AppState>) -> (StatusCode, Json<ReadinessResponse>) {
+    let readiness = state.readiness();
+    let (code, status) = if readiness.is_ready() {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "starting")
+    };
+
+    (
+        code,
+        Json(ReadinessResponse {
+            status: status.to_string(),
+            workers_started: readiness.workers_started(),
+            database_checked: readiness.database_checked(),
+        }),
+    )
+}
+
 /// Build health check routes.
 pub fn routes() -> Router<AppState> {
-    Router::new().route("/health", get(health_check))
+    Router::new()
+        .route("/health", get(health_check))
+        .route("/health/ready", get(readiness_check))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use notebook_store::Store;
+    use sqlx::PgPool;
+    use tower::ServiceExt;
+
+    use crate::config::ServerConfig;
 
     #[tokio::test]
     async fn test_health_check() {
         let response = health_check().await;
         assert_eq!(response.status, "ok");
     }
+
+    #[tokio::test]
+    async fn test_ready_only_after_workers_start() {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let state = AppState::new(Store::from_pool(pool), ServerConfig::default());
+        let app = routes().with_state(state.clone());
+        let probe = || async {
+            let request = Request::builder()
+                .uri("/health/ready")
+                .body(Body::empty())
+                .unwrap();
+            app.clone().oneshot(request).await.unwrap().status()
+        };
+
+        assert_eq!(probe().await, StatusCode::SERVICE_UNAVAILABLE);
+        state.readiness().mark_database_checked();
+        assert_eq!(probe().await, StatusCode::SERVICE_UNAVAILABLE);
+        state.readiness().mark_workers_started();
+        assert_eq!(probe().await, StatusCode::OK);
+    }
 }
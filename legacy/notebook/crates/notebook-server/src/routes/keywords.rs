@@ -0,0 +1,216 @@
+//! Keyword extraction against a notebook's corpus.
+//!
+//! Agents can pre-tag content before writing it by asking which of its terms
+//! stand out. The text is weighted by TF-IDF against the notebook's entries,
+//! as if it had been written there, so terms the notebook already uses
+//! everywhere rank below the ones that set the text apart; see
+//! [`notebook_entropy::weighted_keywords`]. Nothing is stored.
+//!
+//! The corpus is the one the integration cost engine keeps for the notebook,
+//! as for topic inference. If the engine holds no snapshot for the notebook,
+//! typically after a restart, one is rebuilt from storage first, as it would
+//! be on the next write.
+//!
+//! Endpoint: POST /notebooks/{notebook_id}/extract-keywords
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    routing::post,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use notebook_core::{CausalPosition, NotebookId};
+use notebook_entropy::CorpusStats;
+use notebook_entropy::tfidf::tokenize_in;
+use notebook_entropy::weighted_keywords;
+use notebook_store::EntryQuery;
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::ReaderIdentity;
+use crate::public_reads::readable_notebook;
+use crate::routes::suggest::entry_row_to_snapshot_entry;
+use crate::state::AppState;
+
+/// Number of keywords returned when no limit is given.
+pub const DEFAULT_KEYWORD_LIMIT: u32 = 10;
+
+/// Maximum number of keywords returned in one response.
+pub const MAX_KEYWORD_LIMIT: u32 = 100;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Request body for keyword extraction.
+#[derive(Debug, Deserialize)]
+pub struct ExtractKeywordsRequest {
+    /// Text to extract keywords from.
+    pub text: String,
+    /// Maximum number of keywords to return.
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// A keyword with its TF-IDF weight.
+#[derive(Debug, Serialize)]
+pub struct Keyword {
+    /// The keyword, as tokenized (lowercased, stop words removed).
+    pub term: String,
+    /// TF-IDF weight against the notebook's corpus.
+    pub weight: f64,
+}
+
+/// Response for POST /notebooks/{id}/extract-keywords.
+#[derive(Debug, Serialize)]
+pub struct ExtractKeywordsResponse {
+    /// The notebook whose corpus weighted the keywords.
+    pub notebook_id: Uuid,
+    /// Keywords, strongest first.
+    pub keywords: Vec<Keyword>,
+    /// Number of entries in the corpus.
+    pub corpus_documents: usize,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Resolve the effective limit, applying the default and the maximum.
+fn effective_limit(limit: Option<u32>) -> ApiResult<usize> {
+    match limit {
+        Some(0) => Err(ApiError::BadRequest("limit must be at least 1".to_string())),
+        Some(limit) => Ok(limit.min(MAX_KEYWORD_LIMIT) as usize),
+        None => Ok(DEFAULT_KEYWORD_LIMIT as usize),
+    }
+}
+
+/// Corpus statistics of the notebook's coherence snapshot.
+async fn notebook_corpus(state: &AppState, notebook_id: Uuid) -> ApiResult<CorpusStats> {
+    let nb_id = NotebookId::from_uuid(notebook_id);
+    if let Some(snapshot) = state.engines().lock(nb_id).await.get_snapshot(nb_id) {
+        return Ok(snapshot.corpus_stats.clone());
+    }
+
+    // Load entries without holding the engine lock, then rebuild
+    let rows = state
+        .store()
+        .query_entries(&EntryQuery::new(notebook_id))
+        .await?;
+    let entries: Vec<_> = rows.iter().map(entry_row_to_snapshot_entry).collect();
+
+    let mut engine = state.engines().lock(nb_id).await;
+    // A concurrent request may have built the snapshot in the meantime
+    if engine.get_snapshot(nb_id).is_none() {
+        let timestamp = entries
+            .last()
+            .map(|e| e.causal_position)
+            .unwrap_or_else(CausalPosition::first);
+        engine.initialize_from_entries(nb_id, &entries, timestamp);
+        tracing::info!(
+            notebook_id = %notebook_id,
+            entries = entries.len(),
+            "Rebuilt coherence snapshot for keyword extraction"
+        );
+    }
+    engine
+        .get_snapshot(nb_id)
+        .map(|snapshot| snapshot.corpus_stats.clone())
+        .ok_or_else(|| ApiError::Internal("Coherence snapshot missing after rebuild".into()))
+}
+
+// ============================================================================
+// Route Handler
+// ============================================================================
+
+/// POST /notebooks/{notebook_id}/extract-keywords
+///
+/// Extracts the top keywords of a text, weighted against the notebook's
+/// corpus. Public notebooks can be used without credentials.
+///
+/// # Request Body
+///
+/// `{ "text": "...", "limit": 10 }`; `limit` defaults to 10, capped at 100.
+///
+/// # Response
+///
+/// - 200 OK: `{ "notebook_id": "...", "keywords": [{ "term": "sourdough", "weight": 0.87 }], "corpus_documents": 12 }`
+/// - 400 Bad Request: `limit` is zero
+/// - 401 Unauthorized: No credentials and the notebook is not public
+/// - 404 Not Found: Notebook not found
+async fn extract_keywords(
+    State(state): State<AppState>,
+    reader: ReaderIdentity,
+    Path(notebook_id): Path<Uuid>,
+    Json(request): Json<ExtractKeywordsRequest>,
+) -> ApiResult<Json<ExtractKeywordsResponse>> {
+    let limit = effective_limit(request.limit)?;
+    readable_notebook(&state, &reader, notebook_id).await?;
+
+    let corpus = notebook_corpus(&state, notebook_id).await?;
+    let tokens = tokenize_in(&request.text, state.config().catalog_locale);
+    let keywords: Vec<Keyword> = weighted_keywords(&tokens, &corpus, limit)
+        .into_iter()
+        .map(|(term, weight)| Keyword { term, weight })
+        .collect();
+
+    tracing::debug!(
+        notebook_id = %notebook_id,
+        corpus_documents = corpus.document_count,
+        keywords = keywords.len(),
+        "Extracted keywords"
+    );
+
+    Ok(Json(ExtractKeywordsResponse {
+        notebook_id,
+        keywords,
+        corpus_documents: corpus.document_count,
+    }))
+}
+
+/// Build keyword extraction routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/notebooks/{id}/extract-keywords", post(extract_keywords))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_limit_default_and_cap() {
+        assert_eq!(
+            effective_limit(None).unwrap(),
+            DEFAULT_KEYWORD_LIMIT as usize
+        );
+        assert_eq!(
+            effective_limit(Some(1_000)).unwrap(),
+            MAX_KEYWORD_LIMIT as usize
+        );
+        assert!(matches!(
+            effective_limit(Some(0)),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_response_serialize() {
+        let response = ExtractKeywordsResponse {
+            notebook_id: Uuid::nil(),
+            keywords: vec![Keyword {
+                term: "sourdough".to_string(),
+                weight: 0.5,
+            }],
+            corpus_documents: 3,
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["keywords"][0]["term"], "sourdough");
+        assert_eq!(json["keywords"][0]["weight"], 0.5);
+        assert_eq!(json["corpus_documents"], 3);
+    }
+}
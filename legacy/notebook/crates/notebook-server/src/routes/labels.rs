@@ -0,0 +1,285 @@
+//! Notebook labels.
+//!
+//! Authors label the notebooks they can access (e.g. `project-alpha`,
+//! `archived`) to organize their notebook list, which can be filtered with
+//! `GET /notebooks?label=...`. Labels belong to the author who applied them:
+//! nobody else sees them, and they grant no access.
+//!
+//! Endpoints:
+//! - GET /notebooks/{notebook_id}/labels
+//! - POST /notebooks/{notebook_id}/labels
+//! - DELETE /notebooks/{notebook_id}/labels/{label}
+
+use std::collections::HashMap;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    routing::{delete, get},
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use notebook_store::{NotebookLabelRow, StoreError};
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, require_scope};
+use crate::state::AppState;
+
+/// Maximum length of a label, in characters.
+pub const MAX_LABEL_LENGTH: usize = 64;
+
+/// Maximum number of labels an author may apply to one notebook.
+pub const MAX_LABELS_PER_NOTEBOOK: usize = 20;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Request body for POST /notebooks/{id}/labels.
+#[derive(Debug, Deserialize)]
+pub struct AddLabelRequest {
+    /// The label to apply.
+    pub label: String,
+}
+
+/// The caller's labels on a notebook.
+#[derive(Debug, Serialize)]
+pub struct NotebookLabelsResponse {
+    pub notebook_id: Uuid,
+    /// Labels, sorted.
+    pub labels: Vec<String>,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Validate a label and bring it to its stored form: trimmed and
+/// lowercased, 1-64 ASCII letters, digits, `-`, `_`, `.` or `:`.
+pub fn normalize_label(label: &str) -> ApiResult<String> {
+    let label = label.trim().to_ascii_lowercase();
+    if label.is_empty() {
+        return Err(ApiError::BadRequest("label must not be empty".to_string()));
+    }
+    if label.len() > MAX_LABEL_LENGTH {
+        return Err(ApiError::BadRequest(format!(
+            "label must be at most {} characters",
+            MAX_LABEL_LENGTH
+        )));
+    }
+    if let Some(c) = label
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':')))
+    {
+        return Err(ApiError::BadRequest(format!(
+            "label may not contain {:?}; use letters, digits, '-', '_', '.' or ':'",
+            c
+        )));
+    }
+    Ok(label)
+}
+
+/// Group an author's labels by notebook.
+pub fn labels_by_notebook(rows: Vec<NotebookLabelRow>) -> HashMap<Uuid, Vec<String>> {
+    let mut labels: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for row in rows {
+        labels.entry(row.notebook_id).or_default().push(row.label);
+    }
+    labels
+}
+
+/// Ensure the caller owns the notebook or has been granted access to it.
+async fn ensure_accessible(
+    state: &AppState,
+    identity: &AuthorIdentity,
+    notebook_id: Uuid,
+) -> ApiResult<()> {
+    let store = state.store();
+    let author_bytes = *identity.author_id.as_bytes();
+
+    let notebook = store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+    if notebook.owner_id.as_slice() == author_bytes.as_slice()
+        || store.has_read_access(notebook_id, &author_bytes).await?
+        || store.has_write_access(notebook_id, &author_bytes).await?
+    {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden(
+            "Labeling a notebook requires access to it".to_string(),
+        ))
+    }
+}
+
+/// The caller's labels on a notebook, as a response.
+async fn labels_response(
+    state: &AppState,
+    identity: &AuthorIdentity,
+    notebook_id: Uuid,
+) -> ApiResult<Json<NotebookLabelsResponse>> {
+    let labels = state
+        .store()
+        .notebook_labels(notebook_id, identity.author_id.as_bytes())
+        .await?;
+    Ok(Json(NotebookLabelsResponse {
+        notebook_id,
+        labels,
+    }))
+}
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// GET /notebooks/{notebook_id}/labels
+///
+/// Lists the caller's labels on a notebook.
+///
+/// # Response
+///
+/// - 200 OK: `{ "notebook_id": "...", "labels": ["archived", "project-alpha"] }`
+/// - 403 Forbidden: Neither owner nor participant
+/// - 404 Not Found: Notebook not found
+async fn list_labels(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+) -> ApiResult<Json<NotebookLabelsResponse>> {
+    require_scope(&identity, "notebook:read", state.config())?;
+    ensure_accessible(&state, &identity, notebook_id).await?;
+    labels_response(&state, &identity, notebook_id).await
+}
+
+/// POST /notebooks/{notebook_id}/labels
+///
+/// Applies a label to a notebook for the caller. Labels are lowercased;
+/// applying one twice is idempotent. Locked notebooks can be labeled, since
+/// labels are not part of the notebook.
+///
+/// # Request Body
+///
+/// `{ "label": "project-alpha" }`
+///
+/// # Response
+///
+/// - 200 OK: `{ "notebook_id": "...", "labels": [...] }`
+/// - 400 Bad Request: Invalid label, or the notebook already has 20 of the caller's labels
+/// - 403 Forbidden: Neither owner nor participant
+/// - 404 Not Found: Notebook not found
+async fn add_label(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+    Json(request): Json<AddLabelRequest>,
+) -> ApiResult<Json<NotebookLabelsResponse>> {
+    require_scope(&identity, "notebook:read", state.config())?;
+    let label = normalize_label(&request.label)?;
+    ensure_accessible(&state, &identity, notebook_id).await?;
+
+    let store = state.store();
+    let author_bytes = identity.author_id.as_bytes();
+    let existing = store.notebook_labels(notebook_id, author_bytes).await?;
+    if !existing.contains(&label) && existing.len() >= MAX_LABELS_PER_NOTEBOOK {
+        return Err(ApiError::BadRequest(format!(
+            "A notebook can carry at most {} labels",
+            MAX_LABELS_PER_NOTEBOOK
+        )));
+    }
+
+    if store
+        .add_notebook_label(notebook_id, author_bytes, &label)
+        .await?
+    {
+        tracing::info!(notebook_id = %notebook_id, label = %label, "Notebook labeled");
+    }
+
+    labels_response(&state, &identity, notebook_id).await
+}
+
+/// DELETE /notebooks/{notebook_id}/labels/{label}
+///
+/// Removes one of the caller's labels from a notebook. Removing a label that
+/// was not applied succeeds.
+///
+/// # Response
+///
+/// - 200 OK: `{ "notebook_id": "...", "labels": [...] }`
+/// - 400 Bad Request: Invalid label
+/// - 403 Forbidden: Neither owner nor participant
+/// - 404 Not Found: Notebook not found
+async fn remove_label(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path((notebook_id, label)): Path<(Uuid, String)>,
+) -> ApiResult<Json<NotebookLabelsResponse>> {
+    require_scope(&identity, "notebook:read", state.config())?;
+    let label = normalize_label(&label)?;
+    ensure_accessible(&state, &identity, notebook_id).await?;
+
+    if state
+        .store()
+        .remove_notebook_label(notebook_id, identity.author_id.as_bytes(), &label)
+        .await?
+    {
+        tracing::info!(notebook_id = %notebook_id, label = %label, "Notebook label removed");
+    }
+
+    labels_response(&state, &identity, notebook_id).await
+}
+
+/// Build notebook label routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/notebooks/{id}/labels", get(list_labels).post(add_label))
+        .route("/notebooks/{id}/labels/{label}", delete(remove_label))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_label() {
+        assert_eq!(normalize_label(" Project-Alpha ").unwrap(), "project-alpha");
+        assert_eq!(normalize_label("team:infra.v2").unwrap(), "team:infra.v2");
+        assert_eq!(
+            normalize_label(&"a".repeat(MAX_LABEL_LENGTH))
+                .unwrap()
+                .len(),
+            MAX_LABEL_LENGTH
+        );
+
+        for invalid in ["", "   ", "two words", "a/b", "café"] {
+            assert!(
+                matches!(normalize_label(invalid), Err(ApiError::BadRequest(_))),
+                "{:?}",
+                invalid
+            );
+        }
+        assert!(normalize_label(&"a".repeat(MAX_LABEL_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_labels_by_notebook_groups_rows() {
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let row = |notebook_id, label: &str| NotebookLabelRow {
+            notebook_id,
+            label: label.to_string(),
+        };
+        let labels = labels_by_notebook(vec![
+            row(first, "archived"),
+            row(first, "project-alpha"),
+            row(second, "project-alpha"),
+        ]);
+
+        assert_eq!(labels[&first], vec!["archived", "project-alpha"]);
+        assert_eq!(labels[&second], vec!["project-alpha"]);
+    }
+}
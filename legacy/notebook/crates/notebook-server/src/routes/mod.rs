@@ -1,13 +1,34 @@
 //! Route definitions for the HTTP API.
 
+pub mod admin;
+pub mod alerts;
 pub mod authors;
 pub mod browse;
+pub mod capabilities;
+pub mod clusters;
+pub mod coherence;
+pub mod content_policy;
+pub mod cost_distribution;
+pub mod delete_impact;
 pub mod entries;
 pub mod events;
+pub mod explain;
+pub mod feed;
+pub mod graph;
 pub mod health;
+pub mod keywords;
+pub mod labels;
 pub mod notebooks;
 pub mod observe;
+pub mod orphans;
+pub mod pins;
 pub mod share;
+pub mod similar;
+pub mod suggest;
+pub mod topics;
+pub mod version;
+pub mod write_budget;
+pub mod ws;
 
 use axum::Router;
 
@@ -17,12 +38,33 @@ use crate::state::AppState;
 pub fn build_router(state: AppState) -> Router {
     Router::new()
         .merge(health::routes())
+        .merge(capabilities::routes())
+        .merge(version::routes())
+        .merge(admin::routes())
         .merge(authors::routes())
+        .merge(alerts::routes())
+        .merge(content_policy::routes())
+        .merge(write_budget::routes())
         .merge(entries::routes())
         .merge(notebooks::routes())
+        .merge(labels::routes())
         .merge(observe::routes())
+        .merge(orphans::routes())
         .merge(share::routes())
+        .merge(suggest::routes())
+        .merge(topics::routes())
+        .merge(keywords::routes())
+        .merge(explain::routes())
+        .merge(cost_distribution::routes())
+        .merge(delete_impact::routes())
+        .merge(pins::routes())
+        .merge(graph::routes())
         .merge(events::routes())
+        .merge(ws::routes())
         .merge(browse::routes())
+        .merge(clusters::routes())
+        .merge(coherence::routes())
+        .merge(feed::routes())
+        .merge(similar::routes())
         .with_state(state)
 }
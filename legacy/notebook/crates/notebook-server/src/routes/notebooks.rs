@@ -1,29 +1,40 @@
 //! Notebook discovery and management routes for the Knowledge Exchange Platform.
 //!
 //! This module implements the notebook-related HTTP endpoints:
-//! - GET /notebooks - List accessible notebooks with stats
+//! - GET /notebooks - List accessible notebooks with stats and the caller's permissions
 //! - POST /notebooks - Create a new notebook
+//! - PATCH /notebooks/{id} - Rename a notebook (owner or write access)
 //! - DELETE /notebooks/{id} - Delete a notebook (owner only)
+//! - POST /notebooks/{id}/lock - Make a notebook read-only (owner only)
+//! - POST /notebooks/{id}/unlock - Make a notebook writable again (owner only)
+//! - POST /notebooks/{id}/visibility - Make a notebook public or private (owner only)
+//! - POST /notebooks/{id}/orphan-policy - Set how orphan entries are handled (owner only)
 //!
 //! Owned by: agent-discovery
 
 use axum::{
     Json, Router,
-    extract::{Path, State},
-    http::StatusCode,
-    routing::{delete, get},
+    extract::{OriginalUri, Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    routing::{delete, get, post},
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use notebook_core::Permissions;
-use notebook_store::{NewNotebook, Store, StoreError};
+use notebook_store::{NewNotebook, NotebookRow, Store, StoreError};
 
 use crate::error::{ApiError, ApiResult};
 use crate::extract::{AuthorIdentity, require_scope};
+use crate::orphan_policy::OrphanPolicy;
+use crate::pagination::{OFFSET_LIMIT, paginate, pagination_headers};
+use crate::routes::labels::{labels_by_notebook, normalize_label};
 use crate::state::AppState;
 
+/// Maximum notebook name length in characters.
+pub const MAX_NOTEBOOK_NAME_LENGTH: usize = 200;
+
 // ============================================================================
 // Request/Response Types
 // ============================================================================
@@ -47,8 +58,38 @@ pub struct NotebookSummary {
     pub total_entropy: f64,
     /// Sequence number of the most recent entry.
     pub last_activity_sequence: i64,
+    /// Creation timestamp.
+    pub created: DateTime<Utc>,
+    /// When the most recent entry was written; the creation time if the
+    /// notebook has no entries.
+    pub last_activity: DateTime<Utc>,
     /// Number of participants with access.
     pub participant_count: i64,
+    /// Whether the notebook is locked (read-only).
+    pub is_locked: bool,
+    /// Whether entry content is encrypted at rest.
+    pub encrypted: bool,
+    /// Whether the notebook can be read without authentication.
+    pub is_public: bool,
+    /// How writes of orphan entries are handled.
+    pub orphan_policy: OrphanPolicy,
+    /// The current user's labels on the notebook, sorted.
+    pub labels: Vec<String>,
+    /// The current user's role, with `?include=permissions`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<NotebookRole>,
+}
+
+/// A user's role in a notebook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotebookRole {
+    /// Owns the notebook; may read, write and administer it.
+    Owner,
+    /// Participant with write access.
+    Writer,
+    /// Participant with read access only.
+    Reader,
 }
 
 /// Permissions for a notebook.
@@ -67,6 +108,19 @@ impl From<Permissions> for NotebookPermissions {
     }
 }
 
+/// Query parameters for GET /notebooks.
+#[derive(Debug, Default, Deserialize)]
+pub struct ListNotebooksQuery {
+    /// Comma-separated extras to include: `permissions`.
+    pub include: Option<String>,
+    /// Number of notebooks to skip (default: 0).
+    pub offset: Option<usize>,
+    /// Maximum number of notebooks to return (default: all).
+    pub limit: Option<usize>,
+    /// Only list notebooks the current user gave this label.
+    pub label: Option<String>,
+}
+
 /// Response for GET /notebooks.
 #[derive(Debug, Serialize)]
 pub struct ListNotebooksResponse {
@@ -78,6 +132,9 @@ pub struct ListNotebooksResponse {
 pub struct CreateNotebookRequest {
     /// Name for the new notebook.
     pub name: String,
+    /// Encrypt entry content at rest. Defaults to false.
+    #[serde(default)]
+    pub encrypted: bool,
 }
 
 /// Response for POST /notebooks.
@@ -91,6 +148,10 @@ pub struct CreateNotebookResponse {
     pub owner: String,
     /// Creation timestamp.
     pub created: DateTime<Utc>,
+    /// When the notebook was last written to; its creation time.
+    pub last_activity: DateTime<Utc>,
+    /// Whether entry content is encrypted at rest.
+    pub encrypted: bool,
 }
 
 /// Request body for PATCH /notebooks/{id}.
@@ -109,6 +170,47 @@ pub struct RenameNotebookResponse {
     pub name: String,
 }
 
+/// Response for POST /notebooks/{id}/lock and /unlock.
+#[derive(Debug, Serialize)]
+pub struct LockNotebookResponse {
+    /// Notebook ID.
+    pub id: Uuid,
+    /// Whether the notebook is now locked.
+    pub is_locked: bool,
+}
+
+/// Request body for POST /notebooks/{id}/visibility.
+#[derive(Debug, Deserialize)]
+pub struct SetVisibilityRequest {
+    /// Whether the notebook can be read without authentication.
+    pub public: bool,
+}
+
+/// Response for POST /notebooks/{id}/visibility.
+#[derive(Debug, Serialize)]
+pub struct VisibilityResponse {
+    /// Notebook ID.
+    pub id: Uuid,
+    /// Whether the notebook is now public.
+    pub is_public: bool,
+}
+
+/// Request body for POST /notebooks/{id}/orphan-policy.
+#[derive(Debug, Deserialize)]
+pub struct SetOrphanPolicyRequest {
+    /// `allow`, `warn` or `reject`.
+    pub orphan_policy: OrphanPolicy,
+}
+
+/// Response for POST /notebooks/{id}/orphan-policy.
+#[derive(Debug, Serialize)]
+pub struct OrphanPolicyResponse {
+    /// Notebook ID.
+    pub id: Uuid,
+    /// The notebook's orphan policy.
+    pub orphan_policy: OrphanPolicy,
+}
+
 /// Response for DELETE /notebooks/{id}.
 #[derive(Debug, Serialize)]
 pub struct DeleteNotebookResponse {
@@ -122,6 +224,89 @@ pub struct DeleteNotebookResponse {
 // Helper Functions
 // ============================================================================
 
+/// Validate a notebook name and return it trimmed.
+///
+/// Names must be non-empty after trimming and at most
+/// [`MAX_NOTEBOOK_NAME_LENGTH`] characters long.
+fn validate_notebook_name(name: &str) -> ApiResult<&str> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Notebook name cannot be empty".to_string(),
+        ));
+    }
+    if name.chars().count() > MAX_NOTEBOOK_NAME_LENGTH {
+        return Err(ApiError::BadRequest(format!(
+            "Notebook name cannot exceed {} characters",
+            MAX_NOTEBOOK_NAME_LENGTH
+        )));
+    }
+    Ok(name)
+}
+
+/// Map a store error from creating or renaming a notebook.
+///
+/// A clash with another of the owner's notebook names becomes a 409 naming
+/// that notebook, so the client can reuse it; anything else is logged with
+/// `context` and reported as a storage error.
+fn name_conflict_or(e: StoreError, context: &str) -> ApiError {
+    match e {
+        StoreError::DuplicateNotebookName { name, existing_id } => {
+            ApiError::DuplicateNotebookName { name, existing_id }
+        }
+        other => {
+            tracing::error!(error = %other, "{}", context);
+            ApiError::Store(other)
+        }
+    }
+}
+
+/// Whether an `include` parameter asks for the caller's role.
+fn includes_permissions(include: Option<&str>) -> ApiResult<bool> {
+    let mut permissions = false;
+    for name in include.unwrap_or_default().split(',').map(str::trim) {
+        match name {
+            "" => {}
+            "permissions" => permissions = true,
+            other => {
+                return Err(ApiError::BadRequest(format!(
+                    "Unknown include '{}'; expected permissions",
+                    other
+                )));
+            }
+        }
+    }
+    Ok(permissions)
+}
+
+/// Whether a notebook with `labels` passes the list's label filter.
+fn has_label(labels: Option<&Vec<String>>, filter: Option<&str>) -> bool {
+    match filter {
+        Some(filter) => labels.is_some_and(|labels| labels.iter().any(|label| label == filter)),
+        None => true,
+    }
+}
+
+/// A member's effective permissions and role.
+///
+/// Owners may read and write whether or not they hold an access grant;
+/// other members have the flags of their grant.
+fn member_access(is_owner: bool, read: bool, write: bool) -> (NotebookPermissions, NotebookRole) {
+    if is_owner {
+        let permissions = NotebookPermissions {
+            read: true,
+            write: true,
+        };
+        return (permissions, NotebookRole::Owner);
+    }
+    let role = if write {
+        NotebookRole::Writer
+    } else {
+        NotebookRole::Reader
+    };
+    (NotebookPermissions { read, write }, role)
+}
+
 /// Convert a 32-byte author ID to hex string.
 fn author_id_to_hex(id: &[u8]) -> String {
     id.iter().map(|b| format!("{:02x}", b)).collect()
@@ -196,17 +381,35 @@ async fn get_author_permissions(
 /// GET /notebooks - List accessible notebooks with stats.
 ///
 /// Returns all notebooks the authenticated user has access to, including
-/// ownership status, permissions, and statistics.
+/// ownership status, permissions, and statistics. Owners always have read
+/// and write permission.
+///
+/// # Query Parameters
+///
+/// - `include`: `permissions` adds the caller's `role` (`owner`, `writer` or
+///   `reader`) to each notebook
+/// - `offset`: Number of notebooks to skip (default: 0)
+/// - `limit`: Maximum number of notebooks to return (default: all)
+/// - `label`: Only notebooks the caller labeled with this label
 ///
 /// # Response
 ///
-/// - 200 OK: `{ "notebooks": [...] }`
+/// - 200 OK: `{ "notebooks": [...] }`, with `X-Total-Count` and `Link`
+///   pagination headers
+/// - 400 Bad Request: Unknown `include` value, invalid `label`, or `limit` is zero
 /// - 401 Unauthorized: No authentication (future)
 async fn list_notebooks(
     State(state): State<AppState>,
     identity: AuthorIdentity,
-) -> ApiResult<Json<ListNotebooksResponse>> {
+    OriginalUri(uri): OriginalUri,
+    Query(query): Query<ListNotebooksQuery>,
+) -> ApiResult<(HeaderMap, Json<ListNotebooksResponse>)> {
     require_scope(&identity, "notebook:read", state.config())?;
+    let include_role = includes_permissions(query.include.as_deref())?;
+    if query.limit == Some(0) {
+        return Err(ApiError::BadRequest("limit must be at least 1".to_string()));
+    }
+    let label = query.label.as_deref().map(normalize_label).transpose()?;
     let author_id = identity.author_id;
     let store = state.store();
 
@@ -214,6 +417,11 @@ async fn list_notebooks(
 
     // List notebooks accessible to this author
     let notebook_rows = store.list_notebooks_for_author(&author_bytes).await?;
+    let mut labels = labels_by_notebook(store.author_notebook_labels(&author_bytes).await?);
+    let notebook_rows: Vec<NotebookRow> = notebook_rows
+        .into_iter()
+        .filter(|row| has_label(labels.get(&row.id), label.as_deref()))
+        .collect();
 
     let mut notebooks = Vec::with_capacity(notebook_rows.len());
 
@@ -224,6 +432,13 @@ async fn list_notebooks(
                 .await
                 .unwrap_or((0.0, 0, 0));
 
+        let last_activity = store
+            .notebook_last_activity(row.id)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or(row.created);
+
         // Get participant count
         let participant_count = get_participant_count(store, row.id).await.unwrap_or(0);
 
@@ -235,26 +450,43 @@ async fn list_notebooks(
         // Check if this author is the owner
         let owner_bytes: [u8; 32] = row.owner_id.as_slice().try_into().unwrap_or([0u8; 32]);
         let is_owner = owner_bytes == author_bytes;
+        let (permissions, role) = member_access(is_owner, read, write);
 
+        let orphan_policy = OrphanPolicy::of(&row);
         notebooks.push(NotebookSummary {
             id: row.id,
             name: row.name,
             owner: author_id_to_hex(&row.owner_id),
             is_owner,
-            permissions: NotebookPermissions { read, write },
+            permissions,
             total_entries,
             total_entropy,
             last_activity_sequence,
+            created: row.created,
+            last_activity,
             participant_count,
+            is_locked: row.is_locked,
+            encrypted: row.encrypted,
+            is_public: row.is_public,
+            orphan_policy,
+            labels: labels.remove(&row.id).unwrap_or_default(),
+            role: include_role.then_some(role),
         });
     }
 
     // Sort by last_activity_sequence descending (most recent first)
-    notebooks.sort_by(|a, b| b.last_activity_sequence.cmp(&a.last_activity_sequence));
+    notebooks.sort_by_key(|n| std::cmp::Reverse(n.last_activity_sequence));
+    let (notebooks, window) = paginate(notebooks, query.offset.unwrap_or(0), query.limit);
 
-    tracing::info!(count = notebooks.len(), "Listed notebooks for author");
+    tracing::info!(
+        count = notebooks.len(),
+        total = window.total,
+        label = label.as_deref(),
+        "Listed notebooks for author"
+    );
 
-    Ok(Json(ListNotebooksResponse { notebooks }))
+    let headers = pagination_headers(&uri, OFFSET_LIMIT, &window);
+    Ok((headers, Json(ListNotebooksResponse { notebooks })))
 }
 
 /// POST /notebooks - Create a new notebook.
@@ -263,12 +495,18 @@ async fn list_notebooks(
 ///
 /// # Request
 ///
-/// Body: `{ "name": "My Notebook" }`
+/// Body: `{ "name": "My Notebook", "encrypted": false }`
+///
+/// With `encrypted: true`, entry content is encrypted at rest with a
+/// notebook key wrapped by the server master key.
 ///
 /// # Response
 ///
-/// - 201 Created: `{ "id": "...", "name": "...", "owner": "...", "created": "..." }`
-/// - 400 Bad Request: Invalid request body
+/// - 201 Created: `{ "id": "...", "name": "...", "owner": "...", "created": "...", "last_activity": "...", "encrypted": false }`
+/// - 400 Bad Request: Invalid request body, or encryption requested but no
+///   master key is configured
+/// - 409 Conflict: The caller already owns a notebook with this name; the
+///   error's `existing_notebook_id` names it
 /// - 401 Unauthorized: No authentication (future)
 async fn create_notebook(
     State(state): State<AppState>,
@@ -281,19 +519,21 @@ async fn create_notebook(
 
     let author_bytes = *author_id.as_bytes();
 
-    // Validate name is not empty
-    if request.name.trim().is_empty() {
+    let name = validate_notebook_name(&request.name)?;
+
+    if request.encrypted && !store.encryption_available() {
         return Err(ApiError::BadRequest(
-            "Notebook name cannot be empty".to_string(),
+            "Encrypted notebooks are not available on this server".to_string(),
         ));
     }
 
     // Create the notebook
-    let new_notebook = NewNotebook::new(request.name.clone(), author_bytes);
-    let notebook_row = store.insert_notebook(&new_notebook).await.map_err(|e| {
-        tracing::error!(error = %e, "Failed to create notebook");
-        ApiError::Store(e)
-    })?;
+    let new_notebook =
+        NewNotebook::new(name.to_string(), author_bytes).encrypted(request.encrypted);
+    let notebook_row = store
+        .insert_notebook(&new_notebook)
+        .await
+        .map_err(|e| name_conflict_or(e, "Failed to create notebook"))?;
 
     tracing::info!(
         notebook_id = %notebook_row.id,
@@ -308,13 +548,16 @@ async fn create_notebook(
             name: notebook_row.name,
             owner: author_id_to_hex(&notebook_row.owner_id),
             created: notebook_row.created,
+            last_activity: notebook_row.created,
+            encrypted: notebook_row.encrypted,
         }),
     ))
 }
 
 /// PATCH /notebooks/{id} - Rename a notebook.
 ///
-/// Renames a notebook. Only the owner can rename a notebook.
+/// Renames a notebook. The owner and authors with write access may rename
+/// it. Subscribers are notified with a `notebook_renamed` event.
 ///
 /// # Request
 ///
@@ -323,33 +566,28 @@ async fn create_notebook(
 /// # Response
 ///
 /// - 200 OK: `{ "id": "...", "name": "..." }`
-/// - 400 Bad Request: Empty name
-/// - 403 Forbidden: Not the owner
+/// - 400 Bad Request: Empty name or name too long
+/// - 403 Forbidden: Neither owner nor write access
 /// - 404 Not Found: Notebook doesn't exist
+/// - 409 Conflict: The owner already has another notebook with this name;
+///   the error's `existing_notebook_id` names it
 async fn rename_notebook(
     State(state): State<AppState>,
     identity: AuthorIdentity,
     Path(notebook_id): Path<Uuid>,
     Json(request): Json<RenameNotebookRequest>,
 ) -> ApiResult<Json<RenameNotebookResponse>> {
-    require_scope(&identity, "notebook:admin", state.config())?;
+    require_scope(&identity, "notebook:write", state.config())?;
     let author_id = identity.author_id;
     let store = state.store();
 
     let author_bytes = *author_id.as_bytes();
 
-    // Validate name is not empty
-    if request.name.trim().is_empty() {
-        return Err(ApiError::BadRequest(
-            "Notebook name cannot be empty".to_string(),
-        ));
-    }
+    let name = validate_notebook_name(&request.name)?;
 
     // Get the notebook to check ownership
     let notebook_row = store.get_notebook(notebook_id).await.map_err(|e| match e {
-        StoreError::NotebookNotFound(id) => {
-            ApiError::NotFound(format!("Notebook {} not found", id))
-        }
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
         other => ApiError::Store(other),
     })?;
 
@@ -360,20 +598,17 @@ async fn rename_notebook(
         .try_into()
         .map_err(|_| ApiError::Internal("Invalid owner_id in database".to_string()))?;
 
-    if owner_bytes != author_bytes {
+    if owner_bytes != author_bytes && !store.has_write_access(notebook_id, &author_bytes).await? {
         return Err(ApiError::Forbidden(
-            "Only the notebook owner can rename it".to_string(),
+            "Renaming requires ownership or write access".to_string(),
         ));
     }
 
     // Rename the notebook
     let updated = store
-        .rename_notebook(notebook_id, request.name.trim())
+        .rename_notebook(notebook_id, name)
         .await
-        .map_err(|e| {
-            tracing::error!(error = %e, "Failed to rename notebook");
-            ApiError::Store(e)
-        })?;
+        .map_err(|e| name_conflict_or(e, "Failed to rename notebook"))?;
 
     tracing::info!(
         notebook_id = %notebook_id,
@@ -381,12 +616,219 @@ async fn rename_notebook(
         "Notebook renamed"
     );
 
+    state
+        .broadcaster()
+        .publish_from_log(store, notebook_id)
+        .await;
+
     Ok(Json(RenameNotebookResponse {
         id: updated.id,
         name: updated.name,
     }))
 }
 
+/// POST /notebooks/{id}/lock - Make a notebook read-only.
+///
+/// While locked, new entries and revisions are rejected with 409 Conflict.
+/// Reads, browse, and search remain available.
+///
+/// # Response
+///
+/// - 200 OK: `{ "id": "...", "is_locked": true }`
+/// - 403 Forbidden: Not the owner
+/// - 404 Not Found: Notebook doesn't exist
+async fn lock_notebook(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+) -> ApiResult<Json<LockNotebookResponse>> {
+    set_notebook_locked(&state, &identity, notebook_id, true).await
+}
+
+/// POST /notebooks/{id}/unlock - Make a locked notebook writable again.
+///
+/// # Response
+///
+/// - 200 OK: `{ "id": "...", "is_locked": false }`
+/// - 403 Forbidden: Not the owner
+/// - 404 Not Found: Notebook doesn't exist
+async fn unlock_notebook(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+) -> ApiResult<Json<LockNotebookResponse>> {
+    set_notebook_locked(&state, &identity, notebook_id, false).await
+}
+
+/// Shared implementation of lock and unlock. Only the owner may change the lock.
+async fn set_notebook_locked(
+    state: &AppState,
+    identity: &AuthorIdentity,
+    notebook_id: Uuid,
+    locked: bool,
+) -> ApiResult<Json<LockNotebookResponse>> {
+    require_scope(identity, "notebook:admin", state.config())?;
+    let store = state.store();
+
+    let author_bytes = *identity.author_id.as_bytes();
+
+    // Get the notebook to check ownership
+    let notebook_row = store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+
+    if notebook_row.owner_id.as_slice() != author_bytes.as_slice() {
+        return Err(ApiError::Forbidden(
+            "Only the notebook owner can lock or unlock it".to_string(),
+        ));
+    }
+
+    let updated = store
+        .set_notebook_locked(notebook_id, locked)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to update notebook lock");
+            ApiError::Store(e)
+        })?;
+
+    tracing::info!(
+        notebook_id = %notebook_id,
+        is_locked = updated.is_locked,
+        "Notebook lock changed"
+    );
+
+    state
+        .broadcaster()
+        .publish_from_log(store, notebook_id)
+        .await;
+
+    Ok(Json(LockNotebookResponse {
+        id: updated.id,
+        is_locked: updated.is_locked,
+    }))
+}
+
+/// POST /notebooks/{id}/visibility - Make a notebook public or private.
+///
+/// A public notebook's entries can be read and browsed without
+/// authentication; writes always require it.
+///
+/// # Request
+///
+/// Body: `{ "public": true }`
+///
+/// # Response
+///
+/// - 200 OK: `{ "id": "...", "is_public": true }`
+/// - 403 Forbidden: Not the owner
+/// - 404 Not Found: Notebook doesn't exist
+async fn set_visibility(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+    Json(request): Json<SetVisibilityRequest>,
+) -> ApiResult<Json<VisibilityResponse>> {
+    require_scope(&identity, "notebook:admin", state.config())?;
+    let store = state.store();
+
+    let notebook_row = store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+
+    if notebook_row.owner_id.as_slice() != identity.author_id.as_bytes().as_slice() {
+        return Err(ApiError::Forbidden(
+            "Only the notebook owner can change its visibility".to_string(),
+        ));
+    }
+
+    let updated = store
+        .set_notebook_public(notebook_id, request.public)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to update notebook visibility");
+            ApiError::Store(e)
+        })?;
+
+    tracing::info!(
+        notebook_id = %notebook_id,
+        is_public = updated.is_public,
+        "Notebook visibility changed"
+    );
+
+    state
+        .broadcaster()
+        .publish_from_log(store, notebook_id)
+        .await;
+
+    Ok(Json(VisibilityResponse {
+        id: updated.id,
+        is_public: updated.is_public,
+    }))
+}
+
+/// POST /notebooks/{id}/orphan-policy - Set how orphan entries are handled.
+///
+/// Under `allow` orphans are stored as before, under `warn` they are stored
+/// with an `X-Orphan-Warning` header on the write response, and under
+/// `reject` writes of orphans fail with 422; see [`crate::orphan_policy`].
+///
+/// # Request
+///
+/// Body: `{ "orphan_policy": "reject" }`
+///
+/// # Response
+///
+/// - 200 OK: `{ "id": "...", "orphan_policy": "reject" }`
+/// - 400 Bad Request: Unknown policy
+/// - 403 Forbidden: Not the owner
+/// - 404 Not Found: Notebook doesn't exist
+async fn set_orphan_policy(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+    Json(request): Json<SetOrphanPolicyRequest>,
+) -> ApiResult<Json<OrphanPolicyResponse>> {
+    require_scope(&identity, "notebook:admin", state.config())?;
+    let store = state.store();
+
+    let notebook_row = store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+
+    if notebook_row.owner_id.as_slice() != identity.author_id.as_bytes().as_slice() {
+        return Err(ApiError::Forbidden(
+            "Only the notebook owner can change its orphan policy".to_string(),
+        ));
+    }
+
+    let updated = store
+        .set_notebook_orphan_policy(notebook_id, request.orphan_policy.as_str())
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to update notebook orphan policy");
+            ApiError::Store(e)
+        })?;
+
+    tracing::info!(
+        notebook_id = %notebook_id,
+        orphan_policy = %updated.orphan_policy,
+        "Notebook orphan policy changed"
+    );
+
+    state
+        .broadcaster()
+        .publish_from_log(store, notebook_id)
+        .await;
+
+    Ok(Json(OrphanPolicyResponse {
+        id: updated.id,
+        orphan_policy: OrphanPolicy::of(&updated),
+    }))
+}
+
 /// DELETE /notebooks/{id} - Delete a notebook.
 ///
 /// Deletes a notebook. Only the owner can delete a notebook.
@@ -411,9 +853,7 @@ async fn delete_notebook(
 
     // Get the notebook to check ownership
     let notebook_row = store.get_notebook(notebook_id).await.map_err(|e| match e {
-        StoreError::NotebookNotFound(id) => {
-            ApiError::NotFound(format!("Notebook {} not found", id))
-        }
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
         other => ApiError::Store(other),
     })?;
 
@@ -479,6 +919,10 @@ pub fn routes() -> Router<AppState> {
             "/notebooks/{id}",
             delete(delete_notebook).patch(rename_notebook),
         )
+        .route("/notebooks/{id}/lock", post(lock_notebook))
+        .route("/notebooks/{id}/unlock", post(unlock_notebook))
+        .route("/notebooks/{id}/visibility", post(set_visibility))
+        .route("/notebooks/{id}/orphan-policy", post(set_orphan_policy))
 }
 
 // ============================================================================
@@ -510,6 +954,64 @@ mod tests {
         let json = r#"{"name": "My Notebook"}"#;
         let request: CreateNotebookRequest = serde_json::from_str(json).unwrap();
         assert_eq!(request.name, "My Notebook");
+        assert!(!request.encrypted);
+
+        let json = r#"{"name": "Private", "encrypted": true}"#;
+        let request: CreateNotebookRequest = serde_json::from_str(json).unwrap();
+        assert!(request.encrypted);
+    }
+
+    #[test]
+    fn test_lock_notebook_response_serialize() {
+        let response = LockNotebookResponse {
+            id: Uuid::nil(),
+            is_locked: true,
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["is_locked"], true);
+    }
+
+    #[test]
+    fn test_visibility_request_and_response() {
+        let request: SetVisibilityRequest = serde_json::from_str(r#"{"public": true}"#).unwrap();
+        assert!(request.public);
+        assert!(serde_json::from_str::<SetVisibilityRequest>("{}").is_err());
+
+        let response = VisibilityResponse {
+            id: Uuid::nil(),
+            is_public: true,
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["is_public"], true);
+    }
+
+    #[test]
+    fn test_orphan_policy_request_and_response() {
+        let request: SetOrphanPolicyRequest =
+            serde_json::from_str(r#"{"orphan_policy": "reject"}"#).unwrap();
+        assert_eq!(request.orphan_policy, OrphanPolicy::Reject);
+        assert!(
+            serde_json::from_str::<SetOrphanPolicyRequest>(r#"{"orphan_policy": "never"}"#)
+                .is_err()
+        );
+
+        let response = OrphanPolicyResponse {
+            id: Uuid::nil(),
+            orphan_policy: OrphanPolicy::Warn,
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["orphan_policy"], "warn");
+    }
+
+    #[test]
+    fn test_label_filter_keeps_only_labeled_notebooks() {
+        let labeled = vec!["archived".to_string(), "project-alpha".to_string()];
+
+        assert!(has_label(Some(&labeled), Some("project-alpha")));
+        assert!(!has_label(Some(&labeled), Some("project-beta")));
+        assert!(!has_label(None, Some("project-alpha")));
+        assert!(has_label(None, None));
+        assert!(has_label(Some(&labeled), None));
     }
 
     #[test]
@@ -526,14 +1028,58 @@ mod tests {
             total_entries: 10,
             total_entropy: 5.5,
             last_activity_sequence: 100,
+            created: "2026-01-02T03:04:05Z".parse().unwrap(),
+            last_activity: "2026-01-03T00:00:00Z".parse().unwrap(),
             participant_count: 3,
+            is_locked: false,
+            encrypted: true,
+            is_public: false,
+            orphan_policy: OrphanPolicy::Warn,
+            labels: vec!["archived".to_string()],
+            role: None,
         };
         let json = serde_json::to_string(&summary).unwrap();
         assert!(json.contains("Test Notebook"));
         assert!(json.contains("total_entries"));
         assert!(json.contains("total_entropy"));
         assert!(json.contains("last_activity_sequence"));
+        assert!(json.contains(r#""created":"2026-01-02T03:04:05Z""#));
+        assert!(json.contains(r#""last_activity":"2026-01-03T00:00:00Z""#));
         assert!(json.contains("participant_count"));
+        assert!(json.contains(r#""encrypted":true"#));
+        assert!(json.contains(r#""orphan_policy":"warn""#));
+        assert!(json.contains(r#""labels":["archived"]"#));
+        assert!(!json.contains("role"));
+    }
+
+    #[test]
+    fn test_owner_has_full_access_without_grant() {
+        let (permissions, role) = member_access(true, false, false);
+        assert!(permissions.read);
+        assert!(permissions.write);
+        assert_eq!(role, NotebookRole::Owner);
+    }
+
+    #[test]
+    fn test_participant_access_follows_grant() {
+        let (permissions, role) = member_access(false, true, false);
+        assert!(permissions.read);
+        assert!(!permissions.write);
+        assert_eq!(role, NotebookRole::Reader);
+
+        let (permissions, role) = member_access(false, true, true);
+        assert!(permissions.write);
+        assert_eq!(role, NotebookRole::Writer);
+        assert_eq!(serde_json::to_value(role).unwrap(), "writer");
+    }
+
+    #[test]
+    fn test_includes_permissions() {
+        assert!(!includes_permissions(None).unwrap());
+        assert!(!includes_permissions(Some("")).unwrap());
+        assert!(includes_permissions(Some("permissions")).unwrap());
+        assert!(includes_permissions(Some(" permissions ,")).unwrap());
+        assert!(includes_permissions(Some("stats")).is_err());
     }
 
     #[test]
@@ -547,6 +1093,55 @@ mod tests {
         assert!(!api_perms.write);
     }
 
+    #[test]
+    fn test_validate_notebook_name_trims() {
+        assert_eq!(validate_notebook_name("  Research  ").unwrap(), "Research");
+    }
+
+    #[test]
+    fn test_validate_notebook_name_empty_is_bad_request() {
+        let err = validate_notebook_name("   ").unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_validate_notebook_name_length_limit() {
+        let max = "n".repeat(MAX_NOTEBOOK_NAME_LENGTH);
+        assert!(validate_notebook_name(&max).is_ok());
+
+        let err = validate_notebook_name(&format!("{}n", max)).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_name_conflict_is_409_naming_existing_notebook() {
+        let existing_id = Uuid::new_v4();
+        let err = name_conflict_or(
+            StoreError::DuplicateNotebookName {
+                name: "Research".to_string(),
+                existing_id,
+            },
+            "Failed to create notebook",
+        );
+        assert_eq!(err.status_code(), StatusCode::CONFLICT);
+        assert_eq!(err.existing_notebook_id(), Some(existing_id));
+        assert!(err.to_string().contains(&existing_id.to_string()));
+
+        let err = name_conflict_or(
+            StoreError::NotebookNotFound(existing_id),
+            "Failed to rename notebook",
+        );
+        assert_eq!(err.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(err.existing_notebook_id(), None);
+    }
+
+    #[test]
+    fn test_rename_request_deserialize() {
+        let json = r#"{"name": "Renamed"}"#;
+        let request: RenameNotebookRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.name, "Renamed");
+    }
+
     #[test]
     fn test_delete_response_serialize() {
         let response = DeleteNotebookResponse {
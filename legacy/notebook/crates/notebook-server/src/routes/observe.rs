@@ -4,7 +4,10 @@
 //! changed in a notebook since they last looked. Returns changes with their
 //! integration costs and aggregate entropy for the observed period.
 //!
-//! Endpoint: GET /notebooks/{notebook_id}/observe?since={sequence}
+//! Non-entry changes (renames, locks, sharing) are read from the notebook's
+//! change log when `since_event` is given.
+//!
+//! Endpoint: GET /notebooks/{notebook_id}/observe?since={sequence}&since_event={seq}
 //!
 //! Owned by: agent-observe
 
@@ -21,6 +24,7 @@ use notebook_core::IntegrationCost;
 use notebook_store::{EntryQuery, EntryRow, StoreError};
 
 use crate::error::{ApiError, ApiResult};
+use crate::events::{NotebookEvent, read_log};
 use crate::extract::{AuthorIdentity, require_scope};
 use crate::state::AppState;
 
@@ -35,6 +39,10 @@ pub struct ObserveParams {
     /// If not provided, defaults to 0 (full sync - all entries).
     #[serde(default)]
     pub since: Option<u64>,
+    /// Change log `seq` to return logged events since (exclusive).
+    /// If not provided, no logged events are returned.
+    #[serde(default)]
+    pub since_event: Option<u64>,
 }
 
 /// Response for the OBSERVE endpoint.
@@ -46,6 +54,13 @@ pub struct ObserveResponse {
     pub notebook_entropy: f64,
     /// Current sequence number (highest sequence in the notebook).
     pub current_sequence: u64,
+    /// Change log events since `since_event`, oldest first. Omitted unless
+    /// `since_event` was given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<NotebookEvent>>,
+    /// `seq` of the newest change log event; pass it as `since_event` (or
+    /// as `Last-Event-ID` to the events stream) to continue from here.
+    pub current_event_seq: u64,
 }
 
 /// A single change entry in the observe response.
@@ -125,10 +140,12 @@ fn entry_row_to_change(row: &EntryRow) -> ChangeEntry {
 /// # Query Parameters
 ///
 /// - `since`: Optional sequence number (exclusive). Defaults to 0 for full sync.
+/// - `since_event`: Optional change log `seq` (exclusive). When given, the
+///   response includes every logged event after it.
 ///
 /// # Response
 ///
-/// - 200 OK: `{ "changes": [...], "notebook_entropy": 15.5, "current_sequence": 150 }`
+/// - 200 OK: `{ "changes": [...], "notebook_entropy": 15.5, "current_sequence": 150, "current_event_seq": 212 }`
 /// - 404 Not Found: Notebook not found
 /// - 500 Internal Server Error: Database error
 ///
@@ -147,9 +164,7 @@ async fn observe_changes(
 
     // Validate notebook exists
     store.get_notebook(notebook_id).await.map_err(|e| match e {
-        StoreError::NotebookNotFound(id) => {
-            ApiError::NotFound(format!("Notebook {} not found", id))
-        }
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
         other => ApiError::Store(other),
     })?;
 
@@ -194,12 +209,21 @@ async fn observe_changes(
         max_sequence
     };
 
+    let (events, current_event_seq) = match params.since_event {
+        Some(since_event) => {
+            let (events, last) = read_log(store, notebook_id, since_event).await?;
+            (Some(events), last)
+        }
+        None => (None, store.latest_event_seq(notebook_id).await? as u64),
+    };
+
     tracing::debug!(
         notebook_id = %notebook_id,
         since = since_sequence,
         changes_count = changes.len(),
         notebook_entropy = notebook_entropy,
         current_sequence = current_sequence,
+        current_event_seq = current_event_seq,
         "OBSERVE completed"
     );
 
@@ -207,6 +231,8 @@ async fn observe_changes(
         changes,
         notebook_entropy,
         current_sequence,
+        events,
+        current_event_seq,
     }))
 }
 
@@ -227,6 +253,14 @@ mod tests {
     fn test_observe_params_default() {
         let params: ObserveParams = serde_urlencoded::from_str("").unwrap();
         assert!(params.since.is_none());
+        assert!(params.since_event.is_none());
+    }
+
+    #[test]
+    fn test_observe_params_with_since_event() {
+        let params: ObserveParams = serde_urlencoded::from_str("since=3&since_event=7").unwrap();
+        assert_eq!(params.since, Some(3));
+        assert_eq!(params.since_event, Some(7));
     }
 
     #[test]
@@ -265,11 +299,15 @@ mod tests {
             changes: vec![],
             notebook_entropy: 0.0,
             current_sequence: 0,
+            events: None,
+            current_event_seq: 0,
         };
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("changes"));
         assert!(json.contains("notebook_entropy"));
         assert!(json.contains("current_sequence"));
+        assert!(json.contains("current_event_seq"));
+        assert!(!json.contains("\"events\""));
     }
 
     #[test]
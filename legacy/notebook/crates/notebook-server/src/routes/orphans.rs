@@ -0,0 +1,213 @@
+//! Orphan entry listing.
+//!
+//! Lists entries the entropy engine flagged as orphans at write time: entries
+//! that matched no existing cluster and referenced nothing. Agents use this to
+//! find disconnected knowledge that needs linking.
+//!
+//! Endpoint: GET /notebooks/{notebook_id}/orphans?limit={n}&offset={n}
+
+use axum::{
+    Json, Router,
+    extract::{OriginalUri, Path, Query, State},
+    http::HeaderMap,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use notebook_core::NotebookId;
+use notebook_store::{EntryRow, OrphanEntriesQuery, StoreError};
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, require_scope};
+use crate::pagination::{OFFSET_LIMIT, PageWindow, pagination_headers};
+use crate::state::AppState;
+
+/// Number of orphans returned when no limit is given.
+pub const DEFAULT_ORPHANS_LIMIT: u32 = 50;
+
+/// Maximum number of orphans returned in one response.
+pub const MAX_ORPHANS_LIMIT: u32 = 500;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Query parameters for the orphans endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct OrphansParams {
+    /// Maximum number of orphans to return.
+    #[serde(default)]
+    pub limit: Option<u32>,
+
+    /// Number of orphans to skip (default: 0).
+    #[serde(default)]
+    pub offset: Option<u32>,
+}
+
+/// Response for GET /notebooks/{id}/orphans.
+#[derive(Debug, Serialize)]
+pub struct OrphansResponse {
+    /// Orphan entries, oldest first.
+    pub orphans: Vec<OrphanEntry>,
+}
+
+/// A single orphan entry.
+#[derive(Debug, Serialize)]
+pub struct OrphanEntry {
+    /// Entry ID.
+    pub entry_id: Uuid,
+    /// Optional topic/category.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    /// Sequence number in the notebook.
+    pub sequence: u64,
+    /// Creation timestamp.
+    pub created: DateTime<Utc>,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Resolve the effective limit, applying the default and the maximum.
+fn effective_limit(params: &OrphansParams) -> ApiResult<i64> {
+    match params.limit {
+        Some(0) => Err(ApiError::BadRequest("limit must be at least 1".to_string())),
+        Some(limit) => Ok(limit.min(MAX_ORPHANS_LIMIT) as i64),
+        None => Ok(DEFAULT_ORPHANS_LIMIT as i64),
+    }
+}
+
+/// Convert an EntryRow to an OrphanEntry.
+fn entry_row_to_orphan(row: &EntryRow) -> OrphanEntry {
+    OrphanEntry {
+        entry_id: row.id,
+        topic: row.topic.clone(),
+        sequence: row.sequence as u64,
+        created: row.created,
+    }
+}
+
+// ============================================================================
+// Route Handler
+// ============================================================================
+
+/// GET /notebooks/{notebook_id}/orphans - List orphan entries.
+///
+/// # Query Parameters
+///
+/// - `limit`: Maximum number of results (default 50, capped at 500).
+/// - `offset`: Number of orphans to skip (default 0).
+///
+/// # Response
+///
+/// - 200 OK: `{ "orphans": [{ "entry_id": "...", "topic": "...", "sequence": 12, "created": "..." }] }`,
+///   with `X-Total-Count` and `Link` pagination headers
+/// - 400 Bad Request: `limit` is zero
+/// - 404 Not Found: Notebook not found
+async fn list_orphans(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    OriginalUri(uri): OriginalUri,
+    Path(notebook_id): Path<Uuid>,
+    Query(params): Query<OrphansParams>,
+) -> ApiResult<(HeaderMap, Json<OrphansResponse>)> {
+    require_scope(&identity, "notebook:read", state.config())?;
+    let store = state.store();
+    let limit = effective_limit(&params)?;
+
+    // Validate notebook exists
+    store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+
+    let offset = params.offset.unwrap_or(0);
+    let query = OrphanEntriesQuery::new(NotebookId::from_uuid(notebook_id))
+        .limit(limit)
+        .offset(offset as i64);
+    let rows = query.execute(store).await?;
+    let total = query.count(store).await?;
+    let orphans: Vec<OrphanEntry> = rows.iter().map(entry_row_to_orphan).collect();
+
+    tracing::debug!(
+        notebook_id = %notebook_id,
+        orphan_count = orphans.len(),
+        "Listed orphan entries"
+    );
+
+    let window = PageWindow {
+        offset: offset as usize,
+        limit: limit as usize,
+        returned: orphans.len(),
+        total: total as usize,
+    };
+    let headers = pagination_headers(&uri, OFFSET_LIMIT, &window);
+    Ok((headers, Json(OrphansResponse { orphans })))
+}
+
+/// Build orphan routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/notebooks/{id}/orphans", get(list_orphans))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_limit_default() {
+        let params = OrphansParams::default();
+        assert_eq!(
+            effective_limit(&params).unwrap(),
+            DEFAULT_ORPHANS_LIMIT as i64
+        );
+    }
+
+    #[test]
+    fn test_effective_limit_capped() {
+        let params = OrphansParams {
+            limit: Some(10_000),
+            ..Default::default()
+        };
+        assert_eq!(effective_limit(&params).unwrap(), MAX_ORPHANS_LIMIT as i64);
+    }
+
+    #[test]
+    fn test_effective_limit_zero_rejected() {
+        let params = OrphansParams {
+            limit: Some(0),
+            ..Default::default()
+        };
+        assert!(matches!(
+            effective_limit(&params),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_orphans_params_deserialize() {
+        let params: OrphansParams = serde_json::from_str(r#"{"limit": 5}"#).unwrap();
+        assert_eq!(params.limit, Some(5));
+        assert_eq!(params.offset, None);
+    }
+
+    #[test]
+    fn test_orphan_entry_serialize() {
+        let orphan = OrphanEntry {
+            entry_id: Uuid::nil(),
+            topic: Some("stray".to_string()),
+            sequence: 7,
+            created: Utc::now(),
+        };
+        let json = serde_json::to_value(&orphan).unwrap();
+        assert_eq!(json["topic"], "stray");
+        assert_eq!(json["sequence"], 7);
+    }
+}
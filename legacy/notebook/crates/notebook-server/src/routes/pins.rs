@@ -0,0 +1,213 @@
+//! Entry pinning.
+//!
+//! Browse catalogs list only a few representative entries per cluster and
+//! only as many clusters as the token budget allows, so important entries
+//! in large notebooks can drop out. A pinned entry is always listed among
+//! its cluster's representatives, and its cluster is never truncated away.
+//!
+//! Endpoints:
+//! - POST /notebooks/{notebook_id}/entries/{entry_id}/pin
+//! - DELETE /notebooks/{notebook_id}/entries/{entry_id}/pin
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    routing::post,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use notebook_core::NotebookId;
+use notebook_store::StoreError;
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, require_scope};
+use crate::routes::entries::ensure_unlocked;
+use crate::state::AppState;
+
+// ============================================================================
+// Response Types
+// ============================================================================
+
+/// Response for pinning or unpinning an entry.
+#[derive(Debug, Serialize)]
+pub struct PinResponse {
+    pub entry_id: Uuid,
+    pub pinned: bool,
+}
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// POST /notebooks/{notebook_id}/entries/{entry_id}/pin
+///
+/// Pins an entry so catalogs always list it. Pinning is idempotent.
+///
+/// # Response
+///
+/// - 200 OK: `{ "entry_id": "...", "pinned": true }`
+/// - 403 Forbidden: Neither owner nor write access
+/// - 404 Not Found: Notebook or entry not found
+/// - 409 Conflict: Notebook is locked
+async fn pin_entry(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path((notebook_id, entry_id)): Path<(Uuid, Uuid)>,
+) -> ApiResult<Json<PinResponse>> {
+    set_pinned(&state, &identity, notebook_id, entry_id, true).await
+}
+
+/// DELETE /notebooks/{notebook_id}/entries/{entry_id}/pin
+///
+/// Unpins an entry. Unpinning an entry that is not pinned succeeds.
+///
+/// # Response
+///
+/// - 200 OK: `{ "entry_id": "...", "pinned": false }`
+/// - 403 Forbidden: Neither owner nor write access
+/// - 404 Not Found: Notebook or entry not found
+/// - 409 Conflict: Notebook is locked
+async fn unpin_entry(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path((notebook_id, entry_id)): Path<(Uuid, Uuid)>,
+) -> ApiResult<Json<PinResponse>> {
+    set_pinned(&state, &identity, notebook_id, entry_id, false).await
+}
+
+/// Shared implementation of pin and unpin.
+async fn set_pinned(
+    state: &AppState,
+    identity: &AuthorIdentity,
+    notebook_id: Uuid,
+    entry_id: Uuid,
+    pinned: bool,
+) -> ApiResult<Json<PinResponse>> {
+    require_scope(identity, "notebook:write", state.config())?;
+    let store = state.store();
+    let author_bytes = *identity.author_id.as_bytes();
+
+    let notebook = store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+    ensure_unlocked(&notebook)?;
+
+    if notebook.owner_id.as_slice() != author_bytes.as_slice()
+        && !store.has_write_access(notebook_id, &author_bytes).await?
+    {
+        return Err(ApiError::Forbidden(
+            "Pinning entries requires ownership or write access".to_string(),
+        ));
+    }
+
+    // The entry must live in this notebook
+    let row = store.get_entry(entry_id).await.map_err(|e| match e {
+        StoreError::EntryNotFound(id) => ApiError::entry_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+    if row.notebook_id != notebook_id {
+        return Err(ApiError::entry_not_found(entry_id));
+    }
+
+    if row.pinned != pinned {
+        // The store re-checks the lock in the same transaction as the update
+        store
+            .set_entry_pinned(notebook_id, entry_id, pinned)
+            .await
+            .map_err(|e| match e {
+                StoreError::EntryNotFound(id) => ApiError::entry_not_found(id),
+                other => ApiError::Store(other),
+            })?;
+
+        // The notebook's sequence does not move, so drop the cached catalog
+        state
+            .catalog_cache()
+            .invalidate(&NotebookId::from_uuid(notebook_id));
+
+        tracing::info!(
+            notebook_id = %notebook_id,
+            entry_id = %entry_id,
+            pinned,
+            "Entry pin changed"
+        );
+
+        state
+            .broadcaster()
+            .publish_from_log(store, notebook_id)
+            .await;
+    }
+
+    Ok(Json(PinResponse { entry_id, pinned }))
+}
+
+/// Build entry pinning routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route(
+        "/notebooks/{id}/entries/{entry_id}/pin",
+        post(pin_entry).delete(unpin_entry),
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notebook_core::EntryId;
+    use notebook_entropy::Locale;
+    use notebook_store::EntryRow;
+
+    use crate::config::ServerConfig;
+    use crate::routes::browse::{generate_catalog, pinned_entry_ids};
+    use crate::routes::suggest::entry_row_to_snapshot_entry;
+
+    fn make_row(notebook_id: Uuid, sequence: i64, pinned: bool) -> EntryRow {
+        EntryRow {
+            pinned,
+            ..EntryRow::test_row(notebook_id, sequence, "rust borrow checker lifetimes")
+        }
+    }
+
+    #[test]
+    fn test_pinned_row_is_listed_in_catalog() {
+        // Identical content clusters together; the pinned entry comes last
+        let notebook_id = Uuid::new_v4();
+        let mut rows: Vec<EntryRow> = (1..=8).map(|i| make_row(notebook_id, i, false)).collect();
+        rows.push(make_row(notebook_id, 9, true));
+        let pinned_id = EntryId::from_uuid(rows[8].id);
+
+        let pinned = pinned_entry_ids(&rows);
+        assert_eq!(pinned, vec![pinned_id]);
+
+        let entries: Vec<_> = rows.iter().map(entry_row_to_snapshot_entry).collect();
+        let catalog = generate_catalog(
+            &ServerConfig::default(),
+            &entries,
+            &pinned,
+            Locale::default(),
+        );
+        let cluster = catalog
+            .clusters
+            .iter()
+            .find(|c| c.representative_entry_ids.contains(&pinned_id))
+            .expect("pinned entry should be a representative");
+        assert!(cluster.pinned);
+        assert_eq!(catalog.clusters[0].representative_entry_ids[0], pinned_id);
+    }
+
+    #[test]
+    fn test_pin_response_serialization() {
+        let entry_id = Uuid::new_v4();
+        let json = serde_json::to_value(PinResponse {
+            entry_id,
+            pinned: true,
+        })
+        .unwrap();
+        assert_eq!(json["entry_id"], serde_json::json!(entry_id));
+        assert_eq!(json["pinned"], true);
+    }
+}
@@ -120,7 +120,7 @@ async fn is_notebook_owner(
         .await
         .map_err(|e| match e {
             notebook_store::StoreError::NotebookNotFound(_) => {
-                ApiError::NotFound(format!("Notebook {} not found", notebook_id))
+                ApiError::notebook_not_found(notebook_id)
             }
             other => ApiError::Store(other),
         })?;
@@ -186,6 +186,11 @@ async fn grant_access(
         "Access granted"
     );
 
+    state
+        .broadcaster()
+        .publish_from_log(state.store(), notebook_id)
+        .await;
+
     Ok(Json(ShareResponse {
         access_granted: true,
         author_id: request.author_id,
@@ -232,16 +237,12 @@ async fn revoke_access(
         ));
     }
 
-    // Revoke access using direct SQL (store doesn't have revoke_access method)
-    let result =
-        sqlx::query("DELETE FROM notebook_access WHERE notebook_id = $1 AND author_id = $2")
-            .bind(notebook_id)
-            .bind(target_author_id.as_slice())
-            .execute(state.store().pool())
-            .await
-            .map_err(|e| ApiError::Internal(format!("Failed to revoke access: {}", e)))?;
+    let revoked = state
+        .store()
+        .revoke_access(notebook_id, &target_author_id)
+        .await?;
 
-    if result.rows_affected() == 0 {
+    if !revoked {
         return Err(ApiError::NotFound(format!(
             "No access found for author {} on notebook {}",
             author_id_hex, notebook_id
@@ -254,6 +255,11 @@ async fn revoke_access(
         "Access revoked"
     );
 
+    state
+        .broadcaster()
+        .publish_from_log(state.store(), notebook_id)
+        .await;
+
     Ok(Json(RevokeResponse {
         access_revoked: true,
         author_id: author_id_hex,
@@ -284,7 +290,7 @@ async fn list_participants(
         .await
         .map_err(|e| match e {
             notebook_store::StoreError::NotebookNotFound(_) => {
-                ApiError::NotFound(format!("Notebook {} not found", notebook_id))
+                ApiError::notebook_not_found(notebook_id)
             }
             other => ApiError::Store(other),
         })?;
@@ -0,0 +1,251 @@
+//! Similar notebook discovery.
+//!
+//! Ranks the other notebooks the caller can read by how close their content
+//! is to a given notebook, so agents can find related work without knowing
+//! where to look. Each notebook is summarized as a term profile over its
+//! entries and compared by TF-IDF cosine similarity; see
+//! [`notebook_entropy::similarity`].
+//!
+//! Profiles are cached per notebook and dropped whenever its entries change
+//! (writes, revisions, topic renames), so repeated lookups do not re-read
+//! every entry.
+//!
+//! Endpoint: GET /notebooks/{notebook_id}/similar?limit={n}
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    routing::get,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use notebook_core::{Entry, NotebookId};
+use notebook_entropy::{DEFAULT_MIN_SIMILARITY, NotebookProfile, rank_similar};
+use notebook_store::{EntryQuery, NotebookRow};
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, require_scope};
+use crate::routes::suggest::entry_row_to_snapshot_entry;
+use crate::state::AppState;
+
+/// Number of similar notebooks returned when no limit is given.
+pub const DEFAULT_SIMILAR_LIMIT: u32 = 10;
+
+/// Maximum number of similar notebooks returned in one response.
+pub const MAX_SIMILAR_LIMIT: u32 = 50;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Query parameters for the similar notebooks endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct SimilarParams {
+    /// Maximum number of notebooks to return.
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// A notebook similar to the requested one.
+#[derive(Debug, Serialize)]
+pub struct SimilarNotebookView {
+    /// Notebook ID.
+    pub id: Uuid,
+    /// Notebook name.
+    pub name: String,
+    /// Cosine similarity of the notebooks' term profiles, 0.0-1.0.
+    pub similarity: f64,
+    /// Terms the notebooks have most in common, strongest first.
+    pub shared_terms: Vec<String>,
+}
+
+/// Response for GET /notebooks/{id}/similar.
+#[derive(Debug, Serialize)]
+pub struct SimilarNotebooksResponse {
+    /// The notebook others were compared against.
+    pub notebook_id: Uuid,
+    /// Similar notebooks, most similar first.
+    pub similar: Vec<SimilarNotebookView>,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Resolve the effective limit, applying the default and the maximum.
+fn effective_limit(params: &SimilarParams) -> ApiResult<usize> {
+    match params.limit {
+        Some(0) => Err(ApiError::BadRequest("limit must be at least 1".to_string())),
+        Some(limit) => Ok(limit.min(MAX_SIMILAR_LIMIT) as usize),
+        None => Ok(DEFAULT_SIMILAR_LIMIT as usize),
+    }
+}
+
+/// The term profile of `notebook`, from the cache if it is current.
+async fn profile_for(state: &AppState, notebook: &NotebookRow) -> ApiResult<Arc<NotebookProfile>> {
+    let nb_id = NotebookId::from_uuid(notebook.id);
+    let cache = state.notebook_profiles();
+    if let Some(profile) = cache.get(&nb_id, notebook.current_sequence) {
+        return Ok(profile);
+    }
+
+    let rows = state
+        .store()
+        .query_entries(&EntryQuery::new(notebook.id))
+        .await?;
+    let entries: Vec<Entry> = rows.iter().map(entry_row_to_snapshot_entry).collect();
+    let profile = NotebookProfile::from_entries(&entries, state.config().catalog_locale);
+    Ok(cache.insert(nb_id, notebook.current_sequence, profile))
+}
+
+// ============================================================================
+// Route Handler
+// ============================================================================
+
+/// GET /notebooks/{notebook_id}/similar
+///
+/// Lists the caller's other readable notebooks whose content is similar to
+/// this one.
+///
+/// # Query Parameters
+///
+/// - `limit`: Maximum number of results (default 10, capped at 50).
+///
+/// # Response
+///
+/// - 200 OK: `{ "notebook_id": "...", "similar": [{ "id": "...", "name": "...", "similarity": 0.42, "shared_terms": [...] }] }`
+/// - 400 Bad Request: `limit` is zero
+/// - 403 Forbidden: Missing `notebook:read` scope, or no read access to the notebook
+/// - 404 Not Found: Notebook not found
+async fn get_similar(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+    Query(params): Query<SimilarParams>,
+) -> ApiResult<Json<SimilarNotebooksResponse>> {
+    require_scope(&identity, "notebook:read", state.config())?;
+    let limit = effective_limit(&params)?;
+
+    let mut readable = state
+        .store()
+        .readable_notebooks(identity.author_id.as_bytes())
+        .await?;
+    let target = match readable.iter().position(|row| row.id == notebook_id) {
+        Some(index) => readable.swap_remove(index),
+        None => {
+            state
+                .store()
+                .get_notebook(notebook_id)
+                .await
+                .map_err(|e| match e {
+                    notebook_store::StoreError::NotebookNotFound(id) => {
+                        ApiError::notebook_not_found(id)
+                    }
+                    other => ApiError::Store(other),
+                })?;
+            return Err(ApiError::Forbidden(
+                "You do not have access to this notebook".to_string(),
+            ));
+        }
+    };
+
+    let target_profile = profile_for(&state, &target).await?;
+    let mut profiles = Vec::with_capacity(readable.len());
+    for notebook in &readable {
+        profiles.push(profile_for(&state, notebook).await?);
+    }
+    let candidates: Vec<(usize, &NotebookProfile)> = profiles
+        .iter()
+        .enumerate()
+        .map(|(index, profile)| (index, profile.as_ref()))
+        .collect();
+
+    let similar: Vec<SimilarNotebookView> =
+        rank_similar(&target_profile, &candidates, DEFAULT_MIN_SIMILARITY)
+            .into_iter()
+            .take(limit)
+            .map(|ranked| {
+                let notebook = &readable[ranked.key];
+                SimilarNotebookView {
+                    id: notebook.id,
+                    name: notebook.name.clone(),
+                    similarity: ranked.similarity,
+                    shared_terms: ranked.shared_terms,
+                }
+            })
+            .collect();
+
+    tracing::debug!(
+        notebook_id = %notebook_id,
+        candidates = readable.len(),
+        similar = similar.len(),
+        "Ranked similar notebooks"
+    );
+
+    Ok(Json(SimilarNotebooksResponse {
+        notebook_id,
+        similar,
+    }))
+}
+
+/// Build similar notebook routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/notebooks/{id}/similar", get(get_similar))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notebook_core::AuthorId;
+    use notebook_store::Store;
+    use sqlx::PgPool;
+
+    use crate::config::ServerConfig;
+    use crate::error::ErrorCode;
+
+    #[test]
+    fn test_effective_limit_default_and_cap() {
+        assert_eq!(
+            effective_limit(&SimilarParams::default()).unwrap(),
+            DEFAULT_SIMILAR_LIMIT as usize
+        );
+        let params = SimilarParams { limit: Some(1_000) };
+        assert_eq!(
+            effective_limit(&params).unwrap(),
+            MAX_SIMILAR_LIMIT as usize
+        );
+        let params = SimilarParams { limit: Some(0) };
+        assert!(matches!(
+            effective_limit(&params),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_similar_requires_read_scope() {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let state = AppState::new(Store::from_pool(pool), ServerConfig::default());
+        let identity = AuthorIdentity {
+            author_id: AuthorId::zero(),
+            scopes: vec!["notebook:write".to_string()],
+        };
+        let result = get_similar(
+            State(state),
+            identity,
+            Path(Uuid::new_v4()),
+            Query(SimilarParams::default()),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ApiError::Coded(ErrorCode::MissingScope, _))
+        ));
+    }
+}
@@ -0,0 +1,315 @@
+//! Reference suggestions for an entry.
+//!
+//! Uses the TF-IDF vectors held in the notebook's coherence snapshot to
+//! recommend existing entries an entry could reference. Together with the
+//! orphan listing this lets agents find disconnected entries and link them.
+//!
+//! Endpoint: GET /notebooks/{notebook_id}/entries/{entry_id}/suggest-references?limit={n}
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    routing::get,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use notebook_core::{AuthorId, CausalPosition, Entry, EntryId, IntegrationCost, NotebookId};
+use notebook_entropy::IntegrationCostEngine;
+use notebook_store::{EntryQuery, EntryRow, StoreError};
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, require_scope};
+use crate::state::AppState;
+
+/// Number of suggestions returned when no limit is given.
+pub const DEFAULT_SUGGESTIONS_LIMIT: u32 = 5;
+
+/// Maximum number of suggestions returned in one response.
+pub const MAX_SUGGESTIONS_LIMIT: u32 = 50;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Query parameters for the suggest-references endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct SuggestParams {
+    /// Maximum number of suggestions to return.
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// Response for GET /notebooks/{id}/entries/{entry_id}/suggest-references.
+#[derive(Debug, Serialize)]
+pub struct SuggestReferencesResponse {
+    /// The entry suggestions were computed for.
+    pub entry_id: Uuid,
+    /// Suggested references, most similar first.
+    pub suggestions: Vec<SuggestedReference>,
+}
+
+/// A single suggested reference.
+#[derive(Debug, Serialize)]
+pub struct SuggestedReference {
+    /// The suggested entry.
+    pub entry_id: Uuid,
+    /// Cosine similarity between the two entries (0.0 to 1.0).
+    pub similarity: f64,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Resolve the effective limit, applying the default and the maximum.
+fn effective_limit(params: &SuggestParams) -> ApiResult<usize> {
+    match params.limit {
+        Some(0) => Err(ApiError::BadRequest("limit must be at least 1".to_string())),
+        Some(limit) => Ok(limit.min(MAX_SUGGESTIONS_LIMIT) as usize),
+        None => Ok(DEFAULT_SUGGESTIONS_LIMIT as usize),
+    }
+}
+
+/// Build the in-memory entry used to rebuild a coherence snapshot.
+///
+/// Only the fields clustering looks at are carried over.
+pub(crate) fn entry_row_to_snapshot_entry(row: &EntryRow) -> Entry {
+    Entry {
+        id: EntryId::from_uuid(row.id),
+        content: row.content.clone(),
+        content_type: row.content_type.clone(),
+        topic: row.topic.clone(),
+        author: row
+            .author_id_bytes()
+            .map(AuthorId::from_bytes)
+            .unwrap_or_else(AuthorId::zero),
+        signature: row.signature.clone(),
+        references: row
+            .references
+            .iter()
+            .copied()
+            .map(EntryId::from_uuid)
+            .collect(),
+        revision_of: row.revision_of.map(EntryId::from_uuid),
+        causal_position: CausalPosition {
+            sequence: row.sequence as u64,
+            ..CausalPosition::first()
+        },
+        created: row.created,
+        integration_cost: IntegrationCost::zero(),
+    }
+}
+
+/// Rank suggested references for an entry from the notebook's snapshot.
+///
+/// Excludes the entry itself and the entries it already references.
+/// Returns `None` if the snapshot does not track the entry.
+fn rank_suggestions(
+    engine: &IntegrationCostEngine,
+    notebook_id: NotebookId,
+    entry_id: EntryId,
+    references: &[Uuid],
+    limit: usize,
+) -> Option<Vec<SuggestedReference>> {
+    let exclude: Vec<EntryId> = references.iter().copied().map(EntryId::from_uuid).collect();
+    let similar = engine
+        .get_snapshot(notebook_id)?
+        .similar_entries(&entry_id, &exclude, limit)?;
+
+    Some(
+        similar
+            .into_iter()
+            .map(|(id, similarity)| SuggestedReference {
+                entry_id: id.0,
+                similarity,
+            })
+            .collect(),
+    )
+}
+
+// ============================================================================
+// Route Handler
+// ============================================================================
+
+/// GET /notebooks/{notebook_id}/entries/{entry_id}/suggest-references
+///
+/// Returns the existing entries most similar to the given entry that it does
+/// not already reference. If the notebook's coherence snapshot does not know
+/// the entry yet (e.g. after a restart), the snapshot is rebuilt from storage.
+///
+/// # Query Parameters
+///
+/// - `limit`: Maximum number of suggestions (default 5, capped at 50).
+///
+/// # Response
+///
+/// - 200 OK: `{ "entry_id": "...", "suggestions": [{ "entry_id": "...", "similarity": 0.42 }] }`
+/// - 400 Bad Request: `limit` is zero
+/// - 404 Not Found: Notebook or entry not found
+async fn suggest_references(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path((notebook_id, entry_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<SuggestParams>,
+) -> ApiResult<Json<SuggestReferencesResponse>> {
+    require_scope(&identity, "notebook:read", state.config())?;
+    let store = state.store();
+    let limit = effective_limit(&params)?;
+
+    // Validate notebook exists
+    store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+
+    // The entry must live in this notebook
+    let row = store.get_entry(entry_id).await.map_err(|e| match e {
+        StoreError::EntryNotFound(id) => ApiError::entry_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+    if row.notebook_id != notebook_id {
+        return Err(ApiError::entry_not_found(entry_id));
+    }
+
+    let nb_id = NotebookId::from_uuid(notebook_id);
+    let e_id = EntryId::from_uuid(entry_id);
+
+    let tracked = state
+        .engines()
+        .lock(nb_id)
+        .await
+        .get_snapshot(nb_id)
+        .is_some_and(|snapshot| snapshot.contains_entry(&e_id));
+
+    // Load entries without holding the engine lock, then rebuild
+    let rebuild_from = if tracked {
+        None
+    } else {
+        let rows = store.query_entries(&EntryQuery::new(notebook_id)).await?;
+        Some(
+            rows.iter()
+                .map(entry_row_to_snapshot_entry)
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    let mut engine = state.engines().lock(nb_id).await;
+    // A concurrent request may have rebuilt the snapshot in the meantime
+    let still_untracked = !engine
+        .get_snapshot(nb_id)
+        .is_some_and(|snapshot| snapshot.contains_entry(&e_id));
+    if let Some(entries) = rebuild_from.filter(|_| still_untracked) {
+        let timestamp = entries
+            .last()
+            .map(|e| e.causal_position)
+            .unwrap_or_else(CausalPosition::first);
+        engine.initialize_from_entries(nb_id, &entries, timestamp);
+        tracing::info!(
+            notebook_id = %notebook_id,
+            entries = entries.len(),
+            "Rebuilt coherence snapshot for reference suggestions"
+        );
+    }
+
+    let suggestions =
+        rank_suggestions(&engine, nb_id, e_id, &row.references, limit).unwrap_or_default();
+
+    Ok(Json(SuggestReferencesResponse {
+        entry_id,
+        suggestions,
+    }))
+}
+
+/// Build suggestion routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route(
+        "/notebooks/{id}/entries/{entry_id}/suggest-references",
+        get(suggest_references),
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_row(notebook_id: Uuid, content: &str, references: Vec<Uuid>) -> EntryRow {
+        EntryRow {
+            references,
+            ..EntryRow::test_row(notebook_id, 1, content)
+        }
+    }
+
+    #[test]
+    fn test_effective_limit() {
+        assert_eq!(
+            effective_limit(&SuggestParams::default()).unwrap(),
+            DEFAULT_SUGGESTIONS_LIMIT as usize
+        );
+        assert_eq!(
+            effective_limit(&SuggestParams { limit: Some(1000) }).unwrap(),
+            MAX_SUGGESTIONS_LIMIT as usize
+        );
+        assert!(effective_limit(&SuggestParams { limit: Some(0) }).is_err());
+    }
+
+    #[test]
+    fn test_top_suggestion_is_related_entry() {
+        let notebook_id = Uuid::new_v4();
+        let rows = [
+            make_row(
+                notebook_id,
+                "tokio async runtime tasks and executors",
+                vec![],
+            ),
+            make_row(notebook_id, "baking sourdough bread with starter", vec![]),
+            make_row(notebook_id, "watering houseplants in winter", vec![]),
+            make_row(
+                notebook_id,
+                "spawning async tasks on the tokio runtime",
+                vec![],
+            ),
+        ];
+        let entries: Vec<Entry> = rows.iter().map(entry_row_to_snapshot_entry).collect();
+
+        let nb_id = NotebookId::from_uuid(notebook_id);
+        let mut engine = IntegrationCostEngine::new();
+        engine.initialize_from_entries(nb_id, &entries, CausalPosition::first());
+
+        let suggestions =
+            rank_suggestions(&engine, nb_id, entries[3].id, &[], 3).expect("entry is tracked");
+        assert_eq!(suggestions[0].entry_id, rows[0].id);
+        assert!(suggestions.iter().all(|s| s.entry_id != rows[3].id));
+    }
+
+    #[test]
+    fn test_existing_references_are_excluded() {
+        let notebook_id = Uuid::new_v4();
+        let target = make_row(notebook_id, "tokio async runtime tasks", vec![]);
+        let other = make_row(notebook_id, "bread baking at home", vec![]);
+        let query = make_row(notebook_id, "async tasks with tokio", vec![target.id]);
+        let entries: Vec<Entry> = [&target, &other, &query]
+            .into_iter()
+            .map(entry_row_to_snapshot_entry)
+            .collect();
+
+        let nb_id = NotebookId::from_uuid(notebook_id);
+        let mut engine = IntegrationCostEngine::new();
+        engine.initialize_from_entries(nb_id, &entries, CausalPosition::first());
+
+        let suggestions =
+            rank_suggestions(&engine, nb_id, entries[2].id, &query.references, 5).unwrap();
+        assert!(suggestions.iter().all(|s| s.entry_id != target.id));
+    }
+
+    #[test]
+    fn test_untracked_entry_has_no_suggestions() {
+        let engine = IntegrationCostEngine::new();
+        let nb_id = NotebookId::new();
+        assert!(rank_suggestions(&engine, nb_id, EntryId::new(), &[], 5).is_none());
+    }
+}
@@ -0,0 +1,601 @@
+//! Topic listing and bulk topic renames.
+//!
+//! The topics of a notebook can be listed with their entry counts, from a
+//! single aggregate query: a quick overview that is much cheaper than
+//! browse. Entries without a topic are counted under a `null` topic.
+//!
+//! Entries of one topic can be listed in causal order, a page at a time, so
+//! clients can drill into a cluster without fetching the whole notebook.
+//! Each page carries a `next_cursor`, the sequence of its last entry, which
+//! is passed back as `cursor` to fetch the following page.
+//!
+//! Topics drift as a notebook grows ("ml" becomes "machine-learning").
+//! Renaming rewrites the topic of every matching entry in one transaction,
+//! then drops state derived from the old topics: the cached browse catalog
+//! (the notebook's sequence does not move, so the cache would otherwise
+//! keep serving it) and the coherence snapshot, which is re-clustered from
+//! the renamed entries.
+//!
+//! Endpoints:
+//! - GET /notebooks/{notebook_id}/topics
+//! - GET /notebooks/{notebook_id}/topics/{topic}/entries?limit={n}&cursor={seq}
+//! - POST /notebooks/{notebook_id}/topics/rename
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    routing::{get, post},
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use notebook_core::{CausalPosition, Entry, NotebookId};
+use notebook_entropy::{CatalogCache, IntegrationCostEngine};
+use notebook_store::{EntryQuery, Repository, StoreError, TopicCount, TopicCountsQuery};
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, ReaderIdentity, require_scope};
+use crate::public_reads::readable_notebook;
+use crate::routes::entries::{EntryResponse, ensure_unlocked, entries_to_responses};
+use crate::routes::suggest::entry_row_to_snapshot_entry;
+use crate::state::AppState;
+
+/// Number of entries returned per page when no limit is given.
+pub const DEFAULT_TOPIC_ENTRIES_LIMIT: u32 = 50;
+
+/// Maximum number of entries returned in one page.
+pub const MAX_TOPIC_ENTRIES_LIMIT: u32 = 500;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// A topic of a notebook with its entry count.
+#[derive(Debug, Serialize)]
+pub struct TopicSummary {
+    /// The topic, or `None` for entries without one.
+    pub topic: Option<String>,
+    /// Number of entries tagged with the topic.
+    pub entries: u64,
+    /// Sequence of the newest entry tagged with the topic.
+    pub latest_sequence: u64,
+}
+
+impl From<TopicCount> for TopicSummary {
+    fn from(count: TopicCount) -> Self {
+        Self {
+            topic: count.topic,
+            entries: count.entries as u64,
+            latest_sequence: count.latest_sequence as u64,
+        }
+    }
+}
+
+/// Response for GET /notebooks/{id}/topics.
+#[derive(Debug, Serialize)]
+pub struct TopicsResponse {
+    /// Topics, most entries first.
+    pub topics: Vec<TopicSummary>,
+}
+
+/// Query parameters for GET /notebooks/{id}/topics/{topic}/entries.
+#[derive(Debug, Default, Deserialize)]
+pub struct TopicEntriesParams {
+    /// Maximum number of entries to return.
+    #[serde(default)]
+    pub limit: Option<u32>,
+
+    /// `next_cursor` of the previous page; entries after it are returned.
+    #[serde(default)]
+    pub cursor: Option<u64>,
+}
+
+/// Response for GET /notebooks/{id}/topics/{topic}/entries.
+#[derive(Debug, Serialize)]
+pub struct TopicEntriesResponse {
+    pub topic: String,
+    /// Entries tagged with the topic, in causal order.
+    pub entries: Vec<EntryResponse>,
+    /// Cursor for the next page, or `None` on the last page.
+    pub next_cursor: Option<u64>,
+}
+
+/// Request body for POST /notebooks/{id}/topics/rename.
+#[derive(Debug, Deserialize)]
+pub struct RenameTopicRequest {
+    /// Topic to replace. Matched exactly.
+    pub from: String,
+    /// Replacement topic.
+    pub to: String,
+}
+
+/// Response for POST /notebooks/{id}/topics/rename.
+#[derive(Debug, Serialize)]
+pub struct RenameTopicResponse {
+    pub from: String,
+    pub to: String,
+    /// Number of entries whose topic changed.
+    pub entries_updated: u64,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Resolve the effective page size, applying the default and the maximum.
+fn effective_limit(params: &TopicEntriesParams) -> ApiResult<usize> {
+    match params.limit {
+        Some(0) => Err(ApiError::BadRequest("limit must be at least 1".to_string())),
+        Some(limit) => Ok(limit.min(MAX_TOPIC_ENTRIES_LIMIT) as usize),
+        None => Ok(DEFAULT_TOPIC_ENTRIES_LIMIT as usize),
+    }
+}
+
+/// Cut a page of `limit` entries from `entries`, fetched with one extra
+/// entry to tell whether another page follows.
+///
+/// Returns the page and the cursor of the next page.
+fn cut_page(mut entries: Vec<Entry>, limit: usize) -> (Vec<Entry>, Option<u64>) {
+    if entries.len() <= limit {
+        return (entries, None);
+    }
+    entries.truncate(limit);
+    let next_cursor = entries.last().map(|e| e.causal_position.sequence);
+    (entries, next_cursor)
+}
+
+/// Reject blank topics and no-op renames.
+fn validate_rename(request: &RenameTopicRequest) -> ApiResult<()> {
+    if request.from.trim().is_empty() || request.to.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Both 'from' and 'to' topics are required".to_string(),
+        ));
+    }
+    if request.from == request.to {
+        return Err(ApiError::BadRequest(
+            "'from' and 'to' topics must differ".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Drop the notebook's cached catalog and re-cluster its coherence snapshot
+/// from `entries`.
+///
+/// A notebook without a snapshot has nothing to re-cluster; one is built
+/// from storage when it is next needed.
+fn refresh_derived_state(
+    cache: &CatalogCache,
+    engine: &mut IntegrationCostEngine,
+    notebook_id: NotebookId,
+    entries: &[Entry],
+) {
+    cache.invalidate(&notebook_id);
+
+    if engine.get_snapshot(notebook_id).is_some() {
+        let timestamp = entries
+            .last()
+            .map(|e| e.causal_position)
+            .unwrap_or_else(CausalPosition::first);
+        engine.initialize_from_entries(notebook_id, entries, timestamp);
+    }
+}
+
+// ============================================================================
+// Route Handler
+// ============================================================================
+
+/// GET /notebooks/{notebook_id}/topics
+///
+/// Lists the notebook's distinct topics with their entry counts, most
+/// entries first. Untagged entries are counted under a `null` topic.
+///
+/// Public notebooks can be read without credentials.
+///
+/// # Response
+///
+/// - 200 OK: `{ "topics": [{ "topic": "ml", "entries": 12, "latest_sequence": 40 }] }`
+/// - 401 Unauthorized: No credentials and the notebook is not public
+/// - 404 Not Found: Notebook not found
+/// - 429 Too Many Requests: Anonymous read limit exceeded
+async fn list_topics(
+    State(state): State<AppState>,
+    reader: ReaderIdentity,
+    Path(notebook_id): Path<Uuid>,
+) -> ApiResult<Json<TopicsResponse>> {
+    readable_notebook(&state, &reader, notebook_id).await?;
+
+    let counts = TopicCountsQuery::new(NotebookId::from_uuid(notebook_id))
+        .execute(state.store())
+        .await?;
+
+    tracing::debug!(
+        notebook_id = %notebook_id,
+        topics = counts.len(),
+        "Listed topics"
+    );
+
+    Ok(Json(TopicsResponse {
+        topics: counts.into_iter().map(TopicSummary::from).collect(),
+    }))
+}
+
+/// GET /notebooks/{notebook_id}/topics/{topic}/entries
+///
+/// Lists the entries tagged with a topic, oldest first. Revisions carry
+/// their own topic and are listed in their own place in the sequence.
+///
+/// Public notebooks can be read without credentials.
+///
+/// # Query Parameters
+///
+/// - `limit`: Maximum number of entries (default 50, capped at 500).
+/// - `cursor`: `next_cursor` of the previous page.
+///
+/// # Response
+///
+/// - 200 OK: `{ "topic": "...", "entries": [{...}], "next_cursor": 42 }`;
+///   `next_cursor` is `null` on the last page
+/// - 400 Bad Request: `limit` is zero
+/// - 401 Unauthorized: No credentials and the notebook is not public
+/// - 404 Not Found: Notebook not found
+/// - 429 Too Many Requests: Anonymous read limit exceeded
+async fn list_topic_entries(
+    State(state): State<AppState>,
+    reader: ReaderIdentity,
+    Path((notebook_id, topic)): Path<(Uuid, String)>,
+    Query(params): Query<TopicEntriesParams>,
+) -> ApiResult<Json<TopicEntriesResponse>> {
+    let limit = effective_limit(&params)?;
+    readable_notebook(&state, &reader, notebook_id).await?;
+
+    let repo = Repository::new(state.store().clone());
+    let entries = repo
+        .get_entries_by_topic(
+            NotebookId::from_uuid(notebook_id),
+            &topic,
+            params.cursor,
+            limit + 1,
+        )
+        .await?;
+    let (page, next_cursor) = cut_page(entries, limit);
+
+    tracing::debug!(
+        notebook_id = %notebook_id,
+        topic = %topic,
+        returned = page.len(),
+        "Listed topic entries"
+    );
+
+    Ok(Json(TopicEntriesResponse {
+        topic,
+        entries: entries_to_responses(state.store(), &page).await?,
+        next_cursor,
+    }))
+}
+
+/// POST /notebooks/{notebook_id}/topics/rename
+///
+/// Renames a topic on every entry of the notebook that carries it.
+///
+/// # Request Body
+///
+/// `{ "from": "ml", "to": "machine-learning" }`
+///
+/// # Response
+///
+/// - 200 OK: `{ "from": "ml", "to": "machine-learning", "entries_updated": 12 }`
+/// - 400 Bad Request: Blank topic, or `from` equals `to`
+/// - 403 Forbidden: Neither owner nor write access
+/// - 404 Not Found: Notebook doesn't exist
+/// - 409 Conflict: Notebook is locked
+async fn rename_topic(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+    Json(request): Json<RenameTopicRequest>,
+) -> ApiResult<Json<RenameTopicResponse>> {
+    require_scope(&identity, "notebook:write", state.config())?;
+    validate_rename(&request)?;
+    let store = state.store();
+    let author_bytes = *identity.author_id.as_bytes();
+
+    let notebook = store.get_notebook(notebook_id).await.map_err(|e| match e {
+        StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+        other => ApiError::Store(other),
+    })?;
+    ensure_unlocked(&notebook)?;
+
+    if notebook.owner_id.as_slice() != author_bytes.as_slice()
+        && !store.has_write_access(notebook_id, &author_bytes).await?
+    {
+        return Err(ApiError::Forbidden(
+            "Renaming topics requires ownership or write access".to_string(),
+        ));
+    }
+
+    let entries_updated = store
+        .rename_topic(notebook_id, &request.from, &request.to)
+        .await?;
+
+    if entries_updated > 0 {
+        tracing::info!(
+            notebook_id = %notebook_id,
+            from = %request.from,
+            to = %request.to,
+            entries = entries_updated,
+            "Topic renamed"
+        );
+
+        // Load entries without holding the engine lock
+        let rows = store.query_entries(&EntryQuery::new(notebook_id)).await?;
+        let entries: Vec<Entry> = rows.iter().map(entry_row_to_snapshot_entry).collect();
+
+        let nb_id = NotebookId::from_uuid(notebook_id);
+        let mut engine = state.engines().lock(nb_id).await;
+        refresh_derived_state(state.catalog_cache(), &mut engine, nb_id, &entries);
+        drop(engine);
+        state.notebook_profiles().invalidate(&nb_id);
+
+        state
+            .broadcaster()
+            .publish_from_log(store, notebook_id)
+            .await;
+    }
+
+    Ok(Json(RenameTopicResponse {
+        from: request.from,
+        to: request.to,
+        entries_updated,
+    }))
+}
+
+/// Build topic routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/notebooks/{id}/topics", get(list_topics))
+        .route(
+            "/notebooks/{id}/topics/{topic}/entries",
+            get(list_topic_entries),
+        )
+        .route("/notebooks/{id}/topics/rename", post(rename_topic))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notebook_store::EntryRow;
+
+    use crate::config::ServerConfig;
+    use crate::routes::browse::{filter_by_query, generate_catalog};
+
+    fn make_row(notebook_id: Uuid, sequence: i64, content: &str, topic: &str) -> EntryRow {
+        EntryRow {
+            topic: Some(topic.to_string()),
+            ..EntryRow::test_row(notebook_id, sequence, content)
+        }
+    }
+
+    fn rows(notebook_id: Uuid) -> Vec<EntryRow> {
+        vec![
+            make_row(notebook_id, 1, "gradient descent optimizers", "ml"),
+            make_row(notebook_id, 2, "neural network training loops", "ml"),
+            make_row(
+                notebook_id,
+                3,
+                "transformer attention heads",
+                "machine-learning",
+            ),
+            make_row(notebook_id, 4, "sourdough starter hydration", "baking"),
+        ]
+    }
+
+    /// What the store does to matching rows.
+    fn rename(rows: &mut [EntryRow], from: &str, to: &str) {
+        for row in rows.iter_mut().filter(|r| r.topic.as_deref() == Some(from)) {
+            row.topic = Some(to.to_string());
+        }
+    }
+
+    fn request(from: &str, to: &str) -> RenameTopicRequest {
+        RenameTopicRequest {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    fn entry_at(sequence: u64) -> Entry {
+        Entry {
+            causal_position: CausalPosition {
+                sequence,
+                ..CausalPosition::first()
+            },
+            ..entry_row_to_snapshot_entry(&make_row(Uuid::nil(), 0, "x", "ml"))
+        }
+    }
+
+    fn sequences(entries: &[Entry]) -> Vec<u64> {
+        entries.iter().map(|e| e.causal_position.sequence).collect()
+    }
+
+    #[test]
+    fn test_untagged_topic_serializes_as_null() {
+        let summary = TopicSummary::from(TopicCount {
+            topic: None,
+            entries: 3,
+            latest_sequence: 9,
+        });
+        let json = serde_json::to_value(&summary).unwrap();
+        assert!(json["topic"].is_null());
+        assert_eq!(json["entries"], 3);
+        assert_eq!(json["latest_sequence"], 9);
+    }
+
+    #[tokio::test]
+    async fn test_topics_require_read_scope() {
+        let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let state = AppState::new(
+            notebook_store::Store::from_pool(pool),
+            ServerConfig::default(),
+        );
+        let reader = ReaderIdentity::Author(AuthorIdentity {
+            author_id: notebook_core::AuthorId::zero(),
+            scopes: vec!["notebook:write".to_string()],
+        });
+        let result = list_topics(State(state), reader, Path(Uuid::new_v4())).await;
+        assert!(matches!(
+            result,
+            Err(ApiError::Coded(crate::error::ErrorCode::MissingScope, _))
+        ));
+    }
+
+    #[test]
+    fn test_topic_entries_limit() {
+        let params = TopicEntriesParams::default();
+        assert_eq!(
+            effective_limit(&params).unwrap(),
+            DEFAULT_TOPIC_ENTRIES_LIMIT as usize
+        );
+        let params = TopicEntriesParams {
+            limit: Some(10_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            effective_limit(&params).unwrap(),
+            MAX_TOPIC_ENTRIES_LIMIT as usize
+        );
+        let params = TopicEntriesParams {
+            limit: Some(0),
+            ..Default::default()
+        };
+        assert!(matches!(
+            effective_limit(&params),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_cut_page_sets_cursor_only_when_more_follow() {
+        let fetched: Vec<Entry> = [3, 5, 8].into_iter().map(entry_at).collect();
+        let (page, next_cursor) = cut_page(fetched.clone(), 2);
+        assert_eq!(sequences(&page), vec![3, 5]);
+        assert_eq!(next_cursor, Some(5));
+
+        let (page, next_cursor) = cut_page(fetched, 3);
+        assert_eq!(sequences(&page), vec![3, 5, 8]);
+        assert_eq!(next_cursor, None);
+
+        assert_eq!(cut_page(Vec::new(), 2), (Vec::new(), None));
+    }
+
+    #[test]
+    fn test_topic_entries_params_deserialize() {
+        let params: TopicEntriesParams =
+            serde_json::from_str(r#"{"limit": 5, "cursor": 12}"#).unwrap();
+        assert_eq!(params.limit, Some(5));
+        assert_eq!(params.cursor, Some(12));
+    }
+
+    #[tokio::test]
+    async fn test_topic_entries_require_read_scope() {
+        let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let state = AppState::new(
+            notebook_store::Store::from_pool(pool),
+            ServerConfig::default(),
+        );
+        let reader = ReaderIdentity::Author(AuthorIdentity {
+            author_id: notebook_core::AuthorId::zero(),
+            scopes: vec!["notebook:write".to_string()],
+        });
+        let result = list_topic_entries(
+            State(state),
+            reader,
+            Path((Uuid::new_v4(), "ml".to_string())),
+            Query(TopicEntriesParams::default()),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ApiError::Coded(crate::error::ErrorCode::MissingScope, _))
+        ));
+    }
+
+    #[test]
+    fn test_validate_rename() {
+        assert!(validate_rename(&request("ml", "machine-learning")).is_ok());
+
+        for (from, to) in [("", "x"), ("x", "  "), ("same", "same")] {
+            assert!(matches!(
+                validate_rename(&request(from, to)),
+                Err(ApiError::BadRequest(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_browse_reflects_merged_topic() {
+        let config = ServerConfig::default();
+        let mut rows = rows(Uuid::new_v4());
+
+        let entries: Vec<Entry> = rows.iter().map(entry_row_to_snapshot_entry).collect();
+        let mut catalog = generate_catalog(&config, &entries, &[], config.catalog_locale);
+        assert_eq!(
+            filter_by_query(&mut catalog, &entries, "machine-learning"),
+            1
+        );
+
+        rename(&mut rows, "ml", "machine-learning");
+        assert!(rows.iter().all(|r| r.topic.as_deref() != Some("ml")));
+
+        let entries: Vec<Entry> = rows.iter().map(entry_row_to_snapshot_entry).collect();
+        let mut catalog = generate_catalog(&config, &entries, &[], config.catalog_locale);
+        assert_eq!(
+            filter_by_query(&mut catalog, &entries, "machine-learning"),
+            3
+        );
+        assert!(!catalog.clusters.is_empty());
+    }
+
+    #[test]
+    fn test_refresh_invalidates_catalog_and_reclusters() {
+        let notebook_id = Uuid::new_v4();
+        let nb_id = NotebookId::from_uuid(notebook_id);
+        let config = ServerConfig::default();
+        let mut rows = rows(notebook_id);
+        let entries: Vec<Entry> = rows.iter().map(entry_row_to_snapshot_entry).collect();
+
+        let cache = CatalogCache::new();
+        cache.set(
+            nb_id,
+            generate_catalog(&config, &entries, &[], config.catalog_locale),
+            4,
+        );
+        let mut engine = IntegrationCostEngine::new();
+        engine.initialize_from_entries(nb_id, &entries[..2], CausalPosition::first());
+
+        rename(&mut rows, "ml", "machine-learning");
+        let renamed: Vec<Entry> = rows.iter().map(entry_row_to_snapshot_entry).collect();
+        refresh_derived_state(&cache, &mut engine, nb_id, &renamed);
+
+        assert!(cache.get(&nb_id).is_none());
+        let snapshot = engine.get_snapshot(nb_id).unwrap();
+        assert!(renamed.iter().all(|e| snapshot.contains_entry(&e.id)));
+    }
+
+    #[test]
+    fn test_refresh_without_snapshot_leaves_engine_empty() {
+        let nb_id = NotebookId::from_uuid(Uuid::new_v4());
+        let entries: Vec<Entry> = rows(Uuid::new_v4())
+            .iter()
+            .map(entry_row_to_snapshot_entry)
+            .collect();
+
+        let mut engine = IntegrationCostEngine::new();
+        refresh_derived_state(&CatalogCache::new(), &mut engine, nb_id, &entries);
+        assert!(engine.get_snapshot(nb_id).is_none());
+    }
+}
@@ -0,0 +1,90 @@
+//! Server and schema version reporting.
+//!
+//! Lets clients check compatibility before issuing requests: the CLI warns
+//! when the server speaks a different API version.
+//!
+//! Endpoint: GET /version (no authentication required)
+
+use axum::{Json, Router, extract::State, routing::get};
+use serde::Serialize;
+
+use notebook_store::schema::SCHEMA_VERSION;
+
+use crate::error::ApiResult;
+use crate::state::AppState;
+
+/// Version of the HTTP API served by this build.
+///
+/// Bumped on breaking changes to request or response shapes.
+pub const API_VERSION: u32 = 1;
+
+/// Response for GET /version.
+#[derive(Debug, Serialize)]
+pub struct VersionResponse {
+    /// Version of the server crate.
+    pub server_version: String,
+    /// Version of the HTTP API; see [`API_VERSION`].
+    pub api_version: u32,
+    /// Schema version detected in the database.
+    pub schema_version: u32,
+    /// Schema version this build expects after running its migrations.
+    pub expected_schema_version: u32,
+    /// Whether the Apache AGE graph extension is available.
+    pub age_available: bool,
+}
+
+impl VersionResponse {
+    /// Report this build against the detected database state.
+    pub fn new(schema_version: u32, age_available: bool) -> Self {
+        Self {
+            server_version: env!("CARGO_PKG_VERSION").to_string(),
+            api_version: API_VERSION,
+            schema_version,
+            expected_schema_version: SCHEMA_VERSION,
+            age_available,
+        }
+    }
+}
+
+/// GET /version - Report server, API and schema versions.
+///
+/// # Response
+///
+/// - 200 OK: VersionResponse
+/// - 500 Internal Server Error: Schema version could not be read
+async fn get_version(State(state): State<AppState>) -> ApiResult<Json<VersionResponse>> {
+    let store = state.store();
+    let schema_version = store.schema_version().await?;
+    Ok(Json(VersionResponse::new(
+        schema_version,
+        store.age_available(),
+    )))
+}
+
+/// Build version routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/version", get(get_version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_response_reports_build() {
+        let response = VersionResponse::new(SCHEMA_VERSION, true);
+        assert_eq!(response.server_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(response.api_version, API_VERSION);
+        assert_eq!(response.schema_version, response.expected_schema_version);
+        assert!(response.age_available);
+    }
+
+    #[test]
+    fn test_version_response_serialize() {
+        let json = serde_json::to_value(VersionResponse::new(2, false)).unwrap();
+        assert_eq!(json["api_version"], API_VERSION);
+        assert_eq!(json["schema_version"], 2);
+        assert_eq!(json["expected_schema_version"], SCHEMA_VERSION);
+        assert_eq!(json["age_available"], false);
+    }
+}
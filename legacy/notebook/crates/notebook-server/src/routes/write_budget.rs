@@ -0,0 +1,280 @@
+//! Per-notebook write budget.
+//!
+//! Lets a notebook owner set how much entropy each author may add to the
+//! notebook per window before their writes are throttled, replacing the
+//! server-wide budget; see [`crate::throttle`].
+//!
+//! Endpoints:
+//! - PUT /notebooks/{id}/write-budget - Set the entropy budget and window
+//! - GET /notebooks/{id}/write-budget - Get the configured budget
+//! - DELETE /notebooks/{id}/write-budget - Remove the budget
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    routing::put,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use notebook_store::{NotebookWriteBudgetRow, StoreError};
+
+use crate::error::{ApiError, ApiResult};
+use crate::extract::{AuthorIdentity, require_scope};
+use crate::state::AppState;
+
+/// Longest accepted budget window, in seconds (one day).
+///
+/// Contributions are held in memory for the whole window.
+pub const MAX_WINDOW_SECS: u32 = 24 * 60 * 60;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Request body for PUT /notebooks/{id}/write-budget.
+#[derive(Debug, Deserialize)]
+pub struct SetWriteBudgetRequest {
+    /// Entropy each author may contribute per window before throttling.
+    pub entropy_budget: f64,
+    /// Window in seconds. Defaults to the server's `WRITE_BUDGET_WINDOW_SECS`.
+    #[serde(default)]
+    pub window_secs: Option<u32>,
+}
+
+/// A notebook's write budget.
+#[derive(Debug, Serialize)]
+pub struct WriteBudgetResponse {
+    pub notebook_id: Uuid,
+    pub entropy_budget: f64,
+    pub window_secs: i32,
+    pub updated: DateTime<Utc>,
+}
+
+impl From<NotebookWriteBudgetRow> for WriteBudgetResponse {
+    fn from(row: NotebookWriteBudgetRow) -> Self {
+        Self {
+            notebook_id: row.notebook_id,
+            entropy_budget: row.entropy_budget,
+            window_secs: row.window_secs,
+            updated: row.updated,
+        }
+    }
+}
+
+/// Response for DELETE /notebooks/{id}/write-budget.
+#[derive(Debug, Serialize)]
+pub struct DeleteWriteBudgetResponse {
+    pub notebook_id: Uuid,
+    pub deleted: bool,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Validate a budget request, returning the window to store.
+fn validate_budget_request(request: &SetWriteBudgetRequest, default_window: u64) -> ApiResult<i32> {
+    if !(request.entropy_budget.is_finite() && request.entropy_budget > 0.0) {
+        return Err(ApiError::BadRequest(format!(
+            "entropy_budget must be a positive number, got {}",
+            request.entropy_budget
+        )));
+    }
+
+    let window = request.window_secs.map_or(default_window, u64::from);
+    if window == 0 || window > u64::from(MAX_WINDOW_SECS) {
+        return Err(ApiError::BadRequest(format!(
+            "window_secs must be between 1 and {}, got {}",
+            MAX_WINDOW_SECS, window
+        )));
+    }
+    Ok(window as i32)
+}
+
+/// Require the caller to hold `notebook:admin` and own the notebook.
+async fn require_owner(
+    state: &AppState,
+    identity: &AuthorIdentity,
+    notebook_id: Uuid,
+) -> ApiResult<()> {
+    require_scope(identity, "notebook:admin", state.config())?;
+
+    let notebook = state
+        .store()
+        .get_notebook(notebook_id)
+        .await
+        .map_err(|e| match e {
+            StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+            other => ApiError::Store(other),
+        })?;
+
+    if notebook.owner_id.as_slice() != identity.author_id.as_bytes().as_slice() {
+        return Err(ApiError::Forbidden(
+            "Only the notebook owner can manage the write budget".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Route Handlers
+// ============================================================================
+
+/// PUT /notebooks/{id}/write-budget - Set a notebook's write budget.
+///
+/// Replaces any existing budget. Contributions already made count against
+/// the new budget.
+///
+/// # Request
+///
+/// Body: `{ "entropy_budget": 5.0, "window_secs": 300 }`
+///
+/// # Response
+///
+/// - 200 OK: WriteBudgetResponse
+/// - 400 Bad Request: Non-positive budget or window out of range
+/// - 403 Forbidden: Requester is not the owner
+/// - 404 Not Found: Notebook not found
+async fn set_write_budget(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+    Json(request): Json<SetWriteBudgetRequest>,
+) -> ApiResult<Json<WriteBudgetResponse>> {
+    let window_secs = validate_budget_request(&request, state.config().write_budget_window_secs)?;
+    require_owner(&state, &identity, notebook_id).await?;
+
+    let budget = state
+        .store()
+        .upsert_write_budget(notebook_id, request.entropy_budget, window_secs)
+        .await
+        .map_err(|e| match e {
+            StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+            other => ApiError::Store(other),
+        })?;
+
+    tracing::info!(
+        notebook_id = %notebook_id,
+        entropy_budget = budget.entropy_budget,
+        window_secs = budget.window_secs,
+        "Write budget set"
+    );
+
+    state
+        .broadcaster()
+        .publish_from_log(state.store(), notebook_id)
+        .await;
+
+    Ok(Json(WriteBudgetResponse::from(budget)))
+}
+
+/// GET /notebooks/{id}/write-budget - Get a notebook's write budget.
+///
+/// # Response
+///
+/// - 200 OK: WriteBudgetResponse
+/// - 403 Forbidden: Requester is not the owner
+/// - 404 Not Found: Notebook not found or no budget configured
+async fn get_write_budget(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+) -> ApiResult<Json<WriteBudgetResponse>> {
+    require_owner(&state, &identity, notebook_id).await?;
+
+    let budget = state
+        .store()
+        .get_write_budget(notebook_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "Notebook {} has no write budget configured",
+                notebook_id
+            ))
+        })?;
+
+    Ok(Json(WriteBudgetResponse::from(budget)))
+}
+
+/// DELETE /notebooks/{id}/write-budget - Remove a notebook's write budget.
+///
+/// The server-wide budget, if any, applies again.
+///
+/// # Response
+///
+/// - 200 OK: `{ "notebook_id": "...", "deleted": true }` (`false` if none was set)
+/// - 403 Forbidden: Requester is not the owner
+/// - 404 Not Found: Notebook not found
+async fn delete_write_budget(
+    State(state): State<AppState>,
+    identity: AuthorIdentity,
+    Path(notebook_id): Path<Uuid>,
+) -> ApiResult<Json<DeleteWriteBudgetResponse>> {
+    require_owner(&state, &identity, notebook_id).await?;
+
+    let deleted = state.store().delete_write_budget(notebook_id).await?;
+    if deleted {
+        state
+            .broadcaster()
+            .publish_from_log(state.store(), notebook_id)
+            .await;
+    }
+
+    Ok(Json(DeleteWriteBudgetResponse {
+        notebook_id,
+        deleted,
+    }))
+}
+
+/// Build write budget routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route(
+        "/notebooks/{id}/write-budget",
+        put(set_write_budget)
+            .get(get_write_budget)
+            .delete(delete_write_budget),
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(entropy_budget: f64, window_secs: Option<u32>) -> SetWriteBudgetRequest {
+        SetWriteBudgetRequest {
+            entropy_budget,
+            window_secs,
+        }
+    }
+
+    #[test]
+    fn test_validate_budget_request() {
+        assert_eq!(
+            validate_budget_request(&request(5.0, Some(60)), 300).unwrap(),
+            60
+        );
+        assert_eq!(
+            validate_budget_request(&request(5.0, None), 300).unwrap(),
+            300
+        );
+
+        assert!(validate_budget_request(&request(0.0, None), 300).is_err());
+        assert!(validate_budget_request(&request(f64::NAN, None), 300).is_err());
+        assert!(validate_budget_request(&request(5.0, Some(0)), 300).is_err());
+        assert!(validate_budget_request(&request(5.0, Some(MAX_WINDOW_SECS + 1)), 300).is_err());
+    }
+
+    #[test]
+    fn test_window_defaults_to_unset() {
+        let request: SetWriteBudgetRequest =
+            serde_json::from_str(r#"{"entropy_budget": 2.5}"#).unwrap();
+        assert_eq!(request.entropy_budget, 2.5);
+        assert_eq!(request.window_secs, None);
+    }
+}
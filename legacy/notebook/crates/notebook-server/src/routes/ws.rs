@@ -0,0 +1,334 @@
+//! WebSocket endpoint for real-time notifications.
+//!
+//! An alternative to the SSE endpoint for clients that prefer WebSockets,
+//! such as browsers behind proxies that buffer event streams or agents that
+//! want a bidirectional channel. Events come from the same
+//! `EventBroadcaster` and are sent as the same JSON payloads, one per text
+//! message.
+//!
+//! Endpoint: GET /notebooks/{notebook_id}/ws
+//!
+//! # Client Messages
+//!
+//! - `{"type":"ping"}`: answered with `{"type":"pong","timestamp":"..."}`
+//! - `{"type":"subscribe","topics":["a","b"]}`: only deliver entry events
+//!   with one of these topics; omit `topics` (or send `null`) to receive all
+//!   entries again. Answered with `{"type":"subscribed","topics":[...]}`.
+//!
+//! Notebook, access, heartbeat and catchup events are always delivered.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use axum::{
+    Router,
+    extract::{
+        Path, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    response::Response,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::{self, error::RecvError};
+use uuid::Uuid;
+
+use notebook_store::StoreError;
+
+use crate::error::ApiError;
+use crate::events::{CatchupEvent, HEARTBEAT_INTERVAL_SECS, HeartbeatEvent, NotebookEvent};
+use crate::state::AppState;
+
+// ============================================================================
+// Message Types
+// ============================================================================
+
+/// A message sent by the client.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    /// Liveness check.
+    Ping,
+    /// Replace the topic filter for entry events.
+    Subscribe {
+        #[serde(default)]
+        topics: Option<Vec<String>>,
+    },
+}
+
+/// A reply to a client message.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerReply {
+    Pong { timestamp: DateTime<Utc> },
+    Subscribed { topics: Option<Vec<String>> },
+    Error { message: String },
+}
+
+/// Topic filter applied to entry events.
+#[derive(Debug, Default)]
+struct TopicFilter(Option<HashSet<String>>);
+
+impl TopicFilter {
+    /// Whether the event should be delivered under this filter.
+    fn allows(&self, event: &NotebookEvent) -> bool {
+        match (&self.0, event) {
+            (Some(topics), NotebookEvent::Entry(entry)) => entry
+                .topic
+                .as_ref()
+                .is_some_and(|topic| topics.contains(topic)),
+            _ => true,
+        }
+    }
+}
+
+/// Apply a client message, returning the reply to send.
+fn handle_client_message(text: &str, filter: &mut TopicFilter) -> ServerReply {
+    match serde_json::from_str::<ClientMessage>(text) {
+        Ok(ClientMessage::Ping) => ServerReply::Pong {
+            timestamp: Utc::now(),
+        },
+        Ok(ClientMessage::Subscribe { topics }) => {
+            filter.0 = topics.clone().map(|topics| topics.into_iter().collect());
+            ServerReply::Subscribed { topics }
+        }
+        Err(e) => ServerReply::Error {
+            message: format!("Invalid message: {}", e),
+        },
+    }
+}
+
+// ============================================================================
+// WebSocket Endpoint
+// ============================================================================
+
+/// GET /notebooks/{notebook_id}/ws - Subscribe to real-time events over a WebSocket.
+///
+/// # Response
+///
+/// - 101 Switching Protocols: WebSocket established
+/// - 404 Not Found: Notebook not found
+///
+/// # Backpressure
+///
+/// As with SSE, a client that falls behind receives a `catchup` event and
+/// should sync via OBSERVE.
+async fn subscribe_ws(
+    State(state): State<AppState>,
+    Path(notebook_id): Path<Uuid>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    // Validate notebook exists
+    state
+        .store()
+        .get_notebook(notebook_id)
+        .await
+        .map_err(|e| match e {
+            StoreError::NotebookNotFound(id) => ApiError::notebook_not_found(id),
+            other => ApiError::Store(other),
+        })?;
+
+    // Subscribe before upgrading so no events are missed during the handshake
+    let (receiver, _) = state
+        .broadcaster()
+        .subscribe_from_log(state.store(), notebook_id)
+        .await?;
+
+    tracing::info!(
+        notebook_id = %notebook_id,
+        "Client subscribed to WebSocket events"
+    );
+
+    Ok(ws.on_upgrade(move |socket| serve_socket(socket, receiver, notebook_id)))
+}
+
+/// Bridge broadcast events to the socket until either side closes.
+async fn serve_socket(
+    mut socket: WebSocket,
+    mut receiver: broadcast::Receiver<NotebookEvent>,
+    notebook_id: Uuid,
+) {
+    let mut filter = TopicFilter::default();
+    let mut last_sequence = 0u64;
+    let mut heartbeat = tokio::time::interval(Duration::from_secs(HEARTBEAT_INTERVAL_SECS));
+    // The first tick completes immediately
+    heartbeat.tick().await;
+
+    loop {
+        let payload = tokio::select! {
+            event = receiver.recv() => match event {
+                Ok(event) => {
+                    if let NotebookEvent::Entry(ref e) = event {
+                        last_sequence = e.sequence;
+                    }
+                    if !filter.allows(&event) {
+                        continue;
+                    }
+                    serde_json::to_string(&event)
+                }
+                Err(RecvError::Lagged(count)) => {
+                    tracing::warn!(
+                        notebook_id = %notebook_id,
+                        events_missed = count,
+                        "WebSocket client lagged, sending catchup event"
+                    );
+                    serde_json::to_string(&NotebookEvent::Catchup(CatchupEvent {
+                        events_missed: count,
+                        current_sequence: last_sequence,
+                        timestamp: Utc::now(),
+                    }))
+                }
+                Err(RecvError::Closed) => {
+                    tracing::debug!(
+                        notebook_id = %notebook_id,
+                        "Event channel closed, closing WebSocket"
+                    );
+                    let _ = socket.send(Message::Close(None)).await;
+                    break;
+                }
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    serde_json::to_string(&handle_client_message(&text, &mut filter))
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // Protocol-level pings are answered by the WebSocket layer
+                Some(Ok(_)) => continue,
+            },
+            _ = heartbeat.tick() => {
+                serde_json::to_string(&NotebookEvent::Heartbeat(HeartbeatEvent {
+                    timestamp: Utc::now(),
+                }))
+            }
+        };
+
+        match payload {
+            Ok(text) => {
+                if socket.send(Message::Text(text.into())).await.is_err() {
+                    break;
+                }
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to serialize WebSocket message"),
+        }
+    }
+
+    tracing::debug!(notebook_id = %notebook_id, "WebSocket client disconnected");
+}
+
+/// Build WebSocket event routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/notebooks/{id}/ws", get(subscribe_ws))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventBroadcaster;
+    use futures::{SinkExt, StreamExt};
+    use notebook_core::IntegrationCost;
+    use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async, tungstenite};
+
+    type Client = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+    /// Serve the socket bridge without the notebook lookup, which needs a database.
+    async fn connect(broadcaster: EventBroadcaster, notebook_id: Uuid) -> Client {
+        let app = Router::new().route(
+            "/ws",
+            get(move |ws: WebSocketUpgrade| async move {
+                let receiver = broadcaster.subscribe(notebook_id).await;
+                ws.on_upgrade(move |socket| serve_socket(socket, receiver, notebook_id))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let (client, _) = connect_async(format!("ws://{}/ws", addr)).await.unwrap();
+        client
+    }
+
+    async fn next_json(client: &mut Client) -> serde_json::Value {
+        loop {
+            match client.next().await.unwrap().unwrap() {
+                tungstenite::Message::Text(text) => return serde_json::from_str(&text).unwrap(),
+                _ => continue,
+            }
+        }
+    }
+
+    async fn send_json(client: &mut Client, value: serde_json::Value) {
+        client
+            .send(tungstenite::Message::Text(value.to_string().into()))
+            .await
+            .unwrap();
+    }
+
+    async fn publish(broadcaster: &EventBroadcaster, notebook_id: Uuid, topic: Option<&str>) {
+        broadcaster
+            .publish_entry(
+                notebook_id,
+                Uuid::new_v4(),
+                "write",
+                topic.map(String::from),
+                IntegrationCost::zero(),
+                1,
+            )
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_published_write_is_delivered() {
+        let broadcaster = EventBroadcaster::new();
+        let notebook_id = Uuid::new_v4();
+        let mut client = connect(broadcaster.clone(), notebook_id).await;
+
+        // A ping round trip guarantees the server side has subscribed
+        send_json(&mut client, serde_json::json!({"type": "ping"})).await;
+        assert_eq!(next_json(&mut client).await["type"], "pong");
+
+        publish(&broadcaster, notebook_id, Some("rust")).await;
+
+        let event = next_json(&mut client).await;
+        assert_eq!(event["type"], "entry");
+        assert_eq!(event["operation"], "write");
+        assert_eq!(event["topic"], "rust");
+        assert_eq!(event["sequence"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_filters_topics() {
+        let broadcaster = EventBroadcaster::new();
+        let notebook_id = Uuid::new_v4();
+        let mut client = connect(broadcaster.clone(), notebook_id).await;
+
+        send_json(
+            &mut client,
+            serde_json::json!({"type": "subscribe", "topics": ["wanted"]}),
+        )
+        .await;
+        let reply = next_json(&mut client).await;
+        assert_eq!(reply["type"], "subscribed");
+        assert_eq!(reply["topics"], serde_json::json!(["wanted"]));
+
+        publish(&broadcaster, notebook_id, Some("other")).await;
+        publish(&broadcaster, notebook_id, None).await;
+        publish(&broadcaster, notebook_id, Some("wanted")).await;
+
+        let event = next_json(&mut client).await;
+        assert_eq!(event["topic"], "wanted");
+    }
+
+    #[test]
+    fn test_invalid_client_message_gets_error_reply() {
+        let mut filter = TopicFilter::default();
+        let reply = serde_json::to_value(handle_client_message("{\"type\":\"nope\"}", &mut filter))
+            .unwrap();
+        assert_eq!(reply["type"], "error");
+        assert!(filter.0.is_none());
+    }
+}
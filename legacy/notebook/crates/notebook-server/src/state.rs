@@ -2,12 +2,19 @@
 
 use std::sync::Arc;
 
-use notebook_entropy::IntegrationCostEngine;
+use notebook_entropy::{CatalogCache, NotebookProfileCache, PropagationMonitor, SearchIndex};
 use notebook_store::Store;
-use tokio::sync::Mutex;
 
+use crate::alerts::{HttpWebhookSender, WebhookSender};
 use crate::config::ServerConfig;
+use crate::content_validation::ContentValidators;
+use crate::engines::EngineShards;
 use crate::events::EventBroadcaster;
+use crate::public_reads::AnonymousReadLimiter;
+use crate::readiness::Readiness;
+use crate::recompute::RecomputeJobs;
+use crate::tasks::BackgroundTasks;
+use crate::throttle::WriteThrottle;
 
 /// Application state shared across all handlers.
 ///
@@ -18,10 +25,32 @@ pub struct AppState {
     store: Arc<Store>,
     /// Server configuration.
     config: Arc<ServerConfig>,
-    /// Integration cost engine for entropy computation.
-    engine: Arc<Mutex<IntegrationCostEngine>>,
+    /// Per-notebook integration cost engines for entropy computation.
+    engines: Arc<EngineShards>,
     /// Event broadcaster for SSE notifications.
     broadcaster: Arc<EventBroadcaster>,
+    /// Background work spawned by handlers, awaited on shutdown.
+    background_tasks: Arc<BackgroundTasks>,
+    /// Generated browse catalogs, keyed by notebook.
+    catalog_cache: Arc<CatalogCache>,
+    /// Delivers entropy alerts to notebook webhooks.
+    webhook_sender: Arc<dyn WebhookSender>,
+    /// Full-text search index, if one is configured.
+    search_index: Option<Arc<SearchIndex>>,
+    /// Recent entropy contributions, for write throttling.
+    write_throttle: Arc<WriteThrottle>,
+    /// Whether startup has finished, for the readiness probe.
+    readiness: Arc<Readiness>,
+    /// Recent anonymous reads per client, for rate limiting.
+    anonymous_reads: Arc<AnonymousReadLimiter>,
+    /// Progress of integration cost recomputes, keyed by notebook.
+    recompute_jobs: Arc<RecomputeJobs>,
+    /// Term profiles for similar notebook discovery, keyed by notebook.
+    notebook_profiles: Arc<NotebookProfileCache>,
+    /// Checks run on the content of new entries.
+    content_validators: Arc<ContentValidators>,
+    /// Progress of the cost propagation worker, if one is running.
+    propagation: Option<PropagationMonitor>,
 }
 
 impl AppState {
@@ -29,12 +58,43 @@ impl AppState {
     pub fn new(store: Store, config: ServerConfig) -> Self {
         Self {
             store: Arc::new(store),
+            engines: Arc::new(EngineShards::with_config(config.cost_config())),
+            content_validators: Arc::new(ContentValidators::from_config(&config)),
+            write_throttle: Arc::new(WriteThrottle::new(config.write_throttle_interval())),
+            anonymous_reads: Arc::new(AnonymousReadLimiter::new(config.anonymous_reads_per_minute)),
+            broadcaster: Arc::new(EventBroadcaster::with_capacity(
+                config.event_channel_capacity,
+            )),
             config: Arc::new(config),
-            engine: Arc::new(Mutex::new(IntegrationCostEngine::new())),
-            broadcaster: Arc::new(EventBroadcaster::new()),
+            background_tasks: Arc::new(BackgroundTasks::new()),
+            catalog_cache: Arc::new(CatalogCache::new()),
+            webhook_sender: Arc::new(HttpWebhookSender::new()),
+            search_index: None,
+            readiness: Arc::new(Readiness::new()),
+            recompute_jobs: Arc::new(RecomputeJobs::new()),
+            notebook_profiles: Arc::new(NotebookProfileCache::new()),
+            propagation: None,
         }
     }
 
+    /// Attach a full-text search index.
+    pub fn with_search_index(mut self, index: SearchIndex) -> Self {
+        self.search_index = Some(Arc::new(index));
+        self
+    }
+
+    /// Replace the content validators, e.g. to register custom ones.
+    pub fn with_content_validators(mut self, validators: ContentValidators) -> Self {
+        self.content_validators = Arc::new(validators);
+        self
+    }
+
+    /// Attach the monitor of a running cost propagation worker.
+    pub fn with_propagation_monitor(mut self, monitor: PropagationMonitor) -> Self {
+        self.propagation = Some(monitor);
+        self
+    }
+
     /// Get a reference to the database store.
     pub fn store(&self) -> &Store {
         &self.store
@@ -45,15 +105,70 @@ impl AppState {
         &self.config
     }
 
-    /// Get a reference to the integration cost engine.
-    pub fn engine(&self) -> &Arc<Mutex<IntegrationCostEngine>> {
-        &self.engine
+    /// Get a reference to the per-notebook integration cost engines.
+    pub fn engines(&self) -> &EngineShards {
+        &self.engines
     }
 
     /// Get a reference to the event broadcaster.
     pub fn broadcaster(&self) -> &Arc<EventBroadcaster> {
         &self.broadcaster
     }
+
+    /// Get a reference to the tracked background tasks.
+    pub fn background_tasks(&self) -> &Arc<BackgroundTasks> {
+        &self.background_tasks
+    }
+
+    /// Get a reference to the browse catalog cache.
+    pub fn catalog_cache(&self) -> &Arc<CatalogCache> {
+        &self.catalog_cache
+    }
+
+    /// Get a reference to the entropy alert webhook sender.
+    pub fn webhook_sender(&self) -> &Arc<dyn WebhookSender> {
+        &self.webhook_sender
+    }
+
+    /// Get a reference to the full-text search index, if configured.
+    pub fn search_index(&self) -> Option<&Arc<SearchIndex>> {
+        self.search_index.as_ref()
+    }
+
+    /// Get a reference to the write throttle.
+    pub fn write_throttle(&self) -> &WriteThrottle {
+        &self.write_throttle
+    }
+
+    /// Get a reference to the startup readiness state.
+    pub fn readiness(&self) -> &Readiness {
+        &self.readiness
+    }
+
+    /// Get a reference to the anonymous read limiter.
+    pub fn anonymous_reads(&self) -> &AnonymousReadLimiter {
+        &self.anonymous_reads
+    }
+
+    /// Get a reference to the integration cost recompute jobs.
+    pub fn recompute_jobs(&self) -> &Arc<RecomputeJobs> {
+        &self.recompute_jobs
+    }
+
+    /// Get a reference to the content validators.
+    pub fn content_validators(&self) -> &ContentValidators {
+        &self.content_validators
+    }
+
+    /// Get a reference to the notebook profile cache.
+    pub fn notebook_profiles(&self) -> &Arc<NotebookProfileCache> {
+        &self.notebook_profiles
+    }
+
+    /// Get a reference to the cost propagation monitor, if a worker runs.
+    pub fn propagation(&self) -> Option<&PropagationMonitor> {
+        self.propagation.as_ref()
+    }
 }
 
 impl std::fmt::Debug for AppState {
@@ -0,0 +1,102 @@
+//! Tracking of background tasks spawned by request handlers.
+//!
+//! Handlers spawn work that outlives the request, such as backfilling an
+//! integration cost that missed its deadline. Spawning through
+//! [`BackgroundTasks`] lets shutdown wait for that work instead of dropping
+//! it when the runtime exits.
+
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::task::JoinSet;
+
+/// Set of in-flight background tasks.
+#[derive(Debug, Default)]
+pub struct BackgroundTasks {
+    tasks: Mutex<JoinSet<()>>,
+}
+
+impl BackgroundTasks {
+    /// Create an empty task set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn a tracked task on the current runtime.
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        if let Ok(mut tasks) = self.tasks.lock() {
+            // Reap finished tasks so the set only holds in-flight work
+            while tasks.try_join_next().is_some() {}
+            tasks.spawn(task);
+        }
+    }
+
+    /// Number of tasks that have not been reaped yet.
+    pub fn len(&self) -> usize {
+        self.tasks.lock().map(|tasks| tasks.len()).unwrap_or(0)
+    }
+
+    /// Whether no tasks are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Wait for all tracked tasks to finish, up to `timeout`.
+    ///
+    /// Tasks still running at the deadline are aborted. Returns the number of
+    /// tasks that were aborted.
+    pub async fn drain(&self, timeout: Duration) -> usize {
+        let mut tasks = match self.tasks.lock() {
+            Ok(mut tasks) => std::mem::take(&mut *tasks),
+            Err(_) => return 0,
+        };
+
+        let finished = tokio::time::timeout(timeout, async {
+            while tasks.join_next().await.is_some() {}
+        })
+        .await;
+
+        if finished.is_ok() {
+            0
+        } else {
+            let remaining = tasks.len();
+            tasks.abort_all();
+            remaining
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[tokio::test]
+    async fn test_drain_waits_for_tasks() {
+        let tasks = BackgroundTasks::new();
+        let done = Arc::new(AtomicBool::new(false));
+
+        let flag = done.clone();
+        tasks.spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            flag.store(true, Ordering::SeqCst);
+        });
+
+        assert_eq!(tasks.drain(Duration::from_secs(1)).await, 0);
+        assert!(done.load(Ordering::SeqCst));
+        assert!(tasks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_drain_aborts_after_timeout() {
+        let tasks = BackgroundTasks::new();
+        tasks.spawn(std::future::pending());
+
+        assert_eq!(tasks.drain(Duration::from_millis(10)).await, 1);
+    }
+}
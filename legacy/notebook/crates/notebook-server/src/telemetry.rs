@@ -0,0 +1,683 @@
+//! Tracing setup: log filtering, trace sampling and span export.
+//!
+//! The subscriber is filtered by [`log_filter`], built from `LOG_LEVEL` and
+//! the per-target `LOG_DIRECTIVES`. [`TraceSampler`] decides which traces
+//! (root spans) are kept: a share of them, unless the caller's `traceparent`
+//! already decided. Sampling only affects export; logs are written for every
+//! request. When `OTLP_ENDPOINT` is set, [`OtlpLayer`] exports the sampled
+//! spans to a collector as OTLP/HTTP JSON.
+//! A request carrying a W3C `traceparent` header joins the caller's trace:
+//! [`request_span`] records the header and the exporter takes its trace and
+//! parent span IDs for the request's root span.
+//!
+//! The exporter is written here rather than built on `tracing-opentelemetry`
+//! and `opentelemetry-otlp`, which are not available to the offline build.
+//! It covers only what the server needs: string attributes, parent links and
+//! batched JSON export over HTTP.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::{Value, json};
+use tokio::sync::{mpsc, oneshot};
+use tracing::Subscriber;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::{LookupSpan, SpanRef};
+use uuid::Uuid;
+
+use crate::config::ServerConfig;
+use crate::middleware::request_id::{TRACEPARENT_HEADER, parse_traceparent};
+
+/// Service name reported with exported spans.
+pub const SERVICE_NAME: &str = "notebook-server";
+
+/// Finished spans buffered for export; further spans are dropped.
+pub const OTLP_QUEUE_CAPACITY: usize = 4096;
+
+/// Most spans sent in one export request.
+pub const OTLP_BATCH_SIZE: usize = 512;
+
+/// Longest time a finished span waits before it is exported.
+pub const OTLP_EXPORT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Time allowed for one export request.
+pub const OTLP_EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The subscriber's filter for a configuration.
+pub fn log_filter(
+    config: &ServerConfig,
+) -> Result<EnvFilter, tracing_subscriber::filter::ParseError> {
+    EnvFilter::try_new(config.log_filter_directives())
+}
+
+// ============================================================================
+// Sampling
+// ============================================================================
+
+/// Sampling decision, stored on root spans.
+#[derive(Debug, Clone, Copy)]
+struct Sampled(bool);
+
+/// Whether the trace a span belongs to was sampled.
+fn is_sampled<S>(span: &SpanRef<'_, S>) -> bool
+where
+    S: for<'a> LookupSpan<'a>,
+{
+    span.scope()
+        .from_root()
+        .next()
+        .and_then(|root| root.extensions().get::<Sampled>().copied())
+        .is_none_or(|sampled| sampled.0)
+}
+
+/// Reads the `traceparent` field of a span.
+#[derive(Default)]
+struct TraceparentVisitor(Option<String>);
+
+impl Visit for TraceparentVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == TRACEPARENT_HEADER {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+/// Layer deciding which traces are exported.
+///
+/// Every root span is a trace. A root span carrying a valid `traceparent`
+/// follows the caller's sampled flag, so this server's part of a sampled
+/// distributed trace is kept. Other traces are kept at an even stride, so a
+/// ratio of 0.25 keeps every fourth one. Events are never filtered.
+#[derive(Debug)]
+pub struct TraceSampler {
+    ratio: f64,
+    traces: AtomicU64,
+}
+
+impl TraceSampler {
+    /// Keep `ratio` of traces, clamped to 0.0-1.0.
+    pub fn new(ratio: f64) -> Self {
+        Self {
+            ratio: ratio.clamp(0.0, 1.0),
+            traces: AtomicU64::new(0),
+        }
+    }
+
+    /// Decide whether the next trace is kept.
+    fn sample_next(&self) -> bool {
+        if self.ratio >= 1.0 {
+            return true;
+        }
+        let n = self.traces.fetch_add(1, Ordering::Relaxed) as f64;
+        ((n + 1.0) * self.ratio).floor() > (n * self.ratio).floor()
+    }
+}
+
+impl<S> Layer<S> for TraceSampler
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        if span.parent().is_some() {
+            return;
+        }
+        let mut traceparent = TraceparentVisitor::default();
+        attrs.record(&mut traceparent);
+        let sampled = match traceparent.0.as_deref().and_then(parse_traceparent) {
+            Some(parent) => parent.sampled,
+            None => self.sample_next(),
+        };
+        span.extensions_mut().insert(Sampled(sampled));
+    }
+}
+
+// ============================================================================
+// OTLP Export
+// ============================================================================
+
+/// Span for an HTTP request, named and filled like `tower_http`'s default,
+/// plus the inbound `traceparent` header if there is one.
+pub fn request_span<B>(request: &http::Request<B>) -> tracing::Span {
+    let traceparent = request
+        .headers()
+        .get(TRACEPARENT_HEADER)
+        .and_then(|value| value.to_str().ok());
+    tracing::debug_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        version = ?request.version(),
+        traceparent,
+    )
+}
+
+/// Timing and fields of a span being recorded for export.
+#[derive(Debug)]
+struct SpanRecord {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    parent_span_id: Option<[u8; 8]>,
+    start: SystemTime,
+    attributes: Vec<(String, String)>,
+}
+
+/// Collects span fields as strings.
+struct AttributeVisitor<'a>(&'a mut Vec<(String, String)>);
+
+impl Visit for AttributeVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().to_string(), value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .push((field.name().to_string(), format!("{:?}", value)));
+    }
+}
+
+/// Trace and parent span IDs from a root span's `traceparent` field.
+fn remote_parent(attributes: &[(String, String)]) -> Option<([u8; 16], [u8; 8])> {
+    let (_, value) = attributes
+        .iter()
+        .find(|(key, _)| key == TRACEPARENT_HEADER)?;
+    let parent = parse_traceparent(value)?;
+    let mut ids = ([0; 16], [0; 8]);
+    hex::decode_to_slice(parent.trace_id, &mut ids.0).ok()?;
+    hex::decode_to_slice(parent.parent_id, &mut ids.1).ok()?;
+    Some(ids)
+}
+
+fn new_span_id() -> [u8; 8] {
+    let bytes = Uuid::new_v4().into_bytes();
+    let mut id = [0; 8];
+    id.copy_from_slice(&bytes[..8]);
+    id
+}
+
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string()
+}
+
+/// Messages from the layer to the export worker.
+enum ExportMessage {
+    Span(Value),
+    Flush(oneshot::Sender<()>),
+}
+
+/// Layer that records sampled spans and hands them to an [`OtlpWorker`].
+#[derive(Debug)]
+pub struct OtlpLayer {
+    sender: mpsc::Sender<ExportMessage>,
+}
+
+impl OtlpLayer {
+    /// Create the layer with the worker that exports its spans to `endpoint`.
+    pub fn new(endpoint: &str) -> (Self, OtlpWorker) {
+        let (sender, receiver) = mpsc::channel(OTLP_QUEUE_CAPACITY);
+        let client = reqwest::Client::builder()
+            .timeout(OTLP_EXPORT_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        let worker = OtlpWorker {
+            url: format!("{}/v1/traces", endpoint.trim_end_matches('/')),
+            client,
+            receiver,
+            sender: sender.clone(),
+        };
+        (Self { sender }, worker)
+    }
+}
+
+impl<S> Layer<S> for OtlpLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let parent = span.parent().and_then(|parent| {
+            parent
+                .extensions()
+                .get::<SpanRecord>()
+                .map(|record| (record.trace_id, record.span_id))
+        });
+        let mut attributes = Vec::new();
+        attrs.record(&mut AttributeVisitor(&mut attributes));
+        // A root span continues the caller's trace if it carries one
+        let (trace_id, parent_span_id) = match parent.or_else(|| remote_parent(&attributes)) {
+            Some((trace_id, span_id)) => (trace_id, Some(span_id)),
+            None => (Uuid::new_v4().into_bytes(), None),
+        };
+        span.extensions_mut().insert(SpanRecord {
+            trace_id,
+            span_id: new_span_id(),
+            parent_span_id,
+            start: SystemTime::now(),
+            attributes,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id)
+            && let Some(record) = span.extensions_mut().get_mut::<SpanRecord>()
+        {
+            values.record(&mut AttributeVisitor(&mut record.attributes));
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        if !is_sampled(&span) {
+            return;
+        }
+        let Some(record) = span.extensions_mut().remove::<SpanRecord>() else {
+            return;
+        };
+        let otlp_span = otlp_span(
+            span.name(),
+            span.metadata().target(),
+            &record,
+            SystemTime::now(),
+        );
+        // A full queue means the collector is behind; drop rather than block
+        let _ = self.sender.try_send(ExportMessage::Span(otlp_span));
+    }
+}
+
+/// A finished span in the OTLP JSON encoding.
+fn otlp_span(name: &str, target: &str, record: &SpanRecord, end: SystemTime) -> Value {
+    let mut attributes: Vec<Value> = record
+        .attributes
+        .iter()
+        .map(|(key, value)| json!({ "key": key, "value": { "stringValue": value } }))
+        .collect();
+    attributes.push(json!({ "key": "code.namespace", "value": { "stringValue": target } }));
+
+    let mut span = json!({
+        "traceId": hex::encode(record.trace_id),
+        "spanId": hex::encode(record.span_id),
+        "name": name,
+        // SPAN_KIND_INTERNAL
+        "kind": 1,
+        "startTimeUnixNano": unix_nanos(record.start),
+        "endTimeUnixNano": unix_nanos(end),
+        "attributes": attributes,
+    });
+    if let Some(parent_span_id) = record.parent_span_id {
+        span["parentSpanId"] = json!(hex::encode(parent_span_id));
+    }
+    span
+}
+
+/// An OTLP/HTTP export request carrying `spans`.
+fn export_request(spans: Vec<Value>) -> Value {
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [
+                    { "key": "service.name", "value": { "stringValue": SERVICE_NAME } }
+                ]
+            },
+            "scopeSpans": [{
+                "scope": { "name": SERVICE_NAME },
+                "spans": spans,
+            }]
+        }]
+    })
+}
+
+/// Background task POSTing batches of spans to the collector.
+#[derive(Debug)]
+pub struct OtlpWorker {
+    url: String,
+    client: reqwest::Client,
+    receiver: mpsc::Receiver<ExportMessage>,
+    sender: mpsc::Sender<ExportMessage>,
+}
+
+impl OtlpWorker {
+    /// Start exporting. Call after the subscriber is installed.
+    ///
+    /// The worker's own HTTP requests are not traced, so exports don't feed
+    /// spans back into the queue.
+    pub fn spawn(self) -> OtlpHandle {
+        use tracing::instrument::WithSubscriber;
+
+        let handle = OtlpHandle {
+            sender: self.sender.clone(),
+        };
+        let dispatch = tracing::dispatcher::get_default(|dispatch| dispatch.clone());
+        tokio::spawn(
+            self.run(dispatch)
+                .with_subscriber(tracing::subscriber::NoSubscriber::default()),
+        );
+        handle
+    }
+
+    async fn run(mut self, dispatch: tracing::Dispatch) {
+        let mut batch = Vec::new();
+        let mut interval = tokio::time::interval(OTLP_EXPORT_INTERVAL);
+        loop {
+            tokio::select! {
+                message = self.receiver.recv() => match message {
+                    Some(ExportMessage::Span(span)) => {
+                        batch.push(span);
+                        if batch.len() >= OTLP_BATCH_SIZE {
+                            self.export(&mut batch, &dispatch).await;
+                        }
+                    }
+                    Some(ExportMessage::Flush(done)) => {
+                        self.export(&mut batch, &dispatch).await;
+                        let _ = done.send(());
+                    }
+                    None => break,
+                },
+                _ = interval.tick() => self.export(&mut batch, &dispatch).await,
+            }
+        }
+    }
+
+    async fn export(&self, batch: &mut Vec<Value>, dispatch: &tracing::Dispatch) {
+        if batch.is_empty() {
+            return;
+        }
+        let count = batch.len();
+        let request = export_request(std::mem::take(batch));
+        let result = match self.client.post(&self.url).json(&request).send().await {
+            Ok(response) if response.status().is_success() => Ok(()),
+            Ok(response) => Err(format!("collector responded with {}", response.status())),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            tracing::dispatcher::with_default(dispatch, || {
+                tracing::warn!(spans = count, error = %e, "Failed to export spans");
+            });
+        }
+    }
+}
+
+/// Handle for flushing the export queue.
+#[derive(Debug, Clone)]
+pub struct OtlpHandle {
+    sender: mpsc::Sender<ExportMessage>,
+}
+
+impl OtlpHandle {
+    /// Export buffered spans, waiting at most `timeout`. Returns whether
+    /// the queue was flushed in time.
+    pub async fn flush(&self, timeout: Duration) -> bool {
+        let (done, flushed) = oneshot::channel();
+        let flush = async {
+            self.sender.send(ExportMessage::Flush(done)).await.is_ok() && flushed.await.is_ok()
+        };
+        tokio::time::timeout(timeout, flush).await.unwrap_or(false)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tracing::Event;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    /// Layer recording the target and message field of each event.
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<String>>>);
+
+    struct MessageVisitor<'a>(&'a mut String);
+
+    impl Visit for MessageVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "message" {
+                *self.0 = format!("{:?}", value);
+            }
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for Captured {
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            let mut message = String::new();
+            event.record(&mut MessageVisitor(&mut message));
+            self.0.lock().unwrap().push(message);
+        }
+    }
+
+    impl Captured {
+        fn messages(&self) -> Vec<String> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    fn config_with(log_level: &str, log_directives: &str) -> ServerConfig {
+        ServerConfig {
+            log_level: log_level.to_string(),
+            log_directives: log_directives.to_string(),
+            ..ServerConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_directives_filter_per_target() {
+        let config = config_with("info", "sqlx=warn,notebook_entropy=debug");
+        let captured = Captured::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(log_filter(&config).unwrap())
+            .with(captured.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(target: "sqlx::query", "sqlx info");
+            tracing::warn!(target: "sqlx::query", "sqlx warn");
+            tracing::debug!(target: "notebook_entropy::engine", "entropy debug");
+            tracing::trace!(target: "notebook_entropy::engine", "entropy trace");
+            tracing::info!(target: "notebook_server", "server info");
+            tracing::debug!(target: "notebook_server", "server debug");
+        });
+
+        assert_eq!(
+            captured.messages(),
+            vec!["sqlx warn", "entropy debug", "server info"]
+        );
+    }
+
+    #[test]
+    fn test_log_level_alone_filters_everything() {
+        let config = config_with("warn", "");
+        let captured = Captured::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(log_filter(&config).unwrap())
+            .with(captured.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(target: "notebook_server", "server info");
+            tracing::error!(target: "notebook_server", "server error");
+            tracing::info!(target: "sqlx::query", "sqlx info");
+        });
+
+        assert_eq!(captured.messages(), vec!["server error"]);
+    }
+
+    #[test]
+    fn test_invalid_directives_are_rejected() {
+        assert!(log_filter(&config_with("info", "sqlx=loud")).is_err());
+    }
+
+    #[test]
+    fn test_sampler_keeps_share_of_traces() {
+        let (layer, mut worker) = OtlpLayer::new("http://collector:4318");
+        let subscriber = tracing_subscriber::registry()
+            .with(TraceSampler::new(0.25))
+            .with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            for i in 0..8 {
+                let span = tracing::info_span!("request", trace = i);
+                let _entered = span.enter();
+                tracing::info_span!("query").in_scope(|| {});
+            }
+        });
+
+        let mut traces = Vec::new();
+        while let Ok(ExportMessage::Span(span)) = worker.receiver.try_recv() {
+            if span["name"] == "request" {
+                traces.push(span["attributes"][0]["value"]["stringValue"].clone());
+            }
+        }
+        assert_eq!(traces, vec!["3", "7"]);
+    }
+
+    #[test]
+    fn test_sampling_does_not_drop_events() {
+        let captured = Captured::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(TraceSampler::new(0.0))
+            .with(captured.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("outside");
+            let span = tracing::info_span!("request");
+            let _entered = span.enter();
+            tracing::info!("unsampled");
+            tracing::warn!("warning");
+        });
+
+        assert_eq!(captured.messages(), vec!["outside", "unsampled", "warning"]);
+    }
+
+    #[test]
+    fn test_sampler_follows_inbound_sampled_flag() {
+        let traceparent =
+            |flags: &str| format!("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-{flags}");
+        for (ratio, flags, exported) in [(0.0, "01", true), (1.0, "00", false)] {
+            let (layer, mut worker) = OtlpLayer::new("http://collector:4318");
+            let subscriber = tracing_subscriber::registry()
+                .with(TraceSampler::new(ratio))
+                .with(layer);
+
+            tracing::subscriber::with_default(subscriber, || {
+                let request = http::Request::builder()
+                    .header(TRACEPARENT_HEADER, traceparent(flags))
+                    .body(())
+                    .unwrap();
+                request_span(&request).in_scope(|| {});
+            });
+
+            assert_eq!(worker.receiver.try_recv().is_ok(), exported, "{flags}");
+        }
+    }
+
+    #[test]
+    fn test_otlp_span_encoding() {
+        let record = SpanRecord {
+            trace_id: [1; 16],
+            span_id: [2; 8],
+            parent_span_id: Some([3; 8]),
+            start: UNIX_EPOCH + Duration::from_secs(1),
+            attributes: vec![("method".to_string(), "GET".to_string())],
+        };
+        let span = otlp_span(
+            "request",
+            "tower_http::trace",
+            &record,
+            UNIX_EPOCH + Duration::from_secs(2),
+        );
+
+        assert_eq!(span["traceId"], "01".repeat(16));
+        assert_eq!(span["spanId"], "02".repeat(8));
+        assert_eq!(span["parentSpanId"], "03".repeat(8));
+        assert_eq!(span["startTimeUnixNano"], "1000000000");
+        assert_eq!(span["endTimeUnixNano"], "2000000000");
+        assert_eq!(span["attributes"][0]["key"], "method");
+        assert_eq!(span["attributes"][0]["value"]["stringValue"], "GET");
+
+        let request = export_request(vec![span]);
+        let scope_spans = &request["resourceSpans"][0]["scopeSpans"][0];
+        assert_eq!(scope_spans["spans"][0]["name"], "request");
+    }
+
+    #[test]
+    fn test_otlp_layer_exports_sampled_spans_with_parents() {
+        let (layer, mut worker) = OtlpLayer::new("http://collector:4318/");
+        assert_eq!(worker.url, "http://collector:4318/v1/traces");
+        let subscriber = tracing_subscriber::registry()
+            .with(TraceSampler::new(0.5))
+            .with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            for _ in 0..2 {
+                let parent = tracing::info_span!("request", path = "/health");
+                let _entered = parent.enter();
+                tracing::info_span!("query").in_scope(|| {});
+            }
+        });
+
+        let mut spans = Vec::new();
+        while let Ok(ExportMessage::Span(span)) = worker.receiver.try_recv() {
+            spans.push(span);
+        }
+        // Only the second trace is sampled; the child closes first
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0]["name"], "query");
+        assert_eq!(spans[1]["name"], "request");
+        assert_eq!(spans[0]["traceId"], spans[1]["traceId"]);
+        assert_eq!(spans[0]["parentSpanId"], spans[1]["spanId"]);
+        assert!(spans[1].get("parentSpanId").is_none());
+        assert_eq!(spans[1]["attributes"][0]["value"]["stringValue"], "/health");
+    }
+
+    #[test]
+    fn test_request_span_joins_inbound_trace() {
+        let (layer, mut worker) = OtlpLayer::new("http://collector:4318");
+        let subscriber = tracing_subscriber::registry().with(layer);
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+        tracing::subscriber::with_default(subscriber, || {
+            for header in [Some(traceparent), Some("not-a-traceparent"), None] {
+                let mut request = http::Request::builder().uri("/health");
+                if let Some(value) = header {
+                    request = request.header(TRACEPARENT_HEADER, value);
+                }
+                let span = request_span(&request.body(()).unwrap());
+                span.in_scope(|| tracing::info_span!("query").in_scope(|| {}));
+            }
+        });
+
+        let mut spans = Vec::new();
+        while let Ok(ExportMessage::Span(span)) = worker.receiver.try_recv() {
+            spans.push(span);
+        }
+        assert_eq!(spans.len(), 6);
+        let (query, request) = (&spans[0], &spans[1]);
+        assert_eq!(request["name"], "request");
+        assert_eq!(request["traceId"], "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(request["parentSpanId"], "00f067aa0ba902b7");
+        assert_eq!(query["traceId"], request["traceId"]);
+        assert_eq!(query["parentSpanId"], request["spanId"]);
+
+        // Without a valid header the request starts its own trace
+        for request in [&spans[3], &spans[5]] {
+            assert_ne!(request["traceId"], "4bf92f3577b34da6a3ce929d0e0e4736");
+            assert!(request.get("parentSpanId").is_none());
+        }
+    }
+}
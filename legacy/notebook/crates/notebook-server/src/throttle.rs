@@ -0,0 +1,400 @@
+//! Per-author write throttling by entropy contribution.
+//!
+//! Each write is charged the entropy it added to the notebook: its catalog
+//! shift, plus one if it landed as an orphan. While an author's charges in a
+//! notebook over the budget window stay within the budget, their writes are
+//! not delayed. Past the budget, consecutive writes must be spaced at least
+//! `interval × spent / budget` apart; earlier writes get 429 Too Many
+//! Requests with a `Retry-After` header. Coherent writes cost little and so
+//! are rarely delayed, however frequent.
+//!
+//! The server-wide budget comes from [`ServerConfig`](crate::config::ServerConfig);
+//! a notebook owner can set a per-notebook budget that replaces it.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use notebook_core::{AuthorId, IntegrationCost};
+use notebook_store::NotebookWriteBudgetRow;
+
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+
+/// Entropy an author may contribute to a notebook within a window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WriteBudget {
+    /// Entropy allowed per window before writes are spaced out.
+    pub entropy_budget: f64,
+    /// Window over which contributions are summed.
+    pub window: Duration,
+}
+
+impl From<&NotebookWriteBudgetRow> for WriteBudget {
+    fn from(row: &NotebookWriteBudgetRow) -> Self {
+        Self {
+            entropy_budget: row.entropy_budget,
+            window: Duration::from_secs(row.window_secs.max(1) as u64),
+        }
+    }
+}
+
+/// Entropy a write contributed to its notebook.
+pub fn entropy_weight(cost: &IntegrationCost) -> f64 {
+    let orphan = if cost.orphan { 1.0 } else { 0.0 };
+    cost.catalog_shift.max(0.0) + orphan
+}
+
+/// Time and weight of an author's recent writes to a notebook, oldest first.
+type RecentWrites = VecDeque<(Instant, f64)>;
+
+/// Least time between sweeps of authors whose writes have all aged out.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Recent writes per notebook and author.
+#[derive(Debug, Default)]
+struct Recent {
+    writes: HashMap<(Uuid, AuthorId), RecentWrites>,
+    /// Longest budget window checked so far; older writes count against no
+    /// budget.
+    longest_window: Duration,
+    /// When aged-out authors were last swept.
+    swept: Option<Instant>,
+}
+
+impl Recent {
+    /// Drop the authors whose latest write is older than every window, so
+    /// authors who stop writing do not stay tracked. Runs at most once per
+    /// [`SWEEP_INTERVAL`].
+    fn sweep(&mut self, now: Instant) {
+        if self
+            .swept
+            .is_some_and(|at| now.saturating_duration_since(at) < SWEEP_INTERVAL)
+        {
+            return;
+        }
+        self.swept = Some(now);
+        let window = self.longest_window;
+        self.writes.retain(|_, writes| {
+            writes
+                .back()
+                .is_some_and(|(at, _)| now.saturating_duration_since(*at) < window)
+        });
+    }
+}
+
+/// Recent entropy contributions per notebook and author.
+#[derive(Debug)]
+pub struct WriteThrottle {
+    /// Minimum spacing of writes for an author at exactly their budget.
+    interval: Duration,
+    /// Recent writes per notebook and author.
+    recent: Mutex<Recent>,
+}
+
+impl WriteThrottle {
+    /// Create a throttle spacing writes at the budget `interval` apart.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            recent: Mutex::new(Recent::default()),
+        }
+    }
+
+    /// Check whether `author` may write to `notebook_id` at `now`.
+    ///
+    /// Returns how much longer the author must wait if not. Also sweeps out
+    /// other authors whose writes have all aged out.
+    pub fn check(
+        &self,
+        notebook_id: Uuid,
+        author: AuthorId,
+        budget: &WriteBudget,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let mut guard = self.recent.lock().expect("write throttle lock poisoned");
+        guard.longest_window = guard.longest_window.max(budget.window);
+        guard.sweep(now);
+
+        let recent = &mut guard.writes;
+        let key = (notebook_id, author);
+        let Some(writes) = recent.get_mut(&key) else {
+            return Ok(());
+        };
+        while writes
+            .front()
+            .is_some_and(|(at, _)| now.saturating_duration_since(*at) >= budget.window)
+        {
+            writes.pop_front();
+        }
+        let Some(&(last, _)) = writes.back() else {
+            recent.remove(&key);
+            return Ok(());
+        };
+
+        let spent: f64 = writes.iter().map(|(_, weight)| weight).sum();
+        if spent <= budget.entropy_budget {
+            return Ok(());
+        }
+        let required = self.interval.mul_f64(spent / budget.entropy_budget);
+        let elapsed = now.saturating_duration_since(last);
+        if elapsed < required {
+            Err(required - elapsed)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Charge `author` for a write to `notebook_id` made at `at`.
+    pub fn record(&self, notebook_id: Uuid, author: AuthorId, cost: &IntegrationCost, at: Instant) {
+        let mut recent = self.recent.lock().expect("write throttle lock poisoned");
+        let writes = recent.writes.entry((notebook_id, author)).or_default();
+        // Backfilled costs may arrive after later writes; keep the order
+        let position = writes.partition_point(|(when, _)| *when <= at);
+        writes.insert(position, (at, entropy_weight(cost)));
+    }
+}
+
+/// A write to charge against its author's budget once its cost is known.
+#[derive(Debug, Clone, Copy)]
+pub struct WriteCharge {
+    notebook_id: Uuid,
+    author: AuthorId,
+    at: Instant,
+}
+
+impl WriteCharge {
+    /// Charge the write's entropy to its author.
+    pub fn apply(&self, throttle: &WriteThrottle, cost: &IntegrationCost) {
+        throttle.record(self.notebook_id, self.author, cost, self.at);
+    }
+}
+
+/// The write budget that applies to `notebook_id`.
+///
+/// A per-notebook budget replaces the server-wide one; with neither, writes
+/// are not throttled.
+pub async fn write_budget(state: &AppState, notebook_id: Uuid) -> ApiResult<Option<WriteBudget>> {
+    match state.store().get_write_budget(notebook_id).await? {
+        Some(row) => Ok(Some(WriteBudget::from(&row))),
+        None => Ok(state.config().write_budget()),
+    }
+}
+
+/// Reject a write by `author` that comes too soon after they exceeded the
+/// notebook's write budget.
+///
+/// Returns the charge to apply once the write's cost is known, if a budget
+/// applies.
+pub async fn enforce_write_budget(
+    state: &AppState,
+    notebook_id: Uuid,
+    author: AuthorId,
+) -> ApiResult<Option<WriteCharge>> {
+    let Some(budget) = write_budget(state, notebook_id).await? else {
+        return Ok(None);
+    };
+    let now = Instant::now();
+    state
+        .write_throttle()
+        .check(notebook_id, author, &budget, now)
+        .map_err(|retry_after| {
+            tracing::info!(
+                notebook_id = %notebook_id,
+                author = %author,
+                retry_after_ms = retry_after.as_millis() as u64,
+                "Write throttled: entropy budget exceeded"
+            );
+            ApiError::Throttled {
+                message: format!(
+                    "Entropy budget for notebook {} exceeded; retry in {:.1}s",
+                    notebook_id,
+                    retry_after.as_secs_f64()
+                ),
+                retry_after,
+            }
+        })?;
+    Ok(Some(WriteCharge {
+        notebook_id,
+        author,
+        at: now,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_secs(10);
+
+    fn budget(entropy_budget: f64) -> WriteBudget {
+        WriteBudget {
+            entropy_budget,
+            window: Duration::from_secs(300),
+        }
+    }
+
+    fn cost(catalog_shift: f64, orphan: bool) -> IntegrationCost {
+        IntegrationCost {
+            catalog_shift,
+            orphan,
+            ..IntegrationCost::zero()
+        }
+    }
+
+    /// Write every `gap` while allowed; returns how many writes were throttled.
+    fn burst(
+        throttle: &WriteThrottle,
+        budget: &WriteBudget,
+        cost: &IntegrationCost,
+        writes: u32,
+        gap: Duration,
+    ) -> u32 {
+        let notebook = Uuid::nil();
+        let author = AuthorId::zero();
+        let start = Instant::now();
+        let mut throttled = 0;
+        for i in 0..writes {
+            let now = start + gap * i;
+            match throttle.check(notebook, author, budget, now) {
+                Ok(()) => throttle.record(notebook, author, cost, now),
+                Err(_) => throttled += 1,
+            }
+        }
+        throttled
+    }
+
+    #[test]
+    fn test_entropy_weight() {
+        assert_eq!(entropy_weight(&IntegrationCost::zero()), 0.0);
+        assert_eq!(entropy_weight(&cost(0.25, false)), 0.25);
+        assert_eq!(entropy_weight(&cost(0.5, true)), 1.5);
+    }
+
+    #[test]
+    fn test_burst_of_high_cost_writes_is_throttled() {
+        let throttle = WriteThrottle::new(INTERVAL);
+        let throttled = burst(
+            &throttle,
+            &budget(2.0),
+            &cost(0.9, true),
+            10,
+            Duration::from_secs(1),
+        );
+        // Two writes fit the budget; the third pushes past it
+        assert!(throttled >= 5, "only {} writes throttled", throttled);
+    }
+
+    #[test]
+    fn test_low_cost_coherent_writes_are_not_throttled() {
+        let throttle = WriteThrottle::new(INTERVAL);
+        let throttled = burst(
+            &throttle,
+            &budget(2.0),
+            &cost(0.01, false),
+            100,
+            Duration::from_secs(1),
+        );
+        assert_eq!(throttled, 0);
+    }
+
+    #[test]
+    fn test_retry_after_grows_with_overspend() {
+        let throttle = WriteThrottle::new(INTERVAL);
+        let notebook = Uuid::nil();
+        let author = AuthorId::zero();
+        let budget = budget(1.0);
+        let now = Instant::now();
+
+        throttle.record(notebook, author, &cost(1.0, true), now);
+        let wait = throttle.check(notebook, author, &budget, now).unwrap_err();
+        assert_eq!(wait, INTERVAL * 2);
+
+        throttle.record(notebook, author, &cost(1.0, true), now);
+        let wait = throttle.check(notebook, author, &budget, now).unwrap_err();
+        assert_eq!(wait, INTERVAL * 4);
+
+        // Waiting out the spacing lets the next write through
+        assert!(
+            throttle
+                .check(notebook, author, &budget, now + wait)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_budget_recovers_after_window() {
+        let throttle = WriteThrottle::new(INTERVAL);
+        let notebook = Uuid::nil();
+        let author = AuthorId::zero();
+        let budget = budget(1.0);
+        let now = Instant::now();
+
+        throttle.record(notebook, author, &cost(0.0, true), now);
+        throttle.record(notebook, author, &cost(0.0, true), now);
+        assert!(throttle.check(notebook, author, &budget, now).is_err());
+        assert!(
+            throttle
+                .check(notebook, author, &budget, now + budget.window)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_budgets_are_per_author_and_notebook() {
+        let throttle = WriteThrottle::new(INTERVAL);
+        let notebook = Uuid::nil();
+        let author = AuthorId::zero();
+        let budget = budget(1.0);
+        let now = Instant::now();
+
+        throttle.record(notebook, author, &cost(1.0, true), now);
+        assert!(throttle.check(notebook, author, &budget, now).is_err());
+        assert!(
+            throttle
+                .check(notebook, AuthorId::from_bytes([1; 32]), &budget, now)
+                .is_ok()
+        );
+        assert!(throttle.check(Uuid::new_v4(), author, &budget, now).is_ok());
+    }
+
+    #[test]
+    fn test_aged_out_authors_are_swept() {
+        let throttle = WriteThrottle::new(INTERVAL);
+        let notebook = Uuid::nil();
+        let budget = budget(1.0);
+        let now = Instant::now();
+        let tracked = || throttle.recent.lock().unwrap().writes.len();
+
+        for author in [AuthorId::zero(), AuthorId::from_bytes([1; 32])] {
+            throttle.check(notebook, author, &budget, now).unwrap();
+            throttle.record(notebook, author, &cost(0.5, false), now);
+        }
+        assert_eq!(tracked(), 2);
+
+        // Another author's check clears writes older than the window
+        let later = now + budget.window;
+        let other = AuthorId::from_bytes([2; 32]);
+        assert!(throttle.check(notebook, other, &budget, later).is_ok());
+        assert_eq!(tracked(), 0);
+    }
+
+    #[test]
+    fn test_budget_from_row() {
+        let row = NotebookWriteBudgetRow {
+            notebook_id: Uuid::nil(),
+            entropy_budget: 3.5,
+            window_secs: 60,
+            updated: chrono::Utc::now(),
+        };
+        assert_eq!(
+            WriteBudget::from(&row),
+            WriteBudget {
+                entropy_budget: 3.5,
+                window: Duration::from_secs(60),
+            }
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! Catalog cache warmup on startup.
+//!
+//! Browsing a notebook whose catalog is not cached rebuilds its coherence
+//! snapshot from every entry, so the first browse of each notebook after a
+//! restart is slow. With `catalog_warmup` enabled, the server pre-generates
+//! catalogs for the most recently active notebooks in the background, a
+//! bounded number at a time, before anyone asks for them.
+
+use std::future::Future;
+use std::time::Instant;
+
+use futures::StreamExt;
+use uuid::Uuid;
+
+use notebook_core::{Entry, EntryId, NotebookId};
+use notebook_entropy::CatalogCache;
+use notebook_store::EntryQuery;
+
+use crate::config::ServerConfig;
+use crate::error::ApiResult;
+use crate::routes::browse::{generate_catalog, pinned_entry_ids, rows_to_entries};
+use crate::state::AppState;
+
+/// Generate and cache catalogs for `notebooks`, at most `concurrency` at once.
+///
+/// `load` returns a notebook's entries, the IDs of its pinned entries and
+/// its current sequence. Notebooks
+/// that fail to load are logged and skipped. Returns the number of catalogs
+/// cached.
+pub async fn warm_catalogs<F, Fut>(
+    cache: &CatalogCache,
+    config: &ServerConfig,
+    notebooks: Vec<Uuid>,
+    concurrency: usize,
+    load: F,
+) -> usize
+where
+    F: Fn(Uuid) -> Fut,
+    Fut: Future<Output = ApiResult<(Vec<Entry>, Vec<EntryId>, u64)>>,
+{
+    futures::stream::iter(notebooks)
+        .map(|notebook_id| {
+            let loaded = load(notebook_id);
+            async move { (notebook_id, loaded.await) }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .map(|(notebook_id, loaded)| match loaded {
+            Ok((entries, pinned, sequence)) => {
+                let catalog = generate_catalog(config, &entries, &pinned, config.catalog_locale);
+                cache.set(NotebookId::from_uuid(notebook_id), catalog, sequence);
+                1
+            }
+            Err(e) => {
+                tracing::warn!(
+                    notebook_id = %notebook_id,
+                    error = %e,
+                    "Failed to warm catalog"
+                );
+                0
+            }
+        })
+        .fold(0, |warmed, n| async move { warmed + n })
+        .await
+}
+
+/// Warm the catalog cache for the most recently active notebooks.
+///
+/// Meant to be spawned on startup; runs off the request path and only logs
+/// failures.
+pub async fn warm_catalog_cache(state: AppState) {
+    let config = state.config();
+    let started = Instant::now();
+
+    let notebooks = match state
+        .store()
+        .recently_active_notebooks(config.catalog_warmup_notebooks as i64)
+        .await
+    {
+        Ok(notebooks) => notebooks,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to list notebooks for catalog warmup");
+            return;
+        }
+    };
+    let requested = notebooks.len();
+
+    let store = state.store();
+    let warmed = warm_catalogs(
+        state.catalog_cache(),
+        config,
+        notebooks,
+        config.catalog_warmup_concurrency,
+        |notebook_id| async move {
+            // Read the sequence first so a concurrent write leaves the
+            // cached catalog looking stale rather than fresh
+            let notebook = store.get_notebook(notebook_id).await?;
+            let rows = store.query_entries(&EntryQuery::new(notebook_id)).await?;
+            Ok((
+                rows_to_entries(&rows)?,
+                pinned_entry_ids(&rows),
+                notebook.current_sequence as u64,
+            ))
+        },
+    )
+    .await;
+
+    tracing::info!(
+        notebooks = requested,
+        warmed,
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "Catalog warmup complete"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use notebook_core::{AuthorId, EntryBuilder};
+
+    use crate::error::ApiError;
+
+    fn entry(content: &str) -> Entry {
+        EntryBuilder::default()
+            .content(content.as_bytes().to_vec())
+            .content_type("text/plain")
+            .author(AuthorId::zero())
+            .build()
+    }
+
+    /// Three notebooks with a couple of entries each.
+    fn fixture() -> HashMap<Uuid, Vec<Entry>> {
+        [
+            vec!["tokio async runtime", "spawning tokio tasks"],
+            vec!["sourdough starter", "baking bread at home"],
+            vec!["watering houseplants"],
+        ]
+        .into_iter()
+        .map(|contents| (Uuid::new_v4(), contents.into_iter().map(entry).collect()))
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn test_warmed_notebooks_are_fresh() {
+        let notebooks = fixture();
+        let ids: Vec<Uuid> = notebooks.keys().copied().collect();
+        let (warm, cold) = ids.split_at(2);
+        let cache = CatalogCache::new();
+
+        let warmed = warm_catalogs(&cache, &ServerConfig::default(), warm.to_vec(), 2, |id| {
+            let entries = notebooks[&id].clone();
+            async move { Ok((entries, vec![], 7)) }
+        })
+        .await;
+
+        assert_eq!(warmed, 2);
+        for id in warm {
+            let nb_id = NotebookId::from_uuid(*id);
+            assert!(cache.is_fresh(&nb_id));
+            let cached = cache.get(&nb_id).unwrap();
+            assert_eq!(cached.cached_at_sequence, 7);
+            assert_eq!(cached.catalog.total_entries as usize, notebooks[id].len());
+        }
+        assert!(!cache.is_fresh(&NotebookId::from_uuid(cold[0])));
+    }
+
+    #[tokio::test]
+    async fn test_failed_notebook_is_skipped() {
+        let notebooks = fixture();
+        let ids: Vec<Uuid> = notebooks.keys().copied().collect();
+        let failing = ids[0];
+        let cache = CatalogCache::new();
+
+        let warmed = warm_catalogs(&cache, &ServerConfig::default(), ids.clone(), 4, |id| {
+            let entries = notebooks[&id].clone();
+            async move {
+                if id == failing {
+                    Err(ApiError::Internal("boom".to_string()))
+                } else {
+                    Ok((entries, vec![], 1))
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(warmed, ids.len() - 1);
+        assert!(!cache.is_fresh(&NotebookId::from_uuid(failing)));
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_is_bounded() {
+        let ids: Vec<Uuid> = (0..8).map(|_| Uuid::new_v4()).collect();
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let cache = CatalogCache::new();
+
+        let warmed = warm_catalogs(&cache, &ServerConfig::default(), ids, 3, |_| {
+            let (in_flight, peak) = (&in_flight, &peak);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok((vec![entry("content")], vec![], 1))
+            }
+        })
+        .await;
+
+        assert_eq!(warmed, 8);
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+    }
+}
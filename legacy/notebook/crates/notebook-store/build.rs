@@ -7,6 +7,20 @@ const MIGRATION_FILES: &[&str] = &[
     "003_graph.sql",
     "004_coherence_links.sql",
     "006_notebook_sequence.sql",
+    "022_notebook_lock.sql",
+    "023_entry_compression.sql",
+    "024_notebook_events.sql",
+    "025_notebook_encryption.sql",
+    "026_notebook_alerts.sql",
+    "027_entry_pins.sql",
+    "028_notebook_content_policies.sql",
+    "029_notebook_write_budgets.sql",
+    "030_graph_reference_edges.sql",
+    "031_notebook_visibility.sql",
+    "032_notebook_name_per_owner.sql",
+    "033_notebook_orphan_policy.sql",
+    "034_entry_cost_computed.sql",
+    "035_notebook_labels.sql",
 ];
 
 fn main() {
@@ -80,6 +80,21 @@ impl CausalPositionService {
         pool: &PgPool,
         notebook_id: NotebookId,
         author_id: AuthorId,
+    ) -> StoreResult<CausalPosition> {
+        Self::assign_position_after(pool, notebook_id, author_id, None).await
+    }
+
+    /// Like [`assign_position`](Self::assign_position), but only if the
+    /// notebook's sequence has not moved past `expected_sequence`.
+    ///
+    /// The check and the increment are one statement, so of several writers
+    /// expecting the same sequence exactly one gets a position; the others
+    /// fail with [`StoreError::SequenceConflict`]. `None` skips the check.
+    pub async fn assign_position_after(
+        pool: &PgPool,
+        notebook_id: NotebookId,
+        author_id: AuthorId,
+        expected_sequence: Option<i64>,
     ) -> StoreResult<CausalPosition> {
         let notebook_uuid = *notebook_id.as_uuid();
         let author_bytes = author_id.as_bytes();
@@ -94,17 +109,33 @@ impl CausalPositionService {
             r#"
             UPDATE notebooks
             SET current_sequence = current_sequence + 1
-            WHERE id = $1
+            WHERE id = $1 AND ($2::BIGINT IS NULL OR current_sequence <= $2)
             RETURNING current_sequence
             "#,
         )
         .bind(notebook_uuid)
+        .bind(expected_sequence)
         .fetch_optional(&mut *tx)
         .await?;
 
         let next_sequence = match next_seq_row {
             Some((seq,)) => seq,
-            None => return Err(StoreError::NotebookNotFound(notebook_uuid)),
+            None => {
+                // Either the notebook is missing or it moved past the expectation
+                let current: Option<(i64,)> =
+                    sqlx::query_as("SELECT current_sequence FROM notebooks WHERE id = $1")
+                        .bind(notebook_uuid)
+                        .fetch_optional(&mut *tx)
+                        .await?;
+                return Err(match (current, expected_sequence) {
+                    (Some((current,)), Some(expected)) => StoreError::SequenceConflict {
+                        notebook_id: notebook_uuid,
+                        current,
+                        expected,
+                    },
+                    _ => StoreError::NotebookNotFound(notebook_uuid),
+                });
+            }
         };
 
         // Compute total_notebook_entries (current count before this entry)
@@ -316,7 +347,7 @@ impl CausalPositionService {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+
     use notebook_core::{ActivityContext, CausalPosition};
 
     #[test]
@@ -437,6 +468,51 @@ mod integration_tests {
         assert_eq!(pos3.activity_context.total_notebook_entries, 2);
     }
 
+    #[tokio::test]
+    async fn test_expected_sequence_admits_one_of_concurrent_writers() {
+        let pool = setup_test_db().await;
+        let author = create_test_author(&pool).await;
+        let notebook = create_test_notebook(&pool, author).await;
+
+        // Every writer observed the notebook at sequence 0
+        let mut tasks = JoinSet::new();
+        for _ in 0..10 {
+            let pool = pool.clone();
+            tasks.spawn(async move {
+                CausalPositionService::assign_position_after(&pool, notebook, author, Some(0)).await
+            });
+        }
+
+        let mut assigned = Vec::new();
+        let mut conflicts = 0;
+        while let Some(result) = tasks.join_next().await {
+            match result.expect("Task panicked") {
+                Ok(position) => assigned.push(position.sequence),
+                Err(StoreError::SequenceConflict {
+                    current, expected, ..
+                }) => {
+                    assert_eq!((current, expected), (1, 0));
+                    conflicts += 1;
+                }
+                Err(e) => panic!("Unexpected error: {}", e),
+            }
+        }
+        assert_eq!(assigned, vec![1]);
+        assert_eq!(conflicts, 9);
+
+        // An up-to-date expectation, or none, still gets a position
+        let position =
+            CausalPositionService::assign_position_after(&pool, notebook, author, Some(1))
+                .await
+                .unwrap();
+        assert_eq!(position.sequence, 2);
+        let unknown = NotebookId::new();
+        assert!(matches!(
+            CausalPositionService::assign_position_after(&pool, unknown, author, Some(0)).await,
+            Err(StoreError::NotebookNotFound(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_assign_position_concurrent() {
         let pool = setup_test_db().await;
@@ -0,0 +1,131 @@
+//! Transparent compression of entry content at rest.
+//!
+//! Entry content above a size threshold is zstd-compressed before it is
+//! written, and the codec is recorded in the `compression` column. Rows are
+//! decompressed as they are read, so callers always see the original bytes.
+//!
+//! Content that is small, or that doesn't shrink meaningfully (images,
+//! archives, already-compressed data), is stored raw.
+
+use std::borrow::Cow;
+
+/// Codec name stored in the `compression` column for zstd-compressed content.
+pub const ZSTD: &str = "zstd";
+
+/// Default minimum content size, in bytes, before compression is attempted.
+pub const DEFAULT_THRESHOLD_BYTES: usize = 4096;
+
+/// Default zstd compression level.
+pub const DEFAULT_LEVEL: i32 = 3;
+
+/// Compressed content must be at most this fraction of the original size to
+/// be stored compressed; otherwise the raw bytes are kept.
+const MIN_SAVINGS_RATIO: f64 = 0.9;
+
+/// Settings for compressing entry content at rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionConfig {
+    /// Content smaller than this many bytes is stored raw.
+    pub threshold_bytes: usize,
+    /// zstd compression level (1-22).
+    pub level: i32,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            threshold_bytes: DEFAULT_THRESHOLD_BYTES,
+            level: DEFAULT_LEVEL,
+        }
+    }
+}
+
+/// Compress content for storage.
+///
+/// Returns the bytes to store and the codec used, or `None` if the content
+/// is stored raw.
+pub fn compress<'a>(
+    content: &'a [u8],
+    config: &CompressionConfig,
+) -> (Cow<'a, [u8]>, Option<&'static str>) {
+    if content.len() < config.threshold_bytes {
+        return (Cow::Borrowed(content), None);
+    }
+
+    match zstd::bulk::compress(content, config.level) {
+        Ok(compressed) if (compressed.len() as f64) <= content.len() as f64 * MIN_SAVINGS_RATIO => {
+            (Cow::Owned(compressed), Some(ZSTD))
+        }
+        Ok(_) => (Cow::Borrowed(content), None),
+        Err(e) => {
+            tracing::warn!("Failed to compress entry content, storing raw: {}", e);
+            (Cow::Borrowed(content), None)
+        }
+    }
+}
+
+/// Decompress stored content according to its recorded codec.
+pub fn decompress(stored: Vec<u8>, compression: Option<&str>) -> Result<Vec<u8>, String> {
+    match compression {
+        None => Ok(stored),
+        Some(ZSTD) => zstd::stream::decode_all(stored.as_slice())
+            .map_err(|e| format!("Failed to decompress entry content: {}", e)),
+        Some(other) => Err(format!("Unknown content compression '{}'", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::RngCore;
+
+    #[test]
+    fn test_large_compressible_content_round_trips() {
+        let content = "The quick brown fox jumps over the lazy dog.\n"
+            .repeat(1000)
+            .into_bytes();
+        let config = CompressionConfig::default();
+
+        let (stored, codec) = compress(&content, &config);
+        assert_eq!(codec, Some(ZSTD));
+        assert!(stored.len() < content.len() / 10);
+
+        let restored = decompress(stored.into_owned(), codec).unwrap();
+        assert_eq!(restored, content);
+    }
+
+    #[test]
+    fn test_small_content_is_stored_raw() {
+        let content = b"short note".to_vec();
+        let (stored, codec) = compress(&content, &CompressionConfig::default());
+        assert_eq!(codec, None);
+        assert_eq!(stored.as_ref(), content.as_slice());
+    }
+
+    #[test]
+    fn test_incompressible_content_is_stored_raw() {
+        let mut content = vec![0u8; 64 * 1024];
+        rand::thread_rng().fill_bytes(&mut content);
+
+        let (stored, codec) = compress(&content, &CompressionConfig::default());
+        assert_eq!(codec, None);
+        assert_eq!(stored.as_ref(), content.as_slice());
+    }
+
+    #[test]
+    fn test_threshold_is_configurable() {
+        let content = "abc".repeat(100).into_bytes();
+        let config = CompressionConfig {
+            threshold_bytes: 64,
+            level: 19,
+        };
+        let (_, codec) = compress(&content, &config);
+        assert_eq!(codec, Some(ZSTD));
+    }
+
+    #[test]
+    fn test_unknown_codec_is_an_error() {
+        assert!(decompress(vec![1, 2, 3], Some("lz4")).is_err());
+        assert_eq!(decompress(vec![1, 2, 3], None).unwrap(), vec![1, 2, 3]);
+    }
+}
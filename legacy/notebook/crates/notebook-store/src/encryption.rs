@@ -0,0 +1,250 @@
+//! Encryption of entry content at rest for private notebooks.
+//!
+//! A notebook created with `encrypted = true` gets its own random data key.
+//! The data key is stored in `notebooks.data_key`, wrapped (encrypted) by the
+//! server master key, so the database alone is not enough to read content.
+//!
+//! Entry content in an encrypted notebook is compressed first (compression
+//! after encryption would gain nothing) and then sealed with the notebook's
+//! data key. The cipher is recorded in the `encryption` column and rows are
+//! decrypted as they are read, so callers always see the original bytes.
+//!
+//! Sealed values are `nonce || ciphertext || tag` using AES-256-GCM with a
+//! random 96-bit nonce. The associated data binds each value to its owner:
+//! the notebook id for a wrapped data key, the entry id for entry content,
+//! so ciphertext copied onto another row fails to open.
+
+use std::fmt;
+
+use ring::aead::{AES_256_GCM as RING_AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use uuid::Uuid;
+
+/// Cipher name stored in the `encryption` column for sealed content.
+pub const AES_256_GCM: &str = "aes-256-gcm";
+
+/// Length of master and data keys, in bytes.
+pub const KEY_LEN: usize = 32;
+
+/// Environment variable holding the hex-encoded master key.
+pub const MASTER_KEY_ENV: &str = "NOTEBOOK_MASTER_KEY";
+
+/// Server master key used to wrap notebook data keys.
+#[derive(Clone, PartialEq, Eq)]
+pub struct MasterKey([u8; KEY_LEN]);
+
+impl MasterKey {
+    /// Create a master key from raw bytes.
+    pub fn from_bytes(bytes: [u8; KEY_LEN]) -> Self {
+        Self(bytes)
+    }
+
+    /// Parse a hex-encoded 32-byte master key.
+    pub fn from_hex(hex_key: &str) -> Result<Self, String> {
+        let bytes = hex::decode(hex_key.trim())
+            .map_err(|e| format!("master key is not valid hex: {}", e))?;
+        let bytes: [u8; KEY_LEN] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+            format!("master key must be {} bytes, got {}", KEY_LEN, bytes.len())
+        })?;
+        Ok(Self(bytes))
+    }
+
+    /// Wrap a notebook's data key for storage.
+    pub fn wrap(&self, notebook_id: Uuid, data_key: &DataKey) -> Result<Vec<u8>, String> {
+        seal_with(&self.0, notebook_id.as_bytes(), &data_key.0)
+    }
+
+    /// Unwrap a stored data key for a notebook.
+    pub fn unwrap(&self, notebook_id: Uuid, wrapped: &[u8]) -> Result<DataKey, String> {
+        let bytes = open_with(&self.0, notebook_id.as_bytes(), wrapped).map_err(|e| {
+            format!(
+                "failed to unwrap data key of notebook {}: {}",
+                notebook_id, e
+            )
+        })?;
+        let bytes: [u8; KEY_LEN] = bytes
+            .try_into()
+            .map_err(|_| format!("data key of notebook {} has the wrong length", notebook_id))?;
+        Ok(DataKey(bytes))
+    }
+}
+
+impl fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MasterKey(..)")
+    }
+}
+
+/// Per-notebook key that seals entry content.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DataKey([u8; KEY_LEN]);
+
+impl DataKey {
+    /// Generate a fresh random data key.
+    pub fn generate() -> Result<Self, String> {
+        let mut bytes = [0u8; KEY_LEN];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| "failed to generate data key".to_string())?;
+        Ok(Self(bytes))
+    }
+
+    /// Seal entry content for storage.
+    pub fn seal(&self, entry_id: Uuid, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+        seal_with(&self.0, entry_id.as_bytes(), plaintext)
+    }
+
+    /// Open stored entry content according to its recorded cipher.
+    pub fn open(&self, entry_id: Uuid, sealed: &[u8], cipher: &str) -> Result<Vec<u8>, String> {
+        match cipher {
+            AES_256_GCM => open_with(&self.0, entry_id.as_bytes(), sealed)
+                .map_err(|e| format!("failed to decrypt entry {}: {}", entry_id, e)),
+            other => Err(format!("Unknown content encryption '{}'", other)),
+        }
+    }
+}
+
+impl fmt::Debug for DataKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DataKey(..)")
+    }
+}
+
+fn key(bytes: &[u8; KEY_LEN]) -> Result<LessSafeKey, String> {
+    UnboundKey::new(&RING_AES_256_GCM, bytes)
+        .map(LessSafeKey::new)
+        .map_err(|_| "invalid key".to_string())
+}
+
+fn seal_with(key_bytes: &[u8; KEY_LEN], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| "failed to generate nonce".to_string())?;
+
+    let mut in_out = plaintext.to_vec();
+    key(key_bytes)?
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(aad),
+            &mut in_out,
+        )
+        .map_err(|_| "encryption failed".to_string())?;
+
+    let mut sealed = Vec::with_capacity(NONCE_LEN + in_out.len());
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&in_out);
+    Ok(sealed)
+}
+
+fn open_with(key_bytes: &[u8; KEY_LEN], aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>, String> {
+    let key = key(key_bytes)?;
+    if sealed.len() < NONCE_LEN + key.algorithm().tag_len() {
+        return Err("sealed value is truncated".to_string());
+    }
+
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| "invalid nonce")?;
+    let mut in_out = ciphertext.to_vec();
+    let len = key
+        .open_in_place(nonce, Aad::from(aad), &mut in_out)
+        .map_err(|_| "authentication failed".to_string())?
+        .len();
+    in_out.truncate(len);
+    Ok(in_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn master_key() -> MasterKey {
+        MasterKey::from_bytes([7u8; KEY_LEN])
+    }
+
+    #[test]
+    fn test_seal_round_trips_and_hides_plaintext() {
+        let key = DataKey::generate().unwrap();
+        let entry_id = Uuid::new_v4();
+        let plaintext = b"patient notes: confidential".to_vec();
+
+        let sealed = key.seal(entry_id, &plaintext).unwrap();
+        assert!(!sealed.windows(12).any(|w| w == b"confidential"));
+        assert_eq!(key.open(entry_id, &sealed, AES_256_GCM).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_sealing_twice_gives_different_ciphertext() {
+        let key = DataKey::generate().unwrap();
+        let entry_id = Uuid::new_v4();
+        assert_ne!(
+            key.seal(entry_id, b"same").unwrap(),
+            key.seal(entry_id, b"same").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_content_is_bound_to_its_entry() {
+        let key = DataKey::generate().unwrap();
+        let sealed = key.seal(Uuid::new_v4(), b"moved").unwrap();
+        assert!(key.open(Uuid::new_v4(), &sealed, AES_256_GCM).is_err());
+    }
+
+    #[test]
+    fn test_tampered_or_truncated_content_is_rejected() {
+        let key = DataKey::generate().unwrap();
+        let entry_id = Uuid::new_v4();
+        let mut sealed = key.seal(entry_id, b"integrity").unwrap();
+
+        assert!(key.open(entry_id, &sealed[..8], AES_256_GCM).is_err());
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert!(key.open(entry_id, &sealed, AES_256_GCM).is_err());
+    }
+
+    #[test]
+    fn test_wrong_data_key_is_rejected() {
+        let entry_id = Uuid::new_v4();
+        let sealed = DataKey::generate().unwrap().seal(entry_id, b"x").unwrap();
+        let other = DataKey::generate().unwrap();
+        assert!(other.open(entry_id, &sealed, AES_256_GCM).is_err());
+    }
+
+    #[test]
+    fn test_unknown_cipher_is_an_error() {
+        let key = DataKey::generate().unwrap();
+        assert!(key.open(Uuid::new_v4(), &[0u8; 64], "chacha").is_err());
+    }
+
+    #[test]
+    fn test_data_key_wrap_round_trips() {
+        let notebook_id = Uuid::new_v4();
+        let data_key = DataKey::generate().unwrap();
+
+        let wrapped = master_key().wrap(notebook_id, &data_key).unwrap();
+        assert_eq!(
+            master_key().unwrap(notebook_id, &wrapped).unwrap(),
+            data_key
+        );
+
+        // Bound to the notebook and to the master key
+        assert!(master_key().unwrap(Uuid::new_v4(), &wrapped).is_err());
+        let other = MasterKey::from_bytes([8u8; KEY_LEN]);
+        assert!(other.unwrap(notebook_id, &wrapped).is_err());
+    }
+
+    #[test]
+    fn test_master_key_from_hex() {
+        let key = MasterKey::from_hex(&"07".repeat(KEY_LEN)).unwrap();
+        assert_eq!(key, master_key());
+
+        assert!(MasterKey::from_hex("not hex").is_err());
+        assert!(MasterKey::from_hex("0707").is_err());
+    }
+
+    #[test]
+    fn test_keys_are_redacted_in_debug_output() {
+        assert_eq!(format!("{:?}", master_key()), "MasterKey(..)");
+        assert_eq!(format!("{:?}", DataKey::generate().unwrap()), "DataKey(..)");
+    }
+}
@@ -11,7 +11,12 @@ pub type StoreResult<T> = Result<T, StoreError>;
 pub enum StoreError {
     /// Database connection error.
     #[error("database connection error: {0}")]
-    Connection(#[from] sqlx::Error),
+    Connection(sqlx::Error),
+
+    /// A statement ran past the configured statement timeout and was
+    /// cancelled by the database.
+    #[error("database statement timed out: {0}")]
+    Timeout(String),
 
     /// Entry not found.
     #[error("entry not found: {0}")]
@@ -25,6 +30,14 @@ pub enum StoreError {
     #[error("author not found: {0}")]
     AuthorNotFound(Uuid),
 
+    /// Entry has been deleted.
+    #[error("entry deleted: {0}")]
+    EntryDeleted(Uuid),
+
+    /// Notebook is locked against writes.
+    #[error("notebook locked: {0}")]
+    NotebookLocked(Uuid),
+
     /// Invalid reference - referenced entry does not exist.
     #[error("invalid reference: entry {0} does not exist")]
     InvalidReference(Uuid),
@@ -33,10 +46,29 @@ pub enum StoreError {
     #[error("duplicate entry: {0}")]
     DuplicateEntry(Uuid),
 
+    /// The owner already has a notebook with this name.
+    #[error("notebook name already in use: {name:?} (notebook {existing_id})")]
+    DuplicateNotebookName { name: String, existing_id: Uuid },
+
     /// Invalid revision - revision_of entry does not exist.
     #[error("invalid revision: entry {0} does not exist")]
     InvalidRevision(Uuid),
 
+    /// A conditional revise was based on a revision that is no longer the
+    /// latest.
+    #[error("revision conflict: entry {entry_id} has a newer revision {latest}")]
+    RevisionConflict { entry_id: Uuid, latest: Uuid },
+
+    /// A write expected the notebook at an earlier sequence.
+    #[error(
+        "sequence conflict: notebook {notebook_id} is at sequence {current}, past the expected {expected}"
+    )]
+    SequenceConflict {
+        notebook_id: Uuid,
+        current: i64,
+        expected: i64,
+    },
+
     /// Permission denied for the operation.
     #[error("permission denied: {operation} on notebook {notebook_id}")]
     PermissionDenied {
@@ -56,6 +88,10 @@ pub enum StoreError {
     #[error("graph operation failed: {0}")]
     GraphError(String),
 
+    /// The operation needs native graph queries (Apache AGE), which are unavailable.
+    #[error("graph queries unavailable: {0}")]
+    GraphUnavailable(String),
+
     /// Migration error.
     #[error("migration error: {0}")]
     MigrationError(String),
@@ -64,7 +100,28 @@ pub enum StoreError {
     #[error("serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
 
+    /// Entry content could not be encrypted or decrypted.
+    #[error("encryption error: {0}")]
+    EncryptionError(String),
+
     /// Configuration error.
     #[error("configuration error: {0}")]
     ConfigError(String),
 }
+
+/// SQLSTATE `query_canceled`, raised when `statement_timeout` expires.
+const QUERY_CANCELED: &str = "57014";
+
+impl From<sqlx::Error> for StoreError {
+    fn from(e: sqlx::Error) -> Self {
+        let timed_out = e
+            .as_database_error()
+            .and_then(|db| db.code())
+            .is_some_and(|code| code == QUERY_CANCELED);
+        if timed_out {
+            StoreError::Timeout(e.to_string())
+        } else {
+            StoreError::Connection(e)
+        }
+    }
+}
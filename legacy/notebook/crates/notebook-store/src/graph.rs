@@ -4,17 +4,228 @@
 //! - Reference closure (all entries reachable via references)
 //! - Revision chains (ancestors in revision history)
 //! - Citations (entries that reference a given entry)
+//! - Reference lineage (entries an entry transitively references, or that
+//!   transitively reference it), with an explicit direction
 //! - Coherence (semantically related entries)
+//! - Whitelisted query templates (neighbors, referrers, referenced,
+//!   shortest path), AGE only
+//! - Notebook export (every entry with its reference and revision edges)
 //!
 //! When Apache AGE is available, queries use Cypher via AGE graph functions.
 //! When AGE is unavailable, equivalent SQL queries run against the relational
 //! schema (`entries.references`, `entries.revision_of`, `coherence_links`).
 
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::error::{StoreError, StoreResult};
 
+/// Default traversal depth for graph query templates.
+pub const TEMPLATE_DEFAULT_DEPTH: u32 = 10;
+
+/// Maximum traversal depth accepted by graph query templates.
+pub const TEMPLATE_MAX_DEPTH: u32 = 20;
+
+fn default_template_depth() -> u32 {
+    TEMPLATE_DEFAULT_DEPTH
+}
+
+/// A whitelisted, parameterized graph query.
+///
+/// Templates are the only way to run Cypher on behalf of a client. Every
+/// parameter is typed (entry IDs are UUIDs, depths are integers), so the
+/// rendered query never contains caller-supplied text.
+///
+/// Serialized as `{"template": "referenced", "params": {...}}`. The
+/// `referrers` and `referenced` templates were first named `ancestors` and
+/// `descendants`; those names are still accepted but deprecated, as
+/// [`Store::ancestors`](crate::Store::ancestors) and
+/// [`Store::descendants`](crate::Store::descendants) walk the other way.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "template", content = "params", rename_all = "snake_case")]
+pub enum GraphQueryTemplate {
+    /// Entries that reference, or are referenced by, an entry.
+    Neighbors { entry_id: Uuid },
+    /// Entries that transitively reference an entry.
+    #[serde(alias = "ancestors")]
+    Referrers {
+        entry_id: Uuid,
+        #[serde(default = "default_template_depth")]
+        max_depth: u32,
+    },
+    /// Entries transitively referenced by an entry (its reference closure).
+    #[serde(alias = "descendants")]
+    Referenced {
+        entry_id: Uuid,
+        #[serde(default = "default_template_depth")]
+        max_depth: u32,
+    },
+    /// The shortest chain of references leading from one entry to another.
+    ShortestPath {
+        from: Uuid,
+        to: Uuid,
+        #[serde(default = "default_template_depth")]
+        max_depth: u32,
+    },
+}
+
+impl GraphQueryTemplate {
+    /// Template name as used in the serialized form.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Neighbors { .. } => "neighbors",
+            Self::Referrers { .. } => "referrers",
+            Self::Referenced { .. } => "referenced",
+            Self::ShortestPath { .. } => "shortest_path",
+        }
+    }
+
+    /// Entries the query starts from (and, for paths, ends at).
+    pub fn entry_ids(&self) -> Vec<Uuid> {
+        match self {
+            Self::Neighbors { entry_id }
+            | Self::Referrers { entry_id, .. }
+            | Self::Referenced { entry_id, .. } => vec![*entry_id],
+            Self::ShortestPath { from, to, .. } => vec![*from, *to],
+        }
+    }
+
+    /// Requested traversal depth, if the template traverses.
+    pub fn max_depth(&self) -> Option<u32> {
+        match self {
+            Self::Neighbors { .. } => None,
+            Self::Referrers { max_depth, .. }
+            | Self::Referenced { max_depth, .. }
+            | Self::ShortestPath { max_depth, .. } => Some(*max_depth),
+        }
+    }
+
+    /// Render the Cypher for this template, scoped to `notebook_id`.
+    ///
+    /// Only UUIDs and a depth clamped to `1..=TEMPLATE_MAX_DEPTH` are
+    /// interpolated. Each row yields an entry ID and its depth; for
+    /// `shortest_path` the depth is the entry's position along the path.
+    pub fn to_cypher(&self, notebook_id: Uuid) -> String {
+        let depth = self.max_depth().unwrap_or(1).clamp(1, TEMPLATE_MAX_DEPTH);
+
+        match self {
+            Self::Neighbors { entry_id } => format!(
+                "MATCH (s:entry {{id: '{entry_id}', notebook_id: '{notebook_id}'}})\
+                 -[:references]-(n:entry {{notebook_id: '{notebook_id}'}}) \
+                 RETURN DISTINCT n.id, 1"
+            ),
+            Self::Referrers { entry_id, .. } => format!(
+                "MATCH p = (s:entry {{id: '{entry_id}', notebook_id: '{notebook_id}'}})\
+                 <-[:references*1..{depth}]-(n:entry {{notebook_id: '{notebook_id}'}}) \
+                 RETURN n.id, length(p)"
+            ),
+            Self::Referenced { entry_id, .. } => format!(
+                "MATCH p = (s:entry {{id: '{entry_id}', notebook_id: '{notebook_id}'}})\
+                 -[:references*1..{depth}]->(n:entry {{notebook_id: '{notebook_id}'}}) \
+                 RETURN n.id, length(p)"
+            ),
+            Self::ShortestPath { from, to, .. } => format!(
+                "MATCH p = (s:entry {{id: '{from}', notebook_id: '{notebook_id}'}})\
+                 -[:references*1..{depth}]->(t:entry {{id: '{to}', notebook_id: '{notebook_id}'}}) \
+                 WITH p ORDER BY length(p) LIMIT 1 \
+                 UNWIND range(0, length(p)) AS i \
+                 RETURN nodes(p)[i].id, i"
+            ),
+        }
+    }
+}
+
+/// Direction in which reference edges are followed.
+///
+/// An entry's references point at entries that existed when it was written,
+/// so following [`References`](Self::References) walks back in time to its
+/// ancestors and [`ReferencedBy`](Self::ReferencedBy) walks forward to its
+/// descendants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceDirection {
+    /// From an entry to the entries it references.
+    References,
+    /// From an entry to the entries that reference it.
+    ReferencedBy,
+}
+
+impl ReferenceDirection {
+    /// Render the Cypher following reference edges from `entry_id` in this
+    /// direction, up to `max_depth` hops (clamped to `1..=TEMPLATE_MAX_DEPTH`).
+    pub fn to_cypher(self, entry_id: Uuid, max_depth: u32) -> String {
+        let depth = max_depth.clamp(1, TEMPLATE_MAX_DEPTH);
+        let (left, right) = match self {
+            Self::References => ("-", "->"),
+            Self::ReferencedBy => ("<-", "-"),
+        };
+        format!(
+            "MATCH p = (s:entry {{id: '{entry_id}'}})\
+             {left}[:references*1..{depth}]{right}(n:entry) \
+             RETURN n.id, length(p)"
+        )
+    }
+}
+
+/// One entry returned by a graph query template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct GraphQueryHit {
+    pub entry_id: Uuid,
+    pub depth: i32,
+}
+
+/// Kind of edge in an exported notebook graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphEdgeKind {
+    /// The source entry references the target.
+    References,
+    /// The source entry is a revision of the target.
+    RevisionOf,
+}
+
+impl GraphEdgeKind {
+    /// Edge label as stored in the AGE graph and used in exports.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::References => "references",
+            Self::RevisionOf => "revision_of",
+        }
+    }
+}
+
+/// An entry in an exported notebook graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphExportNode {
+    pub entry_id: Uuid,
+    pub topic: Option<String>,
+    /// AuthorId as 32-byte hash
+    pub author_id: Vec<u8>,
+    pub sequence: i64,
+}
+
+/// A typed edge between two exported entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GraphExportEdge {
+    pub source: Uuid,
+    pub target: Uuid,
+    pub kind: GraphEdgeKind,
+}
+
+/// A notebook's entries and the edges between them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NotebookGraph {
+    /// Entries ordered by sequence.
+    pub nodes: Vec<GraphExportNode>,
+    /// Edges whose source and target are both among `nodes`.
+    pub edges: Vec<GraphExportEdge>,
+    /// Whether entries past the node limit were left out.
+    pub truncated: bool,
+}
+
 /// Graph query operations for the store.
 #[derive(Debug, Clone)]
 pub struct GraphQueries<'a> {
@@ -83,6 +294,31 @@ impl<'a> GraphQueries<'a> {
         }
     }
 
+    /// Follow reference edges from an entry in `direction`, up to
+    /// `max_depth` hops.
+    ///
+    /// Results are ordered by depth; entries reached along several paths
+    /// appear once, at their shallowest depth. The starting entry is never
+    /// part of the result, and entries of other notebooks are followed.
+    pub async fn find_reference_lineage(
+        &self,
+        entry_id: Uuid,
+        direction: ReferenceDirection,
+        max_depth: u32,
+    ) -> StoreResult<Vec<GraphQueryHit>> {
+        let rows = if self.age_available {
+            self.find_reference_lineage_age(entry_id, direction, max_depth)
+                .await?
+        } else {
+            self.find_reference_lineage_sql(entry_id, direction, max_depth)
+                .await?
+        };
+
+        Ok(shallowest_hits(
+            rows.into_iter().filter(|(id, _)| *id != entry_id),
+        ))
+    }
+
     /// Add a coherence edge between two entries.
     ///
     /// Always writes to the `coherence_links` relational table (dual-write).
@@ -133,8 +369,8 @@ impl<'a> GraphQueries<'a> {
 
     /// Execute a raw Cypher query.
     ///
-    /// Returns an error when AGE is unavailable — there is no SQL equivalent
-    /// for arbitrary Cypher queries.
+    /// Returns [`StoreError::GraphUnavailable`] when AGE is unavailable — there
+    /// is no SQL equivalent for arbitrary Cypher queries.
     ///
     /// # Warning
     ///
@@ -142,7 +378,7 @@ impl<'a> GraphQueries<'a> {
     /// properly sanitized to prevent injection attacks.
     pub async fn execute_cypher(&self, cypher: &str) -> StoreResult<Vec<serde_json::Value>> {
         if !self.age_available {
-            return Err(StoreError::GraphError(
+            return Err(StoreError::GraphUnavailable(
                 "Cypher queries require Apache AGE, which is not available".to_string(),
             ));
         }
@@ -164,6 +400,99 @@ impl<'a> GraphQueries<'a> {
         Ok(rows.into_iter().map(|(v,)| v).collect())
     }
 
+    /// Run a whitelisted query template against the notebook's graph.
+    ///
+    /// Only entries in `notebook_id` are matched. Results are ordered by
+    /// depth; entries reached along several paths appear once, at their
+    /// shallowest depth.
+    ///
+    /// Returns [`StoreError::GraphUnavailable`] when AGE is unavailable.
+    pub async fn run_template(
+        &self,
+        notebook_id: Uuid,
+        template: &GraphQueryTemplate,
+    ) -> StoreResult<Vec<GraphQueryHit>> {
+        if !self.age_available {
+            return Err(StoreError::GraphUnavailable(
+                "Graph query templates require Apache AGE, which is not available".to_string(),
+            ));
+        }
+
+        let query = format!(
+            r#"
+            SELECT entry_id::text, depth::int FROM cypher('notebook_graph', $$
+                {}
+            $$) AS (entry_id agtype, depth agtype)
+            "#,
+            template.to_cypher(notebook_id)
+        );
+
+        let rows: Vec<(String, i32)> =
+            sqlx::query_as(&query)
+                .fetch_all(self.pool)
+                .await
+                .map_err(|e| {
+                    StoreError::GraphError(format!(
+                        "Graph template '{}' failed: {}",
+                        template.name(),
+                        e
+                    ))
+                })?;
+
+        let rows = rows
+            .into_iter()
+            .map(|(id_str, depth)| Ok((parse_age_uuid(&id_str)?, depth)))
+            .collect::<StoreResult<Vec<_>>>()?;
+
+        Ok(shallowest_hits(rows))
+    }
+
+    /// Export the notebook's entries after `since_sequence` as a graph.
+    ///
+    /// At most `max_nodes` entries are exported, oldest first; `truncated`
+    /// is set when more remain. Edges are kept only when both ends are
+    /// exported, so references to earlier or foreign entries are left out.
+    pub async fn export_notebook(
+        &self,
+        notebook_id: Uuid,
+        since_sequence: i64,
+        max_nodes: usize,
+    ) -> StoreResult<NotebookGraph> {
+        let rows: Vec<(Uuid, Option<String>, Vec<u8>, i64)> = sqlx::query_as(
+            r#"
+            SELECT id, topic, author_id, sequence
+            FROM entries
+            WHERE notebook_id = $1 AND sequence > $2
+            ORDER BY sequence
+            LIMIT $3
+            "#,
+        )
+        .bind(notebook_id)
+        .bind(since_sequence)
+        .bind(max_nodes as i64 + 1)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| StoreError::GraphError(format!("Graph export query failed: {}", e)))?;
+
+        let nodes = rows
+            .into_iter()
+            .map(|(entry_id, topic, author_id, sequence)| GraphExportNode {
+                entry_id,
+                topic,
+                author_id,
+                sequence,
+            })
+            .collect();
+
+        let edges = if self.age_available {
+            self.notebook_edges_age(notebook_id).await?
+        } else {
+            self.notebook_edges_sql(notebook_id, since_sequence).await?
+        };
+
+        Ok(build_notebook_graph(nodes, edges, max_nodes))
+    }
+
     // ========================================================================
     // AGE implementations (original code)
     // ========================================================================
@@ -244,6 +573,68 @@ impl<'a> GraphQueries<'a> {
             .collect()
     }
 
+    async fn find_reference_lineage_age(
+        &self,
+        entry_id: Uuid,
+        direction: ReferenceDirection,
+        max_depth: u32,
+    ) -> StoreResult<Vec<(Uuid, i32)>> {
+        let query = format!(
+            r#"
+            SELECT entry_id::text, depth::int FROM cypher('notebook_graph', $$
+                {}
+            $$) AS (entry_id agtype, depth agtype)
+            "#,
+            direction.to_cypher(entry_id, max_depth)
+        );
+
+        let rows: Vec<(String, i32)> =
+            sqlx::query_as(&query)
+                .fetch_all(self.pool)
+                .await
+                .map_err(|e| {
+                    StoreError::GraphError(format!("Reference lineage query failed: {}", e))
+                })?;
+
+        rows.into_iter()
+            .map(|(id_str, depth)| Ok((parse_age_uuid(&id_str)?, depth)))
+            .collect()
+    }
+
+    async fn notebook_edges_age(&self, notebook_id: Uuid) -> StoreResult<Vec<GraphExportEdge>> {
+        let mut edges = Vec::new();
+        for kind in [GraphEdgeKind::References, GraphEdgeKind::RevisionOf] {
+            let query = format!(
+                r#"
+                SELECT source::text, target::text FROM cypher('notebook_graph', $$
+                    MATCH (a:entry {{notebook_id: '{notebook_id}'}})
+                          -[:{label}]->(b:entry {{notebook_id: '{notebook_id}'}})
+                    RETURN a.id, b.id
+                $$) AS (source agtype, target agtype)
+                "#,
+                label = kind.label()
+            );
+            let rows: Vec<(String, String)> = sqlx::query_as(&query)
+                .fetch_all(self.pool)
+                .await
+                .map_err(|e| {
+                    StoreError::GraphError(format!(
+                        "Graph export {} edges failed: {}",
+                        kind.label(),
+                        e
+                    ))
+                })?;
+            for (source, target) in rows {
+                edges.push(GraphExportEdge {
+                    source: parse_age_uuid(&source)?,
+                    target: parse_age_uuid(&target)?,
+                    kind,
+                });
+            }
+        }
+        Ok(edges)
+    }
+
     // ========================================================================
     // SQL fallback implementations
     // ========================================================================
@@ -287,6 +678,55 @@ impl<'a> GraphQueries<'a> {
         Ok(rows)
     }
 
+    /// Recursive CTE over `"references"` in either direction; referrers are
+    /// found through the GIN index on the array.
+    async fn find_reference_lineage_sql(
+        &self,
+        entry_id: Uuid,
+        direction: ReferenceDirection,
+        max_depth: u32,
+    ) -> StoreResult<Vec<(Uuid, i32)>> {
+        let depth = max_depth.clamp(1, TEMPLATE_MAX_DEPTH) as i32;
+        match direction {
+            ReferenceDirection::References => {
+                self.find_reference_closure_sql(entry_id, depth).await
+            }
+            ReferenceDirection::ReferencedBy => {
+                let rows: Vec<(Uuid, i32)> = sqlx::query_as(
+                    r#"
+                    WITH RECURSIVE referrers AS (
+                        -- Base: entries referencing the starting entry
+                        SELECT id AS entry_id, 1 AS depth
+                        FROM entries
+                        WHERE $1 = ANY("references")
+
+                        UNION
+
+                        -- Recurse: entries referencing already-reached entries
+                        SELECT e.id, r.depth + 1
+                        FROM entries e
+                        JOIN referrers r ON r.entry_id = ANY(e."references")
+                        WHERE r.depth < $2
+                    )
+                    SELECT entry_id, MIN(depth) AS depth
+                    FROM referrers
+                    GROUP BY entry_id
+                    ORDER BY depth, entry_id
+                    "#,
+                )
+                .bind(entry_id)
+                .bind(depth)
+                .fetch_all(self.pool)
+                .await
+                .map_err(|e| {
+                    StoreError::GraphError(format!("SQL referrers query failed: {}", e))
+                })?;
+
+                Ok(rows)
+            }
+        }
+    }
+
     /// Recursive CTE on `revision_of` FK chain.
     async fn find_revision_chain_sql(&self, entry_id: Uuid) -> StoreResult<Vec<(Uuid, i32)>> {
         let rows: Vec<(Uuid, i32)> = sqlx::query_as(
@@ -357,6 +797,43 @@ impl<'a> GraphQueries<'a> {
 
         Ok(rows)
     }
+
+    /// Unnests `"references"` and `revision_of` of the notebook's entries.
+    async fn notebook_edges_sql(
+        &self,
+        notebook_id: Uuid,
+        since_sequence: i64,
+    ) -> StoreResult<Vec<GraphExportEdge>> {
+        let rows: Vec<(Uuid, Uuid, bool)> = sqlx::query_as(
+            r#"
+            SELECT id, unnest("references"), FALSE
+            FROM entries
+            WHERE notebook_id = $1 AND sequence > $2
+            UNION ALL
+            SELECT id, revision_of, TRUE
+            FROM entries
+            WHERE notebook_id = $1 AND sequence > $2 AND revision_of IS NOT NULL
+            "#,
+        )
+        .bind(notebook_id)
+        .bind(since_sequence)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| StoreError::GraphError(format!("SQL graph export query failed: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(source, target, revision)| GraphExportEdge {
+                source,
+                target,
+                kind: if revision {
+                    GraphEdgeKind::RevisionOf
+                } else {
+                    GraphEdgeKind::References
+                },
+            })
+            .collect())
+    }
 }
 
 /// Parse a UUID from AGE's string format.
@@ -368,6 +845,50 @@ fn parse_age_uuid(s: &str) -> StoreResult<Uuid> {
     Uuid::parse_str(s).map_err(|e| StoreError::GraphError(format!("Invalid UUID from AGE: {}", e)))
 }
 
+/// Collapse `(entry, depth)` rows to one hit per entry at its minimum depth,
+/// ordered by depth then entry ID.
+fn shallowest_hits(rows: impl IntoIterator<Item = (Uuid, i32)>) -> Vec<GraphQueryHit> {
+    let mut depths: HashMap<Uuid, i32> = HashMap::new();
+    for (entry_id, depth) in rows {
+        depths
+            .entry(entry_id)
+            .and_modify(|d| *d = (*d).min(depth))
+            .or_insert(depth);
+    }
+
+    let mut hits: Vec<GraphQueryHit> = depths
+        .into_iter()
+        .map(|(entry_id, depth)| GraphQueryHit { entry_id, depth })
+        .collect();
+    hits.sort_by_key(|h| (h.depth, h.entry_id));
+    hits
+}
+
+/// Cap `nodes` (fetched in sequence order) at `max_nodes` and keep the
+/// distinct edges between the remaining nodes.
+fn build_notebook_graph(
+    mut nodes: Vec<GraphExportNode>,
+    edges: Vec<GraphExportEdge>,
+    max_nodes: usize,
+) -> NotebookGraph {
+    let truncated = nodes.len() > max_nodes;
+    nodes.truncate(max_nodes);
+
+    let ids: HashSet<Uuid> = nodes.iter().map(|n| n.entry_id).collect();
+    let mut seen = HashSet::new();
+    let edges = edges
+        .into_iter()
+        .filter(|e| ids.contains(&e.source) && ids.contains(&e.target))
+        .filter(|e| seen.insert(*e))
+        .collect();
+
+    NotebookGraph {
+        nodes,
+        edges,
+        truncated,
+    }
+}
+
 /// Extension trait to add graph queries to the Store.
 pub trait GraphQueryExt {
     /// Get graph query operations.
@@ -379,6 +900,32 @@ impl crate::Store {
     pub fn graph(&self) -> GraphQueries<'_> {
         GraphQueries::new(self.pool(), self.age_available())
     }
+
+    /// Entries an entry references, transitively, up to `max_depth` hops.
+    ///
+    /// Same direction as the `referenced` [`GraphQueryTemplate`].
+    pub async fn ancestors(
+        &self,
+        entry_id: Uuid,
+        max_depth: u32,
+    ) -> StoreResult<Vec<GraphQueryHit>> {
+        self.graph()
+            .find_reference_lineage(entry_id, ReferenceDirection::References, max_depth)
+            .await
+    }
+
+    /// Entries referencing an entry, transitively, up to `max_depth` hops.
+    ///
+    /// Same direction as the `referrers` [`GraphQueryTemplate`].
+    pub async fn descendants(
+        &self,
+        entry_id: Uuid,
+        max_depth: u32,
+    ) -> StoreResult<Vec<GraphQueryHit>> {
+        self.graph()
+            .find_reference_lineage(entry_id, ReferenceDirection::ReferencedBy, max_depth)
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -399,6 +946,200 @@ mod tests {
         assert_eq!(result.to_string(), "550e8400-e29b-41d4-a716-446655440000");
     }
 
+    #[tokio::test]
+    async fn test_cypher_without_age_is_unavailable() {
+        // A lazy pool never connects; the AGE check happens before any query
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let graph = GraphQueries::new(&pool, false);
+
+        let result = graph.execute_cypher("MATCH (n) RETURN n").await;
+        assert!(matches!(result, Err(StoreError::GraphUnavailable(_))));
+    }
+
+    #[tokio::test]
+    async fn test_template_without_age_is_unavailable() {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let graph = GraphQueries::new(&pool, false);
+
+        let template = GraphQueryTemplate::Referenced {
+            entry_id: Uuid::new_v4(),
+            max_depth: 3,
+        };
+        let result = graph.run_template(Uuid::new_v4(), &template).await;
+        assert!(matches!(result, Err(StoreError::GraphUnavailable(_))));
+    }
+
+    #[test]
+    fn test_template_deserializes_with_default_depth() {
+        let entry_id = Uuid::new_v4();
+        let json = serde_json::json!({
+            "template": "referenced",
+            "params": { "entry_id": entry_id }
+        });
+        let template: GraphQueryTemplate = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            template,
+            GraphQueryTemplate::Referenced {
+                entry_id,
+                max_depth: TEMPLATE_DEFAULT_DEPTH
+            }
+        );
+        assert_eq!(template.name(), "referenced");
+
+        // The deprecated names keep their original meaning
+        for (name, expected) in [
+            (
+                "ancestors",
+                GraphQueryTemplate::Referrers {
+                    entry_id,
+                    max_depth: 2,
+                },
+            ),
+            (
+                "descendants",
+                GraphQueryTemplate::Referenced {
+                    entry_id,
+                    max_depth: 2,
+                },
+            ),
+        ] {
+            let json = serde_json::json!({
+                "template": name,
+                "params": { "entry_id": entry_id, "max_depth": 2 }
+            });
+            assert_eq!(
+                serde_json::from_value::<GraphQueryTemplate>(json).unwrap(),
+                expected
+            );
+        }
+
+        // Raw Cypher is not a template
+        let raw = serde_json::json!({ "template": "cypher", "params": { "query": "MATCH (n)" } });
+        assert!(serde_json::from_value::<GraphQueryTemplate>(raw).is_err());
+    }
+
+    #[test]
+    fn test_referenced_cypher_follows_references_transitively() {
+        let entry_id = Uuid::new_v4();
+        let notebook_id = Uuid::new_v4();
+        let cypher = GraphQueryTemplate::Referenced {
+            entry_id,
+            max_depth: 4,
+        }
+        .to_cypher(notebook_id);
+
+        assert!(cypher.contains(&format!("id: '{}'", entry_id)));
+        assert!(cypher.contains("-[:references*1..4]->"));
+        assert_eq!(
+            cypher
+                .matches(&format!("notebook_id: '{}'", notebook_id))
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_template_depth_is_clamped() {
+        let deep = GraphQueryTemplate::Referrers {
+            entry_id: Uuid::new_v4(),
+            max_depth: 10_000,
+        }
+        .to_cypher(Uuid::new_v4());
+        assert!(deep.contains(&format!("<-[:references*1..{}]-", TEMPLATE_MAX_DEPTH)));
+
+        let shallow = GraphQueryTemplate::Referenced {
+            entry_id: Uuid::new_v4(),
+            max_depth: 0,
+        }
+        .to_cypher(Uuid::new_v4());
+        assert!(shallow.contains("-[:references*1..1]->"));
+    }
+
+    #[test]
+    fn test_reference_direction_cypher() {
+        let entry_id = Uuid::new_v4();
+
+        let references = ReferenceDirection::References.to_cypher(entry_id, 3);
+        assert!(references.contains(&format!("id: '{}'", entry_id)));
+        assert!(references.contains("-[:references*1..3]->(n:entry)"));
+
+        let referenced_by = ReferenceDirection::ReferencedBy.to_cypher(entry_id, 0);
+        assert!(referenced_by.contains("<-[:references*1..1]-(n:entry)"));
+
+        let deep = ReferenceDirection::References.to_cypher(entry_id, 10_000);
+        assert!(deep.contains(&format!("*1..{}]", TEMPLATE_MAX_DEPTH)));
+    }
+
+    #[test]
+    fn test_shallowest_hits_builds_reference_closure() {
+        // a -> b -> c -> d plus a shortcut a -> c: each entry keeps its
+        // shallowest depth
+        let b = Uuid::new_v4();
+        let c = Uuid::new_v4();
+        let d = Uuid::new_v4();
+        let rows = vec![(b, 1), (c, 2), (c, 1), (d, 3), (d, 2)];
+
+        let hits = shallowest_hits(rows);
+        assert_eq!(hits.len(), 3);
+        assert_eq!(
+            hits[2],
+            GraphQueryHit {
+                entry_id: d,
+                depth: 2
+            }
+        );
+        assert!(hits[..2].iter().all(|h| h.depth == 1));
+        assert!(hits[..2].iter().any(|h| h.entry_id == b));
+        assert!(hits[..2].iter().any(|h| h.entry_id == c));
+    }
+
+    fn node(sequence: i64) -> GraphExportNode {
+        GraphExportNode {
+            entry_id: Uuid::new_v4(),
+            topic: None,
+            author_id: vec![0; 32],
+            sequence,
+        }
+    }
+
+    #[test]
+    fn test_notebook_graph_keeps_edges_between_exported_entries() {
+        let nodes: Vec<GraphExportNode> = (1..=4).map(node).collect();
+        let edge = |source: usize, target: Uuid, kind| GraphExportEdge {
+            source: nodes[source].entry_id,
+            target,
+            kind,
+        };
+        let edges = vec![
+            edge(1, nodes[0].entry_id, GraphEdgeKind::References),
+            edge(2, nodes[1].entry_id, GraphEdgeKind::RevisionOf),
+            // Reported twice, exported once
+            edge(2, nodes[0].entry_id, GraphEdgeKind::References),
+            edge(2, nodes[0].entry_id, GraphEdgeKind::References),
+            // Reference into another notebook
+            edge(1, Uuid::new_v4(), GraphEdgeKind::References),
+            // Source beyond the node limit
+            edge(3, nodes[0].entry_id, GraphEdgeKind::References),
+        ];
+
+        let graph = build_notebook_graph(nodes.clone(), edges, 3);
+        assert!(graph.truncated);
+        assert_eq!(graph.nodes, nodes[..3]);
+        assert_eq!(graph.edges.len(), 3);
+        assert_eq!(
+            graph
+                .edges
+                .iter()
+                .filter(|e| e.kind == GraphEdgeKind::RevisionOf)
+                .count(),
+            1
+        );
+
+        let graph = build_notebook_graph(nodes, Vec::new(), 4);
+        assert!(!graph.truncated);
+        assert_eq!(graph.nodes.len(), 4);
+    }
+
     #[test]
     fn test_graph_queries_dispatches_based_on_age_flag() {
         // Verify the struct can be constructed with both flags
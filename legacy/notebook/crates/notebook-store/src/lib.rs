@@ -30,6 +30,8 @@
 //! Owned by: agent-store
 
 pub mod causal;
+pub mod compression;
+pub mod encryption;
 pub mod error;
 pub mod graph;
 pub mod models;
@@ -39,11 +41,17 @@ pub mod schema;
 pub mod store;
 
 pub use causal::CausalPositionService;
+pub use compression::CompressionConfig;
+pub use encryption::MasterKey;
 pub use error::{StoreError, StoreResult};
+pub use graph::{
+    GraphEdgeKind, GraphExportEdge, GraphExportNode, GraphQueryHit, GraphQueryTemplate,
+    NotebookGraph, ReferenceDirection, TEMPLATE_DEFAULT_DEPTH, TEMPLATE_MAX_DEPTH,
+};
 pub use models::*;
 pub use queries::{
     AuthorEntriesQuery, BatchEntryQuery, BrokenReferencesQuery, NotebookStats, NotebookStatsQuery,
-    OrphanEntriesQuery, TopicQuery,
+    OrphanEntriesQuery, TopicCount, TopicCountsQuery, TopicQuery,
 };
 pub use repository::{AuthorPublicKey, DEFAULT_MAX_DEPTH, Repository, StoreEntryInput};
 pub use store::{Store, StoreConfig};
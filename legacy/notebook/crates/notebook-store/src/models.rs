@@ -6,9 +6,12 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, Row};
 use uuid::Uuid;
 
+use crate::compression::decompress;
+
 /// Database row for the `authors` table.
 ///
 /// The `id` field is a 32-byte AuthorId (BLAKE3 hash of public key),
@@ -56,6 +59,24 @@ pub struct NotebookRow {
     pub created: DateTime<Utc>,
     /// Atomically incremented sequence counter for concurrent writes.
     pub current_sequence: i64,
+    /// Whether the notebook is locked (read-only).
+    pub is_locked: bool,
+    /// Whether entry content is encrypted at rest.
+    pub encrypted: bool,
+    /// Whether the notebook can be read without authentication.
+    pub is_public: bool,
+    /// How writes of orphan entries are handled; one of [`orphan_policy`].
+    pub orphan_policy: String,
+}
+
+/// Values of `notebooks.orphan_policy`.
+pub mod orphan_policy {
+    /// Orphans are stored like any other entry.
+    pub const ALLOW: &str = "allow";
+    /// Orphans are stored, and the write response warns about them.
+    pub const WARN: &str = "warn";
+    /// Orphans are rejected.
+    pub const REJECT: &str = "reject";
 }
 
 /// Database row for the `notebook_access` table.
@@ -69,6 +90,99 @@ pub struct NotebookAccessRow {
     pub granted: DateTime<Utc>,
 }
 
+/// Event types recorded in the `notebook_events` change log.
+pub mod event_type {
+    /// An entry was written or revised.
+    pub const ENTRY: &str = "entry";
+    /// The notebook was renamed.
+    pub const NOTEBOOK_RENAMED: &str = "notebook_renamed";
+    /// The notebook was locked or unlocked.
+    pub const NOTEBOOK_LOCKED: &str = "notebook_locked";
+    /// The notebook was made public or private.
+    pub const NOTEBOOK_VISIBILITY: &str = "notebook_visibility";
+    /// The notebook's orphan policy changed.
+    pub const NOTEBOOK_ORPHAN_POLICY: &str = "notebook_orphan_policy";
+    /// An author was granted (or had updated) access.
+    pub const ACCESS_GRANTED: &str = "access_granted";
+    /// An author's access was revoked.
+    pub const ACCESS_REVOKED: &str = "access_revoked";
+    /// A topic was renamed across the notebook's entries.
+    pub const TOPIC_RENAMED: &str = "topic_renamed";
+    /// An entry was pinned.
+    pub const ENTRY_PINNED: &str = "entry_pinned";
+    /// An entry was unpinned.
+    pub const ENTRY_UNPINNED: &str = "entry_unpinned";
+    /// The notebook's content-type policy was set or replaced.
+    pub const CONTENT_POLICY_SET: &str = "content_policy_set";
+    /// The notebook's content-type policy was removed.
+    pub const CONTENT_POLICY_REMOVED: &str = "content_policy_removed";
+    /// The notebook's write budget was set or replaced.
+    pub const WRITE_BUDGET_SET: &str = "write_budget_set";
+    /// The notebook's write budget was removed.
+    pub const WRITE_BUDGET_REMOVED: &str = "write_budget_removed";
+    /// The notebook's entropy alert was set or replaced.
+    pub const ALERT_SET: &str = "alert_set";
+    /// The notebook's entropy alert was removed.
+    pub const ALERT_REMOVED: &str = "alert_removed";
+}
+
+/// Database row for the `notebook_events` change log.
+#[derive(Debug, Clone, FromRow)]
+pub struct NotebookEventRow {
+    pub notebook_id: Uuid,
+    /// Gapless per-notebook sequence, starting at 1.
+    pub seq: i64,
+    /// Event kind (`entry`, `notebook_renamed`, ...).
+    pub event_type: String,
+    /// Event-specific fields.
+    pub payload: serde_json::Value,
+    pub created: DateTime<Utc>,
+}
+
+/// Database row for the `notebook_alerts` table.
+#[derive(Debug, Clone, FromRow)]
+pub struct NotebookAlertRow {
+    pub notebook_id: Uuid,
+    /// Recent entropy above which the alert fires.
+    pub entropy_threshold: f64,
+    /// URL the alert is POSTed to.
+    pub webhook_url: String,
+    /// Whether entropy is above the threshold and this crossing was alerted.
+    pub triggered: bool,
+    pub created: DateTime<Utc>,
+    /// When the alert last fired, if ever.
+    pub last_fired: Option<DateTime<Utc>>,
+}
+
+/// Database row for the `notebook_content_policies` table.
+#[derive(Debug, Clone, FromRow)]
+pub struct NotebookContentPolicyRow {
+    pub notebook_id: Uuid,
+    /// Globs of admitted content types; empty admits all.
+    pub allowed_content_types: Vec<String>,
+    /// Globs of rejected content types.
+    pub denied_content_types: Vec<String>,
+    pub updated: DateTime<Utc>,
+}
+
+/// Database row for the `notebook_write_budgets` table.
+#[derive(Debug, Clone, FromRow)]
+pub struct NotebookWriteBudgetRow {
+    pub notebook_id: Uuid,
+    /// Summed entry entropy an author may add per window before being slowed.
+    pub entropy_budget: f64,
+    /// Length of the sliding window, in seconds.
+    pub window_secs: i32,
+    pub updated: DateTime<Utc>,
+}
+
+/// Database row for the `notebook_labels` table, without bookkeeping.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct NotebookLabelRow {
+    pub notebook_id: Uuid,
+    pub label: String,
+}
+
 /// Integration cost stored in entries as JSONB.
 /// Aligns with IntegrationCost type from notebook-core.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -112,8 +226,27 @@ impl From<IntegrationCostJson> for notebook_core::IntegrationCost {
     }
 }
 
+/// Entry content still encrypted with its notebook's data key.
+///
+/// Decoding a row has no access to keys, so encrypted content is held here
+/// until the store opens it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SealedContent {
+    /// Stored bytes: the sealed, possibly compressed, content.
+    pub ciphertext: Vec<u8>,
+    /// Cipher recorded in the `encryption` column.
+    pub encryption: String,
+    /// Codec to undo after decryption.
+    pub compression: Option<String>,
+}
+
 /// Database row for the `entries` table.
-#[derive(Debug, Clone, FromRow)]
+///
+/// `content` holds the original bytes: rows are decompressed as they are
+/// decoded, so queries must also select the `compression` and `encryption`
+/// columns. Encrypted content is left in `sealed` (with `content` empty)
+/// until the store decrypts it; rows returned by `Store` are always opened.
+#[derive(Debug, Clone)]
 pub struct EntryRow {
     pub id: Uuid,
     pub notebook_id: Uuid,
@@ -128,6 +261,58 @@ pub struct EntryRow {
     pub sequence: i64,
     pub created: DateTime<Utc>,
     pub integration_cost: serde_json::Value,
+    /// Whether `integration_cost` was computed, rather than a zero fallback
+    /// written when computation failed or timed out.
+    pub cost_computed: bool,
+    /// Encrypted content awaiting decryption, if any.
+    pub sealed: Option<SealedContent>,
+    /// Whether catalogs always list this entry among its cluster's
+    /// representatives.
+    pub pinned: bool,
+}
+
+impl<'r> FromRow<'r, PgRow> for EntryRow {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let compression: Option<String> = row.try_get("compression")?;
+        let encryption: Option<String> = row.try_get("encryption")?;
+        let (content, sealed) =
+            match encryption {
+                Some(encryption) => (
+                    Vec::new(),
+                    Some(SealedContent {
+                        ciphertext: row.try_get("content")?,
+                        encryption,
+                        compression,
+                    }),
+                ),
+                None => {
+                    let content = decompress(row.try_get("content")?, compression.as_deref())
+                        .map_err(|e| sqlx::Error::ColumnDecode {
+                            index: "content".to_string(),
+                            source: e.into(),
+                        })?;
+                    (content, None)
+                }
+            };
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            notebook_id: row.try_get("notebook_id")?,
+            content,
+            content_type: row.try_get("content_type")?,
+            topic: row.try_get("topic")?,
+            author_id: row.try_get("author_id")?,
+            signature: row.try_get("signature")?,
+            revision_of: row.try_get("revision_of")?,
+            references: row.try_get("references")?,
+            sequence: row.try_get("sequence")?,
+            created: row.try_get("created")?,
+            integration_cost: row.try_get("integration_cost")?,
+            cost_computed: row.try_get("cost_computed")?,
+            sealed,
+            pinned: row.try_get("pinned")?,
+        })
+    }
 }
 
 impl EntryRow {
@@ -148,6 +333,93 @@ impl EntryRow {
     }
 }
 
+#[cfg(any(test, feature = "test-support"))]
+impl EntryRow {
+    /// A plain-text row for tests: no topic, references or revision, zero
+    /// author and signature, and a computed empty cost. Override other fields
+    /// with struct update syntax.
+    pub fn test_row(notebook_id: Uuid, sequence: i64, content: &str) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            notebook_id,
+            content: content.as_bytes().to_vec(),
+            content_type: "text/plain".to_string(),
+            topic: None,
+            author_id: vec![0u8; 32],
+            signature: vec![0u8; 64],
+            revision_of: None,
+            references: vec![],
+            sequence,
+            created: Utc::now(),
+            integration_cost: serde_json::json!({}),
+            cost_computed: true,
+            sealed: None,
+            pinned: false,
+        }
+    }
+}
+
+/// Listing metadata for an entry, without its content.
+#[derive(Debug, Clone, FromRow)]
+pub struct EntrySummaryRow {
+    pub id: Uuid,
+    pub topic: Option<String>,
+    /// AuthorId as 32-byte hash
+    pub author_id: Vec<u8>,
+    pub created: DateTime<Utc>,
+    pub sequence: i64,
+    /// Catalog shift from the stored integration cost.
+    pub catalog_shift: f64,
+    /// Whether the integration cost was computed, rather than a fallback.
+    pub cost_computed: bool,
+    /// Number of entries in the revision chain after this one.
+    pub revision_count: i64,
+    /// Sequence of the newest entry in the revision chain, this one included.
+    pub latest_sequence: i64,
+    /// Entry this one revises.
+    pub revision_of: Option<Uuid>,
+}
+
+/// An entry tagged with the notebook it belongs to, for cross-notebook feeds.
+#[derive(Debug, Clone, FromRow)]
+pub struct ActivityRow {
+    #[sqlx(flatten)]
+    pub entry: EntryRow,
+    pub notebook_name: String,
+}
+
+/// An entry in a revision chain with its distance from the chain's root.
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct RevisionChainRow {
+    #[sqlx(flatten)]
+    pub entry: EntryRow,
+    /// Number of `revision_of` links from the root, starting at 1.
+    pub depth: i32,
+    /// Whether this entry was already visited, closing a cycle.
+    pub cycle: bool,
+}
+
+/// The revisions of an entry, nearest first.
+#[derive(Debug, Clone)]
+pub struct RevisionChain<T = EntryRow> {
+    pub revisions: Vec<T>,
+    /// The chain continues past the depth limit.
+    pub truncated: bool,
+    /// A revision links back to an entry already in the chain; traversal
+    /// stopped there instead of looping.
+    pub cycle: bool,
+}
+
+impl<T> Default for RevisionChain<T> {
+    fn default() -> Self {
+        Self {
+            revisions: Vec::new(),
+            truncated: false,
+            cycle: false,
+        }
+    }
+}
+
 /// Input for creating a new author.
 #[derive(Debug, Clone)]
 pub struct NewAuthor {
@@ -188,19 +460,27 @@ pub struct NewNotebook {
     pub name: String,
     /// AuthorId - 32-byte hash
     pub owner_id: [u8; 32],
+    /// Encrypt entry content at rest (requires a server master key).
+    pub encrypted: bool,
 }
 
 impl NewNotebook {
     pub fn new(name: String, owner_id: [u8; 32]) -> Self {
+        Self::with_id(Uuid::new_v4(), name, owner_id)
+    }
+
+    pub fn with_id(id: Uuid, name: String, owner_id: [u8; 32]) -> Self {
         Self {
-            id: Uuid::new_v4(),
+            id,
             name,
             owner_id,
+            encrypted: false,
         }
     }
 
-    pub fn with_id(id: Uuid, name: String, owner_id: [u8; 32]) -> Self {
-        Self { id, name, owner_id }
+    pub fn encrypted(mut self, encrypted: bool) -> Self {
+        self.encrypted = encrypted;
+        self
     }
 }
 
@@ -228,6 +508,11 @@ pub struct NewEntry {
     pub revision_of: Option<Uuid>,
     pub references: Vec<Uuid>,
     pub integration_cost: IntegrationCostJson,
+    /// Whether `integration_cost` was computed, rather than a fallback.
+    pub cost_computed: bool,
+    /// For a conditional revise: the entry whose latest revision must still
+    /// be `revision_of` when the insert commits.
+    pub if_latest_revision_of: Option<Uuid>,
 }
 
 impl NewEntry {
@@ -244,6 +529,8 @@ impl NewEntry {
             revision_of: None,
             references: Vec::new(),
             integration_cost: IntegrationCostJson::default(),
+            cost_computed: true,
+            if_latest_revision_of: None,
         }
     }
 }
@@ -261,6 +548,8 @@ pub struct NewEntryBuilder {
     revision_of: Option<Uuid>,
     references: Vec<Uuid>,
     integration_cost: IntegrationCostJson,
+    cost_computed: bool,
+    if_latest_revision_of: Option<Uuid>,
 }
 
 impl NewEntryBuilder {
@@ -309,6 +598,19 @@ impl NewEntryBuilder {
         self
     }
 
+    /// Mark the integration cost as a fallback rather than computed.
+    pub fn cost_computed(mut self, computed: bool) -> Self {
+        self.cost_computed = computed;
+        self
+    }
+
+    /// Only insert the revision if `revision_of` is still the latest
+    /// revision of `entry_id`.
+    pub fn if_latest_revision_of(mut self, entry_id: Uuid) -> Self {
+        self.if_latest_revision_of = Some(entry_id);
+        self
+    }
+
     pub fn build(self) -> NewEntry {
         NewEntry {
             id: self.id,
@@ -321,6 +623,8 @@ impl NewEntryBuilder {
             revision_of: self.revision_of,
             references: self.references,
             integration_cost: self.integration_cost,
+            cost_computed: self.cost_computed,
+            if_latest_revision_of: self.if_latest_revision_of,
         }
     }
 }
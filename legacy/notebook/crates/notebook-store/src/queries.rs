@@ -41,9 +41,9 @@ impl BatchEntryQuery {
         // Use ANY() for efficient batch lookup
         let rows = sqlx::query_as::<_, EntryRow>(
             r#"
-            SELECT id, notebook_id, content, content_type, topic,
+            SELECT id, notebook_id, content, compression, encryption, content_type, topic,
                    author_id, signature, revision_of, "references",
-                   sequence, created, integration_cost
+                   sequence, created, integration_cost, cost_computed, pinned
             FROM entries
             WHERE id = ANY($1)
             ORDER BY sequence
@@ -53,7 +53,7 @@ impl BatchEntryQuery {
         .fetch_all(store.pool())
         .await?;
 
-        Ok(rows)
+        store.open_rows(rows).await
     }
 
     /// Execute and return entries in the same order as input IDs.
@@ -124,9 +124,9 @@ impl TopicQuery {
         let query = if self.after_sequence.is_some() && self.limit.is_some() {
             format!(
                 r#"
-                SELECT id, notebook_id, content, content_type, topic,
+                SELECT id, notebook_id, content, compression, encryption, content_type, topic,
                        author_id, signature, revision_of, "references",
-                       sequence, created, integration_cost
+                       sequence, created, integration_cost, cost_computed, pinned
                 FROM entries
                 WHERE notebook_id = $1 AND topic = $2 AND sequence > $3
                 ORDER BY sequence {}
@@ -137,9 +137,9 @@ impl TopicQuery {
         } else if self.after_sequence.is_some() {
             format!(
                 r#"
-                SELECT id, notebook_id, content, content_type, topic,
+                SELECT id, notebook_id, content, compression, encryption, content_type, topic,
                        author_id, signature, revision_of, "references",
-                       sequence, created, integration_cost
+                       sequence, created, integration_cost, cost_computed, pinned
                 FROM entries
                 WHERE notebook_id = $1 AND topic = $2 AND sequence > $3
                 ORDER BY sequence {}
@@ -149,9 +149,9 @@ impl TopicQuery {
         } else if self.limit.is_some() {
             format!(
                 r#"
-                SELECT id, notebook_id, content, content_type, topic,
+                SELECT id, notebook_id, content, compression, encryption, content_type, topic,
                        author_id, signature, revision_of, "references",
-                       sequence, created, integration_cost
+                       sequence, created, integration_cost, cost_computed, pinned
                 FROM entries
                 WHERE notebook_id = $1 AND topic = $2
                 ORDER BY sequence {}
@@ -162,9 +162,9 @@ impl TopicQuery {
         } else {
             format!(
                 r#"
-                SELECT id, notebook_id, content, content_type, topic,
+                SELECT id, notebook_id, content, compression, encryption, content_type, topic,
                        author_id, signature, revision_of, "references",
-                       sequence, created, integration_cost
+                       sequence, created, integration_cost, cost_computed, pinned
                 FROM entries
                 WHERE notebook_id = $1 AND topic = $2
                 ORDER BY sequence {}
@@ -185,7 +185,54 @@ impl TopicQuery {
             q = q.bind(limit);
         }
 
-        Ok(q.fetch_all(store.pool()).await?)
+        store.open_rows(q.fetch_all(store.pool()).await?).await
+    }
+}
+
+/// Number of entries and latest activity of one topic.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct TopicCount {
+    /// The topic, or `None` for entries without one.
+    pub topic: Option<String>,
+    /// Number of entries tagged with the topic.
+    pub entries: i64,
+    /// Sequence of the newest entry tagged with the topic.
+    pub latest_sequence: i64,
+}
+
+/// Query for the distinct topics of a notebook with their entry counts.
+#[derive(Debug, Clone)]
+pub struct TopicCountsQuery {
+    notebook_id: Uuid,
+}
+
+impl TopicCountsQuery {
+    /// Create a new topic counts query.
+    pub fn new(notebook_id: NotebookId) -> Self {
+        Self {
+            notebook_id: notebook_id.0,
+        }
+    }
+
+    /// Execute the query.
+    ///
+    /// Returns one count per topic, largest first; untagged entries are
+    /// counted under a `None` topic, listed last among equal counts.
+    pub async fn execute(&self, store: &Store) -> StoreResult<Vec<TopicCount>> {
+        Ok(sqlx::query_as::<_, TopicCount>(
+            r#"
+            SELECT topic,
+                   COUNT(*)::bigint AS entries,
+                   MAX(sequence) AS latest_sequence
+            FROM entries
+            WHERE notebook_id = $1
+            GROUP BY topic
+            ORDER BY entries DESC, topic NULLS LAST
+            "#,
+        )
+        .bind(self.notebook_id)
+        .fetch_all(store.pool())
+        .await?)
     }
 }
 
@@ -225,9 +272,9 @@ impl AuthorEntriesQuery {
     pub async fn execute(&self, store: &Store) -> StoreResult<Vec<EntryRow>> {
         let query = if self.after_sequence.is_some() && self.limit.is_some() {
             r#"
-            SELECT id, notebook_id, content, content_type, topic,
+            SELECT id, notebook_id, content, compression, encryption, content_type, topic,
                    author_id, signature, revision_of, "references",
-                   sequence, created, integration_cost
+                   sequence, created, integration_cost, cost_computed, pinned
             FROM entries
             WHERE notebook_id = $1 AND author_id = $2 AND sequence > $3
             ORDER BY sequence
@@ -235,18 +282,18 @@ impl AuthorEntriesQuery {
             "#
         } else if self.after_sequence.is_some() {
             r#"
-            SELECT id, notebook_id, content, content_type, topic,
+            SELECT id, notebook_id, content, compression, encryption, content_type, topic,
                    author_id, signature, revision_of, "references",
-                   sequence, created, integration_cost
+                   sequence, created, integration_cost, cost_computed, pinned
             FROM entries
             WHERE notebook_id = $1 AND author_id = $2 AND sequence > $3
             ORDER BY sequence
             "#
         } else if self.limit.is_some() {
             r#"
-            SELECT id, notebook_id, content, content_type, topic,
+            SELECT id, notebook_id, content, compression, encryption, content_type, topic,
                    author_id, signature, revision_of, "references",
-                   sequence, created, integration_cost
+                   sequence, created, integration_cost, cost_computed, pinned
             FROM entries
             WHERE notebook_id = $1 AND author_id = $2
             ORDER BY sequence
@@ -254,9 +301,9 @@ impl AuthorEntriesQuery {
             "#
         } else {
             r#"
-            SELECT id, notebook_id, content, content_type, topic,
+            SELECT id, notebook_id, content, compression, encryption, content_type, topic,
                    author_id, signature, revision_of, "references",
-                   sequence, created, integration_cost
+                   sequence, created, integration_cost, cost_computed, pinned
             FROM entries
             WHERE notebook_id = $1 AND author_id = $2
             ORDER BY sequence
@@ -275,15 +322,20 @@ impl AuthorEntriesQuery {
             q = q.bind(limit);
         }
 
-        Ok(q.fetch_all(store.pool()).await?)
+        store.open_rows(q.fetch_all(store.pool()).await?).await
     }
 }
 
-/// Query for finding orphan entries (no incoming references).
+/// Query for finding orphan entries.
+///
+/// An entry is an orphan when the entropy engine flagged it at write time
+/// (`integration_cost.orphan`): it matched no existing cluster and referenced
+/// no existing entry, so it sits disconnected from the rest of the notebook.
 #[derive(Debug, Clone)]
 pub struct OrphanEntriesQuery {
     notebook_id: Uuid,
     limit: Option<i64>,
+    offset: Option<i64>,
 }
 
 impl OrphanEntriesQuery {
@@ -292,6 +344,7 @@ impl OrphanEntriesQuery {
         Self {
             notebook_id: notebook_id.0,
             limit: None,
+            offset: None,
         }
     }
 
@@ -301,49 +354,51 @@ impl OrphanEntriesQuery {
         self
     }
 
+    /// Skip the first `offset` results.
+    pub fn offset(mut self, offset: i64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
     /// Execute the query.
     ///
-    /// Returns entries that are not referenced by any other entry
-    /// and are not revisions of other entries.
+    /// Returns entries whose integration cost has `orphan` set, oldest first.
     pub async fn execute(&self, store: &Store) -> StoreResult<Vec<EntryRow>> {
-        let query = if self.limit.is_some() {
-            r#"
-            SELECT e.id, e.notebook_id, e.content, e.content_type, e.topic,
-                   e.author_id, e.signature, e.revision_of, e."references",
-                   e.sequence, e.created, e.integration_cost
-            FROM entries e
-            WHERE e.notebook_id = $1
-              AND e.revision_of IS NULL
-              AND NOT EXISTS (
-                  SELECT 1 FROM entries e2
-                  WHERE e2.notebook_id = $1 AND e.id = ANY(e2."references")
-              )
-            ORDER BY e.sequence
-            LIMIT $2
-            "#
-        } else {
+        // LIMIT NULL means no limit
+        let rows = sqlx::query_as::<_, EntryRow>(
             r#"
-            SELECT e.id, e.notebook_id, e.content, e.content_type, e.topic,
-                   e.author_id, e.signature, e.revision_of, e."references",
-                   e.sequence, e.created, e.integration_cost
-            FROM entries e
-            WHERE e.notebook_id = $1
-              AND e.revision_of IS NULL
-              AND NOT EXISTS (
-                  SELECT 1 FROM entries e2
-                  WHERE e2.notebook_id = $1 AND e.id = ANY(e2."references")
-              )
-            ORDER BY e.sequence
-            "#
-        };
-
-        let mut q = sqlx::query_as::<_, EntryRow>(query).bind(self.notebook_id);
+            SELECT id, notebook_id, content, compression, encryption, content_type, topic,
+                   author_id, signature, revision_of, "references",
+                   sequence, created, integration_cost, cost_computed, pinned
+            FROM entries
+            WHERE notebook_id = $1
+              AND (integration_cost->>'orphan')::boolean IS TRUE
+            ORDER BY sequence
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(self.notebook_id)
+        .bind(self.limit)
+        .bind(self.offset.unwrap_or(0))
+        .fetch_all(store.pool())
+        .await?;
 
-        if let Some(limit) = self.limit {
-            q = q.bind(limit);
-        }
+        store.open_rows(rows).await
+    }
 
-        Ok(q.fetch_all(store.pool()).await?)
+    /// Count the orphan entries, ignoring limit and offset.
+    pub async fn count(&self, store: &Store) -> StoreResult<i64> {
+        Ok(sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)::bigint
+            FROM entries
+            WHERE notebook_id = $1
+              AND (integration_cost->>'orphan')::boolean IS TRUE
+            "#,
+        )
+        .bind(self.notebook_id)
+        .fetch_one(store.pool())
+        .await?)
     }
 }
 
@@ -368,9 +423,9 @@ impl BrokenReferencesQuery {
         // Get all entries with references
         let entries: Vec<EntryRow> = sqlx::query_as(
             r#"
-            SELECT id, notebook_id, content, content_type, topic,
+            SELECT id, notebook_id, content, compression, encryption, content_type, topic,
                    author_id, signature, revision_of, "references",
-                   sequence, created, integration_cost
+                   sequence, created, integration_cost, cost_computed, pinned
             FROM entries
             WHERE notebook_id = $1 AND cardinality("references") > 0
             ORDER BY sequence
@@ -401,7 +456,7 @@ impl BrokenReferencesQuery {
                 .collect();
 
             if !broken.is_empty() {
-                result.push((entry, broken));
+                result.push((store.open_row(entry).await?, broken));
             }
         }
 
@@ -486,4 +541,13 @@ mod tests {
         assert_eq!(query.limit, Some(50));
         assert!(query.newest_first);
     }
+
+    #[test]
+    fn test_orphan_query_builder() {
+        let query = OrphanEntriesQuery::new(NotebookId::new())
+            .limit(25)
+            .offset(50);
+        assert_eq!(query.limit, Some(25));
+        assert_eq!(query.offset, Some(50));
+    }
 }
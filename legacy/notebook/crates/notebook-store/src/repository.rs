@@ -17,7 +17,10 @@ use uuid::Uuid;
 
 use crate::Store;
 use crate::error::{StoreError, StoreResult};
-use crate::models::{EntryRow, IntegrationCostJson, NewAuthor, NewEntry, NewNotebook};
+use crate::models::{
+    EntryRow, IntegrationCostJson, NewAuthor, NewEntry, NewNotebook, RevisionChain,
+};
+use crate::queries::{BatchEntryQuery, TopicQuery};
 
 /// Default maximum depth for recursive graph traversal.
 pub const DEFAULT_MAX_DEPTH: u32 = 100;
@@ -31,20 +34,30 @@ pub struct Repository {
     store: Store,
     /// Maximum depth for recursive traversal (default: 100)
     max_depth: u32,
+    /// Maximum length of revision chains (default: the store's)
+    max_revision_depth: u32,
 }
 
 impl Repository {
     /// Create a new repository wrapping the given store.
     pub fn new(store: Store) -> Self {
+        Self::with_max_depth(store, DEFAULT_MAX_DEPTH)
+    }
+
+    /// Create a repository with custom max depth.
+    pub fn with_max_depth(store: Store, max_depth: u32) -> Self {
+        let max_revision_depth = store.max_revision_depth();
         Self {
             store,
-            max_depth: DEFAULT_MAX_DEPTH,
+            max_depth,
+            max_revision_depth,
         }
     }
 
-    /// Create a repository with custom max depth.
-    pub fn with_max_depth(store: Store, max_depth: u32) -> Self {
-        Self { store, max_depth }
+    /// Follow revision chains at most `max_revision_depth` links deep.
+    pub fn with_max_revision_depth(mut self, max_revision_depth: u32) -> Self {
+        self.max_revision_depth = max_revision_depth;
+        self
     }
 
     /// Get reference to the underlying store.
@@ -79,6 +92,48 @@ impl Repository {
         self.entry_row_to_entry(&row).await
     }
 
+    /// Get several entries of a notebook with a single batch query.
+    ///
+    /// Entries are returned in the order of `ids`. IDs that don't exist or
+    /// belong to another notebook are skipped.
+    pub async fn get_entries_in_notebook(
+        &self,
+        notebook_id: NotebookId,
+        ids: &[EntryId],
+    ) -> StoreResult<Vec<Entry>> {
+        let rows = BatchEntryQuery::new(ids.iter().copied())
+            .execute_ordered(&self.store)
+            .await?;
+
+        let mut entries = Vec::with_capacity(rows.len());
+        for row in rows.iter().filter(|row| row.notebook_id == notebook_id.0) {
+            entries.push(self.entry_row_to_entry(row).await?);
+        }
+        Ok(entries)
+    }
+
+    /// Get up to `limit` entries of a notebook tagged with `topic`, in causal
+    /// order, starting after sequence `after` if given.
+    pub async fn get_entries_by_topic(
+        &self,
+        notebook_id: NotebookId,
+        topic: &str,
+        after: Option<u64>,
+        limit: usize,
+    ) -> StoreResult<Vec<Entry>> {
+        let mut query = TopicQuery::new(notebook_id, topic).limit(limit as i64);
+        if let Some(after) = after {
+            query = query.after(after as i64);
+        }
+        let rows = query.execute(&self.store).await?;
+
+        let mut entries = Vec::with_capacity(rows.len());
+        for row in &rows {
+            entries.push(self.entry_row_to_entry(row).await?);
+        }
+        Ok(entries)
+    }
+
     /// Get a specific revision of an entry by revision number.
     ///
     /// Revision 0 is the original entry, revision 1 is the first revision, etc.
@@ -93,31 +148,41 @@ impl Repository {
         }
 
         // Get the revision chain
-        let chain = self.store.get_revisions(id.0).await?;
+        let chain = self
+            .store
+            .get_revisions(id.0, self.max_revision_depth)
+            .await?;
 
         // Find the requested revision
         // Chain is ordered by depth, so index (revision-1) is what we want
         let target_idx = (revision - 1) as usize;
-        if target_idx >= chain.len() {
+        if target_idx >= chain.revisions.len() {
             return Err(StoreError::EntryNotFound(id.0));
         }
 
-        self.entry_row_to_entry(&chain[target_idx]).await
+        self.entry_row_to_entry(&chain.revisions[target_idx]).await
     }
 
     /// Get the full revision chain for an entry.
     ///
-    /// Returns all entries in the revision chain, starting from the
-    /// immediate predecessor and going back to the original.
-    /// Uses cycle-safe traversal with depth limits.
-    pub async fn get_revision_chain(&self, id: EntryId) -> StoreResult<Vec<Entry>> {
-        let rows = self.store.get_revisions(id.0).await?;
+    /// Returns the entries revising this one, nearest first, up to the
+    /// repository's revision depth. The chain reports whether it was cut at
+    /// that depth and whether it looped back on itself.
+    pub async fn get_revision_chain(&self, id: EntryId) -> StoreResult<RevisionChain<Entry>> {
+        let chain = self
+            .store
+            .get_revisions(id.0, self.max_revision_depth)
+            .await?;
 
-        let mut entries = Vec::with_capacity(rows.len());
-        for row in rows {
-            entries.push(self.entry_row_to_entry(&row).await?);
+        let mut revisions = Vec::with_capacity(chain.revisions.len());
+        for row in &chain.revisions {
+            revisions.push(self.entry_row_to_entry(row).await?);
         }
-        Ok(entries)
+        Ok(RevisionChain {
+            revisions,
+            truncated: chain.truncated,
+            cycle: chain.cycle,
+        })
     }
 
     /// Get all entries that this entry references.
@@ -302,6 +367,8 @@ impl Repository {
             revision_of: entry.revision_of.map(|e| e.0),
             references: entry.references.iter().map(|e| e.0).collect(),
             integration_cost: IntegrationCostJson::from(entry.integration_cost),
+            cost_computed: true,
+            if_latest_revision_of: None,
         })
     }
 
@@ -370,6 +437,12 @@ pub struct StoreEntryInput {
     pub entry: Entry,
     /// The notebook to store it in.
     pub notebook_id: NotebookId,
+    /// Whether the entry's integration cost was computed, rather than a
+    /// fallback.
+    pub cost_computed: bool,
+    /// For a conditional revise: the entry whose latest revision must still
+    /// be the entry's `revision_of` when it is stored.
+    pub if_latest_revision_of: Option<EntryId>,
 }
 
 impl Repository {
@@ -380,6 +453,8 @@ impl Repository {
     pub async fn store_entry_in_notebook(&self, input: &StoreEntryInput) -> StoreResult<EntryId> {
         let mut new_entry = self.entry_to_new_entry(&input.entry)?;
         new_entry.notebook_id = input.notebook_id.0;
+        new_entry.cost_computed = input.cost_computed;
+        new_entry.if_latest_revision_of = input.if_latest_revision_of.map(|id| id.0);
 
         let row = self.store.insert_entry(&new_entry).await?;
         Ok(EntryId::from_uuid(row.id))
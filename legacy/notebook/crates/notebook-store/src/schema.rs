@@ -23,10 +23,219 @@ pub const COHERENCE_LINKS_MIGRATION: &str =
 pub const NOTEBOOK_SEQUENCE_MIGRATION: &str =
     include_str!(concat!(env!("OUT_DIR"), "/migrations/006_notebook_sequence.sql"));
 
+/// Embedded migration SQL for the notebook lock flag (022_notebook_lock.sql).
+pub const NOTEBOOK_LOCK_MIGRATION: &str =
+    include_str!(concat!(env!("OUT_DIR"), "/migrations/022_notebook_lock.sql"));
+
+/// Embedded migration SQL for entry content compression (023_entry_compression.sql).
+pub const ENTRY_COMPRESSION_MIGRATION: &str =
+    include_str!(concat!(env!("OUT_DIR"), "/migrations/023_entry_compression.sql"));
+
+/// Embedded migration SQL for the notebook change log (024_notebook_events.sql).
+pub const NOTEBOOK_EVENTS_MIGRATION: &str =
+    include_str!(concat!(env!("OUT_DIR"), "/migrations/024_notebook_events.sql"));
+
+/// Embedded migration SQL for entry encryption (025_notebook_encryption.sql).
+pub const NOTEBOOK_ENCRYPTION_MIGRATION: &str =
+    include_str!(concat!(env!("OUT_DIR"), "/migrations/025_notebook_encryption.sql"));
+
+/// Embedded migration SQL for entropy alert webhooks (026_notebook_alerts.sql).
+pub const NOTEBOOK_ALERTS_MIGRATION: &str =
+    include_str!(concat!(env!("OUT_DIR"), "/migrations/026_notebook_alerts.sql"));
+
+/// Embedded migration SQL for pinned entries (027_entry_pins.sql).
+pub const ENTRY_PINS_MIGRATION: &str =
+    include_str!(concat!(env!("OUT_DIR"), "/migrations/027_entry_pins.sql"));
+
+/// Embedded migration SQL for content-type policies (028_notebook_content_policies.sql).
+pub const CONTENT_POLICIES_MIGRATION: &str = include_str!(concat!(
+    env!("OUT_DIR"),
+    "/migrations/028_notebook_content_policies.sql"
+));
+
+/// Embedded migration SQL for write budgets (029_notebook_write_budgets.sql).
+pub const WRITE_BUDGETS_MIGRATION: &str = include_str!(concat!(
+    env!("OUT_DIR"),
+    "/migrations/029_notebook_write_budgets.sql"
+));
+
+/// Embedded migration SQL for batched reference edges (030_graph_reference_edges.sql).
+pub const GRAPH_REFERENCE_EDGES_MIGRATION: &str = include_str!(concat!(
+    env!("OUT_DIR"),
+    "/migrations/030_graph_reference_edges.sql"
+));
+
+/// Embedded migration SQL for notebook visibility (031_notebook_visibility.sql).
+pub const NOTEBOOK_VISIBILITY_MIGRATION: &str = include_str!(concat!(
+    env!("OUT_DIR"),
+    "/migrations/031_notebook_visibility.sql"
+));
+
+/// Embedded migration SQL for unique notebook names (032_notebook_name_per_owner.sql).
+pub const NOTEBOOK_NAME_PER_OWNER_MIGRATION: &str = include_str!(concat!(
+    env!("OUT_DIR"),
+    "/migrations/032_notebook_name_per_owner.sql"
+));
+
+/// Embedded migration SQL for notebook orphan policies (033_notebook_orphan_policy.sql).
+pub const NOTEBOOK_ORPHAN_POLICY_MIGRATION: &str = include_str!(concat!(
+    env!("OUT_DIR"),
+    "/migrations/033_notebook_orphan_policy.sql"
+));
+
+/// Embedded migration SQL for the entry cost computed flag (034_entry_cost_computed.sql).
+pub const ENTRY_COST_COMPUTED_MIGRATION: &str = include_str!(concat!(
+    env!("OUT_DIR"),
+    "/migrations/034_entry_cost_computed.sql"
+));
+
+/// Embedded migration SQL for notebook labels (035_notebook_labels.sql).
+pub const NOTEBOOK_LABELS_MIGRATION: &str = include_str!(concat!(
+    env!("OUT_DIR"),
+    "/migrations/035_notebook_labels.sql"
+));
+
+/// An embedded migration script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Migration {
+    /// File name, recorded in `schema_migrations` once applied.
+    pub name: &'static str,
+    /// What the migration sets up, for logs and errors.
+    pub description: &'static str,
+    /// The SQL to execute.
+    pub sql: &'static str,
+    /// Failure is logged rather than fatal; the migration stays pending.
+    pub optional: bool,
+}
+
+/// All migrations, in the order they are applied.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        name: "002_schema.sql",
+        description: "Schema",
+        sql: SCHEMA_MIGRATION,
+        optional: false,
+    },
+    // Requires the Apache AGE extension; graph traversal features are
+    // disabled without it
+    Migration {
+        name: "003_graph.sql",
+        description: "Graph",
+        sql: GRAPH_MIGRATION,
+        optional: true,
+    },
+    Migration {
+        name: "004_coherence_links.sql",
+        description: "Coherence links",
+        sql: COHERENCE_LINKS_MIGRATION,
+        optional: false,
+    },
+    Migration {
+        name: "006_notebook_sequence.sql",
+        description: "Notebook sequence",
+        sql: NOTEBOOK_SEQUENCE_MIGRATION,
+        optional: false,
+    },
+    Migration {
+        name: "022_notebook_lock.sql",
+        description: "Notebook lock",
+        sql: NOTEBOOK_LOCK_MIGRATION,
+        optional: false,
+    },
+    Migration {
+        name: "023_entry_compression.sql",
+        description: "Entry compression",
+        sql: ENTRY_COMPRESSION_MIGRATION,
+        optional: false,
+    },
+    Migration {
+        name: "024_notebook_events.sql",
+        description: "Notebook events",
+        sql: NOTEBOOK_EVENTS_MIGRATION,
+        optional: false,
+    },
+    Migration {
+        name: "025_notebook_encryption.sql",
+        description: "Notebook encryption",
+        sql: NOTEBOOK_ENCRYPTION_MIGRATION,
+        optional: false,
+    },
+    Migration {
+        name: "026_notebook_alerts.sql",
+        description: "Notebook alerts",
+        sql: NOTEBOOK_ALERTS_MIGRATION,
+        optional: false,
+    },
+    Migration {
+        name: "027_entry_pins.sql",
+        description: "Entry pins",
+        sql: ENTRY_PINS_MIGRATION,
+        optional: false,
+    },
+    Migration {
+        name: "028_notebook_content_policies.sql",
+        description: "Content policies",
+        sql: CONTENT_POLICIES_MIGRATION,
+        optional: false,
+    },
+    Migration {
+        name: "029_notebook_write_budgets.sql",
+        description: "Write budgets",
+        sql: WRITE_BUDGETS_MIGRATION,
+        optional: false,
+    },
+    // Creating the function does not need AGE; only calling it does
+    Migration {
+        name: "030_graph_reference_edges.sql",
+        description: "Batched reference edges",
+        sql: GRAPH_REFERENCE_EDGES_MIGRATION,
+        optional: false,
+    },
+    Migration {
+        name: "031_notebook_visibility.sql",
+        description: "Notebook visibility",
+        sql: NOTEBOOK_VISIBILITY_MIGRATION,
+        optional: false,
+    },
+    Migration {
+        name: "032_notebook_name_per_owner.sql",
+        description: "Unique notebook names per owner",
+        sql: NOTEBOOK_NAME_PER_OWNER_MIGRATION,
+        optional: false,
+    },
+    Migration {
+        name: "033_notebook_orphan_policy.sql",
+        description: "Notebook orphan policy",
+        sql: NOTEBOOK_ORPHAN_POLICY_MIGRATION,
+        optional: false,
+    },
+    Migration {
+        name: "034_entry_cost_computed.sql",
+        description: "Entry cost computed flag",
+        sql: ENTRY_COST_COMPUTED_MIGRATION,
+        optional: false,
+    },
+    Migration {
+        name: "035_notebook_labels.sql",
+        description: "Notebook labels",
+        sql: NOTEBOOK_LABELS_MIGRATION,
+        optional: false,
+    },
+];
+
+/// Bookkeeping table listing the applied migrations.
+const MIGRATIONS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS schema_migrations (
+    name TEXT PRIMARY KEY,
+    applied TIMESTAMPTZ NOT NULL DEFAULT NOW()
+)
+"#;
+
 /// Run all pending migrations against the database.
 ///
-/// This function is idempotent - it can be run multiple times safely.
-/// Migrations check for existing objects before creating them.
+/// Migrations recorded in `schema_migrations` are skipped. Databases
+/// migrated before that table existed get every migration once more, which
+/// is safe: migrations check for existing objects before creating them.
 ///
 /// # Arguments
 ///
@@ -34,50 +243,75 @@ pub const NOTEBOOK_SEQUENCE_MIGRATION: &str =
 ///
 /// # Errors
 ///
-/// Returns an error if any migration fails to execute.
+/// Returns an error if any non-optional migration fails to execute.
 pub async fn run_migrations(pool: &PgPool) -> StoreResult<()> {
     tracing::info!("Running database migrations...");
 
-    // Run schema migration
-    tracing::debug!("Running schema migration (002_schema.sql)...");
-    sqlx::raw_sql(SCHEMA_MIGRATION)
-        .execute(pool)
-        .await
-        .map_err(|e| StoreError::MigrationError(format!("Schema migration failed: {}", e)))?;
-
-    // Run graph migration (requires Apache AGE extension - non-fatal if unavailable)
-    tracing::debug!("Running graph migration (003_graph.sql)...");
-    match sqlx::raw_sql(GRAPH_MIGRATION).execute(pool).await {
-        Ok(_) => tracing::info!("Graph migration completed successfully"),
-        Err(e) => tracing::warn!(
-            "Graph migration skipped (Apache AGE not available): {}. \
-             Graph traversal features will be disabled.",
-            e
-        ),
-    }
-
-    // Run coherence links migration (pure SQL, always succeeds)
-    tracing::debug!("Running coherence links migration (004_coherence_links.sql)...");
-    sqlx::raw_sql(COHERENCE_LINKS_MIGRATION)
+    sqlx::raw_sql(MIGRATIONS_TABLE)
         .execute(pool)
         .await
-        .map_err(|e| {
-            StoreError::MigrationError(format!("Coherence links migration failed: {}", e))
-        })?;
+        .map_err(|e| StoreError::MigrationError(format!("Migrations table failed: {}", e)))?;
 
-    // Run notebook sequence migration
-    tracing::debug!("Running notebook sequence migration (006_notebook_sequence.sql)...");
-    sqlx::raw_sql(NOTEBOOK_SEQUENCE_MIGRATION)
-        .execute(pool)
-        .await
-        .map_err(|e| {
-            StoreError::MigrationError(format!("Notebook sequence migration failed: {}", e))
-        })?;
+    for migration in pending_migrations(pool).await? {
+        tracing::debug!(
+            "Running {} migration ({})...",
+            migration.description.to_lowercase(),
+            migration.name
+        );
+        match sqlx::raw_sql(migration.sql).execute(pool).await {
+            Ok(_) => {
+                sqlx::query(
+                    "INSERT INTO schema_migrations (name) VALUES ($1) ON CONFLICT DO NOTHING",
+                )
+                .bind(migration.name)
+                .execute(pool)
+                .await?;
+            }
+            Err(e) if migration.optional => tracing::warn!(
+                "{} migration skipped: {}. Dependent features will be disabled.",
+                migration.description,
+                e
+            ),
+            Err(e) => {
+                return Err(StoreError::MigrationError(format!(
+                    "{} migration failed: {}",
+                    migration.description, e
+                )));
+            }
+        }
+    }
 
     tracing::info!("Migrations completed successfully");
     Ok(())
 }
 
+/// List the migrations that have not been applied, without applying them.
+///
+/// Every migration is pending on a database that has never been migrated
+/// through [`run_migrations`].
+pub async fn pending_migrations(pool: &PgPool) -> StoreResult<Vec<&'static Migration>> {
+    let (tracked,): (bool,) =
+        sqlx::query_as("SELECT to_regclass('public.schema_migrations') IS NOT NULL")
+            .fetch_one(pool)
+            .await?;
+    let applied: Vec<String> = if tracked {
+        sqlx::query_scalar("SELECT name FROM schema_migrations")
+            .fetch_all(pool)
+            .await?
+    } else {
+        Vec::new()
+    };
+    Ok(unapplied(&applied))
+}
+
+/// Migrations whose names are not in `applied`, in order.
+fn unapplied(applied: &[String]) -> Vec<&'static Migration> {
+    MIGRATIONS
+        .iter()
+        .filter(|migration| !applied.iter().any(|name| name == migration.name))
+        .collect()
+}
+
 /// Check if the schema has been initialized.
 ///
 /// Returns true if the `entries` table exists.
@@ -97,6 +331,9 @@ pub async fn is_schema_initialized(pool: &PgPool) -> StoreResult<bool> {
     Ok(result.0)
 }
 
+/// Version reported by [`get_schema_version`] once all migrations have run.
+pub const SCHEMA_VERSION: u32 = 5;
+
 /// Get the current schema version by checking which tables exist.
 ///
 /// Returns:
@@ -171,6 +408,29 @@ pub async fn get_schema_version(pool: &PgPool) -> StoreResult<u32> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_migrations_are_ordered_and_unique() {
+        let names: Vec<&str> = MIGRATIONS.iter().map(|m| m.name).collect();
+        let mut sorted = names.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(names, sorted);
+        assert_eq!(MIGRATIONS.last().unwrap().sql, NOTEBOOK_LABELS_MIGRATION);
+    }
+
+    #[test]
+    fn test_unapplied_skips_recorded_migrations() {
+        assert_eq!(unapplied(&[]).len(), MIGRATIONS.len());
+
+        let applied: Vec<String> = MIGRATIONS[..3].iter().map(|m| m.name.to_string()).collect();
+        let pending = unapplied(&applied);
+        assert_eq!(pending.len(), MIGRATIONS.len() - 3);
+        assert_eq!(pending[0], &MIGRATIONS[3]);
+
+        let all: Vec<String> = MIGRATIONS.iter().map(|m| m.name.to_string()).collect();
+        assert!(unapplied(&all).is_empty());
+    }
+
     #[test]
     fn test_schema_migration_embedded() {
         // Verify the migration SQL is properly embedded
@@ -195,6 +455,102 @@ mod tests {
         assert!(NOTEBOOK_SEQUENCE_MIGRATION.contains("ALTER TABLE notebooks"));
     }
 
+    #[test]
+    fn test_notebook_lock_migration_embedded() {
+        assert!(NOTEBOOK_LOCK_MIGRATION.contains("is_locked"));
+        assert!(NOTEBOOK_LOCK_MIGRATION.contains("ALTER TABLE notebooks"));
+    }
+
+    #[test]
+    fn test_entry_compression_migration_embedded() {
+        assert!(ENTRY_COMPRESSION_MIGRATION.contains("compression"));
+        assert!(ENTRY_COMPRESSION_MIGRATION.contains("ALTER TABLE entries"));
+    }
+
+    #[test]
+    fn test_notebook_events_migration_embedded() {
+        assert!(NOTEBOOK_EVENTS_MIGRATION.contains("CREATE TABLE IF NOT EXISTS notebook_events"));
+        assert!(NOTEBOOK_EVENTS_MIGRATION.contains("PRIMARY KEY (notebook_id, seq)"));
+    }
+
+    #[test]
+    fn test_notebook_encryption_migration_embedded() {
+        assert!(NOTEBOOK_ENCRYPTION_MIGRATION.contains("encrypted BOOLEAN"));
+        assert!(NOTEBOOK_ENCRYPTION_MIGRATION.contains("data_key BYTEA"));
+        assert!(NOTEBOOK_ENCRYPTION_MIGRATION.contains("ALTER TABLE entries"));
+    }
+
+    #[test]
+    fn test_notebook_alerts_migration_embedded() {
+        assert!(NOTEBOOK_ALERTS_MIGRATION.contains("CREATE TABLE IF NOT EXISTS notebook_alerts"));
+        assert!(NOTEBOOK_ALERTS_MIGRATION.contains("entropy_threshold DOUBLE PRECISION"));
+        assert!(NOTEBOOK_ALERTS_MIGRATION.contains("triggered BOOLEAN"));
+    }
+
+    #[test]
+    fn test_entry_pins_migration_embedded() {
+        assert!(ENTRY_PINS_MIGRATION.contains("pinned BOOLEAN"));
+        assert!(ENTRY_PINS_MIGRATION.contains("ALTER TABLE entries"));
+    }
+
+    #[test]
+    fn test_content_policies_migration_embedded() {
+        assert!(
+            CONTENT_POLICIES_MIGRATION
+                .contains("CREATE TABLE IF NOT EXISTS notebook_content_policies")
+        );
+        assert!(CONTENT_POLICIES_MIGRATION.contains("allowed_content_types TEXT[]"));
+        assert!(CONTENT_POLICIES_MIGRATION.contains("denied_content_types TEXT[]"));
+    }
+
+    #[test]
+    fn test_write_budgets_migration_embedded() {
+        assert!(
+            WRITE_BUDGETS_MIGRATION.contains("CREATE TABLE IF NOT EXISTS notebook_write_budgets")
+        );
+        assert!(WRITE_BUDGETS_MIGRATION.contains("entropy_budget DOUBLE PRECISION"));
+        assert!(WRITE_BUDGETS_MIGRATION.contains("window_secs INTEGER"));
+    }
+
+    #[test]
+    fn test_graph_reference_edges_migration_embedded() {
+        assert!(GRAPH_REFERENCE_EDGES_MIGRATION.contains("FUNCTION add_reference_edges("));
+        assert!(GRAPH_REFERENCE_EDGES_MIGRATION.contains("p_to_entry_ids UUID[]"));
+    }
+
+    #[test]
+    fn test_notebook_visibility_migration_embedded() {
+        assert!(NOTEBOOK_VISIBILITY_MIGRATION.contains("ALTER TABLE notebooks"));
+        assert!(NOTEBOOK_VISIBILITY_MIGRATION.contains("is_public BOOLEAN"));
+    }
+
+    #[test]
+    fn test_notebook_name_per_owner_migration_embedded() {
+        assert!(NOTEBOOK_NAME_PER_OWNER_MIGRATION.contains("UPDATE notebooks"));
+        assert!(
+            NOTEBOOK_NAME_PER_OWNER_MIGRATION
+                .contains("CREATE UNIQUE INDEX IF NOT EXISTS notebooks_owner_name_key")
+        );
+    }
+
+    #[test]
+    fn test_notebook_orphan_policy_migration_embedded() {
+        assert!(NOTEBOOK_ORPHAN_POLICY_MIGRATION.contains("ALTER TABLE notebooks"));
+        assert!(NOTEBOOK_ORPHAN_POLICY_MIGRATION.contains("orphan_policy TEXT"));
+    }
+
+    #[test]
+    fn test_entry_cost_computed_migration_embedded() {
+        assert!(ENTRY_COST_COMPUTED_MIGRATION.contains("ALTER TABLE entries"));
+        assert!(ENTRY_COST_COMPUTED_MIGRATION.contains("cost_computed BOOLEAN"));
+    }
+
+    #[test]
+    fn test_notebook_labels_migration_embedded() {
+        assert!(NOTEBOOK_LABELS_MIGRATION.contains("CREATE TABLE IF NOT EXISTS notebook_labels"));
+        assert!(NOTEBOOK_LABELS_MIGRATION.contains("PRIMARY KEY (notebook_id, author_id, label)"));
+    }
+
     #[test]
     fn test_coherence_links_migration_embedded() {
         // Verify the coherence links migration SQL is properly embedded
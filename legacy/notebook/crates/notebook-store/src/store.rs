@@ -3,15 +3,33 @@
 //! The `Store` type provides all CRUD operations for entries,
 //! notebooks, authors, and access control.
 
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
-use sqlx::postgres::{PgPool, PgPoolOptions};
+use chrono::{DateTime, Utc};
+use sqlx::PgConnection;
+use sqlx::postgres::{PgConnectOptions, PgPool, PgPoolOptions};
 use uuid::Uuid;
 
+use crate::compression::{
+    CompressionConfig, DEFAULT_LEVEL, DEFAULT_THRESHOLD_BYTES, compress, decompress,
+};
+use crate::encryption::{AES_256_GCM, DataKey, MASTER_KEY_ENV, MasterKey};
 use crate::error::{StoreError, StoreResult};
 use crate::models::*;
+use crate::repository::DEFAULT_MAX_DEPTH;
 use crate::schema;
 
+/// Unique index on notebook names per owner (032_notebook_name_per_owner.sql).
+const NOTEBOOK_OWNER_NAME_INDEX: &str = "notebooks_owner_name_key";
+
+/// Primary key of the entries table (002_schema.sql).
+const ENTRIES_PRIMARY_KEY: &str = "entries_pkey";
+
+/// Default longest a single statement may run, in milliseconds.
+pub const DEFAULT_STATEMENT_TIMEOUT_MS: u64 = 30_000;
+
 /// Configuration for connecting to the database.
 #[derive(Debug, Clone)]
 pub struct StoreConfig {
@@ -21,8 +39,21 @@ pub struct StoreConfig {
     pub max_connections: u32,
     /// Minimum number of connections to maintain.
     pub min_connections: u32,
-    /// Run migrations on connect.
+    /// Handle migrations on connect. When false, the schema is left alone.
     pub run_migrations: bool,
+    /// Apply pending migrations on connect. When false, connecting fails if
+    /// any are pending instead, so schema changes can be applied separately.
+    pub migrate_on_start: bool,
+    /// Compression of entry content at rest.
+    pub compression: CompressionConfig,
+    /// Master key wrapping notebook data keys. Without one, encrypted
+    /// notebooks can be neither created nor read.
+    pub master_key: Option<MasterKey>,
+    /// Longest revision chain followed before it is reported truncated.
+    pub max_revision_depth: u32,
+    /// Longest a single statement may run before the database cancels it,
+    /// in milliseconds. 0 disables the timeout. Migrations are not limited.
+    pub statement_timeout_ms: u64,
 }
 
 impl Default for StoreConfig {
@@ -32,6 +63,11 @@ impl Default for StoreConfig {
             max_connections: 10,
             min_connections: 1,
             run_migrations: true,
+            migrate_on_start: true,
+            compression: CompressionConfig::default(),
+            master_key: None,
+            max_revision_depth: DEFAULT_MAX_DEPTH,
+            statement_timeout_ms: DEFAULT_STATEMENT_TIMEOUT_MS,
         }
     }
 }
@@ -44,6 +80,17 @@ impl StoreConfig {
     /// - `DATABASE_MAX_CONNECTIONS` - Optional, defaults to 10
     /// - `DATABASE_MIN_CONNECTIONS` - Optional, defaults to 1
     /// - `DATABASE_RUN_MIGRATIONS` - Optional, defaults to true
+    /// - `DATABASE_MIGRATE_ON_START` - Optional, defaults to true; when
+    ///   false, pending migrations fail the connection instead of running
+    /// - `ENTRY_COMPRESSION_THRESHOLD` - Optional, minimum content size in
+    ///   bytes before compression is attempted, defaults to 4096
+    /// - `ENTRY_COMPRESSION_LEVEL` - Optional, zstd level, defaults to 3
+    /// - `NOTEBOOK_MASTER_KEY` - Optional, hex-encoded 32-byte key wrapping
+    ///   the data keys of encrypted notebooks
+    /// - `REVISION_CHAIN_MAX_DEPTH` - Optional, longest revision chain
+    ///   followed, defaults to 100
+    /// - `DATABASE_STATEMENT_TIMEOUT_MS` - Optional, longest a statement may
+    ///   run, defaults to 30000; 0 disables the timeout
     pub fn from_env() -> StoreResult<Self> {
         let database_url = std::env::var("DATABASE_URL").map_err(|_| {
             StoreError::ConfigError("DATABASE_URL environment variable not set".to_string())
@@ -64,13 +111,87 @@ impl StoreConfig {
             .map(|s| s.to_lowercase() != "false" && s != "0")
             .unwrap_or(true);
 
+        let migrate_on_start = std::env::var("DATABASE_MIGRATE_ON_START")
+            .ok()
+            .map(|s| s.to_lowercase() != "false" && s != "0")
+            .unwrap_or(true);
+
+        let compression = CompressionConfig {
+            threshold_bytes: std::env::var("ENTRY_COMPRESSION_THRESHOLD")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(DEFAULT_THRESHOLD_BYTES),
+            level: std::env::var("ENTRY_COMPRESSION_LEVEL")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(DEFAULT_LEVEL),
+        };
+
+        let master_key = match std::env::var(MASTER_KEY_ENV) {
+            Ok(hex_key) => Some(MasterKey::from_hex(&hex_key).map_err(|e| {
+                StoreError::ConfigError(format!("invalid {}: {}", MASTER_KEY_ENV, e))
+            })?),
+            Err(_) => None,
+        };
+
+        let max_revision_depth = std::env::var("REVISION_CHAIN_MAX_DEPTH")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .filter(|&depth| depth > 0)
+            .unwrap_or(DEFAULT_MAX_DEPTH);
+
+        let statement_timeout_ms = std::env::var("DATABASE_STATEMENT_TIMEOUT_MS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_STATEMENT_TIMEOUT_MS);
+
         Ok(Self {
             database_url,
             max_connections,
             min_connections,
             run_migrations,
+            migrate_on_start,
+            compression,
+            master_key,
+            max_revision_depth,
+            statement_timeout_ms,
         })
     }
+
+    /// Options for the store's connections, with the statement timeout
+    /// applied.
+    fn connect_options(&self) -> StoreResult<PgConnectOptions> {
+        let options: PgConnectOptions = self.database_url.parse()?;
+        if self.statement_timeout_ms == 0 {
+            return Ok(options);
+        }
+        Ok(options.options([(
+            "statement_timeout",
+            format!("{}ms", self.statement_timeout_ms),
+        )]))
+    }
+}
+
+/// Fail if migrations other than optional ones are pending.
+async fn ensure_migrated(pool: &PgPool) -> StoreResult<()> {
+    let (optional, required): (Vec<&schema::Migration>, Vec<_>) = schema::pending_migrations(pool)
+        .await?
+        .into_iter()
+        .partition(|migration| migration.optional);
+
+    for migration in optional {
+        tracing::warn!("Optional migration {} is not applied", migration.name);
+    }
+    if required.is_empty() {
+        return Ok(());
+    }
+
+    let names: Vec<&str> = required.iter().map(|migration| migration.name).collect();
+    Err(StoreError::MigrationError(format!(
+        "{} migrations pending ({}) and DATABASE_MIGRATE_ON_START is false",
+        names.len(),
+        names.join(", ")
+    )))
 }
 
 /// Database store for the Knowledge Exchange Platform.
@@ -81,12 +202,22 @@ pub struct Store {
     pool: PgPool,
     /// Whether Apache AGE graph extension is available.
     age_available: bool,
+    /// Compression of entry content at rest.
+    compression: CompressionConfig,
+    /// Master key wrapping notebook data keys.
+    master_key: Option<MasterKey>,
+    /// Unwrapped data keys of encrypted notebooks, by notebook.
+    data_keys: Arc<RwLock<HashMap<Uuid, DataKey>>>,
+    /// Longest revision chain followed by revision queries.
+    max_revision_depth: u32,
 }
 
 impl Store {
     /// Connect to the database with the given configuration.
     ///
     /// Optionally runs migrations if `config.run_migrations` is true.
+    /// Statements on the store's connections are cancelled after
+    /// `config.statement_timeout_ms`, surfacing as [`StoreError::Timeout`].
     pub async fn connect(config: StoreConfig) -> StoreResult<Self> {
         tracing::info!("Connecting to database...");
 
@@ -96,13 +227,25 @@ impl Store {
             .idle_timeout(Duration::from_secs(600))
             .max_lifetime(Duration::from_secs(1800))
             .acquire_timeout(Duration::from_secs(5))
-            .connect(&config.database_url)
+            .connect_with(config.connect_options()?)
             .await?;
 
-        tracing::info!("Connected to database");
+        tracing::info!(
+            statement_timeout_ms = config.statement_timeout_ms,
+            "Connected to database"
+        );
 
-        if config.run_migrations {
-            schema::run_migrations(&pool).await?;
+        if config.run_migrations && config.migrate_on_start {
+            // Index builds and backfills may outlast the statement timeout
+            let migration_pool = PgPoolOptions::new()
+                .max_connections(1)
+                .connect(&config.database_url)
+                .await?;
+            let migrated = schema::run_migrations(&migration_pool).await;
+            migration_pool.close().await;
+            migrated?;
+        } else if config.run_migrations {
+            ensure_migrated(&pool).await?;
         }
 
         // Detect AGE availability: schema version >= 3 means graph functions exist
@@ -131,29 +274,73 @@ impl Store {
         Ok(Self {
             pool,
             age_available,
+            compression: config.compression,
+            master_key: config.master_key,
+            data_keys: Arc::default(),
+            max_revision_depth: config.max_revision_depth,
         })
     }
 
     /// Create a store from an existing connection pool.
     ///
-    /// Defaults to `age_available: false` since we cannot detect without querying.
+    /// Defaults to `age_available: false` since we cannot detect without querying,
+    /// uses the default compression settings and revision depth, and has no
+    /// master key.
     pub fn from_pool(pool: PgPool) -> Self {
         Self {
             pool,
             age_available: false,
+            compression: CompressionConfig::default(),
+            master_key: None,
+            data_keys: Arc::default(),
+            max_revision_depth: DEFAULT_MAX_DEPTH,
         }
     }
 
+    /// Use `master_key` to wrap and unwrap notebook data keys.
+    pub fn with_master_key(mut self, master_key: MasterKey) -> Self {
+        self.master_key = Some(master_key);
+        self
+    }
+
+    /// Follow revision chains at most `max_revision_depth` links deep.
+    pub fn with_max_revision_depth(mut self, max_revision_depth: u32) -> Self {
+        self.max_revision_depth = max_revision_depth;
+        self
+    }
+
+    /// Longest revision chain followed by revision queries.
+    pub fn max_revision_depth(&self) -> u32 {
+        self.max_revision_depth
+    }
+
     /// Whether Apache AGE graph extension is available.
     pub fn age_available(&self) -> bool {
         self.age_available
     }
 
+    /// Whether a master key is configured, so encrypted notebooks can be
+    /// created and read.
+    pub fn encryption_available(&self) -> bool {
+        self.master_key.is_some()
+    }
+
     /// Get a reference to the connection pool.
     pub fn pool(&self) -> &PgPool {
         &self.pool
     }
 
+    /// Migrations not yet applied to the database; see
+    /// [`schema::pending_migrations`].
+    pub async fn pending_migrations(&self) -> StoreResult<Vec<&'static schema::Migration>> {
+        schema::pending_migrations(&self.pool).await
+    }
+
+    /// Current schema version of the database; see [`schema::get_schema_version`].
+    pub async fn schema_version(&self) -> StoreResult<u32> {
+        schema::get_schema_version(&self.pool).await
+    }
+
     // ==================== Author Operations ====================
 
     /// Insert a new author.
@@ -215,6 +402,9 @@ impl Store {
     // ==================== Notebook Operations ====================
 
     /// Insert a new notebook.
+    ///
+    /// Fails with [`StoreError::DuplicateNotebookName`] if the owner already
+    /// has a notebook of that name.
     pub async fn insert_notebook(&self, notebook: &NewNotebook) -> StoreResult<NotebookRow> {
         // Verify owner exists
         if !self.author_exists(&notebook.owner_id).await? {
@@ -229,48 +419,244 @@ impl Store {
             )));
         }
 
+        // Encrypted notebooks get a fresh data key, stored wrapped
+        let data_key = if notebook.encrypted {
+            let master_key = self.master_key.as_ref().ok_or_else(|| {
+                StoreError::ConfigError(format!(
+                    "{} must be set to create encrypted notebooks",
+                    MASTER_KEY_ENV
+                ))
+            })?;
+            let data_key = DataKey::generate().map_err(StoreError::EncryptionError)?;
+            let wrapped = master_key
+                .wrap(notebook.id, &data_key)
+                .map_err(StoreError::EncryptionError)?;
+            Some((data_key, wrapped))
+        } else {
+            None
+        };
+
         let row = sqlx::query_as::<_, NotebookRow>(
             r#"
-            INSERT INTO notebooks (id, name, owner_id)
-            VALUES ($1, $2, $3)
-            RETURNING id, name, owner_id, created, current_sequence
+            INSERT INTO notebooks (id, name, owner_id, encrypted, data_key)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, name, owner_id, created, current_sequence, is_locked, encrypted, is_public,
+                orphan_policy
             "#,
         )
         .bind(notebook.id)
         .bind(&notebook.name)
         .bind(notebook.owner_id.as_slice())
+        .bind(notebook.encrypted)
+        .bind(data_key.as_ref().map(|(_, wrapped)| wrapped.as_slice()))
         .fetch_one(&self.pool)
-        .await?;
+        .await;
+        let row = match row {
+            Ok(row) => row,
+            Err(e) => {
+                return Err(self
+                    .name_conflict(e, &notebook.owner_id, &notebook.name)
+                    .await);
+            }
+        };
 
-        // Grant owner full access
-        self.grant_access(&NewNotebookAccess {
-            notebook_id: notebook.id,
-            author_id: notebook.owner_id,
-            read: true,
-            write: true,
-        })
+        if let Some((data_key, _)) = data_key {
+            self.data_keys
+                .write()
+                .expect("data key cache poisoned")
+                .insert(notebook.id, data_key);
+        }
+
+        // Grant owner full access. This is part of creating the notebook, not
+        // a change to it, so it is not recorded in the change log.
+        let mut conn = self.pool.acquire().await?;
+        upsert_access(
+            &mut conn,
+            &NewNotebookAccess {
+                notebook_id: notebook.id,
+                author_id: notebook.owner_id,
+                read: true,
+                write: true,
+            },
+        )
         .await?;
 
         Ok(row)
     }
 
     /// Rename a notebook. Returns the updated row.
+    ///
+    /// Fails with [`StoreError::DuplicateNotebookName`] if the owner already
+    /// has another notebook of that name.
+    ///
+    /// Appends a `notebook_renamed` event to the change log.
     pub async fn rename_notebook(&self, id: Uuid, new_name: &str) -> StoreResult<NotebookRow> {
-        sqlx::query_as::<_, NotebookRow>(
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query_as::<_, NotebookRow>(
             r#"UPDATE notebooks SET name = $2 WHERE id = $1
-            RETURNING id, name, owner_id, created, current_sequence"#,
+            RETURNING id, name, owner_id, created, current_sequence, is_locked, encrypted,
+                is_public, orphan_policy"#,
         )
         .bind(id)
         .bind(new_name)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *tx)
+        .await;
+        let row = match row {
+            Ok(row) => row.ok_or(StoreError::NotebookNotFound(id))?,
+            Err(e) => {
+                // The failed update aborted the transaction; look up the owner outside it
+                drop(tx);
+                let owner_id = self.get_notebook(id).await?.owner_id;
+                return Err(self.name_conflict(e, &owner_id, new_name).await);
+            }
+        };
+
+        append_event(
+            &mut tx,
+            id,
+            event_type::NOTEBOOK_RENAMED,
+            serde_json::json!({ "name": row.name }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(row)
+    }
+
+    /// Turn a failed insert or rename into [`StoreError::DuplicateNotebookName`]
+    /// if it clashed with another of the owner's notebooks.
+    ///
+    /// Any other error, or a clash whose notebook has since been renamed or
+    /// deleted, is passed on as is.
+    async fn name_conflict(&self, err: sqlx::Error, owner_id: &[u8], name: &str) -> StoreError {
+        let clashed = matches!(
+            &err,
+            sqlx::Error::Database(db) if db.constraint() == Some(NOTEBOOK_OWNER_NAME_INDEX)
+        );
+        if !clashed {
+            return err.into();
+        }
+
+        let existing: Result<Option<Uuid>, sqlx::Error> =
+            sqlx::query_scalar("SELECT id FROM notebooks WHERE owner_id = $1 AND name = $2")
+                .bind(owner_id)
+                .bind(name)
+                .fetch_optional(&self.pool)
+                .await;
+        match existing {
+            Ok(Some(existing_id)) => StoreError::DuplicateNotebookName {
+                name: name.to_string(),
+                existing_id,
+            },
+            Ok(None) => err.into(),
+            Err(e) => e.into(),
+        }
+    }
+
+    /// Lock or unlock a notebook. Returns the updated row.
+    ///
+    /// A locked notebook is read-only; enforcement happens at the API layer.
+    /// Appends a `notebook_locked` event to the change log.
+    pub async fn set_notebook_locked(&self, id: Uuid, locked: bool) -> StoreResult<NotebookRow> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query_as::<_, NotebookRow>(
+            r#"UPDATE notebooks SET is_locked = $2 WHERE id = $1
+            RETURNING id, name, owner_id, created, current_sequence, is_locked, encrypted,
+                is_public, orphan_policy"#,
+        )
+        .bind(id)
+        .bind(locked)
+        .fetch_optional(&mut *tx)
         .await?
-        .ok_or(StoreError::NotebookNotFound(id))
+        .ok_or(StoreError::NotebookNotFound(id))?;
+
+        append_event(
+            &mut tx,
+            id,
+            event_type::NOTEBOOK_LOCKED,
+            serde_json::json!({ "locked": row.is_locked }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(row)
+    }
+
+    /// Make a notebook public or private. Returns the updated row.
+    ///
+    /// A public notebook can be read without authentication; enforcement
+    /// happens at the API layer. Appends a `notebook_visibility` event to the
+    /// change log.
+    pub async fn set_notebook_public(&self, id: Uuid, public: bool) -> StoreResult<NotebookRow> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query_as::<_, NotebookRow>(
+            r#"UPDATE notebooks SET is_public = $2 WHERE id = $1
+            RETURNING id, name, owner_id, created, current_sequence, is_locked, encrypted,
+                is_public, orphan_policy"#,
+        )
+        .bind(id)
+        .bind(public)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(StoreError::NotebookNotFound(id))?;
+
+        append_event(
+            &mut tx,
+            id,
+            event_type::NOTEBOOK_VISIBILITY,
+            serde_json::json!({ "public": row.is_public }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(row)
+    }
+
+    /// Set how writes of orphan entries to a notebook are handled. Returns
+    /// the updated row.
+    ///
+    /// `policy` is one of the [`orphan_policy`] values; enforcement happens
+    /// at the API layer. Appends a `notebook_orphan_policy` event to the
+    /// change log.
+    pub async fn set_notebook_orphan_policy(
+        &self,
+        id: Uuid,
+        policy: &str,
+    ) -> StoreResult<NotebookRow> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query_as::<_, NotebookRow>(
+            r#"UPDATE notebooks SET orphan_policy = $2 WHERE id = $1
+            RETURNING id, name, owner_id, created, current_sequence, is_locked, encrypted,
+                is_public, orphan_policy"#,
+        )
+        .bind(id)
+        .bind(policy)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(StoreError::NotebookNotFound(id))?;
+
+        append_event(
+            &mut tx,
+            id,
+            event_type::NOTEBOOK_ORPHAN_POLICY,
+            serde_json::json!({ "orphan_policy": row.orphan_policy }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(row)
     }
 
     /// Get a notebook by ID.
     pub async fn get_notebook(&self, id: Uuid) -> StoreResult<NotebookRow> {
         sqlx::query_as::<_, NotebookRow>(
-            r#"SELECT id, name, owner_id, created, current_sequence FROM notebooks WHERE id = $1"#,
+            r#"SELECT id, name, owner_id, created, current_sequence, is_locked, encrypted, is_public,
+                orphan_policy
+            FROM notebooks WHERE id = $1"#,
         )
         .bind(id)
         .fetch_optional(&self.pool)
@@ -278,6 +664,17 @@ impl Store {
         .ok_or(StoreError::NotebookNotFound(id))
     }
 
+    /// Creation time of a notebook's newest entry, or `None` if it has no
+    /// entries.
+    pub async fn notebook_last_activity(&self, id: Uuid) -> StoreResult<Option<DateTime<Utc>>> {
+        Ok(
+            sqlx::query_scalar("SELECT MAX(created) FROM entries WHERE notebook_id = $1")
+                .bind(id)
+                .fetch_one(&self.pool)
+                .await?,
+        )
+    }
+
     /// List all notebooks for an author (owned or with access).
     pub async fn list_notebooks_for_author(
         &self,
@@ -285,7 +682,8 @@ impl Store {
     ) -> StoreResult<Vec<NotebookRow>> {
         Ok(sqlx::query_as::<_, NotebookRow>(
             r#"
-            SELECT DISTINCT n.id, n.name, n.owner_id, n.created, n.current_sequence
+            SELECT DISTINCT n.id, n.name, n.owner_id, n.created, n.current_sequence,
+                   n.is_locked, n.encrypted, n.is_public, n.orphan_policy
             FROM notebooks n
             LEFT JOIN notebook_access a ON n.id = a.notebook_id
             WHERE n.owner_id = $1 OR a.author_id = $1
@@ -297,29 +695,129 @@ impl Store {
         .await?)
     }
 
+    /// Notebooks an author owns or has been granted read access to, newest
+    /// first.
+    ///
+    /// Unlike [`Self::list_notebooks_for_author`], write-only grants do not
+    /// count.
+    pub async fn readable_notebooks(&self, author_id: &[u8; 32]) -> StoreResult<Vec<NotebookRow>> {
+        Ok(sqlx::query_as::<_, NotebookRow>(
+            r#"
+            SELECT n.id, n.name, n.owner_id, n.created, n.current_sequence,
+                   n.is_locked, n.encrypted, n.is_public, n.orphan_policy
+            FROM notebooks n
+            WHERE n.owner_id = $1
+               OR EXISTS (
+                   SELECT 1 FROM notebook_access a
+                   WHERE a.notebook_id = n.id AND a.author_id = $1 AND a.read = true
+               )
+            ORDER BY n.created DESC
+            "#,
+        )
+        .bind(author_id.as_slice())
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// IDs of the `limit` notebooks with the most recent activity, newest
+    /// first.
+    ///
+    /// Activity is the creation time of a notebook's newest entry, or of the
+    /// notebook itself if it has no entries.
+    pub async fn recently_active_notebooks(&self, limit: i64) -> StoreResult<Vec<Uuid>> {
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            r#"
+            SELECT n.id
+            FROM notebooks n
+            LEFT JOIN LATERAL (
+                SELECT MAX(e.created) AS last_entry
+                FROM entries e
+                WHERE e.notebook_id = n.id
+            ) activity ON true
+            ORDER BY COALESCE(activity.last_entry, n.created) DESC, n.id
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
+    /// List every notebook, oldest first.
+    pub async fn list_all_notebooks(&self) -> StoreResult<Vec<NotebookRow>> {
+        Ok(sqlx::query_as::<_, NotebookRow>(
+            r#"
+            SELECT id, name, owner_id, created, current_sequence, is_locked, encrypted, is_public,
+                   orphan_policy
+            FROM notebooks
+            ORDER BY created, id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
     // ==================== Access Control Operations ====================
 
     /// Grant access to a notebook.
+    ///
+    /// Appends an `access_granted` event to the change log.
     pub async fn grant_access(&self, access: &NewNotebookAccess) -> StoreResult<NotebookAccessRow> {
-        let row = sqlx::query_as::<_, NotebookAccessRow>(
-            r#"
-            INSERT INTO notebook_access (notebook_id, author_id, read, write)
-            VALUES ($1, $2, $3, $4)
-            ON CONFLICT (notebook_id, author_id)
-            DO UPDATE SET read = $3, write = $4, granted = NOW()
-            RETURNING notebook_id, author_id, read, write, granted
-            "#,
+        let mut tx = self.pool.begin().await?;
+
+        let row = upsert_access(&mut tx, access).await?;
+        append_event(
+            &mut tx,
+            access.notebook_id,
+            event_type::ACCESS_GRANTED,
+            serde_json::json!({
+                "author_id": hex_encode(&access.author_id),
+                "read": access.read,
+                "write": access.write,
+            }),
         )
-        .bind(access.notebook_id)
-        .bind(access.author_id.as_slice())
-        .bind(access.read)
-        .bind(access.write)
-        .fetch_one(&self.pool)
         .await?;
 
+        tx.commit().await?;
         Ok(row)
     }
 
+    /// Revoke an author's access to a notebook.
+    ///
+    /// Returns false if the author had no access. Otherwise appends an
+    /// `access_revoked` event to the change log.
+    pub async fn revoke_access(
+        &self,
+        notebook_id: Uuid,
+        author_id: &[u8; 32],
+    ) -> StoreResult<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let result =
+            sqlx::query("DELETE FROM notebook_access WHERE notebook_id = $1 AND author_id = $2")
+                .bind(notebook_id)
+                .bind(author_id.as_slice())
+                .execute(&mut *tx)
+                .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        append_event(
+            &mut tx,
+            notebook_id,
+            event_type::ACCESS_REVOKED,
+            serde_json::json!({ "author_id": hex_encode(author_id) }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
     /// Check if an author has read access to a notebook.
     pub async fn has_read_access(
         &self,
@@ -382,371 +880,3454 @@ impl Store {
         .await?)
     }
 
-    // ==================== Entry Operations ====================
+    // ==================== Change Log Operations ====================
 
-    /// Get the next sequence number for a notebook by atomically incrementing the counter.
-    async fn next_sequence(&self, notebook_id: Uuid) -> StoreResult<i64> {
-        let result: (i64,) = sqlx::query_as(
+    /// Get change log events for a notebook with `seq` greater than `after_seq`,
+    /// oldest first, at most `limit` of them.
+    pub async fn events_after(
+        &self,
+        notebook_id: Uuid,
+        after_seq: i64,
+        limit: i64,
+    ) -> StoreResult<Vec<NotebookEventRow>> {
+        Ok(sqlx::query_as::<_, NotebookEventRow>(
             r#"
-            UPDATE notebooks
-            SET current_sequence = current_sequence + 1
-            WHERE id = $1
-            RETURNING current_sequence
+            SELECT notebook_id, seq, event_type, payload, created
+            FROM notebook_events
+            WHERE notebook_id = $1 AND seq > $2
+            ORDER BY seq
+            LIMIT $3
             "#,
         )
         .bind(notebook_id)
-        .fetch_one(&self.pool)
-        .await?;
+        .bind(after_seq)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?)
+    }
 
-        Ok(result.0)
+    /// Get the `seq` of the newest change log event for a notebook, or 0 if
+    /// nothing has been logged yet.
+    pub async fn latest_event_seq(&self, notebook_id: Uuid) -> StoreResult<i64> {
+        let result: (Option<i64>,) =
+            sqlx::query_as(r#"SELECT MAX(seq) FROM notebook_events WHERE notebook_id = $1"#)
+                .bind(notebook_id)
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(result.0.unwrap_or(0))
     }
 
-    /// Insert a new entry.
-    ///
-    /// This method:
-    /// 1. Validates signature length
-    /// 2. Verifies notebook exists
-    /// 3. Validates all references exist
-    /// 4. Validates revision_of entry exists (if specified)
-    /// 5. Assigns the next sequence number
-    /// 6. Inserts the entry
-    /// 7. Creates graph vertex and edges
-    pub async fn insert_entry(&self, entry: &NewEntry) -> StoreResult<EntryRow> {
-        if entry.signature.len() != 64 {
-            return Err(StoreError::InvalidSignatureLength(entry.signature.len()));
-        }
+    // ==================== Alert Operations ====================
 
-        // Verify notebook exists
-        let _ = self.get_notebook(entry.notebook_id).await?;
+    /// Create or replace a notebook's entropy alert.
+    ///
+    /// Replacing an alert resets it, so the next crossing fires again.
+    /// Appends an `alert_set` event to the change log; the webhook URL is
+    /// left out of it, since subscribers need not be the owner.
+    pub async fn upsert_notebook_alert(
+        &self,
+        notebook_id: Uuid,
+        entropy_threshold: f64,
+        webhook_url: &str,
+    ) -> StoreResult<NotebookAlertRow> {
+        let mut tx = self.pool.begin().await?;
 
-        // Validate references
-        for ref_id in &entry.references {
-            if !self.entry_exists(*ref_id).await? {
-                return Err(StoreError::InvalidReference(*ref_id));
+        let row = sqlx::query_as::<_, NotebookAlertRow>(
+            r#"
+            INSERT INTO notebook_alerts (notebook_id, entropy_threshold, webhook_url)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (notebook_id) DO UPDATE
+            SET entropy_threshold = EXCLUDED.entropy_threshold,
+                webhook_url = EXCLUDED.webhook_url,
+                triggered = FALSE
+            RETURNING notebook_id, entropy_threshold, webhook_url, triggered, created, last_fired
+            "#,
+        )
+        .bind(notebook_id)
+        .bind(entropy_threshold)
+        .bind(webhook_url)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db) if db.is_foreign_key_violation() => {
+                StoreError::NotebookNotFound(notebook_id)
             }
-        }
-
-        // Validate revision_of
-        if let Some(revision_of) = entry.revision_of
-            && !self.entry_exists(revision_of).await?
-        {
-            return Err(StoreError::InvalidRevision(revision_of));
-        }
+            _ => StoreError::from(e),
+        })?;
 
-        // Get next sequence number
-        let sequence = self.next_sequence(entry.notebook_id).await?;
+        append_event(
+            &mut tx,
+            notebook_id,
+            event_type::ALERT_SET,
+            serde_json::json!({ "entropy_threshold": row.entropy_threshold }),
+        )
+        .await?;
 
-        // Serialize integration cost
-        let integration_cost_json = serde_json::to_value(&entry.integration_cost)?;
+        tx.commit().await?;
+        Ok(row)
+    }
 
-        // Insert entry
-        let row = sqlx::query_as::<_, EntryRow>(
+    /// Get a notebook's entropy alert, if one is configured.
+    pub async fn get_notebook_alert(
+        &self,
+        notebook_id: Uuid,
+    ) -> StoreResult<Option<NotebookAlertRow>> {
+        Ok(sqlx::query_as::<_, NotebookAlertRow>(
             r#"
-            INSERT INTO entries (
-                id, notebook_id, content, content_type, topic,
-                author_id, signature, revision_of, "references",
-                sequence, integration_cost
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-            RETURNING id, notebook_id, content, content_type, topic,
-                      author_id, signature, revision_of, "references",
-                      sequence, created, integration_cost
+            SELECT notebook_id, entropy_threshold, webhook_url, triggered, created, last_fired
+            FROM notebook_alerts WHERE notebook_id = $1
             "#,
         )
-        .bind(entry.id)
-        .bind(entry.notebook_id)
-        .bind(&entry.content)
-        .bind(&entry.content_type)
-        .bind(&entry.topic)
-        .bind(entry.author_id.as_slice())
-        .bind(&entry.signature)
-        .bind(entry.revision_of)
-        .bind(&entry.references)
-        .bind(sequence)
-        .bind(integration_cost_json)
-        .fetch_one(&self.pool)
-        .await?;
+        .bind(notebook_id)
+        .fetch_optional(&self.pool)
+        .await?)
+    }
 
-        // Add graph vertex (only if AGE is available; best effort)
-        if self.age_available
-            && let Err(e) = self.add_entry_to_graph(&row).await
-        {
-            tracing::warn!("Failed to add entry to graph: {}", e);
-        }
+    /// Remove a notebook's entropy alert. Returns whether one existed.
+    ///
+    /// Appends an `alert_removed` event when an alert was removed.
+    pub async fn delete_notebook_alert(&self, notebook_id: Uuid) -> StoreResult<bool> {
+        let mut tx = self.pool.begin().await?;
 
-        Ok(row)
-    }
+        let result = sqlx::query(r#"DELETE FROM notebook_alerts WHERE notebook_id = $1"#)
+            .bind(notebook_id)
+            .execute(&mut *tx)
+            .await?;
 
-    /// Check if an entry exists.
-    pub async fn entry_exists(&self, id: Uuid) -> StoreResult<bool> {
-        let result: (bool,) =
-            sqlx::query_as(r#"SELECT EXISTS (SELECT 1 FROM entries WHERE id = $1)"#)
-                .bind(id)
-                .fetch_one(&self.pool)
-                .await?;
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
 
-        Ok(result.0)
+        append_event(
+            &mut tx,
+            notebook_id,
+            event_type::ALERT_REMOVED,
+            serde_json::json!({}),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
     }
 
-    /// Get an entry by ID.
-    pub async fn get_entry(&self, id: Uuid) -> StoreResult<EntryRow> {
-        sqlx::query_as::<_, EntryRow>(
+    /// Set an alert's `triggered` flag if it differs from `triggered`.
+    ///
+    /// Returns whether this call changed it. Concurrent writers that see the
+    /// same crossing race here, and only one of them wins; setting the flag
+    /// also records the firing time.
+    pub async fn set_alert_triggered(
+        &self,
+        notebook_id: Uuid,
+        triggered: bool,
+    ) -> StoreResult<bool> {
+        let result = sqlx::query(
             r#"
-            SELECT id, notebook_id, content, content_type, topic,
-                   author_id, signature, revision_of, "references",
-                   sequence, created, integration_cost
-            FROM entries
-            WHERE id = $1
+            UPDATE notebook_alerts
+            SET triggered = $2,
+                last_fired = CASE WHEN $2 THEN now() ELSE last_fired END
+            WHERE notebook_id = $1 AND triggered <> $2
             "#,
         )
-        .bind(id)
-        .fetch_optional(&self.pool)
-        .await?
-        .ok_or(StoreError::EntryNotFound(id))
+        .bind(notebook_id)
+        .bind(triggered)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
     }
 
-    /// Query entries with filters.
-    pub async fn query_entries(&self, query: &EntryQuery) -> StoreResult<Vec<EntryRow>> {
-        let notebook_id = query.notebook_id.ok_or_else(|| {
-            StoreError::ConfigError("notebook_id is required for entry queries".to_string())
-        })?;
+    // ==================== Content Policy Operations ====================
 
-        // Build dynamic query
-        let mut sql = String::from(
+    /// Create or replace a notebook's content-type policy.
+    ///
+    /// Appends a `content_policy_set` event to the change log.
+    pub async fn upsert_content_policy(
+        &self,
+        notebook_id: Uuid,
+        allowed_content_types: &[String],
+        denied_content_types: &[String],
+    ) -> StoreResult<NotebookContentPolicyRow> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query_as::<_, NotebookContentPolicyRow>(
             r#"
-            SELECT id, notebook_id, content, content_type, topic,
-                   author_id, signature, revision_of, "references",
-                   sequence, created, integration_cost
-            FROM entries
-            WHERE notebook_id = $1
+            INSERT INTO notebook_content_policies
+                (notebook_id, allowed_content_types, denied_content_types)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (notebook_id) DO UPDATE
+            SET allowed_content_types = EXCLUDED.allowed_content_types,
+                denied_content_types = EXCLUDED.denied_content_types,
+                updated = now()
+            RETURNING notebook_id, allowed_content_types, denied_content_types, updated
             "#,
-        );
+        )
+        .bind(notebook_id)
+        .bind(allowed_content_types)
+        .bind(denied_content_types)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db) if db.is_foreign_key_violation() => {
+                StoreError::NotebookNotFound(notebook_id)
+            }
+            _ => StoreError::from(e),
+        })?;
 
-        let mut param_idx = 2;
+        append_event(
+            &mut tx,
+            notebook_id,
+            event_type::CONTENT_POLICY_SET,
+            serde_json::json!({
+                "allowed_content_types": row.allowed_content_types,
+                "denied_content_types": row.denied_content_types,
+            }),
+        )
+        .await?;
 
-        if query.topic.is_some() {
-            sql.push_str(&format!(" AND topic = ${}", param_idx));
-            param_idx += 1;
-        }
+        tx.commit().await?;
+        Ok(row)
+    }
 
-        if query.author_id.is_some() {
-            sql.push_str(&format!(" AND author_id = ${}", param_idx));
-            param_idx += 1;
-        }
+    /// Get a notebook's content-type policy, if one is configured.
+    pub async fn get_content_policy(
+        &self,
+        notebook_id: Uuid,
+    ) -> StoreResult<Option<NotebookContentPolicyRow>> {
+        Ok(sqlx::query_as::<_, NotebookContentPolicyRow>(
+            r#"
+            SELECT notebook_id, allowed_content_types, denied_content_types, updated
+            FROM notebook_content_policies WHERE notebook_id = $1
+            "#,
+        )
+        .bind(notebook_id)
+        .fetch_optional(&self.pool)
+        .await?)
+    }
 
-        if query.after_sequence.is_some() {
-            sql.push_str(&format!(" AND sequence > ${}", param_idx));
-            param_idx += 1;
-        }
+    /// Remove a notebook's content-type policy. Returns whether one existed.
+    ///
+    /// Appends a `content_policy_removed` event when a policy was removed.
+    pub async fn delete_content_policy(&self, notebook_id: Uuid) -> StoreResult<bool> {
+        let mut tx = self.pool.begin().await?;
 
-        if query.newest_first {
-            sql.push_str(" ORDER BY sequence DESC");
-        } else {
-            sql.push_str(" ORDER BY sequence ASC");
-        }
+        let result = sqlx::query(r#"DELETE FROM notebook_content_policies WHERE notebook_id = $1"#)
+            .bind(notebook_id)
+            .execute(&mut *tx)
+            .await?;
 
-        if query.limit.is_some() {
-            sql.push_str(&format!(" LIMIT ${}", param_idx));
+        if result.rows_affected() == 0 {
+            return Ok(false);
         }
 
-        // Execute with appropriate bindings
-        let mut q = sqlx::query_as::<_, EntryRow>(&sql).bind(notebook_id);
+        append_event(
+            &mut tx,
+            notebook_id,
+            event_type::CONTENT_POLICY_REMOVED,
+            serde_json::json!({}),
+        )
+        .await?;
 
-        if let Some(ref topic) = query.topic {
-            q = q.bind(topic);
-        }
+        tx.commit().await?;
+        Ok(true)
+    }
 
-        if let Some(ref author_id) = query.author_id {
-            q = q.bind(author_id.as_slice());
-        }
+    // ==================== Write Budget Operations ====================
 
-        if let Some(after_sequence) = query.after_sequence {
-            q = q.bind(after_sequence);
-        }
+    /// Create or replace a notebook's write budget.
+    ///
+    /// Appends a `write_budget_set` event to the change log.
+    pub async fn upsert_write_budget(
+        &self,
+        notebook_id: Uuid,
+        entropy_budget: f64,
+        window_secs: i32,
+    ) -> StoreResult<NotebookWriteBudgetRow> {
+        let mut tx = self.pool.begin().await?;
 
-        if let Some(limit) = query.limit {
-            q = q.bind(limit);
-        }
+        let row = sqlx::query_as::<_, NotebookWriteBudgetRow>(
+            r#"
+            INSERT INTO notebook_write_budgets (notebook_id, entropy_budget, window_secs)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (notebook_id) DO UPDATE
+            SET entropy_budget = EXCLUDED.entropy_budget,
+                window_secs = EXCLUDED.window_secs,
+                updated = now()
+            RETURNING notebook_id, entropy_budget, window_secs, updated
+            "#,
+        )
+        .bind(notebook_id)
+        .bind(entropy_budget)
+        .bind(window_secs)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db) if db.is_foreign_key_violation() => {
+                StoreError::NotebookNotFound(notebook_id)
+            }
+            _ => StoreError::from(e),
+        })?;
+
+        append_event(
+            &mut tx,
+            notebook_id,
+            event_type::WRITE_BUDGET_SET,
+            serde_json::json!({
+                "entropy_budget": row.entropy_budget,
+                "window_secs": row.window_secs,
+            }),
+        )
+        .await?;
 
-        Ok(q.fetch_all(&self.pool).await?)
+        tx.commit().await?;
+        Ok(row)
     }
 
-    /// Get entries referencing a specific entry.
-    pub async fn get_entries_referencing(&self, entry_id: Uuid) -> StoreResult<Vec<EntryRow>> {
-        Ok(sqlx::query_as::<_, EntryRow>(
+    /// Get a notebook's write budget, if one is configured.
+    pub async fn get_write_budget(
+        &self,
+        notebook_id: Uuid,
+    ) -> StoreResult<Option<NotebookWriteBudgetRow>> {
+        Ok(sqlx::query_as::<_, NotebookWriteBudgetRow>(
             r#"
-            SELECT id, notebook_id, content, content_type, topic,
-                   author_id, signature, revision_of, "references",
-                   sequence, created, integration_cost
-            FROM entries
-            WHERE $1 = ANY("references")
-            ORDER BY sequence
+            SELECT notebook_id, entropy_budget, window_secs, updated
+            FROM notebook_write_budgets WHERE notebook_id = $1
             "#,
         )
-        .bind(entry_id)
-        .fetch_all(&self.pool)
+        .bind(notebook_id)
+        .fetch_optional(&self.pool)
         .await?)
     }
 
-    /// Get all revisions of an entry (revision chain).
-    pub async fn get_revisions(&self, entry_id: Uuid) -> StoreResult<Vec<EntryRow>> {
-        Ok(sqlx::query_as::<_, EntryRow>(
-            r#"
-            WITH RECURSIVE revision_chain AS (
-                SELECT id, notebook_id, content, content_type, topic,
-                       author_id, signature, revision_of, "references",
-                       sequence, created, integration_cost, 1 as depth
-                FROM entries
-                WHERE revision_of = $1
+    /// Remove a notebook's write budget. Returns whether one existed.
+    ///
+    /// Appends a `write_budget_removed` event when a budget was removed.
+    pub async fn delete_write_budget(&self, notebook_id: Uuid) -> StoreResult<bool> {
+        let mut tx = self.pool.begin().await?;
 
-                UNION ALL
+        let result = sqlx::query(r#"DELETE FROM notebook_write_budgets WHERE notebook_id = $1"#)
+            .bind(notebook_id)
+            .execute(&mut *tx)
+            .await?;
 
-                SELECT e.id, e.notebook_id, e.content, e.content_type, e.topic,
-                       e.author_id, e.signature, e.revision_of, e."references",
-                       e.sequence, e.created, e.integration_cost, rc.depth + 1
-                FROM entries e
-                JOIN revision_chain rc ON e.revision_of = rc.id
-                WHERE rc.depth < 100  -- Prevent infinite loops
-            )
-            SELECT id, notebook_id, content, content_type, topic,
-                   author_id, signature, revision_of, "references",
-                   sequence, created, integration_cost
-            FROM revision_chain
-            ORDER BY depth
-            "#,
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        append_event(
+            &mut tx,
+            notebook_id,
+            event_type::WRITE_BUDGET_REMOVED,
+            serde_json::json!({}),
         )
-        .bind(entry_id)
-        .fetch_all(&self.pool)
-        .await?)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
     }
 
-    /// Get activity context for computing causal position.
-    pub async fn get_activity_context(
+    // ==================== Label Operations ====================
+
+    /// Apply a label to a notebook on behalf of an author.
+    ///
+    /// Returns false if the author had already applied it.
+    pub async fn add_notebook_label(
         &self,
         notebook_id: Uuid,
         author_id: &[u8; 32],
-    ) -> StoreResult<(u32, u32)> {
-        // Get total entries in notebook
-        let total: (i64,) =
-            sqlx::query_as(r#"SELECT COUNT(*) FROM entries WHERE notebook_id = $1"#)
-                .bind(notebook_id)
-                .fetch_one(&self.pool)
-                .await?;
+        label: &str,
+    ) -> StoreResult<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO notebook_labels (notebook_id, author_id, label)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(notebook_id)
+        .bind(author_id.as_slice())
+        .bind(label)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db) if db.is_foreign_key_violation() => {
+                StoreError::NotebookNotFound(notebook_id)
+            }
+            _ => StoreError::from(e),
+        })?;
 
-        // Get entries since last by this author
-        let last_by_author: (Option<i64>,) = sqlx::query_as(
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Remove an author's label from a notebook. Returns whether it was applied.
+    pub async fn remove_notebook_label(
+        &self,
+        notebook_id: Uuid,
+        author_id: &[u8; 32],
+        label: &str,
+    ) -> StoreResult<bool> {
+        let result = sqlx::query(
             r#"
-            SELECT MAX(sequence) FROM entries
-            WHERE notebook_id = $1 AND author_id = $2
+            DELETE FROM notebook_labels
+            WHERE notebook_id = $1 AND author_id = $2 AND label = $3
             "#,
         )
         .bind(notebook_id)
         .bind(author_id.as_slice())
-        .fetch_one(&self.pool)
+        .bind(label)
+        .execute(&self.pool)
         .await?;
 
-        let entries_since = match last_by_author.0 {
-            Some(last_seq) => {
-                let count: (i64,) = sqlx::query_as(
-                    r#"
-                    SELECT COUNT(*) FROM entries
-                    WHERE notebook_id = $1 AND sequence > $2
-                    "#,
-                )
-                .bind(notebook_id)
-                .bind(last_seq)
-                .fetch_one(&self.pool)
-                .await?;
-                count.0 as u32
-            }
-            None => total.0 as u32, // Author has no entries yet
-        };
+        Ok(result.rows_affected() == 1)
+    }
 
-        Ok((entries_since, total.0 as u32))
+    /// The labels an author has applied to a notebook, sorted.
+    pub async fn notebook_labels(
+        &self,
+        notebook_id: Uuid,
+        author_id: &[u8; 32],
+    ) -> StoreResult<Vec<String>> {
+        Ok(sqlx::query_scalar(
+            r#"
+            SELECT label FROM notebook_labels
+            WHERE notebook_id = $1 AND author_id = $2
+            ORDER BY label
+            "#,
+        )
+        .bind(notebook_id)
+        .bind(author_id.as_slice())
+        .fetch_all(&self.pool)
+        .await?)
     }
 
-    // ==================== Entropy Operations ====================
+    /// Every label an author has applied, sorted by label within each notebook.
+    pub async fn author_notebook_labels(
+        &self,
+        author_id: &[u8; 32],
+    ) -> StoreResult<Vec<NotebookLabelRow>> {
+        Ok(sqlx::query_as::<_, NotebookLabelRow>(
+            r#"
+            SELECT notebook_id, label FROM notebook_labels
+            WHERE author_id = $1
+            ORDER BY notebook_id, label
+            "#,
+        )
+        .bind(author_id.as_slice())
+        .fetch_all(&self.pool)
+        .await?)
+    }
 
-    /// Get the recent entropy for a notebook (rolling sum of catalog_shift from last 10 entries).
-    pub async fn get_recent_entropy(&self, notebook_id: Uuid) -> StoreResult<f64> {
-        let result: (Option<f64>,) = sqlx::query_as(
+    // ==================== Entry Operations ====================
+
+    /// Get the next sequence number for a notebook by atomically incrementing the counter.
+    ///
+    /// Fails with [`StoreError::NotebookLocked`] if the notebook is locked; the
+    /// increment is rolled back with the caller's transaction.
+    async fn next_sequence(conn: &mut PgConnection, notebook_id: Uuid) -> StoreResult<i64> {
+        let (sequence, is_locked): (i64, bool) = sqlx::query_as(
             r#"
-            SELECT SUM((integration_cost->>'catalog_shift')::FLOAT8)
-            FROM (
-                SELECT integration_cost
-                FROM entries
-                WHERE notebook_id = $1
-                ORDER BY sequence DESC
-                LIMIT 10
-            ) AS recent_entries
+            UPDATE notebooks
+            SET current_sequence = current_sequence + 1
+            WHERE id = $1
+            RETURNING current_sequence, is_locked
             "#,
         )
         .bind(notebook_id)
-        .fetch_one(&self.pool)
+        .fetch_one(conn)
         .await?;
 
-        Ok(result.0.unwrap_or(0.0))
+        if is_locked {
+            return Err(StoreError::NotebookLocked(notebook_id));
+        }
+        Ok(sequence)
     }
 
-    // ==================== Graph Operations ====================
+    /// Lock the given entries against concurrent modification for the rest
+    /// of the transaction and return the IDs of those that exist.
+    async fn lock_entries(conn: &mut PgConnection, ids: &[Uuid]) -> StoreResult<Vec<Uuid>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
 
-    /// Add an entry vertex and edges to the graph.
-    async fn add_entry_to_graph(&self, entry: &EntryRow) -> StoreResult<()> {
-        // Convert author_id to hex string for graph storage
-        let author_hex: String = entry
-            .author_id
-            .iter()
-            .map(|b| format!("{:02x}", b))
-            .collect();
+        let rows: Vec<(Uuid,)> =
+            sqlx::query_as(r#"SELECT id FROM entries WHERE id = ANY($1) FOR SHARE"#)
+                .bind(ids)
+                .fetch_all(conn)
+                .await?;
 
-        // Add vertex
-        sqlx::query("SELECT add_entry_vertex($1, $2, $3, $4, $5)")
-            .bind(entry.id)
-            .bind(entry.notebook_id)
-            .bind(&entry.topic)
-            .bind(&author_hex)
-            .bind(entry.sequence)
-            .execute(&self.pool)
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
+    /// Insert a new entry.
+    ///
+    /// This method:
+    /// 1. Validates signature length
+    /// 2. Verifies notebook exists and is not locked
+    /// 3. For a conditional revise, locks the revised entry and checks that
+    ///    `revision_of` is still its latest revision, failing with
+    ///    [`StoreError::RevisionConflict`] otherwise
+    /// 4. Validates all references and revision_of (if specified) exist,
+    ///    locking them so they cannot be deleted before the insert commits
+    /// 5. Assigns the next sequence number
+    /// 6. Inserts the entry and appends an `entry` event to the change log,
+    ///    atomically with steps 3 to 5; fails with
+    ///    [`StoreError::DuplicateEntry`] if the entry's ID is taken
+    /// 7. Creates graph vertex and edges
+    pub async fn insert_entry(&self, entry: &NewEntry) -> StoreResult<EntryRow> {
+        if entry.signature.len() != 64 {
+            return Err(StoreError::InvalidSignatureLength(entry.signature.len()));
+        }
+
+        // Verify notebook exists and accepts writes
+        let notebook = self.get_notebook(entry.notebook_id).await?;
+        if notebook.is_locked {
+            return Err(StoreError::NotebookLocked(notebook.id));
+        }
+
+        // Compress large content at rest, then seal it if the notebook is
+        // encrypted; the returned row is opened again
+        let (stored_content, compression) = compress(&entry.content, &self.compression);
+        let (stored_content, encryption) = if notebook.encrypted {
+            let sealed = self
+                .data_key(notebook.id)
+                .await?
+                .seal(entry.id, &stored_content)
+                .map_err(StoreError::EncryptionError)?;
+            (sealed, Some(AES_256_GCM))
+        } else {
+            (stored_content.into_owned(), None)
+        };
+
+        let mut tx = self.pool.begin().await?;
+
+        // Conditional revises of one entry queue on its row, so each sees
+        // the revisions committed before it
+        if let Some(revised) = entry.if_latest_revision_of {
+            sqlx::query("SELECT id FROM entries WHERE id = $1 FOR UPDATE")
+                .bind(revised)
+                .execute(&mut *tx)
+                .await?;
+            let latest =
+                Self::latest_revision_in(&mut *tx, revised, self.max_revision_depth).await?;
+            if entry.revision_of != Some(latest) {
+                return Err(StoreError::RevisionConflict {
+                    entry_id: revised,
+                    latest,
+                });
+            }
+        }
+
+        // Validate references and revision_of under row locks
+        let mut targets = entry.references.clone();
+        targets.extend(entry.revision_of);
+        let existing = Self::lock_entries(&mut tx, &targets).await?;
+        if let Some(ref_id) = entry.references.iter().find(|id| !existing.contains(id)) {
+            return Err(StoreError::InvalidReference(*ref_id));
+        }
+        if let Some(revision_of) = entry.revision_of
+            && !existing.contains(&revision_of)
+        {
+            return Err(StoreError::InvalidRevision(revision_of));
+        }
+
+        // Get next sequence number
+        let sequence = Self::next_sequence(&mut tx, entry.notebook_id).await?;
+
+        // Serialize integration cost
+        let integration_cost_json = serde_json::to_value(&entry.integration_cost)?;
+
+        // Insert entry
+        let row = sqlx::query_as::<_, EntryRow>(
+            r#"
+            INSERT INTO entries (
+                id, notebook_id, content, compression, encryption, content_type, topic,
+                author_id, signature, revision_of, "references",
+                sequence, integration_cost, cost_computed
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            RETURNING id, notebook_id, content, compression, encryption, content_type, topic,
+                      author_id, signature, revision_of, "references",
+                      sequence, created, integration_cost, cost_computed, pinned
+            "#,
+        )
+        .bind(entry.id)
+        .bind(entry.notebook_id)
+        .bind(&stored_content)
+        .bind(compression)
+        .bind(encryption)
+        .bind(&entry.content_type)
+        .bind(&entry.topic)
+        .bind(entry.author_id.as_slice())
+        .bind(&entry.signature)
+        .bind(entry.revision_of)
+        .bind(&entry.references)
+        .bind(sequence)
+        .bind(&integration_cost_json)
+        .bind(entry.cost_computed)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.constraint() == Some(ENTRIES_PRIMARY_KEY) => {
+                StoreError::DuplicateEntry(entry.id)
+            }
+            other => other.into(),
+        })?;
+        let row = self.open_row(row).await?;
+
+        let operation = if entry.revision_of.is_some() {
+            "revise"
+        } else {
+            "write"
+        };
+        append_event(
+            &mut tx,
+            entry.notebook_id,
+            event_type::ENTRY,
+            serde_json::json!({
+                "entry_id": row.id,
+                "operation": operation,
+                "topic": row.topic,
+                "author_id": hex_encode(&entry.author_id),
+                "sequence": row.sequence,
+                "integration_cost": integration_cost_json,
+            }),
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        // Add graph vertex (only if AGE is available; best effort)
+        if self.age_available
+            && let Err(e) = self.add_entry_to_graph(&row).await
+        {
+            tracing::warn!("Failed to add entry to graph: {}", e);
+        }
+
+        Ok(row)
+    }
+
+    /// Overwrite the stored integration cost of an entry and mark it as
+    /// computed.
+    ///
+    /// Used to backfill costs that could not be computed at write time.
+    pub async fn update_integration_cost(
+        &self,
+        id: Uuid,
+        cost: &IntegrationCostJson,
+    ) -> StoreResult<()> {
+        let result = sqlx::query(
+            r#"UPDATE entries SET integration_cost = $2, cost_computed = TRUE WHERE id = $1"#,
+        )
+        .bind(id)
+        .bind(serde_json::to_value(cost)?)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(StoreError::EntryNotFound(id));
+        }
+        Ok(())
+    }
+
+    /// Check if an entry exists.
+    pub async fn entry_exists(&self, id: Uuid) -> StoreResult<bool> {
+        let result: (bool,) =
+            sqlx::query_as(r#"SELECT EXISTS (SELECT 1 FROM entries WHERE id = $1)"#)
+                .bind(id)
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(result.0)
+    }
+
+    /// Pin or unpin an entry of a notebook.
+    ///
+    /// Pinned entries are always listed among their cluster's representatives
+    /// in catalogs. The notebook row is locked for the update, so a notebook
+    /// locked concurrently fails with [`StoreError::NotebookLocked`]. An
+    /// `entry_pinned` or `entry_unpinned` event is appended to the change log
+    /// in the same transaction.
+    pub async fn set_entry_pinned(
+        &self,
+        notebook_id: Uuid,
+        id: Uuid,
+        pinned: bool,
+    ) -> StoreResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let (is_locked,): (bool,) =
+            sqlx::query_as(r#"SELECT is_locked FROM notebooks WHERE id = $1 FOR UPDATE"#)
+                .bind(notebook_id)
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or(StoreError::NotebookNotFound(notebook_id))?;
+        if is_locked {
+            return Err(StoreError::NotebookLocked(notebook_id));
+        }
+
+        let result =
+            sqlx::query(r#"UPDATE entries SET pinned = $3 WHERE id = $1 AND notebook_id = $2"#)
+                .bind(id)
+                .bind(notebook_id)
+                .bind(pinned)
+                .execute(&mut *tx)
+                .await?;
+        if result.rows_affected() == 0 {
+            return Err(StoreError::EntryNotFound(id));
+        }
+
+        let event = if pinned {
+            event_type::ENTRY_PINNED
+        } else {
+            event_type::ENTRY_UNPINNED
+        };
+        append_event(
+            &mut tx,
+            notebook_id,
+            event,
+            serde_json::json!({ "entry_id": id }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Rename a topic on every entry of a notebook that carries it.
+    ///
+    /// Returns the number of entries updated. When any entry changed, a
+    /// `topic_renamed` event is appended to the change log in the same
+    /// transaction.
+    ///
+    /// Topics are covered by entry signatures, so renamed entries no longer
+    /// verify against their original signature.
+    pub async fn rename_topic(&self, notebook_id: Uuid, from: &str, to: &str) -> StoreResult<u64> {
+        let mut tx = self.pool.begin().await?;
+
+        let result =
+            sqlx::query(r#"UPDATE entries SET topic = $3 WHERE notebook_id = $1 AND topic = $2"#)
+                .bind(notebook_id)
+                .bind(from)
+                .bind(to)
+                .execute(&mut *tx)
+                .await?;
+        let updated = result.rows_affected();
+
+        if updated > 0 {
+            append_event(
+                &mut tx,
+                notebook_id,
+                event_type::TOPIC_RENAMED,
+                serde_json::json!({ "from": from, "to": to, "entries": updated }),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(updated)
+    }
+
+    /// Return which of the given entry IDs belong to the notebook.
+    ///
+    /// IDs that do not exist or live in another notebook are omitted.
+    pub async fn entries_in_notebook(
+        &self,
+        notebook_id: Uuid,
+        ids: &[Uuid],
+    ) -> StoreResult<Vec<Uuid>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows: Vec<(Uuid,)> =
+            sqlx::query_as(r#"SELECT id FROM entries WHERE notebook_id = $1 AND id = ANY($2)"#)
+                .bind(notebook_id)
+                .bind(ids)
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
+    /// Return which of the given entry IDs exist, in any notebook.
+    pub async fn existing_entry_ids(&self, ids: &[Uuid]) -> StoreResult<Vec<Uuid>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows: Vec<(Uuid,)> = sqlx::query_as(r#"SELECT id FROM entries WHERE id = ANY($1)"#)
+            .bind(ids)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
+    /// Return which of the given entry IDs carry a fallback integration
+    /// cost, written because the cost could not be computed.
+    pub async fn fallback_cost_entry_ids(&self, ids: &[Uuid]) -> StoreResult<Vec<Uuid>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows: Vec<(Uuid,)> =
+            sqlx::query_as(r#"SELECT id FROM entries WHERE id = ANY($1) AND NOT cost_computed"#)
+                .bind(ids)
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
+    /// Integration costs of a notebook's entries, in causal order.
+    ///
+    /// Entries still carrying a fallback cost are left out, since their cost
+    /// was never computed.
+    pub async fn computed_integration_costs(
+        &self,
+        notebook_id: Uuid,
+    ) -> StoreResult<Vec<IntegrationCostJson>> {
+        let rows: Vec<(serde_json::Value,)> = sqlx::query_as(
+            r#"
+            SELECT integration_cost FROM entries
+            WHERE notebook_id = $1 AND cost_computed
+            ORDER BY sequence
+            "#,
+        )
+        .bind(notebook_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|(cost,)| Ok(serde_json::from_value(cost)?))
+            .collect()
+    }
+
+    /// Get an entry by ID.
+    pub async fn get_entry(&self, id: Uuid) -> StoreResult<EntryRow> {
+        let row = sqlx::query_as::<_, EntryRow>(
+            r#"
+            SELECT id, notebook_id, content, compression, encryption, content_type, topic,
+                   author_id, signature, revision_of, "references",
+                   sequence, created, integration_cost, cost_computed, pinned
+            FROM entries
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(StoreError::EntryNotFound(id))?;
+
+        self.open_row(row).await
+    }
+
+    /// Query entries with filters.
+    pub async fn query_entries(&self, query: &EntryQuery) -> StoreResult<Vec<EntryRow>> {
+        let notebook_id = query.notebook_id.ok_or_else(|| {
+            StoreError::ConfigError("notebook_id is required for entry queries".to_string())
+        })?;
+
+        // Build dynamic query
+        let mut sql = String::from(
+            r#"
+            SELECT id, notebook_id, content, compression, encryption, content_type, topic,
+                   author_id, signature, revision_of, "references",
+                   sequence, created, integration_cost, cost_computed, pinned
+            FROM entries
+            WHERE notebook_id = $1
+            "#,
+        );
+
+        let mut param_idx = 2;
+
+        if query.topic.is_some() {
+            sql.push_str(&format!(" AND topic = ${}", param_idx));
+            param_idx += 1;
+        }
+
+        if query.author_id.is_some() {
+            sql.push_str(&format!(" AND author_id = ${}", param_idx));
+            param_idx += 1;
+        }
+
+        if query.after_sequence.is_some() {
+            sql.push_str(&format!(" AND sequence > ${}", param_idx));
+            param_idx += 1;
+        }
+
+        if query.newest_first {
+            sql.push_str(" ORDER BY sequence DESC");
+        } else {
+            sql.push_str(" ORDER BY sequence ASC");
+        }
+
+        if query.limit.is_some() {
+            sql.push_str(&format!(" LIMIT ${}", param_idx));
+        }
+
+        // Execute with appropriate bindings
+        let mut q = sqlx::query_as::<_, EntryRow>(&sql).bind(notebook_id);
+
+        if let Some(ref topic) = query.topic {
+            q = q.bind(topic);
+        }
+
+        if let Some(ref author_id) = query.author_id {
+            q = q.bind(author_id.as_slice());
+        }
+
+        if let Some(after_sequence) = query.after_sequence {
+            q = q.bind(after_sequence);
+        }
+
+        if let Some(limit) = query.limit {
+            q = q.bind(limit);
+        }
+
+        self.open_rows(q.fetch_all(&self.pool).await?).await
+    }
+
+    /// Get entries referencing a specific entry.
+    pub async fn get_entries_referencing(&self, entry_id: Uuid) -> StoreResult<Vec<EntryRow>> {
+        let rows = sqlx::query_as::<_, EntryRow>(
+            r#"
+            SELECT id, notebook_id, content, compression, encryption, content_type, topic,
+                   author_id, signature, revision_of, "references",
+                   sequence, created, integration_cost, cost_computed, pinned
+            FROM entries
+            WHERE $1 = ANY("references")
+            ORDER BY sequence
+            "#,
+        )
+        .bind(entry_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.open_rows(rows).await
+    }
+
+    /// Get the revisions of an entry (revision chain), nearest first.
+    ///
+    /// Follows `revision_of` links forward at most `max_depth` links from
+    /// the entry. If the chain goes on, it is cut there and marked
+    /// `truncated`. A revision that links back to an entry already in the
+    /// chain is left out and marks the chain as a `cycle`.
+    pub async fn get_revisions(
+        &self,
+        entry_id: Uuid,
+        max_depth: u32,
+    ) -> StoreResult<RevisionChain> {
+        // Fetch one level past the limit to tell whether the chain goes on
+        let rows = sqlx::query_as::<_, RevisionChainRow>(
+            r#"
+            WITH RECURSIVE revision_chain AS (
+                SELECT id, notebook_id, content, compression, encryption, content_type, topic,
+                       author_id, signature, revision_of, "references",
+                       sequence, created, integration_cost, cost_computed, pinned, 1 as depth,
+                       ARRAY[$1::uuid, id] AS path, id = $1 AS cycle
+                FROM entries
+                WHERE revision_of = $1
+
+                UNION ALL
+
+                SELECT e.id, e.notebook_id, e.content, e.compression, e.encryption,
+                       e.content_type, e.topic,
+                       e.author_id, e.signature, e.revision_of, e."references",
+                       e.sequence, e.created, e.integration_cost, e.cost_computed, e.pinned, rc.depth + 1,
+                       rc.path || e.id, e.id = ANY(rc.path)
+                FROM entries e
+                JOIN revision_chain rc ON e.revision_of = rc.id
+                WHERE NOT rc.cycle AND rc.depth <= $2
+            )
+            SELECT id, notebook_id, content, compression, encryption, content_type, topic,
+                   author_id, signature, revision_of, "references",
+                   sequence, created, integration_cost, cost_computed, pinned, depth, cycle
+            FROM revision_chain
+            ORDER BY depth
+            "#,
+        )
+        .bind(entry_id)
+        .bind(max_depth as i32)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut chain = RevisionChain::default();
+        let mut revisions = Vec::with_capacity(rows.len());
+        for row in rows {
+            if row.cycle {
+                chain.cycle = true;
+            } else if row.depth as u32 > max_depth {
+                chain.truncated = true;
+            } else {
+                revisions.push(row.entry);
+            }
+        }
+        chain.revisions = self.open_rows(revisions).await?;
+        Ok(chain)
+    }
+
+    /// Count the entries in an entry's revision chain.
+    ///
+    /// Same traversal as `get_revisions` up to the store's
+    /// [`max_revision_depth`](Self::max_revision_depth), but only counts ids
+    /// so callers can report the chain length without loading every revision.
+    pub async fn revision_count(&self, entry_id: Uuid) -> StoreResult<i64> {
+        let (count,): (i64,) = sqlx::query_as(
+            r#"
+            WITH RECURSIVE revision_chain AS (
+                SELECT id, 1 as depth, ARRAY[$1::uuid, id] AS path, id = $1 AS cycle
+                FROM entries
+                WHERE revision_of = $1
+
+                UNION ALL
+
+                SELECT e.id, rc.depth + 1, rc.path || e.id, e.id = ANY(rc.path)
+                FROM entries e
+                JOIN revision_chain rc ON e.revision_of = rc.id
+                WHERE NOT rc.cycle AND rc.depth < $2
+            )
+            SELECT COUNT(*) FROM revision_chain WHERE NOT cycle
+            "#,
+        )
+        .bind(entry_id)
+        .bind(self.max_revision_depth as i32)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Get listing metadata for the given entries in one query.
+    ///
+    /// Revision counts and latest sequences follow `revision_of` links
+    /// forward, like [`Store::revision_count`], at most
+    /// [`max_revision_depth`](Self::max_revision_depth) deep. Missing IDs are omitted;
+    /// rows come back in no particular order.
+    pub async fn entry_summaries(&self, ids: &[Uuid]) -> StoreResult<Vec<EntrySummaryRow>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(sqlx::query_as::<_, EntrySummaryRow>(
+            r#"
+            WITH RECURSIVE revision_chain AS (
+                SELECT id AS root, id, sequence, 0 as depth
+                FROM entries
+                WHERE id = ANY($1)
+
+                UNION ALL
+
+                SELECT rc.root, e.id, e.sequence, rc.depth + 1
+                FROM entries e
+                JOIN revision_chain rc ON e.revision_of = rc.id
+                WHERE rc.depth < $2  -- Stops cycles too
+            ),
+            chain_stats AS (
+                SELECT root, COUNT(*) - 1 AS revision_count, MAX(sequence) AS latest_sequence
+                FROM revision_chain
+                GROUP BY root
+            )
+            SELECT e.id, e.topic, e.author_id, e.created, e.sequence,
+                   COALESCE((e.integration_cost->>'catalog_shift')::float8, 0) AS catalog_shift,
+                   e.cost_computed, cs.revision_count, cs.latest_sequence, e.revision_of
+            FROM entries e
+            JOIN chain_stats cs ON cs.root = e.id
+            "#,
+        )
+        .bind(ids)
+        .bind(self.max_revision_depth as i32)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Get the newest revision in an entry's revision chain.
+    ///
+    /// Follows `revision_of` links forward from the entry and returns the
+    /// most recent descendant by sequence within
+    /// [`max_revision_depth`](Self::max_revision_depth), or the entry itself
+    /// if it has never been revised. Used for optimistic concurrency on
+    /// revisions.
+    pub async fn latest_revision_of(&self, entry_id: Uuid) -> StoreResult<Uuid> {
+        Self::latest_revision_in(&self.pool, entry_id, self.max_revision_depth).await
+    }
+
+    /// [`latest_revision_of`](Self::latest_revision_of) on a given
+    /// connection or transaction.
+    async fn latest_revision_in<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        entry_id: Uuid,
+        max_depth: u32,
+    ) -> StoreResult<Uuid> {
+        let latest: Option<(Uuid,)> = sqlx::query_as(
+            r#"
+            WITH RECURSIVE revision_chain AS (
+                SELECT id, sequence, 0 as depth
+                FROM entries
+                WHERE id = $1
+
+                UNION ALL
+
+                SELECT e.id, e.sequence, rc.depth + 1
+                FROM entries e
+                JOIN revision_chain rc ON e.revision_of = rc.id
+                WHERE rc.depth < $2  -- Stops cycles too
+            )
+            SELECT id FROM revision_chain
+            ORDER BY sequence DESC, depth DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(entry_id)
+        .bind(max_depth as i32)
+        .fetch_optional(executor)
+        .await?;
+
+        latest
+            .map(|(id,)| id)
+            .ok_or(StoreError::EntryNotFound(entry_id))
+    }
+
+    /// Recent entries across every notebook the author can read, newest first.
+    ///
+    /// A notebook is readable if the author owns it or holds a read grant.
+    /// When `since` is given, only entries created after it are returned.
+    pub async fn recent_activity(
+        &self,
+        author_id: &[u8; 32],
+        limit: i64,
+        since: Option<DateTime<Utc>>,
+    ) -> StoreResult<Vec<ActivityRow>> {
+        let rows = sqlx::query_as::<_, ActivityRow>(
+            r#"
+            SELECT e.id, e.notebook_id, e.content, e.compression, e.encryption,
+                   e.content_type, e.topic,
+                   e.author_id, e.signature, e.revision_of, e."references",
+                   e.sequence, e.created, e.integration_cost, e.cost_computed, e.pinned,
+                   n.name AS notebook_name
+            FROM entries e
+            JOIN notebooks n ON n.id = e.notebook_id
+            WHERE (
+                n.owner_id = $1
+                OR EXISTS (
+                    SELECT 1 FROM notebook_access a
+                    WHERE a.notebook_id = n.id AND a.author_id = $1 AND a.read = true
+                )
+            )
+            AND ($2::timestamptz IS NULL OR e.created > $2)
+            ORDER BY e.created DESC, e.id
+            LIMIT $3
+            "#,
+        )
+        .bind(author_id.as_slice())
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut activity = Vec::with_capacity(rows.len());
+        for row in rows {
+            activity.push(ActivityRow {
+                entry: self.open_row(row.entry).await?,
+                notebook_name: row.notebook_name,
+            });
+        }
+        Ok(activity)
+    }
+
+    /// Get activity context for computing causal position.
+    pub async fn get_activity_context(
+        &self,
+        notebook_id: Uuid,
+        author_id: &[u8; 32],
+    ) -> StoreResult<(u32, u32)> {
+        // Get total entries in notebook
+        let total: (i64,) =
+            sqlx::query_as(r#"SELECT COUNT(*) FROM entries WHERE notebook_id = $1"#)
+                .bind(notebook_id)
+                .fetch_one(&self.pool)
+                .await?;
+
+        // Get entries since last by this author
+        let last_by_author: (Option<i64>,) = sqlx::query_as(
+            r#"
+            SELECT MAX(sequence) FROM entries
+            WHERE notebook_id = $1 AND author_id = $2
+            "#,
+        )
+        .bind(notebook_id)
+        .bind(author_id.as_slice())
+        .fetch_one(&self.pool)
+        .await?;
+
+        let entries_since = match last_by_author.0 {
+            Some(last_seq) => {
+                let count: (i64,) = sqlx::query_as(
+                    r#"
+                    SELECT COUNT(*) FROM entries
+                    WHERE notebook_id = $1 AND sequence > $2
+                    "#,
+                )
+                .bind(notebook_id)
+                .bind(last_seq)
+                .fetch_one(&self.pool)
+                .await?;
+                count.0 as u32
+            }
+            None => total.0 as u32, // Author has no entries yet
+        };
+
+        Ok((entries_since, total.0 as u32))
+    }
+
+    // ==================== Encryption Operations ====================
+
+    /// Get the unwrapped data key of an encrypted notebook.
+    ///
+    /// Keys are unwrapped once and then cached for the life of the store.
+    async fn data_key(&self, notebook_id: Uuid) -> StoreResult<DataKey> {
+        if let Some(key) = self
+            .data_keys
+            .read()
+            .expect("data key cache poisoned")
+            .get(&notebook_id)
+        {
+            return Ok(key.clone());
+        }
+
+        let master_key = self.master_key.as_ref().ok_or_else(|| {
+            StoreError::EncryptionError(format!(
+                "notebook {} is encrypted but {} is not set",
+                notebook_id, MASTER_KEY_ENV
+            ))
+        })?;
+
+        let (wrapped,): (Option<Vec<u8>>,) =
+            sqlx::query_as(r#"SELECT data_key FROM notebooks WHERE id = $1"#)
+                .bind(notebook_id)
+                .fetch_optional(&self.pool)
+                .await?
+                .ok_or(StoreError::NotebookNotFound(notebook_id))?;
+        let wrapped = wrapped.ok_or_else(|| {
+            StoreError::EncryptionError(format!("notebook {} has no data key", notebook_id))
+        })?;
+
+        let key = master_key
+            .unwrap(notebook_id, &wrapped)
+            .map_err(StoreError::EncryptionError)?;
+        self.data_keys
+            .write()
+            .expect("data key cache poisoned")
+            .insert(notebook_id, key.clone());
+        Ok(key)
+    }
+
+    /// Decrypt a row's sealed content, if any.
+    ///
+    /// Every `EntryRow` handed out by the store passes through here, so
+    /// callers never see ciphertext.
+    pub(crate) async fn open_row(&self, mut row: EntryRow) -> StoreResult<EntryRow> {
+        if let Some(sealed) = row.sealed.take() {
+            let opened = self
+                .data_key(row.notebook_id)
+                .await?
+                .open(row.id, &sealed.ciphertext, &sealed.encryption)
+                .map_err(StoreError::EncryptionError)?;
+            row.content = decompress(opened, sealed.compression.as_deref())
+                .map_err(StoreError::EncryptionError)?;
+        }
+        Ok(row)
+    }
+
+    /// Decrypt the sealed content of each row.
+    pub(crate) async fn open_rows(&self, rows: Vec<EntryRow>) -> StoreResult<Vec<EntryRow>> {
+        let mut opened = Vec::with_capacity(rows.len());
+        for row in rows {
+            opened.push(self.open_row(row).await?);
+        }
+        Ok(opened)
+    }
+
+    // ==================== Entropy Operations ====================
+
+    /// Get the recent entropy for a notebook (rolling sum of catalog_shift from last 10 entries).
+    pub async fn get_recent_entropy(&self, notebook_id: Uuid) -> StoreResult<f64> {
+        let result: (Option<f64>,) = sqlx::query_as(
+            r#"
+            SELECT SUM((integration_cost->>'catalog_shift')::FLOAT8)
+            FROM (
+                SELECT integration_cost
+                FROM entries
+                WHERE notebook_id = $1
+                ORDER BY sequence DESC
+                LIMIT 10
+            ) AS recent_entries
+            "#,
+        )
+        .bind(notebook_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(result.0.unwrap_or(0.0))
+    }
+
+    // ==================== Graph Operations ====================
+
+    /// Add a reference edge from `entry_id` to each of `ref_ids` in a single
+    /// round trip.
+    ///
+    /// Produces the same edges as one `add_reference_edge` call per
+    /// reference. Requires Apache AGE.
+    pub async fn add_reference_edges_batch(
+        &self,
+        entry_id: Uuid,
+        ref_ids: &[Uuid],
+    ) -> StoreResult<()> {
+        if ref_ids.is_empty() {
+            return Ok(());
+        }
+
+        sqlx::query("SELECT add_reference_edges($1, $2)")
+            .bind(entry_id)
+            .bind(ref_ids)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| StoreError::GraphError(format!("Failed to add reference edges: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Add an entry vertex and edges to the graph.
+    async fn add_entry_to_graph(&self, entry: &EntryRow) -> StoreResult<()> {
+        // Convert author_id to hex string for graph storage
+        let author_hex: String = entry
+            .author_id
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+
+        // Add vertex
+        sqlx::query("SELECT add_entry_vertex($1, $2, $3, $4, $5)")
+            .bind(entry.id)
+            .bind(entry.notebook_id)
+            .bind(&entry.topic)
+            .bind(&author_hex)
+            .bind(entry.sequence)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| StoreError::GraphError(format!("Failed to add vertex: {}", e)))?;
+
+        self.add_reference_edges_batch(entry.id, &entry.references)
+            .await?;
+
+        // Add revision edge if applicable
+        if let Some(revision_of) = entry.revision_of {
+            sqlx::query("SELECT add_revision_edge($1, $2)")
+                .bind(entry.id)
+                .bind(revision_of)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| {
+                    StoreError::GraphError(format!("Failed to add revision edge: {}", e))
+                })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Insert or update an access grant without logging it.
+async fn upsert_access(
+    conn: &mut PgConnection,
+    access: &NewNotebookAccess,
+) -> StoreResult<NotebookAccessRow> {
+    Ok(sqlx::query_as::<_, NotebookAccessRow>(
+        r#"
+        INSERT INTO notebook_access (notebook_id, author_id, read, write)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (notebook_id, author_id)
+        DO UPDATE SET read = $3, write = $4, granted = NOW()
+        RETURNING notebook_id, author_id, read, write, granted
+        "#,
+    )
+    .bind(access.notebook_id)
+    .bind(access.author_id.as_slice())
+    .bind(access.read)
+    .bind(access.write)
+    .fetch_one(conn)
+    .await?)
+}
+
+/// Append an event to a notebook's change log, returning its `seq`.
+///
+/// Must run inside the transaction that makes the change being recorded.
+/// The notebook row is locked first, so concurrent appends to one notebook
+/// serialize and `seq` stays gapless; the lock is released on commit.
+async fn append_event(
+    conn: &mut PgConnection,
+    notebook_id: Uuid,
+    event_type: &str,
+    payload: serde_json::Value,
+) -> StoreResult<i64> {
+    sqlx::query("SELECT 1 FROM notebooks WHERE id = $1 FOR UPDATE")
+        .bind(notebook_id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or(StoreError::NotebookNotFound(notebook_id))?;
+
+    let result: (i64,) = sqlx::query_as(
+        r#"
+        INSERT INTO notebook_events (notebook_id, seq, event_type, payload)
+        SELECT $1, COALESCE(MAX(seq), 0) + 1, $2, $3
+        FROM notebook_events
+        WHERE notebook_id = $1
+        RETURNING seq
+        "#,
+    )
+    .bind(notebook_id)
+    .bind(event_type)
+    .bind(payload)
+    .fetch_one(conn)
+    .await?;
+
+    Ok(result.0)
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_default() {
+        let config = StoreConfig::default();
+        assert_eq!(config.max_connections, 10);
+        assert_eq!(config.min_connections, 1);
+        assert!(config.run_migrations);
+        assert!(config.migrate_on_start);
+        assert_eq!(config.statement_timeout_ms, DEFAULT_STATEMENT_TIMEOUT_MS);
+    }
+
+    #[test]
+    fn test_statement_timeout_is_a_connection_option() {
+        let config = StoreConfig {
+            database_url: "postgres://notebook@localhost/notebook".to_string(),
+            statement_timeout_ms: 250,
+            ..StoreConfig::default()
+        };
+        let options = config.connect_options().unwrap();
+        assert_eq!(options.get_options(), Some("-c statement_timeout=250ms"));
+
+        let disabled = StoreConfig {
+            statement_timeout_ms: 0,
+            ..config
+        };
+        assert_eq!(disabled.connect_options().unwrap().get_options(), None);
+    }
+}
+
+/// Integration tests that require a running PostgreSQL database.
+/// Run with: cargo test --features integration-tests
+#[cfg(all(test, feature = "integration-tests"))]
+mod integration_tests {
+    use super::*;
+    use crate::queries::{TopicCountsQuery, TopicQuery};
+    use notebook_core::NotebookId;
+
+    async fn setup_store() -> Store {
+        let config = StoreConfig::from_env().expect("Invalid store config");
+        Store::connect(config)
+            .await
+            .expect("Failed to connect to database")
+    }
+
+    async fn create_test_notebook(store: &Store, name: &str) -> NotebookRow {
+        let owner_id: [u8; 32] = rand::random();
+        let public_key: [u8; 32] = rand::random();
+        store
+            .insert_author(&NewAuthor::new(owner_id, public_key))
+            .await
+            .expect("Failed to create test author");
+        store
+            .insert_notebook(&NewNotebook::new(name.to_string(), owner_id))
+            .await
+            .expect("Failed to create test notebook")
+    }
+
+    #[tokio::test]
+    async fn test_slow_statement_hits_statement_timeout() {
+        let config = StoreConfig {
+            run_migrations: false,
+            statement_timeout_ms: 100,
+            ..StoreConfig::from_env().expect("Invalid store config")
+        };
+        let store = Store::connect(config)
+            .await
+            .expect("Failed to connect to database");
+
+        let err: StoreError = sqlx::query("SELECT pg_sleep(2)")
+            .execute(store.pool())
+            .await
+            .unwrap_err()
+            .into();
+        assert!(matches!(err, StoreError::Timeout(_)), "{:?}", err);
+
+        // The connection stays usable for statements within the limit
+        assert!(store.schema_version().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_migrated_database_reports_current_schema_version() {
+        let store = setup_store().await;
+        let version = store.schema_version().await.unwrap();
+        if store.age_available() {
+            assert_eq!(version, schema::SCHEMA_VERSION);
+        } else {
+            // The graph migration is optional and skipped without AGE
+            assert_eq!(version, 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pending_migrations_reports_without_applying() {
+        let store = setup_store().await;
+        let migration = schema::MIGRATIONS.last().unwrap();
+        let applied = || async {
+            sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS (SELECT 1 FROM schema_migrations WHERE name = $1)",
+            )
+            .bind(migration.name)
+            .fetch_one(store.pool())
+            .await
+            .unwrap()
+        };
+        assert!(
+            !store
+                .pending_migrations()
+                .await
+                .unwrap()
+                .contains(&migration)
+        );
+
+        // Forget the migration, as if it shipped after the last deploy
+        sqlx::query("DELETE FROM schema_migrations WHERE name = $1")
+            .bind(migration.name)
+            .execute(store.pool())
+            .await
+            .unwrap();
+        assert!(
+            store
+                .pending_migrations()
+                .await
+                .unwrap()
+                .contains(&migration)
+        );
+        assert!(!applied().await);
+
+        // Verifying instead of migrating refuses to start
+        let err = ensure_migrated(store.pool()).await.unwrap_err();
+        assert!(matches!(err, StoreError::MigrationError(ref m) if m.contains(migration.name)));
+
+        schema::run_migrations(store.pool()).await.unwrap();
+        assert!(applied().await);
+        assert!(ensure_migrated(store.pool()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rename_notebook_updates_get_notebook() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Before").await;
+
+        let renamed = store
+            .rename_notebook(notebook.id, "After")
+            .await
+            .expect("Failed to rename notebook");
+        assert_eq!(renamed.name, "After");
+
+        let fetched = store.get_notebook(notebook.id).await.unwrap();
+        assert_eq!(fetched.name, "After");
+        assert_eq!(fetched.created, notebook.created);
+    }
+
+    #[tokio::test]
+    async fn test_entries_in_notebook_excludes_other_notebooks() {
+        let store = setup_store().await;
+        let home = create_test_notebook(&store, "Home").await;
+        let other = create_test_notebook(&store, "Other").await;
+
+        let local = NewEntry::builder(home.id, home.owner_id.clone().try_into().unwrap())
+            .content_str("local")
+            .build();
+        let foreign = NewEntry::builder(other.id, other.owner_id.clone().try_into().unwrap())
+            .content_str("foreign")
+            .build();
+        store.insert_entry(&local).await.unwrap();
+        store.insert_entry(&foreign).await.unwrap();
+
+        let found = store
+            .entries_in_notebook(home.id, &[local.id, foreign.id])
+            .await
+            .unwrap();
+        assert_eq!(found, vec![local.id]);
+    }
+
+    #[tokio::test]
+    async fn test_reference_deleted_during_insert_is_rejected() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Vanishing reference").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let target = NewEntry::builder(notebook.id, author)
+            .content_str("about to vanish")
+            .build();
+        store.insert_entry(&target).await.unwrap();
+
+        // A concurrent transaction deletes the reference but has not committed
+        let mut deleting = store.pool().begin().await.unwrap();
+        sqlx::query("DELETE FROM entries WHERE id = $1")
+            .bind(target.id)
+            .execute(&mut *deleting)
+            .await
+            .unwrap();
+
+        // The insert waits on the reference's row lock instead of seeing the
+        // still-visible row and committing a dangling reference
+        let referencing = NewEntry::builder(notebook.id, author)
+            .content_str("points at it")
+            .references(vec![target.id])
+            .build();
+        let inserting = {
+            let store = store.clone();
+            let referencing = referencing.clone();
+            tokio::spawn(async move { store.insert_entry(&referencing).await })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        assert!(!inserting.is_finished());
+
+        deleting.commit().await.unwrap();
+        let result = inserting.await.unwrap();
+        assert!(matches!(result, Err(StoreError::InvalidReference(id)) if id == target.id));
+        assert!(!store.entry_exists(referencing.id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_insert_with_taken_id_is_duplicate() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Client ids").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let first = NewEntry::builder(notebook.id, author)
+            .content_str("first attempt")
+            .build();
+        let stored = store.insert_entry(&first).await.unwrap();
+
+        let retry = NewEntry::builder(notebook.id, author)
+            .id(first.id)
+            .content_str("retried attempt")
+            .build();
+        let result = store.insert_entry(&retry).await;
+        assert!(matches!(result, Err(StoreError::DuplicateEntry(id)) if id == first.id));
+
+        // The failed insert rolled back without using up a sequence number
+        let next = NewEntry::builder(notebook.id, author)
+            .content_str("second entry")
+            .build();
+        assert_eq!(
+            store.insert_entry(&next).await.unwrap().sequence,
+            stored.sequence + 1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_existing_entry_ids_spans_notebooks_and_skips_missing() {
+        let store = setup_store().await;
+        let home = create_test_notebook(&store, "Home").await;
+        let other = create_test_notebook(&store, "Other").await;
+
+        let local = NewEntry::builder(home.id, home.owner_id.clone().try_into().unwrap())
+            .content_str("local")
+            .build();
+        let foreign = NewEntry::builder(other.id, other.owner_id.clone().try_into().unwrap())
+            .content_str("foreign")
+            .build();
+        store.insert_entry(&local).await.unwrap();
+        store.insert_entry(&foreign).await.unwrap();
+
+        let mut found = store
+            .existing_entry_ids(&[local.id, foreign.id, Uuid::new_v4()])
+            .await
+            .unwrap();
+        found.sort();
+        let mut expected = vec![local.id, foreign.id];
+        expected.sort();
+        assert_eq!(found, expected);
+    }
+
+    #[tokio::test]
+    async fn test_recently_active_notebooks_orders_by_latest_entry() {
+        let store = setup_store().await;
+        let quiet = create_test_notebook(&store, "Quiet").await;
+        let busy = create_test_notebook(&store, "Busy").await;
+
+        // Writing to the older notebook makes it the most recently active
+        let entry = NewEntry::builder(quiet.id, quiet.owner_id.clone().try_into().unwrap())
+            .content_str("fresh activity")
+            .build();
+        store.insert_entry(&entry).await.unwrap();
+
+        // Other tests write concurrently; compare relative positions only
+        let recent = store.recently_active_notebooks(10_000).await.unwrap();
+        let position = |id: Uuid| recent.iter().position(|r| *r == id).unwrap();
+        assert!(position(quiet.id) < position(busy.id));
+
+        assert_eq!(store.recently_active_notebooks(1).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_all_notebooks_includes_every_owner() {
+        let store = setup_store().await;
+        let first = create_test_notebook(&store, "First").await;
+        let second = create_test_notebook(&store, "Second").await;
+
+        // Each test notebook has its own owner
+        let all = store.list_all_notebooks().await.unwrap();
+        assert!(all.iter().any(|n| n.id == first.id));
+        assert!(all.iter().any(|n| n.id == second.id));
+    }
+
+    #[tokio::test]
+    async fn test_referenced_template_returns_reference_closure() {
+        use crate::graph::{GraphQueryHit, GraphQueryTemplate};
+
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Graph templates").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        // root -> middle -> leaf
+        let leaf = NewEntry::builder(notebook.id, author)
+            .content_str("leaf")
+            .build();
+        let middle = NewEntry::builder(notebook.id, author)
+            .content_str("middle")
+            .references(vec![leaf.id])
+            .build();
+        let root = NewEntry::builder(notebook.id, author)
+            .content_str("root")
+            .references(vec![middle.id])
+            .build();
+        for entry in [&leaf, &middle, &root] {
+            store.insert_entry(entry).await.unwrap();
+        }
+
+        let template = GraphQueryTemplate::Referenced {
+            entry_id: root.id,
+            max_depth: 5,
+        };
+        let result = store.graph().run_template(notebook.id, &template).await;
+
+        if !store.age_available() {
+            assert!(matches!(result, Err(StoreError::GraphUnavailable(_))));
+            return;
+        }
+
+        let hits = result.unwrap();
+        assert_eq!(
+            hits,
+            vec![
+                GraphQueryHit {
+                    entry_id: middle.id,
+                    depth: 1
+                },
+                GraphQueryHit {
+                    entry_id: leaf.id,
+                    depth: 2
+                },
+            ]
+        );
+
+        // Same closure as the untemplated traversal
+        let closure = store
+            .graph()
+            .find_reference_closure(root.id, 5)
+            .await
+            .unwrap();
+        assert_eq!(closure.len(), hits.len());
+    }
+
+    #[tokio::test]
+    async fn test_ancestors_and_descendants_follow_reference_direction() {
+        use crate::graph::GraphQueryHit;
+
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Reference lineage").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        // oldest <- older <- middle <- newer <- newest
+        let mut chain: Vec<NewEntry> = Vec::new();
+        for content in ["oldest", "older", "middle", "newer", "newest"] {
+            let references = chain.last().map(|e| vec![e.id]).unwrap_or_default();
+            let entry = NewEntry::builder(notebook.id, author)
+                .content_str(content)
+                .references(references)
+                .build();
+            store.insert_entry(&entry).await.unwrap();
+            chain.push(entry);
+        }
+        let hit = |i: usize, depth| GraphQueryHit {
+            entry_id: chain[i].id,
+            depth,
+        };
+        let middle = chain[2].id;
+
+        let ancestors = store.ancestors(middle, 10).await.unwrap();
+        assert_eq!(ancestors, vec![hit(1, 1), hit(0, 2)]);
+        let descendants = store.descendants(middle, 10).await.unwrap();
+        assert_eq!(descendants, vec![hit(3, 1), hit(4, 2)]);
+        assert!(
+            ancestors
+                .iter()
+                .all(|a| descendants.iter().all(|d| d.entry_id != a.entry_id))
+        );
+
+        // The depth bound applies in both directions
+        assert_eq!(store.ancestors(middle, 1).await.unwrap(), vec![hit(1, 1)]);
+        assert_eq!(store.descendants(middle, 1).await.unwrap(), vec![hit(3, 1)]);
+
+        // The ends of the chain have nothing beyond them
+        assert!(store.ancestors(chain[0].id, 10).await.unwrap().is_empty());
+        assert!(store.descendants(chain[4].id, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_export_matches_entries_and_reference_edges() {
+        use crate::graph::GraphEdgeKind;
+
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Graph export").await;
+        let other = create_test_notebook(&store, "Graph export elsewhere").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let foreign = NewEntry::builder(other.id, other.owner_id.clone().try_into().unwrap())
+            .content_str("elsewhere")
+            .build();
+        store.insert_entry(&foreign).await.unwrap();
+
+        // first <- second, first <- third (which also cites elsewhere),
+        // and a revision of second
+        let first = NewEntry::builder(notebook.id, author)
+            .content_str("first")
+            .topic(Some("export".to_string()))
+            .build();
+        let second = NewEntry::builder(notebook.id, author)
+            .content_str("second")
+            .references(vec![first.id])
+            .build();
+        let third = NewEntry::builder(notebook.id, author)
+            .content_str("third")
+            .references(vec![first.id, foreign.id])
+            .build();
+        let revision = NewEntry::builder(notebook.id, author)
+            .content_str("second, revised")
+            .revision_of(Some(second.id))
+            .build();
+        for entry in [&first, &second, &third, &revision] {
+            store.insert_entry(entry).await.unwrap();
+        }
+
+        let graph = store
+            .graph()
+            .export_notebook(notebook.id, 0, 100)
+            .await
+            .unwrap();
+        assert!(!graph.truncated);
+        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(graph.nodes[0].entry_id, first.id);
+        assert_eq!(graph.nodes[0].topic.as_deref(), Some("export"));
+        assert_eq!(graph.nodes[0].author_id, author.to_vec());
+        let count = |kind| graph.edges.iter().filter(|e| e.kind == kind).count();
+        assert_eq!(count(GraphEdgeKind::References), 2);
+        assert_eq!(count(GraphEdgeKind::RevisionOf), 1);
+
+        // Past the first entry, edges into it fall outside the export
+        let later = store
+            .graph()
+            .export_notebook(notebook.id, graph.nodes[0].sequence, 2)
+            .await
+            .unwrap();
+        assert!(later.truncated);
+        assert_eq!(later.nodes.len(), 2);
+        assert!(later.edges.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_batched_reference_edges_match_per_edge_path() {
+        let store = setup_store().await;
+        if !store.age_available() {
+            return;
+        }
+        let notebook = create_test_notebook(&store, "Many references").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let mut targets = Vec::new();
+        for i in 0..12 {
+            let target = NewEntry::builder(notebook.id, author)
+                .content_str(&format!("target {}", i))
+                .build();
+            store.insert_entry(&target).await.unwrap();
+            targets.push(target.id);
+        }
+
+        // insert_entry adds the edges in one batch
+        let batched = NewEntry::builder(notebook.id, author)
+            .content_str("cites everything")
+            .references(targets.clone())
+            .build();
+        store.insert_entry(&batched).await.unwrap();
+
+        // The same edges added one call at a time
+        let per_edge = NewEntry::builder(notebook.id, author)
+            .content_str("cites everything, slowly")
+            .build();
+        store.insert_entry(&per_edge).await.unwrap();
+        for target in &targets {
+            sqlx::query("SELECT add_reference_edge($1, $2)")
+                .bind(per_edge.id)
+                .bind(target)
+                .execute(store.pool())
+                .await
+                .unwrap();
+        }
+
+        let edges = |id: Uuid| {
+            let store = store.clone();
+            async move {
+                let mut ids: Vec<Uuid> = store
+                    .graph()
+                    .find_reference_closure(id, 1)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|(id, _)| id)
+                    .collect();
+                ids.sort();
+                ids
+            }
+        };
+        let mut expected = targets.clone();
+        expected.sort();
+        assert_eq!(edges(batched.id).await, expected);
+        assert_eq!(edges(per_edge.id).await, edges(batched.id).await);
+    }
+
+    #[tokio::test]
+    async fn test_set_entry_pinned_round_trip() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Pins").await;
+        let entry = NewEntry::builder(notebook.id, notebook.owner_id.clone().try_into().unwrap())
+            .content_str("keep me visible")
+            .build();
+        let row = store.insert_entry(&entry).await.unwrap();
+        assert!(!row.pinned);
+
+        let before = store.latest_event_seq(notebook.id).await.unwrap();
+        store
+            .set_entry_pinned(notebook.id, entry.id, true)
+            .await
+            .unwrap();
+        assert!(store.get_entry(entry.id).await.unwrap().pinned);
+        assert_eq!(
+            logged_types(&store, notebook.id, before).await,
+            vec![event_type::ENTRY_PINNED]
+        );
+
+        store
+            .set_entry_pinned(notebook.id, entry.id, false)
+            .await
+            .unwrap();
+        assert!(!store.get_entry(entry.id).await.unwrap().pinned);
+        assert_eq!(
+            logged_types(&store, notebook.id, before + 1).await,
+            vec![event_type::ENTRY_UNPINNED]
+        );
+        let events = store.events_after(notebook.id, before, 100).await.unwrap();
+        assert_eq!(events[0].payload["entry_id"], serde_json::json!(entry.id));
+
+        let missing = store
+            .set_entry_pinned(notebook.id, Uuid::new_v4(), true)
+            .await;
+        assert!(matches!(missing, Err(StoreError::EntryNotFound(_))));
+
+        // Pinning through another notebook does not reach the entry
+        let other = create_test_notebook(&store, "Other pins").await;
+        let foreign = store.set_entry_pinned(other.id, entry.id, true).await;
+        assert!(matches!(foreign, Err(StoreError::EntryNotFound(_))));
+
+        store.set_notebook_locked(notebook.id, true).await.unwrap();
+        let locked_seq = store.latest_event_seq(notebook.id).await.unwrap();
+        let locked = store.set_entry_pinned(notebook.id, entry.id, true).await;
+        assert!(matches!(locked, Err(StoreError::NotebookLocked(_))));
+        assert!(!store.get_entry(entry.id).await.unwrap().pinned);
+        assert_eq!(
+            store.latest_event_seq(notebook.id).await.unwrap(),
+            locked_seq
+        );
+    }
+
+    #[tokio::test]
+    async fn test_computed_integration_costs_skip_fallbacks() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Cost distribution").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+        let cost = |catalog_shift: f64, orphan: bool| IntegrationCostJson {
+            catalog_shift,
+            orphan,
+            ..IntegrationCostJson::default()
+        };
+        for (content, cost, computed) in [
+            ("first", cost(0.9, true), true),
+            ("fallback", IntegrationCostJson::default(), false),
+            ("second", cost(0.1, false), true),
+        ] {
+            let entry = NewEntry::builder(notebook.id, author)
+                .content_str(content)
+                .integration_cost(cost)
+                .cost_computed(computed)
+                .build();
+            store.insert_entry(&entry).await.unwrap();
+        }
+
+        let costs = store.computed_integration_costs(notebook.id).await.unwrap();
+        let shifts: Vec<f64> = costs.iter().map(|c| c.catalog_shift).collect();
+        assert_eq!(shifts, vec![0.9, 0.1]);
+        assert!(costs[0].orphan);
+        assert!(
+            store
+                .computed_integration_costs(Uuid::new_v4())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fallback_cost_reads_back_as_not_computed() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Fallback costs").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+        let computed = NewEntry::builder(notebook.id, author)
+            .content_str("cost computed in time")
+            .build();
+        // Written after the cost computation failed
+        let fallback = NewEntry::builder(notebook.id, author)
+            .content_str("cost fell back to zero")
+            .cost_computed(false)
+            .build();
+        assert!(store.insert_entry(&computed).await.unwrap().cost_computed);
+        assert!(!store.insert_entry(&fallback).await.unwrap().cost_computed);
+
+        assert!(!store.get_entry(fallback.id).await.unwrap().cost_computed);
+        let ids = [computed.id, fallback.id];
+        assert_eq!(
+            store.fallback_cost_entry_ids(&ids).await.unwrap(),
+            vec![fallback.id]
+        );
+        let summaries = store.entry_summaries(&ids).await.unwrap();
+        let summary = summaries.iter().find(|s| s.id == fallback.id).unwrap();
+        assert!(!summary.cost_computed);
+
+        // A backfilled cost counts as computed
+        store
+            .update_integration_cost(fallback.id, &IntegrationCostJson::default())
+            .await
+            .unwrap();
+        assert!(store.get_entry(fallback.id).await.unwrap().cost_computed);
+        assert!(
+            store
+                .fallback_cost_entry_ids(&ids)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rename_topic_updates_every_matching_entry() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Topics").await;
+        let other = create_test_notebook(&store, "Other topics").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+        let other_author: [u8; 32] = other.owner_id.clone().try_into().unwrap();
+
+        let topical = |nb: Uuid, author: [u8; 32], topic: &str| {
+            NewEntry::builder(nb, author)
+                .content_str("content")
+                .topic(Some(topic.to_string()))
+                .build()
+        };
+        let renamed = [
+            topical(notebook.id, author, "ml"),
+            topical(notebook.id, author, "ml"),
+        ];
+        let untouched = topical(notebook.id, author, "mlops");
+        let foreign = topical(other.id, other_author, "ml");
+        for entry in renamed.iter().chain([&untouched, &foreign]) {
+            store.insert_entry(entry).await.unwrap();
+        }
+
+        let updated = store
+            .rename_topic(notebook.id, "ml", "machine-learning")
+            .await
+            .unwrap();
+        assert_eq!(updated, 2);
+
+        for entry in &renamed {
+            let row = store.get_entry(entry.id).await.unwrap();
+            assert_eq!(row.topic.as_deref(), Some("machine-learning"));
+        }
+        let row = store.get_entry(untouched.id).await.unwrap();
+        assert_eq!(row.topic.as_deref(), Some("mlops"));
+        let row = store.get_entry(foreign.id).await.unwrap();
+        assert_eq!(row.topic.as_deref(), Some("ml"));
+
+        let events = store.events_after(notebook.id, 0, 100).await.unwrap();
+        let last = events.last().unwrap();
+        assert_eq!(last.event_type, event_type::TOPIC_RENAMED);
+        assert_eq!(last.payload["entries"], 2);
+
+        // Nothing left to rename: no change, no event
+        let updated = store
+            .rename_topic(notebook.id, "ml", "machine-learning")
+            .await
+            .unwrap();
+        assert_eq!(updated, 0);
+        let after = store.events_after(notebook.id, 0, 100).await.unwrap();
+        assert_eq!(after.len(), events.len());
+    }
+
+    #[tokio::test]
+    async fn test_writing_an_entry_advances_last_activity() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Activity").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+        assert_eq!(
+            store.notebook_last_activity(notebook.id).await.unwrap(),
+            None
+        );
+
+        let write = || async {
+            let entry = NewEntry::builder(notebook.id, author)
+                .content_str("content")
+                .build();
+            store.insert_entry(&entry).await.unwrap()
+        };
+        let first = write().await;
+        assert_eq!(
+            store.notebook_last_activity(notebook.id).await.unwrap(),
+            Some(first.created)
+        );
+
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let second = write().await;
+        let last_activity = store.notebook_last_activity(notebook.id).await.unwrap();
+        assert_eq!(last_activity, Some(second.created));
+        assert!(last_activity > Some(first.created));
+    }
+
+    #[tokio::test]
+    async fn test_topic_query_pages_through_matching_entries() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Topic pages").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let topical = |topic: &str| {
+            NewEntry::builder(notebook.id, author)
+                .content_str("content")
+                .topic(Some(topic.to_string()))
+                .build()
+        };
+        let mut tagged = Vec::new();
+        for topic in ["ml", "baking", "ml", "mlops", "ml"] {
+            let entry = topical(topic);
+            store.insert_entry(&entry).await.unwrap();
+            if topic == "ml" {
+                tagged.push(entry.id);
+            }
+        }
+
+        let nb_id = NotebookId::from_uuid(notebook.id);
+        let all = TopicQuery::new(nb_id, "ml").execute(&store).await.unwrap();
+        let ids: Vec<Uuid> = all.iter().map(|row| row.id).collect();
+        assert_eq!(ids, tagged);
+
+        let first = TopicQuery::new(nb_id, "ml")
+            .limit(2)
+            .execute(&store)
+            .await
+            .unwrap();
+        assert_eq!(first.len(), 2);
+        let rest = TopicQuery::new(nb_id, "ml")
+            .after(first[1].sequence)
+            .limit(2)
+            .execute(&store)
+            .await
+            .unwrap();
+        let paged: Vec<Uuid> = first.iter().chain(&rest).map(|row| row.id).collect();
+        assert_eq!(paged, tagged);
+    }
+
+    #[tokio::test]
+    async fn test_topic_counts_match_seeded_distribution() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Topic counts").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let mut last_sequence = HashMap::new();
+        for topic in [
+            Some("ml"),
+            None,
+            Some("baking"),
+            Some("ml"),
+            Some("ml"),
+            None,
+        ] {
+            let entry = NewEntry::builder(notebook.id, author)
+                .content_str("content")
+                .topic(topic.map(str::to_string))
+                .build();
+            let row = store.insert_entry(&entry).await.unwrap();
+            last_sequence.insert(topic.map(str::to_string), row.sequence);
+        }
+
+        let counts = TopicCountsQuery::new(NotebookId::from_uuid(notebook.id))
+            .execute(&store)
+            .await
+            .unwrap();
+        let expected = [(Some("ml"), 3), (None, 2), (Some("baking"), 1)];
+        assert_eq!(counts.len(), expected.len());
+        for (count, (topic, entries)) in counts.iter().zip(expected) {
+            let topic = topic.map(str::to_string);
+            assert_eq!(count.topic, topic);
+            assert_eq!(count.entries, entries);
+            assert_eq!(count.latest_sequence, last_sequence[&topic]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notebook_alert_round_trip() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Alerting").await;
+        assert!(
+            store
+                .get_notebook_alert(notebook.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let alert = store
+            .upsert_notebook_alert(notebook.id, 0.5, "https://example.com/hook")
+            .await
+            .unwrap();
+        assert_eq!(alert.entropy_threshold, 0.5);
+        assert!(!alert.triggered);
+        assert_eq!(
+            logged_types(&store, notebook.id, 0).await,
+            vec![event_type::ALERT_SET]
+        );
+
+        // Only the first claim of a crossing wins
+        assert!(store.set_alert_triggered(notebook.id, true).await.unwrap());
+        assert!(!store.set_alert_triggered(notebook.id, true).await.unwrap());
+        let fired = store
+            .get_notebook_alert(notebook.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(fired.triggered);
+        assert!(fired.last_fired.is_some());
+        // Firing is not a configuration change
+        assert_eq!(store.latest_event_seq(notebook.id).await.unwrap(), 1);
+
+        // Replacing the alert re-arms it
+        let replaced = store
+            .upsert_notebook_alert(notebook.id, 0.8, "https://example.com/other")
+            .await
+            .unwrap();
+        assert!(!replaced.triggered);
+        assert_eq!(replaced.webhook_url, "https://example.com/other");
+        assert_eq!(
+            logged_types(&store, notebook.id, 1).await,
+            vec![event_type::ALERT_SET]
+        );
+
+        assert!(store.delete_notebook_alert(notebook.id).await.unwrap());
+        assert_eq!(
+            logged_types(&store, notebook.id, 2).await,
+            vec![event_type::ALERT_REMOVED]
+        );
+
+        // Deleting again changes nothing, so nothing is logged
+        assert!(!store.delete_notebook_alert(notebook.id).await.unwrap());
+        assert_eq!(store.latest_event_seq(notebook.id).await.unwrap(), 3);
+
+        let events = store.events_after(notebook.id, 0, 100).await.unwrap();
+        assert_eq!(events[1].payload["entropy_threshold"], 0.8);
+        assert!(events[1].payload.get("webhook_url").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_alert_for_missing_notebook() {
+        let store = setup_store().await;
+        let missing = Uuid::new_v4();
+        assert!(matches!(
+            store
+                .upsert_notebook_alert(missing, 0.5, "https://example.com/hook")
+                .await,
+            Err(StoreError::NotebookNotFound(id)) if id == missing
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_content_policy_round_trip() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Text only").await;
+        assert!(
+            store
+                .get_content_policy(notebook.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let allowed = vec!["text/*".to_string()];
+        let policy = store
+            .upsert_content_policy(notebook.id, &allowed, &[])
+            .await
+            .unwrap();
+        assert_eq!(policy.allowed_content_types, allowed);
+        assert!(policy.denied_content_types.is_empty());
+
+        assert_eq!(
+            logged_types(&store, notebook.id, 0).await,
+            vec![event_type::CONTENT_POLICY_SET]
+        );
+
+        let denied = vec!["text/html".to_string()];
+        store
+            .upsert_content_policy(notebook.id, &allowed, &denied)
+            .await
+            .unwrap();
+        let fetched = store
+            .get_content_policy(notebook.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.denied_content_types, denied);
+        assert_eq!(
+            logged_types(&store, notebook.id, 1).await,
+            vec![event_type::CONTENT_POLICY_SET]
+        );
+
+        assert!(store.delete_content_policy(notebook.id).await.unwrap());
+        assert_eq!(
+            logged_types(&store, notebook.id, 2).await,
+            vec![event_type::CONTENT_POLICY_REMOVED]
+        );
+
+        // Deleting again changes nothing, so nothing is logged
+        assert!(!store.delete_content_policy(notebook.id).await.unwrap());
+        assert_eq!(store.latest_event_seq(notebook.id).await.unwrap(), 3);
+
+        let events = store.events_after(notebook.id, 0, 100).await.unwrap();
+        assert_eq!(
+            events[1].payload["denied_content_types"],
+            serde_json::json!(denied)
+        );
+
+        let missing = Uuid::new_v4();
+        assert!(matches!(
+            store.upsert_content_policy(missing, &allowed, &[]).await,
+            Err(StoreError::NotebookNotFound(id)) if id == missing
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_write_budget_round_trip() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Budgeted").await;
+        assert!(store.get_write_budget(notebook.id).await.unwrap().is_none());
+
+        let budget = store
+            .upsert_write_budget(notebook.id, 2.5, 60)
+            .await
+            .unwrap();
+        assert_eq!(budget.entropy_budget, 2.5);
+        assert_eq!(budget.window_secs, 60);
+        assert_eq!(
+            logged_types(&store, notebook.id, 0).await,
+            vec![event_type::WRITE_BUDGET_SET]
+        );
+
+        store
+            .upsert_write_budget(notebook.id, 1.0, 300)
             .await
-            .map_err(|e| StoreError::GraphError(format!("Failed to add vertex: {}", e)))?;
+            .unwrap();
+        let fetched = store.get_write_budget(notebook.id).await.unwrap().unwrap();
+        assert_eq!(fetched.entropy_budget, 1.0);
+        assert_eq!(fetched.window_secs, 300);
+        assert_eq!(
+            logged_types(&store, notebook.id, 1).await,
+            vec![event_type::WRITE_BUDGET_SET]
+        );
 
-        // Add reference edges
-        for ref_id in &entry.references {
-            sqlx::query("SELECT add_reference_edge($1, $2)")
-                .bind(entry.id)
-                .bind(ref_id)
-                .execute(&self.pool)
+        assert!(store.delete_write_budget(notebook.id).await.unwrap());
+        assert_eq!(
+            logged_types(&store, notebook.id, 2).await,
+            vec![event_type::WRITE_BUDGET_REMOVED]
+        );
+
+        // Deleting again changes nothing, so nothing is logged
+        assert!(!store.delete_write_budget(notebook.id).await.unwrap());
+        assert_eq!(store.latest_event_seq(notebook.id).await.unwrap(), 3);
+
+        let events = store.events_after(notebook.id, 0, 100).await.unwrap();
+        assert_eq!(events[1].payload["entropy_budget"], 1.0);
+        assert_eq!(events[1].payload["window_secs"], 300);
+
+        let missing = Uuid::new_v4();
+        assert!(matches!(
+            store.upsert_write_budget(missing, 1.0, 60).await,
+            Err(StoreError::NotebookNotFound(id)) if id == missing
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_notebook_labels_are_per_author() {
+        let store = setup_store().await;
+        let labeled = create_test_notebook(&store, "Labeled").await;
+        let other = create_test_notebook(&store, "Unlabeled").await;
+        let owner: [u8; 32] = labeled.owner_id.as_slice().try_into().unwrap();
+        let other_owner: [u8; 32] = other.owner_id.as_slice().try_into().unwrap();
+
+        assert!(
+            store
+                .add_notebook_label(labeled.id, &owner, "project-alpha")
                 .await
-                .map_err(|e| {
-                    StoreError::GraphError(format!("Failed to add reference edge: {}", e))
-                })?;
+                .unwrap()
+        );
+        assert!(
+            !store
+                .add_notebook_label(labeled.id, &owner, "project-alpha")
+                .await
+                .unwrap()
+        );
+        store
+            .add_notebook_label(labeled.id, &owner, "archived")
+            .await
+            .unwrap();
+        assert_eq!(
+            store.notebook_labels(labeled.id, &owner).await.unwrap(),
+            vec!["archived", "project-alpha"]
+        );
+
+        // Another author's labels are their own
+        store
+            .add_notebook_label(labeled.id, &other_owner, "shared")
+            .await
+            .unwrap();
+        let rows = store.author_notebook_labels(&owner).await.unwrap();
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|row| row.notebook_id == labeled.id));
+
+        assert!(
+            store
+                .remove_notebook_label(labeled.id, &owner, "archived")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !store
+                .remove_notebook_label(labeled.id, &owner, "archived")
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            store.notebook_labels(labeled.id, &owner).await.unwrap(),
+            vec!["project-alpha"]
+        );
+
+        let missing = Uuid::new_v4();
+        assert!(matches!(
+            store.add_notebook_label(missing, &owner, "archived").await,
+            Err(StoreError::NotebookNotFound(id)) if id == missing
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_insert_into_locked_notebook_fails() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Locked").await;
+        store.set_notebook_locked(notebook.id, true).await.unwrap();
+
+        let entry = NewEntry::builder(notebook.id, notebook.owner_id.clone().try_into().unwrap())
+            .content_str("written while locked")
+            .build();
+        let result = store.insert_entry(&entry).await;
+        assert!(matches!(
+            result,
+            Err(StoreError::NotebookLocked(id)) if id == notebook.id
+        ));
+        assert_eq!(
+            store
+                .get_notebook(notebook.id)
+                .await
+                .unwrap()
+                .current_sequence,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_v4_and_v7_entry_ids_store_alike() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Id strategies").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        for strategy in [
+            notebook_core::IdStrategy::UuidV4,
+            notebook_core::IdStrategy::UuidV7,
+        ] {
+            let id = strategy.generate();
+            let entry = NewEntry::builder(notebook.id, author)
+                .id(id)
+                .content_str(strategy.as_str())
+                .build();
+            let inserted = store.insert_entry(&entry).await.unwrap();
+            assert_eq!(inserted.id, id);
+            assert_eq!(store.get_entry(id).await.unwrap().id, id);
         }
+    }
 
-        // Add revision edge if applicable
-        if let Some(revision_of) = entry.revision_of {
-            sqlx::query("SELECT add_revision_edge($1, $2)")
+    #[tokio::test]
+    async fn test_set_notebook_locked_round_trip() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Lockable").await;
+        assert!(!notebook.is_locked);
+
+        let locked = store.set_notebook_locked(notebook.id, true).await.unwrap();
+        assert!(locked.is_locked);
+        assert!(store.get_notebook(notebook.id).await.unwrap().is_locked);
+
+        let unlocked = store.set_notebook_locked(notebook.id, false).await.unwrap();
+        assert!(!unlocked.is_locked);
+        assert!(!store.get_notebook(notebook.id).await.unwrap().is_locked);
+    }
+
+    #[tokio::test]
+    async fn test_set_notebook_public_round_trip() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Visible").await;
+        assert!(!notebook.is_public);
+
+        let public = store.set_notebook_public(notebook.id, true).await.unwrap();
+        assert!(public.is_public);
+        assert!(store.get_notebook(notebook.id).await.unwrap().is_public);
+
+        let events = store.events_after(notebook.id, 0, 100).await.unwrap();
+        let last = events.last().unwrap();
+        assert_eq!(last.event_type, event_type::NOTEBOOK_VISIBILITY);
+        assert_eq!(last.payload["public"], true);
+
+        let private = store.set_notebook_public(notebook.id, false).await.unwrap();
+        assert!(!private.is_public);
+    }
+
+    #[tokio::test]
+    async fn test_set_notebook_orphan_policy_round_trip() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Curated").await;
+        assert_eq!(notebook.orphan_policy, orphan_policy::ALLOW);
+
+        let strict = store
+            .set_notebook_orphan_policy(notebook.id, orphan_policy::REJECT)
+            .await
+            .unwrap();
+        assert_eq!(strict.orphan_policy, orphan_policy::REJECT);
+        assert_eq!(
+            store.get_notebook(notebook.id).await.unwrap().orphan_policy,
+            orphan_policy::REJECT
+        );
+
+        let events = store.events_after(notebook.id, 0, 100).await.unwrap();
+        let last = events.last().unwrap();
+        assert_eq!(last.event_type, event_type::NOTEBOOK_ORPHAN_POLICY);
+        assert_eq!(last.payload["orphan_policy"], "reject");
+
+        // The column only accepts known policies
+        assert!(
+            store
+                .set_notebook_orphan_policy(notebook.id, "sometimes")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lock_missing_notebook() {
+        let store = setup_store().await;
+        let result = store.set_notebook_locked(Uuid::new_v4(), true).await;
+        assert!(matches!(result, Err(StoreError::NotebookNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_orphan_query_returns_only_flagged_entries() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Orphans").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let integrated = NewEntry::builder(notebook.id, author)
+            .content_str("well connected")
+            .build();
+        let orphan = NewEntry::builder(notebook.id, author)
+            .content_str("disconnected")
+            .topic(Some("stray".to_string()))
+            .integration_cost(IntegrationCostJson {
+                orphan: true,
+                ..Default::default()
+            })
+            .build();
+        store.insert_entry(&integrated).await.unwrap();
+        store.insert_entry(&orphan).await.unwrap();
+
+        let orphans =
+            crate::OrphanEntriesQuery::new(notebook_core::NotebookId::from_uuid(notebook.id))
+                .limit(10)
+                .execute(&store)
+                .await
+                .unwrap();
+
+        let ids: Vec<Uuid> = orphans.iter().map(|row| row.id).collect();
+        assert_eq!(ids, vec![orphan.id]);
+        assert_eq!(orphans[0].topic.as_deref(), Some("stray"));
+
+        let query =
+            crate::OrphanEntriesQuery::new(notebook_core::NotebookId::from_uuid(notebook.id))
+                .offset(1);
+        assert!(query.execute(&store).await.unwrap().is_empty());
+        assert_eq!(query.count(&store).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_latest_revision_follows_chain() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Revisions").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let original = NewEntry::builder(notebook.id, author)
+            .content_str("v1")
+            .build();
+        store.insert_entry(&original).await.unwrap();
+        assert_eq!(
+            store.latest_revision_of(original.id).await.unwrap(),
+            original.id
+        );
+
+        let second = NewEntry::builder(notebook.id, author)
+            .content_str("v2")
+            .revision_of(Some(original.id))
+            .build();
+        store.insert_entry(&second).await.unwrap();
+        let third = NewEntry::builder(notebook.id, author)
+            .content_str("v3")
+            .revision_of(Some(second.id))
+            .build();
+        store.insert_entry(&third).await.unwrap();
+
+        assert_eq!(
+            store.latest_revision_of(original.id).await.unwrap(),
+            third.id
+        );
+        assert_eq!(store.latest_revision_of(third.id).await.unwrap(), third.id);
+    }
+
+    #[tokio::test]
+    async fn test_get_entries_in_notebook_skips_missing_and_foreign() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Bulk read").await;
+        let other = create_test_notebook(&store, "Other").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+        let other_author: [u8; 32] = other.owner_id.clone().try_into().unwrap();
+
+        let first = NewEntry::builder(notebook.id, author)
+            .content_str("first")
+            .build();
+        let second = NewEntry::builder(notebook.id, author)
+            .content_str("second")
+            .build();
+        let foreign = NewEntry::builder(other.id, other_author)
+            .content_str("foreign")
+            .build();
+        for entry in [&first, &second, &foreign] {
+            store.insert_entry(entry).await.unwrap();
+        }
+
+        let repo = crate::Repository::new(store.clone());
+        let ids: Vec<notebook_core::EntryId> = [second.id, Uuid::new_v4(), foreign.id, first.id]
+            .into_iter()
+            .map(notebook_core::EntryId::from_uuid)
+            .collect();
+        let entries = repo
+            .get_entries_in_notebook(notebook_core::NotebookId::from_uuid(notebook.id), &ids)
+            .await
+            .unwrap();
+
+        let found: Vec<Uuid> = entries.iter().map(|e| *e.id.as_uuid()).collect();
+        assert_eq!(found, vec![second.id, first.id]);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_conditional_revisions_do_not_fork() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Conditional revise").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let original = NewEntry::builder(notebook.id, author)
+            .content_str("v1")
+            .build();
+        store.insert_entry(&original).await.unwrap();
+
+        // Both clients read the original and revise it concurrently
+        let revise = |content: &str| {
+            NewEntry::builder(notebook.id, author)
+                .content_str(content)
+                .revision_of(Some(original.id))
+                .if_latest_revision_of(original.id)
+                .build()
+        };
+        let (first, second) = (revise("v2 by one"), revise("v2 by other"));
+        let (a, b) = tokio::join!(store.insert_entry(&first), store.insert_entry(&second));
+
+        let (winner, loser) = match (a, b) {
+            (Ok(row), Err(err)) | (Err(err), Ok(row)) => (row, err),
+            other => panic!("expected exactly one revision to win: {:?}", other),
+        };
+        assert!(matches!(
+            loser,
+            StoreError::RevisionConflict { entry_id, latest }
+                if entry_id == original.id && latest == winner.id
+        ));
+        assert_eq!(store.revision_count(original.id).await.unwrap(), 1);
+
+        // Based on the winner, the next conditional revise succeeds
+        let next = NewEntry::builder(notebook.id, author)
+            .content_str("v3")
+            .revision_of(Some(winner.id))
+            .if_latest_revision_of(original.id)
+            .build();
+        store.insert_entry(&next).await.unwrap();
+        assert_eq!(
+            store.latest_revision_of(original.id).await.unwrap(),
+            next.id
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revision_count_matches_chain() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Revision count").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let original = NewEntry::builder(notebook.id, author)
+            .content_str("v1")
+            .build();
+        store.insert_entry(&original).await.unwrap();
+        assert_eq!(store.revision_count(original.id).await.unwrap(), 0);
+
+        let second = NewEntry::builder(notebook.id, author)
+            .content_str("v2")
+            .revision_of(Some(original.id))
+            .build();
+        store.insert_entry(&second).await.unwrap();
+        let third = NewEntry::builder(notebook.id, author)
+            .content_str("v3")
+            .revision_of(Some(second.id))
+            .build();
+        store.insert_entry(&third).await.unwrap();
+
+        let chain = store
+            .get_revisions(original.id, DEFAULT_MAX_DEPTH)
+            .await
+            .unwrap();
+        assert_eq!(store.revision_count(original.id).await.unwrap(), 2);
+        assert_eq!(chain.revisions.len(), 2);
+        assert!(!chain.truncated && !chain.cycle);
+    }
+
+    #[tokio::test]
+    async fn test_forked_revisions_are_all_in_chain() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Forked chain").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let original = NewEntry::builder(notebook.id, author)
+            .content_str("v1")
+            .build();
+        store.insert_entry(&original).await.unwrap();
+
+        // Two branches off the original, and a revision of the first branch
+        let revise = |parent: Uuid, content: &str| {
+            NewEntry::builder(notebook.id, author)
+                .content_str(content)
+                .revision_of(Some(parent))
+                .build()
+        };
+        let left = revise(original.id, "left");
+        let right = revise(original.id, "right");
+        let left_two = revise(left.id, "left again");
+        for entry in [&left, &right, &left_two] {
+            store.insert_entry(entry).await.unwrap();
+        }
+
+        let chain = store
+            .get_revisions(original.id, DEFAULT_MAX_DEPTH)
+            .await
+            .unwrap();
+        let links: Vec<(Uuid, Option<Uuid>)> = chain
+            .revisions
+            .iter()
+            .map(|r| (r.id, r.revision_of))
+            .collect();
+        assert_eq!(links.len(), 3);
+        assert!(links.contains(&(left.id, Some(original.id))));
+        assert!(links.contains(&(right.id, Some(original.id))));
+        assert_eq!(links[2], (left_two.id, Some(left.id)));
+        assert_eq!(store.revision_count(original.id).await.unwrap(), 3);
+
+        let summaries = store
+            .entry_summaries(&[left.id, right.id, left_two.id])
+            .await
+            .unwrap();
+        let parent_of = |id: Uuid| summaries.iter().find(|s| s.id == id).unwrap().revision_of;
+        assert_eq!(parent_of(left.id), Some(original.id));
+        assert_eq!(parent_of(right.id), Some(original.id));
+        assert_eq!(parent_of(left_two.id), Some(left.id));
+    }
+
+    #[tokio::test]
+    async fn test_revision_chain_past_depth_is_truncated() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Long chain").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let original = NewEntry::builder(notebook.id, author)
+            .content_str("v0")
+            .build();
+        store.insert_entry(&original).await.unwrap();
+        let mut previous = original.id;
+        for version in 1..=5 {
+            let revision = NewEntry::builder(notebook.id, author)
+                .content_str(&format!("v{}", version))
+                .revision_of(Some(previous))
+                .build();
+            store.insert_entry(&revision).await.unwrap();
+            previous = revision.id;
+        }
+
+        let chain = store.get_revisions(original.id, 3).await.unwrap();
+        assert_eq!(chain.revisions.len(), 3);
+        assert!(chain.truncated);
+        assert!(!chain.cycle);
+
+        // A chain exactly at the limit is complete
+        let chain = store.get_revisions(original.id, 5).await.unwrap();
+        assert_eq!(chain.revisions.len(), 5);
+        assert!(!chain.truncated);
+
+        let shallow = store.clone().with_max_revision_depth(3);
+        assert_eq!(shallow.revision_count(original.id).await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_cyclic_revision_chain_terminates() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Cyclic chain").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let first = NewEntry::builder(notebook.id, author)
+            .content_str("A")
+            .build();
+        store.insert_entry(&first).await.unwrap();
+        let second = NewEntry::builder(notebook.id, author)
+            .content_str("B")
+            .revision_of(Some(first.id))
+            .build();
+        store.insert_entry(&second).await.unwrap();
+
+        // Inserts cannot form a cycle; close one by hand (A revises B revises A)
+        sqlx::query("UPDATE entries SET revision_of = $2 WHERE id = $1")
+            .bind(first.id)
+            .bind(second.id)
+            .execute(store.pool())
+            .await
+            .unwrap();
+
+        let chain = store
+            .get_revisions(first.id, DEFAULT_MAX_DEPTH)
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = chain.revisions.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![second.id]);
+        assert!(chain.cycle);
+        assert!(!chain.truncated);
+        assert_eq!(store.revision_count(first.id).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_latest_revision_of_missing_entry() {
+        let store = setup_store().await;
+        let result = store.latest_revision_of(Uuid::new_v4()).await;
+        assert!(matches!(result, Err(StoreError::EntryNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_entry_summaries_match_entries() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Summaries").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let original = NewEntry::builder(notebook.id, author)
+            .content_str("v1")
+            .topic(Some("drafts".to_string()))
+            .integration_cost(IntegrationCostJson {
+                catalog_shift: 0.25,
+                ..Default::default()
+            })
+            .build();
+        let unrevised = NewEntry::builder(notebook.id, author)
+            .content_str("alone")
+            .build();
+        store.insert_entry(&original).await.unwrap();
+        store.insert_entry(&unrevised).await.unwrap();
+        let revision = NewEntry::builder(notebook.id, author)
+            .content_str("v2")
+            .revision_of(Some(original.id))
+            .build();
+        let revision = store.insert_entry(&revision).await.unwrap();
+
+        let summaries = store
+            .entry_summaries(&[original.id, unrevised.id, Uuid::new_v4()])
+            .await
+            .unwrap();
+        assert_eq!(summaries.len(), 2);
+
+        for summary in &summaries {
+            let row = store.get_entry(summary.id).await.unwrap();
+            let cost: IntegrationCostJson = serde_json::from_value(row.integration_cost).unwrap();
+            assert_eq!(summary.topic, row.topic);
+            assert_eq!(summary.author_id, row.author_id);
+            assert_eq!(summary.sequence, row.sequence);
+            assert_eq!(summary.catalog_shift, cost.catalog_shift);
+            assert_eq!(summary.revision_of, row.revision_of);
+            assert_eq!(
+                summary.revision_count,
+                store.revision_count(summary.id).await.unwrap()
+            );
+        }
+
+        let original_summary = summaries.iter().find(|s| s.id == original.id).unwrap();
+        assert_eq!(original_summary.catalog_shift, 0.25);
+        assert_eq!(original_summary.revision_count, 1);
+        assert_eq!(original_summary.latest_sequence, revision.sequence);
+
+        let unrevised_summary = summaries.iter().find(|s| s.id == unrevised.id).unwrap();
+        assert_eq!(unrevised_summary.revision_count, 0);
+        assert_eq!(
+            unrevised_summary.latest_sequence,
+            unrevised_summary.sequence
+        );
+    }
+
+    async fn stored_compression(store: &Store, id: Uuid) -> (Option<String>, usize) {
+        sqlx::query_as::<_, (Option<String>, i32)>(
+            "SELECT compression, length(content) FROM entries WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_one(store.pool())
+        .await
+        .map(|(compression, len)| (compression, len as usize))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_large_entry_is_compressed_and_round_trips() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Compression").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let content = "Compressible notebook prose. ".repeat(2000);
+        let entry = NewEntry::builder(notebook.id, author)
+            .content_str(&content)
+            .build();
+        let inserted = store.insert_entry(&entry).await.unwrap();
+        assert_eq!(inserted.content, content.as_bytes());
+
+        let (compression, stored_len) = stored_compression(&store, entry.id).await;
+        assert_eq!(compression.as_deref(), Some(crate::compression::ZSTD));
+        assert!(stored_len < content.len());
+
+        let fetched = store.get_entry(entry.id).await.unwrap();
+        assert_eq!(fetched.content, content.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_small_and_incompressible_entries_are_stored_raw() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Raw").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let small = NewEntry::builder(notebook.id, author)
+            .content_str("tiny")
+            .build();
+        store.insert_entry(&small).await.unwrap();
+        assert_eq!(stored_compression(&store, small.id).await.0, None);
+
+        let noise: Vec<u8> = (0..16 * 1024).map(|_| rand::random::<u8>()).collect();
+        let binary = NewEntry::builder(notebook.id, author)
+            .content(noise.clone())
+            .content_type("application/octet-stream".to_string())
+            .build();
+        store.insert_entry(&binary).await.unwrap();
+        assert_eq!(
+            stored_compression(&store, binary.id).await,
+            (None, noise.len())
+        );
+        assert_eq!(store.get_entry(binary.id).await.unwrap().content, noise);
+    }
+
+    async fn create_encrypted_notebook(store: &Store) -> (NotebookRow, [u8; 32]) {
+        let owner_id: [u8; 32] = rand::random();
+        store
+            .insert_author(&NewAuthor::new(owner_id, rand::random()))
+            .await
+            .unwrap();
+        let notebook = store
+            .insert_notebook(&NewNotebook::new("Private".to_string(), owner_id).encrypted(true))
+            .await
+            .unwrap();
+        (notebook, owner_id)
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_entry_is_ciphertext_at_rest() {
+        let master_key = MasterKey::from_bytes(rand::random());
+        let store = setup_store().await.with_master_key(master_key.clone());
+        let (notebook, author) = create_encrypted_notebook(&store).await;
+        assert!(notebook.encrypted);
+
+        let secret = "The launch code is hidden in the lighthouse.";
+        let small = NewEntry::builder(notebook.id, author)
+            .content_str(secret)
+            .build();
+        let inserted = store.insert_entry(&small).await.unwrap();
+        assert_eq!(inserted.content, secret.as_bytes());
+        assert!(inserted.sealed.is_none());
+
+        let large_text = "Compressible private prose. ".repeat(2000);
+        let large = NewEntry::builder(notebook.id, author)
+            .content_str(&large_text)
+            .build();
+        store.insert_entry(&large).await.unwrap();
+
+        // The database only holds ciphertext
+        let (stored, encryption, compression): (Vec<u8>, Option<String>, Option<String>) =
+            sqlx::query_as("SELECT content, encryption, compression FROM entries WHERE id = $1")
+                .bind(small.id)
+                .fetch_one(store.pool())
+                .await
+                .unwrap();
+        assert_eq!(encryption.as_deref(), Some(crate::encryption::AES_256_GCM));
+        assert_eq!(compression, None);
+        assert!(!stored.windows(secret.len()).any(|w| w == secret.as_bytes()));
+        assert!(!String::from_utf8_lossy(&stored).contains("lighthouse"));
+
+        let (large_compression,): (Option<String>,) =
+            sqlx::query_as("SELECT compression FROM entries WHERE id = $1")
+                .bind(large.id)
+                .fetch_one(store.pool())
+                .await
+                .unwrap();
+        assert_eq!(large_compression.as_deref(), Some(crate::compression::ZSTD));
+
+        // Reads see plaintext, including from a store with a cold key cache
+        let fresh = Store::from_pool(store.pool().clone()).with_master_key(master_key);
+        assert_eq!(
+            fresh.get_entry(small.id).await.unwrap().content,
+            secret.as_bytes()
+        );
+        let listed = fresh
+            .query_entries(&EntryQuery::new(notebook.id))
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].content, secret.as_bytes());
+        assert_eq!(listed[1].content, large_text.as_bytes());
+
+        // Without the master key the content cannot be read
+        let keyless = Store::from_pool(store.pool().clone());
+        assert!(matches!(
+            keyless.get_entry(small.id).await,
+            Err(StoreError::EncryptionError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_notebook_requires_master_key() {
+        let store = Store::from_pool(setup_store().await.pool().clone());
+        let owner_id: [u8; 32] = rand::random();
+        store
+            .insert_author(&NewAuthor::new(owner_id, rand::random()))
+            .await
+            .unwrap();
+
+        let result = store
+            .insert_notebook(&NewNotebook::new("Private".to_string(), owner_id).encrypted(true))
+            .await;
+        assert!(matches!(result, Err(StoreError::ConfigError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_unencrypted_notebook_stores_plaintext() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Open").await;
+        assert!(!notebook.encrypted);
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let entry = NewEntry::builder(notebook.id, author)
+            .content_str("public note")
+            .build();
+        store.insert_entry(&entry).await.unwrap();
+
+        let (stored, encryption): (Vec<u8>, Option<String>) =
+            sqlx::query_as("SELECT content, encryption FROM entries WHERE id = $1")
                 .bind(entry.id)
-                .bind(revision_of)
-                .execute(&self.pool)
+                .fetch_one(store.pool())
                 .await
-                .map_err(|e| {
-                    StoreError::GraphError(format!("Failed to add revision edge: {}", e))
-                })?;
+                .unwrap();
+        assert_eq!(stored, b"public note");
+        assert_eq!(encryption, None);
+    }
+
+    #[tokio::test]
+    async fn test_readable_notebooks_skips_write_only_grants() {
+        let store = setup_store().await;
+        let own = create_test_notebook(&store, "Own").await;
+        let shared = create_test_notebook(&store, "Shared").await;
+        let write_only = create_test_notebook(&store, "Write only").await;
+        let private = create_test_notebook(&store, "Private").await;
+        let reader: [u8; 32] = own.owner_id.clone().try_into().unwrap();
+
+        for (notebook, read, write) in [(&shared, true, false), (&write_only, false, true)] {
+            store
+                .grant_access(&NewNotebookAccess {
+                    notebook_id: notebook.id,
+                    author_id: reader,
+                    read,
+                    write,
+                })
+                .await
+                .unwrap();
         }
 
-        Ok(())
+        let ids: Vec<Uuid> = store
+            .readable_notebooks(&reader)
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.id)
+            .collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&own.id));
+        assert!(ids.contains(&shared.id));
+        assert!(!ids.contains(&write_only.id));
+        assert!(!ids.contains(&private.id));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[tokio::test]
+    async fn test_recent_activity_only_includes_readable_notebooks() {
+        let store = setup_store().await;
+        let own = create_test_notebook(&store, "Own").await;
+        let shared = create_test_notebook(&store, "Shared").await;
+        let write_only = create_test_notebook(&store, "Write only").await;
+        let private = create_test_notebook(&store, "Private").await;
+        let reader: [u8; 32] = own.owner_id.clone().try_into().unwrap();
 
-    #[test]
-    fn test_config_default() {
-        let config = StoreConfig::default();
-        assert_eq!(config.max_connections, 10);
-        assert_eq!(config.min_connections, 1);
-        assert!(config.run_migrations);
+        store
+            .grant_access(&NewNotebookAccess {
+                notebook_id: shared.id,
+                author_id: reader,
+                read: true,
+                write: false,
+            })
+            .await
+            .unwrap();
+        store
+            .grant_access(&NewNotebookAccess {
+                notebook_id: write_only.id,
+                author_id: reader,
+                read: false,
+                write: true,
+            })
+            .await
+            .unwrap();
+
+        let mut visible = Vec::new();
+        for notebook in [&own, &shared, &write_only, &private] {
+            let owner: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+            let entry = NewEntry::builder(notebook.id, owner)
+                .content_str(&notebook.name)
+                .build();
+            store.insert_entry(&entry).await.unwrap();
+            if notebook.id == own.id || notebook.id == shared.id {
+                visible.push(entry.id);
+            }
+        }
+
+        let feed = store.recent_activity(&reader, 100, None).await.unwrap();
+        let ids: Vec<Uuid> = feed.iter().map(|row| row.entry.id).collect();
+        assert_eq!(ids.len(), 2);
+        assert!(visible.iter().all(|id| ids.contains(id)));
+
+        // Newest first, tagged with the notebook name
+        assert_eq!(ids[0], visible[1]);
+        assert_eq!(feed[0].notebook_name, "Shared");
+        assert_eq!(feed[1].notebook_name, "Own");
+    }
+
+    #[tokio::test]
+    async fn test_recent_activity_respects_since_and_limit() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Feed").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let first = NewEntry::builder(notebook.id, author)
+            .content_str("first")
+            .build();
+        let first = store.insert_entry(&first).await.unwrap();
+        let second = NewEntry::builder(notebook.id, author)
+            .content_str("second")
+            .build();
+        let second = store.insert_entry(&second).await.unwrap();
+
+        let feed = store
+            .recent_activity(&author, 100, Some(first.created))
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = feed.iter().map(|row| row.entry.id).collect();
+        assert_eq!(ids, vec![second.id]);
+
+        let feed = store.recent_activity(&author, 1, None).await.unwrap();
+        assert_eq!(feed.len(), 1);
+        assert_eq!(feed[0].entry.id, second.id);
+    }
+
+    #[tokio::test]
+    async fn test_rename_missing_notebook() {
+        let store = setup_store().await;
+        let result = store.rename_notebook(Uuid::new_v4(), "Nope").await;
+        assert!(matches!(result, Err(StoreError::NotebookNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_notebook_name_names_existing_notebook() {
+        let store = setup_store().await;
+        let existing = create_test_notebook(&store, "Research").await;
+        let owner_id: [u8; 32] = existing.owner_id.clone().try_into().unwrap();
+
+        let result = store
+            .insert_notebook(&NewNotebook::new("Research".to_string(), owner_id))
+            .await;
+        assert!(matches!(
+            result,
+            Err(StoreError::DuplicateNotebookName { existing_id, .. }) if existing_id == existing.id
+        ));
+
+        // Renames are held to the same rule; keeping one's own name is fine
+        let other = store
+            .insert_notebook(&NewNotebook::new("Scratch".to_string(), owner_id))
+            .await
+            .unwrap();
+        let result = store.rename_notebook(other.id, "Research").await;
+        assert!(matches!(
+            result,
+            Err(StoreError::DuplicateNotebookName { existing_id, .. }) if existing_id == existing.id
+        ));
+        assert_eq!(store.get_notebook(other.id).await.unwrap().name, "Scratch");
+        store
+            .rename_notebook(existing.id, "Research")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_different_owners_may_share_notebook_names() {
+        let store = setup_store().await;
+        let first = create_test_notebook(&store, "Shared Name").await;
+        let second = create_test_notebook(&store, "Shared Name").await;
+        assert_ne!(first.owner_id, second.owner_id);
+        assert_eq!(second.name, "Shared Name");
+    }
+
+    async fn logged_types(store: &Store, notebook_id: Uuid, after_seq: i64) -> Vec<String> {
+        store
+            .events_after(notebook_id, after_seq, 100)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| row.event_type)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_each_mutation_appends_one_event() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Logged").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        // Creating the notebook (and its owner grant) is not a change
+        assert_eq!(store.latest_event_seq(notebook.id).await.unwrap(), 0);
+
+        let entry = NewEntry::builder(notebook.id, author)
+            .content_str("v1")
+            .topic(Some("log".to_string()))
+            .build();
+        store.insert_entry(&entry).await.unwrap();
+        assert_eq!(
+            logged_types(&store, notebook.id, 0).await,
+            vec![event_type::ENTRY]
+        );
+
+        let revision = NewEntry::builder(notebook.id, author)
+            .content_str("v2")
+            .revision_of(Some(entry.id))
+            .build();
+        store.insert_entry(&revision).await.unwrap();
+        assert_eq!(
+            logged_types(&store, notebook.id, 1).await,
+            vec![event_type::ENTRY]
+        );
+
+        store.rename_notebook(notebook.id, "Renamed").await.unwrap();
+        assert_eq!(
+            logged_types(&store, notebook.id, 2).await,
+            vec![event_type::NOTEBOOK_RENAMED]
+        );
+
+        store.set_notebook_locked(notebook.id, true).await.unwrap();
+        assert_eq!(
+            logged_types(&store, notebook.id, 3).await,
+            vec![event_type::NOTEBOOK_LOCKED]
+        );
+
+        let reader: [u8; 32] = rand::random();
+        store
+            .insert_author(&NewAuthor::new(reader, rand::random()))
+            .await
+            .unwrap();
+        store
+            .grant_access(&NewNotebookAccess {
+                notebook_id: notebook.id,
+                author_id: reader,
+                read: true,
+                write: false,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            logged_types(&store, notebook.id, 4).await,
+            vec![event_type::ACCESS_GRANTED]
+        );
+
+        assert!(store.revoke_access(notebook.id, &reader).await.unwrap());
+        assert_eq!(
+            logged_types(&store, notebook.id, 5).await,
+            vec![event_type::ACCESS_REVOKED]
+        );
+
+        // Revoking again changes nothing, so nothing is logged
+        assert!(!store.revoke_access(notebook.id, &reader).await.unwrap());
+        assert_eq!(store.latest_event_seq(notebook.id).await.unwrap(), 6);
+
+        let events = store.events_after(notebook.id, 0, 100).await.unwrap();
+        assert_eq!(events[0].payload["entry_id"], serde_json::json!(entry.id));
+        assert_eq!(events[0].payload["operation"], "write");
+        assert_eq!(events[0].payload["topic"], "log");
+        assert_eq!(events[1].payload["operation"], "revise");
+        assert_eq!(events[2].payload["name"], "Renamed");
+        assert_eq!(events[3].payload["locked"], true);
+        assert_eq!(events[4].payload["write"], false);
+    }
+
+    #[tokio::test]
+    async fn test_event_log_is_gapless_under_concurrent_writes() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Concurrent log").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let mut handles = Vec::new();
+        for i in 0..20 {
+            let store = store.clone();
+            let entry = NewEntry::builder(notebook.id, author)
+                .content_str(&format!("entry {}", i))
+                .build();
+            handles.push(tokio::spawn(async move {
+                store.insert_entry(&entry).await.map(|_| ())
+            }));
+        }
+        for i in 0..5 {
+            let store = store.clone();
+            let id = notebook.id;
+            handles.push(tokio::spawn(async move {
+                store
+                    .rename_notebook(id, &format!("name {}", i))
+                    .await
+                    .map(|_| ())
+            }));
+        }
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+
+        let seqs: Vec<i64> = store
+            .events_after(notebook.id, 0, 100)
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.seq)
+            .collect();
+        assert_eq!(seqs, (1..=25).collect::<Vec<_>>());
+
+        // Replaying from the middle resumes exactly where it left off
+        let tail = store.events_after(notebook.id, 20, 100).await.unwrap();
+        assert_eq!(tail.first().map(|row| row.seq), Some(21));
+        assert_eq!(tail.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_failed_insert_logs_nothing() {
+        let store = setup_store().await;
+        let notebook = create_test_notebook(&store, "Rolled back").await;
+        let author: [u8; 32] = notebook.owner_id.clone().try_into().unwrap();
+
+        let entry = NewEntry::builder(notebook.id, author)
+            .content_str("once")
+            .build();
+        store.insert_entry(&entry).await.unwrap();
+        // Same id again violates the primary key; its event must roll back too
+        assert!(store.insert_entry(&entry).await.is_err());
+
+        assert_eq!(store.latest_event_seq(notebook.id).await.unwrap(), 1);
     }
 }